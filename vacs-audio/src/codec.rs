@@ -0,0 +1,84 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// Codec an [`EncodedAudioFrame`] is encoded with, telling the receiving side how to decode it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameCodec {
+    #[default]
+    Opus,
+    /// Uncompressed 16-bit big-endian (L16, RFC 3551) mono PCM at
+    /// [`TARGET_SAMPLE_RATE`](crate::TARGET_SAMPLE_RATE). Avoids encoding delay and artifacts at
+    /// roughly 24 times the bandwidth of Opus, so it's only suitable for low latency local
+    /// networks.
+    Pcm,
+}
+
+/// A single encoded frame of mono audio, tagged with the codec it is encoded with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedAudioFrame {
+    pub codec: FrameCodec,
+    pub data: Bytes,
+}
+
+impl EncodedAudioFrame {
+    pub fn new(codec: FrameCodec, data: impl Into<Bytes>) -> Self {
+        Self {
+            codec,
+            data: data.into(),
+        }
+    }
+}
+
+/// Encodes `samples` as 16-bit big-endian PCM, clamping them to `-1.0..=1.0`.
+pub(crate) fn encode_pcm(samples: &[f32]) -> Bytes {
+    samples
+        .iter()
+        .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_be_bytes())
+        .collect::<Vec<u8>>()
+        .into()
+}
+
+/// Decodes 16-bit big-endian PCM from `data` into `out`, returning the number of decoded
+/// samples. Samples not fitting into `out` and a trailing odd byte are ignored.
+pub(crate) fn decode_pcm(data: &[u8], out: &mut [f32]) -> usize {
+    let mut decoded = 0;
+    for (out, sample) in out.iter_mut().zip(data.chunks_exact(2)) {
+        *out = i16::from_be_bytes([sample[0], sample[1]]) as f32 / i16::MAX as f32;
+        decoded += 1;
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FRAME_SIZE;
+
+    #[test]
+    fn pcm_round_trip() {
+        let samples = (0..FRAME_SIZE)
+            .map(|i| (i as f32 / FRAME_SIZE as f32) * 2.0 - 1.0)
+            .collect::<Vec<_>>();
+
+        let encoded = encode_pcm(&samples);
+        assert_eq!(encoded.len(), FRAME_SIZE * 2);
+
+        let mut decoded = vec![0.0f32; FRAME_SIZE];
+        assert_eq!(decode_pcm(&encoded, &mut decoded), FRAME_SIZE);
+        for (decoded, sample) in decoded.iter().zip(&samples) {
+            assert!((decoded - sample).abs() < 1.0 / i16::MAX as f32 * 2.0);
+        }
+    }
+
+    #[test]
+    fn pcm_clamps_and_truncates() {
+        let encoded = encode_pcm(&[2.0, -2.0]);
+        let mut decoded = [0.0f32; 1];
+        assert_eq!(decode_pcm(&encoded, &mut decoded), 1);
+        assert_eq!(decoded, [1.0]);
+
+        let mut decoded = [0.0f32; 4];
+        assert_eq!(decode_pcm(&encoded[..3], &mut decoded), 1);
+    }
+}
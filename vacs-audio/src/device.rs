@@ -27,11 +27,98 @@ impl Display for DeviceType {
     }
 }
 
+/// Zero-based device channels a stream captures from or plays back to, e.g. `[2]` for an
+/// interface with the microphone connected to its third input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChannelMap(Vec<u16>);
+
+impl ChannelMap {
+    pub fn new(channels: Vec<u16>) -> Self {
+        Self(channels)
+    }
+
+    #[inline]
+    pub fn channels(&self) -> &[u16] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Fails if the map references a channel a device with `channels` channels does not provide.
+    pub fn validate(&self, channels: u16) -> Result<(), AudioError> {
+        match self.0.iter().find(|&&c| c >= channels) {
+            Some(&channel) => Err(AudioError::InvalidChannelMap { channel, channels }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<Vec<u16>> for ChannelMap {
+    fn from(channels: Vec<u16>) -> Self {
+        Self::new(channels)
+    }
+}
+
+/// Readiness of a configured device, resolved without starting a stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum DeviceReadiness {
+    /// The preferred device (or the default one, if none is preferred) can be opened.
+    #[serde(rename_all = "camelCase")]
+    Ready { device_name: String },
+    /// The preferred device is unavailable, streams would fall back to the named device.
+    #[serde(rename_all = "camelCase")]
+    Fallback { device_name: String },
+    /// No usable device was found or the configuration doesn't fit the resolved device.
+    Unavailable { reason: String },
+}
+
+impl DeviceReadiness {
+    /// Resolves the readiness from the outcome of opening a device, i.e. the name and channel
+    /// count of the resolved device and whether it is a fallback, and the configured channel map.
+    pub fn resolve(
+        opened: Result<(String, u16, bool), AudioError>,
+        channel_map: Option<&ChannelMap>,
+    ) -> Self {
+        let (device_name, channels, is_fallback) = match opened {
+            Ok(opened) => opened,
+            Err(err) => {
+                return Self::Unavailable {
+                    reason: err.to_string(),
+                };
+            }
+        };
+
+        if let Some(map) = channel_map
+            && let Err(err) = map.validate(channels)
+        {
+            return Self::Unavailable {
+                reason: err.to_string(),
+            };
+        }
+
+        if is_fallback {
+            Self::Fallback { device_name }
+        } else {
+            Self::Ready { device_name }
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready { .. })
+    }
+}
+
 pub struct StreamDevice {
     pub(crate) device_type: DeviceType,
     pub(crate) device: cpal::Device,
     pub(crate) config: cpal::StreamConfig,
     pub(crate) sample_format: SampleFormat,
+    pub(crate) channel_map: Option<ChannelMap>,
 }
 
 impl StreamDevice {
@@ -55,6 +142,23 @@ impl StreamDevice {
         self.config.channels
     }
 
+    #[inline]
+    pub fn channel_map(&self) -> Option<&ChannelMap> {
+        self.channel_map.as_ref()
+    }
+
+    /// Restricts the stream to the given device channels. An empty map uses all channels.
+    ///
+    /// Fails if the map references a channel the device does not provide.
+    pub fn with_channel_map(mut self, channel_map: Option<ChannelMap>) -> Result<Self, AudioError> {
+        let channel_map = channel_map.filter(|map| !map.is_empty());
+        if let Some(map) = &channel_map {
+            map.validate(self.channels())?;
+        }
+        self.channel_map = channel_map;
+        Ok(self)
+    }
+
     #[instrument(level = "trace", skip(data_callback, error_callback), err)]
     pub(crate) fn build_input_stream<D, E>(
         &self,
@@ -225,11 +329,12 @@ impl Debug for StreamDevice {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "StreamDevice {{ device_type: {}, device: {}, config: {:?}, sample_format: {:?} }}",
+            "StreamDevice {{ device_type: {}, device: {}, config: {:?}, sample_format: {:?}, channel_map: {:?} }}",
             self.device_type,
             self.device.name().unwrap_or_default(),
             self.config,
-            self.sample_format
+            self.sample_format,
+            self.channel_map
         )
     }
 }
@@ -253,11 +358,26 @@ impl DeviceSelector {
                 device,
                 config: stream_config.config(),
                 sample_format: stream_config.sample_format(),
+                channel_map: None,
             },
             is_fallback,
         ))
     }
 
+    /// Checks whether a device of `device_type` can be opened with the given preferences and
+    /// channel map, without building a stream.
+    #[instrument(level = "debug")]
+    pub fn check_readiness(
+        device_type: DeviceType,
+        preferred_host: Option<&str>,
+        preferred_device_name: Option<&str>,
+        channel_map: Option<&ChannelMap>,
+    ) -> DeviceReadiness {
+        let opened = Self::open(device_type, preferred_host, preferred_device_name)
+            .map(|(device, is_fallback)| (device.name(), device.channels(), is_fallback));
+        DeviceReadiness::resolve(opened, channel_map)
+    }
+
     #[instrument(level = "debug")]
     pub fn all_host_names() -> Vec<String> {
         cpal::available_hosts()
@@ -573,3 +693,68 @@ impl<'a> Debug for HostDebug<'a> {
         f.debug_tuple("Host").field(&self.0.id().name()).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opened(
+        name: &str,
+        channels: u16,
+        is_fallback: bool,
+    ) -> Result<(String, u16, bool), AudioError> {
+        Ok((name.to_string(), channels, is_fallback))
+    }
+
+    #[test]
+    fn readiness_of_opened_devices() {
+        assert_eq!(
+            DeviceReadiness::resolve(opened("Headset", 2, false), None),
+            DeviceReadiness::Ready {
+                device_name: "Headset".to_string()
+            }
+        );
+        assert_eq!(
+            DeviceReadiness::resolve(opened("Speakers", 2, true), Some(&ChannelMap::new(vec![1]))),
+            DeviceReadiness::Fallback {
+                device_name: "Speakers".to_string()
+            }
+        );
+        assert!(
+            DeviceReadiness::resolve(
+                opened("Interface", 4, false),
+                Some(&ChannelMap::new(vec![]))
+            )
+            .is_ready()
+        );
+    }
+
+    #[test]
+    fn readiness_of_unusable_devices() {
+        let readiness = DeviceReadiness::resolve(
+            Err(AudioError::Other(anyhow::anyhow!("No output device found"))),
+            None,
+        );
+        assert_eq!(
+            readiness,
+            DeviceReadiness::Unavailable {
+                reason: "No output device found".to_string()
+            }
+        );
+        assert!(!readiness.is_ready());
+
+        assert_eq!(
+            DeviceReadiness::resolve(
+                opened("Interface", 2, false),
+                Some(&ChannelMap::new(vec![0, 2]))
+            ),
+            DeviceReadiness::Unavailable {
+                reason: AudioError::InvalidChannelMap {
+                    channel: 2,
+                    channels: 2
+                }
+                .to_string()
+            }
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::TARGET_SAMPLE_RATE;
 use biquad::{Biquad, Coefficients, DirectForm2Transposed, Q_BUTTERWORTH_F32, ToHertz, Type};
+use std::time::Duration;
 
 pub fn downmix_interleaved_to_mono(interleaved: &[f32], channels: usize, mono: &mut Vec<f32>) {
     debug_assert!(channels > 0);
@@ -13,6 +14,39 @@ pub fn downmix_interleaved_to_mono(interleaved: &[f32], channels: usize, mono: &
     }
 }
 
+/// Downmixes only the `selected` channels of the interleaved samples to mono.
+pub fn downmix_selected_to_mono(
+    interleaved: &[f32],
+    channels: usize,
+    selected: &[u16],
+    mono: &mut Vec<f32>,
+) {
+    debug_assert!(channels > 0);
+    debug_assert!(!selected.is_empty());
+    debug_assert!(selected.iter().all(|&c| (c as usize) < channels));
+
+    let frames = interleaved.len() / channels;
+    mono.clear();
+    mono.reserve(frames);
+    for frame in interleaved.chunks_exact(channels) {
+        let sum = selected.iter().map(|&c| frame[c as usize]).sum::<f32>();
+        mono.push(sum / selected.len() as f32);
+    }
+}
+
+/// Silences all channels of the interleaved samples that are not `selected`.
+pub fn silence_unselected_channels(interleaved: &mut [f32], channels: usize, selected: &[u16]) {
+    debug_assert!(channels > 0);
+
+    for frame in interleaved.chunks_exact_mut(channels) {
+        for (channel, sample) in frame.iter_mut().enumerate() {
+            if !selected.contains(&(channel as u16)) {
+                *sample = 0.0f32;
+            }
+        }
+    }
+}
+
 #[inline]
 fn downmix_frame_to_mono(frame: &[f32]) -> f32 {
     match frame.len() {
@@ -57,10 +91,68 @@ const GATE_ATTACK_S: f32 = 0.008f32; // 8 ms
 /// Range: 0.050..=0.200 (50–200 ms).
 const GATE_RELEASE_S: f32 = 0.090f32; // 90 ms
 
+/// Noise gate gain (linear) below which a closed gate is considered fully silent.
+/// Reached roughly 0.6 s after closing with the default release time, acting as a hangover.
+const GATE_SILENT_GAIN: f32 = 0.001f32; // -60 dB
+
+/// Input pre-amp gain limits in dB.
+/// Range: -30..=30 dB. Larger boosts mostly amplify the noise floor of very quiet microphones.
+const PRE_AMP_MIN_DB: f32 = -30.0f32;
+const PRE_AMP_MAX_DB: f32 = 30.0f32;
+
 /// Soft limiter ceiling in dBFS. Set just below 0 dBFS to avoid clipping.
 /// Range: -6.0..=-0.1. More negative = gentler, more headroom.
 const LIMITER_THR_DBFS: f32 = -1.0f32;
 
+/// Output limiter release time (seconds). Longer = less pumping after a peak, but quieter
+/// audio following it.
+/// Range: 0.020..=0.200 (20–200 ms).
+const OUTPUT_LIMITER_RELEASE_S: f32 = 0.050f32; // 50 ms
+
+/// Maximum output limiter lookahead, bounding the latency added to playback.
+pub(crate) const MAX_OUTPUT_LIMITER_LOOKAHEAD: Duration = Duration::from_millis(20);
+
+/// Fixed input gain compensating for quiet microphones, separate from any automatic gain control.
+/// Samples are clamped to full scale on overload.
+pub(crate) struct PreAmp {
+    gain: f32, // linear
+}
+
+impl Default for PreAmp {
+    fn default() -> Self {
+        Self { gain: 1.0f32 }
+    }
+}
+
+impl PreAmp {
+    /// Creates a pre-amp applying `gain_db`, limited to -30..=30 dB.
+    pub fn new(gain_db: f32) -> Self {
+        let gain_db = if gain_db.is_finite() {
+            gain_db.clamp(PRE_AMP_MIN_DB, PRE_AMP_MAX_DB)
+        } else {
+            0.0f32
+        };
+        Self {
+            gain: 10.0f32.powf(gain_db / 20.0f32),
+        }
+    }
+
+    #[inline]
+    pub fn process(&self, x: f32) -> f32 {
+        (x * self.gain).clamp(-1.0f32, 1.0f32)
+    }
+
+    #[inline]
+    pub fn process_frame(&self, frame: &mut [f32]) {
+        if self.gain == 1.0f32 {
+            return;
+        }
+        for s in frame.iter_mut() {
+            *s = self.process(*s);
+        }
+    }
+}
+
 /// One-pole DC blocker (very low-cut high-pass).
 /// Removes DC bias and sub-Hz drift without coloring audible band.
 struct DcBlock {
@@ -126,6 +218,11 @@ impl NoiseGate {
         1.0 - (-1.0 / denom).exp()
     }
 
+    #[inline]
+    fn is_silent(&self) -> bool {
+        self.target == 0.0f32 && self.gain < GATE_SILENT_GAIN
+    }
+
     /// Process one full 10 ms frame (RMS measured over the frame).
     pub fn process_frame(&mut self, frame: &mut [f32]) {
         // quick RMS
@@ -181,9 +278,100 @@ impl SoftLimiter {
     }
 }
 
+/// Peak limiter for interleaved playback audio, keeping mixed sources below the limiter ceiling.
+///
+/// Audio is delayed by the lookahead, so gain reduction can be ramped in before a peak reaches the
+/// output instead of reacting once it is already clipping. Without lookahead, gain is reduced
+/// instantly on the peak itself. All state is allocated upfront, so it is safe to use in a device
+/// data callback.
+pub(crate) struct OutputLimiter {
+    thr: f32, // linear amplitude
+    channels: usize,
+    /// Delay line of `lookahead` interleaved frames, `pos` pointing at the oldest sample.
+    delay: Vec<f32>,
+    pos: usize,
+    lookahead: usize, // frames
+    rel: f32,         // one-pole release coefficient
+    gain: f32,
+    target: f32,
+    step: f32,
+    hold: usize, // frames
+}
+
+impl OutputLimiter {
+    /// Creates a limiter for `channels` interleaved channels at `sample_rate`, delaying audio by
+    /// `lookahead` (limited to [`MAX_OUTPUT_LIMITER_LOOKAHEAD`]).
+    pub fn new(lookahead: Duration, channels: usize, sample_rate: u32) -> Self {
+        debug_assert!(channels > 0);
+
+        let lookahead = lookahead.min(MAX_OUTPUT_LIMITER_LOOKAHEAD);
+        let frames = (lookahead.as_secs_f32() * sample_rate as f32).round() as usize;
+        let denom = (OUTPUT_LIMITER_RELEASE_S * sample_rate as f32).max(1e-6);
+        Self {
+            thr: 10.0f32.powf(LIMITER_THR_DBFS / 20.0f32),
+            channels,
+            delay: vec![0.0f32; frames * channels],
+            pos: 0,
+            lookahead: frames,
+            rel: 1.0 - (-1.0 / denom).exp(),
+            gain: 1.0f32,
+            target: 1.0f32,
+            step: 0.0f32,
+            hold: 0,
+        }
+    }
+
+    /// Limits `interleaved` in place, replacing it with the audio delayed by the lookahead.
+    pub fn process_interleaved(&mut self, interleaved: &mut [f32]) {
+        debug_assert_eq!(interleaved.len() % self.channels, 0);
+
+        for frame in interleaved.chunks_exact_mut(self.channels) {
+            let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            if peak > self.thr {
+                // The peak leaves the delay line after `lookahead` more frames, so the gain has to
+                // reach the required reduction by then and hold it until the peak passed.
+                let required = self.thr / peak;
+                if required < self.target {
+                    self.target = required;
+                    let ramp = (self.gain - required) / (self.lookahead + 1) as f32;
+                    self.step = self.step.max(ramp);
+                }
+                self.hold = self.lookahead;
+            } else if self.hold > 0 {
+                self.hold -= 1;
+            } else {
+                self.target = 1.0f32;
+                self.step = 0.0f32;
+            }
+
+            if self.gain > self.target {
+                self.gain = (self.gain - self.step).max(self.target);
+            } else {
+                self.gain += self.rel * (self.target - self.gain);
+            }
+
+            for s in frame.iter_mut() {
+                let delayed = if self.delay.is_empty() {
+                    *s
+                } else {
+                    let delayed = std::mem::replace(&mut self.delay[self.pos], *s);
+                    self.pos = (self.pos + 1) % self.delay.len();
+                    delayed
+                };
+                // Ramps only approximate the required gain, so never exceed full scale.
+                *s = (delayed * self.gain).clamp(-1.0f32, 1.0f32);
+            }
+        }
+    }
+}
+
 /// Capture-side chain for 48 kHz mono, 20 ms frames.
 /// Apply on each full frame **before** Opus encoding.
+///
+/// The pre-amp runs first, so the noise gate thresholds are relative to the boosted signal: a
+/// higher pre-amp gain also opens the gate for quieter input.
 pub struct MicProcessor {
+    pre_amp: PreAmp,
     dc_block: DcBlock,
     hpf: DirectForm2Transposed<f32>,
     noise_gate: NoiseGate,
@@ -200,6 +388,7 @@ impl Default for MicProcessor {
         )
         .expect("Failed to create HPF coefficients");
         Self {
+            pre_amp: PreAmp::default(),
             dc_block: DcBlock::default(),
             hpf: DirectForm2Transposed::new(coeffs),
             noise_gate: NoiseGate::default(),
@@ -209,10 +398,17 @@ impl Default for MicProcessor {
 }
 
 impl MicProcessor {
+    pub fn with_pre_amp(mut self, pre_amp: PreAmp) -> Self {
+        self.pre_amp = pre_amp;
+        self
+    }
+
     /// Process one 20 ms (960-sample) frame at [`TARGET_SAMPLE_RATE`].
     /// Assumes frame is **mono f32** at the target rate.
     pub fn process_frame(&mut self, frame: &mut [f32]) {
-        // Per-sample IIR (stateful) stages first.
+        // Fixed gain before any processing.
+        self.pre_amp.process_frame(frame);
+        // Per-sample IIR (stateful) stages next.
         for s in frame.iter_mut() {
             *s = self.dc_block.process(*s);
             *s = self.hpf.run(*s);
@@ -221,4 +417,118 @@ impl MicProcessor {
         self.noise_gate.process_frame(frame);
         self.soft_limiter.process_frame(frame);
     }
+
+    /// Whether the noise gate has fully closed after the last processed frame, i.e. the frame
+    /// carried no speech.
+    pub fn is_silent(&self) -> bool {
+        self.noise_gate.is_silent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FRAME_SIZE;
+
+    #[test]
+    fn downmix_selected_channels() {
+        let interleaved = [0.25, 0.2, 0.75, 0.0, 0.5, 0.4, 0.5, 0.0];
+        let mut mono = Vec::new();
+
+        downmix_selected_to_mono(&interleaved, 4, &[2], &mut mono);
+        assert_eq!(mono, vec![0.75, 0.5]);
+
+        downmix_selected_to_mono(&interleaved, 4, &[0, 2], &mut mono);
+        assert_eq!(mono, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn silence_unselected() {
+        let mut interleaved = [0.5, 0.5, 0.5, 0.5, 0.25, 0.25, 0.25, 0.25];
+
+        silence_unselected_channels(&mut interleaved, 4, &[1, 3]);
+        assert_eq!(interleaved, [0.0, 0.5, 0.0, 0.5, 0.0, 0.25, 0.0, 0.25]);
+    }
+
+    #[test]
+    fn pre_amp_scales_and_clamps() {
+        let mut frame = [0.1f32, -0.2, 0.6, -0.9];
+        PreAmp::new(0.0).process_frame(&mut frame);
+        assert_eq!(frame, [0.1, -0.2, 0.6, -0.9]);
+
+        // +6 dB roughly doubles the amplitude, clamping samples exceeding full scale
+        PreAmp::new(6.0).process_frame(&mut frame);
+        for (sample, expected) in frame.iter().zip([0.1995f32, -0.399, 1.0, -1.0]) {
+            assert!((sample - expected).abs() < 1e-3, "{sample} != {expected}");
+        }
+
+        let mut frame = [0.5f32];
+        PreAmp::new(-6.0).process_frame(&mut frame);
+        assert!((frame[0] - 0.2506).abs() < 1e-3);
+
+        // Gains are limited to +30 dB
+        let mut frame = [0.001f32];
+        PreAmp::new(100.0).process_frame(&mut frame);
+        assert!((frame[0] - 0.0316).abs() < 1e-3);
+    }
+
+    #[test]
+    fn output_limiter_engages_before_peak() {
+        const LOOKAHEAD_FRAMES: usize = 48;
+        let mut limiter = OutputLimiter::new(Duration::from_millis(1), 1, TARGET_SAMPLE_RATE);
+        let thr = 10.0f32.powf(LIMITER_THR_DBFS / 20.0f32);
+
+        let mut samples = vec![0.5f32; 12_000];
+        samples[100] = 1.0;
+        limiter.process_interleaved(&mut samples);
+
+        // Audio is delayed by the lookahead
+        assert!(samples[..LOOKAHEAD_FRAMES].iter().all(|&s| s == 0.0));
+        assert_eq!(samples[LOOKAHEAD_FRAMES], 0.5);
+
+        // Gain reduction already ramps in while the samples preceding the peak are played back
+        let peak = 100 + LOOKAHEAD_FRAMES;
+        assert!(samples[peak - LOOKAHEAD_FRAMES / 2] < 0.5);
+        assert!(samples[peak - 1] < samples[peak - LOOKAHEAD_FRAMES / 2]);
+        assert!(samples[peak] <= thr + 1e-6, "{} > {thr}", samples[peak]);
+
+        // And is released after the peak passed
+        assert!(samples[peak + 1] < 0.5);
+        assert!((samples[11_999] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn output_limiter_without_lookahead() {
+        let mut limiter = OutputLimiter::new(Duration::ZERO, 2, TARGET_SAMPLE_RATE);
+        let thr = 10.0f32.powf(LIMITER_THR_DBFS / 20.0f32);
+
+        let mut samples = [0.5f32, -0.25, 1.5, -0.5];
+        limiter.process_interleaved(&mut samples);
+        assert_eq!(samples[..2], [0.5, -0.25]);
+        assert!((samples[2] - thr).abs() < 1e-6);
+        assert!((samples[3] + 0.5 * thr / 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mic_processor_silent_after_gate_release() {
+        let mut processor = MicProcessor::default();
+        let mut frame = [0.0f32; FRAME_SIZE];
+        processor.process_frame(&mut frame);
+        assert!(processor.is_silent());
+
+        let mut frame: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| 0.5 * (i as f32 * 0.1).sin())
+            .collect();
+        processor.process_frame(&mut frame);
+        assert!(!processor.is_silent());
+
+        // The gate releases smoothly, so a single quiet frame does not count as silence yet.
+        let mut frame = [0.0f32; FRAME_SIZE];
+        processor.process_frame(&mut frame);
+        assert!(!processor.is_silent());
+        for _ in 0..50 {
+            processor.process_frame(&mut frame);
+        }
+        assert!(processor.is_silent());
+    }
 }
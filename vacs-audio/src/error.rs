@@ -9,6 +9,10 @@ pub enum AudioError {
     UnsupportedConfig,
     #[error("Audio device is busy or access was denied")]
     DeviceBusyOrDenied,
+    #[error(
+        "Audio channel {channel} is not available on the selected device ({channels} channels), check your channel map"
+    )]
+    InvalidChannelMap { channel: u16, channels: u16 },
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -0,0 +1,134 @@
+use crate::{FRAME_SIZE, TARGET_SAMPLE_RATE};
+use ringbuf::HeapCons;
+use ringbuf::traits::{Consumer, Observer};
+use std::time::Duration;
+
+/// Default playout delay applied to network-fed sources before starting playback.
+pub const DEFAULT_JITTER_BUFFER_DEPTH: Duration = Duration::from_millis(40);
+/// Maximum playout delay that can be configured for a single source.
+pub const MAX_JITTER_BUFFER_DEPTH: Duration = Duration::from_millis(400);
+/// Number of samples the ring buffer can hold on top of the maximum depth, absorbing bursts of
+/// frames arriving at once. We keep 10 frames of headroom, which equals 200 ms at 48_000 Hz.
+const JITTER_BUFFER_HEADROOM: usize = FRAME_SIZE * 10;
+
+/// Playout buffer smoothing out jitter in the arrival of decoded audio samples.
+///
+/// After creation and after every underrun, no samples are returned until at least the configured
+/// target depth has been accumulated, trading a bit of latency for continuous playback.
+/// Depths are converted to samples at [`TARGET_SAMPLE_RATE`] and are thus approximate if the
+/// samples have been resampled to a different output rate.
+pub struct JitterBuffer {
+    cons: HeapCons<f32>,
+    target_depth: Duration,
+    target_samples: usize,
+    buffering: bool,
+}
+
+impl JitterBuffer {
+    pub fn new(cons: HeapCons<f32>, target_depth: Duration) -> Self {
+        let mut buffer = Self {
+            cons,
+            target_depth: Duration::ZERO,
+            target_samples: 0,
+            buffering: true,
+        };
+        buffer.set_target_depth(target_depth);
+        buffer
+    }
+
+    /// Ring buffer capacity required to hold the maximum configurable depth including headroom.
+    pub fn capacity() -> usize {
+        depth_to_samples(MAX_JITTER_BUFFER_DEPTH) + JITTER_BUFFER_HEADROOM
+    }
+
+    /// Updates the target depth, clamped to [`MAX_JITTER_BUFFER_DEPTH`]. Takes effect the next time
+    /// the buffer is (re-)filled after an underrun.
+    pub fn set_target_depth(&mut self, depth: Duration) {
+        self.target_depth = depth.min(MAX_JITTER_BUFFER_DEPTH);
+        self.target_samples = depth_to_samples(self.target_depth);
+    }
+
+    pub fn target_depth(&self) -> Duration {
+        self.target_depth
+    }
+
+    pub fn is_buffering(&self) -> bool {
+        self.buffering
+    }
+
+    /// Returns an iterator over the samples available for playback, yielding nothing while the
+    /// buffer is still filling up to its target depth.
+    pub fn pop_iter(&mut self) -> impl Iterator<Item = f32> + '_ {
+        let available = self.cons.occupied_len();
+        if self.buffering {
+            self.buffering = available < self.target_samples;
+        } else if available == 0 {
+            self.buffering = self.target_samples > 0;
+        }
+
+        let limit = if self.buffering { 0 } else { available };
+        self.cons.pop_iter().take(limit)
+    }
+}
+
+fn depth_to_samples(depth: Duration) -> usize {
+    (depth.as_secs_f64() * TARGET_SAMPLE_RATE as f64).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ringbuf::traits::{Producer, Split};
+    use ringbuf::{HeapProd, HeapRb};
+
+    fn buffer(depth: Duration) -> (HeapProd<f32>, JitterBuffer) {
+        let (prod, cons) = HeapRb::new(JitterBuffer::capacity()).split();
+        (prod, JitterBuffer::new(cons, depth))
+    }
+
+    fn push_frames(prod: &mut HeapProd<f32>, frames: usize) {
+        prod.push_slice(&vec![0.5f32; FRAME_SIZE * frames]);
+    }
+
+    #[test]
+    fn independent_depths_per_source() {
+        let (mut shallow_prod, mut shallow) = buffer(Duration::from_millis(20));
+        let (mut deep_prod, mut deep) = buffer(Duration::from_millis(100));
+
+        // One frame (20 ms) is enough for the shallow source only.
+        push_frames(&mut shallow_prod, 1);
+        push_frames(&mut deep_prod, 1);
+        assert_eq!(shallow.pop_iter().count(), FRAME_SIZE);
+        assert_eq!(deep.pop_iter().count(), 0);
+        assert!(deep.is_buffering());
+
+        // The deep source starts playback once 100 ms have been accumulated.
+        push_frames(&mut deep_prod, 4);
+        assert_eq!(deep.pop_iter().count(), FRAME_SIZE * 5);
+        assert!(!deep.is_buffering());
+    }
+
+    #[test]
+    fn rebuffers_after_underrun() {
+        let (mut prod, mut buffer) = buffer(Duration::from_millis(40));
+
+        push_frames(&mut prod, 2);
+        assert_eq!(buffer.pop_iter().count(), FRAME_SIZE * 2);
+
+        // Buffer ran dry, a single frame is not enough to resume playback.
+        assert_eq!(buffer.pop_iter().count(), 0);
+        push_frames(&mut prod, 1);
+        assert_eq!(buffer.pop_iter().count(), 0);
+        push_frames(&mut prod, 1);
+        assert_eq!(buffer.pop_iter().count(), FRAME_SIZE * 2);
+    }
+
+    #[test]
+    fn target_depth_is_clamped() {
+        let (_prod, mut buffer) = buffer(Duration::from_secs(5));
+        assert_eq!(buffer.target_depth(), MAX_JITTER_BUFFER_DEPTH);
+
+        buffer.set_target_depth(Duration::from_millis(60));
+        assert_eq!(buffer.target_depth(), Duration::from_millis(60));
+    }
+}
@@ -1,6 +1,8 @@
+mod codec;
 pub mod device;
 mod dsp;
 pub mod error;
+pub mod jitter;
 pub(crate) mod mixer;
 pub mod sources;
 pub mod stream;
@@ -10,7 +12,7 @@ pub use cpal;
 #[cfg(target_os = "macos")]
 pub use cpal_macos as cpal;
 
-pub type EncodedAudioFrame = bytes::Bytes;
+pub use codec::{EncodedAudioFrame, FrameCodec};
 
 pub const TARGET_SAMPLE_RATE: u32 = 48_000;
 pub const FRAME_DURATION_MS: u64 = 20;
@@ -1,6 +1,7 @@
 use crate::cpal;
 use crate::sources::{AudioSource, AudioSourceId};
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Default)]
 pub struct Mixer {
@@ -9,6 +10,17 @@ pub struct Mixer {
 
 impl Mixer {
     pub fn mix(&mut self, output: &mut [f32]) {
+        self.mix_unclamped(output);
+
+        // Clamp mixed samples to [-1.0, 1.0] to avoid clipping.
+        for sample in output {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+
+    /// Mixes all sources like [`Self::mix`], but leaves samples exceeding full scale for a
+    /// subsequent limiter to handle.
+    pub fn mix_unclamped(&mut self, output: &mut [f32]) {
         // Initialize the output buffer by writing EQUILIBRIUM to all of its samples. AudioSources will
         // add their own samples on top of this.
         output.fill(cpal::Sample::EQUILIBRIUM);
@@ -17,11 +29,6 @@ impl Mixer {
         for src in self.sources.values_mut() {
             src.mix_into(output);
         }
-
-        // Clamp mixed samples to [-1.0, 1.0] to avoid clipping.
-        for sample in output {
-            *sample = sample.clamp(-1.0, 1.0);
-        }
     }
 
     pub fn add_source(&mut self, source_id: AudioSourceId, source: Box<dyn AudioSource>) {
@@ -55,4 +62,10 @@ impl Mixer {
             source.set_volume(volume);
         }
     }
+
+    pub fn set_source_jitter_buffer_depth(&mut self, source_id: AudioSourceId, depth: Duration) {
+        if let Some(source) = self.sources.get_mut(&source_id) {
+            source.set_jitter_buffer_depth(depth);
+        }
+    }
 }
@@ -1,6 +1,8 @@
 pub mod opus;
 pub mod waveform;
 
+use std::time::Duration;
+
 pub type AudioSourceId = usize;
 
 /// Represents an audio source that can be mixed into an output stream.
@@ -49,4 +51,9 @@ pub trait AudioSource: Send {
     /// not destructively to their sample data. The volume should not be applied to the rest of the
     /// data already present in the output buffer.
     fn set_volume(&mut self, volume: f32);
+    /// Adjust the target depth of the source's jitter buffer.
+    ///
+    /// Only network-fed sources (like Opus) buffer their input, so the default implementation of
+    /// this function does nothing.
+    fn set_jitter_buffer_depth(&mut self, _depth: Duration) {}
 }
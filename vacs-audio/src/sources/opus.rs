@@ -1,37 +1,57 @@
+use crate::codec::decode_pcm;
+use crate::jitter::JitterBuffer;
 use crate::sources::AudioSource;
-use crate::{EncodedAudioFrame, FRAME_SIZE, TARGET_SAMPLE_RATE};
+use crate::{EncodedAudioFrame, FRAME_SIZE, FrameCodec, TARGET_SAMPLE_RATE};
 use anyhow::{Context, Result};
 use audioadapter_buffers::direct::SequentialSliceOfVecs;
 use ringbuf::traits::{Consumer, Producer, Split};
 use ringbuf::{HeapCons, HeapProd, HeapRb};
 use rubato::{Async, Indexing, Resampler};
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, mpsc};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, instrument};
 
 const RESAMPLER_BUFFER_SIZE: usize = 8192;
 
+/// Receiver of encoded frames shared between consecutive [`OpusSource`]s, allowing a new source
+/// (e.g. for a different output device) to take over decoding without dropping the frame channel.
+pub type SharedFrameReceiver = Arc<Mutex<mpsc::Receiver<EncodedAudioFrame>>>;
+
 pub struct OpusSource {
-    cons: HeapCons<f32>,
+    buffer: JitterBuffer,
     decoder_task: JoinHandle<()>,
+    cancel: CancellationToken,
     output_channels: u16, // >= 1
     volume: f32,          // 0.0 - 1.0
     amp: f32,             // >= 0.1
 }
 
 impl OpusSource {
+    /// Creates a new source decoding frames from `rx`.
+    ///
+    /// Frames are decoded according to their [`FrameCodec`], raw PCM frames bypass the Opus
+    /// decoder.
+    ///
+    /// Only a single source decodes from a shared receiver at a time. A new source created on the
+    /// same receiver starts decoding once the previous one has been dropped.
     #[instrument(level = "debug", skip(rx, resampler), err)]
     pub fn new(
-        mut rx: mpsc::Receiver<EncodedAudioFrame>,
+        rx: SharedFrameReceiver,
         mut resampler: Option<Async<f32>>,
         output_channels: u16,
         volume: f32,
         amp: f32,
+        jitter_buffer_depth: Duration,
     ) -> Result<Self> {
         tracing::trace!("Creating Opus source");
 
-        // We buffer 10 frames, which equals a total buffer of 200 ms at 48_000 Hz and 20 ms intervals
-        let (mut prod, cons): (HeapProd<f32>, HeapCons<f32>) = HeapRb::new(FRAME_SIZE * 10).split();
+        // The ring buffer is sized to hold the maximum jitter buffer depth plus some headroom, playback
+        // is delayed until the configured depth has been accumulated.
+        let (mut prod, cons): (HeapProd<f32>, HeapCons<f32>) =
+            HeapRb::new(JitterBuffer::capacity()).split();
 
         // Our captured input audio will always be in mono and is transmitted via a webrtc mono stream,
         // so we can safely default to a mono Opus decoder here. Interleaving to stereo output devices
@@ -39,8 +59,15 @@ impl OpusSource {
         let mut decoder = opus::Decoder::new(TARGET_SAMPLE_RATE, opus::Channels::Mono)
             .context("Failed to create Opus decoder")?;
 
+        let cancel = CancellationToken::new();
+        let cancel_clone = cancel.clone();
+
         let decoder_task = tokio::runtime::Handle::current().spawn(
             async move {
+                let mut rx = tokio::select! {
+                    _ = cancel_clone.cancelled() => return,
+                    rx = rx.lock_owned() => rx,
+                };
                 tracing::debug!("Starting Opus decoder task");
 
                 let mut decoded = vec![0.0f32; FRAME_SIZE];
@@ -64,8 +91,21 @@ impl OpusSource {
 
                 let mut overflows = 0usize;
 
-                while let Some(frame) = rx.recv().await {
-                    match decoder.decode_float(&frame, &mut decoded, false) {
+                loop {
+                    let frame = tokio::select! {
+                        _ = cancel_clone.cancelled() => break,
+                        frame = rx.recv() => match frame {
+                            Some(frame) => frame,
+                            None => break,
+                        },
+                    };
+
+                    let result = match frame.codec {
+                        FrameCodec::Opus => decoder.decode_float(&frame.data, &mut decoded, false),
+                        FrameCodec::Pcm => Ok(decode_pcm(&frame.data, &mut decoded)),
+                    };
+
+                    match result {
                         Ok(n) => {
                             let samples = if let Some(resampler) = &mut resampler {
                                 let need = resampler.input_frames_next();
@@ -140,8 +180,9 @@ impl OpusSource {
         );
 
         Ok(Self {
-            cons,
+            buffer: JitterBuffer::new(cons, jitter_buffer_depth),
             decoder_task,
+            cancel,
             output_channels: output_channels.max(1),
             volume: volume.clamp(0.0, 1.0),
             amp: amp.max(0.1),
@@ -155,11 +196,18 @@ impl OpusSource {
     }
 }
 
+impl Drop for OpusSource {
+    fn drop(&mut self) {
+        // Release the shared receiver, so a replacement source can continue decoding.
+        self.cancel.cancel();
+    }
+}
+
 impl AudioSource for OpusSource {
     fn mix_into(&mut self, output: &mut [f32]) {
         // Only a single output channel --> no interleaving required, just copy samples
         if self.output_channels == 1 {
-            for (out_s, s) in output.iter_mut().zip(self.cons.pop_iter()) {
+            for (out_s, s) in output.iter_mut().zip(self.buffer.pop_iter()) {
                 *out_s += s * self.amp * self.volume;
             }
 
@@ -172,7 +220,7 @@ impl AudioSource for OpusSource {
         // Limit by frames so we don’t overrun the output
         for (frame, s) in output
             .chunks_mut(self.output_channels as usize)
-            .zip(self.cons.pop_iter())
+            .zip(self.buffer.pop_iter())
         {
             for x in frame {
                 *x += s * self.amp * self.volume;
@@ -191,4 +239,8 @@ impl AudioSource for OpusSource {
     fn set_volume(&mut self, volume: f32) {
         self.volume = volume.clamp(0.0, 1.0);
     }
+
+    fn set_jitter_buffer_depth(&mut self, depth: Duration) {
+        self.buffer.set_target_depth(depth);
+    }
 }
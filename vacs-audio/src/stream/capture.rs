@@ -1,9 +1,10 @@
+use crate::codec::encode_pcm;
 use crate::cpal;
 use crate::cpal::traits::StreamTrait;
 use crate::device::{DeviceType, StreamDevice};
-use crate::dsp::{MicProcessor, downmix_interleaved_to_mono};
+use crate::dsp::{MicProcessor, PreAmp, downmix_interleaved_to_mono, downmix_selected_to_mono};
 use crate::error::AudioError;
-use crate::{EncodedAudioFrame, FRAME_SIZE, TARGET_SAMPLE_RATE};
+use crate::{EncodedAudioFrame, FRAME_SIZE, FrameCodec, TARGET_SAMPLE_RATE};
 use anyhow::Context;
 use audioadapter_buffers::direct::SequentialSliceOfVecs;
 use bytes::Bytes;
@@ -32,28 +33,41 @@ const INPUT_VOLUME_OPS_PER_DATA_CALLBACK: usize = 16;
 type InputVolumeOp = Box<dyn Fn(&mut f32) + Send>;
 
 pub struct CaptureStream {
-    _stream: cpal::Stream,
+    stream: Option<cpal::Stream>,
+    tx: Option<mpsc::Sender<EncodedAudioFrame>>,
+    codec: FrameCodec,
     volume_ops: parking_lot::Mutex<ringbuf::HeapProd<InputVolumeOp>>,
     muted: Arc<AtomicBool>,
+    suppress_silence: Arc<AtomicBool>,
     cancel: Option<CancellationToken>,
     task: Option<JoinHandle<()>>,
     is_level_meter: bool,
 }
 
 impl CaptureStream {
+    /// Starts capturing from `device`, sending frames encoded with `codec` to `tx`.
+    ///
+    /// With `suppress_silence` set, no frames are sent at all while the noise gate is closed,
+    /// instead of continuously sending encoded silence. This minimizes bandwidth, but the remote
+    /// jitter buffer underruns during every pause and has to re-buffer to its target depth on the
+    /// next speech burst, delaying speech onset by that depth and possibly rougher first syllables.
     #[instrument(level = "debug", skip(tx, error_tx), err)]
     pub fn start(
         device: StreamDevice,
         tx: mpsc::Sender<EncodedAudioFrame>,
+        codec: FrameCodec,
         mut volume: f32,
         amp: f32,
+        pre_amp_db: f32,
         error_tx: mpsc::Sender<AudioError>,
         muted: bool,
+        suppress_silence: bool,
     ) -> Result<Self, AudioError> {
         debug_assert!(matches!(device.device_type, DeviceType::Input));
 
         let muted = Arc::new(AtomicBool::new(muted));
         let muted_clone = muted.clone();
+        let suppress_silence = Arc::new(AtomicBool::new(suppress_silence));
 
         // buffer for ~100ms of input data
         let (mut input_prod, mut input_cons) =
@@ -61,11 +75,20 @@ impl CaptureStream {
                 .split();
 
         let mut mono_buf: Vec<f32> = Vec::with_capacity(MIN_INPUT_BUFFER_SIZE);
+        let channel_map = device.channel_map.clone();
 
         let stream = device.build_input_stream(
             move |input: &[f32], _| {
                 // downmix to mono if necessary
-                let mono: &[f32] = if device.config.channels > 1 {
+                let mono: &[f32] = if let Some(channel_map) = &channel_map {
+                    downmix_selected_to_mono(
+                        input,
+                        device.config.channels as usize,
+                        channel_map.channels(),
+                        &mut mono_buf,
+                    );
+                    &mono_buf
+                } else if device.config.channels > 1 {
                     downmix_interleaved_to_mono(
                         input,
                         device.config.channels as usize,
@@ -116,7 +139,12 @@ impl CaptureStream {
 
         let mut resampler = device.resampler()?;
 
-        let mut opus_framer = OpusFramer::new(tx)?;
+        let mut framer = InputFramer::new(
+            tx.clone(),
+            codec,
+            PreAmp::new(pre_amp_db),
+            suppress_silence.clone(),
+        )?;
 
         let task = tokio::runtime::Handle::current().spawn_blocking(move || {
             tracing::trace!("Input capture stream task started");
@@ -202,21 +230,21 @@ impl CaptureStream {
 
                     resampler_in_buf[0].clear();
 
-                    opus_framer.push_slice(&resampler_out_buf[0][..frames_out], gain);
+                    framer.push_slice(&resampler_out_buf[0][..frames_out], gain);
                 } else {
                     let mut stash: [f32; 1024] = [0.0; 1024];
                     let mut n = 0usize;
 
                     while let Some(sample) = input_cons.try_pop() {
                         if n == stash.len() {
-                            opus_framer.push_slice(&stash[..n], gain);
+                            framer.push_slice(&stash[..n], gain);
                             n = 0;
                         }
                         stash[n] = sample;
                         n += 1;
                     }
                     if n > 0 {
-                        opus_framer.push_slice(&stash[..n], gain);
+                        framer.push_slice(&stash[..n], gain);
                     } else {
                         std::thread::sleep(RESAMPLER_BUFFER_WAIT);
                     }
@@ -228,9 +256,12 @@ impl CaptureStream {
 
         tracing::info!("Input capture stream started");
         Ok(Self {
-            _stream: stream,
+            stream: Some(stream),
+            tx: Some(tx),
+            codec,
             volume_ops: Mutex::new(ops_prod),
             muted,
+            suppress_silence,
             cancel: Some(cancel),
             task: Some(task),
             is_level_meter: false,
@@ -243,9 +274,13 @@ impl CaptureStream {
         emit: Box<dyn Fn(InputLevel) + Send>,
         mut volume: f32,
         amp: f32,
+        pre_amp_db: f32,
         error_tx: mpsc::Sender<AudioError>,
     ) -> Result<Self, AudioError> {
+        let pre_amp = PreAmp::new(pre_amp_db);
         let mut level_meter = InputLevelMeter::new(device.sample_rate() as f32);
+        let mut mono_buf: Vec<f32> = Vec::with_capacity(MIN_INPUT_BUFFER_SIZE);
+        let channel_map = device.channel_map.clone();
 
         let (ops_prod, mut ops_cons) =
             HeapRb::<InputVolumeOp>::new(INPUT_VOLUME_OPS_CAPACITY).split();
@@ -260,9 +295,21 @@ impl CaptureStream {
                     }
                 }
 
+                let input: &[f32] = if let Some(channel_map) = &channel_map {
+                    downmix_selected_to_mono(
+                        input,
+                        device.config.channels as usize,
+                        channel_map.channels(),
+                        &mut mono_buf,
+                    );
+                    &mono_buf
+                } else {
+                    input
+                };
+
                 let gain = amp * volume;
                 for &sample in input {
-                    if let Some(level) = level_meter.push_sample(sample * gain) {
+                    if let Some(level) = level_meter.push_sample(pre_amp.process(sample * gain)) {
                         emit(level);
                     }
                 }
@@ -279,9 +326,12 @@ impl CaptureStream {
 
         tracing::debug!("Input level meter capture stream started");
         Ok(Self {
-            _stream: stream,
+            stream: Some(stream),
+            tx: None,
+            codec: FrameCodec::default(),
             volume_ops: Mutex::new(ops_prod),
             muted: Arc::new(AtomicBool::new(false)),
+            suppress_silence: Arc::new(AtomicBool::new(false)),
             cancel: None,
             task: None,
             is_level_meter: true,
@@ -294,7 +344,7 @@ impl CaptureStream {
         if let Some(cancel) = self.cancel.take() {
             cancel.cancel();
         }
-        drop(self._stream);
+        drop(self.stream.take());
         if let Some(task) = self.task.take()
             && let Err(err) = task.await
         {
@@ -302,6 +352,49 @@ impl CaptureStream {
         }
     }
 
+    /// Switches capture to `device`, continuing to send encoded frames on the same channel.
+    ///
+    /// The new stream is started before the current one is torn down, so a failure to open the
+    /// new device leaves the current stream capturing. The codec, mute and silence suppression
+    /// states are retained. Level meter streams cannot be switched and must be restarted instead.
+    #[instrument(level = "debug", skip(self, error_tx), err)]
+    pub fn switch_device(
+        &mut self,
+        device: StreamDevice,
+        volume: f32,
+        amp: f32,
+        pre_amp_db: f32,
+        error_tx: mpsc::Sender<AudioError>,
+    ) -> Result<(), AudioError> {
+        let Some(tx) = self.tx.clone() else {
+            return Err(AudioError::Other(anyhow::anyhow!(
+                "Cannot switch device of input level meter"
+            )));
+        };
+
+        let started = Instant::now();
+        let stream = Self::start(
+            device,
+            tx,
+            self.codec,
+            volume,
+            amp,
+            pre_amp_db,
+            error_tx,
+            self.is_muted(),
+            self.is_suppressing_silence(),
+        )?;
+
+        if let Some(cancel) = self.cancel.take() {
+            cancel.cancel();
+        }
+        // The capture task of the replaced stream exits on its own once cancelled, no need to
+        // block on it here.
+        *self = stream;
+        tracing::info!(gap = ?started.elapsed(), "Switched input capture device");
+        Ok(())
+    }
+
     pub fn set_muted(&self, muted: bool) {
         self.muted.store(muted, Ordering::Relaxed);
     }
@@ -310,6 +403,15 @@ impl CaptureStream {
         self.muted.load(Ordering::Relaxed)
     }
 
+    pub fn set_suppress_silence(&self, suppress_silence: bool) {
+        self.suppress_silence
+            .store(suppress_silence, Ordering::Relaxed);
+    }
+
+    pub fn is_suppressing_silence(&self) -> bool {
+        self.suppress_silence.load(Ordering::Relaxed)
+    }
+
     pub fn set_volume(&self, volume: f32) {
         if self
             .volume_ops
@@ -324,19 +426,32 @@ impl CaptureStream {
     pub fn is_level_meter(&self) -> bool {
         self.is_level_meter
     }
+
+    pub fn codec(&self) -> FrameCodec {
+        self.codec
+    }
 }
 
-struct OpusFramer {
+/// Collects processed input samples into frames, encoding them with the configured codec.
+struct InputFramer {
     frame: [f32; FRAME_SIZE],
     pos: usize,
     processor: MicProcessor,
+    codec: FrameCodec,
     encoder: opus::Encoder,
     encoded: Vec<u8>,
     tx: mpsc::Sender<EncodedAudioFrame>,
+    suppress_silence: Arc<AtomicBool>,
+    suppressing: bool,
 }
 
-impl OpusFramer {
-    fn new(tx: mpsc::Sender<EncodedAudioFrame>) -> Result<Self, AudioError> {
+impl InputFramer {
+    fn new(
+        tx: mpsc::Sender<EncodedAudioFrame>,
+        codec: FrameCodec,
+        pre_amp: PreAmp,
+        suppress_silence: Arc<AtomicBool>,
+    ) -> Result<Self, AudioError> {
         let mut encoder = opus::Encoder::new(
             TARGET_SAMPLE_RATE,
             opus::Channels::Mono,
@@ -354,10 +469,13 @@ impl OpusFramer {
         Ok(Self {
             frame: [0.0f32; FRAME_SIZE],
             pos: 0usize,
-            processor: MicProcessor::default(),
+            processor: MicProcessor::default().with_pre_amp(pre_amp),
+            codec,
             encoder,
             encoded: vec![0u8; MAX_OPUS_FRAME_SIZE],
             tx,
+            suppress_silence,
+            suppressing: false,
         })
     }
 
@@ -375,23 +493,46 @@ impl OpusFramer {
 
             if self.pos == FRAME_SIZE {
                 self.processor.process_frame(&mut self.frame);
-
-                match self.encoder.encode_float(&self.frame, &mut self.encoded) {
-                    Ok(len) => {
-                        let bytes = Bytes::copy_from_slice(&self.encoded[..len]);
-                        if let Err(err) = self.tx.try_send(bytes) {
-                            tracing::warn!(?err, "Failed to send encoded input audio frame");
-                        }
-                    }
-                    Err(err) => {
-                        tracing::warn!(?err, "Failed to encode input audio frame");
-                    }
+                if !self.suppress_frame() {
+                    self.encode_frame();
                 }
-
                 self.pos = 0;
             }
         }
     }
+
+    /// Whether the processed frame is silence that should not be sent. The encoder is reset when
+    /// entering silence, so the next speech burst starts from a clean state.
+    #[inline]
+    fn suppress_frame(&mut self) -> bool {
+        let suppress = self.suppress_silence.load(Ordering::Relaxed) && self.processor.is_silent();
+        if suppress && !self.suppressing {
+            tracing::trace!("Input silent, suppressing frames until speech resumes");
+            if let Err(err) = self.encoder.reset_state() {
+                tracing::warn!(?err, "Failed to reset opus encoder");
+            }
+        }
+        self.suppressing = suppress;
+        suppress
+    }
+
+    #[inline]
+    fn encode_frame(&mut self) {
+        let data = match self.codec {
+            FrameCodec::Opus => match self.encoder.encode_float(&self.frame, &mut self.encoded) {
+                Ok(len) => Bytes::copy_from_slice(&self.encoded[..len]),
+                Err(err) => {
+                    tracing::warn!(?err, "Failed to encode input audio frame");
+                    return;
+                }
+            },
+            FrameCodec::Pcm => encode_pcm(&self.frame),
+        };
+
+        if let Err(err) = self.tx.try_send(EncodedAudioFrame::new(self.codec, data)) {
+            tracing::warn!(?err, "Failed to send encoded input audio frame");
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize)]
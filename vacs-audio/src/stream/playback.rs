@@ -1,6 +1,7 @@
 use crate::cpal;
 use crate::cpal::traits::StreamTrait;
 use crate::device::{DeviceType, StreamDevice};
+use crate::dsp::{OutputLimiter, silence_unselected_channels};
 use crate::error::AudioError;
 use crate::mixer::Mixer;
 use crate::sources::{AudioSource, AudioSourceId};
@@ -12,6 +13,7 @@ use ringbuf::traits::Split;
 use rubato::Async;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, atomic};
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::instrument;
 
@@ -29,9 +31,12 @@ pub struct PlaybackStream {
 }
 
 impl PlaybackStream {
+    /// Starts playback on `device`. If `limiter_lookahead` is set, mixed audio is passed through an
+    /// output limiter delaying it by the lookahead (at most 20 ms) instead of being clamped.
     #[instrument(level = "debug", skip(error_tx), err)]
     pub fn start(
         device: StreamDevice,
+        limiter_lookahead: Option<Duration>,
         error_tx: mpsc::Sender<AudioError>,
     ) -> Result<Self, AudioError> {
         debug_assert!(matches!(device.device_type, DeviceType::Output));
@@ -42,6 +47,11 @@ impl PlaybackStream {
         let deafened = Arc::new(AtomicBool::new(false));
         let deafened_clone = deafened.clone();
 
+        let channels = device.channels() as usize;
+        let channel_map = device.channel_map.clone();
+        let mut limiter = limiter_lookahead
+            .map(|lookahead| OutputLimiter::new(lookahead, channels, device.sample_rate()));
+
         let stream = device.build_output_stream(
             move |output, _| {
                 for _ in 0..MIXER_OPS_PER_DATA_CALLBACK {
@@ -51,7 +61,16 @@ impl PlaybackStream {
                         break;
                     }
                 }
-                mixer.mix(output);
+                match &mut limiter {
+                    Some(limiter) => {
+                        mixer.mix_unclamped(output);
+                        limiter.process_interleaved(output);
+                    }
+                    None => mixer.mix(output),
+                }
+                if let Some(channel_map) = &channel_map {
+                    silence_unselected_channels(output, channels, channel_map.channels());
+                }
             },
             move |err| {
                 tracing::error!(?err, "CPAL playback stream error");
@@ -174,6 +193,20 @@ impl PlaybackStream {
         }
     }
 
+    #[instrument(level = "trace", skip(self))]
+    pub fn set_jitter_buffer_depth(&self, id: AudioSourceId, depth: Duration) {
+        if self
+            .mixer_ops
+            .lock()
+            .try_push(Box::new(move |mixer: &mut Mixer| {
+                mixer.set_source_jitter_buffer_depth(id, depth);
+            }))
+            .is_err()
+        {
+            tracing::warn!("Failed to set jitter buffer depth for audio source");
+        }
+    }
+
     pub fn resampler(&self) -> Result<Option<Async<f32>>, AudioError> {
         self.device.resampler()
     }
@@ -182,6 +215,10 @@ impl PlaybackStream {
         self.device.channels()
     }
 
+    pub fn sample_rate(&self) -> u32 {
+        self.device.sample_rate()
+    }
+
     pub fn device_name(&self) -> String {
         self.device.name()
     }
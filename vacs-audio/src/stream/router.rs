@@ -0,0 +1,277 @@
+use crate::error::AudioError;
+use crate::sources::{AudioSource, AudioSourceId};
+use crate::stream::playback::PlaybackStream;
+use std::collections::{HashMap, HashSet};
+
+/// Kind of audio played back, deciding which output stream a source is routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputSourceKind {
+    /// Call audio and call progress tones (ringback, call start and end).
+    Call,
+    /// Notifications not tied to an active call (incoming call chimes, clicks).
+    Notification,
+}
+
+/// Output stream audio sources can be routed to by a [`PlaybackRouter`].
+pub trait PlaybackOutput {
+    fn add_audio_source(&self, source: Box<dyn AudioSource>) -> AudioSourceId;
+    fn remove_audio_source(&self, id: AudioSourceId);
+}
+
+impl PlaybackOutput for PlaybackStream {
+    fn add_audio_source(&self, source: Box<dyn AudioSource>) -> AudioSourceId {
+        PlaybackStream::add_audio_source(self, source)
+    }
+
+    fn remove_audio_source(&self, id: AudioSourceId) {
+        PlaybackStream::remove_audio_source(self, id)
+    }
+}
+
+/// Audio source added through a [`PlaybackRouter`], identifying the output it is played on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutedSource {
+    pub output: String,
+    pub id: AudioSourceId,
+}
+
+struct RoutedOutput<O> {
+    output: O,
+    sources: HashSet<AudioSourceId>,
+}
+
+/// Manages multiple named output streams, e.g. bound to different devices, and routes audio
+/// sources to them by their [`OutputSourceKind`].
+///
+/// Kinds without a route are played on the default output, which always exists. Removing an output
+/// removes all sources the router added to it before handing it back, so dropping it afterwards
+/// tears down its stream cleanly. Routes to a removed output fall back to the default one.
+pub struct PlaybackRouter<O: PlaybackOutput = PlaybackStream> {
+    default_output: String,
+    outputs: HashMap<String, RoutedOutput<O>>,
+    routes: HashMap<OutputSourceKind, String>,
+}
+
+impl<O: PlaybackOutput> PlaybackRouter<O> {
+    /// Creates a new router, playing all sources on the given default output.
+    pub fn new(name: impl Into<String>, output: O) -> Self {
+        let name = name.into();
+        let mut outputs = HashMap::new();
+        outputs.insert(
+            name.clone(),
+            RoutedOutput {
+                output,
+                sources: HashSet::new(),
+            },
+        );
+        Self {
+            default_output: name,
+            outputs,
+            routes: HashMap::new(),
+        }
+    }
+
+    /// Adds a named output, returning the output previously registered under that name with all of
+    /// its sources removed. Routes to that name are kept and apply to the new output.
+    pub fn add_output(&mut self, name: impl Into<String>, output: O) -> Option<O> {
+        self.outputs
+            .insert(
+                name.into(),
+                RoutedOutput {
+                    output,
+                    sources: HashSet::new(),
+                },
+            )
+            .map(Self::teardown)
+    }
+
+    /// Removes the named output and all of its sources, returning it for the caller to drop or
+    /// stop. The default output cannot be removed.
+    pub fn remove_output(&mut self, name: &str) -> Option<O> {
+        if name == self.default_output {
+            tracing::warn!(?name, "Tried to remove default output, ignoring");
+            return None;
+        }
+
+        self.routes.retain(|_, output| output != name);
+        self.outputs.remove(name).map(Self::teardown)
+    }
+
+    /// Routes all sources of `kind` added from now on to the named output.
+    pub fn set_route(&mut self, kind: OutputSourceKind, name: &str) -> Result<(), AudioError> {
+        if !self.outputs.contains_key(name) {
+            return Err(AudioError::Other(anyhow::anyhow!(
+                "Output {name} does not exist"
+            )));
+        }
+        self.routes.insert(kind, name.to_string());
+        Ok(())
+    }
+
+    /// Routes all sources of `kind` added from now on to the default output.
+    pub fn clear_route(&mut self, kind: OutputSourceKind) {
+        self.routes.remove(&kind);
+    }
+
+    /// Name of the output sources of `kind` are routed to.
+    pub fn route(&self, kind: OutputSourceKind) -> &str {
+        self.routes
+            .get(&kind)
+            .map(String::as_str)
+            .unwrap_or(&self.default_output)
+    }
+
+    pub fn output(&self, name: &str) -> Option<&O> {
+        self.outputs.get(name).map(|routed| &routed.output)
+    }
+
+    /// Output sources of `kind` are routed to.
+    pub fn routed_output(&self, kind: OutputSourceKind) -> &O {
+        &self.outputs[self.route(kind)].output
+    }
+
+    /// Output `source` is played on, `None` if it has been removed in the meantime.
+    pub fn source_output(&self, source: &RoutedSource) -> Option<&O> {
+        self.outputs
+            .get(&source.output)
+            .filter(|routed| routed.sources.contains(&source.id))
+            .map(|routed| &routed.output)
+    }
+
+    /// Adds `source` to the output sources of `kind` are currently routed to.
+    pub fn add_source(
+        &mut self,
+        kind: OutputSourceKind,
+        source: Box<dyn AudioSource>,
+    ) -> RoutedSource {
+        let name = self.route(kind).to_string();
+        let routed = self
+            .outputs
+            .get_mut(&name)
+            .expect("routes always point to existing outputs");
+        let id = routed.output.add_audio_source(source);
+        routed.sources.insert(id);
+        RoutedSource { output: name, id }
+    }
+
+    pub fn remove_source(&mut self, source: &RoutedSource) {
+        if let Some(routed) = self.outputs.get_mut(&source.output)
+            && routed.sources.remove(&source.id)
+        {
+            routed.output.remove_audio_source(source.id);
+        }
+    }
+
+    fn teardown(routed: RoutedOutput<O>) -> O {
+        for id in routed.sources {
+            routed.output.remove_audio_source(id);
+        }
+        routed.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mixer::Mixer;
+    use parking_lot::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Output mixing its sources on demand instead of in a device data callback.
+    #[derive(Default)]
+    struct MockOutput {
+        mixer: Mutex<Mixer>,
+        next_id: AtomicUsize,
+    }
+
+    impl MockOutput {
+        fn render(&self) -> f32 {
+            let mut output = [0.0f32; 4];
+            self.mixer.lock().mix(&mut output);
+            output[0]
+        }
+    }
+
+    impl PlaybackOutput for MockOutput {
+        fn add_audio_source(&self, source: Box<dyn AudioSource>) -> AudioSourceId {
+            let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+            self.mixer.lock().add_source(id, source);
+            id
+        }
+
+        fn remove_audio_source(&self, id: AudioSourceId) {
+            self.mixer.lock().remove_source(id);
+        }
+    }
+
+    /// Source adding a constant level to every sample.
+    struct ConstantSource(f32);
+
+    impl AudioSource for ConstantSource {
+        fn mix_into(&mut self, output: &mut [f32]) {
+            output.iter_mut().for_each(|sample| *sample += self.0);
+        }
+
+        fn start(&mut self) {}
+
+        fn stop(&mut self) {}
+
+        fn set_volume(&mut self, _volume: f32) {}
+    }
+
+    #[test]
+    fn routes_sources_to_independent_outputs() {
+        let mut router = PlaybackRouter::new("headset", MockOutput::default());
+        router.add_output("speakers", MockOutput::default());
+        router
+            .set_route(OutputSourceKind::Notification, "speakers")
+            .unwrap();
+
+        let call = router.add_source(OutputSourceKind::Call, Box::new(ConstantSource(0.25)));
+        let chime = router.add_source(
+            OutputSourceKind::Notification,
+            Box::new(ConstantSource(0.5)),
+        );
+        assert_eq!(call.output, "headset");
+        assert_eq!(chime.output, "speakers");
+
+        assert_eq!(router.output("headset").unwrap().render(), 0.25);
+        assert_eq!(router.output("speakers").unwrap().render(), 0.5);
+
+        router.remove_source(&call);
+        assert_eq!(router.output("headset").unwrap().render(), 0.0);
+        assert_eq!(router.output("speakers").unwrap().render(), 0.5);
+        assert!(router.source_output(&call).is_none());
+    }
+
+    #[test]
+    fn removing_output_tears_down_sources_and_falls_back() {
+        let mut router = PlaybackRouter::new("headset", MockOutput::default());
+        router.add_output("speakers", MockOutput::default());
+        router
+            .set_route(OutputSourceKind::Notification, "speakers")
+            .unwrap();
+        let chime = router.add_source(
+            OutputSourceKind::Notification,
+            Box::new(ConstantSource(0.5)),
+        );
+
+        let speakers = router.remove_output("speakers").unwrap();
+        assert_eq!(speakers.render(), 0.0);
+        assert!(router.source_output(&chime).is_none());
+        assert_eq!(router.route(OutputSourceKind::Notification), "headset");
+        assert!(router.remove_output("headset").is_none());
+        assert!(
+            router
+                .set_route(OutputSourceKind::Notification, "speakers")
+                .is_err()
+        );
+
+        let chime = router.add_source(
+            OutputSourceKind::Notification,
+            Box::new(ConstantSource(0.5)),
+        );
+        assert_eq!(chime.output, "headset");
+        assert_eq!(router.routed_output(OutputSourceKind::Call).render(), 0.5);
+    }
+}
@@ -93,7 +93,6 @@ function DeviceSelector(props: DeviceSelectorProps) {
                 disabled={
                     devices === undefined ||
                     devices.length === 0 ||
-                    callDisplayType === "accepted" ||
                     callDisplayType === "outgoing"
                 }
             />
@@ -12,7 +12,7 @@ import {CallId, ClientId, PositionId} from "../types/generic.ts";
 import {useProfileStore} from "../stores/profile-store.ts";
 import {StationChange, StationInfo} from "../types/station.ts";
 import {useStationsStore} from "../stores/stations-store.ts";
-import {Call} from "../types/call.ts";
+import {BroadcastCall, BroadcastCallStarted, broadcastCallToCall, Call} from "../types/call.ts";
 import {useErrorOverlayStore} from "../stores/error-overlay-store.ts";
 import {Profile} from "../types/profile.ts";
 import {navigate} from "wouter/use-browser-location";
@@ -27,6 +27,7 @@ export function setupSignalingListeners() {
         removeCall,
         rejectCall,
         acceptIncomingCall,
+        joinBroadcastCall,
         setOutgoingCallAccepted,
         reset: resetCallStore,
     } = useCallStore.getState().actions;
@@ -37,7 +38,7 @@ export function setupSignalingListeners() {
     } = useCallListStore.getState().actions;
     const {setConnectionState, setConnectionInfo, setPositionsToSelect} =
         useConnectionStore.getState();
-    const {setProfile, reset: resetProfileStore} = useProfileStore.getState();
+    const {setProfile, setAvailableProfiles, reset: resetProfileStore} = useProfileStore.getState();
     const {open: openErrorOverlay, closeIfTitle: closeErrorOverlayIfTitle} =
         useErrorOverlayStore.getState();
     const {setFilter} = useFilterStore.getState();
@@ -50,10 +51,10 @@ export function setupSignalingListeners() {
             listen<SessionInfo>("signaling:connected", event => {
                 setConnectionState("connected");
                 setConnectionInfo(event.payload.client);
+                setAvailableProfiles(event.payload.availableProfiles ?? []);
                 if (
                     event.payload.profile.type === "changed" &&
-                    event.payload.profile.activeProfile !== undefined &&
-                    event.payload.profile.activeProfile.profile !== undefined
+                    event.payload.profile.activeProfile !== undefined
                 ) {
                     setProfile(event.payload.profile.activeProfile.profile);
                 }
@@ -111,6 +112,14 @@ export function setupSignalingListeners() {
                     setOutgoingCallAccepted(event.payload.callId, event.payload.acceptingClientId);
                 },
             ),
+            listen<BroadcastCall>("signaling:broadcast-call", event => {
+                joinBroadcastCall(broadcastCallToCall(event.payload));
+            }),
+            listen<BroadcastCallStarted>("signaling:broadcast-call-started", event => {
+                const [recipientId] = event.payload.recipientIds;
+                if (recipientId === undefined) return;
+                setOutgoingCallAccepted(event.payload.callId, recipientId);
+            }),
             listen<CallId>("signaling:call-end", event => {
                 removeCall(event.payload, true);
             }),
@@ -7,6 +7,7 @@ import {useConnectionStore} from "../stores/connection-store.ts";
 import {navigate} from "wouter/use-browser-location";
 import List from "../components/ui/List.tsx";
 import {ClientGroupMode} from "../types/client.ts";
+import {AvailableProfile} from "../types/profile.ts";
 import {clsx} from "clsx";
 import {useSettingsStore} from "../stores/settings-store.ts";
 import {useShallow} from "zustand/react/shallow";
@@ -28,7 +29,7 @@ function MissionPage() {
                 </div>
                 <hr className="h-[2px] bg-white border-none" />
                 <div className="w-full flex-1 min-h-0 flex justify-center py-3 px-2 items-center">
-                    <p className="text-slate-600">Not implemented</p>
+                    <ProfileSelection />
                 </div>
                 <TestProfile />
             </div>
@@ -119,6 +120,56 @@ function ClientConfigRow(name: string | undefined, isSelected: boolean, onClick:
     );
 }
 
+function ProfileSelection() {
+    const availableProfiles = useProfileStore(state => state.availableProfiles);
+    const activeProfileId = useProfileStore(state => state.profile?.id);
+    const isConnected = useConnectionStore(state => state.connectionState === "connected");
+
+    if (!isConnected || availableProfiles.length === 0) {
+        return <p className="text-slate-600">No profiles available</p>;
+    }
+
+    const selectedIndex = availableProfiles.findIndex(p =>
+        p.type === "specific" ? p.profile === activeProfileId : activeProfileId === undefined,
+    );
+
+    return (
+        <List
+            className="w-96"
+            itemsCount={availableProfiles.length}
+            selectedItem={selectedIndex}
+            setSelectedItem={async index => {
+                const profile = availableProfiles[index];
+                if (profile === undefined || index === selectedIndex) return;
+                await invokeSafe("signaling_set_active_profile", {
+                    profileId: profile.type === "specific" ? profile.profile : undefined,
+                });
+            }}
+            defaultRows={4}
+            row={(index, isSelected, onClick) =>
+                ProfileRow(availableProfiles[index], isSelected, onClick)
+            }
+            header={[{title: "Profiles"}]}
+            columnWidths={["1fr"]}
+        />
+    );
+}
+
+function ProfileRow(
+    profile: AvailableProfile | undefined,
+    isSelected: boolean,
+    onClick: () => void,
+) {
+    const color = isSelected ? "bg-blue-700 text-white" : "bg-yellow-50";
+    const label = profile?.type === "specific" ? profile.profile : "Custom";
+
+    return (
+        <div className={clsx("px-0.5 flex items-center font-semibold", color)} onClick={onClick}>
+            {profile !== undefined ? label : ""}
+        </div>
+    );
+}
+
 function TestProfile() {
     const enableTestProfile = useConnectionStore(
         state => state.connectionState === "disconnected" || state.connectionState === "test",
@@ -2,7 +2,7 @@ import {create} from "zustand/react";
 import {invokeStrict} from "../error.ts";
 import {useErrorOverlayStore} from "./error-overlay-store.ts";
 import {useAuthStore} from "./auth-store.ts";
-import {Call, CallSource, CallTarget} from "../types/call.ts";
+import {broadcastCallToCall, Call, CallSource, CallTarget} from "../types/call.ts";
 import {CallId, ClientId, StationId} from "../types/generic.ts";
 import {useConnectionStore} from "./connection-store.ts";
 import {useCallListStore} from "./call-list-store.ts";
@@ -27,6 +27,7 @@ type CallState = {
     actions: {
         setOutgoingCall: (call: Call) => void;
         acceptIncomingCall: (callId: CallId) => void;
+        joinBroadcastCall: (call: Call) => void;
         setOutgoingCallAccepted: (calLId: CallId, targetClientId: ClientId) => void;
         endCall: () => void;
         addIncomingCall: (call: Call) => void;
@@ -77,6 +78,16 @@ export const useCallStore = create<CallState>()((set, get) => ({
                 incomingCalls,
             });
         },
+        joinBroadcastCall: call => {
+            set({
+                callDisplay: {
+                    type: "accepted",
+                    call,
+                    targetClientId: call.source.clientId,
+                    connectionState: "connecting",
+                },
+            });
+        },
         setOutgoingCallAccepted: (callId, targetClientId) => {
             const callDisplay = get().callDisplay;
 
@@ -290,3 +301,39 @@ export const startCall = async (target: CallTarget) => {
         addOutgoingCallToCallList({callId, target});
     } catch {}
 };
+
+export const startBroadcastCall = async (stationIds: StationId[]) => {
+    const {cid} = useAuthStore.getState();
+    const openErrorOverlay = useErrorOverlayStore.getState().open;
+
+    if (cid === undefined) {
+        openErrorOverlay(
+            "Unauthenticated",
+            "You are unauthenticated and cannot start a broadcast call",
+            false,
+            5000,
+        );
+        return;
+    } else if (stationIds.length === 0) {
+        openErrorOverlay("Call error", "No stations selected for the broadcast", false, 5000);
+        return;
+    }
+
+    const {info} = useConnectionStore.getState();
+    const {setOutgoingCall} = useCallStore.getState().actions;
+    const {defaultSource} = useStationsStore.getState();
+
+    const source: CallSource = {
+        clientId: cid,
+        positionId: info.positionId,
+        stationId: defaultSource,
+    };
+
+    try {
+        const callId = await invokeStrict<CallId>("signaling_start_broadcast_call", {
+            source,
+            stationIds,
+        });
+        setOutgoingCall(broadcastCallToCall({callId, source, stationIds}));
+    } catch {}
+};
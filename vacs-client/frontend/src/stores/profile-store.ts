@@ -1,4 +1,5 @@
 import {
+    AvailableProfile,
     DirectAccessKey,
     DirectAccessPage,
     GeoPageContainer,
@@ -13,9 +14,11 @@ type SelectedPage = {current: DirectAccessPage | undefined; parent: DirectAccess
 
 type ProfileState = {
     profile: Profile | undefined;
+    availableProfiles: AvailableProfile[];
     page: SelectedPage;
     testProfilePath: string | undefined;
     setProfile: (profile: Profile | undefined) => void;
+    setAvailableProfiles: (availableProfiles: AvailableProfile[]) => void;
     setPage: (page: DirectAccessPage | undefined) => void;
     setSubpage: (page: DirectAccessPage, parent: DirectAccessPage) => void;
     navigateParentPage: () => void;
@@ -25,9 +28,11 @@ type ProfileState = {
 
 export const useProfileStore = create<ProfileState>()((set, get, store) => ({
     profile: undefined,
+    availableProfiles: [],
     page: {current: undefined, parent: undefined},
     testProfilePath: undefined,
     setProfile: profile => set({profile}),
+    setAvailableProfiles: availableProfiles => set({availableProfiles}),
     setPage: page => set({page: {current: page, parent: undefined}}),
     setSubpage: (page, parent) => set({page: {current: page, parent: get().page.parent ?? parent}}),
     navigateParentPage: () => {
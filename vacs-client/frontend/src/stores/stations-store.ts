@@ -19,7 +19,11 @@ export const useStationsStore = create<StationsState>()((set, get, store) => ({
     defaultSource: undefined,
     temporarySource: undefined,
     setStations: stationsList => {
-        const stations = new Map(stationsList.map(s => [s.id, s.own]));
+        // Stations covered by controllers not connected to vacs can't be called and are treated
+        // like offline stations.
+        const stations = new Map(
+            stationsList.filter(s => s.callable !== false).map(s => [s.id, s.own]),
+        );
 
         const [defaultSource, temporarySource] = checkStationSourcesAreOwn(stations, get);
 
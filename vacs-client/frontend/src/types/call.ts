@@ -19,6 +19,26 @@ export type Call = {
     prio: boolean;
 };
 
+export type BroadcastCall = {
+    callId: CallId;
+    source: CallSource;
+    stationIds: StationId[];
+};
+
+export type BroadcastCallStarted = {
+    callId: CallId;
+    recipientIds: ClientId[];
+};
+
+export function broadcastCallToCall(broadcast: BroadcastCall): Call {
+    return {
+        callId: broadcast.callId,
+        source: broadcast.source,
+        target: {station: broadcast.stationIds[0]},
+        prio: false,
+    };
+}
+
 export function callSourceToTarget(source: CallSource): CallTarget {
     if (source.stationId !== undefined) {
         return {station: source.stationId};
@@ -1,5 +1,5 @@
 import {ClientId, PositionId} from "./generic.ts";
-import {SessionProfile} from "./profile.ts";
+import {AvailableProfile, SessionProfile} from "./profile.ts";
 
 export type ClientInfo = {
     id: ClientId;
@@ -11,6 +11,7 @@ export type ClientInfo = {
 export type SessionInfo = {
     client: ClientInfo;
     profile: SessionProfile;
+    availableProfiles?: AvailableProfile[];
 };
 
 export function splitDisplayName(name: string): [string, string] {
@@ -11,6 +11,11 @@ export type ActiveProfile = {
     profile?: Profile;
 };
 
+export type AvailableProfile = {
+    type: "specific" | "custom" | "none";
+    profile?: ProfileId;
+};
+
 export type Profile = {
     id: ProfileId;
     geo?: GeoPageContainer;
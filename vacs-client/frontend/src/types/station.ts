@@ -3,6 +3,7 @@ import {PositionId, StationId} from "./generic.ts";
 export type StationInfo = {
     id: StationId;
     own: boolean;
+    callable?: boolean;
 };
 
 export type StationChange = {
@@ -17,5 +18,6 @@ export type StationChange = {
     };
     offline?: {
         stationId: StationId;
+        reason?: "temporarilyClosed" | "vatsimOnly";
     };
 };
@@ -3,7 +3,7 @@ use crate::app::{AppFolder, UpdateInfo, get_update, open_app_folder, open_fatal_
 use crate::audio::manager::{AudioManagerHandle, SourceType};
 use crate::build::VersionInfo;
 use crate::config::{
-    AppConfig, CLIENT_SETTINGS_FILE_NAME, ClientConfig, FrontendCallConfig,
+    AppConfig, CLIENT_SETTINGS_FILE_NAME, CallConfig, ClientConfig, FrontendCallConfig,
     FrontendClientPageSettings, Persistable, PersistedClientConfig,
 };
 use crate::error::{Error, FrontendError};
@@ -328,7 +328,12 @@ pub async fn app_set_call_config(
             audio_manager.read().restart(SourceType::CallEnd);
         }
 
-        state.config.client.call = call_config.into();
+        // Auto-answer is only configurable via the config file, keep it as is
+        let auto_answer = std::mem::take(&mut state.config.client.call.auto_answer);
+        state.config.client.call = CallConfig {
+            auto_answer,
+            ..call_config.into()
+        };
         state.config.client.clone().into()
     };
 
@@ -6,9 +6,9 @@ pub(crate) mod signaling;
 pub(crate) mod webrtc;
 
 use crate::app::state::signaling::AppStateSignalingExt;
-use crate::app::state::webrtc::{Call, UnansweredCallGuard};
+use crate::app::state::webrtc::{Broadcast, Call, UnansweredCallGuard};
 use crate::audio::manager::{AudioManager, AudioManagerHandle};
-use crate::config::AppConfig;
+use crate::config::{AppConfig, OutputRoute};
 use crate::error::{StartupError, StartupErrorExt};
 use crate::keybinds::engine::{KeybindEngine, KeybindEngineHandle};
 use crate::signaling::auth::TauriTokenProvider;
@@ -21,6 +21,7 @@ use tauri::{AppHandle, Manager};
 use tokio::sync::{Mutex as TokioMutex, RwLock as TokioRwLock};
 use tokio_util::sync::CancellationToken;
 use vacs_signaling::client::SignalingClient;
+use vacs_signaling::protocol::http::webrtc::IceServer;
 use vacs_signaling::protocol::vatsim::ClientId;
 use vacs_signaling::protocol::ws::shared::CallId;
 use vacs_signaling::transport::tokio::TokioTransport;
@@ -34,9 +35,14 @@ pub struct AppStateInner {
     keybind_engine: KeybindEngineHandle,
     active_call: Option<Call>,
     unanswered_call_guard: Option<UnansweredCallGuard>,
-    held_calls: HashMap<CallId, Call>,  // call_id -> call
-    outgoing_call_id: Option<CallId>,   // peer_id
-    incoming_call_ids: HashSet<CallId>, // peer_id
+    held_calls: HashMap<CallId, Call>, // call_id -> call
+    broadcast: Option<Broadcast>,
+    incoming_broadcast: Option<(CallId, ClientId)>, // call_id, broadcaster_id
+    outgoing_call_id: Option<CallId>,               // peer_id
+    incoming_call_ids: HashSet<CallId>,             // peer_id
+    call_output_routes: HashMap<CallId, OutputRoute>, // call_id -> route, if not primary
+    transmitting: bool,
+    region_ice_servers: Option<Vec<IceServer>>,
     pub test_profile_watcher: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
 }
 
@@ -49,6 +55,12 @@ impl AppStateInner {
             .app_config_dir()
             .map_startup_err(StartupError::Config)?;
 
+        match AppConfig::write_default_file(&config_dir) {
+            Ok(Some(path)) => log::info!("No config file found, wrote defaults to {path:?}"),
+            Ok(None) => {}
+            Err(err) => log::warn!("Failed to write default config file: {err:?}"),
+        }
+
         let config = AppConfig::parse(&config_dir).map_startup_err(StartupError::Config)?;
         let shutdown_token = CancellationToken::new();
 
@@ -58,6 +70,7 @@ impl AppStateInner {
             signaling_client: Self::new_signaling_client(
                 app.clone(),
                 &config.backend.ws_url,
+                config.backend.ws_backup_url.as_deref(),
                 shutdown_token.child_token(),
                 config.client.max_signaling_reconnect_attempts(),
             ),
@@ -76,8 +89,13 @@ impl AppStateInner {
             active_call: None,
             unanswered_call_guard: None,
             held_calls: HashMap::new(),
+            broadcast: None,
+            incoming_broadcast: None,
             outgoing_call_id: None,
             incoming_call_ids: HashSet::new(),
+            call_output_routes: HashMap::new(),
+            transmitting: false,
+            region_ice_servers: None,
             test_profile_watcher: None,
         })
     }
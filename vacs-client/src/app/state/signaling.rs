@@ -1,15 +1,18 @@
 use crate::app::state::http::HttpState;
-use crate::app::state::webrtc::{AppStateWebrtcExt, UnansweredCallGuard};
+use crate::app::state::webrtc::{AppStateWebrtcExt, UnansweredCallGuard, broadcast_audio_codecs};
 use crate::app::state::{AppState, AppStateInner, sealed};
 use crate::audio::manager::{AudioManagerHandle, SourceType};
-use crate::config::{BackendEndpoint, WS_LOGIN_TIMEOUT};
+use crate::build::VersionInfo;
+use crate::config::{AutoAnswerConfig, BackendEndpoint, WS_LOGIN_TIMEOUT, frame_codec};
 use crate::error::{Error, FrontendError};
+use crate::platform::Platform;
 use crate::signaling::auth::TauriTokenProvider;
 use serde::Serialize;
 use serde_json::Value;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio_util::sync::CancellationToken;
+use vacs_audio::FrameCodec;
 use vacs_signaling::client::{SignalingClient, SignalingEvent, State};
 use vacs_signaling::error::{SignalingError, SignalingRuntimeError};
 use vacs_signaling::protocol::http::webrtc::IceConfig;
@@ -18,7 +21,9 @@ use vacs_signaling::protocol::ws::client::{CallRejectReason, ClientMessage};
 use vacs_signaling::protocol::ws::server::{
     CallCancelReason, DisconnectReason, LoginFailureReason, ServerMessage, SessionProfile,
 };
-use vacs_signaling::protocol::ws::shared::{CallErrorReason, CallId, CallSource, ErrorReason};
+use vacs_signaling::protocol::ws::shared::{
+    AudioCodec, CallErrorReason, CallId, CallSource, CallTarget, ErrorReason,
+};
 use vacs_signaling::protocol::ws::{client, server, shared};
 use vacs_signaling::transport::tokio::TokioTransport;
 
@@ -33,6 +38,7 @@ pub trait AppStateSignalingExt: sealed::Sealed {
     async fn disconnect_signaling(&mut self, app: &AppHandle);
     async fn handle_signaling_connection_closed(&mut self, app: &AppHandle);
     async fn send_signaling_message(&mut self, msg: impl Into<ClientMessage>) -> Result<(), Error>;
+    async fn send_transmit_state(&mut self, transmitting: bool);
     fn set_client_id(&mut self, client_id: Option<ClientId>);
     fn outgoing_call_id(&self) -> Option<&CallId>;
     fn set_outgoing_call_id(&mut self, call_id: Option<CallId>);
@@ -55,6 +61,7 @@ pub trait AppStateSignalingExt: sealed::Sealed {
     fn new_signaling_client(
         app: AppHandle,
         ws_url: &str,
+        ws_backup_url: Option<&str>,
         shutdown_token: CancellationToken,
         max_reconnect_attempts: u8,
     ) -> SignalingClient<TokioTransport, TauriTokenProvider>;
@@ -64,6 +71,7 @@ pub trait AppStateSignalingExt: sealed::Sealed {
         &mut self,
         app: &AppHandle,
         call_id: Option<CallId>,
+        auto_answered: bool,
     ) -> Result<bool, Error>;
     async fn end_call(&mut self, app: &AppHandle, call_id: Option<CallId>) -> Result<bool, Error>;
 }
@@ -135,6 +143,25 @@ impl AppStateSignalingExt for AppStateInner {
         Ok(())
     }
 
+    async fn send_transmit_state(&mut self, transmitting: bool) {
+        if self.transmitting == transmitting || self.signaling_client.state() == State::Disconnected
+        {
+            return;
+        }
+
+        log::trace!("Sending transmit state: transmitting={transmitting}");
+        match self
+            .send_signaling_message(client::TransmitState {
+                station_id: None,
+                transmitting,
+            })
+            .await
+        {
+            Ok(()) => self.transmitting = transmitting,
+            Err(err) => log::warn!("Failed to send transmit state: {err:?}"),
+        }
+    }
+
     fn set_client_id(&mut self, client_id: Option<ClientId>) {
         self.client_id = client_id;
     }
@@ -219,11 +246,17 @@ impl AppStateSignalingExt for AppStateInner {
     fn new_signaling_client(
         app: AppHandle,
         ws_url: &str,
+        ws_backup_url: Option<&str>,
         shutdown_token: CancellationToken,
         max_reconnect_attempts: u8,
     ) -> SignalingClient<TokioTransport, TauriTokenProvider> {
-        SignalingClient::new(
-            TokioTransport::new(ws_url),
+        let mut transport = TokioTransport::new(ws_url);
+        if let Some(ws_backup_url) = ws_backup_url {
+            transport = transport.with_backup(ws_backup_url);
+        }
+
+        let client = SignalingClient::new(
+            transport,
             TauriTokenProvider::new(app.clone()),
             move |e| {
                 let handle = app.clone();
@@ -236,7 +269,10 @@ impl AppStateSignalingExt for AppStateInner {
             WS_LOGIN_TIMEOUT,
             max_reconnect_attempts,
             tauri::async_runtime::handle().inner(),
-        )
+        );
+        client.set_client_version(VersionInfo::gather().version);
+        client.set_tags(vec![format!("platform:{}", Platform::get())]);
+        client
     }
 
     fn start_unanswered_call_timer(&mut self, app: &AppHandle, call_id: &CallId) {
@@ -312,6 +348,7 @@ impl AppStateSignalingExt for AppStateInner {
         &mut self,
         app: &AppHandle,
         call_id: Option<CallId>,
+        auto_answered: bool,
     ) -> Result<bool, Error> {
         let Some(own_client_id) = self.client_id.as_ref().cloned() else {
             log::warn!("Cannot accept call without own client ID");
@@ -322,7 +359,7 @@ impl AppStateSignalingExt for AppStateInner {
             Some(id) => id,
             None => return Ok(false),
         };
-        log::debug!("Accepting call {call_id:?}");
+        log::debug!("Accepting call {call_id:?} (auto answered: {auto_answered})");
 
         if !self.config.ice.is_default() && self.is_ice_config_expired() {
             match app
@@ -342,6 +379,7 @@ impl AppStateSignalingExt for AppStateInner {
         self.send_signaling_message(shared::CallAccept {
             call_id,
             accepting_client_id: own_client_id,
+            auto_answered,
         })
         .await?;
         self.remove_incoming_call_id(&call_id);
@@ -362,6 +400,7 @@ impl AppStateSignalingExt for AppStateInner {
 
         let Some(call_id) = call_id.or_else(|| {
             self.active_call_id()
+                .or(self.broadcast_call_id())
                 .or(self.outgoing_call_id.as_ref())
                 .cloned()
         }) else {
@@ -394,18 +433,37 @@ impl AppStateInner {
             SignalingEvent::Connected {
                 client_info,
                 profile,
+                ice_servers,
+                frequency_mismatch,
+                position_without_coverage,
+                available_profiles,
+                endpoint,
             } => {
                 log::debug!(
-                    "Successfully connected to signaling server. Display name: {}, frequency: {}, profile: {profile}",
+                    "Successfully connected to signaling server {}. Display name: {}, frequency: {}, profile: {profile}",
+                    endpoint.as_deref().unwrap_or("<unknown>"),
                     &client_info.display_name,
                     &client_info.frequency,
                 );
 
+                if position_without_coverage {
+                    log::warn!(
+                        "Position {:?} controls no stations in the server's dataset",
+                        client_info.position_id
+                    );
+                }
+
+                app.state::<AppState>().lock().await.region_ice_servers = ice_servers.clone();
+
                 app.emit(
                     "signaling:connected",
                     server::SessionInfo {
                         client: client_info,
                         profile: SessionProfile::Changed(profile),
+                        ice_servers,
+                        frequency_mismatch,
+                        position_without_coverage,
+                        available_profiles,
                     },
                 )
                 .ok();
@@ -480,8 +538,28 @@ impl AppStateInner {
                 }
 
                 state.add_incoming_call_id(call_id);
+                let route = state
+                    .config
+                    .audio
+                    .call_output_routing
+                    .route(source.station_id.as_ref(), source.position_id.as_ref());
+                state.set_call_output_route(*call_id, route);
                 app.emit("signaling:call-invite", msg).ok();
 
+                if should_auto_answer(
+                    &state.config.client.call.auto_answer,
+                    target,
+                    state.active_call_id(),
+                ) {
+                    log::debug!("Auto-answering call {call_id} to {target:?}");
+                    match state.accept_call(app, Some(*call_id), true).await {
+                        Ok(_) => return,
+                        Err(err) => {
+                            log::warn!("Failed to auto-answer call, ringing instead: {err:?}");
+                        }
+                    }
+                }
+
                 if *prio && state.config.client.call.enable_priority_calls {
                     state.audio_manager.read().restart(SourceType::PriorityRing);
                 } else {
@@ -492,9 +570,12 @@ impl AppStateInner {
                 ref msg @ shared::CallAccept {
                     ref call_id,
                     ref accepting_client_id,
+                    ref auto_answered,
                 },
             ) => {
-                log::trace!("Call accept received for call {call_id} from {accepting_client_id}");
+                log::trace!(
+                    "Call accept received for call {call_id} from {accepting_client_id} (auto answered: {auto_answered})"
+                );
 
                 let state = app.state::<AppState>();
                 let mut state = state.lock().await;
@@ -514,16 +595,24 @@ impl AppStateInner {
                     );
 
                     match state
-                        .init_call(app.clone(), *call_id, accepting_client_id.clone(), None)
+                        .init_call(
+                            app.clone(),
+                            *call_id,
+                            accepting_client_id.clone(),
+                            None,
+                            FrameCodec::default(),
+                        )
                         .await
                     {
                         Ok(sdp) => {
+                            let audio_codecs = state.config.audio.audio_codecs();
                             state
                                 .send_signaling_message(shared::WebrtcOffer {
                                     call_id: *call_id,
                                     from_client_id: own_client_id,
                                     to_client_id: accepting_client_id.clone(),
                                     sdp,
+                                    audio_codecs,
                                 })
                                 .await
                         }
@@ -561,14 +650,28 @@ impl AppStateInner {
                 from_client_id,
                 to_client_id,
                 sdp,
+                audio_codecs,
             }) => {
                 log::trace!("WebRTC offer for call {call_id} received from {from_client_id}");
 
                 let state = app.state::<AppState>();
                 let mut state = state.lock().await;
 
+                let audio_codec =
+                    AudioCodec::negotiate(&audio_codecs, &state.config.audio.audio_codecs());
+                log::debug!(
+                    "Negotiated audio codec {} for call {call_id}",
+                    audio_codec.clone().unwrap_or_default()
+                );
+
                 let res = match state
-                    .init_call(app.clone(), call_id, from_client_id.clone(), Some(sdp))
+                    .init_call(
+                        app.clone(),
+                        call_id,
+                        from_client_id.clone(),
+                        Some(sdp),
+                        frame_codec(audio_codec.as_ref()),
+                    )
                     .await
                 {
                     Ok(sdp) => {
@@ -578,6 +681,7 @@ impl AppStateInner {
                                 to_client_id: from_client_id,
                                 from_client_id: to_client_id,
                                 sdp,
+                                audio_codec,
                             })
                             .await
                     }
@@ -603,14 +707,27 @@ impl AppStateInner {
                 call_id,
                 from_client_id,
                 sdp,
+                audio_codec,
                 ..
             }) => {
                 log::trace!("WebRTC answer for call {call_id} received from {from_client_id}");
+                log::debug!(
+                    "Negotiated audio codec {} for call {call_id}",
+                    audio_codec.clone().unwrap_or_default()
+                );
 
                 let state = app.state::<AppState>();
                 let mut state = state.lock().await;
 
-                if let Err(err) = state.accept_call_answer(&from_client_id, sdp).await {
+                if let Err(err) = state
+                    .accept_call_answer(
+                        &call_id,
+                        &from_client_id,
+                        sdp,
+                        frame_codec(audio_codec.as_ref()),
+                    )
+                    .await
+                {
                     log::warn!("Failed to accept answer: {err:?}");
                     if let Err(err) = state
                         .send_signaling_message(shared::CallError {
@@ -633,6 +750,15 @@ impl AppStateInner {
                 let state = app.state::<AppState>();
                 let mut state = state.lock().await;
 
+                // Recipients leaving a broadcast only end the broadcast once the last one left.
+                if state.broadcast_call_id() == Some(&call_id)
+                    && state
+                        .remove_broadcast_recipient(&call_id, &ending_client_id)
+                        .await
+                {
+                    return;
+                }
+
                 if !state.cleanup_call(&call_id).await {
                     log::debug!("Received call end message for peer that is not active");
                 }
@@ -674,7 +800,7 @@ impl AppStateInner {
                 state.cleanup_call(&call_id).await;
 
                 // Remove from outgoing and incoming states
-                state.remove_outgoing_call_id(&call_id);
+                let was_outgoing = state.remove_outgoing_call_id(&call_id);
                 state.remove_incoming_call_id(&call_id);
 
                 state.cancel_unanswered_call_timer(&call_id);
@@ -692,6 +818,12 @@ impl AppStateInner {
                     CallCancelReason::Errored(reason) => {
                         state.emit_call_error(app, call_id, false, reason);
                     }
+                    CallCancelReason::Timeout if was_outgoing => {
+                        state.emit_call_error(app, call_id, false, CallErrorReason::AutoHangup);
+                    }
+                    CallCancelReason::Timeout => {
+                        app.emit("signaling:call-end", &call_id).ok();
+                    }
                 }
             }
             ServerMessage::WebrtcIceCandidate(shared::WebrtcIceCandidate {
@@ -705,7 +837,9 @@ impl AppStateInner {
                 let state = app.state::<AppState>();
                 let state = state.lock().await;
 
-                state.set_remote_ice_candidate(&call_id, candidate).await;
+                state
+                    .set_remote_ice_candidate(&call_id, &from_client_id, candidate)
+                    .await;
             }
             ServerMessage::ClientConnected(server::ClientConnected { client }) => {
                 log::trace!("Client connected: {client:?}");
@@ -730,15 +864,52 @@ impl AppStateInner {
             ref msg @ ServerMessage::SessionInfo(server::SessionInfo {
                 ref client,
                 ref profile,
+                ref ice_servers,
+                ref frequency_mismatch,
+                ..
             }) => {
                 log::trace!("Received session info for client {client:?}: {profile}");
 
+                if let Some(mismatch) = frequency_mismatch {
+                    log::warn!(
+                        "VATSIM frequency {} does not match position frequency {}",
+                        mismatch.actual,
+                        mismatch.expected
+                    );
+                }
+
                 if let SessionProfile::Changed(active_profile) = profile {
                     log::debug!("Active profile changed: {active_profile}");
                 }
 
+                app.state::<AppState>().lock().await.region_ice_servers = ice_servers.clone();
+
                 app.emit("signaling:connected", msg).ok();
             }
+            ServerMessage::ProfileTooLarge(server::ProfileTooLarge { profile_id }) => {
+                log::error!("Profile {profile_id} is too large to be received from the server");
+
+                app.emit::<FrontendError>(
+                    "error",
+                    FrontendError::new(
+                        "Profile error",
+                        format!(
+                            "Profile {profile_id} is too large to be loaded, keeping the previous profile. Please contact the server administrator."
+                        ),
+                    ),
+                )
+                .ok();
+            }
+            ServerMessage::PositionReassigned(reassigned) => {
+                log::info!(
+                    "Position reassigned from {:?} to {:?}: {:?}",
+                    reassigned.from,
+                    reassigned.to,
+                    reassigned.reason
+                );
+
+                app.emit("signaling:position-reassigned", reassigned).ok();
+            }
             ServerMessage::StationList(server::StationList { stations }) => {
                 log::trace!(
                     "Received station list: {} stations covered ({} by self)",
@@ -753,6 +924,139 @@ impl AppStateInner {
 
                 app.emit("signaling:station-changes", changes).ok();
             }
+            ServerMessage::BroadcastCall(broadcast) => {
+                log::trace!("Received broadcast call: {broadcast:?}");
+
+                let state = app.state::<AppState>();
+                let mut state = state.lock().await;
+
+                let Some(own_client_id) = state.client_id.as_ref().cloned() else {
+                    log::warn!("Cannot handle broadcast call without own client ID");
+                    return;
+                };
+
+                if state.active_call_id().is_some()
+                    || state.broadcast_call_id().is_some()
+                    || state.outgoing_call_id().is_some()
+                {
+                    log::debug!(
+                        "Call already active, leaving broadcast call {}",
+                        broadcast.call_id
+                    );
+                    if let Err(err) = state
+                        .send_signaling_message(shared::CallEnd {
+                            call_id: broadcast.call_id,
+                            ending_client_id: own_client_id,
+                        })
+                        .await
+                    {
+                        log::warn!("Failed to leave broadcast call: {err:?}");
+                    }
+                    return;
+                }
+
+                state.set_incoming_broadcast(broadcast.call_id, broadcast.source.client_id.clone());
+                app.emit("signaling:broadcast-call", broadcast).ok();
+            }
+            ServerMessage::BroadcastCallStarted(server::BroadcastCallStarted {
+                call_id,
+                recipient_ids,
+            }) => {
+                log::trace!(
+                    "Broadcast call {call_id} started with {} recipients",
+                    recipient_ids.len()
+                );
+
+                let state = app.state::<AppState>();
+                let mut state = state.lock().await;
+
+                let Some(own_client_id) = state.client_id.as_ref().cloned() else {
+                    log::warn!("Cannot handle broadcast call started without own client ID");
+                    return;
+                };
+
+                match state
+                    .init_broadcast_peers(app.clone(), &call_id, recipient_ids.clone())
+                    .await
+                {
+                    Ok(offers) => {
+                        for (recipient_id, sdp) in offers {
+                            if let Err(err) = state
+                                .send_signaling_message(shared::WebrtcOffer {
+                                    call_id,
+                                    from_client_id: own_client_id.clone(),
+                                    to_client_id: recipient_id,
+                                    sdp,
+                                    audio_codecs: broadcast_audio_codecs(),
+                                })
+                                .await
+                            {
+                                log::warn!("Failed to send broadcast offer: {err:?}");
+                            }
+                        }
+
+                        app.emit(
+                            "signaling:broadcast-call-started",
+                            server::BroadcastCallStarted {
+                                call_id,
+                                recipient_ids,
+                            },
+                        )
+                        .ok();
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to start broadcast call: {err:?}");
+                        let reason: CallErrorReason = err.into();
+                        state.cleanup_call(&call_id).await;
+                        if let Err(err) = state
+                            .send_signaling_message(shared::CallEnd {
+                                call_id,
+                                ending_client_id: own_client_id,
+                            })
+                            .await
+                        {
+                            log::warn!("Failed to end broadcast call: {err:?}");
+                        }
+                        state.emit_call_error(app, call_id, true, reason);
+                    }
+                }
+            }
+            ServerMessage::StationHierarchy(server::StationHierarchy { stations }) => {
+                log::trace!("Received station hierarchy: {} stations", stations.len());
+
+                app.emit("signaling:station-hierarchy", stations).ok();
+            }
+            ServerMessage::StationCoverage(coverage) => {
+                log::trace!("Received station coverage: {coverage:?}");
+
+                app.emit("signaling:station-coverage", coverage).ok();
+            }
+            ServerMessage::ActiveCallList(server::ActiveCallList { calls }) => {
+                log::trace!("Received active call list: {} calls", calls.len());
+
+                app.emit("signaling:active-call-list", calls).ok();
+            }
+            ServerMessage::DatasetInfo(dataset_info) => {
+                log::trace!("Received dataset info: {dataset_info:?}");
+
+                app.emit("signaling:dataset-info", dataset_info).ok();
+            }
+            ServerMessage::Announcement(announcement) => {
+                log::info!("Received announcement: {announcement:?}");
+
+                app.emit("signaling:announcement", announcement).ok();
+            }
+            ServerMessage::MonitoredFrequencies(monitored_frequencies) => {
+                log::debug!("Received monitored frequencies: {monitored_frequencies:?}");
+
+                app.emit("signaling:monitored-frequencies", monitored_frequencies)
+                    .ok();
+            }
+            ServerMessage::TransmitState(transmit_state) => {
+                log::trace!("Received transmit state: {transmit_state:?}");
+
+                app.emit("signaling:transmit-state", transmit_state).ok();
+            }
             ServerMessage::Error(shared::Error {
                 reason,
                 client_id,
@@ -837,6 +1141,28 @@ impl AppStateInner {
 
                     app.emit("signaling:client-not-found", client_id).ok();
                 }
+                ErrorReason::ProfileNotAvailable => {
+                    log::warn!("Received profile not available error from signaling server");
+
+                    app.emit::<FrontendError>(
+                        "error",
+                        FrontendError::from(Error::from(SignalingRuntimeError::ServerError(
+                            ErrorReason::ProfileNotAvailable,
+                        ))),
+                    )
+                    .ok();
+                }
+                ErrorReason::InvalidFrequencies(ref msg) => {
+                    log::warn!("Received invalid frequencies error from signaling server: {msg}");
+
+                    app.emit::<FrontendError>(
+                        "error",
+                        FrontendError::from(Error::from(SignalingRuntimeError::ServerError(
+                            reason,
+                        ))),
+                    )
+                    .ok();
+                }
             },
             ServerMessage::Disconnected(_) | ServerMessage::LoginFailure(_) => {}
         }
@@ -844,7 +1170,11 @@ impl AppStateInner {
 
     async fn cleanup_signaling(&mut self, app: &AppHandle) {
         self.incoming_call_ids.clear();
+        self.call_output_routes.clear();
         self.outgoing_call_id = None;
+        self.incoming_broadcast = None;
+        self.transmitting = false;
+        self.region_ice_servers = None;
 
         {
             let mut audio_manager = self.audio_manager.write();
@@ -861,6 +1191,9 @@ impl AppStateInner {
         if let Some(call_id) = self.active_call_id().cloned() {
             self.cleanup_call(&call_id).await;
         };
+        if let Some(call_id) = self.broadcast_call_id().cloned() {
+            self.cleanup_call(&call_id).await;
+        }
         let call_ids = self.held_calls.keys().cloned().collect::<Vec<_>>();
         for call_id in call_ids {
             self.cleanup_call(&call_id).await;
@@ -877,3 +1210,57 @@ impl AppStateInner {
         }
     }
 }
+
+/// Whether an incoming call to `target` is answered automatically. Calls are never auto-answered
+/// while another call is active, as accepting them would replace it; they ring normally instead.
+fn should_auto_answer(
+    config: &AutoAnswerConfig,
+    target: &CallTarget,
+    active_call_id: Option<&CallId>,
+) -> bool {
+    active_call_id.is_none() && config.applies_to(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vacs_signaling::protocol::vatsim::StationId;
+
+    fn station_config(station: &str) -> AutoAnswerConfig {
+        AutoAnswerConfig {
+            stations: [StationId::from(station)].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn auto_answers_configured_target_without_active_call() {
+        let config = station_config("LOWW_TWR");
+
+        assert!(should_auto_answer(
+            &config,
+            &CallTarget::Station(StationId::from("LOWW_TWR")),
+            None
+        ));
+        assert!(!should_auto_answer(
+            &config,
+            &CallTarget::Station(StationId::from("LOWW_APP")),
+            None
+        ));
+    }
+
+    #[test]
+    fn does_not_auto_answer_during_active_call() {
+        let config = AutoAnswerConfig {
+            all: true,
+            ..Default::default()
+        };
+        let active_call_id = CallId::new();
+
+        assert!(!should_auto_answer(
+            &config,
+            &CallTarget::Station(StationId::from("LOWW_TWR")),
+            Some(&active_call_id)
+        ));
+    }
+}
@@ -1,20 +1,24 @@
 use crate::app::state::signaling::AppStateSignalingExt;
 use crate::app::state::{AppState, AppStateInner, sealed};
 use crate::audio::manager::SourceType;
-use crate::config::{ENCODED_AUDIO_FRAME_BUFFER_SIZE, ICE_CONFIG_EXPIRY_LEEWAY};
+use crate::config::{ENCODED_AUDIO_FRAME_BUFFER_SIZE, ICE_CONFIG_EXPIRY_LEEWAY, OutputRoute};
 use crate::error::{CallError, Error};
 use anyhow::Context;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 use std::time::UNIX_EPOCH;
 use tauri::async_runtime::JoinHandle;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
+use vacs_audio::{EncodedAudioFrame, FrameCodec};
 use vacs_signaling::protocol::http::webrtc::IceConfig;
 use vacs_signaling::protocol::vatsim::ClientId;
 use vacs_signaling::protocol::ws::shared;
-use vacs_signaling::protocol::ws::shared::{CallErrorReason, CallId};
+use vacs_signaling::protocol::ws::shared::{AudioCodec, CallErrorReason, CallId};
 use vacs_webrtc::error::WebrtcError;
 use vacs_webrtc::{Peer, PeerConnectionState, PeerEvent};
 
@@ -28,6 +32,10 @@ pub struct UnansweredCallGuard {
 pub struct Call {
     pub(super) call_id: CallId,
     pub(super) peer_id: ClientId,
+    output_route: OutputRoute,
+    audio_codec: FrameCodec,
+    /// Whether the call only receives audio, i.e. it is a broadcast received from the peer.
+    receive_only: bool,
     peer: Peer,
 }
 
@@ -35,22 +43,79 @@ impl Debug for Call {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Call")
             .field("peer_id", &self.peer_id)
+            .field("output_route", &self.output_route)
+            .field("audio_codec", &self.audio_codec)
+            .field("receive_only", &self.receive_only)
+            .finish()
+    }
+}
+
+/// A broadcast started by this client, sending audio one-way to a separate peer per recipient.
+pub struct Broadcast {
+    pub(super) call_id: CallId,
+    peers: HashMap<ClientId, Peer>,
+    /// Inputs of the connected peers, each receiving a copy of the captured audio frames.
+    inputs: Arc<Mutex<HashMap<ClientId, mpsc::Sender<EncodedAudioFrame>>>>,
+    input_attached: bool,
+}
+
+impl Debug for Broadcast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Broadcast")
+            .field("call_id", &self.call_id)
+            .field("peers", &self.peers.keys().collect::<Vec<_>>())
+            .field("input_attached", &self.input_attached)
             .finish()
     }
 }
 
 pub trait AppStateWebrtcExt: sealed::Sealed {
+    /// Creates the peer of a call, answering `offer_sdp` if given or creating an offer otherwise.
+    /// The offerer passes the default codec, which is replaced once the answer is accepted.
     async fn init_call(
         &mut self,
         app: AppHandle,
         call_id: CallId,
         peer_id: ClientId,
         offer_sdp: Option<String>,
+        audio_codec: FrameCodec,
     ) -> Result<String, Error>;
-    async fn accept_call_answer(&self, peer_id: &ClientId, answer_sdp: String)
-    -> Result<(), Error>;
-    async fn set_remote_ice_candidate(&self, call_id: &CallId, candidate: String);
+    async fn accept_call_answer(
+        &mut self,
+        call_id: &CallId,
+        peer_id: &ClientId,
+        answer_sdp: String,
+        audio_codec: FrameCodec,
+    ) -> Result<(), Error>;
+    async fn set_remote_ice_candidate(
+        &self,
+        call_id: &CallId,
+        peer_id: &ClientId,
+        candidate: String,
+    );
     async fn cleanup_call(&mut self, call_id: &CallId) -> bool;
+    /// Prepares a broadcast sent by this client. Peers are created once the server confirmed the
+    /// recipients, see [`AppStateWebrtcExt::init_broadcast_peers`].
+    fn start_broadcast(&mut self, call_id: CallId) -> Result<(), Error>;
+    /// Creates a peer for every recipient of the broadcast, returning the offer to send to each.
+    async fn init_broadcast_peers(
+        &mut self,
+        app: AppHandle,
+        call_id: &CallId,
+        recipient_ids: Vec<ClientId>,
+    ) -> Result<Vec<(ClientId, String)>, Error>;
+    /// Closes the peer of a recipient that left the broadcast, returning whether the broadcast
+    /// continues with other recipients. The broadcast is cleaned up once its last recipient left.
+    async fn remove_broadcast_recipient(
+        &mut self,
+        call_id: &CallId,
+        recipient_id: &ClientId,
+    ) -> bool;
+    fn broadcast_call_id(&self) -> Option<&CallId>;
+    /// Records a broadcast received from `broadcaster_id`, answering its offer receive-only.
+    fn set_incoming_broadcast(&mut self, call_id: CallId, broadcaster_id: ClientId);
+    /// Sets the output device the call's audio will be played back on once established.
+    fn set_call_output_route(&mut self, call_id: CallId, route: OutputRoute);
     fn emit_call_error(
         &self,
         app: &AppHandle,
@@ -70,12 +135,18 @@ impl AppStateWebrtcExt for AppStateInner {
         call_id: CallId,
         peer_id: ClientId,
         offer_sdp: Option<String>,
+        audio_codec: FrameCodec,
     ) -> Result<String, Error> {
-        if self.active_call.is_some() {
+        if self.active_call.is_some() || self.broadcast.is_some() {
             return Err(WebrtcError::CallActive.into());
         }
 
-        let (peer, mut events_rx) = Peer::new(self.config.ice.clone())
+        let receive_only = self
+            .incoming_broadcast
+            .as_ref()
+            .is_some_and(|(id, broadcaster_id)| *id == call_id && *broadcaster_id == peer_id);
+
+        let (peer, mut events_rx) = Peer::new(self.current_ice_config())
             .await
             .context("Failed to create WebRTC peer")?;
 
@@ -209,6 +280,9 @@ impl AppStateWebrtcExt for AppStateInner {
         self.active_call = Some(Call {
             call_id,
             peer_id,
+            output_route: self.call_output_routes.remove(&call_id).unwrap_or_default(),
+            audio_codec,
+            receive_only,
             peer,
         });
 
@@ -216,13 +290,26 @@ impl AppStateWebrtcExt for AppStateInner {
     }
 
     async fn accept_call_answer(
-        &self,
+        &mut self,
+        call_id: &CallId,
         peer_id: &ClientId,
         answer_sdp: String,
+        audio_codec: FrameCodec,
     ) -> Result<(), Error> {
-        if let Some(call) = &self.active_call {
+        if let Some(broadcast) = &self.broadcast
+            && broadcast.call_id == *call_id
+        {
+            // Broadcasts are always sent with the default codec, see `init_broadcast_peers`.
+            return match broadcast.peers.get(peer_id) {
+                Some(peer) => Ok(peer.accept_answer(answer_sdp).await?),
+                None => Err(WebrtcError::NoCallActive.into()),
+            };
+        }
+
+        if let Some(call) = &mut self.active_call {
             if call.peer_id == *peer_id {
                 call.peer.accept_answer(answer_sdp).await?;
+                call.audio_codec = audio_codec;
                 return Ok(());
             } else {
                 log::warn!(
@@ -234,11 +321,21 @@ impl AppStateWebrtcExt for AppStateInner {
         Err(WebrtcError::NoCallActive.into())
     }
 
-    async fn set_remote_ice_candidate(&self, call_id: &CallId, candidate: String) {
+    async fn set_remote_ice_candidate(
+        &self,
+        call_id: &CallId,
+        peer_id: &ClientId,
+        candidate: String,
+    ) {
         let res = if let Some(call) = &self.active_call
             && call.call_id == *call_id
         {
             call.peer.add_remote_ice_candidate(candidate).await
+        } else if let Some(broadcast) = &self.broadcast
+            && broadcast.call_id == *call_id
+            && let Some(peer) = broadcast.peers.get(peer_id)
+        {
+            peer.add_remote_ice_candidate(candidate).await
         } else if let Some(call) = self.held_calls.get(call_id) {
             call.peer.add_remote_ice_candidate(candidate).await
         } else {
@@ -255,6 +352,15 @@ impl AppStateWebrtcExt for AppStateInner {
             "Cleaning up call {call_id:?} (active: {:?})",
             self.active_call.as_ref()
         );
+        self.call_output_routes.remove(call_id);
+        if self
+            .incoming_broadcast
+            .as_ref()
+            .is_some_and(|(id, _)| id == call_id)
+        {
+            self.incoming_broadcast = None;
+        }
+
         let res = if let Some(call) = &mut self.active_call
             && call.call_id == *call_id
         {
@@ -271,6 +377,19 @@ impl AppStateWebrtcExt for AppStateInner {
             result
         } else if let Some(mut call) = self.held_calls.remove(call_id) {
             call.peer.close().await
+        } else if let Some(broadcast) = self.broadcast.take_if(|b| b.call_id == *call_id) {
+            if broadcast.input_attached {
+                self.audio_manager.write().detach_input_device();
+                self.keybind_engine.read().await.set_call_active(false);
+            }
+
+            let mut res = Ok(());
+            for (_, mut peer) in broadcast.peers {
+                if let Err(err) = peer.close().await {
+                    res = Err(err);
+                }
+            }
+            res
         } else {
             Err(anyhow::anyhow!("Unknown call {call_id:?}").into())
         };
@@ -283,6 +402,15 @@ impl AppStateWebrtcExt for AppStateInner {
         true
     }
 
+    fn set_call_output_route(&mut self, call_id: CallId, route: OutputRoute) {
+        if route == OutputRoute::Primary {
+            self.call_output_routes.remove(&call_id);
+        } else {
+            log::debug!("Routing call {call_id} to {route:?} output");
+            self.call_output_routes.insert(call_id, route);
+        }
+    }
+
     fn emit_call_error(
         &self,
         app: &AppHandle,
@@ -297,6 +425,88 @@ impl AppStateWebrtcExt for AppStateInner {
         .ok();
     }
 
+    fn start_broadcast(&mut self, call_id: CallId) -> Result<(), Error> {
+        if self.active_call.is_some() || self.broadcast.is_some() {
+            return Err(WebrtcError::CallActive.into());
+        }
+
+        self.broadcast = Some(Broadcast {
+            call_id,
+            peers: HashMap::new(),
+            inputs: Arc::new(Mutex::new(HashMap::new())),
+            input_attached: false,
+        });
+        Ok(())
+    }
+
+    async fn init_broadcast_peers(
+        &mut self,
+        app: AppHandle,
+        call_id: &CallId,
+        recipient_ids: Vec<ClientId>,
+    ) -> Result<Vec<(ClientId, String)>, Error> {
+        if self.broadcast_call_id() != Some(call_id) {
+            return Err(WebrtcError::NoCallActive.into());
+        }
+
+        let mut offers = Vec::with_capacity(recipient_ids.len());
+        for recipient_id in recipient_ids {
+            let (peer, events_rx) = Peer::new(self.current_ice_config())
+                .await
+                .context("Failed to create WebRTC peer")?;
+            let sdp = peer
+                .create_offer()
+                .await
+                .context("Failed to create WebRTC offer")?;
+
+            spawn_broadcast_peer_events(app.clone(), *call_id, recipient_id.clone(), events_rx);
+
+            if let Some(broadcast) = &mut self.broadcast {
+                broadcast.peers.insert(recipient_id.clone(), peer);
+            }
+            offers.push((recipient_id, sdp));
+        }
+
+        Ok(offers)
+    }
+
+    async fn remove_broadcast_recipient(
+        &mut self,
+        call_id: &CallId,
+        recipient_id: &ClientId,
+    ) -> bool {
+        let Some(broadcast) = &mut self.broadcast else {
+            return false;
+        };
+        if broadcast.call_id != *call_id {
+            return false;
+        }
+
+        log::debug!("Removing recipient {recipient_id} from broadcast {call_id}");
+        broadcast.inputs.lock().remove(recipient_id);
+        if let Some(mut peer) = broadcast.peers.remove(recipient_id)
+            && let Err(err) = peer.close().await
+        {
+            log::warn!("Failed to close broadcast peer: {err:?}");
+        }
+
+        if !broadcast.peers.is_empty() {
+            return true;
+        }
+
+        log::debug!("Last recipient left broadcast {call_id}");
+        self.cleanup_call(call_id).await;
+        false
+    }
+
+    fn broadcast_call_id(&self) -> Option<&CallId> {
+        self.broadcast.as_ref().map(|broadcast| &broadcast.call_id)
+    }
+
+    fn set_incoming_broadcast(&mut self, call_id: CallId, broadcaster_id: ClientId) {
+        self.incoming_broadcast = Some((call_id, broadcaster_id));
+    }
+
     fn active_call_id(&self) -> Option<&CallId> {
         self.active_call.as_ref().map(|call| &call.call_id)
     }
@@ -344,15 +554,35 @@ impl AppStateInner {
         if let Some(call) = &mut self.active_call
             && call.peer_id == *peer_id
         {
+            let output_route = call.output_route;
+            let audio_codec = call.audio_codec;
+            let receive_only = call.receive_only;
             let (output_tx, output_rx) = mpsc::channel(ENCODED_AUDIO_FRAME_BUFFER_SIZE);
             let (input_tx, input_rx) = mpsc::channel(ENCODED_AUDIO_FRAME_BUFFER_SIZE);
 
             log::debug!("Starting peer {peer_id} in WebRTC manager");
-            if let Err(err) = call.peer.start(input_rx, output_tx) {
+            if let Err(err) = call.peer.start(input_rx, output_tx, audio_codec).await {
                 log::warn!("Failed to start peer in WebRTC manager: {err:?}");
                 return Err(err.into());
             }
 
+            if receive_only {
+                log::debug!("Attaching received broadcast to audio manager");
+                if let Err(err) = self.audio_manager.write().attach_call_output(
+                    app.clone(),
+                    &self.config.audio,
+                    output_rx,
+                    output_route,
+                ) {
+                    log::warn!("Failed to attach broadcast to audio manager: {err:?}");
+                    return Err(err);
+                }
+
+                log::info!("Successfully received broadcast from peer");
+                app.emit("webrtc:call-connected", call_id).ok();
+                return Ok(());
+            }
+
             let attach_muted = {
                 let keybind_engine = self.keybind_engine.read().await;
                 keybind_engine.set_call_active(true);
@@ -363,9 +593,10 @@ impl AppStateInner {
             let mut audio_manager = self.audio_manager.write();
             log::debug!("Attaching call to audio manager");
             if let Err(err) = audio_manager.attach_call_output(
+                app.clone(),
+                &audio_config,
                 output_rx,
-                audio_config.output_device_volume,
-                audio_config.output_device_volume_amp,
+                output_route,
             ) {
                 log::warn!("Failed to attach call to audio manager: {err:?}");
                 return Err(err);
@@ -376,6 +607,7 @@ impl AppStateInner {
                 app.clone(),
                 &audio_config,
                 input_tx,
+                audio_codec,
                 attach_muted,
             ) {
                 log::warn!("Failed to attach input device to audio manager: {err:?}");
@@ -399,4 +631,183 @@ impl AppStateInner {
         }
         Ok(())
     }
+
+    fn current_ice_config(&self) -> IceConfig {
+        match &self.region_ice_servers {
+            Some(ice_servers) => IceConfig::from(ice_servers.clone()),
+            None => self.config.ice.clone(),
+        }
+    }
+
+    /// Starts sending the broadcast to a connected recipient, attaching the input device once the
+    /// first recipient connected.
+    async fn on_broadcast_peer_connected(
+        &mut self,
+        app: &AppHandle,
+        call_id: &CallId,
+        peer_id: &ClientId,
+    ) -> Result<(), Error> {
+        let Some(broadcast) = self
+            .broadcast
+            .as_mut()
+            .filter(|broadcast| broadcast.call_id == *call_id)
+        else {
+            log::debug!("Broadcast {call_id} is not active, ignoring connected peer {peer_id}");
+            return Ok(());
+        };
+        let Some(peer) = broadcast.peers.get_mut(peer_id) else {
+            log::debug!("Peer {peer_id} is not a recipient of broadcast {call_id}, ignoring");
+            return Ok(());
+        };
+
+        // Recipients don't send any audio, so nothing is ever received on the output.
+        let (output_tx, _) = mpsc::channel(1);
+        let (input_tx, input_rx) = mpsc::channel(ENCODED_AUDIO_FRAME_BUFFER_SIZE);
+
+        log::debug!("Starting broadcast peer {peer_id} in WebRTC manager");
+        if let Err(err) = peer.start(input_rx, output_tx, FrameCodec::default()).await {
+            log::warn!("Failed to start broadcast peer in WebRTC manager: {err:?}");
+            return Err(err.into());
+        }
+        broadcast.inputs.lock().insert(peer_id.clone(), input_tx);
+
+        if broadcast.input_attached {
+            return Ok(());
+        }
+
+        let (input_tx, mut input_rx) =
+            mpsc::channel::<EncodedAudioFrame>(ENCODED_AUDIO_FRAME_BUFFER_SIZE);
+        let inputs = Arc::clone(&broadcast.inputs);
+        tauri::async_runtime::spawn(async move {
+            while let Some(frame) = input_rx.recv().await {
+                for (peer_id, input_tx) in inputs.lock().iter() {
+                    if let Err(err) = input_tx.try_send(frame.clone()) {
+                        log::trace!("Dropping broadcast frame for peer {peer_id}: {err}");
+                    }
+                }
+            }
+
+            log::trace!("Broadcast input task finished");
+        });
+
+        let attach_muted = {
+            let keybind_engine = self.keybind_engine.read().await;
+            keybind_engine.set_call_active(true);
+            keybind_engine.should_attach_input_muted()
+        };
+
+        log::debug!("Attaching input device to audio manager for broadcast");
+        if let Err(err) = self.audio_manager.write().attach_input_device(
+            app.clone(),
+            &self.config.audio,
+            input_tx,
+            FrameCodec::default(),
+            attach_muted,
+        ) {
+            log::warn!("Failed to attach input device to audio manager: {err:?}");
+            return Err(err);
+        }
+        if let Some(broadcast) = &mut self.broadcast {
+            broadcast.input_attached = true;
+        }
+
+        log::info!("Successfully started broadcast");
+        app.emit("webrtc:call-connected", call_id).ok();
+        Ok(())
+    }
+}
+
+/// Offered codecs of broadcasts. All recipients receive the same captured frames, so broadcasts
+/// are always sent with the default codec.
+pub fn broadcast_audio_codecs() -> Vec<AudioCodec> {
+    vec![AudioCodec::opus()]
+}
+
+/// Handles the events of the peer of a single broadcast recipient. Unlike regular calls, a
+/// failing recipient only removes its own peer, the broadcast continues for all others.
+fn spawn_broadcast_peer_events(
+    app: AppHandle,
+    call_id: CallId,
+    peer_id: ClientId,
+    mut events_rx: tokio::sync::broadcast::Receiver<PeerEvent>,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events_rx.recv().await {
+                Ok(PeerEvent::ConnectionState(state)) => match state {
+                    PeerConnectionState::Connected => {
+                        log::info!("Connected to broadcast peer {peer_id}");
+
+                        let app_state = app.state::<AppState>();
+                        let mut state = app_state.lock().await;
+                        if let Err(err) = state
+                            .on_broadcast_peer_connected(&app, &call_id, &peer_id)
+                            .await
+                        {
+                            // Ends the broadcast for all recipients, as it cannot be sent.
+                            let reason: CallErrorReason = err.into();
+                            if let Some(own_client_id) = state.client_id.as_ref().cloned()
+                                && let Err(err) = state
+                                    .send_signaling_message(shared::CallEnd {
+                                        call_id,
+                                        ending_client_id: own_client_id,
+                                    })
+                                    .await
+                            {
+                                log::warn!("Failed to send call message: {err:?}");
+                            }
+                            state.cleanup_call(&call_id).await;
+                            state.emit_call_error(&app, call_id, true, reason);
+                        }
+                    }
+                    PeerConnectionState::Failed | PeerConnectionState::Closed => {
+                        log::info!("Connection to broadcast peer {peer_id} ended: {state:?}");
+
+                        let app_state = app.state::<AppState>();
+                        let mut state = app_state.lock().await;
+                        if state.broadcast_call_id() == Some(&call_id)
+                            && !state.remove_broadcast_recipient(&call_id, &peer_id).await
+                        {
+                            app.emit("signaling:call-end", &call_id).ok();
+                        }
+                    }
+                    state => {
+                        log::trace!("Received broadcast peer connection state: {state:?}");
+                    }
+                },
+                Ok(PeerEvent::IceCandidate(candidate)) => {
+                    let app_state = app.state::<AppState>();
+                    let mut state = app_state.lock().await;
+
+                    let Some(own_client_id) = state.client_id.as_ref().cloned() else {
+                        log::warn!("Cannot send ICE candidate without own client ID");
+                        return;
+                    };
+
+                    if let Err(err) = state
+                        .send_signaling_message(shared::WebrtcIceCandidate {
+                            call_id,
+                            from_client_id: own_client_id,
+                            to_client_id: peer_id.clone(),
+                            candidate,
+                        })
+                        .await
+                    {
+                        log::warn!("Failed to send ICE candidate: {err:?}");
+                    }
+                }
+                Ok(PeerEvent::Error(err)) => {
+                    log::warn!("Received error broadcast peer event: {err}");
+                }
+                Err(err) => {
+                    log::warn!("Failed to receive broadcast peer event: {err:?}");
+                    if err == RecvError::Closed {
+                        break;
+                    }
+                }
+            }
+        }
+
+        log::trace!("WebRTC broadcast peer events task finished");
+    });
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use vacs_audio::device::DeviceReadiness;
 
 pub(crate) mod commands;
 pub(crate) mod manager;
@@ -10,6 +11,14 @@ pub struct AudioHosts {
     all: Vec<String>,
 }
 
+/// Readiness of the configured input and output devices, resolved without starting streams.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioReadiness {
+    input: DeviceReadiness,
+    output: DeviceReadiness,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioDevices {
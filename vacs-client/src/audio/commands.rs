@@ -1,7 +1,7 @@
 use crate::app::state::AppState;
 use crate::app::state::webrtc::AppStateWebrtcExt;
 use crate::audio::manager::{AudioManagerHandle, SourceType};
-use crate::audio::{AudioDevices, AudioHosts, AudioVolumes, VolumeType};
+use crate::audio::{AudioDevices, AudioHosts, AudioReadiness, AudioVolumes, VolumeType};
 use crate::config::{AUDIO_SETTINGS_FILE_NAME, AudioConfig, Persistable, PersistedAudioConfig};
 use crate::error::Error;
 use crate::keybinds::engine::KeybindEngineHandle;
@@ -92,6 +92,40 @@ pub async fn audio_get_devices(
     )
 }
 
+/// Checks whether the configured input and output devices can be opened, without starting any
+/// streams.
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn audio_check_readiness(
+    app_state: State<'_, AppState>,
+) -> Result<AudioReadiness, Error> {
+    log::debug!("Checking audio device readiness");
+
+    let audio_config = app_state.lock().await.config.audio.clone();
+    let host = audio_config.host_name.as_deref();
+    let readiness = AudioReadiness {
+        input: DeviceSelector::check_readiness(
+            DeviceType::Input,
+            host,
+            audio_config.input_device_name.as_deref(),
+            audio_config.input_channel_map.as_ref(),
+        ),
+        output: DeviceSelector::check_readiness(
+            DeviceType::Output,
+            host,
+            audio_config.output_device_name.as_deref(),
+            audio_config.output_channel_map.as_ref(),
+        ),
+    };
+    log::trace!(
+        "Audio device readiness: input {:?}, output {:?}",
+        readiness.input,
+        readiness.output
+    );
+
+    Ok(readiness)
+}
+
 #[tauri::command]
 #[vacs_macros::log_err]
 pub async fn audio_set_device(
@@ -104,21 +138,15 @@ pub async fn audio_set_device(
     let mut state = app_state.lock().await;
     let mut audio_manager = audio_manager.write();
 
-    if state.active_call_id().is_some() {
-        return Err(AudioError::Other(anyhow::anyhow!(
-            "Cannot set audio device while call is active"
-        ))
-        .into());
-    }
-
-    let reattach_input_level_meter =
-        if audio_manager.is_input_device_attached() && matches!(device_type, DeviceType::Input) {
-            log::trace!("Detaching input level meter before switching input device");
-            audio_manager.detach_input_device();
-            true
-        } else {
-            false
-        };
+    let reattach_input_level_meter = if audio_manager.is_input_level_meter_attached()
+        && matches!(device_type, DeviceType::Input)
+    {
+        log::trace!("Detaching input level meter before switching input device");
+        audio_manager.detach_input_device();
+        true
+    } else {
+        false
+    };
 
     log::info!(
         "Setting audio device (name: {:?}, type: {:?})",
@@ -128,18 +156,16 @@ pub async fn audio_set_device(
 
     let device_name = Some(device_name).filter(|x| !x.is_empty());
     let (persisted_audio_config, audio_devices): (PersistedAudioConfig, AudioDevices) = {
+        let mut audio_config = state.config.audio.clone();
         match device_type {
-            DeviceType::Input => state.config.audio.input_device_name = device_name,
-            DeviceType::Output => {
-                let mut audio_config = state.config.audio.clone();
-                audio_config.output_device_name = device_name;
-
-                audio_manager.switch_output_device(app.clone(), &audio_config, false)?;
-
-                state.config.audio = audio_config;
-            }
+            DeviceType::Input => audio_config.input_device_name = device_name,
+            DeviceType::Output => audio_config.output_device_name = device_name,
         }
 
+        // Switching keeps an active call running on the new device.
+        audio_manager.switch_device(app.clone(), &audio_config, device_type)?;
+        state.config.audio = audio_config;
+
         let audio_devices = get_audio_devices(
             device_type,
             &state.config.audio,
@@ -313,6 +339,46 @@ pub async fn audio_set_radio_prio(
     Ok(())
 }
 
+/// Adjusts the jitter buffer depth of the currently active call at runtime, e.g. to buffer more
+/// audio for a single peer with a poor connection. Does not change the persisted default.
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn audio_set_jitter_buffer_depth(
+    audio_manager: State<'_, AudioManagerHandle>,
+    depth_ms: u64,
+) -> Result<(), Error> {
+    log::trace!("Setting jitter buffer depth of active call to {depth_ms} ms");
+    audio_manager
+        .read()
+        .set_call_jitter_buffer_depth(Duration::from_millis(depth_ms));
+    Ok(())
+}
+
+/// Toggles suppressing call audio while the microphone is silent, see
+/// [`AudioConfig::suppress_idle_audio`]. Applies to the active call immediately.
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn audio_set_suppress_idle_audio(
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    audio_manager: State<'_, AudioManagerHandle>,
+    enabled: bool,
+) -> Result<(), Error> {
+    log::trace!("Setting idle audio suppression to {enabled}");
+    let mut state = app_state.lock().await;
+    audio_manager.read().set_suppress_idle_audio(enabled);
+    state.config.audio.suppress_idle_audio = enabled;
+
+    let persisted_audio_config: PersistedAudioConfig = state.config.audio.clone().into();
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .expect("Cannot get config directory");
+    persisted_audio_config.persist(&config_dir, AUDIO_SETTINGS_FILE_NAME)?;
+
+    Ok(())
+}
+
 fn get_audio_devices(
     device_type: DeviceType,
     audio_config: &AudioConfig,
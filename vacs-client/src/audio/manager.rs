@@ -1,29 +1,36 @@
 use crate::app::state::AppState;
 use crate::app::state::signaling::AppStateSignalingExt;
 use crate::app::state::webrtc::AppStateWebrtcExt;
-use crate::config::AudioConfig;
+use crate::config::{AudioConfig, OutputRoute};
 use crate::error::{Error, FrontendError};
 use parking_lot::RwLock;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc;
-use vacs_audio::EncodedAudioFrame;
-use vacs_audio::device::{DeviceSelector, DeviceType};
+use vacs_audio::device::{DeviceSelector, DeviceType, StreamDevice};
 use vacs_audio::error::AudioError;
 use vacs_audio::sources::AudioSourceId;
-use vacs_audio::sources::opus::OpusSource;
+use vacs_audio::sources::opus::{OpusSource, SharedFrameReceiver};
 use vacs_audio::sources::waveform::{Waveform, WaveformSource, WaveformTone};
 use vacs_audio::stream::capture::{CaptureStream, InputLevel};
 use vacs_audio::stream::playback::PlaybackStream;
+use vacs_audio::stream::router::{OutputSourceKind, PlaybackRouter, RoutedSource};
+use vacs_audio::{EncodedAudioFrame, FrameCodec};
 use vacs_signaling::protocol::ws::shared;
 use vacs_signaling::protocol::ws::shared::CallErrorReason;
 
 const AUDIO_STREAM_ERROR_CHANNEL_SIZE: usize = 32;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+const PRIMARY_OUTPUT: &str = "primary";
+const NOTIFICATION_OUTPUT: &str = "notification";
+/// Name of the secondary call output in [`RoutedSource`]s. The stream is managed separately, as it
+/// has to survive switching the primary output device during a call.
+const SECONDARY_OUTPUT: &str = "secondary";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SourceType {
     Opus,
     Ring,
@@ -36,6 +43,16 @@ pub enum SourceType {
 }
 
 impl SourceType {
+    /// Chimes and clicks are notifications, everything else is played back alongside calls.
+    fn kind(&self) -> OutputSourceKind {
+        match self {
+            SourceType::Ring | SourceType::PriorityRing | SourceType::Click => {
+                OutputSourceKind::Notification
+            }
+            _ => OutputSourceKind::Call,
+        }
+    }
+
     fn into_waveform_source(
         self,
         sample_rate: f32,
@@ -143,9 +160,13 @@ impl SourceType {
 }
 
 pub struct AudioManager {
-    output: PlaybackStream,
+    /// Primary output stream, alongside the notification output stream if configured.
+    output: PlaybackRouter,
+    /// Output stream of the secondary device, only opened while a call is routed to it.
+    secondary_output: Option<PlaybackStream>,
     input: Option<CaptureStream>,
-    source_ids: HashMap<SourceType, AudioSourceId>,
+    source_ids: HashMap<SourceType, RoutedSource>,
+    call_rx: Option<SharedFrameReceiver>,
 }
 
 pub type AudioManagerHandle = Arc<RwLock<AudioManager>>;
@@ -156,13 +177,15 @@ impl AudioManager {
 
         Ok(Self {
             output,
+            secondary_output: None,
             input: None,
             source_ids,
+            call_rx: None,
         })
     }
 
     pub fn output_device_name(&self) -> String {
-        self.output.device_name()
+        self.primary_output().device_name()
     }
 
     pub fn switch_output_device(
@@ -177,34 +200,134 @@ impl AudioManager {
         Ok(())
     }
 
+    /// Switches the input or output device to the one selected in `audio_config` without dropping
+    /// an active call.
+    ///
+    /// A new stream is started on the same call frame channels, resulting in a brief gap in call
+    /// output audio. Input capture keeps running on the old device if the new one fails to start.
+    /// Input level meters are not switched, they have to be re-attached by the caller instead.
+    pub fn switch_device(
+        &mut self,
+        app: AppHandle,
+        audio_config: &AudioConfig,
+        device_type: DeviceType,
+    ) -> Result<(), Error> {
+        let started = Instant::now();
+        match device_type {
+            DeviceType::Output => {
+                let secondary_call_source = self
+                    .secondary_output
+                    .as_ref()
+                    .and(self.source_ids.get(&SourceType::Opus).cloned());
+                self.switch_output_device(app, audio_config, false)?;
+
+                if let Some(source) = secondary_call_source {
+                    // Calls routed to the secondary device keep playing on their own stream
+                    self.source_ids.insert(SourceType::Opus, source);
+                } else if let Some(call_rx) = self.call_rx.clone() {
+                    let source = OpusSource::new(
+                        call_rx,
+                        self.primary_output().resampler()?,
+                        self.primary_output().channels(),
+                        audio_config.output_device_volume,
+                        audio_config.output_device_volume_amp,
+                        Duration::from_millis(audio_config.jitter_buffer_depth_ms),
+                    )?;
+                    self.source_ids.insert(
+                        SourceType::Opus,
+                        self.output
+                            .add_source(OutputSourceKind::Call, Box::new(source)),
+                    );
+                    log::info!(
+                        "Re-attached call output after switching output device, gap: {:?}",
+                        started.elapsed()
+                    );
+                }
+            }
+            DeviceType::Input => {
+                let Some(input) = self.input.as_mut().filter(|input| !input.is_level_meter())
+                else {
+                    log::debug!("No input capture attached, skipping input device switch");
+                    return Ok(());
+                };
+
+                // The current capture keeps running if the new device fails to start.
+                let device = Self::open_input_device(&app, audio_config)?;
+                let error_tx = Self::spawn_call_error_handler(app);
+                input.switch_device(
+                    device,
+                    audio_config.input_device_volume,
+                    audio_config.input_device_volume_amp,
+                    audio_config.input_pre_amp_db,
+                    error_tx,
+                )?;
+                log::info!(
+                    "Switched input device during call, gap: {:?}",
+                    started.elapsed()
+                );
+            }
+        }
+        Ok(())
+    }
+
     pub fn attach_input_device(
         &mut self,
         app: AppHandle,
         audio_config: &AudioConfig,
         tx: mpsc::Sender<EncodedAudioFrame>,
+        codec: FrameCodec,
         muted: bool,
     ) -> Result<(), Error> {
+        let device = Self::open_input_device(&app, audio_config)?;
+        let error_tx = Self::spawn_call_error_handler(app.clone());
+
+        let capture = CaptureStream::start(
+            device,
+            tx,
+            codec,
+            audio_config.input_device_volume,
+            audio_config.input_device_volume_amp,
+            audio_config.input_pre_amp_db,
+            error_tx,
+            muted,
+            audio_config.suppress_idle_audio,
+        )?;
+
+        app.emit("audio:stop-input-level-meter", Value::Null).ok();
+
+        self.input = Some(capture);
+        Ok(())
+    }
+
+    fn open_input_device(
+        app: &AppHandle,
+        audio_config: &AudioConfig,
+    ) -> Result<StreamDevice, Error> {
         let (device, is_fallback) = DeviceSelector::open(
             DeviceType::Input,
             audio_config.host_name.as_deref(),
             audio_config.input_device_name.as_deref(),
         )?;
+        let device = device.with_channel_map(audio_config.input_channel_map.clone())?;
         if is_fallback {
             app.emit::<FrontendError>("error", FrontendError::from(Error::AudioDevice(Box::from(AudioError::Other(
                 anyhow::anyhow!("Selected audio input device is not available, falling back to next best option. End your call to check your audio settings.")
             )))).non_critical()).ok();
         }
+        Ok(device)
+    }
 
+    /// Spawns a handler ending the active call on errors of streams only used during calls.
+    fn spawn_call_error_handler(app: AppHandle) -> mpsc::Sender<AudioError> {
         let (error_tx, mut error_rx) = mpsc::channel(AUDIO_STREAM_ERROR_CHANNEL_SIZE);
 
-        let app_clone = app.clone();
         tauri::async_runtime::spawn(async move {
             while let Some(err) = error_rx.recv().await {
                 let state = app.state::<AppState>();
                 let mut state = state.lock().await;
 
                 if let Some(call_id) = state.active_call_id().cloned() {
-                    log::debug!("Ending active call {call_id} due to capture stream error");
+                    log::debug!("Ending active call {call_id} due to call audio stream error");
 
                     state.cleanup_call(&call_id).await;
                     if let Err(err) = state
@@ -228,24 +351,10 @@ impl AudioManager {
                 app.emit::<FrontendError>("error", Error::from(err).into())
                     .ok();
             }
-            log::debug!("Playback capture error receiver closed");
+            log::debug!("Call audio stream error receiver closed");
         });
 
-        let capture = CaptureStream::start(
-            device,
-            tx,
-            audio_config.input_device_volume,
-            audio_config.input_device_volume_amp,
-            error_tx,
-            muted,
-        )?;
-
-        app_clone
-            .emit("audio:stop-input-level-meter", Value::Null)
-            .ok();
-
-        self.input = Some(capture);
-        Ok(())
+        error_tx
     }
 
     pub fn attach_input_level_meter(
@@ -259,6 +368,7 @@ impl AudioManager {
             audio_config.host_name.as_deref(),
             audio_config.input_device_name.as_deref(),
         )?;
+        let device = device.with_channel_map(audio_config.input_channel_map.clone())?;
 
         let (error_tx, mut error_rx) = mpsc::channel(AUDIO_STREAM_ERROR_CHANNEL_SIZE);
 
@@ -280,6 +390,7 @@ impl AudioManager {
             emit,
             audio_config.input_device_volume,
             audio_config.input_device_volume_amp,
+            audio_config.input_pre_amp_db,
             error_tx,
         )?);
         Ok(())
@@ -302,34 +413,36 @@ impl AudioManager {
     }
 
     pub fn start(&self, source_type: SourceType) {
-        self.output
-            .start_audio_source(self.source_ids[&source_type]);
+        if let Some((output, source_id)) = self.source(source_type) {
+            output.start_audio_source(source_id);
+        }
     }
 
     pub fn restart(&self, source_type: SourceType) {
-        self.output
-            .restart_audio_source(self.source_ids[&source_type]);
+        if let Some((output, source_id)) = self.source(source_type) {
+            output.restart_audio_source(source_id);
+        }
     }
 
     pub fn stop(&self, source_type: SourceType) {
-        self.output.stop_audio_source(self.source_ids[&source_type]);
+        if let Some((output, source_id)) = self.source(source_type) {
+            output.stop_audio_source(source_id);
+        }
     }
 
     pub fn set_output_volume(&self, source_type: SourceType, volume: f32) {
-        if !self.source_ids.contains_key(&source_type) {
+        let Some((output, source_id)) = self.source(source_type) else {
             log::trace!(
                 "Tried to set output volume {volume} for missing audio source {source_type:?}, skipping"
             );
             return;
-        }
+        };
 
-        self.output
-            .set_volume(self.source_ids[&source_type], volume);
+        output.set_volume(source_id, volume);
 
         match source_type {
             SourceType::Ring | SourceType::Click | SourceType::RingbackOneshot => {
-                self.output
-                    .restart_audio_source(self.source_ids[&source_type]);
+                output.restart_audio_source(source_id);
             }
             _ => {}
         }
@@ -347,11 +460,20 @@ impl AudioManager {
         }
     }
 
+    pub fn set_suppress_idle_audio(&self, suppress: bool) {
+        if let Some(input) = self.input.as_ref().filter(|input| !input.is_level_meter()) {
+            input.set_suppress_silence(suppress);
+        }
+    }
+
+    /// Attaches the call audio to the output device selected by `route`. Calls routed to the
+    /// secondary output device fall back to the primary one if the device cannot be opened.
     pub fn attach_call_output(
         &mut self,
+        app: AppHandle,
+        audio_config: &AudioConfig,
         webrtc_rx: mpsc::Receiver<EncodedAudioFrame>,
-        volume: f32,
-        amp: f32,
+        route: OutputRoute,
     ) -> Result<(), Error> {
         if self.source_ids.contains_key(&SourceType::Opus) {
             log::warn!("Tried to attach call but a call was already attached");
@@ -361,51 +483,203 @@ impl AudioManager {
             .into());
         }
 
-        self.source_ids.insert(
-            SourceType::Opus,
-            self.output.add_audio_source(Box::new(OpusSource::new(
-                webrtc_rx,
-                self.output.resampler()?,
-                self.output.channels(),
-                volume,
-                amp,
-            )?)),
-        );
-        log::info!("Attached call");
+        if route == OutputRoute::Secondary {
+            match Self::open_secondary_output(app, audio_config) {
+                Ok(output) => self.secondary_output = Some(output),
+                Err(err) => {
+                    log::warn!(
+                        "Failed to open secondary output device, playing call on primary output device: {err:?}"
+                    );
+                }
+            }
+        }
+
+        let call_rx: SharedFrameReceiver = Arc::new(tokio::sync::Mutex::new(webrtc_rx));
+        let output = self.call_output();
+        let source = Box::new(OpusSource::new(
+            call_rx.clone(),
+            output.resampler()?,
+            output.channels(),
+            audio_config.output_device_volume,
+            audio_config.output_device_volume_amp,
+            Duration::from_millis(audio_config.jitter_buffer_depth_ms),
+        )?);
+        log::info!("Attached call to output device {}", output.device_name());
+        let source = match &self.secondary_output {
+            Some(secondary_output) => RoutedSource {
+                output: SECONDARY_OUTPUT.to_string(),
+                id: secondary_output.add_audio_source(source),
+            },
+            None => self.output.add_source(OutputSourceKind::Call, source),
+        };
+        self.source_ids.insert(SourceType::Opus, source);
+        self.call_rx = Some(call_rx);
 
         Ok(())
     }
 
+    pub fn set_call_jitter_buffer_depth(&self, depth: Duration) {
+        match self.source(SourceType::Opus) {
+            Some((output, source_id)) => output.set_jitter_buffer_depth(source_id, depth),
+            None => {
+                log::trace!("Tried to set jitter buffer depth without an attached call, skipping")
+            }
+        }
+    }
+
     pub fn detach_call_output(&mut self) {
-        if let Some(source_id) = self.source_ids.remove(&SourceType::Opus) {
-            self.output.remove_audio_source(source_id);
+        self.call_rx = None;
+        if let Some(source) = self.source_ids.remove(&SourceType::Opus) {
+            match &self.secondary_output {
+                Some(secondary_output) if source.output == SECONDARY_OUTPUT => {
+                    secondary_output.remove_audio_source(source.id)
+                }
+                _ => self.output.remove_source(&source),
+            }
             log::info!("Detached call output");
         } else {
             log::debug!("Tried to detach call output but no call was attached");
         }
+        self.secondary_output = None;
+    }
+
+    fn primary_output(&self) -> &PlaybackStream {
+        self.output.routed_output(OutputSourceKind::Call)
+    }
+
+    /// Output stream the call audio is played back on.
+    fn call_output(&self) -> &PlaybackStream {
+        self.secondary_output
+            .as_ref()
+            .unwrap_or_else(|| self.primary_output())
+    }
+
+    /// Output stream and ID of the audio source of `source_type`, if it is attached.
+    fn source(&self, source_type: SourceType) -> Option<(&PlaybackStream, AudioSourceId)> {
+        let source = self.source_ids.get(&source_type)?;
+        let output = if source.output == SECONDARY_OUTPUT {
+            self.secondary_output.as_ref()
+        } else {
+            self.output.source_output(source)
+        }?;
+        Some((output, source.id))
+    }
+
+    /// Opens the output stream chimes and clicks are played back on, if a separate notification
+    /// output device is configured. Returns `None` if it cannot be opened, in which case they are
+    /// played back on the primary output device.
+    fn open_notification_output(
+        app: &AppHandle,
+        audio_config: &AudioConfig,
+    ) -> Option<PlaybackStream> {
+        let device_name = audio_config.notification_output_device_name.as_deref()?;
+        let device = match DeviceSelector::open(
+            DeviceType::Output,
+            audio_config.host_name.as_deref(),
+            Some(device_name),
+        ) {
+            Ok((device, false)) => device,
+            Ok((_, true)) => {
+                log::warn!(
+                    "Notification output device {device_name} is not available, playing notifications on primary output device"
+                );
+                return None;
+            }
+            Err(err) => {
+                log::warn!(
+                    "Failed to open notification output device, playing notifications on primary output device: {err:?}"
+                );
+                return None;
+            }
+        };
+
+        // Missing notifications don't affect calls, so errors are only reported.
+        let (error_tx, mut error_rx) = mpsc::channel(AUDIO_STREAM_ERROR_CHANNEL_SIZE);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Some(err) = error_rx.recv().await {
+                log::warn!("Notification output stream failed: {err:?}");
+                app.emit::<FrontendError>(
+                    "error",
+                    FrontendError::from(Error::from(err)).non_critical(),
+                )
+                .ok();
+            }
+            log::debug!("Notification playback stream error receiver closed");
+        });
+
+        match PlaybackStream::start(device, audio_config.output_limiter_lookahead(), error_tx) {
+            Ok(output) => Some(output),
+            Err(err) => {
+                log::warn!(
+                    "Failed to start notification output stream, playing notifications on primary output device: {err:?}"
+                );
+                None
+            }
+        }
+    }
+
+    fn open_secondary_output(
+        app: AppHandle,
+        audio_config: &AudioConfig,
+    ) -> Result<PlaybackStream, Error> {
+        let routing = &audio_config.call_output_routing;
+        let (device, is_fallback) = DeviceSelector::open(
+            DeviceType::Output,
+            audio_config.host_name.as_deref(),
+            routing.device_name.as_deref(),
+        )?;
+        if is_fallback {
+            return Err(AudioError::Other(anyhow::anyhow!(
+                "Secondary output device is not available"
+            ))
+            .into());
+        }
+        let device = device.with_channel_map(routing.channel_map.clone())?;
+
+        let error_tx = Self::spawn_call_error_handler(app);
+        Ok(PlaybackStream::start(
+            device,
+            audio_config.output_limiter_lookahead(),
+            error_tx,
+        )?)
     }
 
     fn create_playback_stream(
         app: AppHandle,
         audio_config: &AudioConfig,
         restarting: bool,
-    ) -> Result<(PlaybackStream, HashMap<SourceType, AudioSourceId>), Error> {
+    ) -> Result<(PlaybackRouter, HashMap<SourceType, RoutedSource>), Error> {
         let (output_device, is_fallback) = DeviceSelector::open(
             DeviceType::Output,
             audio_config.host_name.as_deref(),
             audio_config.output_device_name.as_deref(),
         )?;
+        let output_device =
+            output_device.with_channel_map(audio_config.output_channel_map.clone())?;
         if is_fallback {
             app.emit::<FrontendError>("error", FrontendError::from(Error::AudioDevice(Box::from(AudioError::Other(
                 anyhow::anyhow!("Selected audio output device is not available, falling back to next best option. Check your audio settings.")
             )))).non_critical()).ok();
         }
 
-        let sample_rate = output_device.sample_rate() as f32;
-        let channels = output_device.channels() as usize;
-
         let (error_tx, mut error_rx) = mpsc::channel(AUDIO_STREAM_ERROR_CHANNEL_SIZE);
-        let output = PlaybackStream::start(output_device, error_tx)?;
+        let mut output = PlaybackRouter::new(
+            PRIMARY_OUTPUT,
+            PlaybackStream::start(
+                output_device,
+                audio_config.output_limiter_lookahead(),
+                error_tx,
+            )?,
+        );
+        if let Some(notification_output) = Self::open_notification_output(&app, audio_config) {
+            log::info!(
+                "Playing notifications on output device {}",
+                notification_output.device_name()
+            );
+            output.add_output(NOTIFICATION_OUTPUT, notification_output);
+            output.set_route(OutputSourceKind::Notification, NOTIFICATION_OUTPUT)?;
+        }
 
         let audio_config_clone = audio_config.clone();
         tauri::async_runtime::spawn(async move {
@@ -473,69 +747,27 @@ impl AudioManager {
         });
 
         let mut source_ids = HashMap::new();
-        source_ids.insert(
-            SourceType::Ring,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::Ring,
-                sample_rate,
-                channels,
-                audio_config.chime_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::PriorityRing,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::PriorityRing,
-                sample_rate,
-                channels,
-                audio_config.chime_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::Ringback,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::Ringback,
-                sample_rate,
-                channels,
-                audio_config.output_device_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::RingbackOneshot,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
+        for (source_type, volume) in [
+            (SourceType::Ring, audio_config.chime_volume),
+            (SourceType::PriorityRing, audio_config.chime_volume),
+            (SourceType::Ringback, audio_config.output_device_volume),
+            (
                 SourceType::RingbackOneshot,
-                sample_rate,
-                channels,
-                audio_config.output_device_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::Click,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::Click,
-                sample_rate,
-                channels,
-                audio_config.click_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::CallStart,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::CallStart,
-                sample_rate,
-                channels,
                 audio_config.output_device_volume,
-            ))),
-        );
-        source_ids.insert(
-            SourceType::CallEnd,
-            output.add_audio_source(Box::new(SourceType::into_waveform_source(
-                SourceType::CallEnd,
-                sample_rate,
-                channels,
-                audio_config.output_device_volume,
-            ))),
-        );
+            ),
+            (SourceType::Click, audio_config.click_volume),
+            (SourceType::CallStart, audio_config.output_device_volume),
+            (SourceType::CallEnd, audio_config.output_device_volume),
+        ] {
+            let kind = source_type.kind();
+            let target = output.routed_output(kind);
+            let source = source_type.into_waveform_source(
+                target.sample_rate() as f32,
+                target.channels() as usize,
+                volume,
+            );
+            source_ids.insert(source_type, output.add_source(kind, Box::new(source)));
+        }
 
         Ok((output, source_ids))
     }
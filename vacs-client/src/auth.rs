@@ -1,13 +1,16 @@
 pub(crate) mod commands;
+pub(crate) mod retry;
 
 use crate::app::state::AppState;
 use crate::app::state::http::HttpState;
 use crate::app::state::signaling::AppStateSignalingExt;
+use crate::auth::retry::AuthCircuitBreaker;
 use crate::config::BackendEndpoint;
 use crate::error::Error;
 use anyhow::Context;
 use tauri::{AppHandle, Emitter, Manager};
 use url::Url;
+use vacs_signaling::auth::AuthGrant;
 use vacs_signaling::protocol::http::auth::{AuthExchangeToken, UserInfo};
 
 #[vacs_macros::log_err]
@@ -28,19 +31,24 @@ pub async fn handle_auth_callback(app: &AppHandle, url: &str) -> Result<(), Erro
     let code = code.context("Auth callback URL does not contain code")?;
     let state = state.context("Auth callback URL does not contain code")?;
 
+    // The code can only be exchanged once, so the exchange is never retried.
+    let http_state = app.state::<HttpState>();
     let cid = app
-        .state::<HttpState>()
-        .http_post::<UserInfo, AuthExchangeToken>(
-            BackendEndpoint::ExchangeCode,
-            None,
-            Some(AuthExchangeToken {
-                code: code.to_string(),
-                state: state.to_string(),
-            }),
-        )
+        .state::<AuthCircuitBreaker>()
+        .call_once(|| {
+            http_state.http_post::<UserInfo, AuthExchangeToken>(
+                BackendEndpoint::ExchangeCode,
+                None,
+                Some(AuthExchangeToken {
+                    code: code.to_string().into(),
+                    state: state.to_string(),
+                }),
+            )
+        })
         .await?
         .cid;
 
+    app.state::<AuthGrant>().record();
     app.state::<AppState>()
         .lock()
         .await
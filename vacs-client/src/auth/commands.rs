@@ -1,18 +1,23 @@
 use crate::app::state::AppState;
 use crate::app::state::http::HttpState;
 use crate::app::state::signaling::AppStateSignalingExt;
+use crate::auth::retry::AuthCircuitBreaker;
 use crate::config::BackendEndpoint;
 use crate::error::{Error, HandleUnauthorizedExt};
 use anyhow::Context;
 use serde_json::Value;
 use tauri::{AppHandle, Emitter, Manager, State};
+use vacs_signaling::auth::AuthGrant;
 use vacs_signaling::protocol::http::auth::{InitVatsimLogin, UserInfo};
 
 #[tauri::command]
 #[vacs_macros::log_err]
-pub async fn auth_open_oauth_url(http_state: State<'_, HttpState>) -> Result<(), Error> {
-    let auth_url = http_state
-        .http_get::<InitVatsimLogin>(BackendEndpoint::InitAuth, None)
+pub async fn auth_open_oauth_url(
+    http_state: State<'_, HttpState>,
+    circuit_breaker: State<'_, AuthCircuitBreaker>,
+) -> Result<(), Error> {
+    let auth_url = circuit_breaker
+        .call(|| http_state.http_get::<InitVatsimLogin>(BackendEndpoint::InitAuth, None))
         .await?
         .url;
 
@@ -39,6 +44,8 @@ pub async fn auth_check_session(
         Ok(user_info) => {
             log::info!("Authenticated as CID {}", user_info.cid);
 
+            app.state::<AuthGrant>().record_if_unset();
+
             app.state::<AppState>()
                 .lock()
                 .await
@@ -87,6 +94,7 @@ pub async fn auth_logout(
         .clear_cookie_store()
         .context("Failed to clear cookie store")?;
 
+    app.state::<AuthGrant>().clear();
     app.state::<AppState>().lock().await.set_client_id(None);
 
     log::info!("Successfully logged out");
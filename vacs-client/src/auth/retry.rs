@@ -0,0 +1,259 @@
+use crate::error::Error;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Maximum number of attempts made for a single auth request before giving up.
+const MAX_ATTEMPTS: u32 = 3;
+/// Base backoff between attempts, doubled after every failed attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+/// Number of consecutive failed auth requests after which the circuit breaker opens.
+const FAILURE_THRESHOLD: u32 = 3;
+/// Time the circuit breaker stays open before requests are attempted again.
+const OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Guards requests to the auth endpoints backed by VATSIM Connect.
+///
+/// Idempotent requests are retried with backoff on transient network or server errors. After
+/// [`FAILURE_THRESHOLD`] consecutive failed requests, the breaker opens and rejects requests
+/// without contacting the server for [`OPEN_DURATION`], avoiding hammering the server during an
+/// outage.
+#[derive(Debug, Default)]
+pub struct AuthCircuitBreaker {
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl AuthCircuitBreaker {
+    /// Runs the idempotent `request`, retrying transient failures. Fails fast with
+    /// [`Error::Network`] while the breaker is open.
+    pub async fn call<T, F, Fut>(&self, request: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        self.run(request, MAX_ATTEMPTS).await
+    }
+
+    /// Runs `request` exactly once, e.g. for exchanging a single-use OAuth code that is consumed
+    /// by the first attempt reaching the server. Failures still count towards opening the breaker.
+    pub async fn call_once<T, Fut>(&self, request: impl FnOnce() -> Fut) -> Result<T, Error>
+    where
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut request = Some(request);
+        self.run(
+            || request.take().expect("request is only attempted once")(),
+            1,
+        )
+        .await
+    }
+
+    async fn run<T, F, Fut>(&self, mut request: F, max_attempts: u32) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        if let Some(remaining) = self.remaining_open() {
+            log::debug!("Auth circuit breaker open, rejecting request for another {remaining:?}");
+            return Err(Error::Network(format!(
+                "Authentication service is currently unavailable. Please try again in {} seconds.",
+                remaining.as_secs().max(1)
+            )));
+        }
+
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match request().await {
+                Ok(result) => {
+                    self.record_success();
+                    return Ok(result);
+                }
+                Err(err) if attempt < max_attempts && is_transient(&err) => {
+                    log::debug!(
+                        "Transient auth request failure (attempt {attempt}), retrying in {backoff:?}: {err}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(err) => {
+                    if is_transient(&err) {
+                        self.record_failure();
+                    }
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn remaining_open(&self) -> Option<Duration> {
+        let mut state = self.state.lock();
+        let open_until = state.open_until?;
+        let remaining = open_until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            // Half-open: let the next request through, a single failure re-opens the breaker.
+            state.open_until = None;
+            state.consecutive_failures = FAILURE_THRESHOLD - 1;
+            return None;
+        }
+        Some(remaining)
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock();
+        state.consecutive_failures = 0;
+        state.open_until = None;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= FAILURE_THRESHOLD {
+            log::warn!(
+                "Auth requests failed {} times in a row, pausing requests for {OPEN_DURATION:?}",
+                state.consecutive_failures
+            );
+            state.open_until = Some(Instant::now() + OPEN_DURATION);
+        }
+    }
+}
+
+/// Returns whether `err` is likely to resolve on its own, i.e. a network error or a server error
+/// response. Client errors such as [`Error::Unauthorized`] are never retried.
+fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Network(_) => true,
+        Error::Reqwest(err) => {
+            err.is_timeout()
+                || err.is_connect()
+                || err.status().is_some_and(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn failing_request(attempts: &AtomicU32, err: fn() -> Error) -> Result<(), Error> {
+        attempts.fetch_add(1, Ordering::Relaxed);
+        Err(err())
+    }
+
+    fn network_error() -> Error {
+        Error::Network("connection reset".to_string())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_retries_transient_failures() {
+        let breaker = AuthCircuitBreaker::default();
+        let attempts = &AtomicU32::new(0);
+
+        let result = breaker
+            .call(|| async move {
+                if attempts.fetch_add(1, Ordering::Relaxed) < MAX_ATTEMPTS - 1 {
+                    Err(network_error())
+                } else {
+                    Ok("token")
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "token");
+        assert_eq!(attempts.load(Ordering::Relaxed), MAX_ATTEMPTS);
+        assert_eq!(breaker.state.lock().consecutive_failures, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_gives_up_after_max_attempts() {
+        let breaker = AuthCircuitBreaker::default();
+        let attempts = AtomicU32::new(0);
+
+        let result = breaker
+            .call(|| failing_request(&attempts, network_error))
+            .await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+        assert_eq!(attempts.load(Ordering::Relaxed), MAX_ATTEMPTS);
+        assert_eq!(breaker.state.lock().consecutive_failures, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_does_not_retry_client_errors() {
+        let breaker = AuthCircuitBreaker::default();
+        let attempts = AtomicU32::new(0);
+
+        let result = breaker
+            .call(|| failing_request(&attempts, || Error::Unauthorized))
+            .await;
+
+        assert!(matches!(result, Err(Error::Unauthorized)));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        assert_eq!(breaker.state.lock().consecutive_failures, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_once_does_not_retry_transient_failures() {
+        let breaker = AuthCircuitBreaker::default();
+        let attempts = AtomicU32::new(0);
+
+        let result = breaker
+            .call_once(|| failing_request(&attempts, network_error))
+            .await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        assert_eq!(breaker.state.lock().consecutive_failures, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_consecutive_failures() {
+        let breaker = AuthCircuitBreaker::default();
+        let attempts = AtomicU32::new(0);
+
+        for _ in 0..FAILURE_THRESHOLD {
+            let _ = breaker
+                .call_once(|| failing_request(&attempts, network_error))
+                .await;
+        }
+        assert_eq!(attempts.load(Ordering::Relaxed), FAILURE_THRESHOLD);
+
+        let result = breaker
+            .call(|| failing_request(&attempts, network_error))
+            .await;
+        assert!(matches!(result, Err(Error::Network(_))));
+        assert_eq!(
+            attempts.load(Ordering::Relaxed),
+            FAILURE_THRESHOLD,
+            "Open breaker must not contact the server"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_breaker_reopens_after_single_failure() {
+        let breaker = AuthCircuitBreaker::default();
+        breaker.state.lock().open_until = Some(Instant::now());
+        let attempts = AtomicU32::new(0);
+
+        let _ = breaker
+            .call_once(|| failing_request(&attempts, network_error))
+            .await;
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        assert!(breaker.remaining_open().is_some());
+
+        breaker.state.lock().open_until = Some(Instant::now());
+        let result = breaker.call_once(|| async { Ok(()) }).await;
+        assert!(result.is_ok());
+        assert_eq!(breaker.state.lock().consecutive_failures, 0);
+        assert!(breaker.remaining_open().is_none());
+    }
+}
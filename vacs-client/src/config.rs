@@ -9,16 +9,20 @@ use keyboard_types::Code;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, LogicalSize, PhysicalPosition, PhysicalSize};
+use vacs_audio::FrameCodec;
+use vacs_audio::device::ChannelMap;
 use vacs_signaling::protocol::http::version::ReleaseChannel;
 use vacs_signaling::protocol::http::webrtc::IceConfig;
 use vacs_signaling::protocol::profile::client_page::{
     ClientGroupMode, ClientPageConfig, FrequencyDisplayMode,
 };
-use vacs_signaling::protocol::vatsim::{ClientId, PositionId};
+use vacs_signaling::protocol::vatsim::{ClientId, PositionId, StationId};
+use vacs_signaling::protocol::ws::shared::{AudioCodec, CallTarget};
 
 /// User-Agent string used for all HTTP requests.
 pub static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
@@ -108,14 +112,63 @@ impl AppConfig {
 
         Ok(config)
     }
+
+    /// Writes the default configuration to `config.toml` in `config_dir` if it doesn't exist yet,
+    /// giving first-time users a starting point to edit. Every setting is commented out, so later
+    /// changes of the defaults still apply until a value is explicitly set. Returns the path of the
+    /// written file, `None` if it already existed.
+    pub fn write_default_file(config_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let path = config_dir.join(DEFAULT_SETTINGS_FILE_NAME);
+        if path.exists() {
+            return Ok(None);
+        }
+
+        let serialized = toml::to_string_pretty(&AppConfig::default())
+            .context("Failed to serialize default config")?;
+        let mut content = String::from(
+            "# vacs configuration\n\
+             #\n\
+             # All settings below are set to their defaults. Uncomment and adjust a setting to\n\
+             # override it. Settings changed in the app are stored in separate files next to this one.\n\n",
+        );
+        for line in serialized.lines() {
+            if !line.is_empty() {
+                content.push_str("# ");
+                content.push_str(line);
+            }
+            content.push('\n');
+        }
+
+        fs::create_dir_all(config_dir).context("Failed to create config directory")?;
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => return Ok(None),
+            Err(err) => return Err(err).context("Failed to create default config file"),
+        };
+        file.write_all(content.as_bytes())
+            .context("Failed to write default config file")?;
+
+        Ok(Some(path))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendConfig {
     pub base_url: String,
     pub ws_url: String,
+    /// Backup signaling server, used if connecting to `ws_url` fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_backup_url: Option<String>,
     pub endpoints: BackendEndpointsConfigs,
     pub timeout_ms: u64,
+    /// Maximum age of the initial auth grant in seconds. Once exceeded, the client forces a full
+    /// interactive reauth instead of refreshing the session indefinitely. `None` disables the limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_max_age_secs: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dev_position_id: Option<PositionId>,
 }
@@ -135,8 +188,10 @@ impl Default for BackendConfig {
                 "wss://vacs.network/ws"
             }
             .to_string(),
+            ws_backup_url: None,
             endpoints: BackendEndpointsConfigs::default(),
             timeout_ms: 2000,
+            auth_max_age_secs: Some(7 * 24 * 60 * 60),
             dev_position_id: None,
         }
     }
@@ -156,6 +211,10 @@ impl BackendConfig {
         };
         format!("{}{}", self.base_url, path)
     }
+
+    pub fn auth_max_age(&self) -> Option<Duration> {
+        self.auth_max_age_secs.map(Duration::from_secs)
+    }
 }
 
 pub enum BackendEndpoint {
@@ -212,10 +271,112 @@ pub struct AudioConfig {
     pub output_device_name: Option<String>, // None means default device
     pub input_device_volume: f32,
     pub input_device_volume_amp: f32,
+    /// Fixed gain (in dB) applied to captured audio before any processing, compensating for quiet
+    /// microphones. Applied before the noise gate, so it also lowers the input level required to
+    /// open the gate. Limited to -30..=30 dB.
+    #[serde(default)]
+    pub input_pre_amp_db: f32,
     pub output_device_volume: f32,
     pub output_device_volume_amp: f32,
     pub click_volume: f32,
     pub chime_volume: f32,
+    /// Default jitter buffer depth (in milliseconds) applied to incoming call audio.
+    /// Can be adjusted for the active call at runtime.
+    pub jitter_buffer_depth_ms: u64,
+    /// Lookahead (in milliseconds) of the output limiter keeping mixed call audio and
+    /// notifications from clipping. Longer lookaheads reduce gain more smoothly before peaks, at
+    /// the cost of delaying all playback by the lookahead. Limited to 20 ms, the limiter is
+    /// disabled if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_limiter_lookahead_ms: Option<u64>,
+    /// Stops sending call audio entirely while the microphone is silent, instead of continuously
+    /// sending encoded silence. Saves bandwidth on constrained connections at the cost of speech
+    /// onset: the remote jitter buffer has to re-buffer at the start of every transmission, adding
+    /// its depth in latency and possibly clipping the first syllable.
+    #[serde(default)]
+    pub suppress_idle_audio: bool,
+    /// Audio codecs offered for calls, in order of preference. The first codec supported by both
+    /// peers is used, Opus if none is.
+    #[serde(default = "default_audio_codecs")]
+    pub codecs: Vec<FrameCodec>,
+    /// Zero-based input device channels feeding the mono capture, all channels if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_channel_map: Option<ChannelMap>,
+    /// Zero-based output device channels audio is played back on, all channels if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_channel_map: Option<ChannelMap>,
+    /// Routing of selected calls to a secondary output device.
+    #[serde(default, skip_serializing_if = "CallOutputRoutingConfig::is_empty")]
+    pub call_output_routing: CallOutputRoutingConfig,
+    /// Name of a separate output device incoming call chimes and clicks are played back on (e.g.
+    /// speakers, so they are audible without wearing a headset). Played back on the primary
+    /// output device if unset or unavailable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_output_device_name: Option<String>,
+}
+
+/// Output device a call's audio is played back on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputRoute {
+    #[default]
+    Primary,
+    Secondary,
+}
+
+/// Plays back calls with selected stations or positions on a secondary output device (e.g.
+/// supervisor lines on speakers), while all other calls and chimes stay on the primary one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallOutputRoutingConfig {
+    /// Name of the secondary output device, routing is disabled if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// Zero-based secondary output device channels audio is played back on, all channels if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_map: Option<ChannelMap>,
+    /// Stations whose calls are routed to the secondary output device.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub stations: HashSet<StationId>,
+    /// Positions whose calls are routed to the secondary output device.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub positions: HashSet<PositionId>,
+}
+
+impl CallOutputRoutingConfig {
+    pub fn is_empty(&self) -> bool {
+        self.device_name.is_none()
+            && self.channel_map.is_none()
+            && self.stations.is_empty()
+            && self.positions.is_empty()
+    }
+
+    /// Resolves the output route of a call with the given remote station or position, i.e. the
+    /// caller's station and position for incoming calls or the dialed target for outgoing ones.
+    pub fn route(
+        &self,
+        station_id: Option<&StationId>,
+        position_id: Option<&PositionId>,
+    ) -> OutputRoute {
+        if self.device_name.is_none() {
+            return OutputRoute::Primary;
+        }
+
+        let routed = station_id.is_some_and(|id| self.stations.contains(id))
+            || position_id.is_some_and(|id| self.positions.contains(id));
+        if routed {
+            OutputRoute::Secondary
+        } else {
+            OutputRoute::Primary
+        }
+    }
+
+    /// Resolves the output route of an outgoing call to `target`.
+    pub fn route_target(&self, target: &CallTarget) -> OutputRoute {
+        match target {
+            CallTarget::Station(station_id) => self.route(Some(station_id), None),
+            CallTarget::Position(position_id) => self.route(None, Some(position_id)),
+            CallTarget::Client(_) => OutputRoute::Primary,
+        }
+    }
 }
 
 impl Default for AudioConfig {
@@ -226,14 +387,55 @@ impl Default for AudioConfig {
             output_device_name: None,
             input_device_volume: 0.5,
             input_device_volume_amp: 4.0,
+            input_pre_amp_db: 0.0,
             output_device_volume: 0.5,
             output_device_volume_amp: 2.0,
             click_volume: 0.5,
             chime_volume: 0.5,
+            jitter_buffer_depth_ms: vacs_audio::jitter::DEFAULT_JITTER_BUFFER_DEPTH.as_millis()
+                as u64,
+            output_limiter_lookahead_ms: None,
+            suppress_idle_audio: false,
+            codecs: default_audio_codecs(),
+            input_channel_map: None,
+            output_channel_map: None,
+            call_output_routing: CallOutputRoutingConfig::default(),
+            notification_output_device_name: None,
         }
     }
 }
 
+fn default_audio_codecs() -> Vec<FrameCodec> {
+    vec![FrameCodec::Opus, FrameCodec::Pcm]
+}
+
+impl AudioConfig {
+    /// Lookahead of the output limiter, `None` if it is disabled.
+    pub fn output_limiter_lookahead(&self) -> Option<Duration> {
+        self.output_limiter_lookahead_ms.map(Duration::from_millis)
+    }
+
+    /// Codecs to offer or accept in call negotiation, in order of preference.
+    pub fn audio_codecs(&self) -> Vec<AudioCodec> {
+        self.codecs
+            .iter()
+            .map(|codec| match codec {
+                FrameCodec::Opus => AudioCodec::opus(),
+                FrameCodec::Pcm => AudioCodec::pcm(),
+            })
+            .collect()
+    }
+}
+
+/// Resolves the frame codec of a negotiated call codec, falling back to Opus if no codec was
+/// negotiated.
+pub fn frame_codec(audio_codec: Option<&AudioCodec>) -> FrameCodec {
+    match audio_codec {
+        Some(codec) if codec.name.eq_ignore_ascii_case(AudioCodec::PCM) => FrameCodec::Pcm,
+        _ => FrameCodec::Opus,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct PersistedAudioConfig {
     pub audio: AudioConfig,
@@ -469,6 +671,27 @@ pub struct TransmitConfig {
     /// Key code for Radio Integration PTT.
     /// Required if mode is `RadioIntegration`.
     pub radio_push_to_talk: Option<Code>,
+    /// Source of transmit key presses. Replaces the key code of the selected mode if set to
+    /// anything other than the keyboard.
+    #[serde(default)]
+    pub ptt_source: PttSource,
+}
+
+/// Source of push-to-talk (or push-to-mute) presses, allowing external hardware such as foot
+/// pedals or HID PTT buttons to be used instead of a keyboard key.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum PttSource {
+    /// Key code configured for the selected transmit mode.
+    #[default]
+    Keyboard,
+    /// Button of a joystick or other HID game controller.
+    Joystick {
+        /// Platform-specific device identifier, as returned by `keybinds_list_ptt_devices`.
+        device: String,
+        /// Zero-based index of the button on the device.
+        button: u8,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -515,6 +738,7 @@ impl TryFrom<FrontendTransmitConfig> for TransmitConfig {
                 .map(|s| s.parse::<Code>())
                 .transpose()
                 .map_err(|_| Error::Other(Box::new(anyhow::anyhow!("Unrecognized key code: {}. Please report this error in our GitHub repository's issue tracker.", value.radio_push_to_talk.unwrap_or_default()))))?,
+            ptt_source: PttSource::default(),
         })
     }
 }
@@ -730,6 +954,41 @@ pub struct CallConfig {
     pub enable_call_start_sound: bool,
     /// Enables sound effect when the call is ended
     pub enable_call_end_sound: bool,
+    /// Incoming calls answered automatically, without any user interaction.
+    #[serde(default, skip_serializing_if = "AutoAnswerConfig::is_empty")]
+    pub auto_answer: AutoAnswerConfig,
+}
+
+/// Answers selected incoming calls automatically (e.g. for an unattended information desk). The
+/// caller is notified the call was auto-answered.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutoAnswerConfig {
+    /// Answers all incoming calls, regardless of their target.
+    #[serde(default)]
+    pub all: bool,
+    /// Stations whose incoming calls are answered automatically.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub stations: HashSet<StationId>,
+    /// Positions whose incoming calls are answered automatically.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub positions: HashSet<PositionId>,
+}
+
+impl AutoAnswerConfig {
+    pub fn is_empty(&self) -> bool {
+        !self.all && self.stations.is_empty() && self.positions.is_empty()
+    }
+
+    /// Whether an incoming call to `target`, i.e. one of the own stations or positions or the
+    /// client itself, is answered automatically.
+    pub fn applies_to(&self, target: &CallTarget) -> bool {
+        self.all
+            || match target {
+                CallTarget::Station(station_id) => self.stations.contains(station_id),
+                CallTarget::Position(position_id) => self.positions.contains(position_id),
+                CallTarget::Client(_) => false,
+            }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -748,6 +1007,7 @@ impl Default for CallConfig {
             enable_priority_calls: true,
             enable_call_start_sound: true,
             enable_call_end_sound: true,
+            auto_answer: AutoAnswerConfig::default(),
         }
     }
 }
@@ -781,6 +1041,7 @@ impl From<FrontendCallConfig> for CallConfig {
             enable_priority_calls: frontend_call_config.enable_priority_calls,
             enable_call_start_sound: frontend_call_config.enable_call_start_sound,
             enable_call_end_sound: frontend_call_config.enable_call_end_sound,
+            auto_answer: AutoAnswerConfig::default(),
         }
     }
 }
@@ -868,3 +1129,43 @@ impl<T: Serialize> Persistable for T {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_answer_applies_to_configured_targets() {
+        let config = AutoAnswerConfig {
+            all: false,
+            stations: [StationId::from("LOWW_TWR")].into(),
+            positions: [PositionId::from("LOWW_APP")].into(),
+        };
+
+        assert!(config.applies_to(&CallTarget::Station(StationId::from("LOWW_TWR"))));
+        assert!(config.applies_to(&CallTarget::Position(PositionId::from("LOWW_APP"))));
+        assert!(!config.applies_to(&CallTarget::Station(StationId::from("LOWW_APP"))));
+        assert!(!config.applies_to(&CallTarget::Position(PositionId::from("LOWW_TWR"))));
+        assert!(!config.applies_to(&CallTarget::Client(ClientId::from("client1"))));
+    }
+
+    #[test]
+    fn auto_answer_all_applies_to_every_target() {
+        let config = AutoAnswerConfig {
+            all: true,
+            ..Default::default()
+        };
+
+        assert!(config.applies_to(&CallTarget::Station(StationId::from("LOWW_TWR"))));
+        assert!(config.applies_to(&CallTarget::Position(PositionId::from("LOWW_APP"))));
+        assert!(config.applies_to(&CallTarget::Client(ClientId::from("client1"))));
+    }
+
+    #[test]
+    fn auto_answer_defaults_to_empty() {
+        let config: AutoAnswerConfig = serde_json::from_str("{}").unwrap();
+
+        assert!(config.is_empty());
+        assert!(!config.applies_to(&CallTarget::Station(StationId::from("LOWW_TWR"))));
+    }
+}
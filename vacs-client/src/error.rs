@@ -7,7 +7,7 @@ use serde_json::Value;
 use std::fmt::{Debug, Display, Formatter};
 use tauri::{AppHandle, Emitter, Manager};
 use thiserror::Error;
-use vacs_signaling::error::{SignalingError, SignalingRuntimeError};
+use vacs_signaling::error::{CloseReason, SignalingError, SignalingRuntimeError};
 use vacs_signaling::protocol::ws::server::{DisconnectReason, LoginFailureReason};
 use vacs_signaling::protocol::ws::shared::{CallErrorReason, CallId, ErrorReason};
 
@@ -230,8 +230,28 @@ fn format_signaling_error(err: &SignalingError) -> String {
             LoginFailureReason::IncompatibleProtocolVersion => {
                 "Login failed: Incompatible protocol version. Please check your client version."
             }
+            LoginFailureReason::NotReady => {
+                "Login failed: Server is still starting up. Please try again in a few seconds."
+            }
+            LoginFailureReason::UnsupportedCodec(_) => {
+                "Login failed: The server does not accept any of the message codecs supported by your client. Please check your client version."
+            }
+            LoginFailureReason::FacilityNotAllowed => {
+                "Login failed: Your VATSIM connection's facility type is not allowed to use vacs."
+            }
+            LoginFailureReason::ClientTooOld { minimum } => {
+                return format!(
+                    "Login failed: Your client is outdated. Please update to version {minimum} or newer."
+                );
+            }
+            LoginFailureReason::InvalidTags => {
+                "Login failed: The server rejected the connection tags sent by your client. Please check your client version."
+            }
         }
         .to_string(),
+        SignalingError::AuthExpiredNeedsReauth => {
+            "Your authentication expired. Please log in again.".to_string()
+        }
         SignalingError::Runtime(runtime_err) => match runtime_err {
             SignalingRuntimeError::ServerError(reason) => match reason {
                 ErrorReason::MalformedMessage => "Server error: Malformed message".to_string(),
@@ -246,6 +266,12 @@ fn format_signaling_error(err: &SignalingError) -> String {
                 ErrorReason::ClientNotFound => {
                     "Server error: Client not found.".to_string()
                 }
+                ErrorReason::ProfileNotAvailable => {
+                    "Server error: The selected profile is not available for your position.".to_string()
+                }
+                ErrorReason::InvalidFrequencies(msg) => {
+                    format!("Server error: Invalid monitored frequencies: {msg}")
+                }
             },
             SignalingRuntimeError::Disconnected(reason) => match reason {
                 None => "Disconnected",
@@ -254,7 +280,15 @@ fn format_signaling_error(err: &SignalingError) -> String {
                 Some(DisconnectReason::AmbiguousVatsimPosition(_)) => {
                     "Disconnected: Multiple VATSIM positions matched your current position. Please select the correct position manually."
                 }
+                Some(DisconnectReason::IdleTimeout) => "Disconnected: Your connection was closed due to inactivity.",
+                Some(DisconnectReason::SendQueueOverflow) => "Disconnected: Your connection could not keep up with the server.",
             }.to_string(),
+            SignalingRuntimeError::Closed(CloseReason::PolicyViolation(_)) => {
+                "Disconnected: The server closed your connection due to a policy violation.".to_string()
+            }
+            SignalingRuntimeError::Closed(CloseReason::GoingAway) => {
+                "Disconnected: The server is restarting.".to_string()
+            }
             _ => runtime_err.to_string(),
         },
         _ => err.to_string(),
@@ -298,6 +332,8 @@ impl CallError {
                     CallErrorReason::AutoHangup => "Target did not answer",
                     CallErrorReason::Other => "Unknown failure",
                     CallErrorReason::TargetNotFound => "Call target not found",
+                    CallErrorReason::Unauthorized => "Not authorized",
+                    CallErrorReason::ReceiveOnly => "Position is receive-only",
                 }
             ),
         }
@@ -4,6 +4,7 @@ use thiserror::Error;
 
 pub mod commands;
 pub mod engine;
+pub mod ptt_source;
 pub mod runtime;
 
 #[derive(Debug, Clone, Error)]
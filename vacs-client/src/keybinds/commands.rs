@@ -1,10 +1,12 @@
 use crate::app::state::AppState;
 use crate::config::{
     CLIENT_SETTINGS_FILE_NAME, FrontendKeybindsConfig, FrontendRadioConfig, FrontendTransmitConfig,
-    KeybindsConfig, Persistable, PersistedClientConfig, RadioConfig, TransmitConfig, TransmitMode,
+    KeybindsConfig, Persistable, PersistedClientConfig, PttSource, RadioConfig, TransmitConfig,
+    TransmitMode,
 };
 use crate::error::Error;
 use crate::keybinds::engine::KeybindEngineHandle;
+use crate::keybinds::ptt_source::{self, PttDevice};
 use crate::keybinds::{Keybind, KeybindsError};
 use crate::platform::Capabilities;
 use crate::radio::{RadioIntegration, RadioState};
@@ -42,7 +44,8 @@ pub async fn keybinds_set_transmit_config(
     let persisted_client_config: PersistedClientConfig = {
         let mut state = app_state.lock().await;
 
-        let transmit_config: TransmitConfig = transmit_config.try_into()?;
+        let mut transmit_config: TransmitConfig = transmit_config.try_into()?;
+        transmit_config.ptt_source = state.config.client.transmit_config.ptt_source.clone();
 
         validate_afv_radio_integration_config(&transmit_config, &state.config.client.radio)?;
 
@@ -65,6 +68,63 @@ pub async fn keybinds_set_transmit_config(
     Ok(())
 }
 
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn keybinds_get_ptt_source(app_state: State<'_, AppState>) -> Result<PttSource, Error> {
+    Ok(app_state
+        .lock()
+        .await
+        .config
+        .client
+        .transmit_config
+        .ptt_source
+        .clone())
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn keybinds_set_ptt_source(
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    keybind_engine: State<'_, KeybindEngineHandle>,
+    ptt_source: PttSource,
+) -> Result<(), Error> {
+    let capabilities = Capabilities::default();
+    if !capabilities.keybind_listener {
+        return Err(Error::CapabilityNotAvailable("Keybinds".to_string()));
+    }
+
+    let persisted_client_config: PersistedClientConfig = {
+        let mut state = app_state.lock().await;
+
+        let mut transmit_config = state.config.client.transmit_config.clone();
+        transmit_config.ptt_source = ptt_source;
+
+        keybind_engine
+            .write()
+            .await
+            .set_config(&transmit_config, &state.config.client.keybinds)
+            .await?;
+
+        state.config.client.transmit_config = transmit_config;
+        state.config.client.clone().into()
+    };
+
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .expect("Cannot get config directory");
+    persisted_client_config.persist(&config_dir, CLIENT_SETTINGS_FILE_NAME)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub fn keybinds_list_ptt_devices() -> Result<Vec<PttDevice>, Error> {
+    Ok(ptt_source::list_devices())
+}
+
 #[tauri::command]
 #[vacs_macros::log_err]
 pub async fn keybinds_get_keybinds_config(
@@ -2,8 +2,9 @@ use crate::app::state::AppState;
 use crate::app::state::signaling::AppStateSignalingExt;
 use crate::app::state::webrtc::AppStateWebrtcExt;
 use crate::audio::manager::AudioManagerHandle;
-use crate::config::{KeybindsConfig, RadioConfig, TransmitConfig, TransmitMode};
+use crate::config::{KeybindsConfig, PttSource, RadioConfig, TransmitConfig, TransmitMode};
 use crate::error::Error;
+use crate::keybinds::ptt_source::{self, EXTERNAL_TRANSMIT_CODE};
 use crate::keybinds::runtime::{DynKeybindListener, KeybindListener, PlatformListener};
 use crate::keybinds::{KeyEvent, Keybind};
 use crate::radio::{DynRadio, RadioState, TransmissionState};
@@ -24,6 +25,9 @@ use crate::platform::Platform;
 pub struct KeybindEngine {
     mode: TransmitMode,
     transmit_code: Option<Code>,
+    /// Transmit code of the keyboard, used if the configured PTT source fails to start.
+    keyboard_transmit_code: Option<Code>,
+    ptt_source: PttSource,
     accept_call_code: Option<Code>,
     end_call_code: Option<Code>,
     toggle_radio_prio_code: Option<Code>,
@@ -53,6 +57,8 @@ impl KeybindEngine {
         Self {
             mode: transmit_config.mode,
             transmit_code: Self::select_active_transmit_code(transmit_config),
+            keyboard_transmit_code: Self::select_keyboard_transmit_code(transmit_config),
+            ptt_source: transmit_config.ptt_source.clone(),
             accept_call_code: Self::select_accept_call_code(call_control_config),
             end_call_code: Self::select_end_call_code(call_control_config),
             toggle_radio_prio_code: Self::select_toggle_radio_prio_code(call_control_config),
@@ -83,7 +89,10 @@ impl KeybindEngine {
                 "TransmitMode set to voice activation and no call controls defined, no keybind engine required"
             );
             return Ok(());
-        } else if self.mode != TransmitMode::VoiceActivation && self.transmit_code.is_none() {
+        } else if self.mode != TransmitMode::VoiceActivation
+            && self.transmit_code.is_none()
+            && self.ptt_source == PttSource::Keyboard
+        {
             log::trace!(
                 "No keybind set for TransmitMode {:?}, keybind engine not starting",
                 self.mode
@@ -91,11 +100,39 @@ impl KeybindEngine {
             return Ok(());
         }
 
-        self.stop_token = Some(self.shutdown_token.child_token());
+        let stop_token = self.shutdown_token.child_token();
+        self.stop_token = Some(stop_token.clone());
+
+        let external_rx = if self.mode == TransmitMode::VoiceActivation {
+            None
+        } else {
+            match ptt_source::start(&self.ptt_source) {
+                Ok(Some(external_rx)) => {
+                    self.transmit_code = Some(EXTERNAL_TRANSMIT_CODE);
+                    Some(external_rx)
+                }
+                Ok(None) => None,
+                Err(err) => {
+                    // A missing or unplugged PTT device must not take down the call control
+                    // keybinds, fall back to the keyboard key configured for the mode instead.
+                    log::warn!(
+                        "Failed to start PTT source {:?}, falling back to keyboard: {err}",
+                        self.ptt_source
+                    );
+                    self.transmit_code = self.keyboard_transmit_code;
+                    None
+                }
+            }
+        };
 
         let (listener, rx) = PlatformListener::start().await?;
         *self.listener.write() = Some(Arc::new(listener));
 
+        let rx = match external_rx {
+            Some(external_rx) => ptt_source::merge(rx, external_rx, stop_token),
+            None => rx,
+        };
+
         if self.mode == TransmitMode::RadioIntegration {
             let radio = self.radio_config.radio(self.app.clone()).await?;
             *self.radio.write() = radio;
@@ -141,6 +178,8 @@ impl KeybindEngine {
         self.stop();
 
         self.transmit_code = Self::select_active_transmit_code(transmit_config);
+        self.keyboard_transmit_code = Self::select_keyboard_transmit_code(transmit_config);
+        self.ptt_source = transmit_config.ptt_source.clone();
         self.mode = transmit_config.mode;
 
         self.accept_call_code = Self::select_accept_call_code(keybinds_config);
@@ -323,7 +362,7 @@ impl KeybindEngine {
                     _ => {}
                 }
             } else {
-                match state.accept_call(app, None).await {
+                match state.accept_call(app, None, false).await {
                     Ok(found) if !found => log::trace!("No incoming call to accept via keybind"),
                     Err(err) => log::warn!("Failed to accept incoming call via keybind: {err}"),
                     _ => {}
@@ -335,7 +374,7 @@ impl KeybindEngine {
             let state = app.state::<AppState>();
             let mut state = state.lock().await;
 
-            match state.accept_call(app, None).await {
+            match state.accept_call(app, None, false).await {
                 Ok(found) if !found => log::trace!("No incoming call to accept via keybind"),
                 Err(err) => log::warn!("Failed to accept incoming call via keybind: {err}"),
                 _ => {}
@@ -480,6 +519,18 @@ impl KeybindEngine {
 
     #[inline]
     fn select_active_transmit_code(config: &TransmitConfig) -> Option<Code> {
+        // External PTT sources emit their presses using a dedicated code, see `ptt_source`.
+        // They take precedence over all platform-specific code mappings below.
+        if config.mode != TransmitMode::VoiceActivation && config.ptt_source != PttSource::Keyboard
+        {
+            return Some(EXTERNAL_TRANSMIT_CODE);
+        }
+
+        Self::select_keyboard_transmit_code(config)
+    }
+
+    #[inline]
+    fn select_keyboard_transmit_code(config: &TransmitConfig) -> Option<Code> {
         #[cfg(target_os = "linux")]
         if matches!(Platform::get(), Platform::LinuxWayland) {
             // Wayland Code Mapping Strategy:
@@ -561,6 +612,12 @@ impl KeybindEngine {
         app.state::<AudioManagerHandle>()
             .read()
             .set_input_muted(muted);
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            state.lock().await.send_transmit_state(!muted).await;
+        });
     }
 
     #[inline]
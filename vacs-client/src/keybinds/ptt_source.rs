@@ -0,0 +1,308 @@
+//! Push-to-talk sources besides the global keyboard listener.
+//!
+//! # Architecture
+//!
+//! The keybind engine only knows about [`KeyEvent`]s for a single transmit [`Code`]. External PTT
+//! sources (joystick buttons, foot pedals, HID PTT buttons) are therefore translated into key
+//! events for [`EXTERNAL_TRANSMIT_CODE`] and merged into the keyboard listener's event stream.
+//! This way, the rest of the engine (transmit modes, radio prio, radio integration) works
+//! unchanged, no matter where the presses originate from. Call control keybinds keep using the
+//! keyboard listener.
+//!
+//! ## Platform Support
+//!
+//! | Platform | Joystick | Notes                                                  |
+//! |----------|----------|--------------------------------------------------------|
+//! | Linux    | ✅       | Joystick API (`/dev/input/js*`)                        |
+//! | Windows  | ✅       | WinMM joystick API, polled                             |
+//! | macOS    | ✅       | IOKit HID manager, joysticks, gamepads and multi-axis  |
+//!
+//! Sources report a release of the transmit button when they stop while it is held (e.g. because
+//! the device was unplugged), so the transmit key never gets stuck.
+
+use crate::config::PttSource;
+use crate::keybinds::{KeyEvent, KeybindsError};
+use keyboard_types::{Code, KeyState};
+use serde::Serialize;
+use std::fmt::Debug;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio_util::sync::CancellationToken;
+
+/// Key code used for transmit events of external PTT sources.
+///
+/// Similar to the Wayland portal shortcuts (F31-F35), an F-key that doesn't exist on most keyboards
+/// is used, so there's no conflict with regular user input.
+pub const EXTERNAL_TRANSMIT_CODE: Code = Code::F30;
+
+/// Trait for external PTT sources emitting transmit key events.
+///
+/// Implementations must stop emitting events once the receiving half of `tx` was dropped.
+pub trait ExternalPttSource: Send + Debug + 'static {
+    fn start(self, code: Code, tx: UnboundedSender<KeyEvent>) -> Result<(), KeybindsError>
+    where
+        Self: Sized;
+}
+
+/// An input device that can be used as an external PTT source.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PttDevice {
+    /// Platform-specific device identifier, used as [`PttSource::Joystick::device`].
+    pub id: String,
+    /// Human-readable device name, if reported by the device.
+    pub name: Option<String>,
+}
+
+/// Starts the given PTT source, returning a receiver for its transmit events.
+///
+/// Returns `None` for [`PttSource::Keyboard`], since keyboard presses are already handled by the
+/// platform keybind listener.
+pub fn start(source: &PttSource) -> Result<Option<UnboundedReceiver<KeyEvent>>, KeybindsError> {
+    match source {
+        PttSource::Keyboard => Ok(None),
+        PttSource::Joystick { device, button } => {
+            let (tx, rx) = unbounded_channel();
+            PlatformJoystickSource::new(device.clone(), *button)
+                .start(EXTERNAL_TRANSMIT_CODE, tx)?;
+            Ok(Some(rx))
+        }
+    }
+}
+
+/// Lists all input devices currently available as external PTT sources.
+pub fn list_devices() -> Vec<PttDevice> {
+    PlatformJoystickSource::list_devices()
+}
+
+/// Merges the key events of the keyboard listener and an external PTT source into a single
+/// receiver, until `stop_token` is cancelled or the keyboard listener closes its channel.
+pub fn merge(
+    mut keyboard: UnboundedReceiver<KeyEvent>,
+    mut external: UnboundedReceiver<KeyEvent>,
+    stop_token: CancellationToken,
+) -> UnboundedReceiver<KeyEvent> {
+    let (tx, rx) = unbounded_channel();
+    tauri::async_runtime::spawn(async move {
+        let mut external_open = true;
+        loop {
+            let event = tokio::select! {
+                biased;
+                _ = stop_token.cancelled() => break,
+                event = keyboard.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                event = external.recv(), if external_open => match event {
+                    Some(event) => event,
+                    None => {
+                        log::warn!("External PTT source stopped, only keyboard events remain available");
+                        external_open = false;
+                        continue;
+                    }
+                },
+            };
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+        log::trace!("PTT source merge loop finished");
+    });
+    rx
+}
+
+/// Translates the state of the transmit button of an external source into key events.
+///
+/// Sends a synthetic release when dropped while the button is still held, so a failing or
+/// unplugged device never leaves the transmit key stuck.
+#[derive(Debug)]
+struct ButtonTracker {
+    code: Code,
+    label: String,
+    pressed: bool,
+    tx: UnboundedSender<KeyEvent>,
+}
+
+impl ButtonTracker {
+    fn new(code: Code, label: String, tx: UnboundedSender<KeyEvent>) -> Self {
+        Self {
+            code,
+            label,
+            pressed: false,
+            tx,
+        }
+    }
+
+    /// Reports the current state of the button, only sending an event if it changed. Returns
+    /// `false` once the receiving half was dropped.
+    fn update(&mut self, pressed: bool) -> bool {
+        if pressed == self.pressed {
+            return !self.tx.is_closed();
+        }
+        self.pressed = pressed;
+        self.send(pressed)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    fn send(&self, pressed: bool) -> bool {
+        self.tx
+            .send(KeyEvent {
+                code: self.code,
+                label: self.label.clone(),
+                state: if pressed {
+                    KeyState::Down
+                } else {
+                    KeyState::Up
+                },
+            })
+            .is_ok()
+    }
+}
+
+impl Drop for ButtonTracker {
+    fn drop(&mut self) {
+        if self.pressed {
+            log::debug!("External PTT source stopped while pressed, releasing transmit key");
+            self.send(false);
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        mod linux;
+        use linux::LinuxJoystickSource as PlatformJoystickSource;
+    } else if #[cfg(target_os = "windows")] {
+        mod windows;
+        use windows::WindowsJoystickSource as PlatformJoystickSource;
+    } else if #[cfg(target_os = "macos")] {
+        mod macos;
+        use macos::MacOsJoystickSource as PlatformJoystickSource;
+    } else {
+        mod unsupported;
+        use unsupported::UnsupportedJoystickSource as PlatformJoystickSource;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn event(code: Code, state: KeyState) -> KeyEvent {
+        KeyEvent {
+            code,
+            label: format!("{code:?}"),
+            state,
+        }
+    }
+
+    fn received(event: Option<KeyEvent>) -> Option<(Code, KeyState)> {
+        event.map(|event| (event.code, event.state))
+    }
+
+    #[tokio::test]
+    async fn merge_forwards_events_of_both_sources() {
+        let (keyboard_tx, keyboard_rx) = unbounded_channel();
+        let (external_tx, external_rx) = unbounded_channel();
+        let mut rx = merge(keyboard_rx, external_rx, CancellationToken::new());
+
+        keyboard_tx.send(event(Code::KeyA, KeyState::Down)).unwrap();
+        assert_eq!(
+            received(rx.recv().await),
+            Some((Code::KeyA, KeyState::Down))
+        );
+
+        external_tx
+            .send(event(EXTERNAL_TRANSMIT_CODE, KeyState::Down))
+            .unwrap();
+        assert_eq!(
+            received(rx.recv().await),
+            Some((EXTERNAL_TRANSMIT_CODE, KeyState::Down))
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_keeps_keyboard_events_after_external_source_stopped() {
+        let (keyboard_tx, keyboard_rx) = unbounded_channel();
+        let (external_tx, external_rx) = unbounded_channel();
+        let mut rx = merge(keyboard_rx, external_rx, CancellationToken::new());
+
+        drop(external_tx);
+        keyboard_tx.send(event(Code::KeyA, KeyState::Up)).unwrap();
+        assert_eq!(received(rx.recv().await), Some((Code::KeyA, KeyState::Up)));
+    }
+
+    #[tokio::test]
+    async fn merge_stops_with_keyboard_listener() {
+        let (keyboard_tx, keyboard_rx) = unbounded_channel();
+        let (_external_tx, external_rx) = unbounded_channel();
+        let mut rx = merge(keyboard_rx, external_rx, CancellationToken::new());
+
+        drop(keyboard_tx);
+        assert_eq!(received(rx.recv().await), None);
+    }
+
+    #[tokio::test]
+    async fn merge_stops_when_cancelled() {
+        let (_keyboard_tx, keyboard_rx) = unbounded_channel();
+        let (_external_tx, external_rx) = unbounded_channel();
+        let stop_token = CancellationToken::new();
+        let mut rx = merge(keyboard_rx, external_rx, stop_token.clone());
+
+        stop_token.cancel();
+        assert_eq!(received(rx.recv().await), None);
+    }
+
+    #[test]
+    fn button_tracker_only_reports_changes() {
+        let (tx, mut rx) = unbounded_channel();
+        let mut tracker = ButtonTracker::new(EXTERNAL_TRANSMIT_CODE, "button".to_string(), tx);
+
+        assert!(tracker.update(false));
+        assert!(tracker.update(true));
+        assert!(tracker.update(true));
+        assert!(tracker.update(false));
+
+        assert_eq!(
+            received(rx.try_recv().ok()),
+            Some((EXTERNAL_TRANSMIT_CODE, KeyState::Down))
+        );
+        assert_eq!(
+            received(rx.try_recv().ok()),
+            Some((EXTERNAL_TRANSMIT_CODE, KeyState::Up))
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn button_tracker_releases_held_button_when_dropped() {
+        let (tx, mut rx) = unbounded_channel();
+        let mut tracker = ButtonTracker::new(EXTERNAL_TRANSMIT_CODE, "button".to_string(), tx);
+
+        tracker.update(true);
+        drop(tracker);
+
+        assert_eq!(
+            received(rx.try_recv().ok()),
+            Some((EXTERNAL_TRANSMIT_CODE, KeyState::Down))
+        );
+        assert_eq!(
+            received(rx.try_recv().ok()),
+            Some((EXTERNAL_TRANSMIT_CODE, KeyState::Up))
+        );
+    }
+
+    #[test]
+    fn button_tracker_stops_once_receiver_dropped() {
+        let (tx, rx) = unbounded_channel();
+        let mut tracker = ButtonTracker::new(EXTERNAL_TRANSMIT_CODE, "button".to_string(), tx);
+
+        drop(rx);
+        assert!(tracker.is_closed());
+        assert!(!tracker.update(false));
+        assert!(!tracker.update(true));
+    }
+}
@@ -0,0 +1,100 @@
+use super::{ButtonTracker, ExternalPttSource, PttDevice};
+use crate::keybinds::{KeyEvent, KeybindsError};
+use keyboard_types::Code;
+use std::fs::File;
+use std::io::Read;
+use tokio::sync::mpsc::UnboundedSender;
+
+const DEVICE_DIR: &str = "/dev/input";
+/// Size of a `struct js_event` (`u32` time, `i16` value, `u8` type, `u8` number).
+const JS_EVENT_SIZE: usize = 8;
+const JS_EVENT_BUTTON: u8 = 0x01;
+/// Flag set for the synthetic events reporting the initial device state after opening it.
+const JS_EVENT_INIT: u8 = 0x80;
+
+/// Joystick source using the Linux joystick API, reading button events from `/dev/input/js*`.
+#[derive(Debug)]
+pub struct LinuxJoystickSource {
+    device: String,
+    button: u8,
+}
+
+impl LinuxJoystickSource {
+    pub fn new(device: String, button: u8) -> Self {
+        Self { device, button }
+    }
+
+    pub fn list_devices() -> Vec<PttDevice> {
+        let Ok(entries) = std::fs::read_dir(DEVICE_DIR) else {
+            return Vec::new();
+        };
+
+        let mut devices = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                file_name.starts_with("js").then(|| PttDevice {
+                    id: entry.path().to_string_lossy().into_owned(),
+                    name: std::fs::read_to_string(format!(
+                        "/sys/class/input/{file_name}/device/name"
+                    ))
+                    .ok()
+                    .map(|name| name.trim().to_string()),
+                })
+            })
+            .collect::<Vec<_>>();
+        devices.sort_by(|a, b| a.id.cmp(&b.id));
+        devices
+    }
+}
+
+impl ExternalPttSource for LinuxJoystickSource {
+    fn start(self, code: Code, tx: UnboundedSender<KeyEvent>) -> Result<(), KeybindsError> {
+        let mut file = File::open(&self.device).map_err(|err| {
+            KeybindsError::Listener(format!(
+                "Failed to open joystick device {}: {err}",
+                self.device
+            ))
+        })?;
+
+        // Reads are blocking, so the thread only notices the receiver being dropped on the
+        // next event of the device. This is fine, as it doesn't hold on to anything else.
+        std::thread::Builder::new()
+            .name("vacs-joystick-ptt".to_string())
+            .spawn(move || {
+                log::debug!(
+                    "Joystick PTT source started: device={}, button={}",
+                    self.device,
+                    self.button
+                );
+
+                let mut tracker =
+                    ButtonTracker::new(code, format!("Joystick button {}", self.button), tx);
+                let mut buf = [0u8; JS_EVENT_SIZE];
+                loop {
+                    if let Err(err) = file.read_exact(&mut buf) {
+                        log::warn!("Failed to read from joystick device {}: {err}", self.device);
+                        break;
+                    }
+
+                    let value = i16::from_ne_bytes([buf[4], buf[5]]);
+                    let kind = buf[6] & !JS_EVENT_INIT;
+                    let number = buf[7];
+                    if kind != JS_EVENT_BUTTON || number != self.button {
+                        continue;
+                    }
+
+                    if !tracker.update(value != 0) {
+                        break;
+                    }
+                }
+
+                log::trace!("Joystick PTT source for {} finished", self.device);
+            })
+            .map_err(|err| {
+                KeybindsError::Listener(format!("Failed to spawn joystick thread: {err}"))
+            })?;
+
+        Ok(())
+    }
+}
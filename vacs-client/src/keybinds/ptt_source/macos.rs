@@ -0,0 +1,459 @@
+use super::{ButtonTracker, ExternalPttSource, PttDevice};
+use crate::keybinds::{KeyEvent, KeybindsError};
+use keyboard_types::Code;
+use std::ffi::{CStr, CString, c_char, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc::UnboundedSender;
+
+type CFTypeRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFNumberRef = *const c_void;
+type CFSetRef = *const c_void;
+type CFArrayRef = *const c_void;
+type CFDictionaryRef = *const c_void;
+type CFRunLoopRef = *mut c_void;
+type CFIndex = isize;
+type CFTypeID = usize;
+type IOHIDManagerRef = *mut c_void;
+type IOHIDDeviceRef = *mut c_void;
+type IOHIDElementRef = *mut c_void;
+type IOHIDValueRef = *mut c_void;
+type IOReturn = i32;
+type IOHIDValueCallback = extern "C" fn(
+    context: *mut c_void,
+    result: IOReturn,
+    sender: *mut c_void,
+    value: IOHIDValueRef,
+);
+type IOHIDDeviceCallback = extern "C" fn(
+    context: *mut c_void,
+    result: IOReturn,
+    sender: *mut c_void,
+    device: IOHIDDeviceRef,
+);
+
+/// Opaque CoreFoundation callback tables, only ever used by reference.
+#[repr(C)]
+struct CFCallBacks {
+    _private: [u8; 0],
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    static kCFRunLoopDefaultMode: CFStringRef;
+    static kCFTypeDictionaryKeyCallBacks: CFCallBacks;
+    static kCFTypeDictionaryValueCallBacks: CFCallBacks;
+    static kCFTypeArrayCallBacks: CFCallBacks;
+
+    fn CFRelease(cf: CFTypeRef);
+    fn CFGetTypeID(cf: CFTypeRef) -> CFTypeID;
+    fn CFNumberGetTypeID() -> CFTypeID;
+    fn CFStringGetTypeID() -> CFTypeID;
+    fn CFNumberCreate(allocator: CFTypeRef, the_type: CFIndex, value: *const c_void)
+    -> CFNumberRef;
+    fn CFNumberGetValue(number: CFNumberRef, the_type: CFIndex, value: *mut c_void) -> u8;
+    fn CFStringCreateWithCString(
+        allocator: CFTypeRef,
+        c_str: *const c_char,
+        encoding: u32,
+    ) -> CFStringRef;
+    fn CFStringGetCString(
+        string: CFStringRef,
+        buffer: *mut c_char,
+        buffer_size: CFIndex,
+        encoding: u32,
+    ) -> u8;
+    fn CFDictionaryCreate(
+        allocator: CFTypeRef,
+        keys: *const CFTypeRef,
+        values: *const CFTypeRef,
+        num_values: CFIndex,
+        key_callbacks: *const CFCallBacks,
+        value_callbacks: *const CFCallBacks,
+    ) -> CFDictionaryRef;
+    fn CFArrayCreate(
+        allocator: CFTypeRef,
+        values: *const CFTypeRef,
+        num_values: CFIndex,
+        callbacks: *const CFCallBacks,
+    ) -> CFArrayRef;
+    fn CFSetGetCount(set: CFSetRef) -> CFIndex;
+    fn CFSetGetValues(set: CFSetRef, values: *mut CFTypeRef);
+    fn CFRunLoopGetCurrent() -> CFRunLoopRef;
+    fn CFRunLoopRunInMode(mode: CFStringRef, seconds: f64, return_after_source: u8) -> i32;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+unsafe extern "C" {
+    fn IOHIDManagerCreate(allocator: CFTypeRef, options: u32) -> IOHIDManagerRef;
+    fn IOHIDManagerSetDeviceMatchingMultiple(manager: IOHIDManagerRef, multiple: CFArrayRef);
+    fn IOHIDManagerOpen(manager: IOHIDManagerRef, options: u32) -> IOReturn;
+    fn IOHIDManagerClose(manager: IOHIDManagerRef, options: u32) -> IOReturn;
+    fn IOHIDManagerCopyDevices(manager: IOHIDManagerRef) -> CFSetRef;
+    fn IOHIDManagerRegisterInputValueCallback(
+        manager: IOHIDManagerRef,
+        callback: Option<IOHIDValueCallback>,
+        context: *mut c_void,
+    );
+    fn IOHIDManagerRegisterDeviceRemovalCallback(
+        manager: IOHIDManagerRef,
+        callback: Option<IOHIDDeviceCallback>,
+        context: *mut c_void,
+    );
+    fn IOHIDManagerScheduleWithRunLoop(
+        manager: IOHIDManagerRef,
+        run_loop: CFRunLoopRef,
+        mode: CFStringRef,
+    );
+    fn IOHIDManagerUnscheduleFromRunLoop(
+        manager: IOHIDManagerRef,
+        run_loop: CFRunLoopRef,
+        mode: CFStringRef,
+    );
+    fn IOHIDDeviceGetProperty(device: IOHIDDeviceRef, key: CFStringRef) -> CFTypeRef;
+    fn IOHIDValueGetElement(value: IOHIDValueRef) -> IOHIDElementRef;
+    fn IOHIDValueGetIntegerValue(value: IOHIDValueRef) -> CFIndex;
+    fn IOHIDElementGetDevice(element: IOHIDElementRef) -> IOHIDDeviceRef;
+    fn IOHIDElementGetUsagePage(element: IOHIDElementRef) -> u32;
+    fn IOHIDElementGetUsage(element: IOHIDElementRef) -> u32;
+}
+
+const IO_RETURN_SUCCESS: IOReturn = 0;
+const IOHID_OPTIONS_TYPE_NONE: u32 = 0;
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const CF_NUMBER_SINT32_TYPE: CFIndex = 3;
+const CF_NUMBER_SINT64_TYPE: CFIndex = 4;
+const HID_PAGE_GENERIC_DESKTOP: u32 = 0x01;
+const HID_PAGE_BUTTON: u32 = 0x09;
+/// Generic desktop usages of devices offered as PTT sources: joysticks (most foot pedals and
+/// HID PTT buttons), gamepads and multi-axis controllers.
+const HID_DEVICE_USAGES: [u32; 3] = [0x04, 0x05, 0x08];
+/// Interval in seconds at which the run loop checks whether the source should stop.
+const RUN_LOOP_INTERVAL: f64 = 0.25;
+
+/// Joystick source using the IOKit HID manager, receiving button events of game controllers.
+///
+/// Devices are identified by their IOKit location ID, formatted as hex (e.g. `0x14200000`).
+#[derive(Debug)]
+pub struct MacOsJoystickSource {
+    device: String,
+    button: u8,
+}
+
+impl MacOsJoystickSource {
+    pub fn new(device: String, button: u8) -> Self {
+        Self { device, button }
+    }
+
+    pub fn list_devices() -> Vec<PttDevice> {
+        let Some(manager) = HidManager::open() else {
+            return Vec::new();
+        };
+
+        let mut devices = manager
+            .devices()
+            .into_iter()
+            .filter_map(|device| {
+                Some(PttDevice {
+                    id: device_id(device)?,
+                    name: string_property(device, "Product"),
+                })
+            })
+            .collect::<Vec<_>>();
+        devices.sort_by(|a, b| a.id.cmp(&b.id));
+        devices.dedup_by(|a, b| a.id == b.id);
+        devices
+    }
+}
+
+impl ExternalPttSource for MacOsJoystickSource {
+    fn start(self, code: Code, tx: UnboundedSender<KeyEvent>) -> Result<(), KeybindsError> {
+        {
+            let manager = HidManager::open().ok_or_else(|| {
+                KeybindsError::Listener("Failed to open IOKit HID manager".to_string())
+            })?;
+            if !manager
+                .devices()
+                .into_iter()
+                .any(|device| device_id(device).as_deref() == Some(self.device.as_str()))
+            {
+                return Err(KeybindsError::Listener(format!(
+                    "Joystick device {} not found",
+                    self.device
+                )));
+            }
+        }
+
+        // The HID manager delivers its callbacks on the run loop of the thread it was scheduled
+        // with, so it lives on a dedicated thread running that loop.
+        std::thread::Builder::new()
+            .name("vacs-joystick-ptt".to_string())
+            .spawn(move || {
+                let Some(manager) = HidManager::open() else {
+                    log::warn!("Failed to open IOKit HID manager");
+                    return;
+                };
+                log::debug!(
+                    "Joystick PTT source started: device={}, button={}",
+                    self.device,
+                    self.button
+                );
+
+                // Owned by this thread, only reclaimed after the manager no longer invokes any
+                // callbacks referencing it.
+                let context = Box::into_raw(Box::new(CallbackContext {
+                    device: self.device.clone(),
+                    // HID button usages are one-based, configured buttons zero-based.
+                    usage: u32::from(self.button) + 1,
+                    tracker: ButtonTracker::new(
+                        code,
+                        format!("Joystick button {}", self.button),
+                        tx,
+                    ),
+                    removed: AtomicBool::new(false),
+                }));
+
+                unsafe {
+                    IOHIDManagerRegisterInputValueCallback(
+                        manager.0,
+                        Some(input_value_callback),
+                        context as *mut c_void,
+                    );
+                    IOHIDManagerRegisterDeviceRemovalCallback(
+                        manager.0,
+                        Some(device_removal_callback),
+                        context as *mut c_void,
+                    );
+                    IOHIDManagerScheduleWithRunLoop(
+                        manager.0,
+                        CFRunLoopGetCurrent(),
+                        kCFRunLoopDefaultMode,
+                    );
+
+                    while !(*context).removed.load(Ordering::Relaxed)
+                        && !(*context).tracker.is_closed()
+                    {
+                        CFRunLoopRunInMode(kCFRunLoopDefaultMode, RUN_LOOP_INTERVAL, 0);
+                    }
+                    if (*context).removed.load(Ordering::Relaxed) {
+                        log::warn!("Joystick device {} was removed", self.device);
+                    }
+
+                    IOHIDManagerUnscheduleFromRunLoop(
+                        manager.0,
+                        CFRunLoopGetCurrent(),
+                        kCFRunLoopDefaultMode,
+                    );
+                    IOHIDManagerRegisterInputValueCallback(manager.0, None, std::ptr::null_mut());
+                    IOHIDManagerRegisterDeviceRemovalCallback(
+                        manager.0,
+                        None,
+                        std::ptr::null_mut(),
+                    );
+                }
+                // Closes the manager before the context (and with it the tracker, releasing a
+                // held button) is dropped.
+                drop(manager);
+                drop(unsafe { Box::from_raw(context) });
+
+                log::trace!("Joystick PTT source for {} finished", self.device);
+            })
+            .map_err(|err| {
+                KeybindsError::Listener(format!("Failed to spawn joystick thread: {err}"))
+            })?;
+
+        Ok(())
+    }
+}
+
+struct CallbackContext {
+    device: String,
+    usage: u32,
+    tracker: ButtonTracker,
+    removed: AtomicBool,
+}
+
+extern "C" fn input_value_callback(
+    context: *mut c_void,
+    _result: IOReturn,
+    _sender: *mut c_void,
+    value: IOHIDValueRef,
+) {
+    let context = unsafe { &mut *(context as *mut CallbackContext) };
+    let (page, usage, device, pressed) = unsafe {
+        let element = IOHIDValueGetElement(value);
+        (
+            IOHIDElementGetUsagePage(element),
+            IOHIDElementGetUsage(element),
+            IOHIDElementGetDevice(element),
+            IOHIDValueGetIntegerValue(value) != 0,
+        )
+    };
+    if page != HID_PAGE_BUTTON
+        || usage != context.usage
+        || device_id(device).as_deref() != Some(context.device.as_str())
+    {
+        return;
+    }
+    context.tracker.update(pressed);
+}
+
+extern "C" fn device_removal_callback(
+    context: *mut c_void,
+    _result: IOReturn,
+    _sender: *mut c_void,
+    device: IOHIDDeviceRef,
+) {
+    let context = unsafe { &*(context as *const CallbackContext) };
+    if device_id(device).as_deref() == Some(context.device.as_str()) {
+        context.removed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Owned IOKit HID manager matching all joysticks, gamepads and multi-axis controllers.
+struct HidManager(IOHIDManagerRef);
+
+impl HidManager {
+    fn open() -> Option<Self> {
+        let manager = unsafe { IOHIDManagerCreate(std::ptr::null(), IOHID_OPTIONS_TYPE_NONE) };
+        if manager.is_null() {
+            return None;
+        }
+        let manager = Self(manager);
+
+        let matching = HID_DEVICE_USAGES
+            .iter()
+            .map(|usage| device_matching(HID_PAGE_GENERIC_DESKTOP, *usage))
+            .collect::<Vec<_>>();
+        unsafe {
+            let array = CFArrayCreate(
+                std::ptr::null(),
+                matching.as_ptr(),
+                matching.len() as CFIndex,
+                &kCFTypeArrayCallBacks,
+            );
+            for dictionary in matching {
+                CFRelease(dictionary);
+            }
+            IOHIDManagerSetDeviceMatchingMultiple(manager.0, array);
+            CFRelease(array);
+
+            if IOHIDManagerOpen(manager.0, IOHID_OPTIONS_TYPE_NONE) != IO_RETURN_SUCCESS {
+                return None;
+            }
+        }
+        Some(manager)
+    }
+
+    fn devices(&self) -> Vec<IOHIDDeviceRef> {
+        unsafe {
+            let set = IOHIDManagerCopyDevices(self.0);
+            if set.is_null() {
+                return Vec::new();
+            }
+            let mut devices = vec![std::ptr::null(); CFSetGetCount(set) as usize];
+            CFSetGetValues(set, devices.as_mut_ptr());
+            CFRelease(set);
+            // The devices are retained by the manager, so they remain valid while it is open.
+            devices
+                .into_iter()
+                .map(|device| device as IOHIDDeviceRef)
+                .collect()
+        }
+    }
+}
+
+impl Drop for HidManager {
+    fn drop(&mut self) {
+        unsafe {
+            IOHIDManagerClose(self.0, IOHID_OPTIONS_TYPE_NONE);
+            CFRelease(self.0 as CFTypeRef);
+        }
+    }
+}
+
+/// Creates a matching dictionary for devices with the given primary usage page and usage. The
+/// caller owns the returned dictionary.
+fn device_matching(page: u32, usage: u32) -> CFDictionaryRef {
+    unsafe {
+        let keys = [cf_string("DeviceUsagePage"), cf_string("DeviceUsage")];
+        let values = [cf_number(page as i32), cf_number(usage as i32)];
+        let dictionary = CFDictionaryCreate(
+            std::ptr::null(),
+            keys.as_ptr(),
+            values.as_ptr(),
+            keys.len() as CFIndex,
+            &kCFTypeDictionaryKeyCallBacks,
+            &kCFTypeDictionaryValueCallBacks,
+        );
+        for cf in keys.into_iter().chain(values) {
+            CFRelease(cf);
+        }
+        dictionary
+    }
+}
+
+/// Creates a CoreFoundation string owned by the caller.
+fn cf_string(value: &str) -> CFStringRef {
+    let value = CString::new(value).expect("CF string must not contain NUL bytes");
+    unsafe { CFStringCreateWithCString(std::ptr::null(), value.as_ptr(), CF_STRING_ENCODING_UTF8) }
+}
+
+/// Creates a CoreFoundation number owned by the caller.
+fn cf_number(value: i32) -> CFNumberRef {
+    unsafe {
+        CFNumberCreate(
+            std::ptr::null(),
+            CF_NUMBER_SINT32_TYPE,
+            &value as *const i32 as *const c_void,
+        )
+    }
+}
+
+fn device_id(device: IOHIDDeviceRef) -> Option<String> {
+    let location_id = unsafe {
+        let key = cf_string("LocationID");
+        let value = IOHIDDeviceGetProperty(device, key);
+        CFRelease(key);
+        if value.is_null() || CFGetTypeID(value) != CFNumberGetTypeID() {
+            return None;
+        }
+        let mut location_id = 0i64;
+        if CFNumberGetValue(
+            value,
+            CF_NUMBER_SINT64_TYPE,
+            &mut location_id as *mut i64 as *mut c_void,
+        ) == 0
+        {
+            return None;
+        }
+        location_id
+    };
+    Some(format!("{location_id:#x}"))
+}
+
+fn string_property(device: IOHIDDeviceRef, key: &str) -> Option<String> {
+    unsafe {
+        let key = cf_string(key);
+        let value = IOHIDDeviceGetProperty(device, key);
+        CFRelease(key);
+        if value.is_null() || CFGetTypeID(value) != CFStringGetTypeID() {
+            return None;
+        }
+        let mut buf = [0 as c_char; 256];
+        if CFStringGetCString(
+            value,
+            buf.as_mut_ptr(),
+            buf.len() as CFIndex,
+            CF_STRING_ENCODING_UTF8,
+        ) == 0
+        {
+            return None;
+        }
+        let name = CStr::from_ptr(buf.as_ptr())
+            .to_string_lossy()
+            .trim()
+            .to_string();
+        (!name.is_empty()).then_some(name)
+    }
+}
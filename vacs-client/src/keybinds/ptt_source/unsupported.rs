@@ -0,0 +1,25 @@
+use super::{ExternalPttSource, PttDevice};
+use crate::keybinds::{KeyEvent, KeybindsError};
+use keyboard_types::Code;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Debug)]
+pub struct UnsupportedJoystickSource;
+
+impl UnsupportedJoystickSource {
+    pub fn new(_device: String, _button: u8) -> Self {
+        Self
+    }
+
+    pub fn list_devices() -> Vec<PttDevice> {
+        Vec::new()
+    }
+}
+
+impl ExternalPttSource for UnsupportedJoystickSource {
+    fn start(self, _code: Code, _tx: UnboundedSender<KeyEvent>) -> Result<(), KeybindsError> {
+        Err(KeybindsError::Listener(
+            "Joystick PTT sources are not supported on this platform".to_string(),
+        ))
+    }
+}
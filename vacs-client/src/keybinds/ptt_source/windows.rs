@@ -0,0 +1,130 @@
+use super::{ButtonTracker, ExternalPttSource, PttDevice};
+use crate::keybinds::{KeyEvent, KeybindsError};
+use keyboard_types::Code;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use windows::Win32::Media::Multimedia::{
+    JOYCAPSW, JOYINFOEX, joyGetDevCapsW, joyGetNumDevs, joyGetPosEx,
+};
+
+const JOYERR_NOERROR: u32 = 0;
+const JOY_RETURNBUTTONS: u32 = 0x0000_0080;
+/// WinMM reports the state of up to 32 buttons as a bitmask.
+const MAX_BUTTONS: u8 = 32;
+/// WinMM doesn't notify about button changes, so the device is polled instead. 5 ms keeps the
+/// added latency well below a single audio frame.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Joystick source using the WinMM joystick API, polling the buttons of a game controller.
+///
+/// Devices are identified by their WinMM joystick ID (e.g. `0`).
+#[derive(Debug)]
+pub struct WindowsJoystickSource {
+    device: String,
+    button: u8,
+}
+
+impl WindowsJoystickSource {
+    pub fn new(device: String, button: u8) -> Self {
+        Self { device, button }
+    }
+
+    pub fn list_devices() -> Vec<PttDevice> {
+        let count = unsafe { joyGetNumDevs() };
+        (0..count)
+            // Unconnected joystick IDs still report their capabilities, only query connected ones.
+            .filter(|id| read_buttons(*id).is_ok())
+            .map(|id| PttDevice {
+                id: id.to_string(),
+                name: device_name(id),
+            })
+            .collect()
+    }
+}
+
+impl ExternalPttSource for WindowsJoystickSource {
+    fn start(self, code: Code, tx: UnboundedSender<KeyEvent>) -> Result<(), KeybindsError> {
+        let id = self.device.parse::<u32>().map_err(|_| {
+            KeybindsError::Listener(format!("Invalid joystick device {}", self.device))
+        })?;
+        if self.button >= MAX_BUTTONS {
+            return Err(KeybindsError::Listener(format!(
+                "Joystick button {} is out of range, at most {MAX_BUTTONS} buttons are supported",
+                self.button
+            )));
+        }
+        read_buttons(id).map_err(|err| {
+            KeybindsError::Listener(format!(
+                "Failed to open joystick device {}: error {err}",
+                self.device
+            ))
+        })?;
+
+        std::thread::Builder::new()
+            .name("vacs-joystick-ptt".to_string())
+            .spawn(move || {
+                log::debug!(
+                    "Joystick PTT source started: device={}, button={}",
+                    self.device,
+                    self.button
+                );
+
+                let mut tracker =
+                    ButtonTracker::new(code, format!("Joystick button {}", self.button), tx);
+                let mask = 1u32 << self.button;
+                loop {
+                    let buttons = match read_buttons(id) {
+                        Ok(buttons) => buttons,
+                        Err(err) => {
+                            log::warn!(
+                                "Failed to read from joystick device {}: error {err}",
+                                self.device
+                            );
+                            break;
+                        }
+                    };
+
+                    if !tracker.update(buttons & mask != 0) {
+                        break;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+
+                log::trace!("Joystick PTT source for {} finished", self.device);
+            })
+            .map_err(|err| {
+                KeybindsError::Listener(format!("Failed to spawn joystick thread: {err}"))
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Reads the button bitmask of joystick `id`, returning the WinMM error code on failure, e.g. if
+/// the device was unplugged.
+fn read_buttons(id: u32) -> Result<u32, u32> {
+    let mut info = JOYINFOEX {
+        dwSize: size_of::<JOYINFOEX>() as u32,
+        dwFlags: JOY_RETURNBUTTONS,
+        ..Default::default()
+    };
+    match unsafe { joyGetPosEx(id, &mut info) } {
+        JOYERR_NOERROR => Ok(info.dwButtons),
+        err => Err(err),
+    }
+}
+
+fn device_name(id: u32) -> Option<String> {
+    let mut caps = JOYCAPSW::default();
+    let res = unsafe { joyGetDevCapsW(id as usize, &mut caps, size_of::<JOYCAPSW>() as u32) };
+    if res != JOYERR_NOERROR {
+        return None;
+    }
+    let len = caps
+        .szPname
+        .iter()
+        .position(|c| *c == 0)
+        .unwrap_or(caps.szPname.len());
+    let name = String::from_utf16_lossy(&caps.szPname[..len]);
+    (!name.trim().is_empty()).then(|| name.trim().to_string())
+}
@@ -16,6 +16,7 @@ use crate::app::state::http::HttpState;
 use crate::app::state::keybinds::AppStateKeybindsExt;
 use crate::app::state::{AppState, AppStateInner};
 use crate::audio::manager::AudioManagerHandle;
+use crate::auth::retry::AuthCircuitBreaker;
 use crate::build::VersionInfo;
 use crate::config::{CLIENT_SETTINGS_FILE_NAME, Persistable, PersistedClientConfig};
 use crate::error::{StartupError, StartupErrorExt};
@@ -24,6 +25,7 @@ use crate::platform::Capabilities;
 use tauri::{App, Manager, RunEvent, WindowEvent};
 use tauri_plugin_deep_link::DeepLinkExt;
 use tokio::sync::Mutex as TokioMutex;
+use vacs_signaling::auth::AuthGrant;
 
 pub fn run() {
     tauri::Builder::default()
@@ -87,8 +89,11 @@ pub fn run() {
                 let transmit_config = state.config.client.transmit_config.clone();
                 let call_control_config = state.config.client.keybinds.clone();
                 let keybind_engine = state.keybind_engine_handle();
+                let auth_grant = AuthGrant::new(state.config.backend.auth_max_age());
 
                 app.manage::<HttpState>(HttpState::new(app.handle())?);
+                app.manage::<AuthGrant>(auth_grant);
+                app.manage::<AuthCircuitBreaker>(AuthCircuitBreaker::default());
                 app.manage::<AudioManagerHandle>(state.audio_manager_handle());
                 app.manage::<AppState>(TokioMutex::new(state));
 
@@ -136,12 +141,15 @@ pub fn run() {
             app::commands::app_unload_test_profile,
             app::commands::app_update,
             audio::commands::audio_get_devices,
+            audio::commands::audio_check_readiness,
             audio::commands::audio_get_hosts,
             audio::commands::audio_get_volumes,
             audio::commands::audio_play_ui_click,
             audio::commands::audio_set_device,
             audio::commands::audio_set_host,
+            audio::commands::audio_set_jitter_buffer_depth,
             audio::commands::audio_set_radio_prio,
+            audio::commands::audio_set_suppress_idle_audio,
             audio::commands::audio_set_volume,
             audio::commands::audio_start_input_level_meter,
             audio::commands::audio_stop_input_level_meter,
@@ -150,12 +158,15 @@ pub fn run() {
             auth::commands::auth_open_oauth_url,
             keybinds::commands::keybinds_get_external_binding,
             keybinds::commands::keybinds_get_keybinds_config,
+            keybinds::commands::keybinds_get_ptt_source,
             keybinds::commands::keybinds_get_radio_config,
             keybinds::commands::keybinds_get_radio_state,
             keybinds::commands::keybinds_get_transmit_config,
+            keybinds::commands::keybinds_list_ptt_devices,
             keybinds::commands::keybinds_open_system_shortcuts_settings,
             keybinds::commands::keybinds_reconnect_radio,
             keybinds::commands::keybinds_set_binding,
+            keybinds::commands::keybinds_set_ptt_source,
             keybinds::commands::keybinds_set_radio_config,
             keybinds::commands::keybinds_set_transmit_config,
             signaling::commands::signaling_accept_call,
@@ -163,8 +174,15 @@ pub fn run() {
             signaling::commands::signaling_connect,
             signaling::commands::signaling_disconnect,
             signaling::commands::signaling_end_call,
+            signaling::commands::signaling_list_active_calls,
+            signaling::commands::signaling_get_dataset_info,
+            signaling::commands::signaling_get_station_hierarchy,
+            signaling::commands::signaling_get_station_coverage,
+            signaling::commands::signaling_set_active_profile,
+            signaling::commands::signaling_set_monitored_frequencies,
             signaling::commands::signaling_get_ignored_clients,
             signaling::commands::signaling_remove_ignored_client,
+            signaling::commands::signaling_start_broadcast_call,
             signaling::commands::signaling_start_call,
             signaling::commands::signaling_terminate,
         ])
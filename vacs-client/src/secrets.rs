@@ -4,6 +4,7 @@ use anyhow::Context;
 use base64::prelude::*;
 use keyring::Entry;
 use keyring::error::Error::NoEntry;
+use vacs_signaling::protocol::secret::Secret;
 
 pub enum SecretKey {
     CookieStoreEncryptionKey,
@@ -18,9 +19,9 @@ impl SecretKey {
 }
 
 #[allow(dead_code)]
-pub fn get(key: SecretKey) -> anyhow::Result<Option<String>> {
+pub fn get(key: SecretKey) -> anyhow::Result<Option<Secret>> {
     match entry_for_key(key)?.get_password() {
-        Ok(password) => Ok(Some(password)),
+        Ok(password) => Ok(Some(password.into())),
         Err(NoEntry) => Ok(None),
         Err(err) => Err(anyhow::anyhow!(err).context("Failed to get password")),
     }
@@ -39,9 +40,9 @@ pub fn get_binary(key: SecretKey) -> anyhow::Result<Option<Vec<u8>>> {
 }
 
 #[allow(dead_code)]
-pub fn set(key: SecretKey, value: &str) -> anyhow::Result<()> {
+pub fn set(key: SecretKey, value: &Secret) -> anyhow::Result<()> {
     entry_for_key(key)?
-        .set_password(value)
+        .set_password(value.expose())
         .context("Failed to set password")
 }
 
@@ -1,10 +1,13 @@
 use crate::app::state::http::HttpState;
+use crate::auth::retry::AuthCircuitBreaker;
 use crate::config::BackendEndpoint;
 use async_trait::async_trait;
-use tauri::{AppHandle, Manager};
-use vacs_signaling::auth::TokenProvider;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use vacs_signaling::auth::{AuthGrant, TokenProvider};
 use vacs_signaling::error::SignalingError;
 use vacs_signaling::protocol::http::ws::WebSocketToken;
+use vacs_signaling::protocol::secret::Secret;
 
 #[derive(Debug, Clone)]
 pub struct TauriTokenProvider {
@@ -19,12 +22,25 @@ impl TauriTokenProvider {
 
 #[async_trait]
 impl TokenProvider for TauriTokenProvider {
-    async fn get_token(&self) -> Result<String, SignalingError> {
+    async fn get_token(&self) -> Result<Secret, SignalingError> {
         log::debug!("Retrieving WebSocket auth token");
         let http_state = self.handle.state::<HttpState>();
 
-        let token = http_state
-            .http_get::<WebSocketToken>(BackendEndpoint::WsToken, None)
+        let auth_grant = self.handle.state::<AuthGrant>();
+        if let Err(err) = auth_grant.check() {
+            log::info!("Auth grant exceeded maximum age, forcing reauthentication");
+            auth_grant.clear();
+            if let Err(err) = http_state.clear_cookie_store() {
+                log::warn!("Failed to clear cookie store: {err:?}");
+            }
+            self.handle.emit("auth:unauthenticated", Value::Null).ok();
+            return Err(err);
+        }
+
+        let token = self
+            .handle
+            .state::<AuthCircuitBreaker>()
+            .call(|| http_state.http_get::<WebSocketToken>(BackendEndpoint::WsToken, None))
             .await
             .map_err(|err| SignalingError::ProtocolError(err.to_string()))?
             .token;
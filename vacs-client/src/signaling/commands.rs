@@ -10,7 +10,10 @@ use crate::error::{Error, HandleUnauthorizedExt};
 use std::collections::HashSet;
 use tauri::{AppHandle, Manager, State};
 use vacs_signaling::protocol::http::webrtc::IceConfig;
-use vacs_signaling::protocol::vatsim::{ClientId, PositionId};
+use vacs_signaling::protocol::profile::{ActiveProfile, ProfileId};
+use vacs_signaling::protocol::vatsim::{ClientId, PositionId, StationId};
+use vacs_signaling::protocol::ws::client;
+use vacs_signaling::protocol::ws::client::ClientMessage;
 use vacs_signaling::protocol::ws::shared;
 use vacs_signaling::protocol::ws::shared::{CallId, CallSource, CallTarget};
 
@@ -85,6 +88,8 @@ pub async fn signaling_start_call(
 
     let mut state = app_state.lock().await;
 
+    let route = state.config.audio.call_output_routing.route_target(&target);
+
     let call_id = CallId::new();
     state
         .send_signaling_message(shared::CallInvite {
@@ -99,6 +104,7 @@ pub async fn signaling_start_call(
         refresh_ice_config(&http_state, &mut state).await;
     }
 
+    state.set_call_output_route(call_id, route);
     state.start_unanswered_call_timer(&app, &call_id);
     state.set_outgoing_call_id(Some(call_id));
 
@@ -107,6 +113,34 @@ pub async fn signaling_start_call(
     Ok(call_id)
 }
 
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_start_broadcast_call(
+    app_state: State<'_, AppState>,
+    source: CallSource,
+    station_ids: Vec<StationId>,
+) -> Result<CallId, Error> {
+    log::debug!("Starting broadcast call to {station_ids:?} as {source:?}");
+
+    let mut state = app_state.lock().await;
+
+    let call_id = CallId::new();
+    state.start_broadcast(call_id)?;
+    if let Err(err) = state
+        .send_signaling_message(shared::BroadcastCall {
+            call_id,
+            source,
+            station_ids,
+        })
+        .await
+    {
+        state.cleanup_call(&call_id).await;
+        return Err(err);
+    }
+
+    Ok(call_id)
+}
+
 #[tauri::command]
 #[vacs_macros::log_err]
 pub async fn signaling_accept_call(
@@ -117,7 +151,7 @@ pub async fn signaling_accept_call(
     log::debug!("Accepting call {call_id:?}");
 
     let mut state = app_state.lock().await;
-    state.accept_call(&app, Some(call_id)).await?;
+    state.accept_call(&app, Some(call_id), false).await?;
 
     Ok(())
 }
@@ -137,6 +171,87 @@ pub async fn signaling_end_call(
     Ok(())
 }
 
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_list_active_calls(app_state: State<'_, AppState>) -> Result<(), Error> {
+    log::debug!("Requesting list of active calls");
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(ClientMessage::ListActiveCalls)
+        .await
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_get_dataset_info(app_state: State<'_, AppState>) -> Result<(), Error> {
+    log::debug!("Requesting dataset info");
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(ClientMessage::GetDatasetInfo)
+        .await
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_get_station_hierarchy(app_state: State<'_, AppState>) -> Result<(), Error> {
+    log::debug!("Requesting station hierarchy");
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(ClientMessage::GetStationHierarchy)
+        .await
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_get_station_coverage(
+    app_state: State<'_, AppState>,
+    station_id: StationId,
+) -> Result<(), Error> {
+    log::debug!("Requesting coverage of station {station_id}");
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(ClientMessage::GetStationCoverage(
+            client::GetStationCoverage { station_id },
+        ))
+        .await
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_set_active_profile(
+    app_state: State<'_, AppState>,
+    profile_id: Option<ProfileId>,
+) -> Result<(), Error> {
+    log::debug!("Requesting active profile switch to {profile_id:?}");
+
+    let profile = profile_id
+        .map(ActiveProfile::Specific)
+        .unwrap_or(ActiveProfile::Custom);
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(client::SetProfile { profile })
+        .await
+}
+
+#[tauri::command]
+#[vacs_macros::log_err]
+pub async fn signaling_set_monitored_frequencies(
+    app_state: State<'_, AppState>,
+    frequencies: Vec<String>,
+) -> Result<(), Error> {
+    log::debug!("Requesting monitored frequencies update to {frequencies:?}");
+
+    let mut state = app_state.lock().await;
+    state
+        .send_signaling_message(client::SetMonitoredFrequencies { frequencies })
+        .await
+}
+
 #[tauri::command]
 #[vacs_macros::log_err]
 pub async fn signaling_get_ignored_clients(
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "http")]
 pub mod auth;
+#[cfg(all(feature = "http", feature = "vatsim"))]
+pub mod coverage;
 #[cfg(feature = "http")]
 pub mod version;
 #[cfg(feature = "http-webrtc")]
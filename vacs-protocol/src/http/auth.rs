@@ -1,3 +1,4 @@
+use crate::secret::Secret;
 use crate::vatsim::ClientId;
 use serde::{Deserialize, Serialize};
 
@@ -8,7 +9,7 @@ pub struct InitVatsimLogin {
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct AuthExchangeToken {
-    pub code: String,
+    pub code: Secret,
     pub state: String,
 }
 
@@ -0,0 +1,263 @@
+use crate::vatsim::{ClientId, PositionId, StationChange, StationId};
+use serde::{Deserialize, Serialize};
+
+/// Current coverage state as returned by `GET /coverage`.
+///
+/// All lists are sorted by their ID without duplicates, allowing lookups via
+/// [`CoverageSnapshot::station`], [`CoverageSnapshot::position`] and
+/// [`CoverageSnapshot::last_controller`] to binary search instead of scanning. Use
+/// [`CoverageSnapshot::new`] to construct a snapshot upholding this invariant.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageSnapshot {
+    pub stations: Vec<StationCoverage>,
+    pub positions: Vec<PositionCoverage>,
+    /// Positions that recently stopped controlling a station, as long as retained by the server.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub last_controlled_by: Vec<LastStationController>,
+}
+
+/// An online station together with the position currently controlling it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StationCoverage {
+    pub station_id: StationId,
+    pub position_id: PositionId,
+}
+
+/// An online position together with the vacs clients staffing it. VATSIM-only positions are
+/// staffed on VATSIM, but not by any vacs client, and thus can't be called.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionCoverage {
+    pub position_id: PositionId,
+    pub client_ids: Vec<ClientId>,
+    pub vatsim_only: bool,
+}
+
+/// The position last controlling a station, before it went offline or was handed off to another
+/// position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastStationController {
+    pub station_id: StationId,
+    pub position_id: PositionId,
+    /// Unix timestamp (seconds) at which the position stopped controlling the station.
+    pub until: u64,
+}
+
+/// Event emitted by `GET /coverage/stream` as a line of newline-delimited JSON.
+///
+/// Every stream starts with a [`CoverageStreamEvent::Snapshot`] of the coverage at the time of
+/// subscribing, followed by events as they happen. Changes racing the snapshot might be reflected
+/// in both, so consumers should apply changes idempotently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CoverageStreamEvent {
+    Snapshot(CoverageSnapshot),
+    #[serde(rename_all = "camelCase")]
+    ClientConnected {
+        client_id: ClientId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position_id: Option<PositionId>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ClientDisconnected {
+        client_id: ClientId,
+    },
+    /// Station changes are emitted with their timestamp, without an additional tag.
+    #[serde(untagged)]
+    StationChange(TimestampedStationChange),
+}
+
+/// A [`StationChange`] together with the time the server applied it to its coverage state.
+///
+/// Only used by the coverage endpoints, station changes broadcast to clients are sent without a
+/// timestamp to keep these frequent messages small. Serialized as the station change with an
+/// additional `at` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampedStationChange {
+    #[serde(flatten)]
+    pub change: StationChange,
+    /// Unix timestamp (milliseconds) at which the change was applied.
+    pub at: u64,
+}
+
+impl CoverageSnapshot {
+    /// Creates a snapshot, sorting stations and positions by their ID.
+    pub fn new(mut stations: Vec<StationCoverage>, mut positions: Vec<PositionCoverage>) -> Self {
+        stations.sort_by(|a, b| a.station_id.cmp(&b.station_id));
+        positions.sort_by(|a, b| a.position_id.cmp(&b.position_id));
+        Self {
+            stations,
+            positions,
+            last_controlled_by: Vec::new(),
+        }
+    }
+
+    /// Sets the positions that recently stopped controlling a station, sorting them by station ID.
+    pub fn with_last_controlled_by(
+        mut self,
+        mut last_controlled_by: Vec<LastStationController>,
+    ) -> Self {
+        last_controlled_by.sort_by(|a, b| a.station_id.cmp(&b.station_id));
+        self.last_controlled_by = last_controlled_by;
+        self
+    }
+
+    /// Returns the coverage of the given station, or `None` if it is offline.
+    pub fn station(&self, station_id: &StationId) -> Option<&StationCoverage> {
+        self.stations
+            .binary_search_by(|station| station.station_id.cmp(station_id))
+            .ok()
+            .map(|idx| &self.stations[idx])
+    }
+
+    /// Returns the coverage of the given position, or `None` if it is offline.
+    pub fn position(&self, position_id: &PositionId) -> Option<&PositionCoverage> {
+        self.positions
+            .binary_search_by(|position| position.position_id.cmp(position_id))
+            .ok()
+            .map(|idx| &self.positions[idx])
+    }
+
+    /// Returns the position last controlling the given station, or `None` if not retained.
+    pub fn last_controller(&self, station_id: &StationId) -> Option<&LastStationController> {
+        self.last_controlled_by
+            .binary_search_by(|last| last.station_id.cmp(station_id))
+            .ok()
+            .map(|idx| &self.last_controlled_by[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn station(station_id: &str, position_id: &str) -> StationCoverage {
+        StationCoverage {
+            station_id: StationId::from(station_id),
+            position_id: PositionId::from(position_id),
+        }
+    }
+
+    fn position(position_id: &str, client_ids: &[&str]) -> PositionCoverage {
+        PositionCoverage {
+            position_id: PositionId::from(position_id),
+            client_ids: client_ids.iter().map(|id| ClientId::from(*id)).collect(),
+            vatsim_only: client_ids.is_empty(),
+        }
+    }
+
+    #[test]
+    fn snapshot_lookups() {
+        let snapshot = CoverageSnapshot::new(
+            vec![
+                station("LOWW_TWR", "LOWW_TWR"),
+                station("LOWW_APP", "LOVV_CTR"),
+                station("LOWW_GND", "LOWW_TWR"),
+            ],
+            vec![
+                position("LOWW_TWR", &["client1"]),
+                position("LOVV_CTR", &[]),
+            ],
+        );
+
+        assert_eq!(
+            snapshot.station(&StationId::from("LOWW_APP")),
+            Some(&station("LOWW_APP", "LOVV_CTR"))
+        );
+        assert_eq!(
+            snapshot.station(&StationId::from("LOWW_GND")),
+            Some(&station("LOWW_GND", "LOWW_TWR"))
+        );
+        assert_eq!(snapshot.station(&StationId::from("LOWW_DEL")), None);
+        assert_eq!(
+            snapshot.position(&PositionId::from("LOVV_CTR")),
+            Some(&position("LOVV_CTR", &[]))
+        );
+        assert_eq!(snapshot.position(&PositionId::from("LOWW_APP")), None);
+    }
+
+    #[test]
+    fn last_controller_lookup() {
+        let last = |station_id: &str, position_id: &str| LastStationController {
+            station_id: StationId::from(station_id),
+            position_id: PositionId::from(position_id),
+            until: 1_700_000_000,
+        };
+        let snapshot = CoverageSnapshot::new(vec![station("LOWW_APP", "LOVV_CTR")], vec![])
+            .with_last_controlled_by(vec![
+                last("LOWW_TWR", "LOWW_TWR"),
+                last("LOWW_APP", "LOWW_APP"),
+            ]);
+
+        assert_eq!(
+            snapshot.last_controller(&StationId::from("LOWW_TWR")),
+            Some(&last("LOWW_TWR", "LOWW_TWR"))
+        );
+        assert_eq!(
+            snapshot.last_controller(&StationId::from("LOWW_APP")),
+            Some(&last("LOWW_APP", "LOWW_APP"))
+        );
+        assert_eq!(snapshot.last_controller(&StationId::from("LOWW_GND")), None);
+
+        // Snapshots without any last controllers omit the field entirely
+        let json = serde_json::to_value(CoverageSnapshot::default()).unwrap();
+        assert_eq!(json, serde_json::json!({"stations": [], "positions": []}));
+    }
+
+    #[test]
+    fn stream_event_serialization() {
+        let events = [
+            (
+                CoverageStreamEvent::Snapshot(CoverageSnapshot::new(
+                    vec![station("LOWW_TWR", "LOWW_TWR")],
+                    vec![],
+                )),
+                r#"{"snapshot":{"stations":[{"stationId":"LOWW_TWR","positionId":"LOWW_TWR"}],"positions":[]}}"#,
+            ),
+            (
+                CoverageStreamEvent::ClientConnected {
+                    client_id: ClientId::from("client1"),
+                    position_id: Some(PositionId::from("LOWW_TWR")),
+                },
+                r#"{"clientConnected":{"clientId":"client1","positionId":"LOWW_TWR"}}"#,
+            ),
+            (
+                CoverageStreamEvent::ClientDisconnected {
+                    client_id: ClientId::from("client1"),
+                },
+                r#"{"clientDisconnected":{"clientId":"client1"}}"#,
+            ),
+            (
+                CoverageStreamEvent::StationChange(TimestampedStationChange {
+                    change: StationChange::Online {
+                        station_id: StationId::from("LOWW_TWR"),
+                        position_id: PositionId::from("LOWW_TWR"),
+                    },
+                    at: 1_700_000_000_000,
+                }),
+                r#"{"online":{"stationId":"LOWW_TWR","positionId":"LOWW_TWR"},"at":1700000000000}"#,
+            ),
+            (
+                CoverageStreamEvent::StationChange(TimestampedStationChange {
+                    change: StationChange::Offline {
+                        station_id: StationId::from("LOWW_TWR"),
+                        reason: None,
+                    },
+                    at: 1_700_000_000_500,
+                }),
+                r#"{"offline":{"stationId":"LOWW_TWR"},"at":1700000000500}"#,
+            ),
+        ];
+
+        for (event, json) in events {
+            assert_eq!(serde_json::to_string(&event).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<CoverageStreamEvent>(json).unwrap(),
+                event
+            );
+        }
+    }
+}
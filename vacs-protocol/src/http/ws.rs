@@ -1,6 +1,7 @@
+use crate::secret::Secret;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct WebSocketToken {
-    pub token: String,
+    pub token: Secret,
 }
@@ -2,6 +2,7 @@
 pub mod http;
 #[cfg(feature = "profile")]
 pub mod profile;
+pub mod secret;
 #[cfg(feature = "vatsim")]
 pub mod vatsim;
 #[cfg(feature = "ws")]
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// A secret value such as an auth token, OAuth code or API key.
+///
+/// [`Debug`](std::fmt::Debug) and [`Display`](std::fmt::Display) redact the value, only showing
+/// a short prefix for longer secrets and its length, so secrets can't accidentally end up in logs
+/// via a `?msg` or an error message. The value itself has to be accessed explicitly using
+/// [`Secret::expose`]. Serialization is transparent, a secret is (de)serialized as a plain string.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Number of leading characters shown when formatting a secret.
+    const VISIBLE_PREFIX_LEN: usize = 4;
+    /// Minimum length of a secret before its prefix is shown, shorter secrets are fully hidden.
+    const MIN_LEN_FOR_PREFIX: usize = 16;
+
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Returns the unredacted secret value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the secret, returning the unredacted value.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn prefix(&self) -> &str {
+        if self.0.chars().count() < Self::MIN_LEN_FOR_PREFIX {
+            return "";
+        }
+        match self.0.char_indices().nth(Self::VISIBLE_PREFIX_LEN) {
+            Some((idx, _)) => &self.0[..idx],
+            None => &self.0,
+        }
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Secret({}***, len={})",
+            self.prefix(),
+            self.0.chars().count()
+        )
+    }
+}
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}***", self.prefix())
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0198c2f4-5e1a-7b3c-9d2e-4f6a8b0c1d2e";
+
+    #[test]
+    fn debug_redacts_value() {
+        let secret = Secret::from(TOKEN);
+        assert_eq!(format!("{secret:?}"), "Secret(0198***, len=36)");
+        assert_eq!(format!("{secret:#?}"), "Secret(0198***, len=36)");
+        assert_eq!(
+            format!("{:?}", Some(secret)),
+            "Some(Secret(0198***, len=36))"
+        );
+    }
+
+    #[test]
+    fn display_redacts_value() {
+        assert_eq!(Secret::from(TOKEN).to_string(), "0198***");
+    }
+
+    #[test]
+    fn short_secrets_fully_hidden() {
+        assert_eq!(
+            format!("{:?}", Secret::from("token1")),
+            "Secret(***, len=6)"
+        );
+        assert_eq!(Secret::from("token1").to_string(), "***");
+        assert_eq!(format!("{:?}", Secret::default()), "Secret(***, len=0)");
+    }
+
+    #[test]
+    fn prefix_respects_char_boundaries() {
+        let secret = Secret::from("äöüßäöüßäöüßäöüß");
+        assert_eq!(format!("{secret:?}"), "Secret(äöüß***, len=16)");
+    }
+
+    #[test]
+    fn serializes_transparently() {
+        let secret = Secret::from(TOKEN);
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, format!("\"{TOKEN}\""));
+        assert_eq!(serde_json::from_str::<Secret>(&json).unwrap(), secret);
+        assert_eq!(secret.expose(), TOKEN);
+    }
+}
@@ -42,9 +42,34 @@ pub enum StationChange {
     Offline {
         /// The ID of the station that went offline.
         station_id: StationId,
+        /// Why the station went offline, if not caused by coverage changes.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<StationOfflineReason>,
     },
 }
 
+/// Reason for a station going offline while still being covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StationOfflineReason {
+    /// The station was temporarily closed at runtime and can't be called until reopened.
+    TemporarilyClosed,
+    /// The station is covered by a controller not connected to vacs and thus can't be called.
+    /// Only sent by servers configured to show such stations.
+    VatsimOnly,
+}
+
+impl StationChange {
+    /// The ID of the station this change applies to.
+    pub fn station_id(&self) -> &StationId {
+        match self {
+            StationChange::Online { station_id, .. }
+            | StationChange::Handoff { station_id, .. }
+            | StationChange::Offline { station_id, .. } => station_id,
+        }
+    }
+}
+
 impl ClientId {
     pub fn new(id: impl Into<String>) -> Self {
         Self(id.into())
@@ -235,6 +260,7 @@ where
             },
             (Some(_), None) => Self::Offline {
                 station_id: station_id.into(),
+                reason: None,
             },
             (Some(from), Some(to)) => Self::Handoff {
                 station_id: station_id.into(),
@@ -1,12 +1,18 @@
 pub mod auth;
 pub mod calls;
+pub mod network;
+pub mod profile;
+pub mod transmit;
 
 pub use auth::*;
 pub use calls::*;
+pub use network::*;
+pub use profile::*;
+pub use transmit::*;
 
 use crate::ws::shared::{
-    CallAccept, CallEnd, CallError, CallInvite, Error, WebrtcAnswer, WebrtcIceCandidate,
-    WebrtcOffer,
+    BroadcastCall, CallAccept, CallEnd, CallError, CallInvite, Error, WebrtcAnswer,
+    WebrtcIceCandidate, WebrtcOffer,
 };
 use serde::{Deserialize, Serialize};
 
@@ -20,11 +26,19 @@ pub enum ClientMessage {
     CallEnd(CallEnd),
     CallReject(CallReject),
     CallError(CallError),
+    BroadcastCall(BroadcastCall),
     WebrtcOffer(WebrtcOffer),
     WebrtcAnswer(WebrtcAnswer),
     WebrtcIceCandidate(WebrtcIceCandidate),
     ListClients,
     ListStations,
+    ListActiveCalls,
+    GetDatasetInfo,
+    GetStationHierarchy,
+    GetStationCoverage(GetStationCoverage),
+    TransmitState(TransmitState),
+    SetProfile(SetProfile),
+    SetMonitoredFrequencies(SetMonitoredFrequencies),
     Disconnect,
     Error(Error),
 }
@@ -51,11 +65,19 @@ impl ClientMessage {
             ClientMessage::CallEnd(_) => "CallEnd",
             ClientMessage::CallReject(_) => "CallReject",
             ClientMessage::CallError(_) => "CallError",
+            ClientMessage::BroadcastCall(_) => "BroadcastCall",
             ClientMessage::WebrtcOffer(_) => "WebrtcOffer",
             ClientMessage::WebrtcAnswer(_) => "WebrtcAnswer",
             ClientMessage::WebrtcIceCandidate(_) => "WebrtcIceCandidate",
             ClientMessage::ListClients => "ListClients",
             ClientMessage::ListStations => "ListStations",
+            ClientMessage::ListActiveCalls => "ListActiveCalls",
+            ClientMessage::GetDatasetInfo => "GetDatasetInfo",
+            ClientMessage::GetStationHierarchy => "GetStationHierarchy",
+            ClientMessage::GetStationCoverage(_) => "GetStationCoverage",
+            ClientMessage::TransmitState(_) => "TransmitState",
+            ClientMessage::SetProfile(_) => "SetProfile",
+            ClientMessage::SetMonitoredFrequencies(_) => "SetMonitoredFrequencies",
             ClientMessage::Disconnect => "Disconnect",
             ClientMessage::Error(_) => "Error",
         }
@@ -1,14 +1,27 @@
+use crate::secret::Secret;
 use crate::vatsim::PositionId;
 use crate::ws::client::ClientMessage;
+use crate::ws::shared::Codec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Login {
-    pub token: String,
+    pub token: Secret,
     pub protocol_version: String,
     pub custom_profile: bool,
     pub position_id: Option<PositionId>,
+    /// Codecs supported by the client, in order of preference. The server selects the first one
+    /// it allows, falling back to JSON if the list is empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codecs: Vec<Codec>,
+    /// Version of the client application, checked against the server's minimum client version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_version: Option<String>,
+    /// Free-form labels describing the connection (e.g. the client platform or an event name),
+    /// allowing operators to filter and group sessions. Not shared with other clients.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl From<Login> for ClientMessage {
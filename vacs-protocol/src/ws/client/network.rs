@@ -0,0 +1,35 @@
+use crate::vatsim::StationId;
+use crate::ws::client::ClientMessage;
+use serde::{Deserialize, Serialize};
+
+/// Requests the current coverage of a single station, answered with a
+/// [`StationCoverage`](crate::ws::server::StationCoverage).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStationCoverage {
+    pub station_id: StationId,
+}
+
+impl From<GetStationCoverage> for ClientMessage {
+    fn from(value: GetStationCoverage) -> Self {
+        Self::GetStationCoverage(value)
+    }
+}
+
+/// Replaces the frequencies the client monitors in addition to those of its position, e.g. when
+/// combining positions during a session. Answered with the accepted
+/// [`MonitoredFrequencies`](crate::ws::server::MonitoredFrequencies), an empty list clears them.
+///
+/// Invalid frequencies are rejected with
+/// [`crate::ws::shared::ErrorReason::InvalidFrequencies`], keeping the previous ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMonitoredFrequencies {
+    pub frequencies: Vec<String>,
+}
+
+impl From<SetMonitoredFrequencies> for ClientMessage {
+    fn from(value: SetMonitoredFrequencies) -> Self {
+        Self::SetMonitoredFrequencies(value)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::profile::{ActiveProfile, ProfileId};
+use crate::ws::client::ClientMessage;
+use serde::{Deserialize, Serialize};
+
+/// Requests switching the active profile of the client.
+///
+/// The profile must be one of the profiles advertised in
+/// [`crate::ws::server::SessionInfo::available_profiles`], otherwise the server rejects the switch
+/// with [`crate::ws::shared::ErrorReason::ProfileNotAvailable`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetProfile {
+    pub profile: ActiveProfile<ProfileId>,
+}
+
+impl From<SetProfile> for ClientMessage {
+    fn from(value: SetProfile) -> Self {
+        Self::SetProfile(value)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::vatsim::StationId;
+use crate::ws::client::ClientMessage;
+use serde::{Deserialize, Serialize};
+
+/// Notifies the server that the client started or stopped transmitting.
+///
+/// If no `station_id` is given, the state applies to all stations currently covered by the
+/// client's position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransmitState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub station_id: Option<StationId>,
+    pub transmitting: bool,
+}
+
+impl From<TransmitState> for ClientMessage {
+    fn from(value: TransmitState) -> Self {
+        Self::TransmitState(value)
+    }
+}
@@ -1,14 +1,16 @@
 pub mod auth;
 pub mod calls;
 pub mod network;
+pub mod transmit;
 
 pub use auth::*;
 pub use calls::*;
 pub use network::*;
+pub use transmit::*;
 
 use crate::ws::shared::{
-    CallAccept, CallEnd, CallError, CallInvite, Error, WebrtcAnswer, WebrtcIceCandidate,
-    WebrtcOffer,
+    BroadcastCall, CallAccept, CallEnd, CallError, CallInvite, Error, WebrtcAnswer,
+    WebrtcIceCandidate, WebrtcOffer,
 };
 use serde::{Deserialize, Serialize};
 
@@ -21,16 +23,27 @@ pub enum ServerMessage {
     CallEnd(CallEnd),
     CallCancelled(CallCancelled),
     CallError(CallError),
+    BroadcastCall(BroadcastCall),
+    BroadcastCallStarted(BroadcastCallStarted),
     WebrtcOffer(WebrtcOffer),
     WebrtcAnswer(WebrtcAnswer),
     WebrtcIceCandidate(WebrtcIceCandidate),
     ClientInfo(ClientInfo),
     SessionInfo(SessionInfo),
+    ProfileTooLarge(ProfileTooLarge),
+    PositionReassigned(PositionReassigned),
     ClientConnected(ClientConnected),
     ClientDisconnected(ClientDisconnected),
     ClientList(ClientList),
     StationList(StationList),
     StationChanges(StationChanges),
+    StationHierarchy(StationHierarchy),
+    StationCoverage(StationCoverage),
+    ActiveCallList(ActiveCallList),
+    DatasetInfo(DatasetInfo),
+    Announcement(Announcement),
+    MonitoredFrequencies(MonitoredFrequencies),
+    TransmitState(TransmitState),
     Disconnected(Disconnected),
     Error(Error),
 }
@@ -56,16 +69,27 @@ impl ServerMessage {
             ServerMessage::CallEnd(_) => "CallEnd",
             ServerMessage::CallCancelled(_) => "CallCancelled",
             ServerMessage::CallError(_) => "CallError",
+            ServerMessage::BroadcastCall(_) => "BroadcastCall",
+            ServerMessage::BroadcastCallStarted(_) => "BroadcastCallStarted",
             ServerMessage::WebrtcOffer(_) => "WebrtcOffer",
             ServerMessage::WebrtcAnswer(_) => "WebrtcAnswer",
             ServerMessage::WebrtcIceCandidate(_) => "WebrtcIceCandidate",
             ServerMessage::ClientInfo(_) => "ClientInfo",
             ServerMessage::SessionInfo(_) => "SessionInfo",
+            ServerMessage::ProfileTooLarge(_) => "ProfileTooLarge",
+            ServerMessage::PositionReassigned(_) => "PositionReassigned",
             ServerMessage::ClientConnected(_) => "ClientConnected",
             ServerMessage::ClientDisconnected(_) => "ClientDisconnected",
             ServerMessage::ClientList(_) => "ClientList",
             ServerMessage::StationList(_) => "StationList",
             ServerMessage::StationChanges(_) => "StationChanges",
+            ServerMessage::StationHierarchy(_) => "StationHierarchy",
+            ServerMessage::StationCoverage(_) => "StationCoverage",
+            ServerMessage::ActiveCallList(_) => "ActiveCallList",
+            ServerMessage::DatasetInfo(_) => "DatasetInfo",
+            ServerMessage::Announcement(_) => "Announcement",
+            ServerMessage::MonitoredFrequencies(_) => "MonitoredFrequencies",
+            ServerMessage::TransmitState(_) => "TransmitState",
             ServerMessage::Disconnected(_) => "Disconnected",
             ServerMessage::Error(_) => "Error",
         }
@@ -1,5 +1,6 @@
 use crate::vatsim::PositionId;
 use crate::ws::server::ServerMessage;
+use crate::ws::shared::Codec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +14,18 @@ pub enum LoginFailureReason {
     InvalidVatsimPosition,
     Timeout,
     IncompatibleProtocolVersion,
+    NotReady,
+    /// None of the codecs offered by the client are allowed by the server. Contains the codecs
+    /// the server accepts.
+    UnsupportedCodec(Vec<Codec>),
+    /// The facility type of the client's active VATSIM connection is not allowed to use vacs.
+    FacilityNotAllowed,
+    /// The client application is older than the minimum version accepted by the server.
+    ClientTooOld {
+        minimum: String,
+    },
+    /// The client sent more tags, or longer ones, than the server accepts.
+    InvalidTags,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +34,9 @@ pub enum DisconnectReason {
     Terminated,
     NoActiveVatsimConnection,
     AmbiguousVatsimPosition(Vec<PositionId>),
+    IdleTimeout,
+    /// The client didn't keep up with the messages sent by the server.
+    SendQueueOverflow,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
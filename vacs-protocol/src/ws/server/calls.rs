@@ -1,4 +1,4 @@
-use crate::vatsim::ClientId;
+use crate::vatsim::{ClientId, StationId};
 use crate::ws::client::CallRejectReason;
 use crate::ws::server::ServerMessage;
 use crate::ws::shared::{CallErrorReason, CallId};
@@ -12,6 +12,9 @@ pub enum CallCancelReason {
     Disconnected,
     Errored(CallErrorReason),
     Rejected(CallRejectReason),
+    /// The call wasn't answered within the server's answer timeout. Sent to the caller and all
+    /// notified clients.
+    Timeout,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +24,31 @@ pub struct CallCancelled {
     pub reason: CallCancelReason,
 }
 
+/// Confirms a started broadcast call to the broadcasting client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastCallStarted {
+    pub call_id: CallId,
+    /// Clients the broadcast was delivered to. The broadcaster is expected to send a WebRTC
+    /// offer to each of them.
+    pub recipient_ids: Vec<ClientId>,
+}
+
+/// An active call involving at least one station relevant to the requesting client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveCallInfo {
+    pub call_id: CallId,
+    /// Relevant stations controlled by either party of the call.
+    pub station_ids: Vec<StationId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveCallList {
+    pub calls: Vec<ActiveCallInfo>,
+}
+
 impl CallCancelled {
     pub fn new(call_id: CallId, reason: CallCancelReason) -> Self {
         Self { call_id, reason }
@@ -32,3 +60,15 @@ impl From<CallCancelled> for ServerMessage {
         Self::CallCancelled(value)
     }
 }
+
+impl From<BroadcastCallStarted> for ServerMessage {
+    fn from(value: BroadcastCallStarted) -> Self {
+        Self::BroadcastCallStarted(value)
+    }
+}
+
+impl From<ActiveCallList> for ServerMessage {
+    fn from(value: ActiveCallList) -> Self {
+        Self::ActiveCallList(value)
+    }
+}
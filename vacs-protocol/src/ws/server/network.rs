@@ -1,4 +1,5 @@
-use crate::profile::{ActiveProfile, Profile};
+use crate::http::webrtc::IceServer;
+use crate::profile::{ActiveProfile, Profile, ProfileId};
 use crate::vatsim::{ClientId, PositionId, StationChange, StationId};
 use crate::ws::server::ServerMessage;
 use serde::{Deserialize, Serialize};
@@ -25,6 +26,64 @@ pub struct ClientInfo {
 pub struct SessionInfo {
     pub client: ClientInfo,
     pub profile: SessionProfile,
+    /// ICE servers configured for the FIR of the client's position, taking precedence over the
+    /// globally configured servers. `None` if the FIR does not define an override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_servers: Option<Vec<IceServer>>,
+    /// Set if the client's VATSIM frequency differs from the frequency defined for its position,
+    /// allowing the controller to correct their VATSIM connection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_mismatch: Option<FrequencyMismatch>,
+    /// Set on connect if the client's position controls no stations in the loaded dataset, which
+    /// usually hints at a callsign or dataset mismatch. Only sent if enabled on the server.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub position_without_coverage: bool,
+    /// Profiles the client may switch to using [`crate::ws::client::SetProfile`]. Contains
+    /// [`ActiveProfile::Custom`] if the client may use its own custom profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub available_profiles: Vec<ActiveProfile<ProfileId>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyMismatch {
+    /// Frequency defined for the client's position in the dataset.
+    pub expected: String,
+    /// Frequency the client is currently connected with on VATSIM.
+    pub actual: String,
+}
+
+/// Sent instead of a [`SessionInfo`] whose serialized size exceeds the maximum websocket message
+/// size, as its profile is too large to be delivered. The client keeps its previous profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTooLarge {
+    pub profile_id: ProfileId,
+}
+
+/// Sent alongside an updated [`SessionInfo`] whenever the server changes the client's position,
+/// allowing the controller to be notified about coverage shifting under them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionReassigned {
+    /// Position the client was assigned to before, `None` if it had no position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<PositionId>,
+    /// Position the client is assigned to now, `None` if it no longer has a position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<PositionId>,
+    pub reason: PositionReassignmentReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PositionReassignmentReason {
+    /// The client's position no longer exists after the dataset was reloaded.
+    NetworkReload,
+    /// The client's VATSIM connection changed, e.g. its callsign or frequency.
+    VatsimConnectionChanged,
+    /// An administrator repaired the client's session, which had an inconsistent position.
+    SessionRepaired,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +91,14 @@ pub struct SessionInfo {
 pub struct StationInfo {
     pub id: StationId,
     pub own: bool,
+    /// Whether the station can be called. Stations covered solely by controllers not connected
+    /// to vacs are only listed as uncallable by servers configured to show them.
+    #[serde(default = "default_callable")]
+    pub callable: bool,
+}
+
+const fn default_callable() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,12 +125,79 @@ pub struct StationList {
     pub stations: Vec<StationInfo>,
 }
 
+/// Static structure of the stations relevant to the client, allowing them to be displayed as a tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StationHierarchy {
+    /// Stations sorted by ID.
+    pub stations: Vec<StationNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StationNode {
+    pub id: StationId,
+    /// Parent station, whose controller takes over the station if none of its own positions are
+    /// online. The parent might not be part of the hierarchy if it isn't relevant to the client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<StationId>,
+    /// Positions controlling the station, in order of priority.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controlled_by: Vec<PositionId>,
+}
+
+/// Current coverage of a single station, sent in response to a
+/// [`GetStationCoverage`](crate::ws::client::GetStationCoverage) request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StationCoverage {
+    pub station_id: StationId,
+    /// Position currently controlling the station, `None` if the station is not online.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_id: Option<PositionId>,
+    /// Whether the station can be called. Stations covered solely by controllers not connected
+    /// to vacs are only reported as uncallable by servers configured to show them.
+    #[serde(default)]
+    pub callable: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StationChanges {
     pub changes: Vec<StationChange>,
 }
 
+/// Identifies the dataset (network coverage data) currently loaded by the server, allowing clients
+/// to detect local profiles being out of date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetInfo {
+    /// Hex-encoded SHA-256 digest of the loaded dataset's content, identical for every server
+    /// loading the same dataset and changing whenever its content changes.
+    pub version: String,
+    /// Unix timestamp (in seconds) of when the dataset was loaded.
+    pub loaded_at: u64,
+}
+
+/// Server-wide announcement shown to all connected clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Announcement {
+    pub message: String,
+    /// Unix timestamp (in seconds) of a scheduled airspace change the announcement warns of, i.e.
+    /// when a new dataset is going to be loaded and coverage might shift.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airspace_change_at: Option<u64>,
+}
+
+/// Frequencies the client monitors in addition to those of its position, sent in response to a
+/// [`SetMonitoredFrequencies`](crate::ws::client::SetMonitoredFrequencies).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitoredFrequencies {
+    pub frequencies: Vec<String>,
+}
+
 impl std::fmt::Display for SessionProfile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -91,6 +225,18 @@ impl From<SessionInfo> for ServerMessage {
     }
 }
 
+impl From<ProfileTooLarge> for ServerMessage {
+    fn from(value: ProfileTooLarge) -> Self {
+        Self::ProfileTooLarge(value)
+    }
+}
+
+impl From<PositionReassigned> for ServerMessage {
+    fn from(value: PositionReassigned) -> Self {
+        Self::PositionReassigned(value)
+    }
+}
+
 impl From<ClientInfo> for ClientConnected {
     fn from(client: ClientInfo) -> Self {
         Self { client }
@@ -151,6 +297,24 @@ impl From<Vec<StationInfo>> for ServerMessage {
     }
 }
 
+impl From<Vec<StationNode>> for StationHierarchy {
+    fn from(stations: Vec<StationNode>) -> Self {
+        Self { stations }
+    }
+}
+
+impl From<StationHierarchy> for ServerMessage {
+    fn from(value: StationHierarchy) -> Self {
+        Self::StationHierarchy(value)
+    }
+}
+
+impl From<StationCoverage> for ServerMessage {
+    fn from(value: StationCoverage) -> Self {
+        Self::StationCoverage(value)
+    }
+}
+
 impl From<Vec<StationChange>> for StationChanges {
     fn from(changes: Vec<StationChange>) -> Self {
         Self { changes }
@@ -168,3 +332,21 @@ impl From<Vec<StationChange>> for ServerMessage {
         Self::StationChanges(value.into())
     }
 }
+
+impl From<DatasetInfo> for ServerMessage {
+    fn from(value: DatasetInfo) -> Self {
+        Self::DatasetInfo(value)
+    }
+}
+
+impl From<Announcement> for ServerMessage {
+    fn from(value: Announcement) -> Self {
+        Self::Announcement(value)
+    }
+}
+
+impl From<MonitoredFrequencies> for ServerMessage {
+    fn from(value: MonitoredFrequencies) -> Self {
+        Self::MonitoredFrequencies(value)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::vatsim::{ClientId, StationId};
+use crate::ws::server::ServerMessage;
+use serde::{Deserialize, Serialize};
+
+/// Broadcast to all other clients on a station whenever a client on that station starts or stops
+/// transmitting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransmitState {
+    pub client_id: ClientId,
+    pub station_id: StationId,
+    pub transmitting: bool,
+}
+
+impl From<TransmitState> for ServerMessage {
+    fn from(value: TransmitState) -> Self {
+        Self::TransmitState(value)
+    }
+}
@@ -1,7 +1,11 @@
+pub mod audio;
 pub mod calls;
+pub mod codec;
 pub mod errors;
 pub mod webrtc;
 
+pub use audio::*;
 pub use calls::*;
+pub use codec::*;
 pub use errors::*;
 pub use webrtc::*;
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+/// Audio codec configuration proposed in a [`WebrtcOffer`](super::WebrtcOffer) or selected in a
+/// [`WebrtcAnswer`](super::WebrtcAnswer).
+///
+/// The offerer lists all configurations it supports in order of preference, the answerer selects
+/// the first one it supports as well using [`AudioCodec::negotiate`]. If the answer doesn't
+/// contain a codec (e.g. because the answerer predates codec negotiation or no common codec was
+/// found), both peers fall back to [`AudioCodec::default`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioCodec {
+    /// Name of the codec, kept as an open string so unknown codecs don't fail deserialization.
+    pub name: String,
+    pub sample_rate: u32,
+    /// Target bitrate in bits per second, `None` lets the encoder use its maximum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    /// Whether in-band forward error correction is used.
+    #[serde(default)]
+    pub fec: bool,
+    /// Whether discontinuous transmission is used, suppressing frames while silent.
+    #[serde(default)]
+    pub dtx: bool,
+}
+
+impl AudioCodec {
+    pub const OPUS: &'static str = "opus";
+    pub const PCM: &'static str = "pcm";
+
+    /// The Opus configuration every client supports: 48 kHz at maximum bitrate with FEC.
+    pub fn opus() -> Self {
+        Self {
+            name: Self::OPUS.to_string(),
+            sample_rate: 48_000,
+            bitrate: None,
+            fec: true,
+            dtx: false,
+        }
+    }
+
+    /// Uncompressed 16-bit PCM at 48 kHz, trading bandwidth for encoding latency on local networks.
+    pub fn pcm() -> Self {
+        Self {
+            name: Self::PCM.to_string(),
+            sample_rate: 48_000,
+            bitrate: None,
+            fec: false,
+            dtx: false,
+        }
+    }
+
+    /// Selects the first of the `offered` codecs matching one of the `supported` codecs by name
+    /// and sample rate, in the offerer's order of preference. Optional features are only enabled
+    /// if both sides support them and the lower of both bitrates is used.
+    ///
+    /// Returns `None` if there's no common codec, in which case the answerer omits the codec and
+    /// both peers fall back to [`AudioCodec::default`].
+    pub fn negotiate(offered: &[AudioCodec], supported: &[AudioCodec]) -> Option<AudioCodec> {
+        offered.iter().find_map(|offer| {
+            let local = supported.iter().find(|local| {
+                local.name.eq_ignore_ascii_case(&offer.name)
+                    && local.sample_rate == offer.sample_rate
+            })?;
+            let bitrate = match (offer.bitrate, local.bitrate) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            Some(AudioCodec {
+                name: offer.name.clone(),
+                sample_rate: offer.sample_rate,
+                bitrate,
+                fec: offer.fec && local.fec,
+                dtx: offer.dtx && local.dtx,
+            })
+        })
+    }
+}
+
+impl Default for AudioCodec {
+    fn default() -> Self {
+        Self::opus()
+    }
+}
+
+impl std::fmt::Display for AudioCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.name, self.sample_rate)?;
+        if let Some(bitrate) = self.bitrate {
+            write!(f, "@{bitrate}")?;
+        }
+        if self.fec {
+            f.write_str("+fec")?;
+        }
+        if self.dtx {
+            f.write_str("+dtx")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vatsim::ClientId;
+    use crate::ws::shared::{CallId, WebrtcAnswer, WebrtcOffer};
+
+    fn opus(bitrate: Option<u32>, fec: bool, dtx: bool) -> AudioCodec {
+        AudioCodec {
+            bitrate,
+            fec,
+            dtx,
+            ..AudioCodec::opus()
+        }
+    }
+
+    #[test]
+    fn offer_answer_round_trip() {
+        let offer = WebrtcOffer {
+            call_id: CallId::new(),
+            from_client_id: ClientId::from("client1"),
+            to_client_id: ClientId::from("client2"),
+            sdp: "sdp1".to_string(),
+            audio_codecs: vec![opus(Some(32_000), true, true), AudioCodec::opus()],
+        };
+        let json = serde_json::to_string(&offer).unwrap();
+        assert!(json.contains(r#""audioCodecs":[{"name":"opus","sampleRate":48000,"bitrate":32000,"fec":true,"dtx":true}"#));
+        assert_eq!(serde_json::from_str::<WebrtcOffer>(&json).unwrap(), offer);
+
+        let answer = WebrtcAnswer {
+            call_id: offer.call_id,
+            from_client_id: ClientId::from("client2"),
+            to_client_id: ClientId::from("client1"),
+            sdp: "sdp2".to_string(),
+            audio_codec: AudioCodec::negotiate(&offer.audio_codecs, &[opus(None, true, false)]),
+        };
+        assert_eq!(answer.audio_codec, Some(opus(Some(32_000), true, false)));
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(serde_json::from_str::<WebrtcAnswer>(&json).unwrap(), answer);
+    }
+
+    #[test]
+    fn messages_without_codecs_fall_back_to_default() {
+        let offer: WebrtcOffer = serde_json::from_str(
+            r#"{"callId":"00000000-0000-0000-0000-000000000000","fromClientId":"client1","toClientId":"client2","sdp":"sdp1"}"#,
+        )
+        .unwrap();
+        assert!(offer.audio_codecs.is_empty());
+        assert!(
+            !serde_json::to_string(&offer)
+                .unwrap()
+                .contains("audioCodecs")
+        );
+
+        let answer: WebrtcAnswer = serde_json::from_str(
+            r#"{"callId":"00000000-0000-0000-0000-000000000000","fromClientId":"client2","toClientId":"client1","sdp":"sdp2"}"#,
+        )
+        .unwrap();
+        assert_eq!(answer.audio_codec.unwrap_or_default(), AudioCodec::opus());
+    }
+
+    #[test]
+    fn negotiate_prefers_offerer_order() {
+        let pcm = AudioCodec::pcm();
+        let offered = [pcm.clone(), AudioCodec::opus()];
+
+        assert_eq!(
+            AudioCodec::negotiate(&offered, &[AudioCodec::opus(), pcm.clone()]),
+            Some(pcm)
+        );
+        assert_eq!(
+            AudioCodec::negotiate(&offered, &[AudioCodec::opus()]),
+            Some(AudioCodec::opus())
+        );
+    }
+
+    #[test]
+    fn negotiate_falls_back_to_pcm() {
+        let offered = [AudioCodec::opus(), AudioCodec::pcm()];
+
+        assert_eq!(
+            AudioCodec::negotiate(&offered, &[AudioCodec::pcm()]),
+            Some(AudioCodec::pcm())
+        );
+        assert_eq!(
+            AudioCodec::negotiate(&offered, &[AudioCodec::pcm(), AudioCodec::opus()]),
+            Some(AudioCodec::opus())
+        );
+    }
+
+    #[test]
+    fn negotiate_mismatch_falls_back() {
+        let offered = [AudioCodec {
+            sample_rate: 16_000,
+            ..AudioCodec::opus()
+        }];
+
+        assert_eq!(AudioCodec::negotiate(&offered, &[AudioCodec::opus()]), None);
+        assert_eq!(AudioCodec::negotiate(&[], &[AudioCodec::opus()]), None);
+    }
+}
@@ -39,6 +39,9 @@ pub enum CallErrorReason {
     CallFailure,
     SignalingFailure,
     AutoHangup,
+    Unauthorized,
+    /// The caller's position is receive-only and may not originate calls.
+    ReceiveOnly,
     Other,
 }
 
@@ -51,11 +54,27 @@ pub struct CallInvite {
     pub prio: bool,
 }
 
+/// A one-way call from the source to all clients covering any of the given stations.
+///
+/// Sent by the broadcasting client to start the broadcast and forwarded by the server to every
+/// recipient. Recipients receive audio only and don't have to accept the call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastCall {
+    pub call_id: CallId,
+    pub source: CallSource,
+    pub station_ids: Vec<StationId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallAccept {
     pub call_id: CallId,
     pub accepting_client_id: ClientId,
+    /// Whether the call was answered automatically by the accepting client, without any action of
+    /// its user.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_answered: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -202,6 +221,18 @@ impl From<CallInvite> for ServerMessage {
     }
 }
 
+impl From<BroadcastCall> for ClientMessage {
+    fn from(value: BroadcastCall) -> Self {
+        Self::BroadcastCall(value)
+    }
+}
+
+impl From<BroadcastCall> for ServerMessage {
+    fn from(value: BroadcastCall) -> Self {
+        Self::BroadcastCall(value)
+    }
+}
+
 impl From<CallAccept> for ClientMessage {
     fn from(value: CallAccept) -> Self {
         Self::CallAccept(value)
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Name of a codec used to encode signaling messages on the wire.
+///
+/// Kept as an open string instead of an enum, so a peer can offer codecs the other side does not
+/// know about yet without failing to deserialize the whole message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[repr(transparent)]
+pub struct Codec(String);
+
+impl Codec {
+    /// Plain JSON text frames, supported by every client and server.
+    pub const JSON: &'static str = "json";
+
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into().to_ascii_lowercase())
+    }
+
+    pub fn json() -> Self {
+        Self(Self::JSON.to_string())
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_json(&self) -> bool {
+        self.0 == Self::JSON
+    }
+}
+
+impl Default for Codec {
+    fn default() -> Self {
+        Self::json()
+    }
+}
+
+impl std::fmt::Display for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Codec {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&str> for Codec {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
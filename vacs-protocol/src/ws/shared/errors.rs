@@ -13,6 +13,8 @@ pub enum ErrorReason {
     UnexpectedMessage(String),
     RateLimited { retry_after_secs: u64 },
     ClientNotFound,
+    ProfileNotAvailable,
+    InvalidFrequencies(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,7 +1,7 @@
 use crate::vatsim::ClientId;
 use crate::ws::client::ClientMessage;
 use crate::ws::server::ServerMessage;
-use crate::ws::shared::CallId;
+use crate::ws::shared::{AudioCodec, CallId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,6 +11,9 @@ pub struct WebrtcOffer {
     pub from_client_id: ClientId,
     pub to_client_id: ClientId,
     pub sdp: String,
+    /// Audio codecs supported by the offerer, in order of preference.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audio_codecs: Vec<AudioCodec>,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,6 +23,9 @@ pub struct WebrtcAnswer {
     pub from_client_id: ClientId,
     pub to_client_id: ClientId,
     pub sdp: String,
+    /// Audio codec selected from the offer's codecs, `None` falls back to [`AudioCodec::default`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_codec: Option<AudioCodec>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +44,7 @@ impl std::fmt::Debug for WebrtcOffer {
             .field("from_client_id", &self.from_client_id)
             .field("to_client_id", &self.to_client_id)
             .field("sdp_len", &self.sdp.len())
+            .field("audio_codecs", &self.audio_codecs)
             .finish()
     }
 }
@@ -49,6 +56,7 @@ impl std::fmt::Debug for WebrtcAnswer {
             .field("from_client_id", &self.from_client_id)
             .field("to_client_id", &self.to_client_id)
             .field("sdp_len", &self.sdp.len())
+            .field("audio_codec", &self.audio_codec)
             .finish()
     }
 }
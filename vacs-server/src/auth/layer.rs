@@ -20,7 +20,9 @@ pub async fn setup_auth_layer(
     tracing::debug!("Setting up authentication layer");
 
     let client = BasicClient::new(ClientId::new(config.auth.oauth.client_id.clone()))
-        .set_client_secret(ClientSecret::new(config.auth.oauth.client_secret.clone()))
+        .set_client_secret(ClientSecret::new(
+            config.auth.oauth.client_secret.expose().to_string(),
+        ))
         .set_auth_uri(AuthUrl::new(config.auth.oauth.auth_url.clone()).context("Invalid auth URL")?)
         .set_token_uri(
             TokenUrl::new(config.auth.oauth.token_url.clone()).context("Invalid token URL")?,
@@ -9,6 +9,7 @@ use oauth2::{
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
+use vacs_protocol::secret::Secret;
 use vacs_protocol::vatsim::ClientId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,7 +31,7 @@ impl AuthUser for User {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Credentials {
-    pub code: String,
+    pub code: Secret,
     pub stored_state: String,
     pub received_state: String,
 }
@@ -84,7 +85,7 @@ impl AuthnBackend for Backend {
         tracing::trace!("Exchanging code for VATSIM access token");
         let token = self
             .client
-            .exchange_code(AuthorizationCode::new(creds.code))
+            .exchange_code(AuthorizationCode::new(creds.code.into_inner()))
             .request_async(&ReqwestClient(&self.http_client))
             .await
             .context("Failed to exchange code")
@@ -217,7 +218,11 @@ pub mod mock {
                 return Ok(None);
             }
 
-            let Some(access_token) = self.access_tokens.get(&creds.code).map(|t| t.clone()) else {
+            let Some(access_token) = self
+                .access_tokens
+                .get(creds.code.expose())
+                .map(|t| t.clone())
+            else {
                 return Err(AppError::Unauthorized("Invalid code".to_string()));
             };
 
@@ -1,3 +1,5 @@
+pub mod remote;
+
 use crate::APP_USER_AGENT;
 use crate::config::DatasetRepoConfig;
 use anyhow::{Context, Result};
@@ -8,7 +10,7 @@ use octocrab::params::repos::Reference;
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 use tracing::instrument;
-use vacs_vatsim::coverage::network::Network;
+use vacs_vatsim::coverage::network::{LoadOptions, Network};
 
 /// File name used to track the currently deployed commit SHA.
 const VERSION_FILE: &str = ".dataset-sha";
@@ -35,6 +37,7 @@ pub struct DatasetManager {
     repo: String,
     deployed_tag: String,
     coverage_dir: PathBuf,
+    load_options: LoadOptions,
 }
 
 impl DatasetManager {
@@ -79,9 +82,16 @@ impl DatasetManager {
             repo: config.repo.clone(),
             deployed_tag: config.deployed_tag.clone(),
             coverage_dir: coverage_dir.into(),
+            load_options: LoadOptions::default(),
         })
     }
 
+    /// Sets the options datasets are validated with before installing them.
+    pub fn with_load_options(mut self, load_options: LoadOptions) -> Self {
+        self.load_options = load_options;
+        self
+    }
+
     /// Get the locally stored commit SHA, if any.
     pub fn local_sha(&self) -> Option<String> {
         let path = self.coverage_dir.join(VERSION_FILE);
@@ -180,47 +190,7 @@ impl DatasetManager {
 
         tracing::info!(size_bytes = bytes.len(), "Downloaded tarball");
 
-        // Create temp dir as a sibling of the coverage directory to guarantee
-        // both are on the same filesystem, which is required for atomic renames.
-        let temp_parent = self.coverage_dir.parent().unwrap_or(Path::new("."));
-        let temp_dir =
-            tempfile::TempDir::new_in(temp_parent).context("Failed to create temp directory")?;
-
-        // Extract tarball (tar.gz) in a blocking task
-        let temp_path = temp_dir.path().to_path_buf();
-        tokio::task::spawn_blocking(move || {
-            let decoder = flate2::read::GzDecoder::new(&bytes[..]);
-            let mut archive = tar::Archive::new(decoder);
-            archive
-                .unpack(&temp_path)
-                .context("Failed to extract tarball")
-        })
-        .await
-        .context("Tarball extraction task panicked")??;
-
-        Ok(temp_dir)
-    }
-
-    /// Find the `dataset/` subdirectory inside the extracted tarball root.
-    ///
-    /// GitHub tarballs contain a single top-level directory named
-    /// `{owner}-{repo}-{short_sha}/` with the repository contents inside.
-    fn find_dataset_dir(extracted: &Path) -> Result<PathBuf> {
-        let top_level = std::fs::read_dir(extracted)
-            .context("Failed to list extracted tarball contents")?
-            .filter_map(|e| e.ok())
-            .find(|e| e.file_type().is_ok_and(|t| t.is_dir()))
-            .context("No top-level directory found in tarball")?;
-
-        let dataset_dir = top_level.path().join("dataset");
-        if dataset_dir.is_dir() {
-            Ok(dataset_dir)
-        } else {
-            anyhow::bail!(
-                "No dataset/ directory found in tarball (looked in {})",
-                top_level.path().display()
-            )
-        }
+        extract_tarball(bytes, &self.coverage_dir).await
     }
 
     /// Download, validate and install the dataset for a given git ref.
@@ -233,24 +203,14 @@ impl DatasetManager {
     #[instrument(level = "info", skip(self))]
     pub async fn fetch_and_install(&self, ref_name: &str, commit_sha: &str) -> Result<Network> {
         let temp_dir = self.download_and_extract(ref_name).await?;
-        let dataset_dir = Self::find_dataset_dir(temp_dir.path())?;
-
-        // Validate by loading — this catches any schema / parse errors before
-        // we touch the on-disk copy.
-        let dataset_path = dataset_dir.to_string_lossy().to_string();
-        tracing::info!(%dataset_path, "Validating downloaded dataset");
-
-        let network = tokio::task::spawn_blocking(move || Network::load_from_dir(&dataset_path))
-            .await
-            .context("Dataset load task panicked")?
-            .map_err(|errs| anyhow::anyhow!("Failed to parse dataset: {errs:?}"))?;
+        let dataset_dir = find_dataset_dir(temp_dir.path())?;
 
-        // Atomically swap the on-disk coverage directory.
-        let cov = self.coverage_dir.clone();
-        let src = dataset_dir.clone();
-        tokio::task::spawn_blocking(move || atomic_replace_dir(&src, &cov))
-            .await
-            .context("Directory replacement task panicked")??;
+        let network = validate_and_install(
+            dataset_dir,
+            self.coverage_dir.clone(),
+            self.load_options.clone(),
+        )
+        .await?;
 
         // Persist the commit SHA so subsequent startups know what we have.
         self.save_sha(commit_sha)?;
@@ -310,6 +270,88 @@ impl DatasetManager {
     }
 }
 
+/// Extract a gzip-compressed tarball into a temp directory next to
+/// `coverage_dir`. Returns the [`TempDir`](tempfile::TempDir) containing the
+/// extracted contents.
+async fn extract_tarball(bytes: Bytes, coverage_dir: &Path) -> Result<tempfile::TempDir> {
+    // Create temp dir as a sibling of the coverage directory to guarantee
+    // both are on the same filesystem, which is required for atomic renames.
+    let temp_parent = coverage_dir.parent().unwrap_or(Path::new("."));
+    let temp_dir =
+        tempfile::TempDir::new_in(temp_parent).context("Failed to create temp directory")?;
+
+    // Extract tarball (tar.gz) in a blocking task
+    let temp_path = temp_dir.path().to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let decoder = flate2::read::GzDecoder::new(&bytes[..]);
+        let mut archive = tar::Archive::new(decoder);
+        archive
+            .unpack(&temp_path)
+            .context("Failed to extract tarball")
+    })
+    .await
+    .context("Tarball extraction task panicked")??;
+
+    Ok(temp_dir)
+}
+
+/// Find the `dataset/` directory inside the extracted archive, either at the
+/// archive root or inside a single top-level directory (as produced by GitHub
+/// tarballs, which wrap the repository contents in `{owner}-{repo}-{short_sha}/`).
+fn find_dataset_dir(extracted: &Path) -> Result<PathBuf> {
+    let dataset_dir = extracted.join("dataset");
+    if dataset_dir.is_dir() {
+        return Ok(dataset_dir);
+    }
+
+    let top_level_dirs = std::fs::read_dir(extracted)
+        .context("Failed to list extracted archive contents")?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .collect::<Vec<_>>();
+
+    if let [top_level] = top_level_dirs.as_slice() {
+        let dataset_dir = top_level.path().join("dataset");
+        if dataset_dir.is_dir() {
+            return Ok(dataset_dir);
+        }
+    }
+
+    anyhow::bail!(
+        "No dataset/ directory found in archive (looked in {})",
+        extracted.display()
+    )
+}
+
+/// Validate the dataset in `dataset_dir` by loading it and, if successful,
+/// atomically move it into place at `coverage_dir`.
+///
+/// Returns the loaded [`Network`] so the caller can swap it in.
+async fn validate_and_install(
+    dataset_dir: PathBuf,
+    coverage_dir: PathBuf,
+    load_options: LoadOptions,
+) -> Result<Network> {
+    // Validate by loading — this catches any schema / parse errors before
+    // we touch the on-disk copy.
+    let dataset_path = dataset_dir.to_string_lossy().to_string();
+    tracing::info!(%dataset_path, "Validating downloaded dataset");
+
+    let network = tokio::task::spawn_blocking(move || {
+        Network::load_from_dir_with_options(&dataset_path, &load_options)
+    })
+    .await
+    .context("Dataset load task panicked")?
+    .map_err(|errs| anyhow::anyhow!("Failed to parse dataset: {errs:?}"))?;
+
+    // Atomically swap the on-disk coverage directory.
+    tokio::task::spawn_blocking(move || atomic_replace_dir(&dataset_dir, &coverage_dir))
+        .await
+        .context("Directory replacement task panicked")??;
+
+    Ok(network)
+}
+
 /// Atomically replace `dst` with `src` using directory renames.
 ///
 /// Both paths **must** reside on the same filesystem (otherwise `rename`
@@ -382,6 +424,44 @@ impl DatasetManager {
             repo: "test-repo".to_string(),
             deployed_tag: "deployed/test".to_string(),
             coverage_dir: coverage_dir.into(),
+            load_options: LoadOptions::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn find_dataset_dir_at_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("dataset/LOVV")).unwrap();
+
+        assert_eq!(
+            find_dataset_dir(dir.path()).unwrap(),
+            dir.path().join("dataset")
+        );
+    }
+
+    #[test]
+    fn find_dataset_dir_in_top_level_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("vacs-data-abc1234/dataset/LOVV")).unwrap();
+
+        assert_eq!(
+            find_dataset_dir(dir.path()).unwrap(),
+            dir.path().join("vacs-data-abc1234/dataset")
+        );
+    }
+
+    #[test]
+    fn find_dataset_dir_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("a/dataset")).unwrap();
+        std::fs::create_dir_all(dir.path().join("b/dataset")).unwrap();
+
+        assert!(find_dataset_dir(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,542 @@
+use crate::APP_USER_AGENT;
+use crate::config::RemoteDatasetConfig;
+use crate::dataset::{extract_tarball, find_dataset_dir, validate_and_install};
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::instrument;
+use vacs_vatsim::coverage::network::{LoadOptions, Network};
+
+/// File name used to track the `ETag` of the currently installed archive.
+const ETAG_FILE: &str = ".dataset-etag";
+
+/// File name of the manifest, expected next to the `dataset/` directory.
+const MANIFEST_FILE: &str = "manifest.toml";
+
+/// Manifest shipped alongside the dataset in the archive, listing the
+/// SHA-256 digest of every file in the `dataset/` directory, keyed by its path
+/// relative to it (using `/` as separator):
+///
+/// ```toml
+/// [files]
+/// "LOVV/stations.toml" = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// ```
+///
+/// Archives are rejected if any file is missing, unlisted or has a different
+/// digest.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, String>,
+}
+
+/// Downloads the dataset as a tarball from a plain URL and installs it into
+/// the local coverage directory.
+///
+/// The installed copy doubles as the cache: if fetching or validating a new
+/// archive fails, the previously installed dataset stays in place.
+pub struct RemoteDataset {
+    client: reqwest::Client,
+    url: String,
+    refresh_interval: Duration,
+    max_size: usize,
+    require_manifest: bool,
+    coverage_dir: PathBuf,
+    load_options: LoadOptions,
+}
+
+impl RemoteDataset {
+    pub fn new(config: &RemoteDatasetConfig, coverage_dir: impl Into<PathBuf>) -> Result<Self> {
+        anyhow::ensure!(!config.url.is_empty(), "Remote dataset URL is empty");
+        anyhow::ensure!(
+            !config.refresh_interval.is_zero(),
+            "Remote dataset refresh interval must not be zero"
+        );
+        anyhow::ensure!(
+            config.max_size > 0,
+            "Remote dataset maximum size must not be zero"
+        );
+
+        let client = reqwest::ClientBuilder::new()
+            .user_agent(APP_USER_AGENT)
+            .timeout(config.timeout)
+            .build()
+            .context("Failed to build HTTP client for remote dataset")?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            refresh_interval: config.refresh_interval,
+            max_size: config.max_size,
+            require_manifest: config.require_manifest,
+            coverage_dir: coverage_dir.into(),
+            load_options: LoadOptions::default(),
+        })
+    }
+
+    /// Sets the options datasets are validated with before installing them.
+    pub fn with_load_options(mut self, load_options: LoadOptions) -> Self {
+        self.load_options = load_options;
+        self
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    /// Get the `ETag` of the locally installed archive, if any.
+    pub fn local_etag(&self) -> Option<String> {
+        let path = self.coverage_dir.join(ETAG_FILE);
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    fn save_etag(&self, etag: Option<&str>) -> Result<()> {
+        let path = self.coverage_dir.join(ETAG_FILE);
+        match etag {
+            Some(etag) => std::fs::write(&path, etag)
+                .with_context(|| format!("Failed to write ETag file at {path:?}")),
+            None => Ok(()),
+        }
+    }
+
+    /// Fetch the archive and install it if it changed since the last fetch.
+    ///
+    /// Returns `Some(Network)` when a new dataset was installed, or `None`
+    /// when the server reported the cached copy as up-to-date.
+    #[instrument(level = "info", skip(self), fields(url = %self.url), err)]
+    pub async fn fetch_and_install(&self) -> Result<Option<Network>> {
+        let mut request = self.client.get(&self.url);
+        if let Some(etag) = self.local_etag() {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to download dataset archive")?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            tracing::info!("Remote dataset is already up-to-date");
+            return Ok(None);
+        }
+        let response = response
+            .error_for_status()
+            .context("Remote dataset request failed")?;
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let bytes = self.read_body(response).await?;
+        tracing::info!(
+            size_bytes = bytes.len(),
+            ?etag,
+            "Downloaded dataset archive"
+        );
+
+        let temp_dir = extract_tarball(bytes, &self.coverage_dir).await?;
+        let dataset_dir = find_dataset_dir(temp_dir.path())?;
+        self.verify_manifest(&dataset_dir).await?;
+
+        let network = validate_and_install(
+            dataset_dir,
+            self.coverage_dir.clone(),
+            self.load_options.clone(),
+        )
+        .await?;
+        self.save_etag(etag.as_deref())?;
+
+        tracing::info!(?etag, "Remote dataset installed successfully");
+        Ok(Some(network))
+    }
+
+    /// Read the archive from `response`, aborting as soon as it exceeds the
+    /// configured maximum size.
+    async fn read_body(&self, mut response: reqwest::Response) -> Result<Bytes> {
+        if let Some(len) = response.content_length() {
+            anyhow::ensure!(
+                len <= self.max_size as u64,
+                "Dataset archive of {len} bytes exceeds the maximum size of {} bytes",
+                self.max_size
+            );
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Failed to read dataset archive response body")?
+        {
+            anyhow::ensure!(
+                body.len() + chunk.len() <= self.max_size,
+                "Dataset archive exceeds the maximum size of {} bytes",
+                self.max_size
+            );
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.into())
+    }
+
+    /// Verify the extracted `dataset_dir` against the manifest next to it. A
+    /// missing manifest is only accepted if not required by the config.
+    async fn verify_manifest(&self, dataset_dir: &Path) -> Result<()> {
+        let manifest_path = dataset_dir
+            .parent()
+            .context("Dataset directory has no parent")?
+            .join(MANIFEST_FILE);
+        if !manifest_path.is_file() {
+            anyhow::ensure!(
+                !self.require_manifest,
+                "Dataset archive does not contain a {MANIFEST_FILE}"
+            );
+            tracing::warn!("Dataset archive does not contain a manifest, skipping verification");
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read manifest at {manifest_path:?}"))?;
+        let manifest: Manifest = toml::from_str(&content).context("Failed to parse manifest")?;
+
+        let dataset_dir = dataset_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || verify_dataset_files(&dataset_dir, &manifest))
+            .await
+            .context("Manifest verification task panicked")??;
+
+        tracing::info!("Dataset archive matches its manifest");
+        Ok(())
+    }
+
+    /// Like [`Self::fetch_and_install`], but logs failures and keeps using the
+    /// cached dataset instead of returning an error.
+    pub async fn refresh(&self) -> Option<Network> {
+        match self.fetch_and_install().await {
+            Ok(network) => network,
+            Err(err) => {
+                tracing::warn!(?err, "Failed to fetch remote dataset, using cached dataset");
+                None
+            }
+        }
+    }
+}
+
+/// Check that the files in `dataset_dir` match the `manifest` exactly.
+fn verify_dataset_files(dataset_dir: &Path, manifest: &Manifest) -> Result<()> {
+    let files = dataset_files(dataset_dir)?;
+
+    if let Some(path) = files
+        .keys()
+        .find(|path| !manifest.files.contains_key(*path))
+    {
+        anyhow::bail!("Dataset file {path} is not listed in the manifest");
+    }
+    for (path, expected) in &manifest.files {
+        let file = files
+            .get(path)
+            .with_context(|| format!("Dataset file {path} listed in the manifest is missing"))?;
+        let actual = file_digest(file)?;
+        anyhow::ensure!(
+            actual.eq_ignore_ascii_case(expected),
+            "Digest mismatch for dataset file {path}: expected {expected}, got {actual}"
+        );
+    }
+    Ok(())
+}
+
+/// List all files in `dir` recursively, keyed by their path relative to it.
+fn dataset_files(dir: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    fn collect(root: &Path, dir: &Path, files: &mut BTreeMap<String, PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to list dataset directory {dir:?}"))?
+        {
+            let path = entry
+                .context("Failed to read dataset directory entry")?
+                .path();
+            if path.is_dir() {
+                collect(root, &path, files)?;
+            } else {
+                let relative = path
+                    .strip_prefix(root)
+                    .context("Dataset file outside of dataset directory")?
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(relative, path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    collect(dir, dir, &mut files)?;
+    Ok(files)
+}
+
+fn file_digest(path: &Path) -> Result<String> {
+    let content = std::fs::read(path).with_context(|| format!("Failed to read {path:?}"))?;
+    Ok(format!("{:x}", Sha256::digest(&content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_matches};
+    use test_log::test;
+    use vacs_protocol::vatsim::StationId;
+    use vacs_vatsim::coverage::test_support::TestFirBuilder;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ARCHIVE_PATH: &str = "/dataset.tar.gz";
+
+    fn create_dataset(dir: &Path, stations: &[&str]) {
+        let mut builder = TestFirBuilder::new("LOVV");
+        for &station in stations {
+            builder = builder.station(station, &[station]);
+        }
+        builder
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .create(dir);
+    }
+
+    fn manifest_for(dir: &Path) -> String {
+        let files = dataset_files(dir).unwrap();
+        let mut manifest = String::from("[files]\n");
+        for (path, file) in files {
+            manifest.push_str(&format!(
+                "\"{path}\" = \"{}\"\n",
+                file_digest(&file).unwrap()
+            ));
+        }
+        manifest
+    }
+
+    /// Packs `dataset` into a tarball wrapped in a single top-level directory, as produced by
+    /// GitHub, optionally adding a manifest next to the `dataset/` directory.
+    fn archive(dataset: &Path, manifest: Option<&str>) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        builder
+            .append_dir_all("vacs-data/dataset", dataset)
+            .unwrap();
+        if let Some(manifest) = manifest {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(manifest.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    format!("vacs-data/{MANIFEST_FILE}"),
+                    manifest.as_bytes(),
+                )
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    async fn serve_archive(server: &MockServer, archive: Vec<u8>, etag: &str) {
+        Mock::given(method("GET"))
+            .and(path(ARCHIVE_PATH))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", etag)
+                    .set_body_bytes(archive),
+            )
+            .mount(server)
+            .await;
+    }
+
+    fn remote_dataset(
+        server: &MockServer,
+        coverage_dir: &Path,
+        configure: impl FnOnce(&mut RemoteDatasetConfig),
+    ) -> RemoteDataset {
+        let mut config = RemoteDatasetConfig {
+            url: format!("{}{ARCHIVE_PATH}", server.uri()),
+            ..Default::default()
+        };
+        configure(&mut config);
+        RemoteDataset::new(&config, coverage_dir).unwrap()
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_installs_dataset() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        serve_archive(&server, archive(dataset.path(), None), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |_| {});
+
+        let network = remote.fetch_and_install().await.unwrap().unwrap();
+        assert!(network.has_station(&StationId::from("LOWW_TWR")));
+        assert!(coverage_dir.join("LOVV/stations.toml").is_file());
+        assert_eq!(remote.local_etag().as_deref(), Some("\"v1\""));
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_skips_not_modified_archive() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        serve_archive(&server, archive(dataset.path(), None), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |_| {});
+        remote.fetch_and_install().await.unwrap().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path(ARCHIVE_PATH))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(remote.fetch_and_install().await.unwrap().is_none());
+        assert_eq!(remote.local_etag().as_deref(), Some("\"v1\""));
+        assert!(coverage_dir.join("LOVV/stations.toml").is_file());
+    }
+
+    #[test(tokio::test)]
+    async fn refresh_keeps_cached_dataset_on_failure() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        serve_archive(&server, archive(dataset.path(), None), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |_| {});
+        remote.fetch_and_install().await.unwrap().unwrap();
+
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path(ARCHIVE_PATH))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        assert!(remote.refresh().await.is_none());
+        assert_eq!(remote.local_etag().as_deref(), Some("\"v1\""));
+        assert!(coverage_dir.join("LOVV/stations.toml").is_file());
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_rejects_oversized_archive() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        let archive = archive(dataset.path(), None);
+        let max_size = archive.len() - 1;
+        serve_archive(&server, archive, "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |config| {
+            config.max_size = max_size;
+        });
+
+        let err = remote.fetch_and_install().await.unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum size"));
+        assert!(!coverage_dir.exists());
+        assert_eq!(remote.local_etag(), None);
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_verifies_manifest() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        let manifest = manifest_for(dataset.path());
+        serve_archive(&server, archive(dataset.path(), Some(&manifest)), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |config| {
+            config.require_manifest = true;
+        });
+
+        let network = remote.fetch_and_install().await.unwrap().unwrap();
+        assert!(network.has_station(&StationId::from("LOWW_TWR")));
+        assert!(!coverage_dir.join(MANIFEST_FILE).exists());
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_rejects_manifest_mismatch() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        let manifest = manifest_for(dataset.path());
+        serve_archive(&server, archive(dataset.path(), None), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |_| {});
+        remote.fetch_and_install().await.unwrap().unwrap();
+
+        // The archive was modified after the manifest was generated
+        let tampered = tempfile::tempdir().unwrap();
+        create_dataset(tampered.path(), &["LOWW_TWR", "LOWW_APP"]);
+        server.reset().await;
+        serve_archive(&server, archive(tampered.path(), Some(&manifest)), "\"v2\"").await;
+
+        let err = remote.fetch_and_install().await.unwrap_err();
+        assert!(err.to_string().contains("Digest mismatch"));
+        assert_eq!(remote.local_etag().as_deref(), Some("\"v1\""));
+        let stations = std::fs::read_to_string(coverage_dir.join("LOVV/stations.toml")).unwrap();
+        assert!(!stations.contains("LOWW_APP"));
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_rejects_unlisted_files() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        let manifest = manifest_for(dataset.path());
+        std::fs::write(dataset.path().join("LOVV/fir.toml"), "transmit = false\n").unwrap();
+        serve_archive(&server, archive(dataset.path(), Some(&manifest)), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |_| {});
+
+        assert_matches!(
+            remote.fetch_and_install().await,
+            Err(err) if err.to_string().contains("LOVV/fir.toml is not listed")
+        );
+        assert!(!coverage_dir.exists());
+    }
+
+    #[test(tokio::test)]
+    async fn fetch_and_install_requires_manifest_if_configured() {
+        let server = MockServer::start().await;
+        let dataset = tempfile::tempdir().unwrap();
+        create_dataset(dataset.path(), &["LOWW_TWR"]);
+        serve_archive(&server, archive(dataset.path(), None), "\"v1\"").await;
+
+        let root = tempfile::tempdir().unwrap();
+        let coverage_dir = root.path().join("coverage");
+        let remote = remote_dataset(&server, &coverage_dir, |config| {
+            config.require_manifest = true;
+        });
+
+        let err = remote.fetch_and_install().await.unwrap_err();
+        assert!(err.to_string().contains(MANIFEST_FILE));
+        assert!(!coverage_dir.exists());
+    }
+}
@@ -2,6 +2,7 @@ use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use serde::Serialize;
 
+pub mod client_ip;
 pub mod error;
 pub mod session;
 
@@ -0,0 +1,184 @@
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum_client_ip::ClientIpSource;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+
+/// Decides per request which [`ClientIpSource`] the [`axum_client_ip::ClientIp`] extractor uses,
+/// so forwarded headers are only honored for connections from trusted reverse proxies.
+///
+/// Connections from any other address are identified by their peer address instead, as they
+/// could set arbitrary forwarded headers, e.g. to evade per-IP rate limiting. Without any trusted
+/// proxies configured, the configured source is used for all connections.
+#[derive(Debug, Clone)]
+pub struct ClientIpResolver {
+    source: ClientIpSource,
+    trusted_proxies: Vec<IpNet>,
+}
+
+impl ClientIpResolver {
+    pub fn new(source: ClientIpSource, trusted_proxies: Vec<IpNet>) -> Self {
+        Self {
+            source,
+            trusted_proxies,
+        }
+    }
+
+    /// Source to extract the client IP of a connection from `peer` with, `None` if the peer
+    /// address is unknown.
+    pub fn source_for(&self, peer: Option<IpAddr>) -> ClientIpSource {
+        if matches!(self.source, ClientIpSource::ConnectInfo) || self.trusted_proxies.is_empty() {
+            return self.source.clone();
+        }
+
+        match peer {
+            Some(peer) if self.is_trusted(peer) => self.source.clone(),
+            _ => {
+                tracing::trace!(?peer, "Ignoring forwarded headers of untrusted peer");
+                ClientIpSource::ConnectInfo
+            }
+        }
+    }
+
+    fn is_trusted(&self, peer: IpAddr) -> bool {
+        // Dual-stack listeners report IPv4 peers as IPv4-mapped IPv6 addresses
+        let peer = match peer {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(peer, IpAddr::V4),
+            IpAddr::V4(_) => peer,
+        };
+        self.trusted_proxies.iter().any(|net| net.contains(&peer))
+    }
+}
+
+/// Middleware inserting the [`ClientIpSource`] resolved for the connection into the request.
+pub async fn resolve_client_ip_source(
+    State(resolver): State<ClientIpResolver>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let source = resolver.source_for(peer);
+    request.extensions_mut().insert(source);
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::middleware;
+    use axum::routing::get;
+    use axum_client_ip::ClientIp;
+    use pretty_assertions::assert_eq;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn net(cidr: &str) -> IpNet {
+        cidr.parse().unwrap()
+    }
+
+    async fn resolved_ip(resolver: ClientIpResolver, headers: &[(&str, &str)]) -> String {
+        let app = Router::new()
+            .route("/", get(async |ClientIp(ip): ClientIp| ip.to_string()))
+            .layer(middleware::from_fn_with_state(
+                resolver,
+                resolve_client_ip_source,
+            ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+
+        let mut request = reqwest::Client::new().get(format!("http://{addr}/"));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.send().await.unwrap().text().await.unwrap()
+    }
+
+    #[test]
+    fn source_for_untrusted_peer_falls_back_to_connect_info() {
+        let resolver = ClientIpResolver::new(ClientIpSource::XRealIp, vec![net("10.0.0.0/8")]);
+
+        assert!(matches!(
+            resolver.source_for(Some("10.1.2.3".parse().unwrap())),
+            ClientIpSource::XRealIp
+        ));
+        assert!(matches!(
+            resolver.source_for(Some(IpAddr::V6(
+                Ipv4Addr::new(10, 1, 2, 3).to_ipv6_mapped()
+            ))),
+            ClientIpSource::XRealIp
+        ));
+        assert!(matches!(
+            resolver.source_for(Some("192.0.2.1".parse().unwrap())),
+            ClientIpSource::ConnectInfo
+        ));
+        assert!(matches!(
+            resolver.source_for(Some(IpAddr::V6(Ipv6Addr::LOCALHOST))),
+            ClientIpSource::ConnectInfo
+        ));
+        assert!(matches!(
+            resolver.source_for(None),
+            ClientIpSource::ConnectInfo
+        ));
+    }
+
+    #[test]
+    fn source_for_without_trusted_proxies_keeps_configured_source() {
+        let resolver = ClientIpResolver::new(ClientIpSource::RightmostXForwardedFor, Vec::new());
+        assert!(matches!(
+            resolver.source_for(Some("192.0.2.1".parse().unwrap())),
+            ClientIpSource::RightmostXForwardedFor
+        ));
+    }
+
+    #[tokio::test]
+    async fn trusted_proxy_forwarded_headers_honored() {
+        let resolver = ClientIpResolver::new(ClientIpSource::XRealIp, vec![net("127.0.0.0/8")]);
+        assert_eq!(
+            resolved_ip(resolver, &[("X-Real-IP", "203.0.113.7")]).await,
+            "203.0.113.7"
+        );
+
+        let resolver = ClientIpResolver::new(
+            ClientIpSource::RightmostXForwardedFor,
+            vec![net("127.0.0.1/32")],
+        );
+        assert_eq!(
+            resolved_ip(
+                resolver,
+                &[("X-Forwarded-For", "198.51.100.1, 203.0.113.7")]
+            )
+            .await,
+            "203.0.113.7"
+        );
+    }
+
+    #[tokio::test]
+    async fn untrusted_forwarded_headers_ignored() {
+        let resolver = ClientIpResolver::new(ClientIpSource::XRealIp, vec![net("10.0.0.0/8")]);
+        assert_eq!(
+            resolved_ip(resolver, &[("X-Real-IP", "203.0.113.7")]).await,
+            "127.0.0.1"
+        );
+
+        let resolver = ClientIpResolver::new(
+            ClientIpSource::RightmostXForwardedFor,
+            vec![net("10.0.0.0/8")],
+        );
+        assert_eq!(
+            resolved_ip(resolver, &[("X-Forwarded-For", "203.0.113.7")]).await,
+            "127.0.0.1"
+        );
+    }
+}
@@ -41,7 +41,7 @@ where
         .with_signed(if config.session.signing_key.is_empty() {
             Key::generate()
         } else {
-            Key::from(config.session.signing_key.as_bytes())
+            Key::from(config.session.signing_key.expose().as_bytes())
         });
 
     tracing::info!("Session manager configured");
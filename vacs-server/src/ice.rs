@@ -4,6 +4,7 @@ use crate::ice::provider::stun::StunOnlyProvider;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
+use vacs_protocol::secret::Secret;
 
 pub mod provider;
 
@@ -29,7 +30,7 @@ pub struct IceConfig {
     pub provider: IceConfigProviderType,
     pub stun_servers: Option<Vec<String>>,
     pub cloudflare_turn_key_id: Option<String>,
-    pub cloudflare_turn_key_api_token: Option<String>,
+    pub cloudflare_turn_key_api_token: Option<Secret>,
     pub turn_credential_ttl: Option<Duration>,
 }
 
@@ -68,7 +69,7 @@ impl IceConfig {
                     (Some(turn_key_id), Some(turn_key_api_token)) => {
                         Ok(Arc::new(CloudflareIceProvider::new(
                             turn_key_id,
-                            turn_key_api_token,
+                            turn_key_api_token.expose(),
                             self.turn_credential_ttl
                                 .unwrap_or(Self::DEFAULT_TURN_CREDENTIAL_TTL)
                                 .as_secs(),
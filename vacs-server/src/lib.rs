@@ -4,6 +4,7 @@ pub mod config;
 pub mod dataset;
 pub mod http;
 pub mod ice;
+pub mod listener;
 pub mod metrics;
 pub mod ratelimit;
 pub mod release;
@@ -0,0 +1,175 @@
+use axum::extract::ConnectInfo;
+use axum::{Extension, Router};
+use futures_util::future::try_join_all;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+/// Binds a listener to each of `addrs`, e.g. `0.0.0.0:3000` and `[::]:3000` for dual-stack
+/// deployments.
+///
+/// If `require_all` is set, any address failing to bind aborts with an error. Otherwise failures
+/// are logged and the remaining addresses are still bound, only failing if none could be bound.
+pub async fn bind_all(addrs: &[String], require_all: bool) -> anyhow::Result<Vec<TcpListener>> {
+    let mut listeners = Vec::with_capacity(addrs.len());
+    for addr in addrs {
+        match TcpListener::bind(addr.as_str()).await {
+            Ok(listener) => {
+                tracing::info!(bind_addr = ?listener.local_addr(), "Started main listener");
+                listeners.push(listener);
+            }
+            Err(err) if require_all => {
+                return Err(anyhow::Error::new(err).context(format!("Failed to bind to {addr}")));
+            }
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    ?addr,
+                    "Failed to bind main listener, continuing with remaining addresses"
+                );
+            }
+        }
+    }
+
+    if listeners.is_empty() {
+        anyhow::bail!("Failed to bind to any of the configured addresses {addrs:?}");
+    }
+    Ok(listeners)
+}
+
+/// Serves `app` on all `listeners` until a shutdown is signaled via `shutdown_rx`. Returns once
+/// all listeners stopped serving or as soon as one of them fails.
+///
+/// Connections not sending their complete request headers within `header_read_timeout` are
+/// closed, so stalled clients cannot hold on to a connection indefinitely.
+pub async fn serve_all(
+    listeners: Vec<TcpListener>,
+    app: Router,
+    header_read_timeout: Duration,
+    shutdown_rx: watch::Receiver<()>,
+) -> std::io::Result<()> {
+    try_join_all(listeners.into_iter().map(|listener| {
+        serve(
+            listener,
+            app.clone(),
+            header_read_timeout,
+            shutdown_rx.clone(),
+        )
+    }))
+    .await?;
+    Ok(())
+}
+
+async fn serve(
+    listener: TcpListener,
+    app: Router,
+    header_read_timeout: Duration,
+    mut shutdown_rx: watch::Receiver<()>,
+) -> std::io::Result<()> {
+    let mut builder = Builder::new(TokioExecutor::new());
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(header_read_timeout);
+    let graceful = GracefulShutdown::new();
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            res = listener.accept() => match res {
+                Ok(conn) => conn,
+                Err(err) => {
+                    // Accept errors are mostly caused by running out of file descriptors, back
+                    // off for a bit instead of spinning.
+                    tracing::warn!(?err, "Failed to accept connection");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            _ = shutdown_rx.changed() => break,
+        };
+
+        let service = TowerToHyperService::new(
+            app.clone()
+                .layer(Extension(ConnectInfo::<SocketAddr>(remote_addr))),
+        );
+        let conn = builder
+            .serve_connection_with_upgrades(TokioIo::new(stream), service)
+            .into_owned();
+        let conn = graceful.watch(conn);
+        tokio::spawn(async move {
+            if let Err(err) = conn.await {
+                tracing::debug!(?err, ?remote_addr, "Failed to serve connection");
+            }
+        });
+    }
+
+    graceful.shutdown().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    fn addrs(addrs: &[&str]) -> Vec<String> {
+        addrs.iter().map(|addr| addr.to_string()).collect()
+    }
+
+    #[test(tokio::test)]
+    async fn serves_on_all_listeners() {
+        let listeners = bind_all(&addrs(&["127.0.0.1:0", "127.0.0.1:0"]), true)
+            .await
+            .unwrap();
+        let bound: Vec<SocketAddr> = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap())
+            .collect();
+        assert_eq!(bound.len(), 2);
+        assert_ne!(bound[0], bound[1]);
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        let app = Router::new().route("/health", get(|| async { "OK" }));
+        let server = tokio::spawn(serve_all(
+            listeners,
+            app,
+            Duration::from_secs(10),
+            shutdown_rx,
+        ));
+
+        for addr in &bound {
+            let response = reqwest::get(format!("http://{addr}/health")).await.unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+            assert_eq!(response.text().await.unwrap(), "OK");
+        }
+
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+
+    #[test(tokio::test)]
+    async fn bind_failure_handling() {
+        let occupied = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let occupied_addr = occupied.local_addr().unwrap().to_string();
+
+        let err = bind_all(&addrs(&[&occupied_addr, "127.0.0.1:0"]), true)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(&occupied_addr));
+
+        let listeners = bind_all(&addrs(&[&occupied_addr, "127.0.0.1:0"]), false)
+            .await
+            .unwrap();
+        assert_eq!(listeners.len(), 1);
+
+        assert!(bind_all(&addrs(&[&occupied_addr]), false).await.is_err());
+        assert!(bind_all(&[], false).await.is_err());
+    }
+}
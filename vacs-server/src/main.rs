@@ -1,4 +1,3 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::signal;
 use tokio::sync::watch;
@@ -6,8 +5,10 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use vacs_server::auth::layer::setup_auth_layer;
 use vacs_server::build::BuildInfo;
-use vacs_server::config::AppConfig;
+use vacs_server::config::{AppConfig, ConfigArgs};
 use vacs_server::dataset::DatasetManager;
+use vacs_server::dataset::remote::RemoteDataset;
+use vacs_server::listener;
 use vacs_server::metrics::setup_prometheus_metric_layer;
 use vacs_server::ratelimit::RateLimiters;
 use vacs_server::release::UpdateChecker;
@@ -16,7 +17,7 @@ use vacs_server::routes::{create_app, create_metrics_app};
 use vacs_server::state::AppState;
 use vacs_server::store::Store;
 use vacs_server::store::redis::RedisStore;
-use vacs_vatsim::coverage::network::Network;
+use vacs_vatsim::coverage::network::{LoadOptions, Network};
 use vacs_vatsim::data_feed::VatsimDataFeed;
 use vacs_vatsim::slurper::SlurperClient;
 
@@ -48,7 +49,12 @@ async fn main() -> anyhow::Result<()> {
     let build_info = BuildInfo::gather();
     tracing::info!(?build_info);
 
-    let config = AppConfig::parse()?;
+    let args = ConfigArgs::from_env()?;
+    let config = AppConfig::parse(&args.overlays)?;
+    if args.print_config {
+        tracing::info!("Merged config:\n{config:#?}");
+        return Ok(());
+    }
 
     let policy = Policy::new(&config.updates.policy_path)?;
     let updates = UpdateChecker::new(config.updates.catalog.to_catalog().await?, policy);
@@ -70,38 +76,77 @@ async fn main() -> anyhow::Result<()> {
 
     let (shutdown_tx, shutdown_rx) = watch::channel(());
 
+    let load_options = config.vatsim.load_options();
+
     // Set up the dataset manager for fetching updates from GitHub (if configured).
     let dataset_manager = match &config.admin.dataset {
-        Some(dataset_config) => {
-            Some(DatasetManager::new(dataset_config, &config.vatsim.coverage_dir).await?)
-        }
+        Some(dataset_config) => Some(
+            DatasetManager::new(dataset_config, &config.vatsim.coverage_dir)
+                .await?
+                .with_load_options(load_options.clone()),
+        ),
         None => {
             tracing::info!("No dataset repository configured, skipping GitHub sync");
             None
         }
     };
 
-    // Try syncing from GitHub first; fall back to loading from disk.
-    let network = match &dataset_manager {
-        Some(dm) => match dm.sync_on_startup().await? {
+    // Without a dataset repository, fall back to fetching the dataset archive from a plain URL
+    // (if configured).
+    let remote_dataset = match (&dataset_manager, &config.remote_dataset) {
+        (None, Some(remote_config)) => Some(
+            RemoteDataset::new(remote_config, &config.vatsim.coverage_dir)?
+                .with_load_options(load_options.clone()),
+        ),
+        (Some(_), Some(_)) => {
+            tracing::warn!("Dataset repository is configured, ignoring remote dataset URL");
+            None
+        }
+        _ => None,
+    };
+
+    // Try syncing from GitHub or the remote URL first; fall back to loading from disk.
+    let network = match (&dataset_manager, &remote_dataset) {
+        (Some(dm), _) => match dm.sync_on_startup().await? {
             Some(network) => {
                 tracing::info!("Using freshly downloaded dataset from GitHub");
                 network
             }
             None => {
                 tracing::info!(path = ?config.vatsim.coverage_dir, "Remote dataset matches local copy, loading from disk");
-                Network::load_from_dir(&config.vatsim.coverage_dir).map_err(|err| {
-                    anyhow::anyhow!("Failed to load network coverage data: {err:?}")
-                })?
+                load_local_network(&config.vatsim.coverage_dir, &load_options)?
             }
         },
-        None => {
+        (None, Some(remote)) => match remote.refresh().await {
+            Some(network) => {
+                tracing::info!("Using freshly downloaded dataset from remote URL");
+                network
+            }
+            None => {
+                tracing::info!(path = ?config.vatsim.coverage_dir, "Using cached remote dataset, loading from disk");
+                load_local_network(&config.vatsim.coverage_dir, &load_options)?
+            }
+        },
+        (None, None) => {
             tracing::info!(path = ?config.vatsim.coverage_dir, "Loading network coverage data from disk");
-            Network::load_from_dir(&config.vatsim.coverage_dir)
-                .map_err(|err| anyhow::anyhow!("Failed to load network coverage data: {err:?}"))?
+            load_local_network(&config.vatsim.coverage_dir, &load_options)?
         }
     };
 
+    tracing::info!(summary = %network.summary(), "Loaded network coverage data");
+
+    let missing_profiles = config.vatsim.default_profiles.missing_profiles(&network);
+    // The empty placeholder network doesn't count as a loaded dataset, the server only becomes
+    // ready once a dataset is loaded later on, e.g. via the admin API or a remote dataset refresh.
+    let dataset_loaded = !network.is_empty();
+    if !dataset_loaded {
+        tracing::warn!(
+            "Running with an empty network, clients won't match any position until a dataset is loaded"
+        );
+    } else if !missing_profiles.is_empty() {
+        anyhow::bail!("Configured default profiles not found in network: {missing_profiles:?}");
+    }
+
     let app_state = Arc::new(AppState::new(
         config.clone(),
         updates,
@@ -114,16 +159,22 @@ async fn main() -> anyhow::Result<()> {
         ice_config_provider,
         dataset_manager,
     ));
+    if dataset_loaded {
+        app_state.mark_ready();
+    }
 
     let auth_layer = setup_auth_layer(&config, redis_pool).await?;
 
     let app = create_app(
         auth_layer,
         Some(prom_layer),
-        config.server.client_ip_source.clone(),
+        config.server.client_ip_resolver(),
     );
-    let listener = tokio::net::TcpListener::bind(config.server.bind_addr).await?;
-    tracing::info!(bind_addr = ?listener.local_addr(), "Started main listener");
+    let listeners = listener::bind_all(
+        &config.server.bind_addrs(),
+        config.server.require_all_bind_addrs,
+    )
+    .await?;
 
     let metrics_app = create_metrics_app(prom_handle);
     let metrics_listener = tokio::net::TcpListener::bind(config.server.metrics_bind_addr).await?;
@@ -134,15 +185,29 @@ async fn main() -> anyhow::Result<()> {
         config.vatsim.controller_update_interval,
     );
 
+    let idle_reaper_task = config
+        .idle
+        .enabled
+        .then(|| AppState::start_idle_reaper_task(app_state.clone()));
+
+    let station_debounce_task = AppState::start_station_debounce_task(app_state.clone());
+
+    let station_coalescing_task = AppState::start_station_coalescing_task(app_state.clone());
+
+    let remote_dataset_task =
+        remote_dataset.map(|remote| AppState::start_remote_dataset_task(app_state.clone(), remote));
+
     let metrics_server = axum::serve(metrics_listener, metrics_app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal(shutdown_tx.clone()));
+        .with_graceful_shutdown(shutdown_signal(shutdown_tx));
 
-    let server = axum::serve(
-        listener,
-        app.with_state(app_state)
-            .into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .with_graceful_shutdown(shutdown_signal(shutdown_tx));
+    // The main listeners shut down once the metrics server's shutdown signal was received.
+    let header_read_timeout = app_state.config.websocket.upgrade_timeout;
+    let server = listener::serve_all(
+        listeners,
+        app.with_state(app_state),
+        header_read_timeout,
+        shutdown_rx,
+    );
 
     tokio::try_join!(metrics_server, server)?;
 
@@ -150,9 +215,48 @@ async fn main() -> anyhow::Result<()> {
         tracing::warn!(?err, "Controller update task finished with error");
     }
 
+    if let Some(idle_reaper_task) = idle_reaper_task
+        && let Err(err) = idle_reaper_task.await
+    {
+        tracing::warn!(?err, "Idle reaper task finished with error");
+    }
+
+    if let Some(station_debounce_task) = station_debounce_task
+        && let Err(err) = station_debounce_task.await
+    {
+        tracing::warn!(?err, "Station debounce task finished with error");
+    }
+
+    if let Some(station_coalescing_task) = station_coalescing_task
+        && let Err(err) = station_coalescing_task.await
+    {
+        tracing::warn!(?err, "Station coalescing task finished with error");
+    }
+
+    if let Some(remote_dataset_task) = remote_dataset_task
+        && let Err(err) = remote_dataset_task.await
+    {
+        tracing::warn!(?err, "Remote dataset task finished with error");
+    }
+
     Ok(())
 }
 
+/// Loads the network coverage data from `dir`, falling back to an empty network if the directory
+/// doesn't exist yet, e.g. on a freshly deployed server without a provisioned dataset.
+fn load_local_network(
+    dir: impl AsRef<std::path::Path>,
+    options: &LoadOptions,
+) -> anyhow::Result<Network> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        tracing::warn!(path = ?dir, "Network coverage data directory not found, using empty network");
+        return Ok(Network::empty());
+    }
+    Network::load_from_dir_with_options(dir, options)
+        .map_err(|err| anyhow::anyhow!("Failed to load network coverage data: {err:?}"))
+}
+
 async fn shutdown_signal(shutdown_tx: watch::Sender<()>) {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -12,6 +12,7 @@ use metrics::{Unit, counter, describe_counter, describe_gauge, describe_histogra
 use semver::Version;
 use vacs_protocol::http::version::ReleaseChannel;
 use vacs_protocol::ws::server::LoginFailureReason;
+use vacs_vatsim::data_feed::ParseStats;
 
 pub fn setup_prometheus_metric_layer() -> (PrometheusMetricLayer<'static>, PrometheusHandle) {
     register_metrics();
@@ -47,6 +48,11 @@ pub fn setup_prometheus_metric_layer() -> (PrometheusMetricLayer<'static>, Prome
                     ],
                 )
                 .unwrap()
+                .set_buckets_for_metric(
+                    Matcher::Prefix("vacs_clients_send_queue_".to_string()),
+                    &[0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0],
+                )
+                .unwrap()
                 .set_buckets_for_metric(
                     Matcher::Full("vacs_message_size_bytes".to_string()),
                     &[
@@ -66,6 +72,7 @@ pub fn register_metrics() {
     MessageMetrics::register();
     ErrorMetrics::register();
     VersionMetrics::register();
+    DataFeedMetrics::register();
 }
 
 pub struct ClientMetrics;
@@ -112,6 +119,21 @@ impl ClientMetrics {
             Unit::Seconds,
             "Duration of client sessions in seconds"
         );
+        describe_histogram!(
+            "vacs_clients_send_queue_depth",
+            Unit::Count,
+            "Depth of a client's outbound message queue when queueing a message"
+        );
+        describe_histogram!(
+            "vacs_clients_send_queue_high_water_mark",
+            Unit::Count,
+            "Highest outbound message queue depth reached during a client session"
+        );
+        describe_counter!(
+            "vacs_clients_send_queue_overflows_total",
+            Unit::Count,
+            "Messages sent to a client with a full outbound message queue, by overflow policy"
+        );
     }
 }
 
@@ -258,3 +280,24 @@ impl VersionMetrics {
         );
     }
 }
+
+pub struct DataFeedMetrics;
+
+impl DataFeedMetrics {
+    pub fn parsed(stats: &ParseStats) {
+        counter!("vacs_data_feed_controllers_total", "result" => "parsed")
+            .increment(stats.parsed as u64);
+        counter!("vacs_data_feed_controllers_total", "result" => "skipped")
+            .increment(stats.skipped as u64);
+        counter!("vacs_data_feed_controllers_total", "result" => "ignored")
+            .increment(stats.ignored as u64);
+    }
+
+    fn register() {
+        describe_counter!(
+            "vacs_data_feed_controllers_total",
+            Unit::Count,
+            "Controller entries of fetched VATSIM data feeds, labeled by result (parsed, skipped as malformed, ignored as non-controlling facility)"
+        );
+    }
+}
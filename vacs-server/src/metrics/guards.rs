@@ -1,5 +1,9 @@
+use crate::config::SendQueueOverflowPolicy;
 use crate::metrics::labels::AsMetricLabel;
 use metrics::{counter, gauge, histogram};
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 use vacs_protocol::ws::server::DisconnectReason;
 use vacs_protocol::ws::shared::CallErrorReason;
@@ -8,6 +12,7 @@ use vacs_protocol::ws::shared::CallErrorReason;
 pub struct ClientConnectionGuard {
     start_time: Instant,
     disconnect_reason: Option<DisconnectReason>,
+    send_queue: SendQueueTracker,
 }
 
 impl ClientConnectionGuard {
@@ -18,6 +23,11 @@ impl ClientConnectionGuard {
     pub fn set_disconnect_reason(&mut self, reason: DisconnectReason) {
         self.disconnect_reason = Some(reason);
     }
+
+    /// Returns the tracker of the client's outbound message channel.
+    pub fn send_queue(&self) -> SendQueueTracker {
+        self.send_queue.clone()
+    }
 }
 
 impl Default for ClientConnectionGuard {
@@ -28,6 +38,7 @@ impl Default for ClientConnectionGuard {
         Self {
             start_time: Instant::now(),
             disconnect_reason: None,
+            send_queue: SendQueueTracker::default(),
         }
     }
 }
@@ -36,6 +47,9 @@ impl Drop for ClientConnectionGuard {
     fn drop(&mut self) {
         gauge!("vacs_clients_connected").decrement(1.0);
 
+        histogram!("vacs_clients_send_queue_high_water_mark")
+            .record(self.send_queue.high_water_mark() as f64);
+
         histogram!("vacs_clients_session_duration_seconds")
             .record(self.start_time.elapsed().as_secs_f64());
 
@@ -47,6 +61,84 @@ impl Drop for ClientConnectionGuard {
     }
 }
 
+/// Tracks the depth of a client's outbound `mpsc` channel, which tokio doesn't expose directly.
+///
+/// Messages are counted with wrapping counters when queued and when taken off the channel, the
+/// depth being the difference between both.
+#[derive(Debug, Clone, Default)]
+pub struct SendQueueTracker {
+    counters: Arc<SendQueueCounters>,
+}
+
+#[derive(Debug, Default)]
+struct SendQueueCounters {
+    queued: AtomicU64,
+    dequeued: AtomicU64,
+    high_water_mark: AtomicU64,
+}
+
+/// Point-in-time view of a client's outbound channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendQueueStats {
+    pub depth: u64,
+    pub high_water_mark: u64,
+}
+
+impl SendQueueTracker {
+    /// Records a message about to be queued. Must be called before sending, so the receiving end
+    /// can never dequeue a message that wasn't counted yet.
+    pub fn record_queued(&self) {
+        let queued = self
+            .counters
+            .queued
+            .fetch_add(1, Ordering::AcqRel)
+            .wrapping_add(1);
+        let depth = queued.wrapping_sub(self.counters.dequeued.load(Ordering::Acquire));
+        self.counters
+            .high_water_mark
+            .fetch_max(depth, Ordering::AcqRel);
+        histogram!("vacs_clients_send_queue_depth").record(depth as f64);
+    }
+
+    /// Reverts [`Self::record_queued`] for a message that could not be sent.
+    pub fn record_send_failed(&self) {
+        self.counters.queued.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    /// Records a message that didn't fit into the full channel and was handled by `policy`.
+    pub fn record_overflow(&self, policy: SendQueueOverflowPolicy) {
+        counter!(
+            "vacs_clients_send_queue_overflows_total",
+            "policy" => policy.as_metric_label(),
+        )
+        .increment(1);
+    }
+
+    pub fn record_dequeued(&self) {
+        self.counters.dequeued.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub fn depth(&self) -> u64 {
+        let dequeued = self.counters.dequeued.load(Ordering::Acquire);
+        self.counters
+            .queued
+            .load(Ordering::Acquire)
+            .wrapping_sub(dequeued)
+    }
+
+    pub fn high_water_mark(&self) -> u64 {
+        self.counters.high_water_mark.load(Ordering::Acquire)
+    }
+
+    pub fn stats(&self) -> SendQueueStats {
+        SendQueueStats {
+            depth: self.depth(),
+            high_water_mark: self.high_water_mark(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CallAttemptOutcome {
     Accepted,
@@ -54,6 +146,7 @@ pub enum CallAttemptOutcome {
     Error(CallErrorReason),
     Cancelled,
     Aborted,
+    TimedOut,
 }
 
 #[derive(Debug)]
@@ -125,3 +218,53 @@ impl Drop for CallGuard {
         histogram!("vacs_calls_duration_seconds").record(self.start_time.elapsed().as_secs_f64());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn send_queue_tracker_depth() {
+        let tracker = SendQueueTracker::default();
+        tracker.record_queued();
+        tracker.record_queued();
+        tracker.record_queued();
+        tracker.record_dequeued();
+        assert_eq!(
+            tracker.stats(),
+            SendQueueStats {
+                depth: 2,
+                high_water_mark: 3,
+            }
+        );
+
+        tracker.record_queued();
+        tracker.record_send_failed();
+        tracker.record_dequeued();
+        tracker.record_dequeued();
+        assert_eq!(
+            tracker.stats(),
+            SendQueueStats {
+                depth: 0,
+                high_water_mark: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn send_queue_tracker_wraps() {
+        let tracker = SendQueueTracker::default();
+        tracker.counters.queued.store(u64::MAX, Ordering::Release);
+        tracker.counters.dequeued.store(u64::MAX, Ordering::Release);
+
+        tracker.record_queued();
+        tracker.record_queued();
+        assert_eq!(tracker.counters.queued.load(Ordering::Acquire), 1);
+        assert_eq!(tracker.depth(), 2);
+
+        tracker.record_dequeued();
+        assert_eq!(tracker.depth(), 1);
+        assert_eq!(tracker.high_water_mark(), 2);
+    }
+}
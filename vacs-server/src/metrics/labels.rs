@@ -1,3 +1,4 @@
+use crate::config::SendQueueOverflowPolicy;
 use crate::metrics::guards::CallAttemptOutcome;
 use crate::release::catalog::BundleType;
 use vacs_protocol::http::version::ReleaseChannel;
@@ -15,6 +16,19 @@ impl AsMetricLabel for DisconnectReason {
             DisconnectReason::Terminated => "terminated",
             DisconnectReason::NoActiveVatsimConnection => "no_active_vatsim_connection",
             DisconnectReason::AmbiguousVatsimPosition(_) => "ambiguous_vatsim_position",
+            DisconnectReason::IdleTimeout => "idle_timeout",
+            DisconnectReason::SendQueueOverflow => "send_queue_overflow",
+        }
+    }
+}
+
+impl AsMetricLabel for SendQueueOverflowPolicy {
+    fn as_metric_label(&self) -> &'static str {
+        match self {
+            SendQueueOverflowPolicy::Wait => "wait",
+            SendQueueOverflowPolicy::DropOldest => "drop_oldest",
+            SendQueueOverflowPolicy::DropNewestNonCritical => "drop_newest_non_critical",
+            SendQueueOverflowPolicy::Disconnect => "disconnect",
         }
     }
 }
@@ -39,6 +53,11 @@ impl AsMetricLabel for LoginFailureReason {
             LoginFailureReason::InvalidVatsimPosition => "invalid_vatsim_position",
             LoginFailureReason::Timeout => "timeout",
             LoginFailureReason::IncompatibleProtocolVersion => "incompatible_protocol_version",
+            LoginFailureReason::NotReady => "not_ready",
+            LoginFailureReason::UnsupportedCodec(_) => "unsupported_codec",
+            LoginFailureReason::FacilityNotAllowed => "facility_not_allowed",
+            LoginFailureReason::ClientTooOld { .. } => "client_too_old",
+            LoginFailureReason::InvalidTags => "invalid_tags",
         }
     }
 }
@@ -50,6 +69,7 @@ impl AsMetricLabel for CallAttemptOutcome {
             CallAttemptOutcome::Rejected => "rejected",
             CallAttemptOutcome::Cancelled => "cancelled",
             CallAttemptOutcome::Aborted => "aborted",
+            CallAttemptOutcome::TimedOut => "timed_out",
             CallAttemptOutcome::Error(CallErrorReason::AudioFailure) => "error_audio_failure",
             CallAttemptOutcome::Error(CallErrorReason::AutoHangup) => "error_auto_hangup",
             CallAttemptOutcome::Error(CallErrorReason::WebrtcFailure) => "error_webrtc_failure",
@@ -59,6 +79,8 @@ impl AsMetricLabel for CallAttemptOutcome {
                 "error_signaling_failure"
             }
             CallAttemptOutcome::Error(CallErrorReason::TargetNotFound) => "error_target_not_found",
+            CallAttemptOutcome::Error(CallErrorReason::Unauthorized) => "error_unauthorized",
+            CallAttemptOutcome::Error(CallErrorReason::ReceiveOnly) => "error_receive_only",
             CallAttemptOutcome::Error(CallErrorReason::Other) => "error_other",
         }
     }
@@ -95,11 +117,19 @@ impl AsMetricLabel for ClientMessage {
             ClientMessage::CallReject(_) => "call_reject",
             ClientMessage::CallEnd(_) => "call_end",
             ClientMessage::CallError(_) => "call_error",
+            ClientMessage::BroadcastCall(_) => "broadcast_call",
             ClientMessage::WebrtcOffer(_) => "webrtc_offer",
             ClientMessage::WebrtcAnswer(_) => "webrtc_answer",
             ClientMessage::WebrtcIceCandidate(_) => "webrtc_ice_candidate",
             ClientMessage::ListClients => "list_clients",
             ClientMessage::ListStations => "list_stations",
+            ClientMessage::ListActiveCalls => "list_active_calls",
+            ClientMessage::GetDatasetInfo => "get_dataset_info",
+            ClientMessage::GetStationHierarchy => "get_station_hierarchy",
+            ClientMessage::GetStationCoverage(_) => "get_station_coverage",
+            ClientMessage::TransmitState(_) => "transmit_state",
+            ClientMessage::SetProfile(_) => "set_profile",
+            ClientMessage::SetMonitoredFrequencies(_) => "set_monitored_frequencies",
             ClientMessage::Disconnect => "disconnect",
             ClientMessage::Error(_) => "error",
         }
@@ -115,16 +145,27 @@ impl AsMetricLabel for ServerMessage {
             ServerMessage::CallEnd(_) => "call_end",
             ServerMessage::CallCancelled(_) => "call_cancelled",
             ServerMessage::CallError(_) => "call_error",
+            ServerMessage::BroadcastCall(_) => "broadcast_call",
+            ServerMessage::BroadcastCallStarted(_) => "broadcast_call_started",
             ServerMessage::WebrtcOffer(_) => "webrtc_offer",
             ServerMessage::WebrtcAnswer(_) => "webrtc_answer",
             ServerMessage::WebrtcIceCandidate(_) => "webrtc_ice_candidate",
             ServerMessage::ClientInfo(_) => "client_info",
             ServerMessage::SessionInfo(_) => "session_info",
+            ServerMessage::ProfileTooLarge(_) => "profile_too_large",
+            ServerMessage::PositionReassigned(_) => "position_reassigned",
             ServerMessage::ClientConnected(_) => "client_connected",
             ServerMessage::ClientDisconnected(_) => "client_disconnected",
             ServerMessage::ClientList(_) => "client_list",
             ServerMessage::StationList(_) => "station_list",
             ServerMessage::StationChanges(_) => "station_changes",
+            ServerMessage::ActiveCallList(_) => "active_call_list",
+            ServerMessage::DatasetInfo(_) => "dataset_info",
+            ServerMessage::Announcement(_) => "announcement",
+            ServerMessage::MonitoredFrequencies(_) => "monitored_frequencies",
+            ServerMessage::StationHierarchy(_) => "station_hierarchy",
+            ServerMessage::StationCoverage(_) => "station_coverage",
+            ServerMessage::TransmitState(_) => "transmit_state",
             ServerMessage::Disconnected(_) => "disconnected",
             ServerMessage::Error(_) => "error",
         }
@@ -140,6 +181,8 @@ impl AsMetricLabel for ErrorReason {
             ErrorReason::UnexpectedMessage(_) => "unexpected_message",
             ErrorReason::RateLimited { .. } => "rate_limited",
             ErrorReason::ClientNotFound => "client_not_found",
+            ErrorReason::ProfileNotAvailable => "profile_not_available",
+            ErrorReason::InvalidFrequencies(_) => "invalid_frequencies",
         }
     }
 }
@@ -1,17 +1,19 @@
 mod admin;
 mod auth;
+mod coverage;
 mod root;
 mod version;
 mod webrtc;
 mod ws;
 
+use crate::http::client_ip::{ClientIpResolver, resolve_client_ip_source};
 use crate::state::AppState;
 use axum::extract::FromRequestParts;
 use axum::http::{Request, StatusCode};
 use axum::middleware::Next;
 use axum::routing::get;
 use axum::{Router, extract, middleware};
-use axum_client_ip::{ClientIp, ClientIpSource};
+use axum_client_ip::ClientIp;
 use axum_login::{AuthManagerLayer, AuthnBackend};
 use axum_prometheus::PrometheusMetricLayer;
 use axum_prometheus::metrics_exporter_prometheus::PrometheusHandle;
@@ -25,7 +27,7 @@ use tracing::{Span, debug_span};
 pub fn create_app<B, S>(
     auth_layer: AuthManagerLayer<B, S, SignedCookie>,
     prom_layer: Option<PrometheusMetricLayer<'static>>,
-    client_ip_source: ClientIpSource,
+    client_ip_resolver: ClientIpResolver,
 ) -> Router<Arc<AppState>>
 where
     B: AuthnBackend + Send + Sync + 'static + Clone,
@@ -34,6 +36,7 @@ where
     let app = Router::new()
         .nest("/admin", admin::routes())
         .nest("/auth", auth::routes())
+        .nest("/coverage", coverage::routes())
         .nest("/ws", ws::routes().merge(crate::ws::routes()))
         .nest("/version", version::routes())
         .nest("/webrtc", webrtc::routes())
@@ -67,7 +70,10 @@ where
             crate::config::SERVER_SHUTDOWN_TIMEOUT,
         ))
         .layer(auth_layer)
-        .layer(client_ip_source.into_extension());
+        .layer(middleware::from_fn_with_state(
+            client_ip_resolver,
+            resolve_client_ip_source,
+        ));
 
     if let Some(prom_layer) = prom_layer {
         app.layer(prom_layer)
@@ -1,18 +1,101 @@
 use crate::state::AppState;
 use axum::Router;
-use axum::routing::post;
+use axum::routing::{get, post};
 use std::sync::Arc;
 
 pub fn routes() -> Router<Arc<AppState>> {
-    Router::new().route("/dataset/reload", post(post::reload_dataset))
+    Router::new()
+        .route("/clients", get(get::clients))
+        .route("/clients/{client_id}/repair", post(post::repair_client))
+        .route("/coverage/scenario", get(get::coverage_scenario))
+        .route("/dataset/reload", post(post::reload_dataset))
+        .route("/firs/{fir_id}/enable", post(post::enable_fir))
+        .route("/firs/{fir_id}/disable", post(post::disable_fir))
+        .route("/stations/{station_id}/close", post(post::close_station))
+        .route("/stations/{station_id}/reopen", post(post::reopen_station))
+        .route(
+            "/stations/{station_id}/override/{position_id}",
+            post(post::override_station_coverage),
+        )
+        .route(
+            "/stations/{station_id}/clear-override",
+            post(post::clear_station_coverage_override),
+        )
+}
+
+mod get {
+    use crate::http::ApiResult;
+    use crate::metrics::guards::SendQueueStats;
+    use crate::state::AppState;
+    use crate::state::history::CoverageScenario;
+    use axum::Json;
+    use axum::extract::{Query, State};
+    use axum::http::HeaderMap;
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+    use tracing::instrument;
+    use vacs_protocol::ws::server::ClientInfo;
+
+    /// Connected client as listed by the admin client list.
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AdminClientInfo {
+        #[serde(flatten)]
+        pub client: ClientInfo,
+        /// Tags the client sent at login.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<String>,
+        pub send_queue: SendQueueStats,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct ClientsQuery {
+        /// Only lists clients tagged with this tag.
+        pub tag: Option<String>,
+    }
+
+    #[instrument(level = "debug", skip(state, headers))]
+    pub async fn clients(
+        State(state): State<Arc<AppState>>,
+        Query(query): Query<ClientsQuery>,
+        headers: HeaderMap,
+    ) -> ApiResult<Vec<AdminClientInfo>> {
+        super::post::verify_github_oidc(&state.config.admin, &headers).await?;
+
+        let clients = state
+            .clients
+            .list_client_sessions(query.tag.as_deref())
+            .await
+            .into_iter()
+            .map(|session| AdminClientInfo {
+                client: session.client_info().clone(),
+                tags: session.tags().to_vec(),
+                send_queue: session.send_queue_stats(),
+            })
+            .collect();
+        Ok(Json(clients))
+    }
+
+    /// Exports the recent connect, disconnect and data feed events as a scenario, allowing a
+    /// reported coverage issue to be captured as a regression test.
+    #[instrument(level = "debug", skip(state, headers))]
+    pub async fn coverage_scenario(
+        State(state): State<Arc<AppState>>,
+        headers: HeaderMap,
+    ) -> ApiResult<CoverageScenario> {
+        super::post::verify_github_oidc(&state.config.admin, &headers).await?;
+
+        Ok(Json(state.coverage_scenario()))
+    }
 }
 
 mod post {
-    use crate::http::StatusCodeResult;
     use crate::http::error::AppError;
-    use crate::state::AppState;
+    use crate::http::{ApiResult, StatusCodeResult};
+    use crate::state::clients::{ClientManagerError, ClientRepair};
+    use crate::state::{AppState, MAX_AIRSPACE_CHANGE_DELAY};
     use axum::Json;
-    use axum::extract::State;
+    use axum::extract::{Path, State};
     use axum::http::{HeaderMap, StatusCode};
     use jsonwebtoken::{DecodingKey, Validation, decode, jwk::JwkSet};
     use serde::Deserialize;
@@ -48,6 +131,11 @@ mod post {
         /// stored on disk. If omitted, `git_ref` is used as-is.
         #[serde(default)]
         pub sha: Option<String>,
+        /// Delay in seconds before the downloaded dataset replaces the loaded one, announcing the
+        /// airspace change to clients beforehand. Replaced immediately if omitted. Must not exceed
+        /// [`MAX_AIRSPACE_CHANGE_DELAY`].
+        #[serde(default)]
+        pub scheduled_in_secs: Option<u64>,
     }
 
     /// Verify that the request carries a valid GitHub Actions OIDC token.
@@ -59,7 +147,7 @@ mod post {
     /// 2. Issuer matches GitHub's OIDC issuer
     /// 3. Audience matches the configured expected audience
     /// 4. Subject matches the configured allowed subject (repo + environment)
-    async fn verify_github_oidc(
+    pub(super) async fn verify_github_oidc(
         config: &crate::config::AdminConfig,
         headers: &HeaderMap,
     ) -> Result<(), AppError> {
@@ -166,6 +254,15 @@ mod post {
         let commit_sha = body.sha.as_deref().unwrap_or(git_ref);
         tracing::info!(%git_ref, %commit_sha, "Dataset reload triggered");
 
+        if let Some(delay) = body.scheduled_in_secs
+            && delay > MAX_AIRSPACE_CHANGE_DELAY.as_secs()
+        {
+            return Err(AppError::BadRequest(format!(
+                "scheduled_in_secs must not exceed {}",
+                MAX_AIRSPACE_CHANGE_DELAY.as_secs()
+            )));
+        }
+
         let previous_sha = state.dataset.as_ref().and_then(|d| d.local_sha());
 
         let dataset = state.dataset.as_ref().ok_or_else(|| {
@@ -183,6 +280,18 @@ mod post {
                 ))
             })?;
 
+        if let Some(delay) = body.scheduled_in_secs.filter(|&secs| secs > 0) {
+            state.schedule_airspace_change(network, Duration::from_secs(delay));
+
+            tracing::info!(
+                from = ?previous_sha,
+                to = %commit_sha,
+                delay,
+                "Dataset installed, network replacement scheduled"
+            );
+            return Ok(StatusCode::ACCEPTED);
+        }
+
         state.replace_network(network).await;
 
         tracing::info!(
@@ -193,4 +302,141 @@ mod post {
 
         Ok(StatusCode::OK)
     }
+
+    /// Re-derives the position and coverage of a client whose session got into an inconsistent
+    /// state, without disconnecting it.
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn repair_client(
+        State(state): State<Arc<AppState>>,
+        Path(client_id): Path<String>,
+        headers: HeaderMap,
+    ) -> ApiResult<ClientRepair> {
+        verify_github_oidc(&state.config.admin, &headers).await?;
+
+        let client_id = client_id.into();
+        match state.repair_client(&client_id).await {
+            Ok(repair) => {
+                tracing::info!(%client_id, ?repair, "Client repair completed");
+                Ok(Json(repair))
+            }
+            Err(ClientManagerError::UnknownClient(_)) => Err(AppError::NotFound),
+            Err(err) => Err(AppError::InternalServerError(err.into())),
+        }
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn enable_fir(
+        State(state): State<Arc<AppState>>,
+        Path(fir_id): Path<String>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        set_fir_enabled(&state, &headers, fir_id, true).await
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn disable_fir(
+        State(state): State<Arc<AppState>>,
+        Path(fir_id): Path<String>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        set_fir_enabled(&state, &headers, fir_id, false).await
+    }
+
+    async fn set_fir_enabled(
+        state: &AppState,
+        headers: &HeaderMap,
+        fir_id: String,
+        enabled: bool,
+    ) -> StatusCodeResult {
+        verify_github_oidc(&state.config.admin, headers).await?;
+
+        let fir_id = fir_id.into();
+        match state.set_fir_enabled(&fir_id, enabled).await {
+            Ok(changed) => {
+                tracing::info!(%fir_id, enabled, changed, "FIR state update completed");
+                Ok(StatusCode::OK)
+            }
+            Err(ClientManagerError::UnknownFir(_)) => Err(AppError::NotFound),
+            Err(err) => Err(AppError::InternalServerError(err.into())),
+        }
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn close_station(
+        State(state): State<Arc<AppState>>,
+        Path(station_id): Path<String>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        set_station_closed(&state, &headers, station_id, true).await
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn reopen_station(
+        State(state): State<Arc<AppState>>,
+        Path(station_id): Path<String>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        set_station_closed(&state, &headers, station_id, false).await
+    }
+
+    async fn set_station_closed(
+        state: &AppState,
+        headers: &HeaderMap,
+        station_id: String,
+        closed: bool,
+    ) -> StatusCodeResult {
+        verify_github_oidc(&state.config.admin, headers).await?;
+
+        let station_id = station_id.into();
+        match state.set_station_closed(&station_id, closed).await {
+            Ok(changed) => {
+                tracing::info!(%station_id, closed, changed, "Station state update completed");
+                Ok(StatusCode::OK)
+            }
+            Err(ClientManagerError::UnknownStation(_)) => Err(AppError::NotFound),
+            Err(err) => Err(AppError::InternalServerError(err.into())),
+        }
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn override_station_coverage(
+        State(state): State<Arc<AppState>>,
+        Path((station_id, position_id)): Path<(String, String)>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        verify_github_oidc(&state.config.admin, &headers).await?;
+
+        let station_id = station_id.into();
+        let position_id = position_id.into();
+        match state.set_coverage_override(&station_id, &position_id).await {
+            Ok(changed) => {
+                tracing::info!(%station_id, %position_id, changed, "Coverage override set");
+                Ok(StatusCode::OK)
+            }
+            Err(ClientManagerError::UnknownStation(_)) => Err(AppError::NotFound),
+            Err(err @ ClientManagerError::PositionOffline(_)) => {
+                Err(AppError::BadRequest(err.to_string()))
+            }
+            Err(err) => Err(AppError::InternalServerError(err.into())),
+        }
+    }
+
+    #[instrument(level = "info", skip(state, headers))]
+    pub async fn clear_station_coverage_override(
+        State(state): State<Arc<AppState>>,
+        Path(station_id): Path<String>,
+        headers: HeaderMap,
+    ) -> StatusCodeResult {
+        verify_github_oidc(&state.config.admin, &headers).await?;
+
+        let station_id = station_id.into();
+        match state.clear_coverage_override(&station_id).await {
+            Ok(changed) => {
+                tracing::info!(%station_id, changed, "Coverage override cleared");
+                Ok(StatusCode::OK)
+            }
+            Err(ClientManagerError::UnknownStation(_)) => Err(AppError::NotFound),
+            Err(err) => Err(AppError::InternalServerError(err.into())),
+        }
+    }
 }
@@ -0,0 +1,289 @@
+use crate::auth::users::Backend;
+use crate::state::AppState;
+use axum::Router;
+use axum::body::Bytes;
+use axum::routing::get;
+use axum_login::login_required;
+use futures_util::{Stream, StreamExt};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch};
+use vacs_protocol::http::coverage::{
+    CoverageSnapshot, CoverageStreamEvent, TimestampedStationChange,
+};
+use vacs_protocol::ws::server::{self, ServerMessage};
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(get::snapshot).layer(login_required!(Backend)))
+        .route("/stream", get(get::stream).layer(login_required!(Backend)))
+}
+
+mod get {
+    use super::*;
+    use axum::Json;
+    use axum::body::Body;
+    use axum::extract::State;
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    pub async fn snapshot(State(state): State<Arc<AppState>>) -> Json<CoverageSnapshot> {
+        Json(state.clients.coverage_snapshot().await)
+    }
+
+    pub async fn stream(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+        tracing::debug!("Starting coverage stream");
+        // Subscribe before taking the snapshot, so no change is missed in between.
+        let (changes_rx, shutdown_rx) = state.subscribe_station_changes();
+        let (broadcast_rx, _) = state.get_client_receivers();
+        let snapshot = state.clients.coverage_snapshot().await;
+
+        (
+            [(header::CONTENT_TYPE, "application/x-ndjson")],
+            Body::from_stream(super::ndjson_stream(
+                snapshot,
+                changes_rx,
+                broadcast_rx,
+                shutdown_rx,
+            )),
+        )
+    }
+}
+
+struct StreamReceivers {
+    changes_rx: broadcast::Receiver<TimestampedStationChange>,
+    broadcast_rx: broadcast::Receiver<ServerMessage>,
+    shutdown_rx: watch::Receiver<()>,
+}
+
+/// Streams the initial `snapshot`, followed by every timestamped station change and client
+/// connecting or disconnecting, each as a line of JSON. The stream ends on server shutdown or once the consumer
+/// falls too far behind, instead of buffering events for it indefinitely.
+fn ndjson_stream(
+    snapshot: CoverageSnapshot,
+    changes_rx: broadcast::Receiver<TimestampedStationChange>,
+    broadcast_rx: broadcast::Receiver<ServerMessage>,
+    shutdown_rx: watch::Receiver<()>,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let receivers = StreamReceivers {
+        changes_rx,
+        broadcast_rx,
+        shutdown_rx,
+    };
+    let events = futures_util::stream::unfold(receivers, |mut receivers| async move {
+        loop {
+            let event = tokio::select! {
+                biased;
+                _ = receivers.shutdown_rx.changed() => {
+                    tracing::debug!("Shutting down coverage stream");
+                    return None;
+                }
+                change = receivers.changes_rx.recv() => change.map(CoverageStreamEvent::StationChange),
+                message = receivers.broadcast_rx.recv() => match message {
+                    Ok(ServerMessage::ClientConnected(server::ClientConnected { client })) => {
+                        Ok(CoverageStreamEvent::ClientConnected {
+                            client_id: client.id,
+                            position_id: client.position_id,
+                        })
+                    }
+                    Ok(ServerMessage::ClientDisconnected(server::ClientDisconnected { client_id })) => {
+                        Ok(CoverageStreamEvent::ClientDisconnected { client_id })
+                    }
+                    Ok(_) => continue,
+                    Err(err) => Err(err),
+                },
+            };
+
+            match event {
+                Ok(event) => {
+                    if let Some(line) = ndjson_line(&event) {
+                        return Some((Ok(line), receivers));
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::info!(skipped, "Coverage stream consumer too slow, closing stream");
+                    return None;
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    futures_util::stream::iter(ndjson_line(&CoverageStreamEvent::Snapshot(snapshot)).map(Ok))
+        .chain(events)
+}
+
+fn ndjson_line(event: &CoverageStreamEvent) -> Option<Bytes> {
+    match serde_json::to_vec(event) {
+        Ok(mut line) => {
+            line.push(b'\n');
+            Some(Bytes::from(line))
+        }
+        Err(err) => {
+            tracing::warn!(?err, ?event, "Failed to serialize coverage stream event");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::test_util::TestSetup;
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+    use vacs_protocol::vatsim::{ClientId, PositionId, StationChange, StationId};
+
+    fn online(station_id: &str) -> StationChange {
+        StationChange::Online {
+            station_id: StationId::from(station_id),
+            position_id: PositionId::from("LOWW_TWR"),
+        }
+    }
+
+    fn timestamped(change: StationChange) -> TimestampedStationChange {
+        TimestampedStationChange { change, at: 0 }
+    }
+
+    fn event(line: &Bytes) -> CoverageStreamEvent {
+        serde_json::from_slice(line).unwrap()
+    }
+
+    #[test(tokio::test)]
+    async fn stream_emits_station_changes() {
+        let setup = TestSetup::new();
+        let (changes_rx, shutdown_rx) = setup.app_state.subscribe_station_changes();
+        let (broadcast_rx, _) = setup.app_state.get_client_receivers();
+        let mut stream = Box::pin(ndjson_stream(
+            CoverageSnapshot::default(),
+            changes_rx,
+            broadcast_rx,
+            shutdown_rx,
+        ));
+
+        let snapshot = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            event(&snapshot),
+            CoverageStreamEvent::Snapshot(CoverageSnapshot::default())
+        );
+
+        setup
+            .app_state
+            .clients
+            .broadcast_station_changes(&[online("LOWW_TWR"), online("LOWW_GND")])
+            .await;
+
+        let first = stream.next().await.unwrap().unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&first).unwrap();
+        assert_eq!(
+            json["online"],
+            serde_json::json!({"stationId": "LOWW_TWR", "positionId": "LOWW_TWR"})
+        );
+        assert!(json["at"].as_u64().unwrap() > 0);
+
+        let CoverageStreamEvent::StationChange(second) =
+            event(&stream.next().await.unwrap().unwrap())
+        else {
+            panic!("Expected station change");
+        };
+        assert_eq!(second.change, online("LOWW_GND"));
+        assert!(second.at > 0);
+
+        // Live broadcasts to clients don't carry timestamps
+        let message = serde_json::to_value(ServerMessage::StationChanges(server::StationChanges {
+            changes: vec![online("LOWW_TWR")],
+        }))
+        .unwrap();
+        assert!(!message.to_string().contains("\"at\""));
+    }
+
+    #[test(tokio::test)]
+    async fn stream_emits_client_events() {
+        let (_changes_tx, changes_rx) = broadcast::channel::<TimestampedStationChange>(1);
+        let (broadcast_tx, broadcast_rx) = broadcast::channel(4);
+        let (_shutdown_tx, shutdown_rx) = watch::channel(());
+        let mut stream = Box::pin(ndjson_stream(
+            CoverageSnapshot::default(),
+            changes_rx,
+            broadcast_rx,
+            shutdown_rx,
+        ));
+        stream.next().await.unwrap().unwrap();
+
+        broadcast_tx
+            .send(ServerMessage::ClientConnected(server::ClientConnected {
+                client: server::ClientInfo {
+                    id: ClientId::from("client1"),
+                    display_name: "client1".to_string(),
+                    frequency: "119.400".to_string(),
+                    position_id: Some(PositionId::from("LOWW_TWR")),
+                },
+            }))
+            .unwrap();
+        // Unrelated broadcasts are skipped
+        broadcast_tx
+            .send(ServerMessage::ClientList(server::ClientList {
+                clients: vec![],
+            }))
+            .unwrap();
+        broadcast_tx
+            .send(ServerMessage::ClientDisconnected(
+                server::ClientDisconnected {
+                    client_id: ClientId::from("client1"),
+                },
+            ))
+            .unwrap();
+
+        assert_eq!(
+            event(&stream.next().await.unwrap().unwrap()),
+            CoverageStreamEvent::ClientConnected {
+                client_id: ClientId::from("client1"),
+                position_id: Some(PositionId::from("LOWW_TWR")),
+            }
+        );
+        assert_eq!(
+            event(&stream.next().await.unwrap().unwrap()),
+            CoverageStreamEvent::ClientDisconnected {
+                client_id: ClientId::from("client1"),
+            }
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn stream_closes_for_slow_consumers() {
+        let (changes_tx, changes_rx) = broadcast::channel(1);
+        let (_broadcast_tx, broadcast_rx) = broadcast::channel::<ServerMessage>(1);
+        let (_shutdown_tx, shutdown_rx) = watch::channel(());
+        let mut stream = Box::pin(ndjson_stream(
+            CoverageSnapshot::default(),
+            changes_rx,
+            broadcast_rx,
+            shutdown_rx,
+        ));
+        stream.next().await.unwrap().unwrap();
+
+        changes_tx.send(timestamped(online("LOWW_TWR"))).unwrap();
+        changes_tx.send(timestamped(online("LOWW_GND"))).unwrap();
+
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn stream_ends_on_shutdown() {
+        let (_changes_tx, changes_rx) = broadcast::channel::<TimestampedStationChange>(1);
+        let (_broadcast_tx, broadcast_rx) = broadcast::channel::<ServerMessage>(1);
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        let mut stream = Box::pin(ndjson_stream(
+            CoverageSnapshot::default(),
+            changes_rx,
+            broadcast_rx,
+            shutdown_rx,
+        ));
+        stream.next().await.unwrap().unwrap();
+
+        shutdown_tx.send(()).unwrap();
+
+        assert!(stream.next().await.is_none());
+    }
+}
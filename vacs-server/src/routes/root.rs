@@ -25,6 +25,10 @@ mod get {
     use std::time::Duration;
 
     pub async fn health(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+        if !state.is_ready() {
+            return (StatusCode::SERVICE_UNAVAILABLE, "Not Ready");
+        }
+
         match tokio::time::timeout(Duration::from_secs(3), state.health_check()).await {
             Ok(Ok(_)) => (StatusCode::OK, "OK"),
             _ => (StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable"),
@@ -45,3 +49,31 @@ mod get {
         Ok(Json(version_info))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::test_util::TestSetup;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    #[tokio::test]
+    async fn health_reports_readiness() {
+        let setup = TestSetup::new();
+        assert!(!setup.app_state.is_ready());
+
+        let response = get::health(State(setup.app_state.clone()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        setup.app_state.mark_ready();
+        assert!(setup.app_state.is_ready());
+
+        let response = get::health(State(setup.app_state.clone()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -31,7 +31,9 @@ mod get {
         tracing::debug!(?user, "Generating websocket token");
         let token = state.generate_ws_auth_token(user.cid.as_str()).await?;
 
-        Ok(Json(WebSocketToken { token }))
+        Ok(Json(WebSocketToken {
+            token: token.into(),
+        }))
     }
 }
 
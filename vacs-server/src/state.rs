@@ -1,35 +1,52 @@
 pub mod calls;
 pub mod clients;
+pub mod history;
 
 use crate::config;
 use crate::config::AppConfig;
 use crate::dataset::DatasetManager;
+use crate::dataset::remote::RemoteDataset;
 use crate::ice::provider::IceConfigProvider;
-use crate::metrics::ErrorMetrics;
 use crate::metrics::guards::ClientConnectionGuard;
+use crate::metrics::{DataFeedMetrics, ErrorMetrics};
 use crate::ratelimit::RateLimiters;
 use crate::release::UpdateChecker;
-use crate::state::calls::CallManager;
-use crate::state::clients::{ClientManager, ClientSession};
+use crate::state::calls::{CallHook, CallManager};
+use crate::state::clients::{
+    ClientManager, ClientManagerError, ClientReceiver, ClientRepair, ClientSession,
+};
+use crate::state::history::{CoverageHistory, CoverageScenario};
 use crate::store::{Store, StoreBackend};
 use anyhow::Context;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::{broadcast, mpsc, watch};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, broadcast, watch};
 use tokio::task::JoinHandle;
 use tokio::time;
 use tracing::{Instrument, instrument};
 use uuid::Uuid;
+use vacs_protocol::http::coverage::TimestampedStationChange;
 use vacs_protocol::profile::{ActiveProfile, ProfileId};
-use vacs_protocol::vatsim::{ClientId, PositionId};
-use vacs_protocol::ws::server::{ClientInfo, DisconnectReason, ServerMessage, StationInfo};
+use vacs_protocol::vatsim::{ClientId, PositionId, StationChange, StationId, StationOfflineReason};
+use vacs_protocol::ws::server::{
+    ActiveCallInfo, Announcement, ClientInfo, DisconnectReason, ServerMessage, StationInfo,
+};
 use vacs_protocol::ws::shared::{Error, ErrorReason};
 use vacs_vatsim::ControllerInfo;
+use vacs_vatsim::coverage::flight_information_region::FlightInformationRegionId;
 use vacs_vatsim::coverage::network::Network;
+use vacs_vatsim::coverage::simulate::{self, SimulationEvent};
 use vacs_vatsim::data_feed::DataFeed;
 use vacs_vatsim::slurper::SlurperClient;
 
+/// Maximum delay of a scheduled airspace change.
+pub const MAX_AIRSPACE_CHANGE_DELAY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Minimum period of the station debounce and coalescing tasks, so tiny windows don't result in a
+/// zero (invalid) or busy ticker.
+const MIN_STATION_TICK: Duration = Duration::from_millis(1);
+
 pub struct AppState {
     pub config: AppConfig,
     pub updates: UpdateChecker,
@@ -42,6 +59,14 @@ pub struct AppState {
     slurper: SlurperClient,
     data_feed: Arc<dyn DataFeed>,
     rate_limiters: RateLimiters,
+    ws_connections: Arc<Semaphore>,
+    ready: AtomicBool,
+    /// Stations temporarily closed at runtime, reset on every dataset reload.
+    closed_stations: Arc<parking_lot::RwLock<HashSet<StationId>>>,
+    /// Recent coverage events, exportable as a scenario for reproducing coverage issues.
+    coverage_history: CoverageHistory,
+    /// Incremented whenever an airspace change is scheduled, discarding the pending one.
+    airspace_change_generation: AtomicU64,
     shutdown_rx: watch::Receiver<()>,
 }
 
@@ -60,38 +85,106 @@ impl AppState {
         dataset: Option<DatasetManager>,
     ) -> Self {
         let (broadcast_tx, _) = broadcast::channel(config::BROADCAST_CHANNEL_CAPACITY);
+        let ws_connections = Arc::new(Semaphore::new(config.websocket.max_connections));
+        let closed_stations = Arc::new(parking_lot::RwLock::new(HashSet::new()));
+        let clients = ClientManager::new(broadcast_tx.clone(), network)
+            .with_unknown_facility_tolerance(config.vatsim.unknown_facility_tolerance)
+            .with_max_pending_disconnects(config.vatsim.max_pending_disconnects)
+            .with_frequency_only_matching(config.vatsim.frequency_only_matching)
+            .with_ambiguous_position_selection(config.vatsim.ambiguous_position_selection)
+            .with_send_queue_overflow_policy(config.websocket.send_queue_overflow_policy)
+            .with_max_message_size(config.websocket.max_message_size)
+            .with_position_without_coverage_warning(config.vatsim.warn_position_without_coverage)
+            .with_show_vatsim_only_stations(config.vatsim.show_vatsim_only_stations)
+            .with_retain_coverage_without_clients(config.vatsim.retain_coverage_without_clients)
+            .with_station_flap_debounce(config.vatsim.station_flap_debounce)
+            .with_station_change_coalescing(config.vatsim.station_change_coalescing)
+            .with_last_controller_retention(config.vatsim.last_controller_retention)
+            .with_relief_patterns(config.vatsim.relief_patterns.clone())
+            .with_default_profiles(config.vatsim.default_profiles.clone())
+            .with_closed_stations(closed_stations.clone());
         Self {
             config,
             updates,
             ice_config_provider,
             store,
             calls: CallManager::new(),
-            clients: ClientManager::new(broadcast_tx.clone(), network),
+            clients,
             dataset,
             broadcast_tx,
             slurper,
             data_feed,
             rate_limiters,
+            ws_connections,
+            ready: AtomicBool::new(false),
+            closed_stations,
+            coverage_history: CoverageHistory::new(config::COVERAGE_HISTORY_CAPACITY),
+            airspace_change_generation: AtomicU64::new(0),
             shutdown_rx,
         }
     }
 
+    /// Sets the hook notified whenever an active call starts or ends. Defaults to a
+    /// [`NoopCallHook`](crate::state::calls::NoopCallHook).
+    pub fn with_call_hook(mut self, hook: Arc<dyn CallHook>) -> Self {
+        self.calls = self.calls.with_hook(hook);
+        self
+    }
+
+    /// Reserves a slot for a new websocket connection, returning `None` if the configured
+    /// connection limit has been reached. The slot is released once the permit is dropped.
+    pub fn try_acquire_ws_connection(&self) -> Option<OwnedSemaphorePermit> {
+        self.ws_connections.clone().try_acquire_owned().ok()
+    }
+
     pub fn get_client_receivers(
         &self,
     ) -> (broadcast::Receiver<ServerMessage>, watch::Receiver<()>) {
         (self.broadcast_tx.subscribe(), self.shutdown_rx.clone())
     }
 
-    #[instrument(level = "debug", skip(self, client_connection_guard), err)]
+    /// Subscribes to the server-wide feed of station changes, alongside a receiver notified on
+    /// server shutdown.
+    pub fn subscribe_station_changes(
+        &self,
+    ) -> (
+        broadcast::Receiver<TimestampedStationChange>,
+        watch::Receiver<()>,
+    ) {
+        (
+            self.clients.subscribe_station_changes(),
+            self.shutdown_rx.clone(),
+        )
+    }
+
     pub async fn register_client(
         &self,
         client_info: ClientInfo,
         active_profile: ActiveProfile<ProfileId>,
         client_connection_guard: ClientConnectionGuard,
-    ) -> anyhow::Result<(ClientSession, mpsc::Receiver<ServerMessage>)> {
+    ) -> anyhow::Result<(ClientSession, ClientReceiver)> {
+        self.register_client_with_tags(
+            client_info,
+            active_profile,
+            Vec::new(),
+            client_connection_guard,
+        )
+        .await
+    }
+
+    /// Registers the client like [`Self::register_client`], storing the tags it sent at login on
+    /// its session.
+    #[instrument(level = "debug", skip(self, client_connection_guard), err)]
+    pub async fn register_client_with_tags(
+        &self,
+        client_info: ClientInfo,
+        active_profile: ActiveProfile<ProfileId>,
+        tags: Vec<String>,
+        client_connection_guard: ClientConnectionGuard,
+    ) -> anyhow::Result<(ClientSession, ClientReceiver)> {
         tracing::trace!("Registering client");
 
-        if self.clients.is_empty().await {
+        if !self.clients.retains_coverage_without_clients() && self.clients.is_empty().await {
             tracing::debug!("First client connected, triggering initial VATSIM controller sync");
             if let Err(err) = self
                 .update_vatsim_controllers(&mut HashSet::new(), false)
@@ -103,8 +196,12 @@ impl AppState {
 
         let (client, rx) = self
             .clients
-            .add_client(client_info, active_profile, client_connection_guard)
+            .add_client_with_tags(client_info, active_profile, tags, client_connection_guard)
             .await?;
+        self.coverage_history.record(SimulationEvent::Connect {
+            client_id: client.id().clone(),
+            position_id: client.position_id().cloned(),
+        });
 
         tracing::trace!("Client registered");
         Ok((client, rx))
@@ -116,15 +213,33 @@ impl AppState {
         client_id: &ClientId,
         disconnect_reason: Option<DisconnectReason>,
     ) {
+        let changes = self
+            .unregister_client_deferred(client_id, disconnect_reason)
+            .await;
+        self.clients.broadcast_station_changes(&changes).await;
+    }
+
+    /// Unregisters the client like [`Self::unregister_client`], but returns the resulting station
+    /// changes instead of broadcasting them.
+    async fn unregister_client_deferred(
+        &self,
+        client_id: &ClientId,
+        disconnect_reason: Option<DisconnectReason>,
+    ) -> Vec<StationChange> {
         tracing::trace!("Unregistering client");
 
-        self.clients
-            .remove_client(client_id.clone(), disconnect_reason)
+        let changes = self
+            .clients
+            .remove_client_deferred(client_id.clone(), disconnect_reason)
             .await;
+        self.coverage_history.record(SimulationEvent::Disconnect {
+            client_id: client_id.clone(),
+        });
 
         self.calls.cleanup_client_calls(self, client_id).await;
 
         tracing::debug!("Client unregistered");
+        changes
     }
 
     pub async fn list_clients(&self, self_client_id: Option<&ClientId>) -> Vec<ClientInfo> {
@@ -141,6 +256,27 @@ impl AppState {
             .await
     }
 
+    /// Lists active calls involving at least one station relevant to `active_profile`.
+    pub async fn list_active_calls(
+        &self,
+        active_profile: &ActiveProfile<ProfileId>,
+    ) -> Vec<ActiveCallInfo> {
+        let mut calls = Vec::new();
+        for call in self.calls.active_calls() {
+            let station_ids = self
+                .clients
+                .relevant_stations_of_clients(active_profile, &[&call.caller_id, &call.callee_id])
+                .await;
+            if !station_ids.is_empty() {
+                calls.push(ActiveCallInfo {
+                    call_id: call.call_id,
+                    station_ids,
+                });
+            }
+        }
+        calls
+    }
+
     pub async fn get_client(&self, client_id: &ClientId) -> Option<ClientSession> {
         self.clients.get_client(client_id).await
     }
@@ -219,10 +355,11 @@ impl AppState {
     #[instrument(level = "debug", skip(self), err)]
     pub async fn get_vatsim_controllers(&self) -> anyhow::Result<Vec<ControllerInfo>> {
         tracing::debug!("Retrieving controller info from VATSIM data feed");
-        self.data_feed
-            .fetch_controller_info()
-            .await
-            .map_err(Into::into)
+        let controllers = self.data_feed.fetch_controller_info().await?;
+        if let Some(stats) = self.data_feed.take_parse_stats() {
+            DataFeedMetrics::parsed(&stats);
+        }
+        Ok(controllers)
     }
 
     #[instrument(level = "debug", skip(state))]
@@ -245,13 +382,167 @@ impl AppState {
                             break;
                         }
                         _ = ticker.tick() => {
-                            if state.clients.is_empty().await {
+                            if !state.clients.retains_coverage_without_clients()
+                                && state.clients.is_empty().await
+                            {
                                 tracing::trace!("No clients connected, skipping controller update");
                                 continue;
                             }
 
-                            if let Err(err) = state.update_vatsim_controllers(&mut pending_disconnect, state.config.vatsim.require_active_connection).await {
-                                tracing::warn!(?err, "Failed to update controller info");
+                            // Fetching controllers doesn't modify any state and is cancelled on
+                            // shutdown, while an already started sync always runs to completion
+                            // to avoid leaving the coverage state half-updated.
+                            let controllers = tokio::select! {
+                                biased;
+                                _ = shutdown.changed() => {
+                                    tracing::info!("Shutdown requested while fetching controllers, cancelling update cycle");
+                                    break;
+                                }
+                                controllers = state.get_vatsim_controllers() => controllers,
+                            };
+
+                            match controllers {
+                                Ok(controllers) => {
+                                    state.sync_vatsim_controllers(controllers, &mut pending_disconnect, state.config.vatsim.require_active_connection).await;
+                                }
+                                Err(err) => {
+                                    tracing::warn!(?err, "Failed to update controller info");
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        )
+    }
+
+    /// Periodically sends the station offline changes held back by the station flap debounce,
+    /// ticking twice per window but at most every [`MIN_STATION_TICK`]. Returns `None` if
+    /// debouncing is disabled.
+    #[instrument(level = "debug", skip(state))]
+    pub fn start_station_debounce_task(state: Arc<AppState>) -> Option<JoinHandle<()>> {
+        let window = state.clients.station_flap_debounce();
+        if window.is_zero() {
+            return None;
+        }
+
+        Some(tokio::spawn(
+            async move {
+                let mut ticker = time::interval((window / 2).max(MIN_STATION_TICK));
+                ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+                let mut shutdown = state.shutdown_rx.clone();
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown.changed() => {
+                            tracing::info!("Shutting down station debounce task");
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            state.clients.flush_debounced_station_changes().await;
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        ))
+    }
+
+    /// Periodically sends the station changes collected within the coalescing window, ticking
+    /// twice per window but at most every [`MIN_STATION_TICK`]. Returns `None` if coalescing is
+    /// disabled.
+    #[instrument(level = "debug", skip(state))]
+    pub fn start_station_coalescing_task(state: Arc<AppState>) -> Option<JoinHandle<()>> {
+        let window = state.clients.station_change_coalescing();
+        if window.is_zero() {
+            return None;
+        }
+
+        Some(tokio::spawn(
+            async move {
+                let mut ticker = time::interval((window / 2).max(MIN_STATION_TICK));
+                ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+                let mut shutdown = state.shutdown_rx.clone();
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown.changed() => {
+                            tracing::info!("Shutting down station coalescing task");
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            state.clients.flush_coalesced_station_changes().await;
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        ))
+    }
+
+    #[instrument(level = "debug", skip(state))]
+    pub fn start_idle_reaper_task(state: Arc<AppState>) -> JoinHandle<()> {
+        tokio::spawn(
+            async move {
+                let idle_config = state.config.idle.clone();
+                let mut ticker = time::interval(idle_config.check_interval);
+                ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+                let mut shutdown = state.shutdown_rx.clone();
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown.changed() => {
+                            tracing::info!("Shutting down idle reaper task");
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            let idle_clients = state
+                                .clients
+                                .idle_clients(idle_config.timeout, idle_config.exempt_without_position)
+                                .await;
+
+                            for client_id in idle_clients {
+                                tracing::info!(?client_id, timeout = ?idle_config.timeout, "Disconnecting idle client");
+                                state
+                                    .unregister_client(&client_id, Some(DisconnectReason::IdleTimeout))
+                                    .await;
+                            }
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        )
+    }
+
+    #[instrument(level = "debug", skip(state, remote))]
+    pub fn start_remote_dataset_task(
+        state: Arc<AppState>,
+        remote: RemoteDataset,
+    ) -> JoinHandle<()> {
+        tokio::spawn(
+            async move {
+                let mut ticker = time::interval(remote.refresh_interval());
+                ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+                // The dataset was already fetched during startup, skip the immediate first tick.
+                ticker.tick().await;
+
+                let mut shutdown = state.shutdown_rx.clone();
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown.changed() => {
+                            tracing::info!("Shutting down remote dataset task");
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            if let Some(network) = remote.refresh().await {
+                                tracing::info!("Remote dataset changed, replacing network");
+                                state.replace_network(network).await;
                             }
                         }
                     }
@@ -281,38 +572,827 @@ impl AppState {
         let controllers = self.get_vatsim_controllers().await?;
         tracing::trace!(elapsed = ?start.elapsed(), "Finished retrieving VATSIM controllers");
 
+        self.sync_vatsim_controllers(controllers, pending_disconnect, require_active_connection)
+            .await;
+
+        tracing::debug!(elapsed = ?start.elapsed(), "Finished updating VATSIM controllers");
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, controllers, pending_disconnect), fields(controllers = controllers.len(), pending_disconnect = pending_disconnect.len()))]
+    async fn sync_vatsim_controllers(
+        &self,
+        controllers: Vec<ControllerInfo>,
+        pending_disconnect: &mut HashSet<ClientId>,
+        require_active_connection: bool,
+    ) {
         let start_sync = std::time::Instant::now();
-        let current: HashMap<ClientId, ControllerInfo> = controllers
+        let current: HashMap<ClientId, ControllerInfo> = vacs_vatsim::dedupe_controllers(
+            controllers
+                .into_iter()
+                .filter(|c| !c.callsign.ends_with("_SUP")),
+        );
+
+        let connected: HashSet<ClientId> = self
+            .clients
+            .list_clients(None)
+            .await
             .into_iter()
-            .filter(|c| !c.callsign.ends_with("_SUP"))
-            .map(|c| (c.cid.clone(), c))
+            .map(|client| client.id)
             .collect();
+        // Only keep controllers relevant to coverage, i.e. vacs clients and controllers matching a
+        // position of the network, to keep the history small.
+        self.coverage_history.record(SimulationEvent::DataFeed {
+            controllers: current
+                .values()
+                .filter(|c| {
+                    connected.contains(&c.cid) || !self.clients.find_positions(c).is_empty()
+                })
+                .cloned()
+                .collect(),
+        });
 
-        let disconnected_clients = self
+        let (disconnected_clients, mut changes) = self
             .clients
-            .sync_vatsim_state(&current, pending_disconnect, require_active_connection)
+            .sync_vatsim_state_deferred(&current, pending_disconnect, require_active_connection)
             .await;
         tracing::trace!(elapsed = ?start_sync.elapsed(), "Finished syncing VATSIM state");
 
         let start_unregister = std::time::Instant::now();
         for (cid, disconnect_reason) in disconnected_clients {
-            self.unregister_client(&cid, Some(disconnect_reason)).await;
+            changes.extend(
+                self.unregister_client_deferred(&cid, Some(disconnect_reason))
+                    .await,
+            );
         }
         tracing::trace!(elapsed = ?start_unregister.elapsed(), "Finished unregistering clients");
 
-        tracing::debug!(elapsed = ?start.elapsed(), "Finished updating VATSIM controllers");
-        Ok(())
+        // Clients receive all changes of a sync as a single batch, never observing the
+        // intermediate state between the coverage update and the removal of disconnected clients.
+        self.clients
+            .broadcast_station_changes(&simulate::coalesce_station_changes(changes))
+            .await;
+        // Clients that missed changes since the last sync, e.g. due to a full send queue, are
+        // brought back in sync with the coverage state once per sync.
+        self.clients.resync_stale_clients().await;
+    }
+
+    /// Whether the initial network coverage data has been loaded successfully and clients can be
+    /// served. Until then, the health endpoint reports not ready and logins are rejected.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    pub fn mark_ready(&self) {
+        if !self.ready.swap(true, Ordering::AcqRel) {
+            tracing::info!("Network coverage data loaded, ready to serve clients");
+        }
     }
 
     pub async fn health_check(&self) -> anyhow::Result<()> {
         self.store.is_healthy().await
     }
 
+    /// Exports the recent coverage events as a scenario, allowing the current situation to be
+    /// replayed in a test.
+    pub fn coverage_scenario(&self) -> CoverageScenario {
+        self.coverage_history.scenario(self.clients.dataset_info())
+    }
+
     pub fn rate_limiters(&self) -> &RateLimiters {
         &self.rate_limiters
     }
 
+    /// Replaces the network and reopens all closed stations. Clients receive all resulting
+    /// station changes as a single batch. Marks the server as ready unless `network` is empty.
     pub async fn replace_network(&self, network: Network) {
-        self.clients.replace_network(network).await;
+        let dataset_loaded = !network.is_empty();
+        let reopened_stations = std::mem::take(&mut *self.closed_stations.write());
+        let mut changes = self.clients.replace_network_deferred(network).await;
+        // Clients saw reopened stations offline, so only their current controller is relevant
+        changes.retain(|change| !reopened_stations.contains(change.station_id()));
+
+        for station_id in reopened_stations {
+            tracing::debug!(?station_id, "Reopening closed station after network reload");
+            if let Some(position_id) = self.clients.visible_controlling_position(&station_id).await
+            {
+                changes.push(StationChange::Online {
+                    station_id,
+                    position_id,
+                });
+            }
+        }
+        self.clients.broadcast_station_changes(&changes).await;
+
+        if dataset_loaded {
+            self.mark_ready();
+        }
+    }
+
+    /// Replaces the network with `network` once `delay` elapsed, e.g. for a dataset reload
+    /// significantly changing the airspace structure. All clients are warned with an announcement
+    /// the configured lead time before, or right away if the change is due sooner.
+    ///
+    /// Scheduling another change discards the pending one, as does any other replacement of the
+    /// network or its profiles in the meantime (e.g. an immediate reload or a remote dataset
+    /// refresh), so an outdated dataset never overwrites a newer one. `delay` is capped at
+    /// [`MAX_AIRSPACE_CHANGE_DELAY`].
+    pub fn schedule_airspace_change(self: &Arc<Self>, network: Network, delay: Duration) {
+        let delay = delay.min(MAX_AIRSPACE_CHANGE_DELAY);
+        let generation = self
+            .airspace_change_generation
+            .fetch_add(1, Ordering::AcqRel)
+            + 1;
+        let network_generation = self.clients.network_generation();
+        let lead = self
+            .config
+            .admin
+            .airspace_change_announcement_lead
+            .min(delay);
+        let change_at = SystemTime::now() + delay;
+        tracing::info!(?delay, ?lead, "Scheduling airspace change");
+
+        let state = Arc::clone(self);
+        tokio::spawn(
+            async move {
+                if !state
+                    .await_airspace_change(generation, network_generation, delay - lead)
+                    .await
+                {
+                    tracing::info!("Scheduled airspace change discarded before announcement");
+                    return;
+                }
+
+                let announcement = Announcement {
+                    message: "Airspace change scheduled, station coverage may change shortly"
+                        .to_string(),
+                    airspace_change_at: Some(
+                        change_at
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                    ),
+                };
+                if let Err(err) = state.clients.broadcast(announcement) {
+                    tracing::warn!(?err, "Failed to broadcast airspace change announcement");
+                }
+
+                if !state
+                    .await_airspace_change(generation, network_generation, lead)
+                    .await
+                {
+                    tracing::info!("Scheduled airspace change discarded after announcement");
+                    return;
+                }
+
+                tracing::info!("Performing scheduled airspace change");
+                state.replace_network(network).await;
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Waits for `duration`, returning whether the airspace change `generation` is still due
+    /// afterwards, i.e. it wasn't superseded by another one, the network wasn't replaced since
+    /// `network_generation` and the server isn't shutting down.
+    async fn await_airspace_change(
+        &self,
+        generation: u64,
+        network_generation: u64,
+        duration: Duration,
+    ) -> bool {
+        let mut shutdown = self.shutdown_rx.clone();
+        tokio::select! {
+            biased;
+            _ = shutdown.changed() => return false,
+            _ = time::sleep(duration) => {}
+        }
+        self.airspace_change_generation.load(Ordering::Acquire) == generation
+            && self.clients.network_generation() == network_generation
+    }
+
+    pub async fn set_fir_enabled(
+        &self,
+        fir_id: &FlightInformationRegionId,
+        enabled: bool,
+    ) -> Result<bool, ClientManagerError> {
+        self.clients.set_fir_enabled(fir_id, enabled).await
+    }
+
+    /// Repairs the session of the given client, re-deriving its position from the current network
+    /// and its connection in the VATSIM data feed. If the data feed is unavailable, the client
+    /// keeps its current position, but the online state is still fixed and resent.
+    #[instrument(level = "info", skip(self))]
+    pub async fn repair_client(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<ClientRepair, ClientManagerError> {
+        let controller = match self.get_vatsim_controllers().await {
+            Ok(controllers) => vacs_vatsim::dedupe_controllers(
+                controllers
+                    .into_iter()
+                    .filter(|c| !c.callsign.ends_with("_SUP")),
+            )
+            .remove(client_id),
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    "Failed to retrieve VATSIM controllers, keeping client position"
+                );
+                None
+            }
+        };
+        self.clients
+            .repair_client(client_id, controller.as_ref())
+            .await
+    }
+
+    pub async fn set_coverage_override(
+        &self,
+        station_id: &StationId,
+        position_id: &PositionId,
+    ) -> Result<bool, ClientManagerError> {
+        self.clients
+            .set_coverage_override(station_id, position_id)
+            .await
+    }
+
+    pub async fn clear_coverage_override(
+        &self,
+        station_id: &StationId,
+    ) -> Result<bool, ClientManagerError> {
+        self.clients.clear_coverage_override(station_id).await
+    }
+
+    pub fn is_station_closed(&self, station_id: &StationId) -> bool {
+        self.closed_stations.read().contains(station_id)
+    }
+
+    /// Temporarily closes or reopens the given station. Closed stations are reported offline to
+    /// clients and can't be called until reopened or the dataset is reloaded. Returns whether the
+    /// state of the station changed.
+    #[instrument(level = "info", skip(self))]
+    pub async fn set_station_closed(
+        &self,
+        station_id: &StationId,
+        closed: bool,
+    ) -> Result<bool, ClientManagerError> {
+        if !self.clients.has_station(station_id) {
+            return Err(ClientManagerError::UnknownStation(station_id.to_string()));
+        }
+
+        let changed = if closed {
+            self.closed_stations.write().insert(station_id.clone())
+        } else {
+            self.closed_stations.write().remove(station_id)
+        };
+        if !changed {
+            tracing::debug!("Station state unchanged, skipping station changes");
+            return Ok(false);
+        }
+
+        if let Some(position_id) = self.clients.visible_controlling_position(station_id).await {
+            let change = if closed {
+                StationChange::Offline {
+                    station_id: station_id.clone(),
+                    reason: Some(StationOfflineReason::TemporarilyClosed),
+                }
+            } else {
+                StationChange::Online {
+                    station_id: station_id.clone(),
+                    position_id,
+                }
+            };
+            self.clients.broadcast_station_changes(&[change]).await;
+        }
+
+        tracing::info!("Station state updated");
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::AppState;
+    use crate::state::clients::{ClientManagerError, ClientReceiver};
+    use crate::ws::test_util::{TestSetup, create_client_info};
+    use pretty_assertions::{assert_eq, assert_matches};
+    use std::collections::HashSet;
+    use std::time::Duration;
+    use test_log::test;
+    use vacs_protocol::profile::ActiveProfile;
+    use vacs_protocol::vatsim::{
+        ClientId, PositionId, StationChange, StationId, StationOfflineReason,
+    };
+    use vacs_protocol::ws::server::{Announcement, ClientInfo, ServerMessage, StationInfo};
+    use vacs_vatsim::coverage::network::Network;
+    use vacs_vatsim::coverage::test_support::TestFirBuilder;
+    use vacs_vatsim::{ControllerInfo, FacilityType};
+
+    fn create_network(dir: &std::path::Path) -> Network {
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP"])
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir)
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn station_tasks_clamp_tiny_windows() {
+        let setup = TestSetup::with_config(|config| {
+            config.vatsim.station_flap_debounce = Duration::from_nanos(1);
+            config.vatsim.station_change_coalescing = Duration::from_nanos(1);
+        });
+
+        let debounce_task = AppState::start_station_debounce_task(setup.app_state.clone()).unwrap();
+        let coalescing_task =
+            AppState::start_station_coalescing_task(setup.app_state.clone()).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(!debounce_task.is_finished());
+        assert!(!coalescing_task.is_finished());
+        debounce_task.abort();
+        coalescing_task.abort();
+    }
+
+    #[test(tokio::test)]
+    async fn replace_network_marks_ready_only_for_loaded_datasets() {
+        let setup = TestSetup::new();
+
+        setup.app_state.replace_network(Network::empty()).await;
+        assert!(!setup.app_state.is_ready());
+
+        let dir = tempfile::tempdir().unwrap();
+        setup
+            .app_state
+            .replace_network(create_network(dir.path()))
+            .await;
+        assert!(setup.app_state.is_ready());
+    }
+
+    /// Creates a test setup with a client controlling LOWW_TWR, returning its receiver with all
+    /// messages sent during registration drained.
+    async fn closed_station_setup() -> (tempfile::TempDir, TestSetup, ClientReceiver) {
+        let setup = TestSetup::new();
+        let dir = tempfile::tempdir().unwrap();
+        setup
+            .app_state
+            .replace_network(create_network(dir.path()))
+            .await;
+
+        let (_session, mut rx) = setup
+            .register_client_with_profile(
+                ClientInfo {
+                    position_id: Some(PositionId::from("LOWW_TWR")),
+                    ..create_client_info(2)
+                },
+                ActiveProfile::Custom,
+            )
+            .await;
+        while rx.try_recv().is_ok() {}
+
+        (dir, setup, rx)
+    }
+
+    fn station_changes(rx: &mut ClientReceiver) -> Vec<StationChange> {
+        let mut changes = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            if let ServerMessage::StationChanges(station_changes) = message {
+                changes.extend(station_changes.changes);
+            }
+        }
+        changes
+    }
+
+    async fn listed_stations(setup: &TestSetup) -> Vec<StationInfo> {
+        setup
+            .app_state
+            .list_stations(&ActiveProfile::Custom, None)
+            .await
+    }
+
+    #[test(tokio::test)]
+    async fn controller_update_task_stops_on_shutdown() {
+        let setup = TestSetup::new();
+        let (_session, _rx) = setup
+            .app_state
+            .register_client(
+                ClientInfo {
+                    id: ClientId::from("client1"),
+                    display_name: "Client 1".to_string(),
+                    frequency: "100.000".to_string(),
+                    position_id: None,
+                },
+                ActiveProfile::None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
+
+        let task = super::AppState::start_controller_update_task(
+            setup.app_state.clone(),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        setup.shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("Controller update task did not stop on shutdown")
+            .expect("Controller update task panicked");
+
+        assert!(
+            setup
+                .app_state
+                .clients
+                .is_client_connected(&ClientId::from("client1"))
+                .await
+        );
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn idle_reaper_task_disconnects_idle_clients() {
+        let setup = TestSetup::with_config(|config| {
+            config.idle.timeout = Duration::from_secs(60);
+            config.idle.check_interval = Duration::from_secs(1);
+        });
+        let client_id = ClientId::from("client1");
+        let (session, _rx) = setup.register_client(create_client_info(1)).await;
+
+        let task = super::AppState::start_idle_reaper_task(setup.app_state.clone());
+
+        tokio::time::sleep(Duration::from_secs(45)).await;
+        session.touch();
+        tokio::time::sleep(Duration::from_secs(45)).await;
+        assert!(
+            setup
+                .app_state
+                .clients
+                .is_client_connected(&client_id)
+                .await,
+            "client active within the idle timeout must stay connected"
+        );
+
+        tokio::time::sleep(Duration::from_secs(20)).await;
+        assert!(
+            !setup
+                .app_state
+                .clients
+                .is_client_connected(&client_id)
+                .await,
+            "client idle beyond the idle timeout must be disconnected"
+        );
+
+        setup.shutdown_tx.send(()).unwrap();
+        task.await.expect("Idle reaper task panicked");
+    }
+
+    #[test(tokio::test)]
+    async fn closed_station_is_offline_until_reopened() {
+        let (_dir, setup, mut rx) = closed_station_setup().await;
+        let station_id = StationId::from("LOWW_TWR");
+
+        assert!(
+            setup
+                .app_state
+                .set_station_closed(&station_id, true)
+                .await
+                .unwrap()
+        );
+        assert!(setup.app_state.is_station_closed(&station_id));
+        assert_eq!(
+            station_changes(&mut rx),
+            vec![StationChange::Offline {
+                station_id: station_id.clone(),
+                reason: Some(StationOfflineReason::TemporarilyClosed),
+            }]
+        );
+        assert!(
+            listed_stations(&setup)
+                .await
+                .iter()
+                .all(|station| station.id != station_id),
+            "Closed station must not be listed"
+        );
+
+        assert!(
+            !setup
+                .app_state
+                .set_station_closed(&station_id, true)
+                .await
+                .unwrap(),
+            "Closing an already closed station must not change anything"
+        );
+        assert!(station_changes(&mut rx).is_empty());
+
+        assert!(
+            setup
+                .app_state
+                .set_station_closed(&station_id, false)
+                .await
+                .unwrap()
+        );
+        assert!(!setup.app_state.is_station_closed(&station_id));
+        assert_eq!(
+            station_changes(&mut rx),
+            vec![StationChange::Online {
+                station_id: station_id.clone(),
+                position_id: PositionId::from("LOWW_TWR"),
+            }]
+        );
+        assert!(
+            listed_stations(&setup)
+                .await
+                .iter()
+                .any(|station| station.id == station_id)
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn closed_station_suppresses_coverage_changes() {
+        let (_dir, setup, mut rx) = closed_station_setup().await;
+        let station_id = StationId::from("LOWW_APP");
+
+        setup
+            .app_state
+            .set_station_closed(&station_id, true)
+            .await
+            .unwrap();
+        assert!(
+            station_changes(&mut rx).is_empty(),
+            "Closing an offline station must not send any changes"
+        );
+
+        let (_session, _app_rx) = setup
+            .register_client_with_profile(
+                ClientInfo {
+                    position_id: Some(PositionId::from("LOWW_APP")),
+                    ..create_client_info(3)
+                },
+                ActiveProfile::Custom,
+            )
+            .await;
+        assert!(
+            station_changes(&mut rx).is_empty(),
+            "Closed station coming online must not be sent to clients"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn closed_station_unknown() {
+        let (_dir, setup, _rx) = closed_station_setup().await;
+
+        assert_matches!(
+            setup
+                .app_state
+                .set_station_closed(&StationId::from("LOWW_GND"), true)
+                .await,
+            Err(ClientManagerError::UnknownStation(_))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn closed_station_reopened_on_network_reload() {
+        let (dir, setup, mut rx) = closed_station_setup().await;
+        let station_id = StationId::from("LOWW_TWR");
+
+        setup
+            .app_state
+            .set_station_closed(&station_id, true)
+            .await
+            .unwrap();
+        station_changes(&mut rx);
+
+        setup
+            .app_state
+            .replace_network(create_network(dir.path()))
+            .await;
+
+        assert!(!setup.app_state.is_station_closed(&station_id));
+        assert_eq!(
+            station_changes(&mut rx),
+            vec![StationChange::Online {
+                station_id,
+                position_id: PositionId::from("LOWW_TWR"),
+            }]
+        );
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn scheduled_airspace_change_announced_before_batched_reload() {
+        let (dir, mut setup, mut rx) = closed_station_setup().await;
+        setup
+            .app_state
+            .set_station_closed(&StationId::from("LOWW_TWR"), true)
+            .await
+            .unwrap();
+        station_changes(&mut rx);
+        while setup.broadcast_rx.try_recv().is_ok() {}
+
+        // LOWW_APP is additionally covered by LOWW_TWR, which is reopened by the reload
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP", "LOWW_TWR"])
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir.path());
+        let lead = setup
+            .app_state
+            .config
+            .admin
+            .airspace_change_announcement_lead;
+        setup
+            .app_state
+            .schedule_airspace_change(network, lead + Duration::from_secs(60));
+
+        tokio::time::sleep(Duration::from_secs(59)).await;
+        assert!(setup.broadcast_rx.try_recv().is_err());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_matches!(
+            setup.broadcast_rx.try_recv(),
+            Ok(ServerMessage::Announcement(Announcement {
+                airspace_change_at: Some(_),
+                ..
+            }))
+        );
+        assert!(
+            rx.try_recv().is_err(),
+            "network must not be replaced before the scheduled time"
+        );
+        assert!(
+            setup
+                .app_state
+                .is_station_closed(&StationId::from("LOWW_TWR"))
+        );
+
+        tokio::time::sleep(lead).await;
+        let mut batches = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            if let ServerMessage::StationChanges(station_changes) = message {
+                batches.push(station_changes.changes);
+            }
+        }
+        assert_eq!(
+            batches,
+            vec![vec![
+                StationChange::Online {
+                    station_id: StationId::from("LOWW_APP"),
+                    position_id: PositionId::from("LOWW_TWR"),
+                },
+                StationChange::Online {
+                    station_id: StationId::from("LOWW_TWR"),
+                    position_id: PositionId::from("LOWW_TWR"),
+                },
+            ]]
+        );
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn rescheduled_airspace_change_discards_pending_one() {
+        let (dir, setup, mut rx) = closed_station_setup().await;
+        let network = || {
+            TestFirBuilder::new("LOVV")
+                .station("LOWW_APP", &["LOWW_APP", "LOWW_TWR"])
+                .station("LOWW_TWR", &["LOWW_TWR"])
+                .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+                .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+                .build(dir.path())
+        };
+        let lead = setup
+            .app_state
+            .config
+            .admin
+            .airspace_change_announcement_lead;
+        setup.app_state.schedule_airspace_change(network(), lead);
+        setup
+            .app_state
+            .schedule_airspace_change(network(), lead * 2);
+
+        tokio::time::sleep(lead + Duration::from_secs(1)).await;
+        assert!(station_changes(&mut rx).is_empty());
+
+        tokio::time::sleep(lead).await;
+        assert_eq!(
+            station_changes(&mut rx),
+            vec![StationChange::Online {
+                station_id: StationId::from("LOWW_APP"),
+                position_id: PositionId::from("LOWW_TWR"),
+            }]
+        );
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn network_replacement_discards_pending_airspace_change() {
+        let (dir, mut setup, mut rx) = closed_station_setup().await;
+        while setup.broadcast_rx.try_recv().is_ok() {}
+
+        let lead = setup
+            .app_state
+            .config
+            .admin
+            .airspace_change_announcement_lead;
+        setup.app_state.schedule_airspace_change(
+            TestFirBuilder::new("LOVV")
+                .station("LOWW_APP", &["LOWW_APP", "LOWW_TWR"])
+                .station("LOWW_TWR", &["LOWW_TWR"])
+                .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+                .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+                .build(dir.path()),
+            lead + Duration::from_secs(60),
+        );
+
+        // An immediate reload of the current dataset supersedes the scheduled one
+        setup
+            .app_state
+            .replace_network(create_network(dir.path()))
+            .await;
+        station_changes(&mut rx);
+
+        tokio::time::sleep(lead + Duration::from_secs(61)).await;
+        assert!(
+            setup.broadcast_rx.try_recv().is_err(),
+            "discarded airspace change must not be announced"
+        );
+        assert!(station_changes(&mut rx).is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn sync_sends_single_ordered_batch() {
+        let setup = TestSetup::new();
+        let dir = tempfile::tempdir().unwrap();
+        setup
+            .app_state
+            .replace_network(
+                TestFirBuilder::new("LOVV")
+                    .station("LOWW_APP", &["LOWW_APP"])
+                    .station("LOWW_TWR", &["LOWW_TWR"])
+                    .station("LOWW_GND", &["LOWW_GND"])
+                    .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+                    .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+                    .position("LOWW_GND", &["LOWW"], "121.600", "GND")
+                    .build(dir.path()),
+            )
+            .await;
+
+        let mut sessions = Vec::new();
+        for (id, position_id) in [(2, Some("LOWW_TWR")), (3, Some("LOWW_APP")), (4, None)] {
+            sessions.push(
+                setup
+                    .register_client_with_profile(
+                        ClientInfo {
+                            position_id: position_id.map(PositionId::from),
+                            ..create_client_info(id)
+                        },
+                        ActiveProfile::Custom,
+                    )
+                    .await,
+            );
+        }
+        let rx = &mut sessions[0].1;
+        while rx.try_recv().is_ok() {}
+
+        // client4 moves to LOWW_GND during the sync, while client3 is disconnected afterwards.
+        let controllers = vec![
+            ControllerInfo {
+                cid: ClientId::from("client2"),
+                callsign: "LOWW_TWR".to_string(),
+                frequency: "119.400".to_string(),
+                facility_type: FacilityType::Tower,
+            },
+            ControllerInfo {
+                cid: ClientId::from("client4"),
+                callsign: "LOWW_GND".to_string(),
+                frequency: "121.600".to_string(),
+                facility_type: FacilityType::Ground,
+            },
+        ];
+        let mut pending_disconnect = HashSet::from([ClientId::from("client3")]);
+        setup
+            .app_state
+            .sync_vatsim_controllers(controllers, &mut pending_disconnect, true)
+            .await;
+
+        let rx = &mut sessions[0].1;
+        let mut batches = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            if let ServerMessage::StationChanges(station_changes) = message {
+                batches.push(station_changes.changes);
+            }
+        }
+        assert_eq!(
+            batches,
+            vec![vec![
+                StationChange::Online {
+                    station_id: StationId::from("LOWW_GND"),
+                    position_id: PositionId::from("LOWW_GND"),
+                },
+                StationChange::Offline {
+                    station_id: StationId::from("LOWW_APP"),
+                    reason: None,
+                },
+            ]]
+        );
     }
 }
@@ -1,8 +1,11 @@
+mod hook;
 mod manager;
+pub use hook::*;
 pub use manager::*;
 
 use crate::metrics::guards::{CallAttemptGuard, CallAttemptOutcome, CallGuard};
 use std::collections::HashSet;
+use std::time::SystemTime;
 use vacs_protocol::vatsim::ClientId;
 use vacs_protocol::ws::shared::{CallId, CallTarget};
 
@@ -32,11 +35,21 @@ pub struct ActiveCall {
     pub callee_id: ClientId,
 }
 
+/// A one-way broadcast from a single client to all clients covering a set of stations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveBroadcast {
+    pub call_id: CallId,
+    pub broadcaster_id: ClientId,
+    pub recipients: HashSet<ClientId>,
+}
+
 #[derive(Debug)]
 struct ActiveCallEntry {
     call_id: CallId,
     caller_id: ClientId,
     callee_id: ClientId,
+    target: CallTarget,
+    started_at: SystemTime,
     _guard: CallGuard,
 }
 
@@ -138,12 +151,27 @@ impl ActiveCall {
     }
 }
 
+impl ActiveBroadcast {
+    /// Returns whether `client_id` and `peer_id` are the broadcaster and one of its recipients.
+    pub fn links(&self, client_id: &ClientId, peer_id: &ClientId) -> bool {
+        (self.broadcaster_id == *client_id && self.recipients.contains(peer_id))
+            || (self.broadcaster_id == *peer_id && self.recipients.contains(client_id))
+    }
+}
+
 impl ActiveCallEntry {
-    pub fn new(call_id: CallId, caller_id: ClientId, callee_id: ClientId) -> Self {
+    pub fn new(
+        call_id: CallId,
+        caller_id: ClientId,
+        callee_id: ClientId,
+        target: CallTarget,
+    ) -> Self {
         Self {
             call_id,
             caller_id,
             callee_id,
+            target,
+            started_at: SystemTime::now(),
             _guard: CallGuard::new(),
         }
     }
@@ -161,6 +189,24 @@ impl ActiveCallEntry {
     pub fn involves(&self, client_id: &ClientId) -> bool {
         self.caller_id == *client_id || self.callee_id == *client_id
     }
+
+    pub fn started_record(&self) -> CallRecord {
+        CallRecord {
+            call_id: self.call_id,
+            caller_id: self.caller_id.clone(),
+            callee_id: self.callee_id.clone(),
+            target: self.target.clone(),
+            started_at: self.started_at,
+            duration: None,
+        }
+    }
+
+    pub fn ended_record(&self) -> CallRecord {
+        CallRecord {
+            duration: Some(self.started_at.elapsed().unwrap_or_default()),
+            ..self.started_record()
+        }
+    }
 }
 
 impl From<ActiveCallEntry> for ActiveCall {
@@ -0,0 +1,40 @@
+use std::time::{Duration, SystemTime};
+use vacs_protocol::vatsim::ClientId;
+use vacs_protocol::ws::shared::{CallId, CallTarget};
+
+/// Metadata about an established call passed to a [`CallHook`].
+///
+/// Audio is exchanged peer-to-peer between clients and never passes the server, so only call
+/// metadata is available here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallRecord {
+    pub call_id: CallId,
+    pub caller_id: ClientId,
+    pub callee_id: ClientId,
+    /// Target the caller originally dialed, e.g. the station that was called.
+    pub target: CallTarget,
+    pub started_at: SystemTime,
+    /// Duration of the call, only set once the call has ended.
+    pub duration: Option<Duration>,
+}
+
+/// Extension point notified about the lifecycle of active calls, allowing operators to log call
+/// events to their own systems (e.g. for training review).
+///
+/// Hooks are invoked synchronously after the call state has been updated, without holding any
+/// locks. Implementations performing I/O should hand the record off to a background task instead
+/// of blocking.
+pub trait CallHook: Send + Sync + 'static {
+    /// Called once a call has been accepted and is active.
+    fn call_started(&self, _call: &CallRecord) {}
+
+    /// Called once an active call has ended, either by one of the participants or due to a
+    /// disconnect.
+    fn call_ended(&self, _call: &CallRecord) {}
+}
+
+/// Default [`CallHook`] ignoring all call events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopCallHook;
+
+impl CallHook for NoopCallHook {}
@@ -1,9 +1,13 @@
 use crate::metrics::guards::CallAttemptOutcome;
 use crate::state::AppState;
-use crate::state::calls::{ActiveCall, ActiveCallEntry, RingingCall, RingingCallEntry};
+use crate::state::calls::{
+    ActiveBroadcast, ActiveCall, ActiveCallEntry, CallHook, NoopCallHook, RingingCall,
+    RingingCallEntry,
+};
 use parking_lot::RwLock;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::instrument;
 use vacs_protocol::vatsim::ClientId;
 use vacs_protocol::ws::server;
@@ -29,6 +33,8 @@ pub struct CallManager {
     client_incoming_calls: RwLock<HashMap<ClientId, HashSet<CallId>>>,
     client_outgoing_calls: RwLock<HashMap<ClientId, CallId>>,
     client_active_calls: RwLock<HashMap<ClientId, CallId>>,
+    broadcasts: RwLock<HashMap<CallId, ActiveBroadcast>>,
+    hook: Arc<dyn CallHook>,
 }
 
 impl Default for CallManager {
@@ -42,6 +48,7 @@ impl std::fmt::Debug for CallManager {
         f.debug_struct("CallStateManager")
             .field("ringing_calls", &self.ringing_calls.read().len())
             .field("active_calls", &self.active_calls.read().len())
+            .field("broadcasts", &self.broadcasts.read().len())
             .finish()
     }
 }
@@ -54,9 +61,17 @@ impl CallManager {
             client_incoming_calls: RwLock::new(HashMap::new()),
             client_outgoing_calls: RwLock::new(HashMap::new()),
             client_active_calls: RwLock::new(HashMap::new()),
+            broadcasts: RwLock::new(HashMap::new()),
+            hook: Arc::new(NoopCallHook),
         }
     }
 
+    /// Sets the hook notified whenever an active call starts or ends.
+    pub fn with_hook(mut self, hook: Arc<dyn CallHook>) -> Self {
+        self.hook = hook;
+        self
+    }
+
     pub fn has_outgoing_call(&self, client_id: &ClientId) -> bool {
         self.client_outgoing_calls.read().contains_key(client_id)
     }
@@ -68,6 +83,18 @@ impl CallManager {
             .is_some_and(|active| active.involves(client_id))
     }
 
+    pub fn has_broadcast_link(
+        &self,
+        call_id: &CallId,
+        client_id: &ClientId,
+        peer_id: &ClientId,
+    ) -> bool {
+        self.broadcasts
+            .read()
+            .get(call_id)
+            .is_some_and(|broadcast| broadcast.links(client_id, peer_id))
+    }
+
     pub fn ringing_call(&self, call_id: &CallId) -> Option<RingingCall> {
         self.ringing_calls.read().get(call_id).map(Into::into)
     }
@@ -76,6 +103,13 @@ impl CallManager {
         self.active_calls.read().get(call_id).map(Into::into)
     }
 
+    pub fn active_calls(&self) -> Vec<ActiveCall> {
+        let mut calls: Vec<ActiveCall> =
+            self.active_calls.read().values().map(Into::into).collect();
+        calls.sort_by_key(|call| call.call_id);
+        calls
+    }
+
     pub fn start_call_attempt(
         &self,
         call_id: &CallId,
@@ -111,6 +145,83 @@ impl CallManager {
         Ok(())
     }
 
+    pub fn broadcast(&self, call_id: &CallId) -> Option<ActiveBroadcast> {
+        self.broadcasts.read().get(call_id).cloned()
+    }
+
+    /// Starts a broadcast from `broadcaster_id` to all `recipients`. Broadcasts don't ring, so
+    /// recipients are part of the broadcast right away. A client can only run one broadcast at
+    /// a time.
+    pub fn start_broadcast(
+        &self,
+        call_id: &CallId,
+        broadcaster_id: &ClientId,
+        recipients: &HashSet<ClientId>,
+    ) -> Result<(), StartCallError> {
+        let mut broadcasts = self.broadcasts.write();
+        if broadcasts
+            .values()
+            .any(|broadcast| broadcast.broadcaster_id == *broadcaster_id)
+        {
+            tracing::warn!("Client already has an active broadcast");
+            return Err(StartCallError::CallerBusy);
+        }
+
+        match broadcasts.entry(*call_id) {
+            Entry::Occupied(_) => {
+                tracing::warn!("Broadcast call ID already in use");
+                Err(StartCallError::CallerBusy)
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(ActiveBroadcast {
+                    call_id: *call_id,
+                    broadcaster_id: broadcaster_id.clone(),
+                    recipients: recipients.clone(),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Ends the broadcast if `ending_client_id` is its broadcaster, returning it so all
+    /// recipients can be notified.
+    pub fn end_broadcast(
+        &self,
+        call_id: &CallId,
+        ending_client_id: &ClientId,
+    ) -> Option<ActiveBroadcast> {
+        let mut broadcasts = self.broadcasts.write();
+        match broadcasts.entry(*call_id) {
+            Entry::Occupied(entry) if entry.get().broadcaster_id == *ending_client_id => {
+                Some(entry.remove())
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes `leaving_client_id` from the recipients of the broadcast, returning the remaining
+    /// broadcast. The broadcast is ended once its last recipient left.
+    pub fn leave_broadcast(
+        &self,
+        call_id: &CallId,
+        leaving_client_id: &ClientId,
+    ) -> Option<ActiveBroadcast> {
+        let mut broadcasts = self.broadcasts.write();
+        let Entry::Occupied(mut entry) = broadcasts.entry(*call_id) else {
+            return None;
+        };
+
+        if !entry.get_mut().recipients.remove(leaving_client_id) {
+            return None;
+        }
+
+        if entry.get().recipients.is_empty() {
+            Some(entry.remove())
+        } else {
+            Some(entry.get().clone())
+        }
+    }
+
     pub fn reject_call(
         &self,
         call_id: &CallId,
@@ -189,7 +300,9 @@ impl CallManager {
             *call_id,
             ringing.caller_id.clone(),
             accepting_client_id.clone(),
+            ringing.target.clone(),
         );
+        let record = active.started_record();
 
         self.active_calls.write().insert(*call_id, active);
         {
@@ -198,6 +311,8 @@ impl CallManager {
             client_active_calls.insert(accepting_client_id.clone(), *call_id);
         }
 
+        self.hook.call_started(&record);
+
         Some(ringing.complete(CallAttemptOutcome::Accepted))
     }
 
@@ -242,6 +357,16 @@ impl CallManager {
         Some(ringing.complete(CallAttemptOutcome::Cancelled))
     }
 
+    /// Removes the call if it's still ringing once its answer timeout expired, returning it so the
+    /// caller and all notified clients can be informed.
+    pub fn expire_ringing_call(&self, call_id: &CallId) -> Option<RingingCall> {
+        let ringing = self.ringing_calls.write().remove(call_id)?;
+
+        self.cleanup_ringing_call(&ringing);
+
+        Some(ringing.complete(CallAttemptOutcome::TimedOut))
+    }
+
     pub fn end_active_call(
         &self,
         call_id: &CallId,
@@ -263,6 +388,8 @@ impl CallManager {
             client_active_calls.remove(&active.callee_id);
         }
 
+        self.hook.call_ended(&active.ended_record());
+
         Some(ActiveCall::from(active))
     }
 
@@ -272,6 +399,8 @@ impl CallManager {
 
         let mut cleaned_ringing_calls: Vec<RingingCall> = Vec::new();
         let mut cleaned_active_call: Option<ActiveCall> = None;
+        let mut ended_broadcasts: Vec<ActiveBroadcast> = Vec::new();
+        let mut left_broadcasts: Vec<ActiveBroadcast> = Vec::new();
 
         let outgoing_call_id = { self.client_outgoing_calls.write().remove(client_id) };
         if let Some(outgoing_call_id) = outgoing_call_id {
@@ -341,10 +470,25 @@ impl CallManager {
                     }
                 }
 
+                self.hook.call_ended(&active.ended_record());
                 cleaned_active_call = Some(ActiveCall::from(active));
             }
         }
 
+        self.broadcasts.write().retain(|call_id, broadcast| {
+            if broadcast.broadcaster_id == *client_id {
+                tracing::trace!(?call_id, "Ending broadcast of client");
+                ended_broadcasts.push(broadcast.clone());
+                false
+            } else if broadcast.recipients.remove(client_id) {
+                tracing::trace!(?call_id, "Removing client from broadcast");
+                left_broadcasts.push(broadcast.clone());
+                !broadcast.recipients.is_empty()
+            } else {
+                true
+            }
+        });
+
         for ringing in cleaned_ringing_calls {
             self.client_outgoing_calls
                 .write()
@@ -398,6 +542,40 @@ impl CallManager {
                 // TODO error metrics
             }
         }
+
+        for broadcast in ended_broadcasts {
+            let end = CallEnd::new(broadcast.call_id, client_id.clone());
+            for recipient_id in broadcast.recipients {
+                tracing::trace!(?recipient_id, "Sending call end to broadcast recipient");
+                if let Err(err) = state.send_message(&recipient_id, end.clone()).await {
+                    tracing::warn!(
+                        ?err,
+                        ?recipient_id,
+                        "Failed to send call end to broadcast recipient"
+                    );
+                    // TODO error metrics
+                }
+            }
+        }
+
+        for broadcast in left_broadcasts {
+            let broadcaster_id = &broadcast.broadcaster_id;
+            tracing::trace!(?broadcaster_id, "Sending call end to broadcaster");
+            if let Err(err) = state
+                .send_message(
+                    broadcaster_id,
+                    CallEnd::new(broadcast.call_id, client_id.clone()),
+                )
+                .await
+            {
+                tracing::warn!(
+                    ?err,
+                    ?broadcaster_id,
+                    "Failed to send call end to broadcaster"
+                );
+                // TODO error metrics
+            }
+        }
     }
 
     fn remove_client_incoming_call(&self, call_id: &CallId, client_id: &ClientId) {
@@ -426,3 +604,127 @@ impl CallManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::calls::CallRecord;
+    use parking_lot::Mutex;
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+    use vacs_protocol::vatsim::StationId;
+
+    #[derive(Debug, Default)]
+    struct MockCallHook {
+        started: Mutex<Vec<CallRecord>>,
+        ended: Mutex<Vec<CallRecord>>,
+    }
+
+    impl CallHook for MockCallHook {
+        fn call_started(&self, call: &CallRecord) {
+            self.started.lock().push(call.clone());
+        }
+
+        fn call_ended(&self, call: &CallRecord) {
+            self.ended.lock().push(call.clone());
+        }
+    }
+
+    fn setup_active_call(manager: &CallManager) -> (CallId, ClientId, ClientId) {
+        let call_id = CallId::new();
+        let caller_id = ClientId::from("client1");
+        let callee_id = ClientId::from("client2");
+        manager
+            .start_call_attempt(
+                &call_id,
+                &caller_id,
+                &CallTarget::Station(StationId::from("LOWW_TWR")),
+                &HashSet::from([callee_id.clone()]),
+            )
+            .unwrap();
+        assert!(manager.accept_call(&call_id, &callee_id).is_some());
+        (call_id, caller_id, callee_id)
+    }
+
+    #[test]
+    fn hook_invoked_on_call_start_and_end() {
+        let hook = Arc::new(MockCallHook::default());
+        let manager = CallManager::new().with_hook(hook.clone());
+
+        let (call_id, caller_id, callee_id) = setup_active_call(&manager);
+
+        {
+            let started = hook.started.lock();
+            assert_eq!(started.len(), 1);
+            assert_eq!(started[0].call_id, call_id);
+            assert_eq!(started[0].caller_id, caller_id);
+            assert_eq!(started[0].callee_id, callee_id);
+            assert_eq!(
+                started[0].target,
+                CallTarget::Station(StationId::from("LOWW_TWR"))
+            );
+            assert_eq!(started[0].duration, None);
+        }
+        assert!(hook.ended.lock().is_empty());
+
+        assert!(manager.end_active_call(&call_id, &caller_id).is_some());
+
+        let ended = hook.ended.lock();
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].call_id, call_id);
+        assert_eq!(ended[0].started_at, hook.started.lock()[0].started_at);
+        assert!(ended[0].duration.is_some());
+    }
+
+    #[test]
+    fn hook_not_invoked_for_unanswered_calls() {
+        let hook = Arc::new(MockCallHook::default());
+        let manager = CallManager::new().with_hook(hook.clone());
+
+        let call_id = CallId::new();
+        let caller_id = ClientId::from("client1");
+        manager
+            .start_call_attempt(
+                &call_id,
+                &caller_id,
+                &CallTarget::Client(ClientId::from("client2")),
+                &HashSet::from([ClientId::from("client2")]),
+            )
+            .unwrap();
+        assert!(manager.end_ringing_call(&call_id, &caller_id).is_some());
+
+        assert!(hook.started.lock().is_empty());
+        assert!(hook.ended.lock().is_empty());
+    }
+
+    #[test]
+    fn expire_ringing_call() {
+        let manager = CallManager::new();
+
+        let call_id = CallId::new();
+        let caller_id = ClientId::from("client1");
+        let callee_id = ClientId::from("client2");
+        manager
+            .start_call_attempt(
+                &call_id,
+                &caller_id,
+                &CallTarget::Client(callee_id.clone()),
+                &HashSet::from([callee_id.clone()]),
+            )
+            .unwrap();
+
+        let expired = manager.expire_ringing_call(&call_id).unwrap();
+        assert_eq!(expired.caller_id, caller_id);
+        assert_eq!(expired.notified_clients, HashSet::from([callee_id.clone()]));
+        assert!(manager.ringing_call(&call_id).is_none());
+        assert!(!manager.has_outgoing_call(&caller_id));
+        assert!(manager.accept_call(&call_id, &callee_id).is_none());
+        assert!(manager.expire_ringing_call(&call_id).is_none());
+
+        // Answered calls are no longer ringing and don't expire
+        let (call_id, caller_id, callee_id) = setup_active_call(&manager);
+        assert!(manager.expire_ringing_call(&call_id).is_none());
+        assert!(manager.has_active_call(&call_id, &caller_id));
+        assert!(manager.has_active_call(&call_id, &callee_id));
+    }
+}
@@ -1,7 +1,13 @@
+pub mod debounce;
+pub mod last_controller;
 pub mod manager;
+pub mod queue;
 pub mod session;
 
+pub use debounce::*;
+pub use last_controller::*;
 pub use manager::*;
+pub use queue::*;
 pub use session::*;
 
 use thiserror::Error;
@@ -12,6 +18,22 @@ pub enum ClientManagerError {
     DuplicateClient(String),
     #[error("failed to send message: {0}")]
     MessageSendError(String),
+    #[error("FIR {0} not found")]
+    UnknownFir(String),
+    #[error("station {0} not found")]
+    UnknownStation(String),
+    #[error("position {0} is not online")]
+    PositionOffline(String),
+    #[error("client {0} not found")]
+    UnknownClient(String),
+    #[error("profile {0} is not available")]
+    ProfileNotAvailable(String),
+    #[error("invalid profiles: {0}")]
+    InvalidProfiles(String),
+    #[error("invalid network: {0}")]
+    InvalidNetwork(String),
+    #[error("invalid frequencies: {0}")]
+    InvalidFrequencies(String),
 }
 
 pub type Result<T, E = ClientManagerError> = std::result::Result<T, E>;
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+use vacs_protocol::vatsim::{PositionId, StationChange, StationId};
+
+/// Suppresses stations flapping offline and back online within a short window, e.g. caused by a
+/// controller with a bad connection repeatedly dropping off the network.
+///
+/// Offline changes are held back for the configured window. If the station comes back online at
+/// the same position in the meantime, both changes are dropped, as clients never noticed the
+/// station being offline. If it comes back at a different position, the changes are merged into a
+/// handoff. Offline changes still pending once the window elapsed are returned by
+/// [`StationDebouncer::expire`] and have to be sent by the caller.
+///
+/// The debouncer only affects the changes sent to clients, the coverage state of the server is
+/// always updated immediately.
+#[derive(Debug, Default)]
+pub struct StationDebouncer {
+    window: Duration,
+    /// Position controlling each station, as last sent to clients.
+    controllers: HashMap<StationId, PositionId>,
+    pending: HashMap<StationId, PendingOffline>,
+}
+
+#[derive(Debug)]
+struct PendingOffline {
+    change: StationChange,
+    position_id: Option<PositionId>,
+    since: Instant,
+}
+
+/// Result of debouncing a batch of station changes.
+#[derive(Debug, Default, PartialEq)]
+pub struct DebouncedChanges {
+    /// Changes to send to clients right away.
+    pub changes: Vec<StationChange>,
+    /// Changes dropped as the station came back online at the same position, alongside the time
+    /// its offline change was held back. Clients connecting after that never knew the station
+    /// was online and still have to receive these.
+    pub restored: Vec<(StationChange, Instant)>,
+}
+
+impl StationDebouncer {
+    /// Creates a new debouncer, disabled if `window` is zero.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn debounce(&mut self, changes: Vec<StationChange>, now: Instant) -> DebouncedChanges {
+        if !self.is_enabled() {
+            return DebouncedChanges {
+                changes,
+                restored: Vec::new(),
+            };
+        }
+
+        let mut debounced = DebouncedChanges::default();
+        for change in changes {
+            match change {
+                StationChange::Online {
+                    station_id,
+                    position_id,
+                } => {
+                    self.controllers
+                        .insert(station_id.clone(), position_id.clone());
+                    match self.pending.remove(&station_id) {
+                        Some(pending) if pending.position_id.as_ref() == Some(&position_id) => {
+                            tracing::trace!(
+                                ?station_id,
+                                ?position_id,
+                                "Station came back online within debounce window, suppressing changes"
+                            );
+                            debounced.restored.push((
+                                StationChange::Online {
+                                    station_id,
+                                    position_id,
+                                },
+                                pending.since,
+                            ));
+                        }
+                        Some(PendingOffline {
+                            position_id: Some(from_position_id),
+                            ..
+                        }) => debounced.changes.push(StationChange::Handoff {
+                            station_id,
+                            from_position_id,
+                            to_position_id: position_id,
+                        }),
+                        _ => debounced.changes.push(StationChange::Online {
+                            station_id,
+                            position_id,
+                        }),
+                    }
+                }
+                StationChange::Handoff {
+                    ref station_id,
+                    ref to_position_id,
+                    ..
+                } => {
+                    self.pending.remove(station_id);
+                    self.controllers
+                        .insert(station_id.clone(), to_position_id.clone());
+                    debounced.changes.push(change);
+                }
+                // Stations going offline while still covered (e.g. closed at runtime) are
+                // intentional and sent right away.
+                StationChange::Offline {
+                    ref station_id,
+                    reason: Some(_),
+                } => {
+                    self.pending.remove(station_id);
+                    self.controllers.remove(station_id);
+                    debounced.changes.push(change);
+                }
+                StationChange::Offline {
+                    ref station_id,
+                    reason: None,
+                } => {
+                    if !self.pending.contains_key(station_id) {
+                        let position_id = self.controllers.remove(station_id);
+                        self.pending.insert(
+                            station_id.clone(),
+                            PendingOffline {
+                                change,
+                                position_id,
+                                since: now,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        debounced
+    }
+
+    /// Removes and returns all held back offline changes whose debounce window has elapsed.
+    pub fn expire(&mut self, now: Instant) -> Vec<StationChange> {
+        let window = self.window;
+        let mut expired = self
+            .pending
+            .extract_if(|_, pending| now.saturating_duration_since(pending.since) >= window)
+            .map(|(_, pending)| pending.change)
+            .collect::<Vec<_>>();
+        expired.sort();
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use vacs_protocol::vatsim::StationOfflineReason;
+
+    const WINDOW: Duration = Duration::from_secs(10);
+
+    fn online(station: &str, position: &str) -> StationChange {
+        StationChange::Online {
+            station_id: StationId::from(station),
+            position_id: PositionId::from(position),
+        }
+    }
+
+    fn offline(station: &str) -> StationChange {
+        StationChange::Offline {
+            station_id: StationId::from(station),
+            reason: None,
+        }
+    }
+
+    fn debounced(changes: Vec<StationChange>) -> DebouncedChanges {
+        DebouncedChanges {
+            changes,
+            restored: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn disabled_passes_changes_through() {
+        let mut debouncer = StationDebouncer::new(Duration::ZERO);
+        let now = Instant::now();
+        let changes = vec![online("LOWW_TWR", "LOWW_TWR"), offline("LOWW_TWR")];
+        assert_eq!(debouncer.debounce(changes.clone(), now), debounced(changes));
+        assert_eq!(debouncer.expire(now + WINDOW), vec![]);
+    }
+
+    #[test]
+    fn flap_at_same_position_suppressed() {
+        let mut debouncer = StationDebouncer::new(WINDOW);
+        let start = Instant::now();
+        assert_eq!(
+            debouncer.debounce(vec![online("LOWW_TWR", "LOWW_TWR")], start),
+            debounced(vec![online("LOWW_TWR", "LOWW_TWR")])
+        );
+
+        let offline_at = start + Duration::from_secs(1);
+        assert_eq!(
+            debouncer.debounce(vec![offline("LOWW_TWR")], offline_at),
+            debounced(vec![])
+        );
+        assert_eq!(
+            debouncer.debounce(
+                vec![online("LOWW_TWR", "LOWW_TWR")],
+                offline_at + Duration::from_secs(5)
+            ),
+            DebouncedChanges {
+                changes: vec![],
+                restored: vec![(online("LOWW_TWR", "LOWW_TWR"), offline_at)],
+            }
+        );
+        assert_eq!(debouncer.expire(offline_at + WINDOW), vec![]);
+    }
+
+    #[test]
+    fn flap_at_different_position_becomes_handoff() {
+        let mut debouncer = StationDebouncer::new(WINDOW);
+        let now = Instant::now();
+        debouncer.debounce(vec![online("LOWW_TWR", "LOWW_TWR")], now);
+        debouncer.debounce(vec![offline("LOWW_TWR")], now);
+        assert_eq!(
+            debouncer.debounce(vec![online("LOWW_TWR", "LOWW_APP")], now),
+            debounced(vec![StationChange::Handoff {
+                station_id: StationId::from("LOWW_TWR"),
+                from_position_id: PositionId::from("LOWW_TWR"),
+                to_position_id: PositionId::from("LOWW_APP"),
+            }])
+        );
+    }
+
+    #[test]
+    fn offline_sent_after_window() {
+        let mut debouncer = StationDebouncer::new(WINDOW);
+        let now = Instant::now();
+        debouncer.debounce(vec![online("LOWW_TWR", "LOWW_TWR")], now);
+        debouncer.debounce(vec![offline("LOWW_TWR"), offline("LOWW_GND")], now);
+
+        assert_eq!(debouncer.expire(now + Duration::from_secs(9)), vec![]);
+        assert_eq!(
+            debouncer.expire(now + WINDOW),
+            vec![offline("LOWW_GND"), offline("LOWW_TWR")]
+        );
+        assert_eq!(debouncer.expire(now + WINDOW * 2), vec![]);
+
+        // Coming back online after the window is a regular online change again
+        assert_eq!(
+            debouncer.debounce(vec![online("LOWW_TWR", "LOWW_TWR")], now + WINDOW),
+            debounced(vec![online("LOWW_TWR", "LOWW_TWR")])
+        );
+    }
+
+    #[test]
+    fn offline_with_reason_not_debounced() {
+        let mut debouncer = StationDebouncer::new(WINDOW);
+        let now = Instant::now();
+        let closed = StationChange::Offline {
+            station_id: StationId::from("LOWW_TWR"),
+            reason: Some(StationOfflineReason::TemporarilyClosed),
+        };
+        debouncer.debounce(vec![offline("LOWW_TWR")], now);
+        assert_eq!(
+            debouncer.debounce(vec![closed.clone()], now),
+            debounced(vec![closed])
+        );
+        assert_eq!(debouncer.expire(now + WINDOW), vec![]);
+    }
+}
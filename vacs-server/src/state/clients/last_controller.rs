@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::Instant;
+use vacs_protocol::http::coverage::LastStationController;
+use vacs_protocol::vatsim::{PositionId, StationId};
+
+/// Remembers the position last controlling each station after it went offline or was handed off,
+/// allowing clients to show who had a station last.
+///
+/// Entries are kept for the configured retention and dropped once it elapsed. A zero retention
+/// disables the memory entirely.
+#[derive(Debug, Default)]
+pub struct LastControllers {
+    retention: Duration,
+    entries: HashMap<StationId, LastController>,
+}
+
+#[derive(Debug)]
+struct LastController {
+    position_id: PositionId,
+    until: SystemTime,
+    recorded_at: Instant,
+}
+
+impl LastControllers {
+    /// Creates a new memory, disabled if `retention` is zero.
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.retention.is_zero()
+    }
+
+    /// Records `position_id` as the last controller of `station_id`, replacing any previous entry.
+    /// Also drops all entries past their retention.
+    pub fn record(&mut self, station_id: StationId, position_id: PositionId, now: Instant) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        self.prune(now);
+        self.entries.insert(
+            station_id,
+            LastController {
+                position_id,
+                until: SystemTime::now(),
+                recorded_at: now,
+            },
+        );
+    }
+
+    /// Drops all entries past their retention.
+    pub fn prune(&mut self, now: Instant) {
+        let retention = self.retention;
+        self.entries
+            .retain(|_, entry| now.saturating_duration_since(entry.recorded_at) < retention);
+    }
+
+    /// Returns all entries still within their retention, sorted by station ID.
+    pub fn list(&self, now: Instant) -> Vec<LastStationController> {
+        let mut entries = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| now.saturating_duration_since(entry.recorded_at) < self.retention)
+            .map(|(station_id, entry)| LastStationController {
+                station_id: station_id.clone(),
+                position_id: entry.position_id.clone(),
+                until: entry
+                    .until
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.station_id.cmp(&b.station_id));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const RETENTION: Duration = Duration::from_secs(60);
+
+    fn last_controllers(memory: &LastControllers, now: Instant) -> Vec<(String, String)> {
+        memory
+            .list(now)
+            .into_iter()
+            .map(|entry| (entry.station_id.to_string(), entry.position_id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn disabled_records_nothing() {
+        let mut memory = LastControllers::new(Duration::ZERO);
+        let now = Instant::now();
+        memory.record(
+            StationId::from("LOWW_TWR"),
+            PositionId::from("LOWW_TWR"),
+            now,
+        );
+        assert_eq!(last_controllers(&memory, now), vec![]);
+    }
+
+    #[test]
+    fn latest_controller_kept_until_retention_elapsed() {
+        let mut memory = LastControllers::new(RETENTION);
+        let start = Instant::now();
+        memory.record(
+            StationId::from("LOWW_TWR"),
+            PositionId::from("LOWW_TWR"),
+            start,
+        );
+        memory.record(
+            StationId::from("LOWW_GND"),
+            PositionId::from("LOWW_GND"),
+            start,
+        );
+
+        let later = start + Duration::from_secs(30);
+        memory.record(
+            StationId::from("LOWW_TWR"),
+            PositionId::from("LOWW_APP"),
+            later,
+        );
+        assert_eq!(
+            last_controllers(&memory, later),
+            vec![
+                ("LOWW_GND".to_string(), "LOWW_GND".to_string()),
+                ("LOWW_TWR".to_string(), "LOWW_APP".to_string()),
+            ]
+        );
+
+        let expired = start + RETENTION;
+        assert_eq!(
+            last_controllers(&memory, expired),
+            vec![("LOWW_TWR".to_string(), "LOWW_APP".to_string())]
+        );
+        memory.prune(later + RETENTION);
+        assert!(memory.entries.is_empty());
+    }
+}
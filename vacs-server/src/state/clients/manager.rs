@@ -1,29 +1,93 @@
+use crate::config::{DefaultProfilesConfig, SendQueueOverflowPolicy};
 use crate::metrics::guards::ClientConnectionGuard;
+use crate::state::clients::debounce::StationDebouncer;
+use crate::state::clients::last_controller::LastControllers;
+use crate::state::clients::queue::{ClientReceiver, client_channel};
 use crate::state::clients::session::ClientSession;
 use crate::state::clients::{ClientManagerError, Result};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::broadcast::error::SendError;
-use tokio::sync::{RwLock, broadcast, mpsc};
+use tokio::sync::{RwLock, broadcast};
+use tokio::time::Instant;
 use tracing::instrument;
+use vacs_protocol::http::coverage::{
+    CoverageSnapshot, PositionCoverage, StationCoverage, TimestampedStationChange,
+};
+use vacs_protocol::http::webrtc::IceServer;
 use vacs_protocol::profile::{ActiveProfile, ProfileId};
 use vacs_protocol::vatsim::{ClientId, PositionId, StationChange, StationId};
 use vacs_protocol::ws::server;
 use vacs_protocol::ws::server::{
-    ClientInfo, DisconnectReason, ServerMessage, SessionProfile, StationInfo,
+    ClientInfo, DisconnectReason, ServerMessage, SessionProfile, StationInfo, StationNode,
+};
+use vacs_vatsim::coverage::flight_information_region::FlightInformationRegionId;
+use vacs_vatsim::coverage::network::{
+    LoadOptions, Network, RelevantStations, ResolveOptions, ResolveOutcome,
 };
-use vacs_vatsim::coverage::network::{Network, RelevantStations};
 use vacs_vatsim::coverage::position::Position;
 use vacs_vatsim::coverage::profile::Profile;
-use vacs_vatsim::{ControllerInfo, FacilityType};
+use vacs_vatsim::coverage::relief::ReliefPatterns;
+use vacs_vatsim::coverage::simulate;
+use vacs_vatsim::{ControllerInfo, FacilityType, frequencies_match, normalize_frequency};
+
+/// Maximum number of frequencies a client can monitor in addition to those of its position.
+const MAX_MONITORED_FREQUENCIES: usize = 8;
+
+/// Outcome of matching a controller's VATSIM connection against the positions of the network.
+#[derive(Debug, Clone)]
+pub enum PositionMatch {
+    /// No position matches, the controller is connected without a position.
+    None,
+    /// A single position matches, or the selected one of multiple matching positions.
+    Position(Position),
+    /// Multiple positions match and none was selected, or selections are not allowed.
+    Ambiguous(Vec<PositionId>),
+    /// Multiple positions match, but the selected position is not one of them.
+    InvalidSelection(Vec<PositionId>),
+}
 
 #[derive(Debug)]
 pub struct ClientManager {
     broadcast_tx: broadcast::Sender<ServerMessage>,
     network: parking_lot::RwLock<Network>,
+    network_loaded_at: parking_lot::RwLock<SystemTime>,
+    /// Incremented whenever the network (or only its profiles) is replaced.
+    network_generation: AtomicU64,
     clients: RwLock<HashMap<ClientId, ClientSession>>,
     online_positions: RwLock<HashMap<PositionId, HashSet<ClientId>>>,
     online_stations: RwLock<HashMap<StationId, PositionId>>,
     vatsim_only_positions: RwLock<HashSet<PositionId>>,
+    unknown_facility_tolerance: u32,
+    max_pending_disconnects: usize,
+    frequency_only_matching: bool,
+    ambiguous_position_selection: bool,
+    warn_position_without_coverage: bool,
+    show_vatsim_only_stations: bool,
+    retain_coverage_without_clients: bool,
+    send_queue_overflow_policy: SendQueueOverflowPolicy,
+    max_message_size: usize,
+    default_profiles: DefaultProfilesConfig,
+    closed_stations: Arc<parking_lot::RwLock<HashSet<StationId>>>,
+    /// Stations forced to be controlled by a specific online position, overriding the coverage
+    /// resolution of the network.
+    coverage_overrides: parking_lot::RwLock<HashMap<StationId, PositionId>>,
+    /// Server-wide feed of all station changes, independent of any client profile.
+    station_changes_tx: broadcast::Sender<TimestampedStationChange>,
+    /// Last change applied to each station's coverage, alongside its Unix timestamp (milliseconds).
+    station_changed_at: parking_lot::Mutex<HashMap<StationId, (StationChange, u64)>>,
+    station_debouncer: parking_lot::Mutex<StationDebouncer>,
+    station_change_coalescing: Duration,
+    /// Station changes collected within the coalescing window, sent once it elapsed.
+    coalesced_station_changes: parking_lot::Mutex<Option<CoalescedStationChanges>>,
+    last_controllers: parking_lot::Mutex<LastControllers>,
+    /// Makes the next network replacements fail while computing the new state.
+    #[cfg(test)]
+    fail_network_replacement: std::sync::atomic::AtomicBool,
 }
 
 impl ClientManager {
@@ -31,25 +95,222 @@ impl ClientManager {
         Self {
             broadcast_tx,
             network: parking_lot::RwLock::new(network),
+            network_loaded_at: parking_lot::RwLock::new(SystemTime::now()),
+            network_generation: AtomicU64::new(0),
             clients: RwLock::new(HashMap::new()),
             online_positions: RwLock::new(HashMap::new()),
             online_stations: RwLock::new(HashMap::new()),
             vatsim_only_positions: RwLock::new(HashSet::new()),
+            unknown_facility_tolerance: 0,
+            max_pending_disconnects: usize::MAX,
+            frequency_only_matching: false,
+            ambiguous_position_selection: true,
+            warn_position_without_coverage: false,
+            show_vatsim_only_stations: false,
+            retain_coverage_without_clients: false,
+            send_queue_overflow_policy: SendQueueOverflowPolicy::default(),
+            max_message_size: crate::config::DEFAULT_WEBSOCKET_MAX_MESSAGE_SIZE,
+            default_profiles: DefaultProfilesConfig::default(),
+            closed_stations: Default::default(),
+            coverage_overrides: parking_lot::RwLock::new(HashMap::new()),
+            station_changes_tx: broadcast::channel(crate::config::STATION_CHANGE_CHANNEL_CAPACITY)
+                .0,
+            station_changed_at: parking_lot::Mutex::new(HashMap::new()),
+            station_debouncer: parking_lot::Mutex::new(StationDebouncer::default()),
+            station_change_coalescing: Duration::ZERO,
+            coalesced_station_changes: parking_lot::Mutex::new(None),
+            last_controllers: parking_lot::Mutex::new(LastControllers::default()),
+            #[cfg(test)]
+            fail_network_replacement: Default::default(),
         }
     }
 
+    /// Sets the number of consecutive VATSIM polls a client's facility type may be reported as
+    /// unknown before it is treated like a missing VATSIM connection. While tolerated, the client
+    /// keeps its last known good client info and position.
+    pub fn with_unknown_facility_tolerance(mut self, polls: u32) -> Self {
+        self.unknown_facility_tolerance = polls;
+        self
+    }
+
+    /// Sets the maximum number of clients tracked as pending disconnect by
+    /// [`Self::sync_vatsim_state`]. Excess entries are dropped, restarting the grace period of the
+    /// affected clients.
+    pub fn with_max_pending_disconnects(mut self, max: usize) -> Self {
+        self.max_pending_disconnects = max;
+        self
+    }
+
+    /// Enables matching controllers whose callsign doesn't match any position by their frequency
+    /// alone, as long as exactly one position uses that frequency.
+    pub fn with_frequency_only_matching(mut self, enabled: bool) -> Self {
+        self.frequency_only_matching = enabled;
+        self
+    }
+
+    /// Sets whether controllers matching multiple positions may select one of them. If disabled,
+    /// ambiguous matches are always rejected, regardless of any selection.
+    pub fn with_ambiguous_position_selection(mut self, enabled: bool) -> Self {
+        self.ambiguous_position_selection = enabled;
+        self
+    }
+
+    /// Enables warning clients on connect if their position controls no stations in the network.
+    pub fn with_position_without_coverage_warning(mut self, enabled: bool) -> Self {
+        self.warn_position_without_coverage = enabled;
+        self
+    }
+
+    /// Enables showing stations covered solely by VATSIM-only positions to clients as uncallable,
+    /// instead of omitting them.
+    pub fn with_show_vatsim_only_stations(mut self, enabled: bool) -> Self {
+        self.show_vatsim_only_stations = enabled;
+        self
+    }
+
+    /// Keeps VATSIM-only positions and the stations they cover once the last client disconnects,
+    /// leaving their coverage to be maintained by [`Self::sync_vatsim_state`] alone.
+    pub fn with_retain_coverage_without_clients(mut self, enabled: bool) -> Self {
+        self.retain_coverage_without_clients = enabled;
+        self
+    }
+
+    /// Whether coverage is kept up to date while no client is connected.
+    pub fn retains_coverage_without_clients(&self) -> bool {
+        self.retain_coverage_without_clients
+    }
+
+    /// Sets the window in which a station going offline and back online is not sent to clients.
+    /// A zero window disables debouncing. Held back offline changes are sent by
+    /// [`Self::flush_debounced_station_changes`].
+    pub fn with_station_flap_debounce(self, window: Duration) -> Self {
+        *self.station_debouncer.lock() = StationDebouncer::new(window);
+        self
+    }
+
+    /// Sets the window in which station changes are collected and merged before being sent to
+    /// clients. A zero window disables coalescing, sending all changes right away. Collected
+    /// changes are sent by [`Self::flush_coalesced_station_changes`].
+    pub fn with_station_change_coalescing(mut self, window: Duration) -> Self {
+        self.station_change_coalescing = window;
+        self
+    }
+
+    /// Sets how long the position last controlling a station is remembered after it went offline
+    /// or was handed off. A zero retention disables the memory.
+    pub fn with_last_controller_retention(self, retention: Duration) -> Self {
+        *self.last_controllers.lock() = LastControllers::new(retention);
+        self
+    }
+
+    /// Sets the handling of messages sent to clients whose outbound channel is full.
+    pub fn with_send_queue_overflow_policy(mut self, policy: SendQueueOverflowPolicy) -> Self {
+        self.send_queue_overflow_policy = policy;
+        self
+    }
+
+    /// Sets the maximum size of messages sent to clients.
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Sets the callsign infixes stripped from relief and training callsigns before matching them
+    /// against positions.
+    pub fn with_relief_patterns(self, relief_patterns: ReliefPatterns) -> Self {
+        self.network.write().set_relief_patterns(relief_patterns);
+        self
+    }
+
+    /// Shares the set of stations temporarily closed at runtime. Closed stations are omitted from
+    /// station lists and coverage changes bringing them online are not sent to clients.
+    pub fn with_closed_stations(
+        mut self,
+        closed_stations: Arc<parking_lot::RwLock<HashSet<StationId>>>,
+    ) -> Self {
+        self.closed_stations = closed_stations;
+        self
+    }
+
+    /// Sets the profiles assigned to positions that don't define an explicit profile.
+    pub fn with_default_profiles(mut self, default_profiles: DefaultProfilesConfig) -> Self {
+        self.default_profiles = default_profiles;
+        self
+    }
+
+    /// Resolves the profile of `position`, falling back to the configured defaults if the
+    /// position doesn't define an explicit profile.
+    pub fn resolve_profile_id(&self, position: &Position) -> Option<ProfileId> {
+        self.default_profiles
+            .resolve(&self.network.read(), position)
+    }
+
     #[instrument(level = "debug", skip(self))]
     pub fn find_positions(&self, controller_info: &ControllerInfo) -> Vec<Position> {
-        self.network
+        let network = self.network.read();
+        let options = ResolveOptions {
+            frequency_only_matching: self.frequency_only_matching,
+        };
+        match network.resolve_with_options(
+            &controller_info.callsign,
+            &controller_info.frequency,
+            controller_info.facility_type,
+            &options,
+        ) {
+            ResolveOutcome::NoMatch => Vec::new(),
+            ResolveOutcome::Single(position) => vec![position.clone()],
+            ResolveOutcome::Ambiguous(positions) => positions.into_iter().cloned().collect(),
+        }
+    }
+
+    /// Resolves the position of a controller, using `selected` to pick one of multiple matching
+    /// positions if selections are allowed.
+    ///
+    /// Used both when a client logs in, with the position selected by the user, and when its
+    /// VATSIM connection changes, with the client's current position, so ambiguous matches are
+    /// handled the same way in both cases.
+    pub fn match_position(
+        &self,
+        controller_info: &ControllerInfo,
+        selected: Option<&PositionId>,
+    ) -> PositionMatch {
+        let mut positions = self.find_positions(controller_info);
+        if positions.len() <= 1 {
+            return positions
+                .pop()
+                .map(PositionMatch::Position)
+                .unwrap_or(PositionMatch::None);
+        }
+
+        let position_ids = positions.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+        match selected {
+            Some(selected) if self.ambiguous_position_selection => {
+                match positions.into_iter().find(|p| &p.id == selected) {
+                    Some(position) => PositionMatch::Position(position),
+                    None => PositionMatch::InvalidSelection(position_ids),
+                }
+            }
+            _ => PositionMatch::Ambiguous(position_ids),
+        }
+    }
+
+    /// Returns a counter incremented on every replacement of the network or its profiles, allowing
+    /// callers to detect whether the network changed in the meantime.
+    pub fn network_generation(&self) -> u64 {
+        self.network_generation.load(Ordering::Acquire)
+    }
+
+    /// Returns the version identifier and load time of the currently loaded network.
+    pub fn dataset_info(&self) -> server::DatasetInfo {
+        let loaded_at = self
+            .network_loaded_at
             .read()
-            .find_positions(
-                &controller_info.callsign,
-                &controller_info.frequency,
-                controller_info.facility_type,
-            )
-            .into_iter()
-            .cloned()
-            .collect()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        server::DatasetInfo {
+            version: self.network.read().digest().to_string(),
+            loaded_at: loaded_at.as_secs(),
+        }
     }
 
     pub fn get_profile(&self, profile_id: Option<&ProfileId>) -> Option<Profile> {
@@ -60,6 +321,168 @@ impl ClientManager {
         position_id.and_then(|position_id| self.network.read().get_position(position_id).cloned())
     }
 
+    pub fn ice_servers(&self, position_id: Option<&PositionId>) -> Option<Vec<IceServer>> {
+        position_id.and_then(|position_id| {
+            self.network
+                .read()
+                .ice_servers(position_id)
+                .map(<[_]>::to_vec)
+        })
+    }
+
+    /// Returns the profiles a client on `position_id` may switch to: the profile resolved for the
+    /// position, if any, followed by [`ActiveProfile::Custom`].
+    pub fn available_profiles(
+        &self,
+        position_id: Option<&PositionId>,
+    ) -> Vec<ActiveProfile<ProfileId>> {
+        self.available_profiles_in(&self.network.read(), position_id)
+    }
+
+    fn available_profiles_in(
+        &self,
+        network: &Network,
+        position_id: Option<&PositionId>,
+    ) -> Vec<ActiveProfile<ProfileId>> {
+        position_id
+            .and_then(|position_id| network.get_position(position_id))
+            .and_then(|position| self.default_profiles.resolve(network, position))
+            .map(ActiveProfile::Specific)
+            .into_iter()
+            .chain(std::iter::once(ActiveProfile::Custom))
+            .collect()
+    }
+
+    /// Switches the active profile of the client to `profile`, which must be one of the client's
+    /// [`ClientManager::available_profiles`]. Returns the updated session info for the client.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn set_active_profile(
+        &self,
+        client_id: &ClientId,
+        profile: ActiveProfile<ProfileId>,
+    ) -> Result<server::SessionInfo> {
+        let mut clients = self.clients.write().await;
+        let session = clients
+            .get_mut(client_id)
+            .ok_or_else(|| ClientManagerError::UnknownClient(client_id.to_string()))?;
+
+        let network = self.network.read();
+        let available_profiles = self.available_profiles_in(&network, session.position_id());
+        if !available_profiles.contains(&profile) {
+            tracing::debug!(
+                ?available_profiles,
+                "Requested profile not available, rejecting switch"
+            );
+            return Err(ClientManagerError::ProfileNotAvailable(profile.to_string()));
+        }
+
+        tracing::debug!("Switching active profile");
+        let session_profile = session.set_active_profile(profile, &network);
+        let position_id = session.position_id();
+        Ok(server::SessionInfo {
+            client: session.client_info().clone(),
+            profile: session_profile,
+            ice_servers: position_id
+                .and_then(|position_id| network.ice_servers(position_id))
+                .map(<[_]>::to_vec),
+            frequency_mismatch: frequency_mismatch(&network, session.client_info()),
+            position_without_coverage: false,
+            available_profiles,
+        })
+    }
+
+    /// Replaces the frequencies the client monitors in addition to those of its position,
+    /// returning the accepted frequencies with duplicates removed.
+    ///
+    /// Frequencies are validated against the rules of the client's position, so clients without a
+    /// position can't monitor additional frequencies. Invalid frequencies reject the whole update.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn set_monitored_frequencies(
+        &self,
+        client_id: &ClientId,
+        frequencies: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let mut clients = self.clients.write().await;
+        let session = clients
+            .get_mut(client_id)
+            .ok_or_else(|| ClientManagerError::UnknownClient(client_id.to_string()))?;
+
+        let mut deduplicated: Vec<String> = Vec::with_capacity(frequencies.len());
+        for frequency in frequencies {
+            if !deduplicated
+                .iter()
+                .any(|existing| frequencies_match(existing, &frequency))
+            {
+                deduplicated.push(frequency);
+            }
+        }
+
+        if !deduplicated.is_empty() {
+            if deduplicated.len() > MAX_MONITORED_FREQUENCIES {
+                return Err(ClientManagerError::InvalidFrequencies(format!(
+                    "at most {MAX_MONITORED_FREQUENCIES} frequencies can be monitored"
+                )));
+            }
+            let network = self.network.read();
+            let position = session
+                .position_id()
+                .and_then(|position_id| network.get_position(position_id))
+                .ok_or_else(|| {
+                    ClientManagerError::InvalidFrequencies(
+                        "client has no position to monitor frequencies on".to_string(),
+                    )
+                })?;
+            position
+                .validate_monitor_frequencies(&deduplicated)
+                .map_err(|err| ClientManagerError::InvalidFrequencies(err.to_string()))?;
+        }
+
+        tracing::debug!(frequencies = ?deduplicated, "Updating monitored frequencies");
+        session.set_monitored_frequencies(deduplicated.clone());
+        Ok(deduplicated)
+    }
+
+    /// Returns all clients callers on the given frequency can reach, i.e. clients whose position
+    /// uses or monitors it, or who monitor it at runtime. Clients without a position are only
+    /// reachable on their VATSIM frequency.
+    pub async fn clients_reachable_on(&self, frequency: &str) -> HashSet<ClientId> {
+        let clients = self.clients.read().await;
+        let network = self.network.read();
+        clients
+            .values()
+            .filter(|session| {
+                session.monitors(frequency)
+                    || match session
+                        .position_id()
+                        .and_then(|position_id| network.get_position(position_id))
+                    {
+                        Some(position) => position.is_reachable_on(frequency),
+                        None => frequencies_match(&session.client_info().frequency, frequency),
+                    }
+            })
+            .map(|session| session.id().clone())
+            .collect()
+    }
+
+    /// Returns a warning if the client's VATSIM frequency differs from the frequency defined for
+    /// its position. Clients without a position never have a mismatch.
+    pub fn frequency_mismatch(
+        &self,
+        client_info: &ClientInfo,
+    ) -> Option<server::FrequencyMismatch> {
+        frequency_mismatch(&self.network.read(), client_info)
+    }
+
+    /// Returns whether the client should be warned that its position controls no stations, i.e.
+    /// its coverage closure is empty. Always `false` if the warning is disabled or the client has
+    /// no position.
+    pub fn position_without_coverage(&self, position_id: Option<&PositionId>) -> bool {
+        self.warn_position_without_coverage
+            && position_id.is_some_and(|position_id| {
+                self.network.read().coverage_closure(position_id).is_empty()
+            })
+    }
+
     pub async fn clients_for_position(&self, position_id: &PositionId) -> HashSet<ClientId> {
         self.online_positions
             .read()
@@ -76,13 +499,92 @@ impl ClientManager {
         self.clients_for_position(&position_id).await
     }
 
-    #[instrument(level = "debug", skip(self, client_connection_guard), err)]
+    pub async fn controlling_position(&self, station_id: &StationId) -> Option<PositionId> {
+        self.online_stations.read().await.get(station_id).cloned()
+    }
+
+    /// Returns the position controlling `station_id` if it is online and controlled by a vacs
+    /// client, i.e. visible to clients.
+    pub async fn visible_controlling_position(&self, station_id: &StationId) -> Option<PositionId> {
+        let position_id = self.online_stations.read().await.get(station_id).cloned()?;
+        self.online_positions
+            .read()
+            .await
+            .contains_key(&position_id)
+            .then_some(position_id)
+    }
+
+    /// Returns the current coverage of `station_id` as visible to clients. Closed stations and,
+    /// unless enabled, stations covered solely by VATSIM-only positions are reported as offline.
+    pub async fn station_coverage(&self, station_id: &StationId) -> server::StationCoverage {
+        let online_positions = self.online_positions.read().await;
+        let online_stations = self.online_stations.read().await;
+
+        let position_id = online_stations
+            .get(station_id)
+            .filter(|_| !self.closed_stations.read().contains(station_id));
+        let callable =
+            position_id.is_some_and(|position_id| online_positions.contains_key(position_id));
+
+        server::StationCoverage {
+            station_id: station_id.clone(),
+            position_id: position_id
+                .filter(|_| callable || self.show_vatsim_only_stations)
+                .cloned(),
+            callable,
+        }
+    }
+
+    pub fn has_station(&self, station_id: &StationId) -> bool {
+        self.network.read().has_station(station_id)
+    }
+
+    /// Returns the member stations if `station_id` refers to a station group.
+    pub fn station_group_members(&self, station_id: &StationId) -> Option<Vec<StationId>> {
+        self.network
+            .read()
+            .station_group_members(station_id)
+            .map(<[StationId]>::to_vec)
+    }
+
+    pub async fn stations_for_position(&self, position_id: &PositionId) -> Vec<StationId> {
+        let mut stations: Vec<StationId> = self
+            .online_stations
+            .read()
+            .await
+            .iter()
+            .filter(|(_, controlling_pos)| *controlling_pos == position_id)
+            .map(|(station_id, _)| station_id.clone())
+            .collect();
+        stations.sort();
+        stations
+    }
+
     pub async fn add_client(
         &self,
         client_info: ClientInfo,
         active_profile: ActiveProfile<ProfileId>,
         client_connection_guard: ClientConnectionGuard,
-    ) -> Result<(ClientSession, mpsc::Receiver<ServerMessage>)> {
+    ) -> Result<(ClientSession, ClientReceiver)> {
+        self.add_client_with_tags(
+            client_info,
+            active_profile,
+            Vec::new(),
+            client_connection_guard,
+        )
+        .await
+    }
+
+    /// Adds the client like [`Self::add_client`], tagging its session with the given connection
+    /// metadata.
+    #[instrument(level = "debug", skip(self, client_connection_guard), err)]
+    pub async fn add_client_with_tags(
+        &self,
+        client_info: ClientInfo,
+        active_profile: ActiveProfile<ProfileId>,
+        tags: Vec<String>,
+        client_connection_guard: ClientConnectionGuard,
+    ) -> Result<(ClientSession, ClientReceiver)> {
         tracing::trace!("Adding client");
 
         if self.clients.read().await.contains_key(&client_info.id) {
@@ -92,14 +594,17 @@ impl ClientManager {
             ));
         }
 
-        let (tx, rx) = mpsc::channel(crate::config::CLIENT_CHANNEL_CAPACITY);
+        let (tx, rx) = client_channel(crate::config::CLIENT_CHANNEL_CAPACITY);
 
         let client = ClientSession::new(
             client_info.clone(),
             active_profile,
             tx,
             client_connection_guard,
-        );
+        )
+        .with_overflow_policy(self.send_queue_overflow_policy)
+        .with_max_message_size(self.max_message_size)
+        .with_tags(tags);
         self.clients
             .write()
             .await
@@ -157,11 +662,23 @@ impl ClientManager {
                 } else {
                     let all_positions: HashSet<&PositionId> =
                         online_positions.keys().chain(vatsim_only.iter()).collect();
-                    let all_changes = self.network.read().coverage_changes(
-                        None,
-                        Some(position_id),
-                        &all_positions,
-                    );
+                    let all_changes = guard_coverage("add_client", || {
+                        self.network.read().coverage_changes(
+                            None,
+                            Some(position_id),
+                            &all_positions,
+                        )
+                    });
+                    // Keep the previous online stations if the computation failed
+                    let all_changes = match all_changes {
+                        Some(all_changes) => {
+                            let mut all_positions_after = all_positions.clone();
+                            all_positions_after.insert(position_id);
+                            self.reconcile_coverage_overrides(all_changes, &all_positions_after)
+                                .await
+                        }
+                        None => Vec::new(),
+                    };
                     drop(vatsim_only);
 
                     online_positions
@@ -172,7 +689,7 @@ impl ClientManager {
                         "Updating online stations list after position addition"
                     );
                     self.update_online_stations(&all_changes).await;
-                    Self::client_visible_changes(&all_changes, &online_positions)
+                    self.client_visible_changes(&all_changes, &online_positions)
                 }
             }
         } else {
@@ -194,17 +711,30 @@ impl ClientManager {
         Ok((client, rx))
     }
 
-    #[instrument(level = "debug", skip(self))]
     pub async fn remove_client(
         &self,
         client_id: ClientId,
         disconnect_reason: Option<DisconnectReason>,
     ) {
+        let changes = self
+            .remove_client_deferred(client_id, disconnect_reason)
+            .await;
+        self.broadcast_station_changes(&changes).await;
+    }
+
+    /// Removes the client like [`Self::remove_client`], but returns the resulting station changes
+    /// instead of broadcasting them, allowing them to be merged with other changes.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn remove_client_deferred(
+        &self,
+        client_id: ClientId,
+        disconnect_reason: Option<DisconnectReason>,
+    ) -> Vec<StationChange> {
         tracing::trace!("Removing client");
 
         let Some(client) = self.clients.write().await.remove(&client_id) else {
             tracing::debug!("Client not found in client list, skipping removal");
-            return;
+            return Vec::new();
         };
 
         let changes = if let Some(position_id) = client.position_id() {
@@ -222,6 +752,9 @@ impl ClientManager {
                     let mut after_all = before_all.clone();
                     after_all.remove(position_id);
                     let all_changes = self.network.read().coverage_diff(&before_all, &after_all);
+                    let all_changes = self
+                        .reconcile_coverage_overrides(all_changes, &after_all)
+                        .await;
                     drop(vatsim_only);
 
                     online_positions.remove(position_id);
@@ -231,10 +764,7 @@ impl ClientManager {
                         "Updating online stations list after position removal"
                     );
                     self.update_online_stations(&all_changes).await;
-                    changes.extend(Self::client_visible_changes(
-                        &all_changes,
-                        &online_positions,
-                    ));
+                    changes.extend(self.client_visible_changes(&all_changes, &online_positions));
                 } else {
                     tracing::trace!(
                         ?position_id,
@@ -260,23 +790,34 @@ impl ClientManager {
             );
             Vec::new()
         };
+
+        let summary = client.summary();
+        tracing::info!(
+            client_id = ?summary.client_id,
+            duration = ?summary.duration,
+            positions = ?summary.positions,
+            calls = summary.calls,
+            station_changes = summary.station_changes,
+            ?disconnect_reason,
+            "Session summary"
+        );
         client.disconnect(disconnect_reason);
 
         if let Err(err) = self.broadcast(server::ClientDisconnected { client_id }) {
             tracing::warn!(?err, "Failed to broadcast client disconnected message");
         }
 
-        if self.clients.read().await.is_empty() {
+        if self.clients.read().await.is_empty() && !self.retain_coverage_without_clients {
             tracing::debug!(
                 "Last client disconnected, clearing VATSIM-only positions and online stations"
             );
             self.vatsim_only_positions.write().await.clear();
             self.online_stations.write().await.clear();
+            self.coverage_overrides.write().clear();
         }
 
-        self.broadcast_station_changes(&changes).await;
-
         tracing::debug!("Client removed");
+        changes
     }
 
     pub async fn list_clients(&self, self_client_id: Option<&ClientId>) -> Vec<ClientInfo> {
@@ -293,11 +834,84 @@ impl ClientManager {
         clients
     }
 
+    /// Returns the sessions of all connected clients sorted by client ID, optionally only those
+    /// tagged with `tag`.
+    pub async fn list_client_sessions(&self, tag: Option<&str>) -> Vec<ClientSession> {
+        let mut clients: Vec<ClientSession> = self
+            .clients
+            .read()
+            .await
+            .values()
+            .filter(|c| tag.is_none_or(|tag| c.has_tag(tag)))
+            .cloned()
+            .collect();
+
+        clients.sort_by(|a, b| a.id().cmp(b.id()));
+        clients
+    }
+
+    /// Returns the current coverage state, including VATSIM-only positions and the stations they
+    /// control.
+    pub async fn coverage_snapshot(&self) -> CoverageSnapshot {
+        let online_positions = self.online_positions.read().await;
+        let vatsim_only = self.vatsim_only_positions.read().await;
+        let online_stations = self.online_stations.read().await;
+
+        let positions = online_positions
+            .iter()
+            .map(|(position_id, client_ids)| {
+                let mut client_ids: Vec<ClientId> = client_ids.iter().cloned().collect();
+                client_ids.sort();
+                PositionCoverage {
+                    position_id: position_id.clone(),
+                    client_ids,
+                    vatsim_only: false,
+                }
+            })
+            .chain(vatsim_only.iter().map(|position_id| PositionCoverage {
+                position_id: position_id.clone(),
+                client_ids: Vec::new(),
+                vatsim_only: true,
+            }))
+            .collect();
+        let stations = online_stations
+            .iter()
+            .map(|(station_id, position_id)| StationCoverage {
+                station_id: station_id.clone(),
+                position_id: position_id.clone(),
+            })
+            .collect();
+
+        let last_controlled_by = self.last_controllers.lock().list(Instant::now());
+
+        CoverageSnapshot::new(stations, positions).with_last_controlled_by(last_controlled_by)
+    }
+
+    /// Transforms station changes to the changes visible to clients, keeping stations covered
+    /// solely by VATSIM-only positions as uncallable if enabled.
+    fn client_visible_changes(
+        &self,
+        changes: &[StationChange],
+        online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+    ) -> Vec<StationChange> {
+        if self.show_vatsim_only_stations {
+            simulate::client_visible_changes_with_vatsim_only(changes, online_positions)
+        } else {
+            simulate::client_visible_changes(changes, online_positions)
+        }
+    }
+
     pub async fn list_stations(
         &self,
         profile: &ActiveProfile<ProfileId>,
         self_position_id: Option<&PositionId>,
     ) -> Vec<StationInfo> {
+        // Lock the online maps before resolving the relevant stations. Network replacements swap
+        // the network while holding the online positions write lock, so the network and online
+        // maps read here always belong to the same dataset.
+        let online_positions = self.online_positions.read().await;
+        let online_stations = self.online_stations.read().await;
+
         // Resolve relevant station IDs synchronously to avoid holding parking_lot
         // lock across await points
         let relevant_station_ids = {
@@ -308,37 +922,32 @@ impl ClientManager {
                 RelevantStations::None => return Vec::new(),
             }
         };
-        let online_stations = self.online_stations.read().await;
-        let online_positions = self.online_positions.read().await;
+        let closed_stations = self.closed_stations.read().clone();
+
+        // Stations controlled by positions without any vacs client are covered by VATSIM-only
+        // positions and are only listed (as uncallable) if enabled.
+        let station_info = |id: &StationId, controller: &PositionId| {
+            let callable = online_positions.contains_key(controller);
+            (callable || self.show_vatsim_only_stations).then(|| StationInfo {
+                id: id.clone(),
+                own: self_position_id.is_some_and(|self_pos| controller == self_pos),
+                callable,
+            })
+        };
 
         let mut stations: Vec<StationInfo> = match relevant_station_ids {
             None => online_stations
                 .iter()
-                .filter(|(_, position_id)| online_positions.contains_key(*position_id))
-                .map(|(id, controller)| {
-                    let own = self_position_id
-                        .map(|self_pos| controller == self_pos)
-                        .unwrap_or(false);
-                    StationInfo {
-                        id: id.clone(),
-                        own,
-                    }
-                })
+                .filter(|(id, _)| !closed_stations.contains(*id))
+                .filter_map(|(id, controller)| station_info(id, controller))
                 .collect(),
             Some(ids) => ids
                 .iter()
+                .filter(|id| !closed_stations.contains(*id))
                 .filter_map(|id| {
-                    online_stations.get(id).and_then(|controller| {
-                        online_positions.contains_key(controller).then(|| {
-                            let own = self_position_id
-                                .map(|self_pos| controller == self_pos)
-                                .unwrap_or(false);
-                            StationInfo {
-                                id: id.clone(),
-                                own,
-                            }
-                        })
-                    })
+                    online_stations
+                        .get(id)
+                        .and_then(|controller| station_info(id, controller))
                 })
                 .collect(),
         };
@@ -347,6 +956,76 @@ impl ClientManager {
         stations
     }
 
+    /// Returns the structure of all stations relevant to `profile`, sorted by station ID. Stations
+    /// of disabled FIRs are omitted.
+    pub fn station_hierarchy(&self, profile: &ActiveProfile<ProfileId>) -> Vec<StationNode> {
+        let network = self.network.read();
+        let relevant_stations = network.relevant_stations(profile);
+
+        let mut stations: Vec<StationNode> = network
+            .stations()
+            .filter(|station| network.is_fir_enabled(&station.fir_id))
+            .filter(|station| match &relevant_stations {
+                RelevantStations::All => true,
+                RelevantStations::Subset(ids) => ids.contains(&station.id),
+                RelevantStations::None => false,
+            })
+            .map(|station| StationNode {
+                id: station.id.clone(),
+                parent_id: station.parent_id.clone(),
+                controlled_by: station.controlled_by.clone(),
+            })
+            .collect();
+
+        stations.sort_by(|a, b| a.id.cmp(&b.id));
+        stations
+    }
+
+    /// Returns the online stations relevant to `profile` that are controlled by the position of
+    /// any of the given clients.
+    pub async fn relevant_stations_of_clients(
+        &self,
+        profile: &ActiveProfile<ProfileId>,
+        client_ids: &[&ClientId],
+    ) -> Vec<StationId> {
+        let relevant_station_ids = {
+            let network = self.network.read();
+            match network.relevant_stations(profile) {
+                RelevantStations::All => None,
+                RelevantStations::Subset(ids) => Some(ids.clone()),
+                RelevantStations::None => return Vec::new(),
+            }
+        };
+
+        let position_ids: HashSet<PositionId> = {
+            let clients = self.clients.read().await;
+            client_ids
+                .iter()
+                .filter_map(|id| clients.get(*id)?.position_id().cloned())
+                .collect()
+        };
+        if position_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let mut stations: Vec<StationId> = self
+            .online_stations
+            .read()
+            .await
+            .iter()
+            .filter(|(station_id, position_id)| {
+                position_ids.contains(*position_id)
+                    && relevant_station_ids
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(*station_id))
+            })
+            .map(|(station_id, _)| station_id.clone())
+            .collect();
+
+        stations.sort();
+        stations
+    }
+
     pub async fn get_client(&self, client_id: &ClientId) -> Option<ClientSession> {
         self.clients.read().await.get(client_id).cloned()
     }
@@ -359,6 +1038,27 @@ impl ClientManager {
         self.clients.read().await.is_empty()
     }
 
+    /// Returns the IDs of all clients that have been inactive for at least `timeout`.
+    /// Clients without a position are skipped if `exempt_without_position` is set.
+    pub async fn idle_clients(
+        &self,
+        timeout: std::time::Duration,
+        exempt_without_position: bool,
+    ) -> Vec<ClientId> {
+        let mut idle: Vec<ClientId> = self
+            .clients
+            .read()
+            .await
+            .values()
+            .filter(|c| !(exempt_without_position && c.position_id().is_none()))
+            .filter(|c| c.idle_for() >= timeout)
+            .map(|c| c.id().clone())
+            .collect();
+
+        idle.sort();
+        idle
+    }
+
     #[allow(clippy::result_large_err)]
     pub fn broadcast(
         &self,
@@ -377,168 +1077,663 @@ impl ClientManager {
         }
     }
 
+    /// Replaces the network, removing online positions no longer part of it and recalculating
+    /// profiles and online stations. If computing the new state fails, the previous network and
+    /// all online state are kept.
+    ///
+    /// If only the profiles of `network` differ, the coverage recalculation is skipped entirely and
+    /// only the sessions with changed profiles are updated, like [`Self::reload_profiles`].
     pub async fn replace_network(&self, network: Network) {
-        tracing::info!(?network, "Replacing network coverage data");
-        *self.network.write() = network;
+        let changes = self.replace_network_deferred(network).await;
+        self.broadcast_station_changes(&changes).await;
+    }
 
-        tracing::debug!("Network coverage data replaced, starting housekeeping");
+    /// Like [`Self::replace_network`], but returns the resulting station changes instead of
+    /// broadcasting them, allowing the caller to send them to clients together with other changes
+    /// as a single batch.
+    pub async fn replace_network_deferred(&self, mut network: Network) -> Vec<StationChange> {
+        tracing::info!(?network, "Replacing network coverage data");
+        // Keep FIRs disabled at runtime disabled across reloads
+        {
+            let current = self.network.read();
+            for fir_id in current.disabled_firs() {
+                network.set_fir_enabled(fir_id, false);
+            }
+            // Relief patterns are configured on the server, not part of the dataset
+            network.set_relief_patterns(current.relief_patterns().clone());
+        }
+        self.install_network(network, None).await
+    }
 
-        let old_online_stations = self.online_stations.read().await.clone();
+    /// Replaces only the FIR `fir_id` with its counterpart in `network`, e.g. a freshly loaded copy
+    /// of the dataset, leaving all other FIRs untouched. The FIR is added if it's new and removed
+    /// if `network` doesn't contain it.
+    ///
+    /// Coverage is only recalculated for stations depending on the FIR, all other stations keep
+    /// their controller and no changes are broadcast for them. Clients on positions of the FIR are
+    /// handled like on a full [`Self::replace_network`]. Fails without applying any changes if the
+    /// FIR is unknown to both networks or the other FIRs reference anything it no longer contains.
+    #[instrument(level = "info", skip(self, network))]
+    pub async fn replace_fir(
+        &self,
+        fir_id: &FlightInformationRegionId,
+        network: Network,
+    ) -> Result<()> {
+        let (network, affected_stations) = {
+            let current = self.network.read();
+            if !current.has_fir(fir_id) && !network.has_fir(fir_id) {
+                return Err(ClientManagerError::UnknownFir(fir_id.to_string()));
+            }
+
+            // `network` was validated when it was loaded, only the references between the FIRs
+            // have to be checked again.
+            let replaced = current
+                .replace_fir(fir_id, &network, &LoadOptions::default())
+                .map_err(|errors| {
+                    tracing::warn!(?errors, "Refusing to replace FIR");
+                    ClientManagerError::InvalidNetwork(
+                        errors
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    )
+                })?;
+
+            let mut affected_stations = current.fir_coverage(fir_id);
+            affected_stations.extend(replaced.fir_coverage(fir_id));
+            // Stations forced to a position of the FIR might lose their override
+            affected_stations.extend(
+                self.coverage_overrides
+                    .read()
+                    .iter()
+                    .filter(|(_, position_id)| {
+                        current
+                            .get_position(position_id)
+                            .is_some_and(|position| &position.fir_id == fir_id)
+                    })
+                    .map(|(station_id, _)| station_id.clone()),
+            );
+            (replaced, affected_stations)
+        };
+
+        tracing::info!(
+            ?network,
+            affected_stations = affected_stations.len(),
+            "Replacing FIR coverage data"
+        );
+        let changes = self
+            .install_network(network, Some(&affected_stations))
+            .await;
+        self.broadcast_station_changes(&changes).await;
+        Ok(())
+    }
 
+    /// Installs `network`, removing online positions no longer part of it and recalculating
+    /// profiles and online stations. If `affected_stations` is set, only those stations are
+    /// recalculated and all others keep their controller. Returns the resulting station changes
+    /// for the caller to broadcast.
+    async fn install_network(
+        &self,
+        network: Network,
+        affected_stations: Option<&HashSet<StationId>>,
+    ) -> Vec<StationChange> {
+        let missing_profiles = self.default_profiles.missing_profiles(&network);
+        if !missing_profiles.is_empty() {
+            tracing::warn!(
+                ?missing_profiles,
+                "Configured default profiles not found in new network, ignoring them"
+            );
+        }
+        // Hold the online maps for the whole replacement, so readers locking them first never
+        // observe the new network with stale or partially updated online maps.
         let mut online_positions = self.online_positions.write().await;
+
+        // Profile changes can't affect coverage, so there's nothing to recalculate or broadcast.
+        // Checked while holding the online positions lock, as all network modifications do.
+        if self.network.read().has_same_coverage(&network) {
+            tracing::debug!("Coverage data unchanged, only replacing profiles");
+            match self.swap_profiles(network, &online_positions).await {
+                Ok(session_updates) => {
+                    drop(online_positions);
+                    self.send_profile_session_updates(session_updates).await;
+                }
+                Err(err) => {
+                    tracing::error!(?err, "Failed to replace profiles, keeping previous network");
+                }
+            }
+            return Vec::new();
+        }
+
         let mut clients = self.clients.write().await;
         let mut vatsim_only = self.vatsim_only_positions.write().await;
+        let old_online_stations = self.online_stations.read().await.clone();
 
-        let (session_updates, new_online_stations) = {
-            let network = self.network.read();
-            let mut session_updates: Vec<(ClientSession, server::SessionInfo)> = Vec::new();
+        // Compute the complete new state before mutating anything, so a failure during the
+        // computation leaves the previous network and all online state intact.
+        let replacement = {
+            let old_network = self.network.read();
+            guard_coverage("replace_network", || {
+                self.plan_network_replacement(
+                    &old_network,
+                    &network,
+                    &online_positions,
+                    &clients,
+                    &vatsim_only,
+                    &old_online_stations,
+                    affected_stations,
+                )
+            })
+        };
+        let Some(replacement) = replacement else {
+            tracing::error!("Failed to compute state for new network, keeping previous network");
+            return Vec::new();
+        };
 
-            // Remove positions that no longer exist in the new network
-            let stale_positions: Vec<PositionId> = online_positions
-                .keys()
-                .filter(|pos_id| network.get_position(pos_id).is_none())
-                .cloned()
-                .collect();
+        *self.network.write() = network;
+        *self.network_loaded_at.write() = SystemTime::now();
+        self.network_generation.fetch_add(1, Ordering::AcqRel);
+        *online_positions = replacement.online_positions;
+        *clients = replacement.clients;
+        *vatsim_only = replacement.vatsim_only;
+        self.update_online_stations(&replacement.online_station_changes)
+            .await;
 
-            for stale_pos_id in &stale_positions {
-                tracing::debug!(
-                    ?stale_pos_id,
-                    "Position no longer exists in new network, removing"
+        drop(vatsim_only);
+        drop(clients);
+        drop(online_positions);
+
+        for (session, session_info) in replacement.session_updates {
+            if let Err(err) = session.send_message(session_info).await {
+                tracing::warn!(
+                    ?err,
+                    client_id = ?session.id(),
+                    "Failed to send updated session info after network reload"
                 );
-                if let Some(client_ids) = online_positions.remove(stale_pos_id) {
-                    for client_id in client_ids {
-                        if let Some(session) = clients.get_mut(&client_id) {
-                            tracing::debug!(
-                                ?client_id,
-                                ?stale_pos_id,
-                                "Clearing stale position from client"
-                            );
-                            session.set_position_id(None);
-                            let session_profile = session.update_active_profile(None, &network);
-                            session_updates.push((
-                                session.clone(),
-                                server::SessionInfo {
-                                    client: session.client_info().clone(),
-                                    profile: session_profile,
-                                },
-                            ));
-                        }
-                    }
-                }
             }
-
-            // Remove VATSIM-only positions that no longer exist in the new network
-            let stale_vatsim_only: Vec<PositionId> = vatsim_only
-                .iter()
-                .filter(|pos_id| network.get_position(pos_id).is_none())
-                .cloned()
-                .collect();
-
-            for stale_pos_id in &stale_vatsim_only {
-                tracing::debug!(
-                    ?stale_pos_id,
-                    "VATSIM-only position no longer exists in new network, removing"
+        }
+        for (session, reassigned) in replacement.reassignments {
+            if let Err(err) = session.send_message(reassigned).await {
+                tracing::warn!(
+                    ?err,
+                    client_id = ?session.id(),
+                    "Failed to send position reassignment after network reload"
                 );
-                vatsim_only.remove(stale_pos_id);
             }
+        }
 
-            // Re-transmit profiles for all clients on surviving positions.
-            // Profile *content* may change during a dataset reload even when
-            // the profile ID stays the same, and we cannot cheaply detect
-            // content changes, so we always send the resolved profile.
-            for (pos_id, client_ids) in online_positions.iter() {
-                let new_profile_id = network
-                    .get_position(pos_id)
-                    .and_then(|p| p.profile_id.clone());
+        tracing::info!("Network housekeeping completed");
+        replacement.station_changes
+    }
 
-                for client_id in client_ids {
-                    if let Some(session) = clients.get_mut(client_id) {
-                        let tracked =
-                            session.update_active_profile(new_profile_id.clone(), &network);
-
-                        let session_profile = match tracked {
-                            // Profile ID changed or was cleared, send change.
-                            SessionProfile::Changed(_) => tracked,
-
-                            // Profile ID unchanged, but content may have changed under the same ID
-                            // during the reload. Re-resolve Specific profiles; skip Custom/None.
-                            SessionProfile::Unchanged => match session.active_profile() {
-                                ActiveProfile::Specific(profile_id) => {
-                                    match network.get_profile(profile_id) {
-                                        Some(profile) => SessionProfile::Changed(
-                                            ActiveProfile::Specific(profile.into()),
-                                        ),
-                                        None => {
-                                            tracing::warn!(
-                                                ?profile_id,
-                                                "Profile not found in new network"
-                                            );
-                                            SessionProfile::Changed(ActiveProfile::None)
-                                        }
-                                    }
-                                }
-                                _ => continue,
-                            },
-                        };
+    /// Computes the state resulting from replacing `old_network` with `network`, without
+    /// modifying the manager. Positions and VATSIM-only positions no longer existing are removed,
+    /// profiles of affected clients are re-resolved and the online stations are recalculated.
+    fn plan_network_replacement(
+        &self,
+        old_network: &Network,
+        network: &Network,
+        online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+        clients: &HashMap<ClientId, ClientSession>,
+        vatsim_only: &HashSet<PositionId>,
+        old_online_stations: &HashMap<StationId, PositionId>,
+        affected_stations: Option<&HashSet<StationId>>,
+    ) -> NetworkReplacement {
+        let mut online_positions = online_positions.clone();
+        let mut clients = clients.clone();
+        let mut vatsim_only = vatsim_only.clone();
+        let mut session_updates: Vec<(ClientSession, server::SessionInfo)> = Vec::new();
+        let mut reassignments: Vec<(ClientSession, server::PositionReassigned)> = Vec::new();
+
+        // Remove positions that no longer exist in the new network
+        let stale_positions: Vec<PositionId> = online_positions
+            .keys()
+            .filter(|pos_id| network.get_position(pos_id).is_none())
+            .cloned()
+            .collect();
 
+        for stale_pos_id in &stale_positions {
+            tracing::debug!(
+                ?stale_pos_id,
+                "Position no longer exists in new network, removing"
+            );
+            if let Some(client_ids) = online_positions.remove(stale_pos_id) {
+                for client_id in client_ids {
+                    if let Some(session) = clients.get_mut(&client_id) {
                         tracing::debug!(
                             ?client_id,
-                            ?pos_id,
-                            "Re-transmitting profile to client after network reload"
+                            ?stale_pos_id,
+                            "Clearing stale position from client"
                         );
+                        session.set_position_id(None);
+                        let session_profile = session.update_active_profile(None, network);
                         session_updates.push((
                             session.clone(),
                             server::SessionInfo {
                                 client: session.client_info().clone(),
                                 profile: session_profile,
+                                ice_servers: None,
+                                frequency_mismatch: None,
+                                position_without_coverage: false,
+                                available_profiles: self.available_profiles_in(network, None),
+                            },
+                        ));
+                        reassignments.push((
+                            session.clone(),
+                            server::PositionReassigned {
+                                from: Some(stale_pos_id.clone()),
+                                to: None,
+                                reason: server::PositionReassignmentReason::NetworkReload,
                             },
                         ));
                     }
                 }
             }
+        }
 
-            // Recalculate the full online stations map from scratch, including
-            // VATSIM-only positions for correct coverage computation
-            let all_online_pos_ids: HashSet<&PositionId> =
-                online_positions.keys().chain(vatsim_only.iter()).collect();
+        // Remove VATSIM-only positions that no longer exist in the new network
+        vatsim_only.retain(|pos_id| {
+            let exists = network.get_position(pos_id).is_some();
+            if !exists {
+                tracing::debug!(
+                    ?pos_id,
+                    "VATSIM-only position no longer exists in new network, removing"
+                );
+            }
+            exists
+        });
+
+        // Re-transmit profiles for all clients on surviving positions.
+        session_updates.extend(self.profile_session_updates(
+            old_network,
+            network,
+            &online_positions,
+            &mut clients,
+        ));
+
+        #[cfg(test)]
+        if self
+            .fail_network_replacement
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            panic!("injected network replacement failure");
+        }
 
-            let mut new_online_stations: HashMap<StationId, PositionId> = HashMap::new();
-            let covered = network.covered_stations(None, &all_online_pos_ids);
-            for covered_station in covered {
-                if let Some(controlling_pos) =
-                    network.controlling_position(&covered_station.station.id, &all_online_pos_ids)
-                {
-                    new_online_stations.insert(
-                        covered_station.station.id.clone(),
-                        controlling_pos.id.clone(),
+        // Recalculate the online stations, including VATSIM-only positions for correct coverage
+        // computation. Either from scratch or only for the affected stations, keeping the
+        // controllers of all others.
+        let all_online_pos_ids: HashSet<&PositionId> =
+            online_positions.keys().chain(vatsim_only.iter()).collect();
+        let mut new_online_stations = match affected_stations {
+            None => simulate::online_stations(network, &all_online_pos_ids),
+            Some(affected_stations) => {
+                let mut online_stations = old_online_stations.clone();
+                online_stations.retain(|station_id, _| !affected_stations.contains(station_id));
+                online_stations.extend(affected_stations.iter().filter_map(|station_id| {
+                    network
+                        .controlling_position(station_id, &all_online_pos_ids)
+                        .map(|position| (station_id.clone(), position.id.clone()))
+                }));
+                online_stations
+            }
+        };
+        self.apply_coverage_overrides(network, &mut new_online_stations, &all_online_pos_ids);
+
+        let online_station_changes =
+            simulate::station_diff(old_online_stations, &new_online_stations);
+        let station_changes =
+            self.client_visible_changes(&online_station_changes, &online_positions);
+
+        NetworkReplacement {
+            online_positions,
+            clients,
+            vatsim_only,
+            online_station_changes,
+            station_changes,
+            session_updates,
+            reassignments,
+        }
+    }
+
+    /// Replaces only the profiles of the current network with the ones of `network`, e.g. a freshly
+    /// loaded copy of the dataset, and re-resolves the active profiles of all clients.
+    ///
+    /// Unlike [`Self::replace_network`], online positions and stations are left untouched and no
+    /// station changes are broadcast. Fails without applying any changes if `network` differs in
+    /// anything besides its profiles.
+    #[instrument(level = "info", skip_all)]
+    pub async fn reload_profiles(&self, network: Network) -> Result<()> {
+        let missing_profiles = self.default_profiles.missing_profiles(&network);
+        if !missing_profiles.is_empty() {
+            tracing::warn!(
+                ?missing_profiles,
+                "Configured default profiles not found in new profiles, ignoring them"
+            );
+        }
+
+        // Hold the online positions lock like `replace_network`, so both can't interleave
+        let online_positions = self.online_positions.read().await;
+        let session_updates = self.swap_profiles(network, &online_positions).await?;
+        drop(online_positions);
+
+        self.send_profile_session_updates(session_updates).await;
+        Ok(())
+    }
+
+    /// Replaces the profiles of the current network with the ones of `network` and re-resolves the
+    /// active profiles of all clients, returning the session updates to send. Callers have to hold
+    /// the online positions lock, serializing this with all other network modifications.
+    async fn swap_profiles(
+        &self,
+        network: Network,
+        online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+    ) -> Result<Vec<(ClientSession, server::SessionInfo)>> {
+        let old_network = {
+            let mut current = self.network.write();
+            let old_network = current.clone();
+            if let Err(err) = current.replace_profiles(network) {
+                tracing::warn!(?err, "Refusing to reload profiles");
+                return Err(ClientManagerError::InvalidProfiles(err.to_string()));
+            }
+            old_network
+        };
+        *self.network_loaded_at.write() = SystemTime::now();
+        self.network_generation.fetch_add(1, Ordering::AcqRel);
+
+        let mut clients = self.clients.write().await;
+        let network = self.network.read();
+        Ok(self.profile_session_updates(&old_network, &network, online_positions, &mut clients))
+    }
+
+    async fn send_profile_session_updates(
+        &self,
+        session_updates: Vec<(ClientSession, server::SessionInfo)>,
+    ) {
+        tracing::info!(updates = session_updates.len(), "Profiles reloaded");
+        for (session, session_info) in session_updates {
+            if let Err(err) = session.send_message(session_info).await {
+                tracing::warn!(
+                    ?err,
+                    client_id = ?session.id(),
+                    "Failed to send updated session info after profile reload"
+                );
+            }
+        }
+    }
+
+    /// Re-resolves the active profiles of all clients on online positions after the network was
+    /// changed from `old_network` to `network`, returning the session updates to send.
+    ///
+    /// Profile *content* may change during a dataset reload even when the profile ID stays the
+    /// same, so we compare the content hashes of the old and new profile and only send genuine
+    /// changes.
+    fn profile_session_updates(
+        &self,
+        old_network: &Network,
+        network: &Network,
+        online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+        clients: &mut HashMap<ClientId, ClientSession>,
+    ) -> Vec<(ClientSession, server::SessionInfo)> {
+        let mut session_updates = Vec::new();
+        for (pos_id, client_ids) in online_positions.iter() {
+            let new_profile_id = network
+                .get_position(pos_id)
+                .and_then(|p| self.default_profiles.resolve(network, p));
+
+            for client_id in client_ids {
+                if let Some(session) = clients.get_mut(client_id) {
+                    let tracked = session.update_active_profile(new_profile_id.clone(), network);
+
+                    let session_profile = match tracked {
+                        // Profile ID changed or was cleared, send change.
+                        SessionProfile::Changed(_) => tracked,
+
+                        // Profile ID unchanged, but content may have changed under the same ID
+                        // during the reload. Re-resolve Specific profiles; skip Custom/None.
+                        SessionProfile::Unchanged => match session.active_profile() {
+                            ActiveProfile::Specific(profile_id) => {
+                                match network.get_profile(profile_id) {
+                                    Some(profile)
+                                        if old_network.get_profile(profile_id).is_some_and(
+                                            |old| old.content_hash == profile.content_hash,
+                                        ) && old_network.ice_servers(pos_id)
+                                            == network.ice_servers(pos_id)
+                                            && frequency_mismatch(
+                                                old_network,
+                                                session.client_info(),
+                                            ) == frequency_mismatch(
+                                                network,
+                                                session.client_info(),
+                                            ) =>
+                                    {
+                                        tracing::trace!(
+                                            ?client_id,
+                                            ?profile_id,
+                                            "Profile content unchanged, skipping re-transmit"
+                                        );
+                                        continue;
+                                    }
+                                    Some(profile) => SessionProfile::Changed(
+                                        ActiveProfile::Specific(profile.into()),
+                                    ),
+                                    None => {
+                                        tracing::warn!(
+                                            ?profile_id,
+                                            "Profile not found in new network"
+                                        );
+                                        SessionProfile::Changed(ActiveProfile::None)
+                                    }
+                                }
+                            }
+                            _ => continue,
+                        },
+                    };
+
+                    tracing::debug!(
+                        ?client_id,
+                        ?pos_id,
+                        "Re-transmitting profile to client after network reload"
                     );
+                    session_updates.push((
+                        session.clone(),
+                        server::SessionInfo {
+                            client: session.client_info().clone(),
+                            profile: session_profile,
+                            ice_servers: network.ice_servers(pos_id).map(<[_]>::to_vec),
+                            frequency_mismatch: frequency_mismatch(network, session.client_info()),
+                            position_without_coverage: false,
+                            available_profiles: self.available_profiles_in(network, Some(pos_id)),
+                        },
+                    ));
                 }
             }
+        }
+        session_updates
+    }
+
+    /// Enables or disables the given FIR at runtime. Positions of a disabled FIR are excluded from
+    /// coverage computation, so stations solely covered by them go offline until the FIR is
+    /// enabled again. Returns whether the state of the FIR changed.
+    #[instrument(level = "info", skip(self))]
+    pub async fn set_fir_enabled(
+        &self,
+        fir_id: &FlightInformationRegionId,
+        enabled: bool,
+    ) -> Result<bool> {
+        // Snapshot the online stations only once the online maps are locked, like
+        // `install_network` does, so concurrent updates can't slip in between the snapshot and
+        // the recalculation.
+        let online_positions = self.online_positions.write().await;
+        let vatsim_only = self.vatsim_only_positions.write().await;
+        let old_online_stations = self.online_stations.read().await.clone();
+
+        let new_online_stations = {
+            let mut network = self.network.write();
+            if !network.has_fir(fir_id) {
+                return Err(ClientManagerError::UnknownFir(fir_id.to_string()));
+            }
+            if !network.set_fir_enabled(fir_id, enabled) {
+                tracing::debug!("FIR state unchanged, skipping coverage update");
+                return Ok(false);
+            }
 
-            (session_updates, new_online_stations)
+            let all_online_pos_ids: HashSet<&PositionId> =
+                online_positions.keys().chain(vatsim_only.iter()).collect();
+            let mut new_online_stations = simulate::online_stations(&network, &all_online_pos_ids);
+            self.apply_coverage_overrides(&network, &mut new_online_stations, &all_online_pos_ids);
+            new_online_stations
         };
 
-        let all_changes = Self::compute_station_diff(&old_online_stations, &new_online_stations);
+        let all_changes = simulate::station_diff(&old_online_stations, &new_online_stations);
         self.update_online_stations(&all_changes).await;
-        let station_changes = Self::client_visible_changes(&all_changes, &online_positions);
+        let station_changes = self.client_visible_changes(&all_changes, &online_positions);
 
         drop(vatsim_only);
-        drop(clients);
         drop(online_positions);
 
-        for (session, session_info) in session_updates {
-            if let Err(err) = session.send_message(session_info).await {
+        tracing::info!(changes = station_changes.len(), "FIR state updated");
+        self.broadcast_station_changes(&station_changes).await;
+
+        Ok(true)
+    }
+
+    /// Re-derives the position of a client from the current network and its VATSIM connection
+    /// (`controller`, `None` if it's missing from the data feed), fixing the online positions if
+    /// they got out of sync with the session. Afterwards, the coverage is recalculated and the
+    /// client receives a fresh session info and station list, preceded by a
+    /// [`server::PositionReassigned`] if its position changed. Returns the changes made.
+    ///
+    /// Clients without a VATSIM connection or matching multiple positions keep their current
+    /// position if it still exists, as the regular VATSIM sync takes care of them.
+    #[instrument(level = "info", skip(self, controller))]
+    pub async fn repair_client(
+        &self,
+        client_id: &ClientId,
+        controller: Option<&ControllerInfo>,
+    ) -> Result<ClientRepair> {
+        let old_online_stations = self.online_stations.read().await.clone();
+
+        let mut clients = self.clients.write().await;
+        let mut online_positions = self.online_positions.write().await;
+        let mut vatsim_only = self.vatsim_only_positions.write().await;
+
+        let session = clients
+            .get_mut(client_id)
+            .ok_or_else(|| ClientManagerError::UnknownClient(client_id.to_string()))?;
+        let previous_position_id = session.position_id().cloned();
+
+        if let Some(controller) = controller {
+            session.update_client_info(controller);
+        }
+        let position_id = match controller
+            .map(|controller| self.match_position(controller, previous_position_id.as_ref()))
+        {
+            Some(PositionMatch::Position(position)) => Some(position.id),
+            Some(PositionMatch::None) => None,
+            Some(PositionMatch::Ambiguous(_) | PositionMatch::InvalidSelection(_)) | None => {
+                previous_position_id
+                    .clone()
+                    .filter(|position_id| self.network.read().get_position(position_id).is_some())
+            }
+        };
+
+        let mut removed_from_positions = Vec::new();
+        online_positions.retain(|pos_id, client_ids| {
+            if Some(pos_id) != position_id.as_ref() && client_ids.remove(client_id) {
+                removed_from_positions.push(pos_id.clone());
+            }
+            !client_ids.is_empty()
+        });
+        removed_from_positions.sort();
+        let added_to_position = position_id.as_ref().is_some_and(|position_id| {
+            vatsim_only.remove(position_id);
+            online_positions
+                .entry(position_id.clone())
+                .or_default()
+                .insert(client_id.clone())
+        });
+        session.set_position_id(position_id.clone());
+
+        let (session_info, new_online_stations) = {
+            let network = self.network.read();
+            session.update_active_profile(
+                position_id
+                    .as_ref()
+                    .and_then(|position_id| network.get_position(position_id))
+                    .and_then(|position| self.default_profiles.resolve(&network, position)),
+                &network,
+            );
+            // Always send the active profile, as the client's state can't be trusted either
+            let session_profile =
+                session.set_active_profile(session.active_profile().clone(), &network);
+            let session_info = server::SessionInfo {
+                client: session.client_info().clone(),
+                profile: session_profile,
+                ice_servers: position_id
+                    .as_ref()
+                    .and_then(|position_id| network.ice_servers(position_id))
+                    .map(<[_]>::to_vec),
+                frequency_mismatch: frequency_mismatch(&network, session.client_info()),
+                position_without_coverage: false,
+                available_profiles: self.available_profiles_in(&network, position_id.as_ref()),
+            };
+
+            let all_online_pos_ids: HashSet<&PositionId> =
+                online_positions.keys().chain(vatsim_only.iter()).collect();
+            let mut new_online_stations = simulate::online_stations(&network, &all_online_pos_ids);
+            self.apply_coverage_overrides(&network, &mut new_online_stations, &all_online_pos_ids);
+            (session_info, new_online_stations)
+        };
+        let session = session.clone();
+
+        let all_changes = simulate::station_diff(&old_online_stations, &new_online_stations);
+        self.update_online_stations(&all_changes).await;
+        let station_changes = self.client_visible_changes(&all_changes, &online_positions);
+
+        drop(vatsim_only);
+        drop(online_positions);
+        drop(clients);
+
+        let repair = ClientRepair {
+            previous_position_id,
+            position_id,
+            removed_from_positions,
+            added_to_position,
+            station_changes: all_changes.len(),
+        };
+        tracing::info!(?repair, "Client session repaired");
+
+        if let Err(err) = session.send_message(session_info).await {
+            tracing::warn!(?err, "Failed to send session info to repaired client");
+        }
+        if repair.previous_position_id != repair.position_id {
+            if let Err(err) = session
+                .send_message(server::PositionReassigned {
+                    from: repair.previous_position_id.clone(),
+                    to: repair.position_id.clone(),
+                    reason: server::PositionReassignmentReason::SessionRepaired,
+                })
+                .await
+            {
                 tracing::warn!(
                     ?err,
-                    client_id = ?session.id(),
-                    "Failed to send updated session info after network reload"
+                    "Failed to send position reassignment to repaired client"
                 );
             }
+            if let Err(err) = self.broadcast(session.client_info().clone()) {
+                tracing::warn!(?err, "Failed to broadcast client info update");
+            }
         }
-
         self.broadcast_station_changes(&station_changes).await;
 
-        tracing::info!("Network housekeeping completed");
+        let stations = self
+            .list_stations(session.active_profile(), session.position_id())
+            .await;
+        if let Err(err) = session.send_message(server::StationList { stations }).await {
+            tracing::warn!(?err, "Failed to send station list to repaired client");
+        }
+
+        Ok(repair)
     }
 
     pub async fn sync_vatsim_state(
@@ -547,6 +1742,23 @@ impl ClientManager {
         pending_disconnect: &mut HashSet<ClientId>,
         require_active_connection: bool,
     ) -> Vec<(ClientId, DisconnectReason)> {
+        let (disconnected_clients, changes) = self
+            .sync_vatsim_state_deferred(controllers, pending_disconnect, require_active_connection)
+            .await;
+        self.broadcast_station_changes(&changes).await;
+        self.resync_stale_clients().await;
+        disconnected_clients
+    }
+
+    /// Syncs the VATSIM state like [`Self::sync_vatsim_state`], but returns the resulting station
+    /// changes instead of broadcasting them, allowing them to be merged with the changes caused by
+    /// disconnecting clients.
+    pub async fn sync_vatsim_state_deferred(
+        &self,
+        controllers: &HashMap<ClientId, ControllerInfo>,
+        pending_disconnect: &mut HashSet<ClientId>,
+        require_active_connection: bool,
+    ) -> (Vec<(ClientId, DisconnectReason)>, Vec<StationChange>) {
         let mut updates: Vec<ServerMessage> = Vec::new();
         let mut disconnected_clients: Vec<(ClientId, DisconnectReason)> = Vec::new();
         let mut coverage_changes: Vec<StationChange> = Vec::new();
@@ -556,6 +1768,18 @@ impl ClientManager {
             let mut online_positions = self.online_positions.write().await;
             let mut vatsim_only = self.vatsim_only_positions.write().await;
 
+            // Clients disconnecting on their own while pending would otherwise linger forever
+            pending_disconnect.retain(|cid| {
+                let connected = clients.contains_key(cid);
+                if !connected {
+                    tracing::trace!(
+                        ?cid,
+                        "Client no longer connected, removing pending disconnect"
+                    );
+                }
+                connected
+            });
+
             let start_all_positions: HashSet<PositionId> = online_positions
                 .keys()
                 .chain(vatsim_only.iter())
@@ -566,6 +1790,7 @@ impl ClientManager {
             fn disconnect_or_mark_pending(
                 cid: &ClientId,
                 pending_disconnect: &mut HashSet<ClientId>,
+                max_pending_disconnects: usize,
                 disconnected_clients: &mut Vec<(ClientId, DisconnectReason)>,
             ) {
                 if pending_disconnect.remove(cid) {
@@ -575,6 +1800,12 @@ impl ClientManager {
                     );
                     disconnected_clients
                         .push((cid.clone(), DisconnectReason::NoActiveVatsimConnection));
+                } else if pending_disconnect.len() >= max_pending_disconnects {
+                    tracing::warn!(
+                        ?cid,
+                        max_pending_disconnects,
+                        "Too many clients pending disconnect, deferring marking client"
+                    );
                 } else {
                     tracing::trace!(
                         ?cid,
@@ -589,24 +1820,39 @@ impl ClientManager {
 
                 match controllers.get(cid) {
                     Some(controller) if controller.facility_type == FacilityType::Unknown => {
+                        let polls = session.record_unknown_facility();
+                        if polls <= self.unknown_facility_tolerance {
+                            tracing::debug!(
+                                ?cid,
+                                ?polls,
+                                tolerance = ?self.unknown_facility_tolerance,
+                                "Facility type reported as unknown, keeping last known client info"
+                            );
+                            continue;
+                        }
+
                         if require_active_connection {
                             disconnect_or_mark_pending(
                                 cid,
                                 pending_disconnect,
+                                self.max_pending_disconnects,
                                 &mut disconnected_clients,
                             );
                         }
                     }
                     None => {
+                        session.reset_unknown_facility();
                         if require_active_connection {
                             disconnect_or_mark_pending(
                                 cid,
                                 pending_disconnect,
+                                self.max_pending_disconnects,
                                 &mut disconnected_clients,
                             );
                         }
                     }
                     Some(controller) => {
+                        session.reset_unknown_facility();
                         if pending_disconnect.remove(cid) {
                             tracing::trace!(
                                 ?cid,
@@ -616,6 +1862,7 @@ impl ClientManager {
 
                         let updated = session.update_client_info(controller);
                         if updated {
+                            session.touch();
                             tracing::trace!(
                                 ?cid,
                                 ?session,
@@ -623,39 +1870,28 @@ impl ClientManager {
                             );
 
                             let old_position_id = session.position_id().cloned();
-                            let new_positions: Vec<Position> = self
-                                .network
-                                .read()
-                                .find_positions(
-                                    &controller.callsign,
-                                    &controller.frequency,
-                                    controller.facility_type,
-                                )
-                                .into_iter()
-                                .cloned()
-                                .collect();
-
-                            let new_position = if new_positions.len() > 1 {
-                                tracing::info!(
-                                    ?cid,
-                                    ?old_position_id,
-                                    ?new_positions,
-                                    "Multiple positions found for updated client info, disconnecting as ambiguous"
-                                );
-                                pending_disconnect.remove(cid);
-                                disconnected_clients.push((
-                                    cid.clone(),
-                                    DisconnectReason::AmbiguousVatsimPosition(
-                                        new_positions.into_iter().map(|p| p.id.clone()).collect(),
-                                    ),
-                                ));
-                                continue;
-                            } else if new_positions.len() == 1 {
-                                Some(&new_positions[0])
-                            } else {
-                                None
+                            let new_position = match self
+                                .match_position(controller, old_position_id.as_ref())
+                            {
+                                PositionMatch::None => None,
+                                PositionMatch::Position(position) => Some(position),
+                                PositionMatch::Ambiguous(position_ids)
+                                | PositionMatch::InvalidSelection(position_ids) => {
+                                    tracing::info!(
+                                        ?cid,
+                                        ?old_position_id,
+                                        ?position_ids,
+                                        "Multiple positions found for updated client info, disconnecting as ambiguous"
+                                    );
+                                    pending_disconnect.remove(cid);
+                                    disconnected_clients.push((
+                                        cid.clone(),
+                                        DisconnectReason::AmbiguousVatsimPosition(position_ids),
+                                    ));
+                                    continue;
+                                }
                             };
-                            let new_position_id = new_position.map(|p| p.id.clone());
+                            let new_position_id = new_position.as_ref().map(|p| p.id.clone());
 
                             if old_position_id != new_position_id {
                                 tracing::debug!(
@@ -701,11 +1937,32 @@ impl ClientManager {
                                     }
                                 }
 
-                                let session_profile = {
+                                let (
+                                    session_profile,
+                                    ice_servers,
+                                    frequency_mismatch,
+                                    available_profiles,
+                                ) = {
                                     let network = self.network.read();
-                                    session.update_active_profile(
-                                        new_position.and_then(|p| p.profile_id.clone()),
+                                    let session_profile = session.update_active_profile(
+                                        new_position.as_ref().and_then(|p| {
+                                            self.default_profiles.resolve(&network, p)
+                                        }),
                                         &network,
+                                    );
+                                    let ice_servers = new_position
+                                        .as_ref()
+                                        .and_then(|p| network.ice_servers(&p.id))
+                                        .map(<[_]>::to_vec);
+                                    let frequency_mismatch =
+                                        frequency_mismatch(&network, session.client_info());
+                                    let available_profiles = self
+                                        .available_profiles_in(&network, new_position_id.as_ref());
+                                    (
+                                        session_profile,
+                                        ice_servers,
+                                        frequency_mismatch,
+                                        available_profiles,
                                     )
                                 };
 
@@ -713,6 +1970,10 @@ impl ClientManager {
                                     .send_message(server::SessionInfo {
                                         client: session.client_info().clone(),
                                         profile: session_profile,
+                                        ice_servers,
+                                        frequency_mismatch,
+                                        position_without_coverage: false,
+                                        available_profiles,
                                     })
                                     .await
                                 {
@@ -722,6 +1983,20 @@ impl ClientManager {
                                         "Failed to send updated session info to client"
                                     );
                                 }
+                                if let Err(err) = session
+                                    .send_message(server::PositionReassigned {
+                                        from: old_position_id,
+                                        to: new_position_id,
+                                        reason: server::PositionReassignmentReason::VatsimConnectionChanged,
+                                    })
+                                    .await
+                                {
+                                    tracing::warn!(
+                                        ?err,
+                                        ?session,
+                                        "Failed to send position reassignment to client"
+                                    );
+                                }
                             }
 
                             tracing::trace!(?cid, ?session, "Client info updated, broadcasting");
@@ -740,17 +2015,7 @@ impl ClientManager {
                 {
                     continue;
                 }
-                let positions: Vec<Position> = self
-                    .network
-                    .read()
-                    .find_positions(
-                        &controller.callsign,
-                        &controller.frequency,
-                        controller.facility_type,
-                    )
-                    .into_iter()
-                    .cloned()
-                    .collect();
+                let positions = self.find_positions(controller);
                 if positions.len() == 1 && !online_positions.contains_key(&positions[0].id) {
                     new_vatsim_only.insert(positions[0].id.clone());
                 }
@@ -772,12 +2037,16 @@ impl ClientManager {
                 let end_all: HashSet<&PositionId> =
                     online_positions.keys().chain(vatsim_only.iter()).collect();
 
-                let all_changes = self.network.read().coverage_diff(&start_all, &end_all);
-                self.update_online_stations(&all_changes).await;
-                coverage_changes.extend(Self::client_visible_changes(
-                    &all_changes,
-                    &online_positions,
-                ));
+                if let Some(all_changes) = guard_coverage("sync_vatsim_state", || {
+                    self.network.read().coverage_diff(&start_all, &end_all)
+                }) {
+                    let all_changes = self
+                        .reconcile_coverage_overrides(all_changes, &end_all)
+                        .await;
+                    self.update_online_stations(&all_changes).await;
+                    coverage_changes
+                        .extend(self.client_visible_changes(&all_changes, &online_positions));
+                }
             }
         }
 
@@ -789,97 +2058,175 @@ impl ClientManager {
             }
         }
 
-        self.broadcast_station_changes(&coverage_changes).await;
-
-        disconnected_clients
+        (disconnected_clients, coverage_changes)
     }
 
-    fn compute_station_diff(
-        old: &HashMap<StationId, PositionId>,
-        new: &HashMap<StationId, PositionId>,
-    ) -> Vec<StationChange> {
-        let mut changes = Vec::new();
-
-        // Stations that went offline or changed controller
-        for (station_id, old_pos_id) in old {
-            match new.get(station_id) {
-                None => {
-                    changes.push(StationChange::Offline {
-                        station_id: station_id.clone(),
-                    });
-                }
-                Some(new_pos_id) if new_pos_id != old_pos_id => {
-                    changes.push(StationChange::Handoff {
-                        station_id: station_id.clone(),
-                        from_position_id: old_pos_id.clone(),
-                        to_position_id: new_pos_id.clone(),
-                    });
-                }
-                _ => {}
-            }
+    /// Forces `position_id` to control `station_id` until the override is cleared or the position
+    /// goes offline. The position must be online. Returns whether the override changed.
+    #[instrument(level = "info", skip(self))]
+    pub async fn set_coverage_override(
+        &self,
+        station_id: &StationId,
+        position_id: &PositionId,
+    ) -> Result<bool> {
+        if !self.has_station(station_id) {
+            return Err(ClientManagerError::UnknownStation(station_id.to_string()));
         }
 
-        // Stations that came online
-        for (station_id, new_pos_id) in new {
-            if !old.contains_key(station_id) {
-                changes.push(StationChange::Online {
-                    station_id: station_id.clone(),
-                    position_id: new_pos_id.clone(),
-                });
-            }
+        let online_positions = self.online_positions.write().await;
+        let vatsim_only = self.vatsim_only_positions.read().await;
+        if !online_positions.contains_key(position_id) && !vatsim_only.contains(position_id) {
+            return Err(ClientManagerError::PositionOffline(position_id.to_string()));
         }
 
-        changes.sort();
-        changes
+        let previous = self
+            .coverage_overrides
+            .write()
+            .insert(station_id.clone(), position_id.clone());
+        if previous.as_ref() == Some(position_id) {
+            tracing::debug!("Coverage override unchanged, skipping station changes");
+            return Ok(false);
+        }
+
+        let all_online_pos_ids: HashSet<&PositionId> =
+            online_positions.keys().chain(vatsim_only.iter()).collect();
+        let all_changes = self
+            .reconcile_coverage_overrides(Vec::new(), &all_online_pos_ids)
+            .await;
+        self.update_online_stations(&all_changes).await;
+        let station_changes = self.client_visible_changes(&all_changes, &online_positions);
+
+        drop(vatsim_only);
+        drop(online_positions);
+
+        tracing::info!(changes = station_changes.len(), "Coverage override set");
+        self.broadcast_station_changes(&station_changes).await;
+
+        Ok(true)
     }
 
-    /// Transforms station changes to only include changes visible to vacs clients.
-    /// Stations covered solely by VATSIM-only positions are not callable, so:
-    /// - `Online` for a VATSIM-only position is dropped
-    /// - `Offline` events are always forwarded, even if the previous covering position
-    ///   was VATSIM-only. Clients handle duplicate/unknown `Offline` events gracefully.
-    /// - `Handoff` to a VATSIM-only position becomes `Offline` (station leaves vacs coverage)
-    /// - `Handoff` from a VATSIM-only position becomes `Online` (station enters vacs coverage)
-    fn client_visible_changes(
-        changes: &[StationChange],
-        online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+    /// Clears the coverage override of `station_id`, restoring the normal coverage resolution.
+    /// Returns whether an override was set.
+    #[instrument(level = "info", skip(self))]
+    pub async fn clear_coverage_override(&self, station_id: &StationId) -> Result<bool> {
+        if !self.has_station(station_id) {
+            return Err(ClientManagerError::UnknownStation(station_id.to_string()));
+        }
+
+        let online_positions = self.online_positions.write().await;
+        let vatsim_only = self.vatsim_only_positions.read().await;
+
+        if self.coverage_overrides.write().remove(station_id).is_none() {
+            tracing::debug!("No coverage override set, skipping station changes");
+            return Ok(false);
+        }
+
+        let all_online_pos_ids: HashSet<&PositionId> =
+            online_positions.keys().chain(vatsim_only.iter()).collect();
+        let new_online_stations = {
+            let network = self.network.read();
+            let mut online_stations = simulate::online_stations(&network, &all_online_pos_ids);
+            self.apply_coverage_overrides(&network, &mut online_stations, &all_online_pos_ids);
+            online_stations
+        };
+        let old_online_stations = self.online_stations.read().await.clone();
+        let all_changes = simulate::station_diff(&old_online_stations, &new_online_stations);
+        self.update_online_stations(&all_changes).await;
+        let station_changes = self.client_visible_changes(&all_changes, &online_positions);
+
+        drop(vatsim_only);
+        drop(online_positions);
+
+        tracing::info!(changes = station_changes.len(), "Coverage override cleared");
+        self.broadcast_station_changes(&station_changes).await;
+
+        Ok(true)
+    }
+
+    pub fn coverage_override(&self, station_id: &StationId) -> Option<PositionId> {
+        self.coverage_overrides.read().get(station_id).cloned()
+    }
+
+    /// Drops overrides whose station no longer exists or whose forced position is no longer
+    /// online and applies the remaining ones to `online_stations`.
+    fn apply_coverage_overrides(
+        &self,
+        network: &Network,
+        online_stations: &mut HashMap<StationId, PositionId>,
+        all_online_pos_ids: &HashSet<&PositionId>,
+    ) {
+        let mut overrides = self.coverage_overrides.write();
+        overrides.retain(|station_id, position_id| {
+            let active =
+                network.has_station(station_id) && all_online_pos_ids.contains(position_id);
+            if !active {
+                tracing::info!(
+                    ?station_id,
+                    ?position_id,
+                    "Forced position went offline, clearing coverage override"
+                );
+            }
+            active
+        });
+        online_stations.extend(
+            overrides
+                .iter()
+                .map(|(station_id, position_id)| (station_id.clone(), position_id.clone())),
+        );
+    }
+
+    /// Rewrites the coverage `changes` computed by the network so they account for coverage
+    /// overrides. Changes of overridden stations are replaced by the changes required to move
+    /// them from their tracked controller to the forced position or, if an override was dropped,
+    /// back to their normally resolved controller.
+    async fn reconcile_coverage_overrides(
+        &self,
+        changes: Vec<StationChange>,
+        all_online_pos_ids: &HashSet<&PositionId>,
     ) -> Vec<StationChange> {
-        changes
+        let (affected, new_online_stations) = {
+            let network = self.network.read();
+            let affected = self
+                .coverage_overrides
+                .read()
+                .keys()
+                .cloned()
+                .collect::<HashSet<_>>();
+            if affected.is_empty() {
+                return changes;
+            }
+
+            let mut online_stations = simulate::online_stations(&network, all_online_pos_ids);
+            online_stations.retain(|station_id, _| affected.contains(station_id));
+            self.apply_coverage_overrides(&network, &mut online_stations, all_online_pos_ids);
+            (affected, online_stations)
+        };
+
+        let old_online_stations = self
+            .online_stations
+            .read()
+            .await
             .iter()
-            .filter_map(|change| match change {
-                StationChange::Online { position_id, .. } => {
-                    if online_positions.contains_key(position_id) {
-                        Some(change.clone())
-                    } else {
-                        None
-                    }
-                }
-                StationChange::Handoff {
-                    station_id,
-                    from_position_id,
-                    to_position_id,
-                } => {
-                    let from_vacs = online_positions.contains_key(from_position_id);
-                    let to_vacs = online_positions.contains_key(to_position_id);
-                    match (from_vacs, to_vacs) {
-                        // vacs -> vacs: normal handoff
-                        (true, true) => Some(change.clone()),
-                        // vacs -> VATSIM-only: station leaves vacs coverage
-                        (true, false) => Some(StationChange::Offline {
-                            station_id: station_id.clone(),
-                        }),
-                        // VATSIM-only -> vacs: station enters vacs coverage
-                        (false, true) => Some(StationChange::Online {
-                            station_id: station_id.clone(),
-                            position_id: to_position_id.clone(),
-                        }),
-                        // VATSIM-only -> VATSIM-only: invisible to clients
-                        (false, false) => None,
-                    }
-                }
-                StationChange::Offline { .. } => Some(change.clone()),
+            .filter(|(station_id, _)| affected.contains(*station_id))
+            .map(|(station_id, position_id)| (station_id.clone(), position_id.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut changes = changes
+            .into_iter()
+            .filter(|change| {
+                let station_id = match change {
+                    StationChange::Online { station_id, .. }
+                    | StationChange::Offline { station_id, .. }
+                    | StationChange::Handoff { station_id, .. } => station_id,
+                };
+                !affected.contains(station_id)
             })
-            .collect()
+            .collect::<Vec<_>>();
+        changes.extend(simulate::station_diff(
+            &old_online_stations,
+            &new_online_stations,
+        ));
+        changes
     }
 
     async fn update_online_stations(&self, changes: &[StationChange]) {
@@ -888,7 +2235,12 @@ impl ClientManager {
         }
 
         let mut online_stations = self.online_stations.write().await;
+        let mut last_controllers = self.last_controllers.lock();
+        let mut station_changed_at = self.station_changed_at.lock();
+        let now = Instant::now();
+        let changed_at = unix_millis(SystemTime::now());
         for change in changes {
+            station_changed_at.insert(change.station_id().clone(), (change.clone(), changed_at));
             match change {
                 StationChange::Online {
                     station_id,
@@ -896,74 +2248,249 @@ impl ClientManager {
                 } => {
                     online_stations.insert(station_id.clone(), position_id.clone());
                 }
-                StationChange::Offline { station_id } => {
-                    online_stations.remove(station_id);
+                StationChange::Offline { station_id, .. } => {
+                    if let Some(position_id) = online_stations.remove(station_id) {
+                        last_controllers.record(station_id.clone(), position_id, now);
+                    }
                 }
                 StationChange::Handoff {
                     station_id,
+                    from_position_id,
                     to_position_id,
-                    ..
                 } => {
                     online_stations.insert(station_id.clone(), to_position_id.clone());
+                    last_controllers.record(station_id.clone(), from_position_id.clone(), now);
+                }
+            }
+        }
+    }
+
+    /// Subscribes to the server-wide feed of all station changes, unfiltered by any profile. Each
+    /// change is timestamped with the time it was applied to the coverage state.
+    pub fn subscribe_station_changes(&self) -> broadcast::Receiver<TimestampedStationChange> {
+        self.station_changes_tx.subscribe()
+    }
+
+    /// Sends the given station changes to all clients the stations are relevant to. Changes
+    /// bringing a closed station online are dropped and stations flapping offline are debounced,
+    /// see [`Self::with_station_flap_debounce`]. If coalescing is enabled, the changes are only
+    /// collected and sent merged once the window elapsed, see
+    /// [`Self::with_station_change_coalescing`].
+    pub async fn broadcast_station_changes(&self, changes: &[StationChange]) {
+        if self.station_change_coalescing.is_zero() {
+            self.dispatch_station_changes(changes).await;
+        } else if !changes.is_empty() {
+            self.coalesced_station_changes
+                .lock()
+                .get_or_insert_with(|| CoalescedStationChanges {
+                    changes: Vec::new(),
+                    since: Instant::now(),
+                })
+                .changes
+                .extend_from_slice(changes);
+        }
+    }
+
+    /// Sends the station changes collected within the coalescing window once it elapsed, merged
+    /// into at most one change per station.
+    pub async fn flush_coalesced_station_changes(&self) {
+        let coalesced = {
+            let mut coalesced = self.coalesced_station_changes.lock();
+            if coalesced.as_ref().is_some_and(|coalesced| {
+                coalesced.since.elapsed() >= self.station_change_coalescing
+            }) {
+                coalesced.take()
+            } else {
+                None
+            }
+        };
+
+        if let Some(coalesced) = coalesced {
+            let changes = simulate::coalesce_station_changes(coalesced.changes);
+            tracing::trace!(?changes, "Sending coalesced station changes");
+            self.dispatch_station_changes(&changes).await;
+        }
+    }
+
+    /// Window in which station changes are collected before being sent to clients, zero if
+    /// disabled.
+    pub fn station_change_coalescing(&self) -> Duration {
+        self.station_change_coalescing
+    }
+
+    async fn dispatch_station_changes(&self, changes: &[StationChange]) {
+        let changes = {
+            let closed_stations = self.closed_stations.read();
+            changes
+                .iter()
+                .filter(|change| match change {
+                    StationChange::Online { station_id, .. }
+                    | StationChange::Handoff { station_id, .. } => {
+                        !closed_stations.contains(station_id)
+                    }
+                    StationChange::Offline { .. } => true,
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        let debounced = self
+            .station_debouncer
+            .lock()
+            .debounce(changes, Instant::now());
+        for (change, since) in debounced.restored {
+            self.send_station_changes(&[change], Some(since)).await;
+        }
+        self.send_station_changes(&debounced.changes, None).await;
+    }
+
+    /// Sends all offline changes held back by the station flap debounce whose window elapsed.
+    pub async fn flush_debounced_station_changes(&self) {
+        let expired = self.station_debouncer.lock().expire(Instant::now());
+        self.send_station_changes(&expired, None).await;
+    }
+
+    /// Resends the full station list to all clients that missed station changes, e.g. because
+    /// their send queue overflowed, bringing them back in sync with the coverage state. Returns
+    /// the number of resynced clients.
+    pub async fn resync_stale_clients(&self) -> usize {
+        let stale_clients = self
+            .clients
+            .read()
+            .await
+            .values()
+            .filter(|client| client.take_station_changes_missed())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut resynced = 0usize;
+        for client in stale_clients {
+            let stations = self
+                .list_stations(client.active_profile(), client.position_id())
+                .await;
+            // Dropping the station list marks the client as stale again.
+            match client.send_message(server::StationList { stations }).await {
+                Ok(()) => resynced += 1,
+                Err(err) => {
+                    tracing::warn!(?err, ?client, "Failed to resync station list of client");
+                    client.mark_station_changes_missed();
                 }
             }
         }
+
+        if resynced > 0 {
+            tracing::debug!(
+                resynced,
+                "Resynced station lists of clients that missed changes"
+            );
+        }
+        resynced
+    }
+
+    /// Window in which stations flapping offline and back online are not sent to clients, zero
+    /// if disabled.
+    pub fn station_flap_debounce(&self) -> Duration {
+        self.station_debouncer.lock().window()
     }
 
-    async fn broadcast_station_changes(&self, changes: &[StationChange]) {
+    /// Sends the given station changes to all clients they are relevant to. If `connected_after`
+    /// is set, the changes are only sent to clients connected since then and not published to the
+    /// server-wide station change feed.
+    async fn send_station_changes(
+        &self,
+        changes: &[StationChange],
+        connected_after: Option<Instant>,
+    ) {
         if changes.is_empty() {
             return;
         }
 
+        if connected_after.is_none() && self.station_changes_tx.receiver_count() > 0 {
+            let station_changed_at = self.station_changed_at.lock();
+            let now = unix_millis(SystemTime::now());
+            for change in changes {
+                // Changes not applied to the coverage state as is (e.g. stations closed at runtime
+                // or merged by the flap debounce) are timestamped when sent instead.
+                let at = station_changed_at
+                    .get(change.station_id())
+                    .filter(|(applied, _)| applied == change)
+                    .map_or(now, |(_, at)| *at);
+                // Sending only fails if all subscribers dropped in the meantime.
+                let _ = self.station_changes_tx.send(TimestampedStationChange {
+                    change: change.clone(),
+                    at,
+                });
+            }
+        }
+
         tracing::trace!("Sending station changes to clients");
-        let mut filtered_changes_cache: HashMap<ActiveProfile<ProfileId>, Vec<StationChange>> =
-            HashMap::new();
+        let mut recipients = 0usize;
+        // Clients on the same FIR with the same profile receive the same changes.
+        let mut filtered_changes_cache: HashMap<
+            (ActiveProfile<ProfileId>, Option<FlightInformationRegionId>),
+            Vec<StationChange>,
+        > = HashMap::new();
 
         let clients = self
             .clients
             .read()
             .await
             .values()
+            .filter(|client| connected_after.is_none_or(|since| client.connected_at() >= since))
             .cloned()
             .collect::<Vec<_>>();
 
         for client in clients {
-            let profile = client.active_profile();
-
-            let changes_to_send = if let Some(cached_changes) = filtered_changes_cache.get(profile)
-            {
-                cached_changes.clone()
-            } else {
-                let relevant_station_ids = {
-                    let network = self.network.read();
-                    match network.relevant_stations(profile) {
-                        RelevantStations::All => None,
-                        RelevantStations::Subset(ids) => Some(ids.clone()),
-                        RelevantStations::None => Some(HashSet::new()),
-                    }
-                };
+            let fir_id = client.position_id().and_then(|position_id| {
+                self.network
+                    .read()
+                    .get_position(position_id)
+                    .map(|position| position.fir_id.clone())
+            });
+            let cache_key = (client.active_profile().clone(), fir_id);
+
+            let changes_to_send =
+                if let Some(cached_changes) = filtered_changes_cache.get(&cache_key) {
+                    cached_changes.clone()
+                } else {
+                    let filtered_changes = {
+                        let network = self.network.read();
+                        // Changes of FIRs not relevant to the client's FIR are dropped before
+                        // applying the profile filter. Changes of unknown stations are kept.
+                        let relevant_firs = cache_key
+                            .1
+                            .as_ref()
+                            .and_then(|fir_id| network.relevant_firs(fir_id));
+                        let relevant_stations = network.relevant_stations(&cache_key.0);
+
+                        changes
+                            .iter()
+                            .filter(|change| {
+                                let station_id = match change {
+                                    StationChange::Online { station_id, .. } => station_id,
+                                    StationChange::Offline { station_id, .. } => station_id,
+                                    StationChange::Handoff { station_id, .. } => station_id,
+                                };
+                                let fir_relevant = relevant_firs.as_ref().is_none_or(|firs| {
+                                    network
+                                        .station_fir(station_id)
+                                        .is_none_or(|fir_id| firs.contains(fir_id))
+                                });
+                                fir_relevant
+                                    && match &relevant_stations {
+                                        RelevantStations::All => true,
+                                        RelevantStations::Subset(ids) => ids.contains(station_id),
+                                        RelevantStations::None => false,
+                                    }
+                            })
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    };
 
-                let filtered_changes = match relevant_station_ids {
-                    None => changes.to_vec(),
-                    Some(relevant_ids) if relevant_ids.is_empty() => Vec::new(),
-                    Some(relevant_ids) => changes
-                        .iter()
-                        .filter(|change| {
-                            let station_id = match change {
-                                StationChange::Online { station_id, .. } => station_id,
-                                StationChange::Offline { station_id } => station_id,
-                                StationChange::Handoff { station_id, .. } => station_id,
-                            };
-                            relevant_ids.contains(station_id)
-                        })
-                        .cloned()
-                        .collect(),
+                    filtered_changes_cache.insert(cache_key, filtered_changes.clone());
+                    filtered_changes
                 };
 
-                filtered_changes_cache.insert(profile.clone(), filtered_changes.clone());
-                filtered_changes
-            };
-
             if changes_to_send.is_empty() {
                 continue;
             }
@@ -975,15 +2502,125 @@ impl ClientManager {
                 .await
             {
                 tracing::warn!(?err, ?client, "Failed to send station changes to client");
+                client.mark_station_changes_missed();
+            } else {
+                recipients += 1;
             }
         }
+
+        tracing::debug!(
+            changes = ?changes.iter().map(describe_station_change).collect::<Vec<_>>(),
+            recipients,
+            "Broadcast station changes"
+        );
+    }
+}
+
+/// Station changes collected within the coalescing window, see
+/// [`ClientManager::with_station_change_coalescing`].
+#[derive(Debug)]
+struct CoalescedStationChanges {
+    changes: Vec<StationChange>,
+    since: Instant,
+}
+
+/// State resulting from replacing the network, computed up front so it can be applied at once.
+struct NetworkReplacement {
+    online_positions: HashMap<PositionId, HashSet<ClientId>>,
+    clients: HashMap<ClientId, ClientSession>,
+    vatsim_only: HashSet<PositionId>,
+    /// Changes to apply to the online stations.
+    online_station_changes: Vec<StationChange>,
+    /// Changes visible to clients, excluding stations controlled by VATSIM-only positions.
+    station_changes: Vec<StationChange>,
+    session_updates: Vec<(ClientSession, server::SessionInfo)>,
+    /// Notices for clients whose position was changed, sent after their updated session info.
+    reassignments: Vec<(ClientSession, server::PositionReassigned)>,
+}
+
+/// Changes made by [`ClientManager::repair_client`] to a client session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientRepair {
+    pub previous_position_id: Option<PositionId>,
+    pub position_id: Option<PositionId>,
+    /// Positions the client was wrongly listed as online at, sorted by ID.
+    pub removed_from_positions: Vec<PositionId>,
+    /// Whether the client was missing from the online positions of its position.
+    pub added_to_position: bool,
+    /// Number of station changes caused by recalculating the coverage.
+    pub station_changes: usize,
+}
+
+/// Runs a coverage computation, catching any panic caused by a logic bug or malformed network
+/// data so it can't take down the calling task. The panic is logged and `None` is returned, in
+/// which case callers keep their previous online stations instead of applying partial results.
+fn guard_coverage<T>(operation: &'static str, compute: impl FnOnce() -> T) -> Option<T> {
+    match std::panic::catch_unwind(AssertUnwindSafe(compute)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic payload");
+            tracing::error!(
+                operation,
+                reason,
+                "Coverage computation panicked, keeping previous coverage state"
+            );
+            None
+        }
+    }
+}
+
+/// Compact, human-readable description of a station change for log output.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn describe_station_change(change: &StationChange) -> String {
+    match change {
+        StationChange::Online {
+            station_id,
+            position_id,
+        } => format!("{station_id} online at {position_id}"),
+        StationChange::Handoff {
+            station_id,
+            from_position_id,
+            to_position_id,
+        } => format!("{station_id} handoff {from_position_id} -> {to_position_id}"),
+        StationChange::Offline {
+            station_id,
+            reason: None,
+        } => format!("{station_id} offline"),
+        StationChange::Offline {
+            station_id,
+            reason: Some(reason),
+        } => format!("{station_id} offline ({reason:?})"),
     }
 }
 
+fn frequency_mismatch(
+    network: &Network,
+    client_info: &ClientInfo,
+) -> Option<server::FrequencyMismatch> {
+    let position = network.get_position(client_info.position_id.as_ref()?)?;
+    let expected = normalize_frequency(&position.frequency);
+    let actual = normalize_frequency(&client_info.frequency);
+    (!frequencies_match(&expected, &actual))
+        .then_some(server::FrequencyMismatch { expected, actual })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pretty_assertions::assert_eq;
+    use pretty_assertions::{assert_eq, assert_matches};
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::time::Duration;
+    use vacs_protocol::vatsim::StationOfflineReason;
     use vacs_vatsim::coverage::test_support::TestFirBuilder;
 
     fn pos(id: &str) -> PositionId {
@@ -1007,6 +2644,57 @@ mod tests {
         }
     }
 
+    /// Serializes the complete coverage state of the manager into a deterministic JSON value, so
+    /// complex multi-client and VATSIM-only states can be asserted at once and unexpected side
+    /// effects are caught that granular assertions would miss.
+    async fn coverage_snapshot(manager: &ClientManager) -> serde_json::Value {
+        let clients: BTreeMap<ClientId, Option<PositionId>> = manager
+            .clients
+            .read()
+            .await
+            .iter()
+            .map(|(id, session)| (id.clone(), session.position_id().cloned()))
+            .collect();
+        let online_positions: BTreeMap<PositionId, BTreeSet<ClientId>> = manager
+            .online_positions
+            .read()
+            .await
+            .iter()
+            .map(|(id, clients)| (id.clone(), clients.iter().cloned().collect()))
+            .collect();
+        let online_stations: BTreeMap<StationId, PositionId> = manager
+            .online_stations
+            .read()
+            .await
+            .iter()
+            .map(|(station_id, position_id)| (station_id.clone(), position_id.clone()))
+            .collect();
+        let vatsim_only_positions: BTreeSet<PositionId> = manager
+            .vatsim_only_positions
+            .read()
+            .await
+            .iter()
+            .cloned()
+            .collect();
+
+        serde_json::json!({
+            "clients": clients,
+            "online_positions": online_positions,
+            "online_stations": online_stations,
+            "vatsim_only_positions": vatsim_only_positions,
+        })
+    }
+
+    async fn assert_coverage_snapshot(manager: &ClientManager, expected: serde_json::Value) {
+        let actual = coverage_snapshot(manager).await;
+        assert_eq!(
+            actual,
+            expected,
+            "Coverage snapshot mismatch, actual state:\n{}",
+            serde_json::to_string_pretty(&actual).unwrap()
+        );
+    }
+
     fn client_info(id: &str, position_id: &str, freq: &str) -> ClientInfo {
         ClientInfo {
             id: ClientId::from(id),
@@ -1040,17 +2728,21 @@ mod tests {
     struct DrainedMessages {
         station_changes: Vec<StationChange>,
         session_infos: Vec<server::SessionInfo>,
+        reassignments: Vec<server::PositionReassigned>,
     }
 
     /// Drain all pending messages from a client receiver, collecting station
-    /// changes (sorted for deterministic comparison) and session info updates.
-    fn drain_messages(rx: &mut mpsc::Receiver<ServerMessage>) -> DrainedMessages {
+    /// changes (sorted for deterministic comparison), session info updates and
+    /// position reassignments.
+    fn drain_messages(rx: &mut ClientReceiver) -> DrainedMessages {
         let mut station_changes = Vec::new();
         let mut session_infos = Vec::new();
+        let mut reassignments = Vec::new();
         while let Ok(msg) = rx.try_recv() {
             match msg {
                 ServerMessage::StationChanges(sc) => station_changes.extend(sc.changes),
                 ServerMessage::SessionInfo(si) => session_infos.push(si),
+                ServerMessage::PositionReassigned(pr) => reassignments.push(pr),
                 _ => {}
             }
         }
@@ -1058,6 +2750,7 @@ mod tests {
         DrainedMessages {
             station_changes,
             session_infos,
+            reassignments,
         }
     }
 
@@ -1069,7 +2762,7 @@ mod tests {
         }];
         let positions = online_positions(&["LOWW_TWR"]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert_eq!(result, changes);
     }
 
@@ -1081,7 +2774,7 @@ mod tests {
         }];
         let positions = online_positions(&[]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert!(result.is_empty());
     }
 
@@ -1094,7 +2787,7 @@ mod tests {
         }];
         let positions = online_positions(&["LOVV_CTR", "LOWW_APP"]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert_eq!(result, changes);
     }
 
@@ -1107,11 +2800,12 @@ mod tests {
         }];
         let positions = online_positions(&["LOWW_APP"]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert_eq!(
             result,
             vec![StationChange::Offline {
                 station_id: station("LOWW_TWR"),
+                reason: None
             }]
         );
     }
@@ -1125,7 +2819,7 @@ mod tests {
         }];
         let positions = online_positions(&["LOWW_APP"]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert_eq!(
             result,
             vec![StationChange::Online {
@@ -1144,7 +2838,7 @@ mod tests {
         }];
         let positions = online_positions(&[]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert!(result.is_empty());
     }
 
@@ -1152,10 +2846,11 @@ mod tests {
     fn offline_is_always_visible() {
         let changes = vec![StationChange::Offline {
             station_id: station("LOWW_TWR"),
+            reason: None,
         }];
         let positions = online_positions(&[]);
 
-        let result = ClientManager::client_visible_changes(&changes, &positions);
+        let result = simulate::client_visible_changes(&changes, &positions);
         assert_eq!(result, changes);
     }
 
@@ -1234,80 +2929,213 @@ mod tests {
             vec![
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn vatsim_only_changes_shown_as_uncallable() {
+        let changes = vec![
+            StationChange::Online {
+                station_id: station("LOWW_APP"),
+                position_id: pos("LOWW_APP"),
+            },
+            StationChange::Online {
+                station_id: station("LOWW_DEL"),
+                position_id: pos("LOWW_TWR"),
+            },
+            StationChange::Handoff {
+                station_id: station("LOWW_GND"),
+                from_position_id: pos("LOWW_APP"),
+                to_position_id: pos("LOWW_TWR"),
+            },
+            StationChange::Handoff {
+                station_id: station("LOWW_TWR"),
+                from_position_id: pos("LOVV_CTR"),
+                to_position_id: pos("LOWW_TWR"),
+            },
+        ];
+        let positions = online_positions(&["LOWW_APP"]);
+
+        let result = simulate::client_visible_changes_with_vatsim_only(&changes, &positions);
+        assert_eq!(
+            result,
+            vec![
+                StationChange::Online {
+                    station_id: station("LOWW_APP"),
+                    position_id: pos("LOWW_APP"),
+                },
+                StationChange::Offline {
+                    station_id: station("LOWW_DEL"),
+                    reason: Some(StationOfflineReason::VatsimOnly),
+                },
+                StationChange::Offline {
+                    station_id: station("LOWW_GND"),
+                    reason: Some(StationOfflineReason::VatsimOnly),
                 },
             ]
         );
     }
 
     #[tokio::test]
-    async fn vatsim_only_position_becomes_vacs_when_client_connects() {
+    async fn vatsim_only_position_shown_as_uncallable_if_enabled() {
         let (_dir, network) = create_lovv_network();
-        let manager = client_manager(network);
+        let manager = client_manager(network).with_show_vatsim_only_stations(true);
 
-        // vacs client connects as LOVV_CTR (covers everything including LOWW_APP,
-        // LOWW_TWR, etc.)
-        let (_client, mut rx_ctr) = manager
+        let (_client, mut rx) = manager
             .add_client(
-                client_info("client0", "LOVV_CTR", "132.600"),
+                client_info("client0", "LOWW_APP", "134.675"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        drain_messages(&mut rx_ctr);
+        drain_messages(&mut rx);
 
-        // LOWW_TWR comes online on VATSIM only
         let vatsim_controllers = HashMap::from([
             (
                 cid("client0"),
-                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+                controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
             ),
             (
                 cid("vatsim_client1"),
                 controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
             ),
         ]);
-        manager
+        let disconnected = manager
             .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
             .await;
+        assert!(disconnected.is_empty());
 
-        // LOWW_TWR station is NOT callable (VATSIM-only)
         let stations = manager
-            .list_stations(&ActiveProfile::Custom, Some(&pos("LOVV_CTR")))
+            .list_stations(&ActiveProfile::Custom, Some(&pos("LOWW_APP")))
             .await;
-        let station_ids: Vec<&str> = stations.iter().map(|s| s.id.as_str()).collect();
-        assert!(!station_ids.contains(&"LOWW_TWR"));
+        assert_eq!(
+            stations
+                .iter()
+                .map(|s| (s.id.as_str(), s.own, s.callable))
+                .collect::<Vec<_>>(),
+            vec![
+                ("LOWW_APP", true, true),
+                ("LOWW_DEL", false, false),
+                ("LOWW_GND", false, false),
+                ("LOWW_TWR", false, false),
+            ]
+        );
 
-        // CTR client should have received Offline for stations that became VATSIM-only
-        let changes_after_sync = drain_messages(&mut rx_ctr).station_changes;
+        let vatsim_only = Some(StationOfflineReason::VatsimOnly);
         assert_eq!(
-            changes_after_sync,
+            drain_messages(&mut rx).station_changes,
             vec![
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: vatsim_only
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: vatsim_only
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: vatsim_only
                 },
             ]
         );
 
-        // Now a vacs client connects as LOWW_TWR
-        let _client_twr = manager
-            .add_client(
-                client_info("client2", "LOWW_TWR", "119.400"),
-                ActiveProfile::Custom,
-                ClientConnectionGuard::default(),
+        // The VATSIM-only controller going offline takes the stations out of coverage entirely
+        manager
+            .sync_vatsim_state(
+                &HashMap::from([(
+                    cid("client0"),
+                    controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
+                )]),
+                &mut HashSet::new(),
+                false,
+            )
+            .await;
+        let stations = manager
+            .list_stations(&ActiveProfile::Custom, Some(&pos("LOWW_APP")))
+            .await;
+        assert!(stations.iter().all(|s| s.callable));
+    }
+
+    #[tokio::test]
+    async fn vatsim_only_position_becomes_vacs_when_client_connects() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // vacs client connects as LOVV_CTR (covers everything including LOWW_APP,
+        // LOWW_TWR, etc.)
+        let (_client, mut rx_ctr) = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        drain_messages(&mut rx_ctr);
+
+        // LOWW_TWR comes online on VATSIM only
+        let vatsim_controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+            ),
+            (
+                cid("vatsim_client1"),
+                controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
+            ),
+        ]);
+        manager
+            .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
+            .await;
+
+        // LOWW_TWR station is NOT callable (VATSIM-only)
+        let stations = manager
+            .list_stations(&ActiveProfile::Custom, Some(&pos("LOVV_CTR")))
+            .await;
+        let station_ids: Vec<&str> = stations.iter().map(|s| s.id.as_str()).collect();
+        assert!(!station_ids.contains(&"LOWW_TWR"));
+
+        // CTR client should have received Offline for stations that became VATSIM-only
+        let changes_after_sync = drain_messages(&mut rx_ctr).station_changes;
+        assert_eq!(
+            changes_after_sync,
+            vec![
+                StationChange::Offline {
+                    station_id: station("LOWW_DEL"),
+                    reason: None
+                },
+                StationChange::Offline {
+                    station_id: station("LOWW_GND"),
+                    reason: None
+                },
+                StationChange::Offline {
+                    station_id: station("LOWW_TWR"),
+                    reason: None
+                },
+            ]
+        );
+
+        // Now a vacs client connects as LOWW_TWR
+        let _client_twr = manager
+            .add_client(
+                client_info("client2", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
@@ -1344,6 +3172,78 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn client_missing_station_changes_resynced_on_next_sync() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network)
+            .with_send_queue_overflow_policy(SendQueueOverflowPolicy::DropNewestNonCritical);
+
+        let (client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+
+        // Fill the client's send queue, so the station changes caused by the next client are
+        // dropped
+        for _ in 0..crate::config::CLIENT_CHANNEL_CAPACITY {
+            client
+                .send_message(server::ClientDisconnected {
+                    client_id: cid("filler"),
+                })
+                .await
+                .unwrap();
+        }
+        manager
+            .add_client(
+                client_info("client1", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        assert!(drain_messages(&mut rx).station_changes.is_empty());
+
+        let controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+            ),
+            (
+                cid("client1"),
+                controller("client1", "LOWW_TWR", "119.400", FacilityType::Tower),
+            ),
+        ]);
+        manager
+            .sync_vatsim_state(&controllers, &mut HashSet::new(), false)
+            .await;
+
+        let stations = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|message| match message {
+                ServerMessage::StationList(list) => Some(list.stations),
+                _ => None,
+            })
+            .expect("No station list received");
+        assert_eq!(
+            stations,
+            manager
+                .list_stations(&ActiveProfile::Custom, Some(&pos("LOVV_CTR")))
+                .await
+        );
+        assert!(
+            stations
+                .iter()
+                .any(|s| s.id == station("LOWW_TWR") && !s.own)
+        );
+
+        // Clients are only resynced once after missing changes
+        assert_eq!(manager.resync_stale_clients().await, 0);
+    }
+
     #[tokio::test]
     async fn vacs_client_disconnect_with_vatsim_only_covering_same_position() {
         let (_dir, network) = create_lovv_network();
@@ -1425,12 +3325,15 @@ mod tests {
             vec![
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
                 },
             ]
         );
@@ -1521,72 +3424,169 @@ mod tests {
             vec![
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
                 },
             ]
         );
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn coverage_snapshot_remembers_last_controller() {
+        let (_dir, network) = create_lovv_network();
+        let manager =
+            client_manager(network).with_last_controller_retention(Duration::from_secs(60));
+
+        for (id, position_id, freq) in [
+            ("client0", "LOVV_CTR", "132.600"),
+            ("client1", "LOWW_TWR", "119.400"),
+        ] {
+            manager
+                .add_client(
+                    client_info(id, position_id, freq),
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+        }
+        assert!(
+            manager
+                .coverage_snapshot()
+                .await
+                .last_controlled_by
+                .is_empty()
+        );
+
+        // LOWW_TWR is handed off to LOVV_CTR, remembering LOWW_TWR as its last controller
+        manager.remove_client(cid("client1"), None).await;
+        let snapshot = manager.coverage_snapshot().await;
+        assert_eq!(
+            snapshot
+                .station(&station("LOWW_TWR"))
+                .map(|station| &station.position_id),
+            Some(&pos("LOVV_CTR"))
+        );
+        assert_eq!(
+            snapshot
+                .last_controller(&station("LOWW_TWR"))
+                .map(|last| &last.position_id),
+            Some(&pos("LOWW_TWR"))
+        );
+        assert_eq!(snapshot.last_controller(&station("LOWW_APP")), None);
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!(
+            manager
+                .coverage_snapshot()
+                .await
+                .last_controlled_by
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
-    async fn last_client_disconnect_clears_vatsim_only_state() {
+    async fn coverage_snapshot_multi_client_with_vatsim_only() {
         let (_dir, network) = create_lovv_network();
         let manager = client_manager(network);
 
-        // vacs client connects
-        let _client = manager
-            .add_client(
-                client_info("client0", "LOWW_APP", "134.675"),
-                ActiveProfile::Custom,
-                ClientConnectionGuard::default(),
-            )
-            .await
-            .unwrap();
+        for (id, position_id, freq) in [
+            ("client0", "LOVV_CTR", "132.600"),
+            ("client1", "LOWW_APP", "134.675"),
+        ] {
+            manager
+                .add_client(
+                    client_info(id, position_id, freq),
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+        }
 
-        // Sync with VATSIM-only TWR
         let vatsim_controllers = HashMap::from([
             (
                 cid("client0"),
-                controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
             ),
             (
-                cid("vatsim_client1"),
-                controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
+                cid("client1"),
+                controller("client1", "LOWW_APP", "134.675", FacilityType::Approach),
+            ),
+            (
+                cid("vatsim_client2"),
+                controller(
+                    "vatsim_client2",
+                    "LOWW_GND",
+                    "121.600",
+                    FacilityType::Ground,
+                ),
             ),
         ]);
         manager
             .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
             .await;
 
-        assert!(!manager.vatsim_only_positions.read().await.is_empty());
-        assert!(!manager.online_stations.read().await.is_empty());
-
-        // Last vacs client disconnects
-        manager
-            .remove_client(cid("client0"), Some(DisconnectReason::Terminated))
-            .await;
+        assert_coverage_snapshot(
+            &manager,
+            serde_json::json!({
+                "clients": {
+                    "client0": "LOVV_CTR",
+                    "client1": "LOWW_APP",
+                },
+                "online_positions": {
+                    "LOVV_CTR": ["client0"],
+                    "LOWW_APP": ["client1"],
+                },
+                "online_stations": {
+                    "LOWW_APP": "LOWW_APP",
+                    "LOWW_DEL": "LOWW_GND",
+                    "LOWW_GND": "LOWW_GND",
+                    "LOWW_TWR": "LOWW_APP",
+                },
+                "vatsim_only_positions": ["LOWW_GND"],
+            }),
+        )
+        .await;
 
-        assert!(
-            manager.vatsim_only_positions.read().await.is_empty(),
-            "VATSIM-only positions should be cleared after last client disconnects"
+        let snapshot = manager.coverage_snapshot().await;
+        assert_eq!(
+            snapshot.station(&StationId::from("LOWW_DEL")),
+            Some(&StationCoverage {
+                station_id: StationId::from("LOWW_DEL"),
+                position_id: PositionId::from("LOWW_GND"),
+            })
         );
-        assert!(
-            manager.online_stations.read().await.is_empty(),
-            "online stations should be cleared after last client disconnects"
+        assert_eq!(
+            snapshot.position(&PositionId::from("LOWW_GND")),
+            Some(&PositionCoverage {
+                position_id: PositionId::from("LOWW_GND"),
+                client_ids: Vec::new(),
+                vatsim_only: true,
+            })
+        );
+        assert_eq!(
+            snapshot
+                .position(&PositionId::from("LOWW_APP"))
+                .map(|position| position.client_ids.clone()),
+            Some(vec![cid("client1")])
         );
+        assert_eq!(snapshot.position(&PositionId::from("LOWW_TWR")), None);
     }
 
     #[tokio::test]
-    async fn clients_for_station_returns_empty_for_vatsim_only() {
+    async fn sync_vatsim_state_tolerates_transient_unknown_facility() {
         let (_dir, network) = create_lovv_network();
-        let manager = client_manager(network);
+        let manager = client_manager(network).with_unknown_facility_tolerance(2);
 
-        // vacs client connects as LOVV_CTR
-        let _client = manager
+        manager
             .add_client(
                 client_info("client0", "LOVV_CTR", "132.600"),
                 ActiveProfile::Custom,
@@ -1595,94 +3595,92 @@ mod tests {
             .await
             .unwrap();
 
-        // LOWW_TWR online VATSIM-only
-        let vatsim_controllers = HashMap::from([
-            (
-                cid("client0"),
-                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
-            ),
-            (
-                cid("vatsim_client1"),
-                controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
-            ),
-        ]);
-        manager
-            .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
+        let known = HashMap::from([(
+            cid("client0"),
+            controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+        )]);
+        let unknown = HashMap::from([(
+            cid("client0"),
+            controller("client0", "LOVV_CTR", "132.600", FacilityType::Unknown),
+        )]);
+        let mut pending_disconnect = HashSet::new();
+
+        for _ in 0..2 {
+            let disconnected = manager
+                .sync_vatsim_state(&unknown, &mut pending_disconnect, true)
+                .await;
+            assert!(disconnected.is_empty());
+            assert!(pending_disconnect.is_empty());
+        }
+
+        // A known facility resets the tolerance
+        let disconnected = manager
+            .sync_vatsim_state(&known, &mut pending_disconnect, true)
             .await;
+        assert!(disconnected.is_empty());
 
-        // LOWW_TWR station exists internally but has no callable clients
-        let clients = manager.clients_for_station(&station("LOWW_TWR")).await;
-        assert!(
-            clients.is_empty(),
-            "clients_for_station should return empty for VATSIM-only station"
-        );
+        for _ in 0..2 {
+            let disconnected = manager
+                .sync_vatsim_state(&unknown, &mut pending_disconnect, true)
+                .await;
+            assert!(disconnected.is_empty());
+            assert!(pending_disconnect.is_empty());
+        }
+
+        // Last known good position is kept while tolerated
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(client.position_id(), Some(&pos("LOVV_CTR")));
     }
 
     #[tokio::test]
-    async fn replace_network_removes_stale_position() {
-        let (dir, network) = create_lovv_network();
-        let manager = client_manager(network);
+    async fn sync_vatsim_state_disconnects_sustained_unknown_facility() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_unknown_facility_tolerance(1);
 
-        // Client connects as LOWW_DEL
-        let (_client, mut rx) = manager
+        manager
             .add_client(
-                client_info("client0", "LOWW_DEL", "122.125"),
+                client_info("client0", "LOVV_CTR", "132.600"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        // Drain initial station changes from add_client
-        drain_messages(&mut rx);
+        let unknown = HashMap::from([(
+            cid("client0"),
+            controller("client0", "LOVV_CTR", "132.600", FacilityType::Unknown),
+        )]);
+        let mut pending_disconnect = HashSet::new();
 
-        assert!(
-            manager
-                .online_positions
-                .read()
-                .await
-                .contains_key(&pos("LOWW_DEL"))
+        // Tolerated
+        let disconnected = manager
+            .sync_vatsim_state(&unknown, &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert!(pending_disconnect.is_empty());
+
+        // Tolerance exceeded, treated like a missing connection
+        let disconnected = manager
+            .sync_vatsim_state(&unknown, &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert!(pending_disconnect.contains(&cid("client0")));
+
+        let disconnected = manager
+            .sync_vatsim_state(&unknown, &mut pending_disconnect, true)
+            .await;
+        assert_eq!(
+            disconnected,
+            vec![(cid("client0"), DisconnectReason::NoActiveVatsimConnection)]
         );
+    }
 
-        // Replace with a network that no longer has LOWW_DEL position
-        let new_network = create_lovv_network_without_del(dir.path());
-        manager.replace_network(new_network).await;
+    #[tokio::test]
+    async fn sync_vatsim_state_missing_client_not_tolerated() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_unknown_facility_tolerance(2);
 
-        // Position should be gone
-        assert!(
-            !manager
-                .online_positions
-                .read()
-                .await
-                .contains_key(&pos("LOWW_DEL")),
-            "LOWW_DEL position should be removed after network replace"
-        );
-
-        // Client's position_id should be cleared
-        let client = manager.get_client(&cid("client0")).await.unwrap();
-        assert_eq!(
-            client.position_id(),
-            None,
-            "Client's position_id should be None after their position is removed"
-        );
-
-        // Client should receive Offline for LOWW_DEL station
-        let changes = drain_messages(&mut rx).station_changes;
-        assert_eq!(
-            changes,
-            vec![StationChange::Offline {
-                station_id: station("LOWW_DEL"),
-            }]
-        );
-    }
-
-    #[tokio::test]
-    async fn replace_network_removes_stale_station() {
-        let (dir, network) = create_lovv_network();
-        let manager = client_manager(network);
-
-        // Client connects as LOVV_CTR which covers all stations
-        let (_client, mut rx) = manager
+        manager
             .add_client(
                 client_info("client0", "LOVV_CTR", "132.600"),
                 ActiveProfile::Custom,
@@ -1691,178 +3689,206 @@ mod tests {
             .await
             .unwrap();
 
-        drain_messages(&mut rx);
+        let mut pending_disconnect = HashSet::new();
 
-        // LOWW_DEL station should be online
-        assert!(
-            manager
-                .online_stations
-                .read()
-                .await
-                .contains_key(&station("LOWW_DEL"))
+        // Genuine disappearance is not subject to the unknown facility tolerance
+        let disconnected = manager
+            .sync_vatsim_state(&HashMap::new(), &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert!(pending_disconnect.contains(&cid("client0")));
+
+        let disconnected = manager
+            .sync_vatsim_state(&HashMap::new(), &mut pending_disconnect, true)
+            .await;
+        assert_eq!(
+            disconnected,
+            vec![(cid("client0"), DisconnectReason::NoActiveVatsimConnection)]
         );
+    }
 
-        // Replace with network that has no LOWW_DEL station
-        let new_network = create_lovv_network_without_del(dir.path());
-        manager.replace_network(new_network).await;
+    #[tokio::test]
+    async fn sync_vatsim_state_prunes_disconnected_pending_clients() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
 
-        // LOWW_DEL station should be gone
-        assert!(
-            !manager
-                .online_stations
-                .read()
+        for (client, position, frequency) in [
+            ("client0", "LOVV_CTR", "132.600"),
+            ("client1", "LOWW_APP", "134.675"),
+        ] {
+            manager
+                .add_client(
+                    client_info(client, position, frequency),
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
                 .await
-                .contains_key(&station("LOWW_DEL")),
-            "LOWW_DEL station should be removed after network replace"
-        );
-
-        // Remaining stations (LOWW_APP, LOWW_TWR, LOWW_GND) should still be online
-        let online_stations = manager.online_stations.read().await;
-        assert!(online_stations.contains_key(&station("LOWW_APP")));
-        assert!(online_stations.contains_key(&station("LOWW_TWR")));
-        assert!(online_stations.contains_key(&station("LOWW_GND")));
-        drop(online_stations);
+                .unwrap();
+        }
 
-        // Client should receive exactly Offline for LOWW_DEL
-        let changes = drain_messages(&mut rx).station_changes;
+        let mut pending_disconnect = HashSet::new();
+        manager
+            .sync_vatsim_state(&HashMap::new(), &mut pending_disconnect, true)
+            .await;
         assert_eq!(
-            changes,
-            vec![StationChange::Offline {
-                station_id: station("LOWW_DEL"),
-            }],
-            "Only LOWW_DEL should go offline"
+            pending_disconnect,
+            HashSet::from([cid("client0"), cid("client1")])
         );
+
+        // Disconnecting on its own while pending
+        manager.remove_client(cid("client0"), None).await;
+
+        let controllers = HashMap::from([(
+            cid("client1"),
+            controller("client1", "LOWW_APP", "134.675", FacilityType::Approach),
+        )]);
+        let disconnected = manager
+            .sync_vatsim_state(&controllers, &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert!(pending_disconnect.is_empty());
     }
 
     #[tokio::test]
-    async fn replace_network_adds_new_station() {
-        let (dir, network) = create_lovv_network();
+    async fn repair_client_fixes_online_positions() {
+        let (_dir, network) = create_lovv_network();
         let manager = client_manager(network);
-
-        // Client connects as LOVV_CTR
         let (_client, mut rx) = manager
             .add_client(
-                client_info("client0", "LOVV_CTR", "132.600"),
+                client_info("client0", "LOWW_TWR", "119.400"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
-
         drain_messages(&mut rx);
 
-        // No LOVV_N1 station initially
-        assert!(
-            !manager
-                .online_stations
-                .read()
-                .await
-                .contains_key(&station("LOVV_N1"))
-        );
-
-        // Replace with network that adds LOVV_N1 station controlled by LOVV_CTR
-        let new_network = create_lovv_network_with_extra_station(dir.path());
-        manager.replace_network(new_network).await;
+        // Corrupt the online positions, listing the client at a second position
+        manager
+            .online_positions
+            .write()
+            .await
+            .entry(pos("LOWW_APP"))
+            .or_default()
+            .insert(cid("client0"));
 
-        // LOVV_N1 should now be online, controlled by LOVV_CTR
-        let online_stations = manager.online_stations.read().await;
+        let repair = manager.repair_client(&cid("client0"), None).await.unwrap();
         assert_eq!(
-            online_stations.get(&station("LOVV_N1")),
-            Some(&pos("LOVV_CTR")),
-            "LOVV_N1 should be online and controlled by LOVV_CTR"
+            repair,
+            ClientRepair {
+                previous_position_id: Some(pos("LOWW_TWR")),
+                position_id: Some(pos("LOWW_TWR")),
+                removed_from_positions: vec![pos("LOWW_APP")],
+                added_to_position: false,
+                station_changes: 0,
+            }
         );
-        drop(online_stations);
-
-        // Client should receive Online for LOVV_N1
-        let changes = drain_messages(&mut rx).station_changes;
         assert_eq!(
-            changes,
-            vec![StationChange::Online {
-                station_id: station("LOVV_N1"),
-                position_id: pos("LOVV_CTR"),
-            }]
+            manager
+                .online_positions
+                .read()
+                .await
+                .keys()
+                .cloned()
+                .collect::<HashSet<_>>(),
+            HashSet::from([pos("LOWW_TWR")])
         );
+
+        let messages = drain_messages(&mut rx);
+        assert_eq!(messages.session_infos.len(), 1);
+        assert!(messages.reassignments.is_empty());
+
+        assert!(matches!(
+            manager.repair_client(&cid("client1"), None).await,
+            Err(ClientManagerError::UnknownClient(_))
+        ));
     }
 
     #[tokio::test]
-    async fn replace_network_updates_station_controller() {
-        let (dir, network) = create_lovv_network();
+    async fn repair_client_rederives_position_from_controller() {
+        let (_dir, network) = create_lovv_network();
         let manager = client_manager(network);
-
-        // LOVV_CTR connects, covers all stations
-        let _client_ctr = manager
+        let (_client, mut rx) = manager
             .add_client(
-                client_info("client0", "LOVV_CTR", "132.600"),
+                client_info("client0", "LOWW_TWR", "119.400"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
+        drain_messages(&mut rx);
 
-        // LOWW_APP should be controlled by LOVV_CTR (only online position)
-        assert_eq!(
-            manager
-                .online_stations
-                .read()
-                .await
-                .get(&station("LOWW_APP")),
-            Some(&pos("LOVV_CTR"))
-        );
-
-        // Now LOWW_APP also connects
-        let _client_app = manager
-            .add_client(
-                client_info("client1", "LOWW_APP", "134.675"),
-                ActiveProfile::Custom,
-                ClientConnectionGuard::default(),
-            )
+        let controller = controller("client0", "LOWW_GND", "121.600", FacilityType::Ground);
+        let repair = manager
+            .repair_client(&cid("client0"), Some(&controller))
             .await
             .unwrap();
+        assert_eq!(repair.previous_position_id, Some(pos("LOWW_TWR")));
+        assert_eq!(repair.position_id, Some(pos("LOWW_GND")));
+        assert_eq!(repair.removed_from_positions, vec![pos("LOWW_TWR")]);
+        assert!(repair.added_to_position);
 
-        // LOWW_APP station should now be controlled by LOWW_APP position
-        // (higher priority in controlled_by list)
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(client.position_id(), Some(&pos("LOWW_GND")));
+
+        let messages = drain_messages(&mut rx);
         assert_eq!(
-            manager
-                .online_stations
-                .read()
-                .await
-                .get(&station("LOWW_APP")),
-            Some(&pos("LOWW_APP"))
+            messages.reassignments,
+            vec![server::PositionReassigned {
+                from: Some(pos("LOWW_TWR")),
+                to: Some(pos("LOWW_GND")),
+                reason: server::PositionReassignmentReason::SessionRepaired,
+            }]
         );
+    }
 
-        // Replace with same network structure — controllers should remain
-        let new_network = Network::load_from_dir(dir.path()).unwrap();
-        manager.replace_network(new_network).await;
+    #[tokio::test]
+    async fn sync_vatsim_state_caps_pending_disconnects() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_max_pending_disconnects(1);
 
-        // Station controller assignments should be preserved
-        let online = manager.online_stations.read().await;
-        assert_eq!(
-            online.get(&station("LOWW_APP")),
-            Some(&pos("LOWW_APP")),
-            "LOWW_APP should still be controlled by LOWW_APP position after no-op reload"
-        );
+        for (client, position, frequency) in [
+            ("client0", "LOVV_CTR", "132.600"),
+            ("client1", "LOWW_APP", "134.675"),
+        ] {
+            manager
+                .add_client(
+                    client_info(client, position, frequency),
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut pending_disconnect = HashSet::new();
+        let disconnected = manager
+            .sync_vatsim_state(&HashMap::new(), &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert_eq!(pending_disconnect.len(), 1);
     }
 
     #[tokio::test]
-    async fn replace_network_cleans_vatsim_only_positions() {
-        let (dir, network) = create_lovv_network();
+    async fn last_client_disconnect_clears_vatsim_only_state() {
+        let (_dir, network) = create_lovv_network();
         let manager = client_manager(network);
 
-        // vacs client connects as LOVV_CTR
+        // vacs client connects
         let _client = manager
             .add_client(
-                client_info("client0", "LOVV_CTR", "132.600"),
+                client_info("client0", "LOWW_APP", "134.675"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        // LOWW_TWR comes online on VATSIM only
+        // Sync with VATSIM-only TWR
         let vatsim_controllers = HashMap::from([
             (
                 cid("client0"),
-                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+                controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
             ),
             (
                 cid("vatsim_client1"),
@@ -1873,225 +3899,1872 @@ mod tests {
             .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
             .await;
 
-        assert!(
-            manager
-                .vatsim_only_positions
-                .read()
-                .await
-                .contains(&pos("LOWW_TWR")),
-            "LOWW_TWR should be in vatsim_only"
-        );
+        assert!(!manager.vatsim_only_positions.read().await.is_empty());
+        assert!(!manager.online_stations.read().await.is_empty());
 
-        // Replace with network that no longer has LOWW_TWR position
-        let new_network = create_lovv_network_without_del(dir.path());
+        // Last vacs client disconnects
+        manager
+            .remove_client(cid("client0"), Some(DisconnectReason::Terminated))
+            .await;
 
-        // Verify LOWW_TWR position actually doesn't exist in the new network
         assert!(
-            new_network.get_position(&pos("LOWW_TWR")).is_some(),
-            "LOWW_TWR position should still exist in the reduced network"
+            manager.vatsim_only_positions.read().await.is_empty(),
+            "VATSIM-only positions should be cleared after last client disconnects"
         );
-
-        manager.replace_network(new_network).await;
-
-        // LOWW_TWR position still exists (we only removed DEL), so it stays
-        // in vatsim_only
         assert!(
-            manager
-                .vatsim_only_positions
-                .read()
-                .await
-                .contains(&pos("LOWW_TWR")),
-            "LOWW_TWR should still be in vatsim_only (position still exists)"
+            manager.online_stations.read().await.is_empty(),
+            "online stations should be cleared after last client disconnects"
         );
     }
 
     #[tokio::test]
-    async fn replace_network_removes_nonexistent_vatsim_only() {
-        let (dir, network) = create_lovv_network();
-        let manager = client_manager(network);
+    async fn last_client_disconnect_retains_vatsim_only_coverage_if_enabled() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_retain_coverage_without_clients(true);
 
-        // vacs client connects as LOVV_CTR
         let _client = manager
             .add_client(
-                client_info("client0", "LOVV_CTR", "132.600"),
+                client_info("client0", "LOWW_APP", "134.675"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        // LOWW_DEL comes online on VATSIM only
+        let twr = controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower);
         let vatsim_controllers = HashMap::from([
             (
                 cid("client0"),
-                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
-            ),
-            (
-                cid("vatsim_client1"),
-                controller(
-                    "vatsim_client1",
-                    "LOWW_DEL",
-                    "122.125",
-                    FacilityType::Delivery,
-                ),
+                controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
             ),
+            (cid("vatsim_client1"), twr.clone()),
         ]);
         manager
             .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
             .await;
 
-        assert!(
+        manager
+            .remove_client(cid("client0"), Some(DisconnectReason::Terminated))
+            .await;
+
+        let snapshot = manager.coverage_snapshot().await;
+        assert_eq!(
+            snapshot
+                .station(&station("LOWW_TWR"))
+                .map(|station| station.position_id.clone()),
+            Some(pos("LOWW_TWR"))
+        );
+        assert!(
+            snapshot
+                .position(&pos("LOWW_TWR"))
+                .is_some_and(|position| position.vatsim_only)
+        );
+        assert!(snapshot.position(&pos("LOWW_APP")).is_none());
+
+        // Coverage keeps following the data feed without any vacs client
+        manager
+            .sync_vatsim_state(&HashMap::new(), &mut HashSet::new(), false)
+            .await;
+        assert!(manager.vatsim_only_positions.read().await.is_empty());
+        assert!(manager.online_stations.read().await.is_empty());
+
+        manager
+            .sync_vatsim_state(
+                &HashMap::from([(cid("vatsim_client1"), twr)]),
+                &mut HashSet::new(),
+                false,
+            )
+            .await;
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_TWR"))
+        );
+    }
+
+    #[tokio::test]
+    async fn list_client_sessions_filters_by_tag() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        for (client_info, tags) in [
+            (
+                client_info("client0", "LOVV_CTR", "132.600"),
+                vec!["platform:MacOs".to_string(), "event:fno".to_string()],
+            ),
+            (
+                client_info_without_position("client1"),
+                vec!["platform:Windows".to_string()],
+            ),
+            (client_info_without_position("client2"), Vec::new()),
+        ] {
             manager
-                .vatsim_only_positions
-                .read()
+                .add_client_with_tags(
+                    client_info,
+                    ActiveProfile::Custom,
+                    tags,
+                    ClientConnectionGuard::default(),
+                )
                 .await
-                .contains(&pos("LOWW_DEL"))
+                .unwrap();
+        }
+
+        let session = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(session.tags(), ["platform:MacOs", "event:fno"]);
+
+        let ids = |sessions: Vec<ClientSession>| {
+            sessions
+                .iter()
+                .map(|session| session.id().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(manager.list_client_sessions(None).await),
+            vec![cid("client0"), cid("client1"), cid("client2")]
+        );
+        assert_eq!(
+            ids(manager.list_client_sessions(Some("platform:MacOs")).await),
+            vec![cid("client0")]
+        );
+        assert_eq!(
+            ids(manager.list_client_sessions(Some("event:fno")).await),
+            vec![cid("client0")]
+        );
+        assert!(
+            manager
+                .list_client_sessions(Some("platform:macos"))
+                .await
+                .is_empty()
         );
+    }
 
-        // Replace with network that no longer has LOWW_DEL position
-        let new_network = create_lovv_network_without_del(dir.path());
+    #[tokio::test]
+    async fn idle_clients_respects_position_exemption() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        for client_info in [
+            client_info("client0", "LOVV_CTR", "132.600"),
+            client_info_without_position("client1"),
+        ] {
+            manager
+                .add_client(
+                    client_info,
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            manager.idle_clients(Duration::ZERO, false).await,
+            vec![cid("client0"), cid("client1")]
+        );
+        assert_eq!(
+            manager.idle_clients(Duration::ZERO, true).await,
+            vec![cid("client0")]
+        );
         assert!(
-            new_network.get_position(&pos("LOWW_DEL")).is_none(),
-            "Precondition: LOWW_DEL should not exist in the new network"
+            manager
+                .idle_clients(Duration::from_secs(3600), false)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn clients_for_station_returns_empty_for_vatsim_only() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // vacs client connects as LOVV_CTR
+        let _client = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // LOWW_TWR online VATSIM-only
+        let vatsim_controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+            ),
+            (
+                cid("vatsim_client1"),
+                controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
+            ),
+        ]);
+        manager
+            .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
+            .await;
+
+        // LOWW_TWR station exists internally but has no callable clients
+        let clients = manager.clients_for_station(&station("LOWW_TWR")).await;
+        assert!(
+            clients.is_empty(),
+            "clients_for_station should return empty for VATSIM-only station"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_removes_stale_position() {
+        let (dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // Client connects as LOWW_DEL
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_DEL", "122.125"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // Drain initial station changes from add_client
+        drain_messages(&mut rx);
+
+        assert!(
+            manager
+                .online_positions
+                .read()
+                .await
+                .contains_key(&pos("LOWW_DEL"))
         );
+
+        // Replace with a network that no longer has LOWW_DEL position
+        let new_network = create_lovv_network_without_del(dir.path());
         manager.replace_network(new_network).await;
 
-        // LOWW_DEL should be cleaned from vatsim_only
+        // Position should be gone
         assert!(
             !manager
-                .vatsim_only_positions
+                .online_positions
                 .read()
                 .await
-                .contains(&pos("LOWW_DEL")),
-            "LOWW_DEL should be removed from vatsim_only after network replace"
+                .contains_key(&pos("LOWW_DEL")),
+            "LOWW_DEL position should be removed after network replace"
+        );
+
+        // Client's position_id should be cleared
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.position_id(),
+            None,
+            "Client's position_id should be None after their position is removed"
+        );
+
+        // Client should receive Offline for LOWW_DEL station and a reassignment notice
+        let messages = drain_messages(&mut rx);
+        assert_eq!(
+            messages.station_changes,
+            vec![StationChange::Offline {
+                station_id: station("LOWW_DEL"),
+                reason: None
+            }]
+        );
+        assert_eq!(
+            messages.reassignments,
+            vec![server::PositionReassigned {
+                from: Some(pos("LOWW_DEL")),
+                to: None,
+                reason: server::PositionReassignmentReason::NetworkReload,
+            }]
         );
     }
 
     #[tokio::test]
-    async fn replace_network_with_profile_reassignment() {
-        let dir = tempfile::tempdir().unwrap();
-        let fir_path = dir.path().join("LOVV");
-        std::fs::create_dir(&fir_path).unwrap();
-
-        let network = create_lovv_network_with_profiles(dir.path());
+    async fn replace_network_removes_stale_station() {
+        let (dir, network) = create_lovv_network();
         let manager = client_manager(network);
 
-        // Client connects as LOWW_APP with profile APP_PROFILE
-        let _client = manager
+        // Client connects as LOVV_CTR which covers all stations
+        let (_client, mut rx) = manager
             .add_client(
-                client_info("client0", "LOWW_APP", "134.675"),
-                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        // Verify initial profile
-        let client = manager.get_client(&cid("client0")).await.unwrap();
-        assert_eq!(
-            client.active_profile(),
-            &ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+        drain_messages(&mut rx);
+
+        // LOWW_DEL station should be online
+        assert!(
+            manager
+                .online_stations
+                .read()
+                .await
+                .contains_key(&station("LOWW_DEL"))
         );
 
-        // Replace network where LOWW_APP's profile_id changes to CTR_PROFILE
-        let new_network = create_lovv_network_with_reassigned_profile(dir.path());
+        // Replace with network that has no LOWW_DEL station
+        let new_network = create_lovv_network_without_del(dir.path());
         manager.replace_network(new_network).await;
 
-        // Client's active profile should now be CTR_PROFILE
-        let client = manager.get_client(&cid("client0")).await.unwrap();
+        // LOWW_DEL station should be gone
+        assert!(
+            !manager
+                .online_stations
+                .read()
+                .await
+                .contains_key(&station("LOWW_DEL")),
+            "LOWW_DEL station should be removed after network replace"
+        );
+
+        // Remaining stations (LOWW_APP, LOWW_TWR, LOWW_GND) should still be online
+        let online_stations = manager.online_stations.read().await;
+        assert!(online_stations.contains_key(&station("LOWW_APP")));
+        assert!(online_stations.contains_key(&station("LOWW_TWR")));
+        assert!(online_stations.contains_key(&station("LOWW_GND")));
+        drop(online_stations);
+
+        // Client should receive exactly Offline for LOWW_DEL
+        let changes = drain_messages(&mut rx).station_changes;
         assert_eq!(
-            client.active_profile(),
-            &ActiveProfile::Specific(ProfileId::from("CTR_PROFILE")),
-            "Client's profile should be updated to CTR_PROFILE after network reload"
+            changes,
+            vec![StationChange::Offline {
+                station_id: station("LOWW_DEL"),
+                reason: None
+            }],
+            "Only LOWW_DEL should go offline"
         );
     }
 
     #[tokio::test]
-    async fn replace_network_custom_profile_stays_custom() {
-        let dir = tempfile::tempdir().unwrap();
-        let fir_path = dir.path().join("LOVV");
-        std::fs::create_dir(&fir_path).unwrap();
-
-        let network = create_lovv_network_with_profiles(dir.path());
+    async fn replace_network_adds_new_station() {
+        let (dir, network) = create_lovv_network();
         let manager = client_manager(network);
 
-        // Client connects with Custom profile (user's own selection)
-        let _client = manager
+        // Client connects as LOVV_CTR
+        let (_client, mut rx) = manager
             .add_client(
-                client_info("client0", "LOWW_APP", "134.675"),
+                client_info("client0", "LOVV_CTR", "132.600"),
                 ActiveProfile::Custom,
                 ClientConnectionGuard::default(),
             )
             .await
             .unwrap();
 
-        // Replace network where LOWW_APP's profile changes
-        let new_network = create_lovv_network_with_reassigned_profile(dir.path());
+        drain_messages(&mut rx);
+
+        // No LOVV_N1 station initially
+        assert!(
+            !manager
+                .online_stations
+                .read()
+                .await
+                .contains_key(&station("LOVV_N1"))
+        );
+
+        // Replace with network that adds LOVV_N1 station controlled by LOVV_CTR
+        let new_network = create_lovv_network_with_extra_station(dir.path());
         manager.replace_network(new_network).await;
 
-        // Client's profile should remain Custom
-        let client = manager.get_client(&cid("client0")).await.unwrap();
+        // LOVV_N1 should now be online, controlled by LOVV_CTR
+        let online_stations = manager.online_stations.read().await;
+        assert_eq!(
+            online_stations.get(&station("LOVV_N1")),
+            Some(&pos("LOVV_CTR")),
+            "LOVV_N1 should be online and controlled by LOVV_CTR"
+        );
+        drop(online_stations);
+
+        // Client should receive Online for LOVV_N1
+        let changes = drain_messages(&mut rx).station_changes;
+        assert_eq!(
+            changes,
+            vec![StationChange::Online {
+                station_id: station("LOVV_N1"),
+                position_id: pos("LOVV_CTR"),
+            }]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn list_stations_consistent_during_replace_network() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let network_a = TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .position_with_profile("LOWW_TWR", &["LOWW"], "119.400", "TWR", "LOWW")
+            .tabbed_profile("LOWW", &[("TWR", "LOWW_TWR")])
+            .build(dir_a.path());
+        let dir_b = tempfile::tempdir().unwrap();
+        let network_b = TestFirBuilder::new("LOVV")
+            .station("LOWW_GND", &["LOWW_TWR"])
+            .position_with_profile("LOWW_TWR", &["LOWW"], "119.400", "TWR", "LOWW")
+            .tabbed_profile("LOWW", &[("GND", "LOWW_GND")])
+            .build(dir_b.path());
+
+        let manager = Arc::new(client_manager(network_a.clone()));
+        let profile = ActiveProfile::Specific(ProfileId::from("LOWW"));
+        // Drop the receiver right away, so session updates sent during reloads never block.
+        let (_client, _) = manager
+            .add_client(
+                client_info("client0", "LOWW_TWR", "119.400"),
+                profile.clone(),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        let replacer = tokio::spawn({
+            let manager = manager.clone();
+            async move {
+                for i in 0..200 {
+                    let network = if i % 2 == 0 {
+                        network_b.clone()
+                    } else {
+                        network_a.clone()
+                    };
+                    manager.replace_network(network).await;
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+
+        let mut iterations = 0;
+        while !replacer.is_finished() {
+            let ids = manager
+                .list_stations(&profile, None)
+                .await
+                .into_iter()
+                .map(|s| s.id)
+                .collect::<Vec<_>>();
+            assert!(
+                ids == [station("LOWW_TWR")] || ids == [station("LOWW_GND")],
+                "Torn station list after {iterations} iterations: {ids:?}"
+            );
+            iterations += 1;
+            tokio::task::yield_now().await;
+        }
+        replacer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn replace_network_updates_station_controller() {
+        let (dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // LOVV_CTR connects, covers all stations
+        let _client_ctr = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // LOWW_APP should be controlled by LOVV_CTR (only online position)
+        assert_eq!(
+            manager
+                .online_stations
+                .read()
+                .await
+                .get(&station("LOWW_APP")),
+            Some(&pos("LOVV_CTR"))
+        );
+
+        // Now LOWW_APP also connects
+        let _client_app = manager
+            .add_client(
+                client_info("client1", "LOWW_APP", "134.675"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // LOWW_APP station should now be controlled by LOWW_APP position
+        // (higher priority in controlled_by list)
+        assert_eq!(
+            manager
+                .online_stations
+                .read()
+                .await
+                .get(&station("LOWW_APP")),
+            Some(&pos("LOWW_APP"))
+        );
+
+        // Replace with same network structure — controllers should remain
+        let new_network = Network::load_from_dir(dir.path()).unwrap();
+        manager.replace_network(new_network).await;
+
+        // Station controller assignments should be preserved
+        let online = manager.online_stations.read().await;
+        assert_eq!(
+            online.get(&station("LOWW_APP")),
+            Some(&pos("LOWW_APP")),
+            "LOWW_APP should still be controlled by LOWW_APP position after no-op reload"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_cleans_vatsim_only_positions() {
+        let (dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // vacs client connects as LOVV_CTR
+        let _client = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // LOWW_TWR comes online on VATSIM only
+        let vatsim_controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+            ),
+            (
+                cid("vatsim_client1"),
+                controller("vatsim_client1", "LOWW_TWR", "119.400", FacilityType::Tower),
+            ),
+        ]);
+        manager
+            .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
+            .await;
+
+        assert!(
+            manager
+                .vatsim_only_positions
+                .read()
+                .await
+                .contains(&pos("LOWW_TWR")),
+            "LOWW_TWR should be in vatsim_only"
+        );
+
+        // Replace with network that no longer has LOWW_TWR position
+        let new_network = create_lovv_network_without_del(dir.path());
+
+        // Verify LOWW_TWR position actually doesn't exist in the new network
+        assert!(
+            new_network.get_position(&pos("LOWW_TWR")).is_some(),
+            "LOWW_TWR position should still exist in the reduced network"
+        );
+
+        manager.replace_network(new_network).await;
+
+        // LOWW_TWR position still exists (we only removed DEL), so it stays
+        // in vatsim_only
+        assert!(
+            manager
+                .vatsim_only_positions
+                .read()
+                .await
+                .contains(&pos("LOWW_TWR")),
+            "LOWW_TWR should still be in vatsim_only (position still exists)"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_removes_nonexistent_vatsim_only() {
+        let (dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        // vacs client connects as LOVV_CTR
+        let _client = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // LOWW_DEL comes online on VATSIM only
+        let vatsim_controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOVV_CTR", "132.600", FacilityType::Enroute),
+            ),
+            (
+                cid("vatsim_client1"),
+                controller(
+                    "vatsim_client1",
+                    "LOWW_DEL",
+                    "122.125",
+                    FacilityType::Delivery,
+                ),
+            ),
+        ]);
+        manager
+            .sync_vatsim_state(&vatsim_controllers, &mut HashSet::new(), false)
+            .await;
+
+        assert!(
+            manager
+                .vatsim_only_positions
+                .read()
+                .await
+                .contains(&pos("LOWW_DEL"))
+        );
+
+        // Replace with network that no longer has LOWW_DEL position
+        let new_network = create_lovv_network_without_del(dir.path());
+        assert!(
+            new_network.get_position(&pos("LOWW_DEL")).is_none(),
+            "Precondition: LOWW_DEL should not exist in the new network"
+        );
+        manager.replace_network(new_network).await;
+
+        // LOWW_DEL should be cleaned from vatsim_only
+        assert!(
+            !manager
+                .vatsim_only_positions
+                .read()
+                .await
+                .contains(&pos("LOWW_DEL")),
+            "LOWW_DEL should be removed from vatsim_only after network replace"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_fir_leaves_other_firs_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        lovv_fir().create(dir.path());
+        TestFirBuilder::new("EDMM")
+            .station("EDMM_CTR", &["EDMM_CTR"])
+            .station("EDMM_N", &["EDMM_N", "EDMM_CTR"])
+            .position("EDMM_CTR", &["EDMM"], "127.950", "CTR")
+            .position("EDMM_N", &["EDMM"], "133.680", "CTR")
+            .create(dir.path());
+        let manager = client_manager(Network::load_from_dir(dir.path()).unwrap());
+
+        let (_client_lovv, mut rx_lovv) = manager
+            .add_client(
+                client_info("client0", "LOWW_DEL", "122.125"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let (_client_edmm, mut rx_edmm) = manager
+            .add_client(
+                client_info("client1", "EDMM_CTR", "127.950"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx_lovv);
+        drain_messages(&mut rx_edmm);
+
+        let updated_dir = tempfile::tempdir().unwrap();
+        let updated = create_lovv_network_without_del(updated_dir.path());
+        manager
+            .replace_fir(&FlightInformationRegionId::from("LOVV"), updated)
+            .await
+            .unwrap();
+
+        assert_coverage_snapshot(
+            &manager,
+            serde_json::json!({
+                "clients": { "client0": null, "client1": "EDMM_CTR" },
+                "online_positions": { "EDMM_CTR": ["client1"] },
+                "online_stations": { "EDMM_CTR": "EDMM_CTR", "EDMM_N": "EDMM_CTR" },
+                "vatsim_only_positions": [],
+            }),
+        )
+        .await;
+        assert!(manager.has_station(&station("EDMM_N")));
+        assert!(!manager.has_station(&station("LOWW_DEL")));
+
+        let offline_del = StationChange::Offline {
+            station_id: station("LOWW_DEL"),
+            reason: None,
+        };
+        let messages = drain_messages(&mut rx_lovv);
+        assert_eq!(messages.station_changes, vec![offline_del.clone()]);
+        assert_eq!(
+            messages.reassignments,
+            vec![server::PositionReassigned {
+                from: Some(pos("LOWW_DEL")),
+                to: None,
+                reason: server::PositionReassignmentReason::NetworkReload,
+            }]
+        );
+
+        // The EDMM client only learns about the removed LOVV station
+        let messages = drain_messages(&mut rx_edmm);
+        assert_eq!(messages.station_changes, vec![offline_del]);
+        assert!(messages.session_infos.is_empty());
+        assert!(messages.reassignments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn replace_fir_rejects_unknown_and_invalid_firs() {
+        let dir = tempfile::tempdir().unwrap();
+        lovv_fir().create(dir.path());
+        TestFirBuilder::new("EDMM")
+            .station("EDMM_CTR", &["EDMM_CTR", "LOVV_CTR"])
+            .position("EDMM_CTR", &["EDMM"], "127.950", "CTR")
+            .create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        let content_hash = network.content_hash();
+        let manager = client_manager(network);
+
+        assert_matches!(
+            manager
+                .replace_fir(&FlightInformationRegionId::from("LKAA"), Network::empty())
+                .await,
+            Err(ClientManagerError::UnknownFir(_))
+        );
+        // EDMM_CTR falls back to LOVV_CTR, which can't be removed
+        assert_matches!(
+            manager
+                .replace_fir(&FlightInformationRegionId::from("LOVV"), Network::empty())
+                .await,
+            Err(ClientManagerError::InvalidNetwork(_))
+        );
+        assert_eq!(manager.network.read().content_hash(), content_hash);
+    }
+
+    #[tokio::test]
+    async fn replace_network_with_profile_reassignment() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        // Client connects as LOWW_APP with profile APP_PROFILE
+        let _client = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // Verify initial profile
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+        );
+
+        // Replace network where LOWW_APP's profile_id changes to CTR_PROFILE
+        let new_network = create_lovv_network_with_reassigned_profile(dir.path());
+        manager.replace_network(new_network).await;
+
+        // Client's active profile should now be CTR_PROFILE
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::Specific(ProfileId::from("CTR_PROFILE")),
+            "Client's profile should be updated to CTR_PROFILE after network reload"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_custom_profile_stays_custom() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        // Client connects with Custom profile (user's own selection)
+        let _client = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // Replace network where LOWW_APP's profile changes
+        let new_network = create_lovv_network_with_reassigned_profile(dir.path());
+        manager.replace_network(new_network).await;
+
+        // Client's profile should remain Custom
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::Custom,
+            "Client with Custom profile should remain Custom after network reload"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_profile_cleared_sends_session_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        // Client connects as LOWW_APP with Specific(APP_PROFILE)
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        // Drain initial messages
+        drain_messages(&mut rx);
+
+        // Reload with a network where LOWW_APP no longer has a profile_id
+        let new_network = create_lovv_network_without_profiles(dir.path());
+        manager.replace_network(new_network).await;
+
+        // Client's internal state should be cleared to None
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::None,
+            "Client's profile should be cleared after position loses its profile_id"
+        );
+
+        // Client should have received a SessionInfo with Changed(None)
+        let session_infos = drain_messages(&mut rx).session_infos;
+        assert_eq!(session_infos.len(), 1, "Exactly one SessionInfo expected");
+        assert_eq!(
+            session_infos[0].profile,
+            SessionProfile::Changed(ActiveProfile::None),
+            "Client should be told profile was cleared"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_same_profile_id_content_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        // Client connects as LOWW_APP with Specific(APP_PROFILE)
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        drain_messages(&mut rx);
+
+        // Reload with same profile ID but different tab content
+        let new_network = create_lovv_network_with_modified_profile_content(dir.path());
+        manager.replace_network(new_network).await;
+
+        // Internal profile ID should stay the same
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+        );
+
+        // Client should receive the updated profile content
+        let session_infos = drain_messages(&mut rx).session_infos;
+        assert_eq!(session_infos.len(), 1, "Exactly one SessionInfo expected");
+        match &session_infos[0].profile {
+            SessionProfile::Changed(ActiveProfile::Specific(profile)) => {
+                assert_eq!(profile.id, ProfileId::from("APP_PROFILE"));
+                // The modified profile has a different tab label
+                match &profile.profile_type {
+                    vacs_protocol::profile::ProfileType::Tabbed(tabs) => {
+                        assert_eq!(tabs[0].label, vec!["Updated"]);
+                    }
+                    other => panic!("Expected Tabbed profile, got: {other:?}"),
+                }
+            }
+            other => panic!("Expected Changed(Specific(...)), got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reload_profiles_updates_sessions_without_station_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+        let online_stations = manager.online_stations.read().await.clone();
+
+        let new_network = create_lovv_network_with_modified_profile_content(dir.path());
+        manager.reload_profiles(new_network).await.unwrap();
+
+        let messages = drain_messages(&mut rx);
+        assert!(messages.station_changes.is_empty());
+        assert_eq!(messages.session_infos.len(), 1);
+        match &messages.session_infos[0].profile {
+            SessionProfile::Changed(ActiveProfile::Specific(profile)) => {
+                assert_eq!(profile.id, ProfileId::from("APP_PROFILE"));
+                match &profile.profile_type {
+                    vacs_protocol::profile::ProfileType::Tabbed(tabs) => {
+                        assert_eq!(tabs[0].label, vec!["Updated"]);
+                    }
+                    other => panic!("Expected Tabbed profile, got: {other:?}"),
+                }
+            }
+            other => panic!("Expected Changed(Specific(...)), got: {other:?}"),
+        }
+        assert_eq!(*manager.online_stations.read().await, online_stations);
+    }
+
+    #[tokio::test]
+    async fn reload_profiles_rejects_coverage_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+
+        let new_network = create_lovv_network_without_del(dir.path());
+        assert!(matches!(
+            manager.reload_profiles(new_network).await,
+            Err(ClientManagerError::InvalidProfiles(_))
+        ));
+
+        assert!(manager.has_station(&station("LOWW_DEL")));
+        let messages = drain_messages(&mut rx);
+        assert!(messages.station_changes.is_empty());
+        assert!(messages.session_infos.is_empty());
+    }
+
+    #[tokio::test]
+    async fn replace_network_profiles_only_skips_coverage_recalculation() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+        let online_stations = manager.online_stations.read().await.clone();
+
+        // A full replacement would block on the coverage locks held here
+        let vatsim_only_guard = manager.vatsim_only_positions.write().await;
+        let online_stations_guard = manager.online_stations.write().await;
+        let new_network = create_lovv_network_with_modified_profile_content(dir.path());
+        tokio::time::timeout(Duration::from_secs(1), manager.replace_network(new_network))
+            .await
+            .expect("profiles-only replacement should not wait for coverage locks");
+        drop(online_stations_guard);
+        drop(vatsim_only_guard);
+
+        let messages = drain_messages(&mut rx);
+        assert!(messages.station_changes.is_empty());
+        assert_eq!(messages.session_infos.len(), 1);
+        assert!(matches!(
+            &messages.session_infos[0].profile,
+            SessionProfile::Changed(ActiveProfile::Specific(profile))
+                if profile.id == ProfileId::from("APP_PROFILE")
+        ));
+        assert_eq!(*manager.online_stations.read().await, online_stations);
+    }
+
+    #[tokio::test]
+    async fn replace_network_same_profile_content_not_resent() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        // Client connects as LOWW_APP with Specific(APP_PROFILE)
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+
+        drain_messages(&mut rx);
+
+        // Reload with byte-identical profile content
+        let new_network = create_lovv_network_with_profiles(dir.path());
+        manager.replace_network(new_network).await;
+
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(
+            client.active_profile(),
+            &ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+        );
+
+        let session_infos = drain_messages(&mut rx).session_infos;
+        assert!(
+            session_infos.is_empty(),
+            "Unchanged profile content should not be re-transmitted"
+        );
+    }
+
+    #[tokio::test]
+    async fn coverage_panic_keeps_previous_state() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let online_stations = manager.online_stations.read().await.clone();
+
+        // Panic mid-computation while holding the manager's locks, as a malformed network would
+        let result = {
+            let _online_positions = manager.online_positions.write().await;
+            let _vatsim_only = manager.vatsim_only_positions.write().await;
+            guard_coverage("test", || {
+                let _network = manager.network.read();
+                panic!("malformed network");
+            })
+        };
+        assert_eq!(result, None::<()>);
+        assert_eq!(*manager.online_stations.read().await, online_stations);
+
+        // Locks are released and usable, so the manager keeps serving clients
+        manager
+            .add_client(
+                client_info("client1", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_TWR"))
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_network_failure_keeps_previous_state() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        let (_client_app, mut rx_app) = manager
+            .add_client(
+                client_info("client0", "LOWW_APP", "134.675"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let (_client_twr, mut rx_twr) = manager
+            .add_client(
+                client_info("client1", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx_app);
+        drain_messages(&mut rx_twr);
+        let snapshot = coverage_snapshot(&manager).await;
+
+        // The new network drops the LOWW_TWR position, which would clear client1's position
+        let new_dir = tempfile::tempdir().unwrap();
+        manager
+            .fail_network_replacement
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        manager
+            .replace_network(create_lovv_network_without_twr_position(new_dir.path()))
+            .await;
+
+        assert_coverage_snapshot(&manager, snapshot).await;
+        assert!(
+            manager
+                .network
+                .read()
+                .get_position(&pos("LOWW_TWR"))
+                .is_some()
+        );
+        let app_messages = drain_messages(&mut rx_app);
+        let twr_messages = drain_messages(&mut rx_twr);
+        assert_eq!(app_messages.station_changes, vec![]);
+        assert!(app_messages.session_infos.is_empty());
+        assert_eq!(twr_messages.station_changes, vec![]);
+        assert!(twr_messages.session_infos.is_empty());
+
+        // Once the computation succeeds, the replacement is applied as a whole
+        manager
+            .fail_network_replacement
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        manager
+            .replace_network(create_lovv_network_without_twr_position(new_dir.path()))
+            .await;
+
+        assert!(
+            manager
+                .network
+                .read()
+                .get_position(&pos("LOWW_TWR"))
+                .is_none()
+        );
+        assert_coverage_snapshot(
+            &manager,
+            serde_json::json!({
+                "clients": {"client0": "LOWW_APP", "client1": null},
+                "online_positions": {"LOWW_APP": ["client0"]},
+                "online_stations": {
+                    "LOWW_APP": "LOWW_APP",
+                    "LOWW_DEL": "LOWW_APP",
+                    "LOWW_GND": "LOWW_APP",
+                    "LOWW_TWR": "LOWW_APP",
+                },
+                "vatsim_only_positions": [],
+            }),
+        )
+        .await;
+        assert_eq!(drain_messages(&mut rx_twr).session_infos.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn disabled_fir_is_excluded_from_coverage_until_enabled() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+        let fir_id = FlightInformationRegionId::from("LOVV");
+
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        manager
+            .add_client(
+                client_info("client1", "LOWW_APP", "134.675"),
+                ActiveProfile::None,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+
+        let online_before = manager.online_stations.read().await.clone();
+        assert!(!online_before.is_empty());
+
+        // Disabling the FIR takes all of its stations offline
+        assert!(manager.set_fir_enabled(&fir_id, false).await.unwrap());
+        let changes = drain_messages(&mut rx).station_changes;
+        assert_eq!(changes.len(), online_before.len());
+        assert!(
+            changes
+                .iter()
+                .all(|c| matches!(c, StationChange::Offline { .. }))
+        );
+        assert!(manager.online_stations.read().await.is_empty());
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            None
+        );
+
+        // Disabling again is a no-op
+        assert!(!manager.set_fir_enabled(&fir_id, false).await.unwrap());
+        assert!(drain_messages(&mut rx).station_changes.is_empty());
+
+        // Re-enabling restores the previous coverage
+        assert!(manager.set_fir_enabled(&fir_id, true).await.unwrap());
+        let changes = drain_messages(&mut rx).station_changes;
+        assert_eq!(changes.len(), online_before.len());
+        assert!(
+            changes
+                .iter()
+                .all(|c| matches!(c, StationChange::Online { .. }))
+        );
+        assert_eq!(*manager.online_stations.read().await, online_before);
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_TWR"))
+        );
+
+        assert!(matches!(
+            manager
+                .set_fir_enabled(&FlightInformationRegionId::from("LOWW"), false)
+                .await,
+            Err(ClientManagerError::UnknownFir(_))
+        ));
+    }
+
+    /// Adds clients on LOWW_TWR and LOWW_APP, returning the receiver of the LOWW_TWR client.
+    async fn coverage_override_setup(manager: &ClientManager) -> ClientReceiver {
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info("client0", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        manager
+            .add_client(
+                client_info("client1", "LOWW_APP", "134.675"),
+                ActiveProfile::None,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+        rx
+    }
+
+    #[tokio::test]
+    async fn coverage_override_set_and_clear() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+        let mut rx = coverage_override_setup(&manager).await;
+
+        assert!(
+            manager
+                .set_coverage_override(&station("LOWW_TWR"), &pos("LOWW_APP"))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            drain_messages(&mut rx).station_changes,
+            vec![StationChange::Handoff {
+                station_id: station("LOWW_TWR"),
+                from_position_id: pos("LOWW_TWR"),
+                to_position_id: pos("LOWW_APP"),
+            }]
+        );
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_APP"))
+        );
+        assert_eq!(
+            manager.coverage_override(&station("LOWW_TWR")),
+            Some(pos("LOWW_APP"))
+        );
+
+        // Setting the same override again is a no-op
+        assert!(
+            !manager
+                .set_coverage_override(&station("LOWW_TWR"), &pos("LOWW_APP"))
+                .await
+                .unwrap()
+        );
+        assert!(drain_messages(&mut rx).station_changes.is_empty());
+
+        // Clearing restores the normal coverage resolution
+        assert!(
+            manager
+                .clear_coverage_override(&station("LOWW_TWR"))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            drain_messages(&mut rx).station_changes,
+            vec![StationChange::Handoff {
+                station_id: station("LOWW_TWR"),
+                from_position_id: pos("LOWW_APP"),
+                to_position_id: pos("LOWW_TWR"),
+            }]
+        );
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_TWR"))
+        );
+        assert_eq!(manager.coverage_override(&station("LOWW_TWR")), None);
+
+        assert!(
+            !manager
+                .clear_coverage_override(&station("LOWW_TWR"))
+                .await
+                .unwrap()
+        );
+        assert!(drain_messages(&mut rx).station_changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn coverage_override_rejects_unknown_station_and_offline_position() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+        let _rx = coverage_override_setup(&manager).await;
+
+        assert!(matches!(
+            manager
+                .set_coverage_override(&station("LOWW_CTR"), &pos("LOWW_APP"))
+                .await,
+            Err(ClientManagerError::UnknownStation(_))
+        ));
+        assert!(matches!(
+            manager
+                .set_coverage_override(&station("LOWW_TWR"), &pos("LOWW_DEL"))
+                .await,
+            Err(ClientManagerError::PositionOffline(_))
+        ));
+        assert!(matches!(
+            manager.clear_coverage_override(&station("LOWW_CTR")).await,
+            Err(ClientManagerError::UnknownStation(_))
+        ));
+        assert_eq!(manager.coverage_override(&station("LOWW_TWR")), None);
+    }
+
+    #[tokio::test]
+    async fn coverage_override_suppresses_coverage_changes() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+        let mut rx = coverage_override_setup(&manager).await;
+
+        // LOWW_GND is normally covered by its parent's position LOWW_TWR
+        manager
+            .set_coverage_override(&station("LOWW_GND"), &pos("LOWW_APP"))
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+
+        // LOWW_GND coming online would normally take over its station
+        manager
+            .add_client(
+                client_info("client2", "LOWW_GND", "121.600"),
+                ActiveProfile::None,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let changes = drain_messages(&mut rx).station_changes;
+        assert!(
+            changes.iter().all(|c| !matches!(
+                c,
+                StationChange::Handoff { station_id, .. } if *station_id == station("LOWW_GND")
+            )),
+            "Overridden station must not be handed off: {changes:?}"
+        );
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_GND")).await,
+            Some(pos("LOWW_APP"))
+        );
+
+        // Clearing the override hands the station to its normal controller
+        manager
+            .clear_coverage_override(&station("LOWW_GND"))
+            .await
+            .unwrap();
+        assert_eq!(
+            drain_messages(&mut rx).station_changes,
+            vec![StationChange::Handoff {
+                station_id: station("LOWW_GND"),
+                from_position_id: pos("LOWW_APP"),
+                to_position_id: pos("LOWW_GND"),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn coverage_override_cleared_when_forced_position_goes_offline() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+        let mut rx = coverage_override_setup(&manager).await;
+
+        manager
+            .set_coverage_override(&station("LOWW_TWR"), &pos("LOWW_APP"))
+            .await
+            .unwrap();
+        drain_messages(&mut rx);
+
+        manager.remove_client(cid("client1"), None).await;
+
+        // The forced position already left, so clients see the station come back online
+        let changes = drain_messages(&mut rx).station_changes;
+        assert!(
+            changes.contains(&StationChange::Online {
+                station_id: station("LOWW_TWR"),
+                position_id: pos("LOWW_TWR"),
+            }),
+            "Overridden station must return to its normal controller: {changes:?}"
+        );
+        assert_eq!(manager.coverage_override(&station("LOWW_TWR")), None);
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_TWR"))
+        );
+    }
+
+    #[tokio::test]
+    async fn dataset_info_reflects_loaded_network() {
+        let (dir, network) = create_lovv_network();
+        let digest = network.digest().to_string();
+        let manager = client_manager(network);
+
+        let info = manager.dataset_info();
+        assert_eq!(info.version, digest);
+        assert!(info.loaded_at > 0);
+
+        // Reloading identical content keeps the version
+        manager.replace_network(lovv_fir().build(dir.path())).await;
+        assert_eq!(manager.dataset_info().version, info.version);
+
+        let new_network = create_lovv_network_without_del(dir.path());
+        let new_digest = new_network.digest().to_string();
+        manager.replace_network(new_network).await;
+
+        let new_info = manager.dataset_info();
+        assert_ne!(new_info.version, info.version);
+        assert_eq!(new_info.version, new_digest);
+        assert!(new_info.loaded_at >= info.loaded_at);
+    }
+
+    #[test]
+    fn find_positions_frequency_only_unique_match() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_frequency_only_matching(true);
+
+        // Callsign prefix doesn't match any position, frequency is unique
+        let positions = manager.find_positions(&controller(
+            "client0",
+            "LOWX_TWR",
+            "119.400",
+            FacilityType::Tower,
+        ));
+        assert_eq!(
+            positions.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![pos("LOWW_TWR")]
+        );
+
+        // Unknown frequency still doesn't match
+        let positions = manager.find_positions(&controller(
+            "client0",
+            "LOWX_TWR",
+            "118.000",
+            FacilityType::Tower,
+        ));
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn find_positions_frequency_only_ambiguous_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_fir()
+            .station_with_parent("LOWW_E_TWR", "LOWW_APP", &["LOWW_E_TWR"])
+            .position("LOWW_E_TWR", &["LOWW_E"], "119.400", "TWR")
+            .build(dir.path());
+        let manager = client_manager(network).with_frequency_only_matching(true);
+
+        let positions = manager.find_positions(&controller(
+            "client0",
+            "LOWX_TWR",
+            "119.400",
+            FacilityType::Tower,
+        ));
+        assert!(positions.is_empty());
+    }
+
+    /// LOVV with an additional LOWW_E_TWR position sharing LOWW_TWR's frequency, so callsigns
+    /// like `LOWW_E_R_TWR` match both.
+    fn create_lovv_network_with_ambiguous_twr(dir: &std::path::Path) -> Network {
+        lovv_fir()
+            .station_with_parent("LOWW_E_TWR", "LOWW_APP", &["LOWW_E_TWR"])
+            .position("LOWW_E_TWR", &["LOWW_E"], "119.400", "TWR")
+            .build(dir)
+    }
+
+    fn matched_position(position_match: PositionMatch) -> Option<PositionId> {
+        match position_match {
+            PositionMatch::Position(position) => Some(position.id),
+            other => panic!("Expected a matching position, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn match_position_ambiguous_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let ambiguous = controller("client0", "LOWW_E_R_TWR", "119.400", FacilityType::Tower);
+        let candidates = vec![pos("LOWW_E_TWR"), pos("LOWW_TWR")];
+
+        let manager = client_manager(create_lovv_network_with_ambiguous_twr(dir.path()));
+        assert_eq!(
+            matched_position(manager.match_position(
+                &controller("client0", "LOWW_TWR", "119.400", FacilityType::Tower),
+                None
+            )),
+            Some(pos("LOWW_TWR"))
+        );
+        assert!(matches!(
+            manager.match_position(&ambiguous, None),
+            PositionMatch::Ambiguous(ids) if ids == candidates
+        ));
+        assert_eq!(
+            matched_position(manager.match_position(&ambiguous, Some(&pos("LOWW_TWR")))),
+            Some(pos("LOWW_TWR"))
+        );
+        assert!(matches!(
+            manager.match_position(&ambiguous, Some(&pos("LOWW_APP"))),
+            PositionMatch::InvalidSelection(ids) if ids == candidates
+        ));
+
+        let manager = client_manager(create_lovv_network_with_ambiguous_twr(dir.path()))
+            .with_ambiguous_position_selection(false);
+        assert!(matches!(
+            manager.match_position(&ambiguous, Some(&pos("LOWW_TWR"))),
+            PositionMatch::Ambiguous(ids) if ids == candidates
+        ));
+    }
+
+    #[tokio::test]
+    async fn sync_vatsim_state_ambiguous_position_consistent_with_login() {
+        let relief = |callsign: &str| {
+            HashMap::from([(
+                cid("client0"),
+                controller("client0", callsign, "119.400", FacilityType::Tower),
+            )])
+        };
+
+        for selection_allowed in [true, false] {
+            let dir = tempfile::tempdir().unwrap();
+            let manager = client_manager(create_lovv_network_with_ambiguous_twr(dir.path()))
+                .with_ambiguous_position_selection(selection_allowed);
+
+            // Connected at the selected position, as accepted by the login flow
+            let login = manager.match_position(
+                &relief("LOWW_E_R_TWR")[&cid("client0")],
+                Some(&pos("LOWW_TWR")),
+            );
+            assert_eq!(
+                matches!(login, PositionMatch::Position(_)),
+                selection_allowed
+            );
+            manager
+                .add_client(
+                    client_info("client0", "LOWW_TWR", "119.400"),
+                    ActiveProfile::Custom,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+
+            let mut pending_disconnect = HashSet::new();
+            let disconnected = manager
+                .sync_vatsim_state(&relief("LOWW_E_S_TWR"), &mut pending_disconnect, true)
+                .await;
+
+            if selection_allowed {
+                assert!(disconnected.is_empty());
+                let client = manager.get_client(&cid("client0")).await.unwrap();
+                assert_eq!(client.position_id(), Some(&pos("LOWW_TWR")));
+            } else {
+                assert_eq!(
+                    disconnected,
+                    vec![(
+                        cid("client0"),
+                        DisconnectReason::AmbiguousVatsimPosition(vec![
+                            pos("LOWW_E_TWR"),
+                            pos("LOWW_TWR")
+                        ])
+                    )]
+                );
+            }
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn find_positions_relief_patterns_kept_across_reload() {
+        fn network(dir: &std::path::Path) -> Network {
+            lovv_fir()
+                .station_with_parent("LOWW_E_TWR", "LOWW_APP", &["LOWW_E_TWR"])
+                .position("LOWW_E_TWR", &["LOWW_E"], "119.400", "TWR")
+                .build(dir)
+        }
+        let relief = controller("client0", "LOWW_E_R_TWR", "119.400", FacilityType::Tower);
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            client_manager(network(dir.path()))
+                .find_positions(&relief)
+                .len(),
+            2
+        );
+
+        let manager = client_manager(network(dir.path()))
+            .with_relief_patterns(ReliefPatterns::new(&["R"]).unwrap());
+        assert_eq!(
+            manager
+                .find_positions(&relief)
+                .into_iter()
+                .map(|p| p.id)
+                .collect::<Vec<_>>(),
+            vec![pos("LOWW_E_TWR")]
+        );
+
+        let new_dir = tempfile::tempdir().unwrap();
+        manager.replace_network(network(new_dir.path())).await;
+        assert_eq!(
+            manager
+                .find_positions(&relief)
+                .into_iter()
+                .map(|p| p.id)
+                .collect::<Vec<_>>(),
+            vec![pos("LOWW_E_TWR")]
+        );
+    }
+
+    #[test]
+    fn find_positions_frequency_only_disabled() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
+
+        let positions = manager.find_positions(&controller(
+            "client0",
+            "LOWX_TWR",
+            "119.400",
+            FacilityType::Tower,
+        ));
+        assert!(positions.is_empty());
+
+        // Regular callsign matches are unaffected
+        let positions = manager.find_positions(&controller(
+            "client0",
+            "LOWW_TWR",
+            "119.400",
+            FacilityType::Tower,
+        ));
+        assert_eq!(
+            positions.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![pos("LOWW_TWR")]
+        );
+    }
+
+    #[tokio::test]
+    async fn relevant_stations_of_clients_filters_by_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        for (id, position_id, freq) in [
+            ("client0", "LOVV_CTR", "132.600"),
+            ("client1", "LOWW_TWR", "119.400"),
+            ("client2", "LOWW_GND", "121.600"),
+        ] {
+            manager
+                .add_client(
+                    client_info(id, position_id, freq),
+                    ActiveProfile::None,
+                    ClientConnectionGuard::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let ctr_profile = ActiveProfile::Specific(ProfileId::from("CTR_PROFILE"));
+        assert_eq!(
+            manager
+                .relevant_stations_of_clients(&ctr_profile, &[&cid("client1")])
+                .await,
+            vec![station("LOWW_TWR")]
+        );
+        assert_eq!(
+            manager
+                .relevant_stations_of_clients(&ctr_profile, &[&cid("client2")])
+                .await,
+            Vec::<StationId>::new(),
+            "Stations outside of the profile must not be returned"
+        );
+        assert_eq!(
+            manager
+                .relevant_stations_of_clients(&ActiveProfile::Custom, &[&cid("client2")])
+                .await,
+            vec![station("LOWW_DEL"), station("LOWW_GND")]
+        );
+        assert_eq!(
+            manager
+                .relevant_stations_of_clients(&ActiveProfile::None, &[&cid("client1")])
+                .await,
+            Vec::<StationId>::new()
+        );
+    }
+
+    #[test]
+    fn resolve_profile_id_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network).with_default_profiles(DefaultProfilesConfig {
+            facility_types: HashMap::from([
+                (FacilityType::Tower, ProfileId::from("APP_PROFILE")),
+                (FacilityType::Ground, ProfileId::from("MISSING_PROFILE")),
+            ]),
+            network: Some(ProfileId::from("CTR_PROFILE")),
+        });
+        let resolve = |position_id: &str| {
+            let position = manager.get_position(Some(&pos(position_id))).unwrap();
+            manager.resolve_profile_id(&position)
+        };
+
+        // Explicit position profile
+        assert_eq!(resolve("LOWW_APP"), Some(ProfileId::from("APP_PROFILE")));
+        // Facility type default
+        assert_eq!(resolve("LOWW_TWR"), Some(ProfileId::from("APP_PROFILE")));
+        // Facility type default doesn't exist, network default
+        assert_eq!(resolve("LOWW_GND"), Some(ProfileId::from("CTR_PROFILE")));
+        // No facility type default, network default
+        assert_eq!(resolve("LOWW_DEL"), Some(ProfileId::from("CTR_PROFILE")));
+    }
+
+    #[test]
+    fn resolve_profile_id_without_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        let position = manager.get_position(Some(&pos("LOWW_TWR"))).unwrap();
+        assert_eq!(manager.resolve_profile_id(&position), None);
+        let position = manager.get_position(Some(&pos("LOVV_CTR"))).unwrap();
+        assert_eq!(
+            manager.resolve_profile_id(&position),
+            Some(ProfileId::from("CTR_PROFILE"))
+        );
+    }
+
+    #[test]
+    fn available_profiles_include_custom() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = create_lovv_network_with_profiles(dir.path());
+        let manager = client_manager(network);
+
+        assert_eq!(
+            manager.available_profiles(Some(&pos("LOVV_CTR"))),
+            vec![
+                ActiveProfile::Specific(ProfileId::from("CTR_PROFILE")),
+                ActiveProfile::Custom,
+            ]
+        );
+        assert_eq!(
+            manager.available_profiles(Some(&pos("LOWW_TWR"))),
+            vec![ActiveProfile::Custom]
+        );
         assert_eq!(
-            client.active_profile(),
-            &ActiveProfile::Custom,
-            "Client with Custom profile should remain Custom after network reload"
+            manager.available_profiles(None),
+            vec![ActiveProfile::Custom]
         );
     }
 
-    #[tokio::test]
-    async fn replace_network_profile_cleared_sends_session_update() {
+    #[test]
+    fn default_profiles_missing_profiles() {
         let dir = tempfile::tempdir().unwrap();
-        let fir_path = dir.path().join("LOVV");
-        std::fs::create_dir(&fir_path).unwrap();
-
         let network = create_lovv_network_with_profiles(dir.path());
-        let manager = client_manager(network);
-
-        // Client connects as LOWW_APP with Specific(APP_PROFILE)
-        let (_client, mut rx) = manager
-            .add_client(
-                client_info("client0", "LOWW_APP", "134.675"),
-                ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
-                ClientConnectionGuard::default(),
-            )
-            .await
-            .unwrap();
+        let default_profiles = DefaultProfilesConfig {
+            facility_types: HashMap::from([
+                (FacilityType::Tower, ProfileId::from("APP_PROFILE")),
+                (FacilityType::Ground, ProfileId::from("MISSING_PROFILE")),
+            ]),
+            network: Some(ProfileId::from("MISSING_PROFILE")),
+        };
 
-        // Drain initial messages
-        drain_messages(&mut rx);
+        assert_eq!(
+            default_profiles.missing_profiles(&network),
+            vec![&ProfileId::from("MISSING_PROFILE")]
+        );
+        assert!(
+            DefaultProfilesConfig::default()
+                .missing_profiles(&network)
+                .is_empty()
+        );
+    }
 
-        // Reload with a network where LOWW_APP no longer has a profile_id
-        let new_network = create_lovv_network_without_profiles(dir.path());
-        manager.replace_network(new_network).await;
+    #[test]
+    fn frequency_mismatch_compares_normalized_frequencies() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network);
 
-        // Client's internal state should be cleared to None
-        let client = manager.get_client(&cid("client0")).await.unwrap();
         assert_eq!(
-            client.active_profile(),
-            &ActiveProfile::None,
-            "Client's profile should be cleared after position loses its profile_id"
+            manager.frequency_mismatch(&client_info("client0", "LOWW_TWR", "119.400")),
+            None
         );
-
-        // Client should have received a SessionInfo with Changed(None)
-        let session_infos = drain_messages(&mut rx).session_infos;
-        assert_eq!(session_infos.len(), 1, "Exactly one SessionInfo expected");
         assert_eq!(
-            session_infos[0].profile,
-            SessionProfile::Changed(ActiveProfile::None),
-            "Client should be told profile was cleared"
+            manager.frequency_mismatch(&client_info("client0", "LOWW_TWR", "119.4")),
+            None
+        );
+        assert_eq!(
+            manager.frequency_mismatch(&client_info("client0", "LOWW_TWR", "119.405")),
+            Some(server::FrequencyMismatch {
+                expected: "119.400".to_string(),
+                actual: "119.405".to_string(),
+            })
+        );
+        assert_eq!(
+            manager.frequency_mismatch(&client_info_without_position("client0")),
+            None
         );
     }
 
+    #[test]
+    fn position_without_coverage_warns_for_positions_controlling_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_fir()
+            .position("LOWW_E_TWR", &["LOWW"], "123.800", "TWR")
+            .build(dir.path());
+        let manager = client_manager(network).with_position_without_coverage_warning(true);
+
+        assert!(manager.position_without_coverage(Some(&pos("LOWW_E_TWR"))));
+        assert!(!manager.position_without_coverage(Some(&pos("LOWW_TWR"))));
+        assert!(!manager.position_without_coverage(Some(&pos("LOVV_CTR"))));
+        assert!(!manager.position_without_coverage(None));
+    }
+
+    #[test]
+    fn position_without_coverage_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_fir()
+            .position("LOWW_E_TWR", &["LOWW"], "123.800", "TWR")
+            .build(dir.path());
+        let manager = client_manager(network);
+
+        assert!(!manager.position_without_coverage(Some(&pos("LOWW_E_TWR"))));
+    }
+
     #[tokio::test]
-    async fn replace_network_same_profile_id_content_changed() {
+    async fn replace_network_sends_fir_ice_servers() {
         let dir = tempfile::tempdir().unwrap();
         let fir_path = dir.path().join("LOVV");
         std::fs::create_dir(&fir_path).unwrap();
 
         let network = create_lovv_network_with_profiles(dir.path());
         let manager = client_manager(network);
+        assert_eq!(manager.ice_servers(Some(&pos("LOWW_APP"))), None);
 
-        // Client connects as LOWW_APP with Specific(APP_PROFILE)
         let (_client, mut rx) = manager
             .add_client(
                 client_info("client0", "LOWW_APP", "134.675"),
@@ -2103,33 +5776,22 @@ mod tests {
 
         drain_messages(&mut rx);
 
-        // Reload with same profile ID but different tab content
-        let new_network = create_lovv_network_with_modified_profile_content(dir.path());
+        // Reload with an ICE server override for the LOVV FIR
+        TestFirBuilder::new("LOVV")
+            .ice_server(&["turn:lovv.example.com:3478"])
+            .create(dir.path());
+        let new_network = Network::load_from_dir(dir.path()).unwrap();
         manager.replace_network(new_network).await;
 
-        // Internal profile ID should stay the same
-        let client = manager.get_client(&cid("client0")).await.unwrap();
+        let expected = vec![IceServer::from("turn:lovv.example.com:3478".to_string())];
         assert_eq!(
-            client.active_profile(),
-            &ActiveProfile::Specific(ProfileId::from("APP_PROFILE")),
+            manager.ice_servers(Some(&pos("LOWW_APP"))),
+            Some(expected.clone())
         );
 
-        // Client should receive the updated profile content
         let session_infos = drain_messages(&mut rx).session_infos;
         assert_eq!(session_infos.len(), 1, "Exactly one SessionInfo expected");
-        match &session_infos[0].profile {
-            SessionProfile::Changed(ActiveProfile::Specific(profile)) => {
-                assert_eq!(profile.id, ProfileId::from("APP_PROFILE"));
-                // The modified profile has a different tab label
-                match &profile.profile_type {
-                    vacs_protocol::profile::ProfileType::Tabbed(tabs) => {
-                        assert_eq!(tabs[0].label, vec!["Updated"]);
-                    }
-                    other => panic!("Expected Tabbed profile, got: {other:?}"),
-                }
-            }
-            other => panic!("Expected Changed(Specific(...)), got: {other:?}"),
-        }
+        assert_eq!(session_infos[0].ice_servers, Some(expected));
     }
 
     #[tokio::test]
@@ -2409,12 +6071,15 @@ controlled_by = ["LOWW_DEL"]
             vec![
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
                 },
             ]
         );
@@ -2525,6 +6190,7 @@ controlled_by = ["LOWW_DEL"]
             changes0,
             vec![StationChange::Offline {
                 station_id: station("LOWW_DEL"),
+                reason: None
             }],
             "client0"
         );
@@ -2533,11 +6199,57 @@ controlled_by = ["LOWW_DEL"]
             changes1,
             vec![StationChange::Offline {
                 station_id: station("LOWW_DEL"),
+                reason: None
             }],
             "client1"
         );
     }
 
+    #[tokio::test]
+    async fn empty_network_accepts_clients_without_position() {
+        let manager = client_manager(Network::empty());
+
+        let (_client, mut rx) = manager
+            .add_client(
+                client_info_without_position("client0"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.list_stations(&ActiveProfile::Custom, None).await,
+            vec![]
+        );
+
+        let vatsim_controllers = HashMap::from([
+            (
+                cid("client0"),
+                controller("client0", "LOWW_APP", "134.675", FacilityType::Approach),
+            ),
+            (
+                cid("vatsim_client1"),
+                controller(
+                    "vatsim_client1",
+                    "LOVV_CTR",
+                    "132.600",
+                    FacilityType::Enroute,
+                ),
+            ),
+        ]);
+        let mut pending_disconnect = HashSet::new();
+        let disconnected = manager
+            .sync_vatsim_state(&vatsim_controllers, &mut pending_disconnect, true)
+            .await;
+        assert!(disconnected.is_empty());
+        assert!(pending_disconnect.is_empty());
+
+        let client = manager.get_client(&cid("client0")).await.unwrap();
+        assert_eq!(client.position_id(), None);
+        assert!(manager.vatsim_only_positions.read().await.is_empty());
+        assert_eq!(drain_messages(&mut rx).station_changes, vec![]);
+    }
+
     #[tokio::test]
     async fn add_client_vatsim_only_position_not_controlling_any_station() {
         let (_dir, network) = create_lovv_network();
@@ -2697,12 +6409,15 @@ controlled_by = ["LOWW_DEL"]
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
                 },
             ],
             "CTR client"
@@ -2719,12 +6434,15 @@ controlled_by = ["LOWW_DEL"]
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_DEL"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_GND"),
+                    reason: None
                 },
                 StationChange::Offline {
                     station_id: station("LOWW_TWR"),
+                    reason: None
                 },
             ],
             "APP client"
@@ -2777,6 +6495,7 @@ controlled_by = ["LOWW_DEL"]
             changes_ctr,
             vec![StationChange::Offline {
                 station_id: station("LOWW_DEL"),
+                reason: None
             }]
         );
 
@@ -2787,11 +6506,321 @@ controlled_by = ["LOWW_DEL"]
             changes_nopos,
             vec![StationChange::Offline {
                 station_id: station("LOWW_DEL"),
+                reason: None
             }],
             "No-position client should receive station changes too"
         );
     }
 
+    #[tokio::test]
+    async fn broadcast_station_changes_limited_to_relevant_firs() {
+        let dir = tempfile::tempdir().unwrap();
+        for (fir, frequency) in [
+            ("LOVV", "132.600"),
+            ("EDMM", "127.950"),
+            ("LKAA", "132.350"),
+        ] {
+            let mut builder = TestFirBuilder::new(fir)
+                .station(&format!("{fir}_CTR"), &[&format!("{fir}_CTR")])
+                .station(&format!("{fir}_N"), &[&format!("{fir}_CTR")])
+                .position(&format!("{fir}_CTR"), &[fir], frequency, "CTR");
+            if fir == "LOVV" {
+                builder = builder.fir_settings(r#"neighbors = ["EDMM"]"#);
+            }
+            builder.create(dir.path());
+        }
+        let manager = client_manager(Network::load_from_dir(dir.path()).unwrap());
+
+        let (_client_lovv, mut rx_lovv) = manager
+            .add_client(
+                client_info("client0", "LOVV_CTR", "132.600"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let (_client_lkaa, mut rx_lkaa) = manager
+            .add_client(
+                client_info("client1", "LKAA_CTR", "132.350"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        drain_messages(&mut rx_lovv);
+        drain_messages(&mut rx_lkaa);
+
+        let online = |fir: &str| StationChange::Online {
+            station_id: station(&format!("{fir}_N")),
+            position_id: pos(&format!("{fir}_CTR")),
+        };
+        manager
+            .broadcast_station_changes(&[online("LOVV"), online("EDMM"), online("LKAA")])
+            .await;
+
+        // LOVV configures EDMM as its only neighbor
+        assert_eq!(
+            drain_messages(&mut rx_lovv).station_changes,
+            vec![online("EDMM"), online("LOVV")]
+        );
+        // LKAA doesn't configure neighbors and receives changes of all FIRs
+        assert_eq!(
+            drain_messages(&mut rx_lkaa).station_changes,
+            vec![online("EDMM"), online("LKAA"), online("LOVV")]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn station_flap_debounce_suppresses_intermediate_changes() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_station_flap_debounce(Duration::from_secs(10));
+
+        let (_observer, mut rx_observer) = manager
+            .add_client(
+                client_info_without_position("observer"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let add_twr = || {
+            manager.add_client(
+                client_info("client0", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+        };
+        let _twr = add_twr().await.unwrap();
+        let online_stations = coverage_snapshot(&manager).await["online_stations"].clone();
+        assert!(!drain_messages(&mut rx_observer).station_changes.is_empty());
+
+        // Position drops off and comes back within the window
+        tokio::time::advance(Duration::from_secs(1)).await;
+        manager.remove_client(cid("client0"), None).await;
+        assert_eq!(
+            coverage_snapshot(&manager).await["online_stations"],
+            serde_json::json!({})
+        );
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let (_late, mut rx_late) = manager
+            .add_client(
+                client_info_without_position("late"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        tokio::time::advance(Duration::from_secs(4)).await;
+        let _twr = add_twr().await.unwrap();
+
+        assert_eq!(
+            coverage_snapshot(&manager).await["online_stations"],
+            online_stations
+        );
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+        // Clients connected while the station was offline still learn about it coming back
+        let online = |id: &str| StationChange::Online {
+            station_id: station(id),
+            position_id: pos("LOWW_TWR"),
+        };
+        assert_eq!(
+            drain_messages(&mut rx_late).station_changes,
+            vec![online("LOWW_DEL"), online("LOWW_GND"), online("LOWW_TWR")]
+        );
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        manager.flush_debounced_station_changes().await;
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+
+        // Offline changes are sent once the window elapsed without the position coming back
+        manager.remove_client(cid("client0"), None).await;
+        tokio::time::advance(Duration::from_secs(9)).await;
+        manager.flush_debounced_station_changes().await;
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        manager.flush_debounced_station_changes().await;
+        let offline = |id: &str| StationChange::Offline {
+            station_id: station(id),
+            reason: None,
+        };
+        assert_eq!(
+            drain_messages(&mut rx_observer).station_changes,
+            vec![
+                offline("LOWW_DEL"),
+                offline("LOWW_GND"),
+                offline("LOWW_TWR")
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn station_change_coalescing_disabled_sends_changes_right_away() {
+        let (_dir, network) = create_lovv_network();
+        let manager = client_manager(network).with_station_change_coalescing(Duration::ZERO);
+
+        let (_observer, mut rx_observer) = manager
+            .add_client(
+                client_info_without_position("observer"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let _twr = manager
+            .add_client(
+                client_info("client0", "LOWW_TWR", "119.400"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let online = |id: &str| StationChange::Online {
+            station_id: station(id),
+            position_id: pos("LOWW_TWR"),
+        };
+        assert_eq!(
+            drain_messages(&mut rx_observer).station_changes,
+            vec![online("LOWW_DEL"), online("LOWW_GND"), online("LOWW_TWR")]
+        );
+
+        manager.remove_client(cid("client0"), None).await;
+        let offline = |id: &str| StationChange::Offline {
+            station_id: station(id),
+            reason: None,
+        };
+        assert_eq!(
+            drain_messages(&mut rx_observer).station_changes,
+            vec![
+                offline("LOWW_DEL"),
+                offline("LOWW_GND"),
+                offline("LOWW_TWR")
+            ]
+        );
+
+        manager.flush_coalesced_station_changes().await;
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn station_change_coalescing_merges_changes_within_window() {
+        let (_dir, network) = create_lovv_network();
+        let manager =
+            client_manager(network).with_station_change_coalescing(Duration::from_secs(10));
+
+        let (_observer, mut rx_observer) = manager
+            .add_client(
+                client_info_without_position("observer"),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+            .await
+            .unwrap();
+        let add_client = |id: &str, position_id: &str, frequency: &str| {
+            manager.add_client(
+                client_info(id, position_id, frequency),
+                ActiveProfile::Custom,
+                ClientConnectionGuard::default(),
+            )
+        };
+
+        let _ctr = add_client("client0", "LOVV_CTR", "132.600").await.unwrap();
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+        tokio::time::advance(Duration::from_secs(9)).await;
+        manager.flush_coalesced_station_changes().await;
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        manager.flush_coalesced_station_changes().await;
+        let online = |id: &str| StationChange::Online {
+            station_id: station(id),
+            position_id: pos("LOVV_CTR"),
+        };
+        assert_eq!(
+            drain_messages(&mut rx_observer).station_changes,
+            vec![
+                online("LOWW_APP"),
+                online("LOWW_DEL"),
+                online("LOWW_GND"),
+                online("LOWW_TWR")
+            ]
+        );
+
+        // Handoffs within the window collapse into a single one to the final position, while
+        // stations ending up where they started aren't sent at all
+        let _app = add_client("client1", "LOWW_APP", "134.675").await.unwrap();
+        let _twr = add_client("client2", "LOWW_TWR", "119.400").await.unwrap();
+        let _gnd = add_client("client3", "LOWW_GND", "121.600").await.unwrap();
+        manager.remove_client(cid("client3"), None).await;
+        tokio::time::advance(Duration::from_secs(10)).await;
+        manager.flush_coalesced_station_changes().await;
+
+        let handoff = |id: &str, to: &str| StationChange::Handoff {
+            station_id: station(id),
+            from_position_id: pos("LOVV_CTR"),
+            to_position_id: pos(to),
+        };
+        let mut expected = vec![
+            handoff("LOWW_APP", "LOWW_APP"),
+            handoff("LOWW_DEL", "LOWW_TWR"),
+            handoff("LOWW_GND", "LOWW_TWR"),
+            handoff("LOWW_TWR", "LOWW_TWR"),
+        ];
+        expected.sort();
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, expected);
+
+        // Coverage is updated right away, only the changes sent to clients are delayed
+        manager.remove_client(cid("client2"), None).await;
+        assert_eq!(
+            manager.controlling_position(&station("LOWW_TWR")).await,
+            Some(pos("LOWW_APP"))
+        );
+        assert_eq!(drain_messages(&mut rx_observer).station_changes, vec![]);
+    }
+
+    #[test]
+    fn station_hierarchy_matches_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = client_manager(create_lovv_network_with_profiles(dir.path()));
+
+        let node = |id: &str, parent_id: Option<&str>, controlled_by: &[&str]| StationNode {
+            id: station(id),
+            parent_id: parent_id.map(station),
+            controlled_by: controlled_by.iter().map(|id| pos(id)).collect(),
+        };
+        let hierarchy = manager.station_hierarchy(&ActiveProfile::Custom);
+        assert_eq!(
+            hierarchy,
+            vec![
+                node("LOWW_APP", None, &["LOWW_APP", "LOVV_CTR"]),
+                node("LOWW_DEL", Some("LOWW_GND"), &["LOWW_DEL"]),
+                node("LOWW_GND", Some("LOWW_TWR"), &["LOWW_GND"]),
+                node("LOWW_TWR", Some("LOWW_APP"), &["LOWW_TWR"]),
+            ]
+        );
+
+        // Every station of the network is part of the hierarchy exactly as defined
+        let network = manager.network.read();
+        assert_eq!(hierarchy.len(), network.stations().count());
+        for station in network.stations() {
+            let node = hierarchy.iter().find(|n| n.id == station.id).unwrap();
+            assert_eq!(node.parent_id, station.parent_id);
+            assert_eq!(node.controlled_by, station.controlled_by);
+        }
+        drop(network);
+
+        // Scoped to the stations relevant to the profile
+        assert_eq!(
+            manager
+                .station_hierarchy(&ActiveProfile::Specific(ProfileId::from("APP_PROFILE")))
+                .into_iter()
+                .map(|node| node.id)
+                .collect::<Vec<_>>(),
+            vec![station("LOWW_GND"), station("LOWW_TWR")]
+        );
+        assert_eq!(manager.station_hierarchy(&ActiveProfile::None), vec![]);
+    }
+
     /// Base builder for the standard LOVV FIR used by most tests.
     fn lovv_fir() -> TestFirBuilder {
         TestFirBuilder::new("LOVV")
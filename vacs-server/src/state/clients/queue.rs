@@ -0,0 +1,114 @@
+use parking_lot::Mutex;
+use std::sync::{Arc, Weak};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{SendError, TryRecvError, TrySendError};
+use vacs_protocol::ws::server::ServerMessage;
+
+/// Creates the bounded outbound message channel of a client.
+pub fn client_channel(capacity: usize) -> (ClientSender, ClientReceiver) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let rx = Arc::new(Mutex::new(rx));
+    (
+        ClientSender {
+            tx,
+            rx: Arc::downgrade(&rx),
+        },
+        ClientReceiver { rx },
+    )
+}
+
+/// Sending half of a client's outbound message channel.
+///
+/// Only holds a weak reference to the receiving half, so the channel is still closed once the
+/// [`ClientReceiver`] is dropped, while allowing senders to evict the oldest queued message if the
+/// channel is full.
+#[derive(Debug, Clone)]
+pub struct ClientSender {
+    tx: mpsc::Sender<ServerMessage>,
+    rx: Weak<Mutex<mpsc::Receiver<ServerMessage>>>,
+}
+
+impl ClientSender {
+    pub async fn send(&self, message: ServerMessage) -> Result<(), SendError<ServerMessage>> {
+        self.tx.send(message).await
+    }
+
+    pub fn try_send(&self, message: ServerMessage) -> Result<(), TrySendError<ServerMessage>> {
+        self.tx.try_send(message)
+    }
+
+    /// Removes the oldest queued message from the channel. Returns `None` if the channel is empty
+    /// or the receiving half was dropped.
+    pub fn evict_oldest(&self) -> Option<ServerMessage> {
+        self.rx.upgrade()?.lock().try_recv().ok()
+    }
+}
+
+/// Receiving half of a client's outbound message channel.
+#[derive(Debug)]
+pub struct ClientReceiver {
+    rx: Arc<Mutex<mpsc::Receiver<ServerMessage>>>,
+}
+
+impl ClientReceiver {
+    /// Receives the next message, returning `None` once all senders were dropped.
+    ///
+    /// The lock on the underlying receiver is only held while polling, so senders evicting
+    /// messages are never blocked by a pending receive.
+    pub async fn recv(&mut self) -> Option<ServerMessage> {
+        std::future::poll_fn(|cx| self.rx.lock().poll_recv(cx)).await
+    }
+
+    pub fn try_recv(&mut self) -> Result<ServerMessage, TryRecvError> {
+        self.rx.lock().try_recv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use vacs_protocol::vatsim::ClientId;
+    use vacs_protocol::ws::server;
+
+    fn message(n: usize) -> ServerMessage {
+        ServerMessage::ClientDisconnected(server::ClientDisconnected {
+            client_id: ClientId::from(format!("client{n}")),
+        })
+    }
+
+    #[tokio::test]
+    async fn evict_oldest() {
+        let (tx, mut rx) = client_channel(2);
+        tx.try_send(message(1)).unwrap();
+        tx.try_send(message(2)).unwrap();
+        assert!(matches!(
+            tx.try_send(message(3)),
+            Err(TrySendError::Full(_))
+        ));
+
+        assert_eq!(tx.evict_oldest(), Some(message(1)));
+        tx.try_send(message(3)).unwrap();
+
+        assert_eq!(rx.recv().await, Some(message(2)));
+        assert_eq!(rx.try_recv(), Ok(message(3)));
+        assert_eq!(tx.evict_oldest(), None);
+    }
+
+    #[tokio::test]
+    async fn dropping_receiver_closes_channel() {
+        let (tx, rx) = client_channel(2);
+        drop(rx);
+        assert!(tx.send(message(1)).await.is_err());
+        assert_eq!(tx.evict_oldest(), None);
+    }
+
+    #[tokio::test]
+    async fn dropping_senders_ends_receiver() {
+        let (tx, mut rx) = client_channel(2);
+        tx.send(message(1)).await.unwrap();
+        drop(tx);
+        assert!(rx.recv().await.is_some());
+        assert_eq!(rx.recv().await, None);
+    }
+}
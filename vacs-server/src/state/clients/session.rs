@@ -1,6 +1,8 @@
 use crate::config;
-use crate::metrics::guards::ClientConnectionGuard;
+use crate::config::SendQueueOverflowPolicy;
+use crate::metrics::guards::{ClientConnectionGuard, SendQueueStats, SendQueueTracker};
 use crate::state::AppState;
+use crate::state::clients::queue::{ClientReceiver, ClientSender};
 use crate::state::clients::{ClientManagerError, Result};
 use crate::ws::application_message::handle_application_message;
 use crate::ws::message::{MessageResult, receive_message, send_message};
@@ -11,6 +13,8 @@ use parking_lot::Mutex;
 use std::fmt::{Debug, Formatter};
 use std::ops::ControlFlow;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{broadcast, mpsc, oneshot, watch};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
@@ -20,35 +24,140 @@ use vacs_protocol::vatsim::{ClientId, PositionId};
 use vacs_protocol::ws::client::ClientMessage;
 use vacs_protocol::ws::server::{ClientInfo, DisconnectReason, ServerMessage, SessionProfile};
 use vacs_protocol::ws::{server, shared};
-use vacs_vatsim::ControllerInfo;
 use vacs_vatsim::coverage::network::Network;
+use vacs_vatsim::{ControllerInfo, frequencies_match};
 
 #[derive(Clone)]
 pub struct ClientSession {
     client_info: ClientInfo,
     active_profile: ActiveProfile<ProfileId>,
-    tx: mpsc::Sender<ServerMessage>,
+    tx: ClientSender,
+    send_queue: SendQueueTracker,
+    overflow_policy: SendQueueOverflowPolicy,
+    max_message_size: usize,
+    /// Connection metadata sent by the client at login, for operators to filter sessions by.
+    tags: Arc<[String]>,
+    /// Frequencies monitored in addition to those of the client's position, set by the client at
+    /// runtime when combining positions. Cleared whenever the client's position changes.
+    monitored_frequencies: Vec<String>,
     client_shutdown_tx: watch::Sender<Option<DisconnectReason>>,
     client_connection_guard: Arc<Mutex<ClientConnectionGuard>>,
+    transmit_state: Arc<Mutex<TransmitTracker>>,
+    last_activity: Arc<Mutex<Instant>>,
+    unknown_facility_polls: Arc<Mutex<u32>>,
+    stats: Arc<Mutex<SessionStats>>,
+    /// Whether station changes for the client were dropped, leaving its station list out of date
+    /// until it is resynced with a full station list.
+    station_changes_missed: Arc<AtomicBool>,
+}
+
+/// Lightweight counters tracked over the lifetime of a session, reported on disconnect.
+#[derive(Debug)]
+struct SessionStats {
+    connected_at: Instant,
+    positions: Vec<PositionId>,
+    calls: u32,
+    station_changes: u64,
+}
+
+impl SessionStats {
+    fn new(position_id: Option<&PositionId>) -> Self {
+        Self {
+            connected_at: Instant::now(),
+            positions: position_id.into_iter().cloned().collect(),
+            calls: 0,
+            station_changes: 0,
+        }
+    }
+
+    fn record_position(&mut self, position_id: &PositionId) {
+        if !self.positions.contains(position_id) {
+            self.positions.push(position_id.clone());
+        }
+    }
+}
+
+/// Summary of a client session, emitted when the client disconnects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub client_id: ClientId,
+    pub duration: std::time::Duration,
+    /// All positions held during the session, in the order they were first held.
+    pub positions: Vec<PositionId>,
+    /// Number of calls established with the client as caller or answerer.
+    pub calls: u32,
+    /// Number of station changes sent to the client.
+    pub station_changes: u64,
+}
+
+/// Last transmit state forwarded to other clients, used to debounce transmit indicator updates.
+#[derive(Debug, Default)]
+struct TransmitTracker {
+    transmitting: bool,
+    last_change: Option<Instant>,
+    /// Transmit start held back until the debounce window ends.
+    pending_start: bool,
+}
+
+/// How a transmit state reported by the client is forwarded to other clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransmitUpdate {
+    /// The state is unchanged, or already scheduled to be forwarded, and is dropped.
+    Unchanged,
+    /// The state changed and is forwarded right away.
+    Forward,
+    /// The state is forwarded once the debounce window ends at the given instant, see
+    /// [`ClientSession::flush_transmit_state`].
+    Deferred(Instant),
 }
 
 impl ClientSession {
     pub fn new(
         client_info: ClientInfo,
         active_profile: ActiveProfile<ProfileId>,
-        tx: mpsc::Sender<ServerMessage>,
+        tx: ClientSender,
         client_connection_guard: ClientConnectionGuard,
     ) -> Self {
         let (client_shutdown_tx, _) = watch::channel(None);
+        let stats = SessionStats::new(client_info.position_id.as_ref());
+        let send_queue = client_connection_guard.send_queue();
         Self {
             client_info,
             active_profile,
             tx,
+            send_queue,
+            overflow_policy: SendQueueOverflowPolicy::default(),
+            max_message_size: config::DEFAULT_WEBSOCKET_MAX_MESSAGE_SIZE,
+            tags: Arc::from([]),
+            monitored_frequencies: Vec::new(),
             client_shutdown_tx,
             client_connection_guard: Arc::new(Mutex::new(client_connection_guard)),
+            transmit_state: Arc::new(Mutex::new(TransmitTracker::default())),
+            last_activity: Arc::new(Mutex::new(Instant::now())),
+            unknown_facility_polls: Arc::new(Mutex::new(0)),
+            stats: Arc::new(Mutex::new(stats)),
+            station_changes_missed: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Sets the handling of messages sent while the client's outbound channel is full.
+    pub fn with_overflow_policy(mut self, overflow_policy: SendQueueOverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Sets the maximum size of messages sent to the client.
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Sets the tags the client sent at login.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags.into();
+        self
+    }
+
     #[inline]
     pub fn id(&self) -> &ClientId {
         &self.client_info.id
@@ -69,6 +178,31 @@ impl ClientSession {
         &self.active_profile
     }
 
+    #[inline]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    #[inline]
+    pub fn monitored_frequencies(&self) -> &[String] {
+        &self.monitored_frequencies
+    }
+
+    /// Whether the client monitors `frequency` in addition to those of its position.
+    pub fn monitors(&self, frequency: &str) -> bool {
+        self.monitored_frequencies
+            .iter()
+            .any(|monitored| frequencies_match(monitored, frequency))
+    }
+
+    pub fn set_monitored_frequencies(&mut self, frequencies: Vec<String>) {
+        self.monitored_frequencies = frequencies;
+    }
+
     #[tracing::instrument(level = "trace")]
     pub fn update_client_info(&mut self, controller_info: &ControllerInfo) -> bool {
         let mut changed = false;
@@ -97,6 +231,12 @@ impl ClientSession {
 
     #[inline]
     pub fn set_position_id(&mut self, position_id: Option<PositionId>) {
+        if let Some(position_id) = &position_id {
+            self.stats.lock().record_position(position_id);
+        }
+        if self.client_info.position_id != position_id {
+            self.monitored_frequencies.clear();
+        }
         self.client_info.position_id = position_id;
     }
 
@@ -135,6 +275,153 @@ impl ClientSession {
         }
     }
 
+    /// Explicitly switches the active profile, e.g. if requested by the client. Unlike
+    /// [`ClientSession::update_active_profile`], the change is always reported to the client.
+    #[tracing::instrument(level = "trace")]
+    pub fn set_active_profile(
+        &mut self,
+        profile: ActiveProfile<ProfileId>,
+        network: &Network,
+    ) -> SessionProfile {
+        self.active_profile = match profile {
+            ActiveProfile::Specific(profile_id) if network.get_profile(&profile_id).is_none() => {
+                tracing::warn!(
+                    ?profile_id,
+                    "Active profile does not exist, falling back to None"
+                );
+                ActiveProfile::None
+            }
+            profile => profile,
+        };
+
+        SessionProfile::Changed(match &self.active_profile {
+            ActiveProfile::Specific(profile_id) => network
+                .get_profile(profile_id)
+                .map(|profile| ActiveProfile::Specific(profile.into()))
+                .unwrap_or(ActiveProfile::None),
+            ActiveProfile::Custom => ActiveProfile::Custom,
+            ActiveProfile::None => ActiveProfile::None,
+        })
+    }
+
+    /// Marks the client as active, resetting its idle timer.
+    #[inline]
+    pub fn touch(&self) {
+        *self.last_activity.lock() = Instant::now();
+    }
+
+    /// Time elapsed since the last inbound message or VATSIM presence change of this client.
+    #[inline]
+    pub fn idle_for(&self) -> std::time::Duration {
+        self.last_activity.lock().elapsed()
+    }
+
+    /// Records a VATSIM poll in which this client's facility type was reported as unknown and
+    /// returns the number of consecutive such polls.
+    pub fn record_unknown_facility(&self) -> u32 {
+        let mut polls = self.unknown_facility_polls.lock();
+        *polls = polls.saturating_add(1);
+        *polls
+    }
+
+    /// Resets the consecutive unknown facility counter, e.g. after a known facility was reported.
+    #[inline]
+    pub fn reset_unknown_facility(&self) {
+        *self.unknown_facility_polls.lock() = 0;
+    }
+
+    #[inline]
+    pub fn is_transmitting(&self) -> bool {
+        self.transmit_state.lock().transmitting
+    }
+
+    /// Records a transmit state reported by the client and returns how it is forwarded to other
+    /// clients.
+    ///
+    /// Transmit starts arriving within [`config::CLIENT_TRANSMIT_STATE_DEBOUNCE`] of the previously
+    /// forwarded change are deferred until the window ends, so the latest state is still
+    /// forwarded. Transmit stops are always forwarded while transmitting, so no indicator is left
+    /// stuck, and cancel a deferred start.
+    pub fn update_transmit_state(&self, transmitting: bool) -> TransmitUpdate {
+        let mut state = self.transmit_state.lock();
+        if state.transmitting == transmitting {
+            state.pending_start = false;
+            return TransmitUpdate::Unchanged;
+        }
+
+        let now = Instant::now();
+        if transmitting && let Some(last_change) = state.last_change {
+            let deadline = last_change + config::CLIENT_TRANSMIT_STATE_DEBOUNCE;
+            if now < deadline {
+                if state.pending_start {
+                    return TransmitUpdate::Unchanged;
+                }
+                state.pending_start = true;
+                return TransmitUpdate::Deferred(deadline);
+            }
+        }
+
+        state.transmitting = transmitting;
+        state.last_change = Some(now);
+        state.pending_start = false;
+        TransmitUpdate::Forward
+    }
+
+    /// Applies a transmit start deferred by [`ClientSession::update_transmit_state`], returning
+    /// whether it is still pending and should be forwarded to other clients now.
+    pub fn flush_transmit_state(&self) -> bool {
+        let mut state = self.transmit_state.lock();
+        if !std::mem::take(&mut state.pending_start) {
+            return false;
+        }
+
+        state.transmitting = true;
+        state.last_change = Some(Instant::now());
+        true
+    }
+
+    /// Records an established call the client participated in.
+    #[inline]
+    pub fn record_call(&self) {
+        self.stats.lock().calls += 1;
+    }
+
+    /// Marks the client as having missed station changes, e.g. if sending them failed.
+    pub fn mark_station_changes_missed(&self) {
+        self.station_changes_missed.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the client missed station changes since the last call, resetting the flag.
+    pub fn take_station_changes_missed(&self) -> bool {
+        self.station_changes_missed.swap(false, Ordering::Relaxed)
+    }
+
+    /// Current depth and high-water mark of the client's outbound message channel.
+    #[inline]
+    pub fn send_queue_stats(&self) -> SendQueueStats {
+        self.send_queue.stats()
+    }
+
+    #[inline]
+    pub fn connected_at(&self) -> Instant {
+        self.stats.lock().connected_at
+    }
+
+    pub fn summary(&self) -> SessionSummary {
+        self.summary_at(Instant::now())
+    }
+
+    fn summary_at(&self, now: Instant) -> SessionSummary {
+        let stats = self.stats.lock();
+        SessionSummary {
+            client_id: self.client_info.id.clone(),
+            duration: now.saturating_duration_since(stats.connected_at),
+            positions: stats.positions.clone(),
+            calls: stats.calls,
+            station_changes: stats.station_changes,
+        }
+    }
+
     #[instrument(level = "debug", skip(self))]
     pub fn disconnect(&self, disconnect_reason: Option<DisconnectReason>) {
         tracing::trace!("Disconnecting client");
@@ -150,10 +437,88 @@ impl ClientSession {
     pub async fn send_message(&self, message: impl Into<ServerMessage>) -> Result<()> {
         let message = message.into();
         tracing::span::Span::current().record("message", tracing::field::debug(&message));
-        self.tx
-            .send(message)
-            .await
-            .map_err(|err| ClientManagerError::MessageSendError(err.to_string()))
+        let station_changes = match &message {
+            ServerMessage::StationChanges(changes) => changes.changes.len() as u64,
+            _ => 0,
+        };
+        let station_update = is_station_update(&message);
+        self.send_queue.record_queued();
+        let queued = match self.tx.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(message)) => self.send_overflowing(message).await?,
+            Err(err @ TrySendError::Closed(_)) => {
+                self.send_queue.record_send_failed();
+                return Err(ClientManagerError::MessageSendError(err.to_string()));
+            }
+        };
+        if queued && station_changes > 0 {
+            self.stats.lock().station_changes += station_changes;
+        } else if !queued && station_update {
+            self.mark_station_changes_missed();
+        }
+        Ok(())
+    }
+
+    /// Handles a message that didn't fit into the full outbound channel according to the
+    /// configured [`SendQueueOverflowPolicy`]. Returns whether the message was queued.
+    async fn send_overflowing(&self, message: ServerMessage) -> Result<bool> {
+        if is_critical(&message) {
+            return self.send_waiting(message).await;
+        }
+
+        match self.overflow_policy {
+            SendQueueOverflowPolicy::Wait => self.send_waiting(message).await,
+            SendQueueOverflowPolicy::DropOldest => {
+                self.send_queue.record_overflow(self.overflow_policy);
+                match self.tx.evict_oldest() {
+                    Some(evicted) if is_critical(&evicted) => {
+                        tracing::debug!(
+                            ?evicted,
+                            "Send queue full and oldest message is critical, dropping new message"
+                        );
+                        self.send_queue.record_send_failed();
+                        // Critical messages are never dropped, re-queue it in the freed slot
+                        self.tx
+                            .send(evicted)
+                            .await
+                            .map_err(|err| ClientManagerError::MessageSendError(err.to_string()))?;
+                        Ok(false)
+                    }
+                    Some(evicted) => {
+                        tracing::debug!(?evicted, "Send queue full, dropped oldest message");
+                        if is_station_update(&evicted) {
+                            self.mark_station_changes_missed();
+                        }
+                        self.send_queue.record_dequeued();
+                        self.send_waiting(message).await
+                    }
+                    None => self.send_waiting(message).await,
+                }
+            }
+            SendQueueOverflowPolicy::DropNewestNonCritical => {
+                tracing::debug!("Send queue full, dropping message");
+                self.send_queue.record_overflow(self.overflow_policy);
+                self.send_queue.record_send_failed();
+                Ok(false)
+            }
+            SendQueueOverflowPolicy::Disconnect => {
+                tracing::warn!("Send queue full, disconnecting client");
+                self.send_queue.record_overflow(self.overflow_policy);
+                self.send_queue.record_send_failed();
+                self.disconnect(Some(DisconnectReason::SendQueueOverflow));
+                Err(ClientManagerError::MessageSendError(
+                    "send queue full".to_string(),
+                ))
+            }
+        }
+    }
+
+    async fn send_waiting(&self, message: ServerMessage) -> Result<bool> {
+        self.tx.send(message).await.map_err(|err| {
+            self.send_queue.record_send_failed();
+            ClientManagerError::MessageSendError(err.to_string())
+        })?;
+        Ok(true)
     }
 
     pub async fn send_error(&self, err: impl Into<shared::Error>) {
@@ -171,7 +536,7 @@ impl ClientSession {
         websocket_rx: R,
         websocket_tx: T,
         broadcast_rx: &mut broadcast::Receiver<ServerMessage>,
-        rx: &mut mpsc::Receiver<ServerMessage>,
+        rx: &mut ClientReceiver,
         app_shutdown_rx: &mut watch::Receiver<()>,
     ) {
         tracing::debug!("Starting to handle client interaction");
@@ -194,6 +559,16 @@ impl ClientSession {
         let (ping_handle, mut ping_shutdown_rx) =
             ClientSession::spawn_ping_task(&ws_outbound_tx, pong_update_rx);
 
+        let position_without_coverage = app_state
+            .clients
+            .position_without_coverage(self.client_info.position_id.as_ref());
+        if position_without_coverage {
+            tracing::warn!(
+                position_id = ?self.client_info.position_id,
+                "Client position controls no stations in the loaded dataset"
+            );
+        }
+
         tracing::trace!("Sending initial session info");
         if let Err(err) = send_message(
             &ws_outbound_tx,
@@ -209,7 +584,16 @@ impl ClientSession {
                     ActiveProfile::Custom => SessionProfile::Changed(ActiveProfile::Custom),
                     ActiveProfile::None => SessionProfile::Changed(ActiveProfile::None),
                 },
+                ice_servers: app_state
+                    .clients
+                    .ice_servers(self.client_info.position_id.as_ref()),
+                frequency_mismatch: app_state.clients.frequency_mismatch(&self.client_info),
+                position_without_coverage,
+                available_profiles: app_state
+                    .clients
+                    .available_profiles(self.client_info.position_id.as_ref()),
             },
+            self.max_message_size,
         )
         .await
         {
@@ -218,7 +602,13 @@ impl ClientSession {
 
         tracing::trace!("Sending initial client list");
         let clients = app_state.list_clients(Some(&self.client_info.id)).await;
-        if let Err(err) = send_message(&ws_outbound_tx, server::ClientList { clients }).await {
+        if let Err(err) = send_message(
+            &ws_outbound_tx,
+            server::ClientList { clients },
+            self.max_message_size,
+        )
+        .await
+        {
             tracing::warn!(?err, "Failed to send initial client list");
         }
 
@@ -226,7 +616,13 @@ impl ClientSession {
         let stations = app_state
             .list_stations(&self.active_profile, self.client_info.position_id.as_ref())
             .await;
-        if let Err(err) = send_message(&ws_outbound_tx, server::StationList { stations }).await {
+        if let Err(err) = send_message(
+            &ws_outbound_tx,
+            server::StationList { stations },
+            self.max_message_size,
+        )
+        .await
+        {
             tracing::warn!(?err, "Failed to send initial stations list");
         }
 
@@ -247,6 +643,7 @@ impl ClientSession {
                 msg = ws_inbound_rx.recv() => {
                     match msg {
                         Some(msg) => {
+                            self.touch();
                             match handle_application_message(app_state, self, msg).await {
                                 ControlFlow::Continue(()) => continue,
                                 ControlFlow::Break(()) => {
@@ -265,8 +662,9 @@ impl ClientSession {
                 msg = rx.recv() => {
                     match msg {
                         Some(msg) => {
+                            self.send_queue.record_dequeued();
                             tracing::trace!("Received direct message");
-                            if let Err(err) = send_message(&ws_outbound_tx, msg).await {
+                            if let Err(err) = send_message(&ws_outbound_tx, msg, self.max_message_size).await {
                                 tracing::warn!(?err, "Failed to send direct message");
                             }
                         }
@@ -287,7 +685,7 @@ impl ClientSession {
                                 continue;
                             }
 
-                            if let Err(err) = send_message(&ws_outbound_tx, msg).await {
+                            if let Err(err) = send_message(&ws_outbound_tx, msg, self.max_message_size).await {
                                 tracing::warn!(?err, "Failed to send broadcast message");
                             }
                         }
@@ -507,21 +905,84 @@ impl Drop for TaskDropLogger {
     }
 }
 
+/// Returns whether the message must never be dropped, even if the client can't keep up.
+fn is_critical(message: &ServerMessage) -> bool {
+    matches!(
+        message,
+        ServerMessage::Disconnected(_)
+            | ServerMessage::CallInvite(_)
+            | ServerMessage::CallAccept(_)
+            | ServerMessage::CallEnd(_)
+            | ServerMessage::CallCancelled(_)
+            | ServerMessage::CallError(_)
+            | ServerMessage::BroadcastCall(_)
+            | ServerMessage::BroadcastCallStarted(_)
+            | ServerMessage::WebrtcOffer(_)
+            | ServerMessage::WebrtcAnswer(_)
+            | ServerMessage::WebrtcIceCandidate(_)
+    )
+}
+
+/// Returns whether the message updates the client's station list, so dropping it leaves the
+/// client out of sync with the coverage state.
+fn is_station_update(message: &ServerMessage) -> bool {
+    matches!(
+        message,
+        ServerMessage::StationChanges(_) | ServerMessage::StationList(_)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::clients::queue::client_channel;
     use crate::ws::test_util::{TestSetup, create_client_info};
     use axum::extract::ws;
     use axum::extract::ws::Utf8Bytes;
     use pretty_assertions::{assert_eq, assert_matches};
+    use std::time::Duration;
     use test_log::test;
+    use vacs_protocol::vatsim::{StationChange, StationId};
+    use vacs_protocol::ws::shared::CallId;
+
+    fn overflowing_session(policy: SendQueueOverflowPolicy) -> (ClientSession, ClientReceiver) {
+        let (tx, rx) = client_channel(2);
+        let session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        )
+        .with_overflow_policy(policy);
+        (session, rx)
+    }
+
+    /// Fills the session's channel of capacity 2 with non-critical messages.
+    async fn fill_send_queue(session: &ClientSession) {
+        for id in 1..=2 {
+            session.send_message(client_disconnected(id)).await.unwrap();
+        }
+    }
+
+    fn client_disconnected(id: u8) -> ServerMessage {
+        ServerMessage::ClientDisconnected(server::ClientDisconnected {
+            client_id: ClientId::from(format!("client{id}")),
+        })
+    }
+
+    fn call_end() -> ServerMessage {
+        ServerMessage::CallEnd(shared::CallEnd {
+            call_id: CallId::new(),
+            ending_client_id: ClientId::from("client2"),
+        })
+    }
 
     #[test(tokio::test)]
     async fn new_client_session() {
         let client_info_1 = create_client_info(1);
         let profile_id_1 = ProfileId::from("profile1");
         let active_profile = ActiveProfile::Specific(profile_id_1.clone());
-        let (tx, _rx) = mpsc::channel::<ServerMessage>(10);
+        let (tx, _rx) = client_channel(10);
         let session = ClientSession::new(
             client_info_1.clone(),
             active_profile,
@@ -534,10 +995,137 @@ mod tests {
         assert_matches!(session.active_profile(), ActiveProfile::Specific(profile_id) if *profile_id == profile_id_1);
     }
 
+    #[test(tokio::test)]
+    async fn send_queue_stats_track_queued_messages() {
+        let (tx, rx) = client_channel(10);
+        let session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        );
+
+        for _ in 0..3 {
+            session
+                .send_message(server::ClientList { clients: vec![] })
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            session.send_queue_stats(),
+            SendQueueStats {
+                depth: 3,
+                high_water_mark: 3,
+            }
+        );
+
+        drop(rx);
+        assert!(
+            session
+                .send_message(server::ClientList { clients: vec![] })
+                .await
+                .is_err()
+        );
+        assert_eq!(session.send_queue_stats().depth, 3);
+    }
+
+    #[test(tokio::test)]
+    async fn unknown_facility_polls_shared_between_clones() {
+        let (tx, _rx) = client_channel(10);
+        let session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        );
+        let clone = session.clone();
+
+        assert_eq!(session.record_unknown_facility(), 1);
+        assert_eq!(clone.record_unknown_facility(), 2);
+
+        clone.reset_unknown_facility();
+        assert_eq!(session.record_unknown_facility(), 1);
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn update_transmit_state_debounces() {
+        let (tx, _rx) = client_channel(10);
+        let session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        );
+
+        assert_eq!(
+            session.update_transmit_state(false),
+            TransmitUpdate::Unchanged
+        );
+        assert_eq!(session.update_transmit_state(true), TransmitUpdate::Forward);
+        assert_eq!(
+            session.update_transmit_state(true),
+            TransmitUpdate::Unchanged
+        );
+        assert!(session.is_transmitting());
+
+        // Stops are always forwarded, but a quick restart is deferred until the window ends.
+        let stopped_at = Instant::now();
+        assert_eq!(
+            session.update_transmit_state(false),
+            TransmitUpdate::Forward
+        );
+        assert_eq!(
+            session.update_transmit_state(true),
+            TransmitUpdate::Deferred(stopped_at + config::CLIENT_TRANSMIT_STATE_DEBOUNCE)
+        );
+        assert_eq!(
+            session.update_transmit_state(true),
+            TransmitUpdate::Unchanged
+        );
+        assert!(!session.is_transmitting());
+
+        tokio::time::advance(config::CLIENT_TRANSMIT_STATE_DEBOUNCE).await;
+        assert!(session.flush_transmit_state());
+        assert!(session.is_transmitting());
+        assert!(!session.flush_transmit_state());
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn update_transmit_state_stop_cancels_deferred_start() {
+        let (tx, _rx) = client_channel(10);
+        let session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        );
+
+        assert_eq!(session.update_transmit_state(true), TransmitUpdate::Forward);
+        assert_eq!(
+            session.update_transmit_state(false),
+            TransmitUpdate::Forward
+        );
+        assert_matches!(
+            session.update_transmit_state(true),
+            TransmitUpdate::Deferred(_)
+        );
+        assert_eq!(
+            session.update_transmit_state(false),
+            TransmitUpdate::Unchanged
+        );
+
+        tokio::time::advance(config::CLIENT_TRANSMIT_STATE_DEBOUNCE).await;
+        assert!(!session.flush_transmit_state());
+        assert!(!session.is_transmitting());
+
+        // Once the window passed, starts are forwarded right away again.
+        assert_eq!(session.update_transmit_state(true), TransmitUpdate::Forward);
+    }
+
     #[test(tokio::test)]
     async fn send_message() {
         let client_info_1 = create_client_info(1);
-        let (tx, mut rx) = mpsc::channel(10);
+        let (tx, mut rx) = client_channel(10);
         let session = ClientSession::new(
             client_info_1,
             ActiveProfile::None,
@@ -556,10 +1144,146 @@ mod tests {
         assert_eq!(received, message);
     }
 
+    #[test(tokio::test)]
+    async fn session_summary() {
+        let (tx, _rx) = client_channel(10);
+        let mut session = ClientSession::new(
+            create_client_info(1),
+            ActiveProfile::None,
+            tx,
+            ClientConnectionGuard::default(),
+        );
+
+        session.set_position_id(Some(PositionId::from("LOVV_CTR")));
+        session.set_position_id(None);
+        session.set_position_id(Some(PositionId::from("LOVV_CTR")));
+        session.record_call();
+        session.record_call();
+        session
+            .send_message(server::StationChanges {
+                changes: vec![
+                    StationChange::Online {
+                        station_id: StationId::from("LOWW_APP"),
+                        position_id: PositionId::from("LOVV_CTR"),
+                    },
+                    StationChange::Offline {
+                        station_id: StationId::from("LOWW_TWR"),
+                        reason: None,
+                    },
+                ],
+            })
+            .await
+            .unwrap();
+        session
+            .send_message(server::ClientList { clients: vec![] })
+            .await
+            .unwrap();
+
+        let connected_at = session.stats.lock().connected_at;
+        let summary = session.summary_at(connected_at + std::time::Duration::from_secs(90));
+        assert_eq!(
+            summary,
+            SessionSummary {
+                client_id: ClientId::from("client1"),
+                duration: std::time::Duration::from_secs(90),
+                positions: vec![PositionId::from("position1"), PositionId::from("LOVV_CTR"),],
+                calls: 2,
+                station_changes: 2,
+            }
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn send_queue_overflow_wait() {
+        let (session, mut rx) = overflowing_session(SendQueueOverflowPolicy::Wait);
+        fill_send_queue(&session).await;
+
+        let mut send = tokio::spawn({
+            let session = session.clone();
+            async move { session.send_message(client_disconnected(3)).await }
+        });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut send)
+                .await
+                .is_err(),
+            "Send should wait for room in the channel"
+        );
+
+        assert_eq!(rx.recv().await, Some(client_disconnected(1)));
+        send.await.unwrap().unwrap();
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(2)));
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(3)));
+    }
+
+    #[test(tokio::test)]
+    async fn send_queue_overflow_drop_oldest() {
+        let (session, mut rx) = overflowing_session(SendQueueOverflowPolicy::DropOldest);
+        fill_send_queue(&session).await;
+
+        session.send_message(client_disconnected(3)).await.unwrap();
+        assert_eq!(session.send_queue_stats().depth, 2);
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(2)));
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(3)));
+        assert!(rx.try_recv().is_err());
+
+        // Critical messages are never evicted, the new message is dropped instead
+        let call_end = call_end();
+        session.send_message(call_end.clone()).await.unwrap();
+        session.send_message(client_disconnected(4)).await.unwrap();
+        session.send_message(client_disconnected(5)).await.unwrap();
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(4)));
+        assert_eq!(rx.try_recv(), Ok(call_end));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test(tokio::test)]
+    async fn send_queue_overflow_drop_newest_non_critical() {
+        let (session, mut rx) = overflowing_session(SendQueueOverflowPolicy::DropNewestNonCritical);
+        fill_send_queue(&session).await;
+
+        session.send_message(client_disconnected(3)).await.unwrap();
+        assert_eq!(session.send_queue_stats().depth, 2);
+
+        // Critical messages wait for room in the channel instead
+        let call_end = call_end();
+        let send = tokio::spawn({
+            let session = session.clone();
+            let call_end = call_end.clone();
+            async move { session.send_message(call_end).await }
+        });
+        assert_eq!(rx.recv().await, Some(client_disconnected(1)));
+        send.await.unwrap().unwrap();
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(2)));
+        assert_eq!(rx.try_recv(), Ok(call_end));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test(tokio::test)]
+    async fn send_queue_overflow_disconnect() {
+        let (session, mut rx) = overflowing_session(SendQueueOverflowPolicy::Disconnect);
+        let shutdown_rx = session.client_shutdown_tx.subscribe();
+        fill_send_queue(&session).await;
+
+        assert!(
+            session
+                .send_message(client_disconnected(3))
+                .await
+                .is_err_and(|err| err.to_string().contains("send queue full"))
+        );
+        assert_eq!(
+            *shutdown_rx.borrow(),
+            Some(DisconnectReason::SendQueueOverflow)
+        );
+        assert_eq!(session.send_queue_stats().depth, 2);
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(1)));
+        assert_eq!(rx.try_recv(), Ok(client_disconnected(2)));
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test(tokio::test)]
     async fn send_message_error() {
         let client_info_1 = create_client_info(1);
-        let (tx, _) = mpsc::channel(10);
+        let (tx, _) = client_channel(10);
         let session = ClientSession::new(
             client_info_1,
             ActiveProfile::None,
@@ -0,0 +1,152 @@
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use vacs_protocol::ws::server::DatasetInfo;
+use vacs_vatsim::coverage::simulate::SimulationEvent;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioStep {
+    /// Milliseconds since the first event of the scenario.
+    pub offset_ms: u64,
+    /// Event in the format of the coverage simulation, so steps can be replayed via
+    /// [`vacs_vatsim::coverage::simulate::simulate`].
+    #[serde(flatten)]
+    pub event: SimulationEvent,
+}
+
+/// Snapshot of the recent coverage events, allowing a production situation to be replayed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageScenario {
+    pub dataset: DatasetInfo,
+    /// Unix timestamp (in seconds) of when the scenario was exported.
+    pub captured_at: u64,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// Bounded history of the most recent coverage-affecting [`SimulationEvent`]s, dropping the
+/// oldest events once full.
+#[derive(Debug)]
+pub struct CoverageHistory {
+    events: Mutex<VecDeque<(Instant, SimulationEvent)>>,
+    capacity: usize,
+}
+
+impl CoverageHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    pub fn record(&self, event: SimulationEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.events.lock();
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back((Instant::now(), event));
+    }
+
+    pub fn scenario(&self, dataset: DatasetInfo) -> CoverageScenario {
+        let events = self.events.lock();
+        let first = events.front().map(|(at, _)| *at);
+        let steps = events
+            .iter()
+            .map(|(at, event)| ScenarioStep {
+                offset_ms: first
+                    .map(|first| at.duration_since(first).as_millis() as u64)
+                    .unwrap_or_default(),
+                event: event.clone(),
+            })
+            .collect();
+
+        CoverageScenario {
+            dataset,
+            captured_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+    use vacs_protocol::vatsim::{ClientId, PositionId};
+    use vacs_vatsim::{ControllerInfo, FacilityType};
+
+    fn disconnect(id: &str) -> SimulationEvent {
+        SimulationEvent::Disconnect {
+            client_id: ClientId::from(id),
+        }
+    }
+
+    fn dataset() -> DatasetInfo {
+        DatasetInfo {
+            version: "test".to_string(),
+            loaded_at: 0,
+        }
+    }
+
+    #[test]
+    fn history_drops_oldest_events() {
+        let history = CoverageHistory::new(2);
+        history.record(disconnect("client1"));
+        history.record(disconnect("client2"));
+        history.record(disconnect("client3"));
+
+        let events: Vec<SimulationEvent> = history
+            .scenario(dataset())
+            .steps
+            .into_iter()
+            .map(|step| step.event)
+            .collect();
+        assert_eq!(events, vec![disconnect("client2"), disconnect("client3")]);
+    }
+
+    #[test]
+    fn scenario_steps_replayable() {
+        let events = vec![
+            SimulationEvent::Connect {
+                client_id: ClientId::from("client1"),
+                position_id: Some(PositionId::from("LOWW_TWR")),
+            },
+            SimulationEvent::DataFeed {
+                controllers: vec![ControllerInfo {
+                    cid: ClientId::from("client2"),
+                    callsign: "LOWW_APP".to_string(),
+                    frequency: "134.675".to_string(),
+                    facility_type: FacilityType::Approach,
+                }],
+            },
+            disconnect("client1"),
+        ];
+        let history = CoverageHistory::new(4);
+        for event in &events {
+            history.record(event.clone());
+        }
+
+        let value = serde_json::to_value(history.scenario(dataset())).unwrap();
+        assert_eq!(value["dataset"]["version"], "test");
+        let steps = value["steps"].as_array().unwrap();
+        assert_eq!(steps[0]["type"], "connect");
+        assert_eq!(steps[0]["offsetMs"], 0);
+        assert_eq!(steps[1]["controllers"][0]["callsign"], "LOWW_APP");
+
+        let replayed: Vec<SimulationEvent> = steps
+            .iter()
+            .map(|step| serde_json::from_value(step.clone()).unwrap())
+            .collect();
+        assert_eq!(replayed, events);
+    }
+}
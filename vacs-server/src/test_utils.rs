@@ -1,7 +1,9 @@
+mod datafeed_replay;
 mod test_app;
 mod test_client;
 mod ws;
 
+pub use datafeed_replay::*;
 pub use test_app::*;
 pub use test_client::*;
 pub use ws::*;
@@ -0,0 +1,68 @@
+use crate::state::clients::ClientManager;
+use std::collections::{HashMap, HashSet};
+use vacs_protocol::vatsim::ClientId;
+use vacs_protocol::ws::server::DisconnectReason;
+use vacs_vatsim::ControllerInfo;
+use vacs_vatsim::coverage::simulate;
+use vacs_vatsim::data_feed::replay::DataFeedSnapshot;
+
+/// Summary of a recorded data feed replay.
+#[derive(Debug, Default)]
+pub struct DataFeedReplayReport {
+    pub snapshots: usize,
+    pub disconnected_clients: Vec<(ClientId, DisconnectReason)>,
+}
+
+/// Replays recorded data feed snapshots through [`ClientManager::sync_vatsim_state_deferred`] in
+/// order, mirroring the periodic controller update task. If `time_scale` is set, the original
+/// cadence between snapshots is reproduced, scaled by the given factor (e.g. `0.1` replays ten
+/// times faster). Clients disconnected by the sync are removed from the manager, with the station
+/// changes of each snapshot broadcast as a single batch.
+pub async fn replay_data_feed(
+    clients: &ClientManager,
+    snapshots: &[DataFeedSnapshot],
+    time_scale: Option<f64>,
+    require_active_connection: bool,
+) -> DataFeedReplayReport {
+    let mut report = DataFeedReplayReport::default();
+    let mut pending_disconnect = HashSet::new();
+    let mut previous: Option<&DataFeedSnapshot> = None;
+
+    for snapshot in snapshots {
+        if let (Some(time_scale), Some(previous)) = (time_scale, previous) {
+            tokio::time::sleep(snapshot.delay_since(previous, time_scale)).await;
+        }
+
+        let controllers: HashMap<ClientId, ControllerInfo> = vacs_vatsim::dedupe_controllers(
+            snapshot
+                .controllers
+                .iter()
+                .filter(|c| !c.callsign.ends_with("_SUP"))
+                .cloned(),
+        );
+
+        let (disconnected, mut changes) = clients
+            .sync_vatsim_state_deferred(
+                &controllers,
+                &mut pending_disconnect,
+                require_active_connection,
+            )
+            .await;
+        for (cid, reason) in &disconnected {
+            changes.extend(
+                clients
+                    .remove_client_deferred(cid.clone(), Some(reason.clone()))
+                    .await,
+            );
+        }
+        clients
+            .broadcast_station_changes(&simulate::coalesce_station_changes(changes))
+            .await;
+
+        report.snapshots += 1;
+        report.disconnected_clients.extend(disconnected);
+        previous = Some(snapshot);
+    }
+
+    report
+}
@@ -1,13 +1,13 @@
 use crate::auth::layer::setup_mock_auth_layer;
 use crate::config::{AppConfig, AuthConfig, VatsimConfig};
 use crate::ice::provider::stun::StunOnlyProvider;
+use crate::listener;
 use crate::ratelimit::RateLimiters;
 use crate::release::UpdateChecker;
 use crate::routes::create_app;
 use crate::state::AppState;
 use crate::store::Store;
 use crate::store::memory::MemoryStore;
-use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
@@ -29,7 +29,18 @@ impl TestApp {
     }
 
     pub async fn new_with_network(network: Network) -> Self {
-        let config = AppConfig {
+        Self::new_with_network_and_config(network, |_| {}).await
+    }
+
+    pub async fn new_with_config(configure: impl FnOnce(&mut AppConfig)) -> Self {
+        Self::new_with_network_and_config(Network::default(), configure).await
+    }
+
+    pub async fn new_with_network_and_config(
+        network: Network,
+        configure: impl FnOnce(&mut AppConfig),
+    ) -> Self {
+        let mut config = AppConfig {
             auth: AuthConfig {
                 login_flow_timeout_millis: 100,
                 ..Default::default()
@@ -37,14 +48,30 @@ impl TestApp {
             vatsim: VatsimConfig {
                 user_service: Default::default(),
                 require_active_connection: false,
-                slurper_base_url: Default::default(),
+                slurper_base_url: "http://localhost:12345".to_string(),
                 controller_update_interval: Default::default(),
                 data_feed_url: Default::default(),
                 data_feed_timeout: Default::default(),
                 coverage_dir: Default::default(),
+                unknown_facility_tolerance: 0,
+                max_pending_disconnects: usize::MAX,
+                frequency_only_matching: false,
+                ambiguous_position_selection: true,
+                warn_position_without_coverage: false,
+                show_vatsim_only_stations: false,
+                retain_coverage_without_clients: false,
+                station_flap_debounce: Default::default(),
+                station_change_coalescing: Default::default(),
+                last_controller_retention: Default::default(),
+                relief_patterns: Default::default(),
+                allowed_facility_types: VatsimConfig::default_allowed_facility_types(),
+                default_profiles: Default::default(),
+                require_profiles: false,
+                max_firs: None,
             },
             ..Default::default()
         };
+        configure(&mut config);
 
         let mock_data_feed = Arc::new(MockDataFeed::default());
 
@@ -53,29 +80,27 @@ impl TestApp {
             config.clone(),
             UpdateChecker::default(),
             Store::Memory(MemoryStore::default()),
-            SlurperClient::new("http://localhost:12345").unwrap(),
+            SlurperClient::new(&config.vatsim.slurper_base_url).unwrap(),
             mock_data_feed.clone(),
             network,
             RateLimiters::default(),
-            shutdown_rx,
+            shutdown_rx.clone(),
             Arc::new(StunOnlyProvider::default()),
             None,
         ));
+        state.mark_ready();
 
         let auth_layer = setup_mock_auth_layer(&config).await.unwrap();
-        let app = create_app(auth_layer, None, config.server.client_ip_source.clone());
+        let app = create_app(auth_layer, None, config.server.client_ip_resolver());
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
 
-        let state_clone = state.clone();
+        let app = app.with_state(state.clone());
+        let header_read_timeout = config.websocket.upgrade_timeout;
         let handle = tokio::spawn(async move {
-            axum::serve(
-                listener,
-                app.with_state(state_clone)
-                    .into_make_service_with_connect_info::<SocketAddr>(),
-            )
-            .await
-            .unwrap();
+            listener::serve_all(vec![listener], app, header_read_timeout, shutdown_rx)
+                .await
+                .unwrap();
         });
 
         Self {
@@ -66,10 +66,13 @@ impl TestClient {
         FS: FnOnce(&[StationInfo]) -> anyhow::Result<()> + Copy,
     {
         let login_msg = ClientMessage::Login(vacs_protocol::ws::client::Login {
-            token: self.token.to_string(),
+            token: self.token.as_str().into(),
             protocol_version: VACS_PROTOCOL_VERSION.to_string(),
             custom_profile: false,
             position_id: None,
+            codecs: Vec::new(),
+            client_version: None,
+            tags: Vec::new(),
         });
         self.send_and_expect_with_timeout(login_msg, Duration::from_millis(100), |msg| match msg {
             ServerMessage::SessionInfo(server::SessionInfo { client, .. }) => {
@@ -1,17 +1,21 @@
 use crate::metrics::ErrorMetrics;
 use crate::state::AppState;
 use crate::state::calls::{CallTerminationOutcome, StartCallError};
-use crate::state::clients::session::ClientSession;
+use crate::state::clients::ClientManagerError;
+use crate::state::clients::session::{ClientSession, TransmitUpdate};
 use std::collections::HashSet;
 use std::ops::ControlFlow;
 use std::sync::Arc;
-use vacs_protocol::ws::client::{CallReject, ClientMessage};
+use tracing::Instrument;
+use vacs_protocol::profile::{ActiveProfile, ProfileId};
+use vacs_protocol::vatsim::StationId;
+use vacs_protocol::ws::client::{CallReject, ClientMessage, SetMonitoredFrequencies, SetProfile};
 use vacs_protocol::ws::server::CallCancelReason;
 use vacs_protocol::ws::shared::{
-    CallAccept, CallEnd, CallError, CallErrorReason, CallId, CallInvite, CallTarget, ErrorReason,
-    WebrtcAnswer, WebrtcIceCandidate, WebrtcOffer,
+    BroadcastCall, CallAccept, CallEnd, CallError, CallErrorReason, CallId, CallInvite, CallTarget,
+    ErrorReason, WebrtcAnswer, WebrtcIceCandidate, WebrtcOffer,
 };
-use vacs_protocol::ws::{server, shared};
+use vacs_protocol::ws::{client, server, shared};
 
 #[tracing::instrument(level = "trace", skip(state))]
 pub async fn handle_application_message(
@@ -31,14 +35,57 @@ pub async fn handle_application_message(
         }
         ClientMessage::ListStations => {
             tracing::trace!("Returning list of stations");
+            let profile = active_profile(state, client).await;
             let stations = state
                 .clients
-                .list_stations(client.active_profile(), client.position_id())
+                .list_stations(&profile, client.position_id())
                 .await;
             if let Err(err) = client.send_message(server::StationList { stations }).await {
                 tracing::warn!(?err, "Failed to send station list");
             }
         }
+        ClientMessage::GetStationHierarchy => {
+            tracing::trace!("Returning station hierarchy");
+            let profile = active_profile(state, client).await;
+            let stations = state.clients.station_hierarchy(&profile);
+            if let Err(err) = client
+                .send_message(server::StationHierarchy { stations })
+                .await
+            {
+                tracing::warn!(?err, "Failed to send station hierarchy");
+            }
+        }
+        ClientMessage::GetStationCoverage(client::GetStationCoverage { station_id }) => {
+            tracing::trace!(?station_id, "Returning station coverage");
+            let coverage = state.clients.station_coverage(&station_id).await;
+            if let Err(err) = client.send_message(coverage).await {
+                tracing::warn!(?err, "Failed to send station coverage");
+            }
+        }
+        ClientMessage::ListActiveCalls => {
+            tracing::trace!("Returning list of active calls");
+            let profile = active_profile(state, client).await;
+            let calls = state.list_active_calls(&profile).await;
+            if let Err(err) = client.send_message(server::ActiveCallList { calls }).await {
+                tracing::warn!(?err, "Failed to send active call list");
+            }
+        }
+        ClientMessage::GetDatasetInfo => {
+            tracing::trace!("Returning dataset info");
+            let dataset_info = state.clients.dataset_info();
+            if let Err(err) = client.send_message(dataset_info).await {
+                tracing::warn!(?err, "Failed to send dataset info");
+            }
+        }
+        ClientMessage::TransmitState(transmit_state) => {
+            handle_transmit_state(state, client, transmit_state).await;
+        }
+        ClientMessage::SetProfile(set_profile) => {
+            handle_set_profile(state, client, set_profile).await;
+        }
+        ClientMessage::SetMonitoredFrequencies(set_monitored_frequencies) => {
+            handle_set_monitored_frequencies(state, client, set_monitored_frequencies).await;
+        }
         ClientMessage::CallInvite(call_invite) => {
             handle_call_invite(state, client, call_invite).await;
         }
@@ -54,6 +101,9 @@ pub async fn handle_application_message(
         ClientMessage::CallError(call_error) => {
             handle_call_error(state, client, call_error).await;
         }
+        ClientMessage::BroadcastCall(broadcast) => {
+            handle_broadcast_call(state, client, broadcast).await;
+        }
         ClientMessage::WebrtcOffer(webrtc_offer) => {
             handle_webrtc_offer(state, client, webrtc_offer).await;
         }
@@ -69,8 +119,185 @@ pub async fn handle_application_message(
     ControlFlow::Continue(())
 }
 
+/// Returns the client's current active profile. Profile switches are applied to the session held
+/// by the client manager, so it takes precedence over the profile of the local session copy.
+async fn active_profile(state: &AppState, client: &ClientSession) -> ActiveProfile<ProfileId> {
+    state
+        .clients
+        .get_client(client.id())
+        .await
+        .map(|session| session.active_profile().clone())
+        .unwrap_or_else(|| client.active_profile().clone())
+}
+
 #[tracing::instrument(level = "trace", skip(state, client))]
-async fn handle_call_invite(state: &AppState, client: &ClientSession, invite: CallInvite) {
+async fn handle_set_profile(state: &AppState, client: &ClientSession, set_profile: SetProfile) {
+    tracing::trace!("Handling set profile");
+
+    let profile = set_profile.profile;
+    let session_info = match state
+        .clients
+        .set_active_profile(client.id(), profile.clone())
+        .await
+    {
+        Ok(session_info) => session_info,
+        Err(ClientManagerError::ProfileNotAvailable(_)) => {
+            tracing::debug!(?profile, "Profile not available, rejecting switch");
+            client
+                .send_error(shared::Error::new(ErrorReason::ProfileNotAvailable))
+                .await;
+            return;
+        }
+        Err(err) => {
+            tracing::warn!(?err, "Failed to switch active profile");
+            client
+                .send_error(shared::Error::new(ErrorReason::Internal(err.to_string())))
+                .await;
+            return;
+        }
+    };
+
+    if let Err(err) = client.send_message(session_info).await {
+        tracing::warn!(?err, "Failed to send updated session info");
+        return;
+    }
+
+    let stations = state
+        .clients
+        .list_stations(&profile, client.position_id())
+        .await;
+    if let Err(err) = client.send_message(server::StationList { stations }).await {
+        tracing::warn!(?err, "Failed to send station list");
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(state, client))]
+async fn handle_set_monitored_frequencies(
+    state: &AppState,
+    client: &ClientSession,
+    set_monitored_frequencies: SetMonitoredFrequencies,
+) {
+    tracing::trace!("Handling set monitored frequencies");
+
+    let frequencies = match state
+        .clients
+        .set_monitored_frequencies(client.id(), set_monitored_frequencies.frequencies)
+        .await
+    {
+        Ok(frequencies) => frequencies,
+        Err(ClientManagerError::InvalidFrequencies(reason)) => {
+            tracing::debug!(?reason, "Invalid monitored frequencies, rejecting update");
+            client
+                .send_error(shared::Error::new(ErrorReason::InvalidFrequencies(reason)))
+                .await;
+            return;
+        }
+        Err(err) => {
+            tracing::warn!(?err, "Failed to update monitored frequencies");
+            client
+                .send_error(shared::Error::new(ErrorReason::Internal(err.to_string())))
+                .await;
+            return;
+        }
+    };
+
+    if let Err(err) = client
+        .send_message(server::MonitoredFrequencies { frequencies })
+        .await
+    {
+        tracing::warn!(?err, "Failed to send monitored frequencies");
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(state, client))]
+async fn handle_transmit_state(
+    state: &Arc<AppState>,
+    client: &ClientSession,
+    transmit_state: client::TransmitState,
+) {
+    tracing::trace!("Handling transmit state");
+
+    let Some(position_id) = client.position_id() else {
+        tracing::trace!("Client has no position, ignoring transmit state");
+        return;
+    };
+
+    let station_ids = match transmit_state.station_id {
+        Some(station_id) => {
+            let controlling_position = state.clients.controlling_position(&station_id).await;
+            if controlling_position.as_ref() != Some(position_id) {
+                tracing::debug!(
+                    ?station_id,
+                    "Station not controlled by client position, ignoring transmit state"
+                );
+                return;
+            }
+            vec![station_id]
+        }
+        None => state.clients.stations_for_position(position_id).await,
+    };
+
+    if station_ids.is_empty() {
+        tracing::trace!("Client position does not control any stations, ignoring transmit state");
+        return;
+    }
+
+    match client.update_transmit_state(transmit_state.transmitting) {
+        TransmitUpdate::Unchanged => {
+            tracing::trace!("Transmit state unchanged, skipping broadcast");
+        }
+        TransmitUpdate::Forward => {
+            broadcast_transmit_state(state, client, station_ids, transmit_state.transmitting).await;
+        }
+        TransmitUpdate::Deferred(deadline) => {
+            tracing::trace!("Transmit start debounced, deferring broadcast");
+            let state = state.clone();
+            let client = client.clone();
+            tokio::spawn(
+                async move {
+                    tokio::time::sleep_until(deadline).await;
+                    if !state.clients.is_client_connected(client.id()).await {
+                        tracing::trace!("Client disconnected, dropping deferred transmit state");
+                        return;
+                    }
+                    if client.flush_transmit_state() {
+                        broadcast_transmit_state(&state, &client, station_ids, true).await;
+                    }
+                }
+                .in_current_span(),
+            );
+        }
+    }
+}
+
+async fn broadcast_transmit_state(
+    state: &AppState,
+    client: &ClientSession,
+    station_ids: Vec<StationId>,
+    transmitting: bool,
+) {
+    for station_id in station_ids {
+        let message = server::TransmitState {
+            client_id: client.id().clone(),
+            station_id: station_id.clone(),
+            transmitting,
+        };
+
+        for peer_id in state.clients.clients_for_station(&station_id).await {
+            if peer_id == *client.id() {
+                continue;
+            }
+
+            tracing::trace!(?peer_id, ?station_id, "Sending transmit state to peer");
+            if let Err(err) = state.send_message(&peer_id, message.clone()).await {
+                tracing::warn!(?err, ?peer_id, "Failed to send transmit state to peer");
+            }
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(state, client))]
+async fn handle_call_invite(state: &Arc<AppState>, client: &ClientSession, invite: CallInvite) {
     tracing::trace!("Handling call invite");
     let caller_id = client.id();
     let call_id = &invite.call_id;
@@ -100,6 +327,17 @@ async fn handle_call_invite(state: &AppState, client: &ClientSession, invite: Ca
         return;
     }
 
+    if state
+        .clients
+        .get_position(client.position_id())
+        .is_some_and(|position| !position.transmit)
+    {
+        tracing::debug!("Caller position is receive-only, rejecting call invite");
+        // TODO error metrics
+        send_call_error(client, call_id, CallErrorReason::ReceiveOnly, None).await;
+        return;
+    }
+
     let target_clients = match &invite.target {
         CallTarget::Client(client_id) => {
             if state.clients.is_client_connected(client_id).await {
@@ -109,7 +347,24 @@ async fn handle_call_invite(state: &AppState, client: &ClientSession, invite: Ca
             }
         }
         CallTarget::Position(position_id) => state.clients.clients_for_position(position_id).await,
-        CallTarget::Station(station_id) => state.clients.clients_for_station(station_id).await,
+        CallTarget::Station(station_id) if state.is_station_closed(station_id) => {
+            tracing::debug!(?station_id, "Target station is temporarily closed");
+            HashSet::new()
+        }
+        CallTarget::Station(station_id) => match state.clients.station_group_members(station_id) {
+            Some(members) => {
+                let mut clients = HashSet::new();
+                for member_id in &members {
+                    if state.is_station_closed(member_id) {
+                        tracing::debug!(?member_id, "Skipping temporarily closed group member");
+                        continue;
+                    }
+                    clients.extend(state.clients.clients_for_station(member_id).await);
+                }
+                clients
+            }
+            None => state.clients.clients_for_station(station_id).await,
+        },
     }
     .into_iter()
     .filter(|client_id| client_id != client.id())
@@ -126,7 +381,7 @@ async fn handle_call_invite(state: &AppState, client: &ClientSession, invite: Ca
         .calls
         .start_call_attempt(call_id, client.id(), &invite.target, &target_clients)
     {
-        Ok(_) => {}
+        Ok(_) => spawn_answer_timeout(state, *call_id),
         Err(StartCallError::CallerBusy) => {
             tracing::debug!("Client already has an outgoing call, rejecting call invite");
             // TODO error metrics
@@ -151,6 +406,156 @@ async fn handle_call_invite(state: &AppState, client: &ClientSession, invite: Ca
     }
 }
 
+/// Cancels the call for the caller and all notified clients if it's still ringing once the
+/// configured answer timeout expired.
+fn spawn_answer_timeout(state: &Arc<AppState>, call_id: CallId) {
+    let timeout = state.config.calls.answer_timeout;
+    if timeout.is_zero() {
+        return;
+    }
+
+    let state = Arc::clone(state);
+    tokio::spawn(
+        async move {
+            tokio::time::sleep(timeout).await;
+
+            let Some(ringing) = state.calls.expire_ringing_call(&call_id) else {
+                return;
+            };
+
+            tracing::debug!(?timeout, "Call was not answered in time, cancelling");
+            let cancelled = server::CallCancelled::new(call_id, CallCancelReason::Timeout);
+            for client_id in std::iter::once(&ringing.caller_id).chain(&ringing.notified_clients) {
+                tracing::trace!(?client_id, "Sending call timeout to client");
+                if let Err(err) = state.send_message(client_id, cancelled.clone()).await {
+                    // TODO error metrics
+                    tracing::warn!(?err, ?client_id, "Failed to send call timeout to client");
+                }
+            }
+        }
+        .in_current_span(),
+    );
+}
+
+#[tracing::instrument(level = "trace", skip(state, client))]
+async fn handle_broadcast_call(state: &AppState, client: &ClientSession, broadcast: BroadcastCall) {
+    tracing::trace!("Handling broadcast call");
+    let broadcaster_id = client.id();
+    let call_id = &broadcast.call_id;
+
+    if let Err(until) = state.rate_limiters().check_call_invite(broadcaster_id) {
+        tracing::debug!(?until, "Rate limit exceeded, rejecting broadcast call");
+        let reason = ErrorReason::RateLimited {
+            retry_after_secs: until.as_secs(),
+        };
+        ErrorMetrics::error(&reason);
+        client
+            .send_error(shared::Error::from(reason).with_call_id(broadcast.call_id))
+            .await;
+        return;
+    }
+
+    if broadcast.source.client_id != *broadcaster_id {
+        tracing::debug!("Source client ID mismatch, rejecting broadcast call");
+        // TODO error metrics
+        send_call_error(
+            client,
+            call_id,
+            CallErrorReason::Other,
+            Some("Source client ID mismatch"),
+        )
+        .await;
+        return;
+    }
+
+    if !state
+        .config
+        .admin
+        .broadcast_client_ids
+        .contains(broadcaster_id)
+    {
+        tracing::debug!("Client not authorized to broadcast, rejecting broadcast call");
+        // TODO error metrics
+        send_call_error(client, call_id, CallErrorReason::Unauthorized, None).await;
+        return;
+    }
+
+    if state
+        .clients
+        .get_position(client.position_id())
+        .is_some_and(|position| !position.transmit)
+    {
+        tracing::debug!("Broadcaster position is receive-only, rejecting broadcast call");
+        // TODO error metrics
+        send_call_error(client, call_id, CallErrorReason::ReceiveOnly, None).await;
+        return;
+    }
+
+    let mut recipients = HashSet::new();
+    for station_id in &broadcast.station_ids {
+        if state.is_station_closed(station_id) {
+            tracing::debug!(?station_id, "Skipping temporarily closed station");
+            continue;
+        }
+        recipients.extend(state.clients.clients_for_station(station_id).await);
+    }
+    recipients.remove(broadcaster_id);
+
+    if recipients.is_empty() {
+        tracing::trace!("No clients found for broadcast call, returning target not found error");
+        // TODO error metrics
+        send_call_error(client, call_id, CallErrorReason::TargetNotFound, None).await;
+        return;
+    }
+
+    match state
+        .calls
+        .start_broadcast(call_id, broadcaster_id, &recipients)
+    {
+        Ok(_) => {}
+        Err(StartCallError::CallerBusy) => {
+            tracing::debug!("Client already has an active broadcast, rejecting broadcast call");
+            // TODO error metrics
+            send_call_error(client, call_id, CallErrorReason::CallActive, None).await;
+            return;
+        }
+    }
+
+    let mut recipient_ids = Vec::with_capacity(recipients.len());
+    for recipient_id in recipients {
+        tracing::trace!(?recipient_id, "Sending broadcast call to recipient");
+        if let Err(err) = state.send_message(&recipient_id, broadcast.clone()).await {
+            tracing::warn!(
+                ?err,
+                ?recipient_id,
+                "Failed to send broadcast call to recipient"
+            );
+            // TODO error metrics
+            state.calls.leave_broadcast(call_id, &recipient_id);
+        } else {
+            recipient_ids.push(recipient_id);
+        }
+    }
+
+    if recipient_ids.is_empty() {
+        tracing::trace!("Broadcast call could not be delivered to any recipient");
+        // TODO error metrics
+        send_call_error(client, call_id, CallErrorReason::CallFailure, None).await;
+        return;
+    }
+
+    recipient_ids.sort();
+    if let Err(err) = client
+        .send_message(server::BroadcastCallStarted {
+            call_id: *call_id,
+            recipient_ids,
+        })
+        .await
+    {
+        tracing::warn!(?err, "Failed to send broadcast call started");
+    }
+}
+
 #[tracing::instrument(level = "trace", skip(state, client))]
 async fn handle_call_accept(state: &AppState, client: &ClientSession, accept: CallAccept) {
     tracing::trace!("Handling call acceptance");
@@ -186,6 +591,11 @@ async fn handle_call_accept(state: &AppState, client: &ClientSession, accept: Ca
         return;
     }
 
+    client.record_call();
+    if let Some(caller) = state.get_client(&ringing.caller_id).await {
+        caller.record_call();
+    }
+
     if ringing.notified_clients.len() > 1 {
         let cancelled = server::CallCancelled::new(
             *call_id,
@@ -318,6 +728,31 @@ async fn handle_call_end(state: &AppState, client: &ClientSession, end: CallEnd)
             send_call_error(client, call_id, CallErrorReason::TargetNotFound, None).await;
             return;
         }
+    } else if let Some(broadcast) = state.calls.end_broadcast(call_id, ender_id) {
+        tracing::trace!("Broadcast found, ending");
+        for recipient_id in broadcast.recipients {
+            tracing::trace!(?recipient_id, "Sending call end to broadcast recipient");
+            if let Err(err) = state.send_message(&recipient_id, end.clone()).await {
+                // TODO error metrics
+                tracing::warn!(
+                    ?err,
+                    ?recipient_id,
+                    "Failed to send call end to broadcast recipient"
+                );
+            }
+        }
+    } else if let Some(broadcast) = state.calls.leave_broadcast(call_id, ender_id) {
+        tracing::trace!("Leaving broadcast");
+        let broadcaster_id = &broadcast.broadcaster_id;
+        tracing::trace!(?broadcaster_id, "Sending call end to broadcaster");
+        if let Err(err) = state.send_message(broadcaster_id, end.clone()).await {
+            // TODO error metrics
+            tracing::warn!(
+                ?err,
+                ?broadcaster_id,
+                "Failed to send call end to broadcaster"
+            );
+        }
     } else {
         tracing::trace!("No ringing or active call found, returning call error");
         // TODO error metrics
@@ -388,7 +823,11 @@ async fn handle_webrtc_offer(state: &AppState, client: &ClientSession, offer: We
         return;
     }
 
-    if !state.calls.has_active_call(call_id, client_id) {
+    if !state.calls.has_active_call(call_id, client_id)
+        && !state
+            .calls
+            .has_broadcast_link(call_id, client_id, &offer.to_client_id)
+    {
         tracing::debug!("No active call found for WebRTC offer, returning call error");
         // TODO error metrics
         // TODO other call error reason?
@@ -421,7 +860,11 @@ async fn handle_webrtc_answer(state: &AppState, client: &ClientSession, answer:
         return;
     }
 
-    if !state.calls.has_active_call(call_id, client_id) {
+    if !state.calls.has_active_call(call_id, client_id)
+        && !state
+            .calls
+            .has_broadcast_link(call_id, client_id, &answer.to_client_id)
+    {
         tracing::debug!("No active call found for WebRTC answer, returning call error");
         // TODO error metrics
         // TODO other call error reason?
@@ -461,7 +904,11 @@ async fn handle_webrtc_ice_candidate(
         return;
     }
 
-    if !state.calls.has_active_call(call_id, client_id) {
+    if !state.calls.has_active_call(call_id, client_id)
+        && !state
+            .calls
+            .has_broadcast_link(call_id, client_id, &ice_candidate.to_client_id)
+    {
         tracing::debug!("No active call found for WebRTC ice candidate, returning call error");
         // TODO error metrics
         // TODO other call error reason?
@@ -499,11 +946,74 @@ async fn send_call_error(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::clients::ClientReceiver;
     use crate::ws::test_util::{TestSetup, create_client_info};
     use pretty_assertions::{assert_eq, assert_matches};
     use test_log::test;
-    use vacs_protocol::vatsim::ClientId;
-    use vacs_protocol::ws::server::{self, ServerMessage};
+    use vacs_protocol::vatsim::{ClientId, PositionId, StationId};
+    use vacs_protocol::ws::server::{self, ClientInfo, ServerMessage};
+    use vacs_protocol::ws::shared::CallSource;
+    use vacs_vatsim::coverage::test_support::TestFirBuilder;
+
+    /// Creates a test setup authorizing `client1` to broadcast, with the LOWW_APP and LOWW_TWR
+    /// stations each covered by their own position.
+    async fn broadcast_setup() -> (tempfile::TempDir, TestSetup) {
+        let setup = TestSetup::with_config(|config| {
+            config.admin.broadcast_client_ids = HashSet::from([ClientId::from("client1")]);
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP"])
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir.path());
+        setup.app_state.replace_network(network).await;
+        (dir, setup)
+    }
+
+    fn client_info_at(id: u8, position_id: &str) -> ClientInfo {
+        ClientInfo {
+            position_id: Some(PositionId::from(position_id)),
+            ..create_client_info(id)
+        }
+    }
+
+    fn broadcast_call(station_ids: &[&str]) -> BroadcastCall {
+        BroadcastCall {
+            call_id: CallId::new(),
+            source: CallSource::new(ClientId::from("client1")),
+            station_ids: station_ids.iter().map(|id| StationId::from(*id)).collect(),
+        }
+    }
+
+    fn drain_messages(rx: &mut ClientReceiver) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    fn received_broadcast_calls(rx: &mut ClientReceiver) -> Vec<BroadcastCall> {
+        drain_messages(rx)
+            .into_iter()
+            .filter_map(|message| match message {
+                ServerMessage::BroadcastCall(broadcast) => Some(broadcast),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn received_call_ends(rx: &mut ClientReceiver) -> Vec<CallEnd> {
+        drain_messages(rx)
+            .into_iter()
+            .filter_map(|message| match message {
+                ServerMessage::CallEnd(end) => Some(end),
+                _ => None,
+            })
+            .collect()
+    }
 
     #[test(tokio::test)]
     async fn handle_application_message_list_clients_without_self() {
@@ -545,6 +1055,98 @@ mod tests {
         );
     }
 
+    #[test(tokio::test)]
+    async fn handle_application_message_list_active_calls() {
+        let mut setup = TestSetup::new();
+        setup.register_client(create_client_info(1)).await;
+
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::ListActiveCalls,
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_matches!(
+            message,
+            ServerMessage::ActiveCallList(server::ActiveCallList { calls }) if calls.is_empty()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_get_dataset_info() {
+        let mut setup = TestSetup::new();
+        setup.register_client(create_client_info(1)).await;
+
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::GetDatasetInfo,
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        let expected = setup.app_state.clients.dataset_info();
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_matches!(
+            message,
+            ServerMessage::DatasetInfo(info) if info.version == expected.version
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_get_station_coverage() {
+        let (_dir, mut setup) = broadcast_setup().await;
+        setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        drain_messages(&mut setup.rx);
+
+        let station_id = StationId::from("LOWW_TWR");
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::GetStationCoverage(client::GetStationCoverage {
+                station_id: station_id.clone(),
+            }),
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        let expected = setup.app_state.clients.station_coverage(&station_id).await;
+        assert_eq!(expected.position_id, Some(PositionId::from("LOWW_TWR")));
+        assert!(expected.callable);
+        assert_eq!(
+            setup.rx.recv().await,
+            Some(ServerMessage::StationCoverage(expected))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_get_station_coverage_offline() {
+        let (_dir, mut setup) = broadcast_setup().await;
+        setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        drain_messages(&mut setup.rx);
+
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::GetStationCoverage(client::GetStationCoverage {
+                station_id: StationId::from("LOWW_APP"),
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            setup.rx.recv().await,
+            Some(ServerMessage::StationCoverage(server::StationCoverage {
+                station_id: StationId::from("LOWW_APP"),
+                position_id: None,
+                callable: false,
+            }))
+        );
+    }
+
     #[test(tokio::test)]
     async fn handle_application_message_list_clients() {
         let mut setup = TestSetup::new();
@@ -590,10 +1192,30 @@ mod tests {
                 from_client_id: ClientId::from("client1"),
                 to_client_id: ClientId::from("client2"),
                 sdp: "sdp1".to_string(),
+                audio_codecs: Vec::new(),
+            }),
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_transmit_state_without_stations() {
+        let mut setup = TestSetup::new();
+        setup.register_client(create_client_info(1)).await;
+
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::TransmitState(client::TransmitState {
+                station_id: None,
+                transmitting: true,
             }),
         )
         .await;
         assert_eq!(control_flow, ControlFlow::Continue(()));
+        assert!(!setup.session.is_transmitting());
+        assert!(setup.rx.try_recv().is_err());
     }
 
     #[test(tokio::test)]
@@ -610,4 +1232,585 @@ mod tests {
         .await;
         assert_eq!(control_flow, ControlFlow::Continue(()));
     }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_reaches_covering_clients() {
+        let (_dir, mut setup) = broadcast_setup().await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        let (_, mut client3_rx) = setup.register_client(client_info_at(3, "LOWW_TWR")).await;
+        let (_, mut client4_rx) = setup.register_client(client_info_at(4, "LOWW_APP")).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        assert_eq!(
+            received_broadcast_calls(&mut client2_rx),
+            vec![broadcast.clone()]
+        );
+        assert_eq!(
+            received_broadcast_calls(&mut client3_rx),
+            vec![broadcast.clone()]
+        );
+        assert!(
+            received_broadcast_calls(&mut client4_rx).is_empty(),
+            "Clients not covering a target station must not receive the broadcast"
+        );
+
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_eq!(
+            message,
+            ServerMessage::BroadcastCallStarted(server::BroadcastCallStarted {
+                call_id: broadcast.call_id,
+                recipient_ids: vec![ClientId::from("client2"), ClientId::from("client3")],
+            })
+        );
+        assert!(setup.app_state.calls.has_broadcast_link(
+            &broadcast.call_id,
+            setup.session.id(),
+            &ClientId::from("client2")
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_multiple_stations() {
+        let (_dir, mut setup) = broadcast_setup().await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        let (_, mut client3_rx) = setup.register_client(client_info_at(3, "LOWW_APP")).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR", "LOWW_APP"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+
+        assert_eq!(
+            received_broadcast_calls(&mut client2_rx),
+            vec![broadcast.clone()]
+        );
+        assert_eq!(
+            received_broadcast_calls(&mut client3_rx),
+            vec![broadcast.clone()]
+        );
+        assert_matches!(
+            setup.rx.recv().await,
+            Some(ServerMessage::BroadcastCallStarted(server::BroadcastCallStarted { recipient_ids, .. }))
+                if recipient_ids.len() == 2
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_unauthorized() {
+        let mut setup = TestSetup::new();
+        let (_, mut client2_rx) = setup.register_client(create_client_info(2)).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+
+        assert_matches!(
+            setup.rx.recv().await,
+            Some(ServerMessage::CallError(CallError { call_id, reason: CallErrorReason::Unauthorized, .. }))
+                if call_id == broadcast.call_id
+        );
+        assert!(received_broadcast_calls(&mut client2_rx).is_empty());
+        assert!(
+            setup
+                .app_state
+                .calls
+                .broadcast(&broadcast.call_id)
+                .is_none()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_without_recipients() {
+        let (_dir, mut setup) = broadcast_setup().await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+
+        assert_matches!(
+            setup.rx.recv().await,
+            Some(ServerMessage::CallError(CallError {
+                reason: CallErrorReason::TargetNotFound,
+                ..
+            }))
+        );
+        assert!(
+            setup
+                .app_state
+                .calls
+                .broadcast(&broadcast.call_id)
+                .is_none()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_end() {
+        let (_dir, setup) = broadcast_setup().await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        let (_, mut client3_rx) = setup.register_client(client_info_at(3, "LOWW_TWR")).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+        drain_messages(&mut client2_rx);
+        drain_messages(&mut client3_rx);
+
+        let end = CallEnd::new(broadcast.call_id, ClientId::from("client1"));
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::CallEnd(end.clone()),
+        )
+        .await;
+
+        assert_eq!(received_call_ends(&mut client2_rx), vec![end.clone()]);
+        assert_eq!(received_call_ends(&mut client3_rx), vec![end]);
+        assert!(
+            setup
+                .app_state
+                .calls
+                .broadcast(&broadcast.call_id)
+                .is_none()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_broadcast_call_recipient_leaves() {
+        let (_dir, setup) = broadcast_setup().await;
+        let (_, mut client1_rx) = setup.register_client(create_client_info(1)).await;
+        let (client2, _client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        let (client3, _client3_rx) = setup.register_client(client_info_at(3, "LOWW_TWR")).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+        drain_messages(&mut client1_rx);
+
+        let end = CallEnd::new(broadcast.call_id, client2.id().clone());
+        handle_application_message(
+            &setup.app_state,
+            &client2,
+            ClientMessage::CallEnd(end.clone()),
+        )
+        .await;
+
+        assert_eq!(received_call_ends(&mut client1_rx), vec![end]);
+        let remaining = setup
+            .app_state
+            .calls
+            .broadcast(&broadcast.call_id)
+            .expect("Broadcast should continue with remaining recipients");
+        assert_eq!(remaining.recipients, HashSet::from([client3.id().clone()]));
+
+        handle_application_message(
+            &setup.app_state,
+            &client3,
+            ClientMessage::CallEnd(CallEnd::new(broadcast.call_id, client3.id().clone())),
+        )
+        .await;
+        assert!(
+            setup
+                .app_state
+                .calls
+                .broadcast(&broadcast.call_id)
+                .is_none(),
+            "Broadcast should end once the last recipient left"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn broadcast_call_cleaned_up_on_broadcaster_disconnect() {
+        let (_dir, setup) = broadcast_setup().await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+
+        let broadcast = broadcast_call(&["LOWW_TWR"]);
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::BroadcastCall(broadcast.clone()),
+        )
+        .await;
+        drain_messages(&mut client2_rx);
+
+        setup
+            .app_state
+            .calls
+            .cleanup_client_calls(&setup.app_state, setup.session.id())
+            .await;
+
+        assert_eq!(
+            received_call_ends(&mut client2_rx),
+            vec![CallEnd::new(broadcast.call_id, ClientId::from("client1"))]
+        );
+        assert!(
+            setup
+                .app_state
+                .calls
+                .broadcast(&broadcast.call_id)
+                .is_none()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_call_invite_closed_station() {
+        let (_dir, mut setup) = broadcast_setup().await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        let station_id = StationId::from("LOWW_TWR");
+        setup
+            .app_state
+            .set_station_closed(&station_id, true)
+            .await
+            .unwrap();
+        drain_messages(&mut client2_rx);
+
+        let call_id = CallId::new();
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::CallInvite(CallInvite {
+                call_id,
+                source: CallSource::new(ClientId::from("client1")),
+                target: CallTarget::Station(station_id),
+                prio: false,
+            }),
+        )
+        .await;
+
+        assert_matches!(
+            setup.rx.recv().await,
+            Some(ServerMessage::CallError(CallError {
+                reason: CallErrorReason::TargetNotFound,
+                ..
+            }))
+        );
+        assert!(drain_messages(&mut client2_rx).is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_call_invite_receive_only() {
+        let mut setup = TestSetup::new();
+        let dir = tempfile::tempdir().unwrap();
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .receive_only_position("position1", &["LOVV"], "135.825", "FSS")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir.path());
+        setup.app_state.replace_network(network).await;
+        let (_, mut client1_rx) = setup.register_client(create_client_info(1)).await;
+        let (client2, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_TWR")).await;
+        drain_messages(&mut client1_rx);
+        drain_messages(&mut client2_rx);
+
+        let call_id = CallId::new();
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::CallInvite(CallInvite {
+                call_id,
+                source: CallSource::new(ClientId::from("client1")),
+                target: CallTarget::Station(StationId::from("LOWW_TWR")),
+                prio: false,
+            }),
+        )
+        .await;
+
+        assert_matches!(
+            setup.rx.recv().await,
+            Some(ServerMessage::CallError(CallError { call_id: id, reason: CallErrorReason::ReceiveOnly, .. }))
+                if id == call_id
+        );
+        assert!(drain_messages(&mut client2_rx).is_empty());
+
+        let call_id = CallId::new();
+        handle_application_message(
+            &setup.app_state,
+            &client2,
+            ClientMessage::CallInvite(CallInvite {
+                call_id,
+                source: CallSource::new(ClientId::from("client2")),
+                target: CallTarget::Position(PositionId::from("position1")),
+                prio: false,
+            }),
+        )
+        .await;
+
+        assert!(
+            drain_messages(&mut client1_rx)
+                .into_iter()
+                .any(|message| matches!(
+                    message,
+                    ServerMessage::CallInvite(invite) if invite.call_id == call_id
+                )),
+            "receive-only position did not receive call invite"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_call_invite_station_group() {
+        let setup = TestSetup::new();
+        let dir = tempfile::tempdir().unwrap();
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP"])
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station_group("LOWW_ALL", &["LOWW_APP", "LOWW_TWR"])
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir.path());
+        setup.app_state.replace_network(network).await;
+        let (_, mut client2_rx) = setup.register_client(client_info_at(2, "LOWW_APP")).await;
+        let (_, mut client3_rx) = setup.register_client(client_info_at(3, "LOWW_TWR")).await;
+        drain_messages(&mut client2_rx);
+        drain_messages(&mut client3_rx);
+
+        let call_id = CallId::new();
+        handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::CallInvite(CallInvite {
+                call_id,
+                source: CallSource::new(ClientId::from("client1")),
+                target: CallTarget::Station(StationId::from("LOWW_ALL")),
+                prio: false,
+            }),
+        )
+        .await;
+
+        for rx in [&mut client2_rx, &mut client3_rx] {
+            assert!(
+                drain_messages(rx).into_iter().any(|message| matches!(
+                    message,
+                    ServerMessage::CallInvite(invite) if invite.call_id == call_id
+                )),
+                "group member did not receive call invite"
+            );
+        }
+    }
+
+    /// Creates a test setup whose `position1` uses `profile1`, with `profile2` existing in the
+    /// network but not being offered to the position.
+    async fn profile_setup() -> (tempfile::TempDir, TestSetup) {
+        let setup = TestSetup::new();
+        let dir = tempfile::tempdir().unwrap();
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["position1"])
+            .position_with_profile("position1", &["LOWW"], "134.675", "APP", "profile1")
+            .tabbed_profile("profile1", &[("APP", "LOWW_APP")])
+            .tabbed_profile("profile2", &[("APP", "LOWW_APP")])
+            .build(dir.path());
+        setup.app_state.replace_network(network).await;
+        (dir, setup)
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_set_profile() {
+        let (_dir, mut setup) = profile_setup().await;
+        setup.register_client(create_client_info(1)).await;
+
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::SetProfile(client::SetProfile {
+                profile: ActiveProfile::Custom,
+            }),
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_matches!(
+            message,
+            ServerMessage::SessionInfo(server::SessionInfo {
+                profile: server::SessionProfile::Changed(ActiveProfile::Custom),
+                available_profiles,
+                ..
+            }) if available_profiles == vec![
+                ActiveProfile::Specific(ProfileId::from("profile1")),
+                ActiveProfile::Custom,
+            ]
+        );
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_matches!(message, ServerMessage::StationList(_));
+
+        let session = setup
+            .app_state
+            .clients
+            .get_client(&ClientId::from("client1"))
+            .await
+            .unwrap();
+        assert_eq!(session.active_profile(), &ActiveProfile::Custom);
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_set_profile_not_available() {
+        let (_dir, mut setup) = profile_setup().await;
+        setup.register_client(create_client_info(1)).await;
+
+        let control_flow = handle_application_message(
+            &setup.app_state,
+            &setup.session,
+            ClientMessage::SetProfile(client::SetProfile {
+                profile: ActiveProfile::Specific(ProfileId::from("profile2")),
+            }),
+        )
+        .await;
+        assert_eq!(control_flow, ControlFlow::Continue(()));
+
+        let message = setup.rx.recv().await.expect("No message received");
+        assert_matches!(
+            message,
+            ServerMessage::Error(shared::Error {
+                reason: ErrorReason::ProfileNotAvailable,
+                ..
+            })
+        );
+        assert!(setup.rx.try_recv().is_err());
+
+        let session = setup
+            .app_state
+            .clients
+            .get_client(&ClientId::from("client1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            session.active_profile(),
+            &ActiveProfile::Specific(ProfileId::from("profile1"))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_set_monitored_frequencies() {
+        let (_dir, mut setup) = profile_setup().await;
+        setup.register_client(create_client_info(1)).await;
+        let client_id = ClientId::from("client1");
+
+        assert!(
+            !setup
+                .app_state
+                .clients
+                .clients_reachable_on("121.500")
+                .await
+                .contains(&client_id)
+        );
+
+        for (frequencies, reachable) in [
+            (vec!["121.500", "121.500", "118.005"], true),
+            (vec![], false),
+        ] {
+            handle_application_message(
+                &setup.app_state,
+                &setup.session,
+                ClientMessage::SetMonitoredFrequencies(client::SetMonitoredFrequencies {
+                    frequencies: frequencies.into_iter().map(str::to_string).collect(),
+                }),
+            )
+            .await;
+
+            let message = setup.rx.recv().await.expect("No message received");
+            let expected = if reachable {
+                vec!["121.500".to_string(), "118.005".to_string()]
+            } else {
+                Vec::new()
+            };
+            assert_eq!(
+                message,
+                ServerMessage::MonitoredFrequencies(server::MonitoredFrequencies {
+                    frequencies: expected
+                })
+            );
+            assert_eq!(
+                setup
+                    .app_state
+                    .clients
+                    .clients_reachable_on("121.500")
+                    .await
+                    .contains(&client_id),
+                reachable
+            );
+            // The position's primary frequency stays reachable regardless of monitored ones
+            assert!(
+                setup
+                    .app_state
+                    .clients
+                    .clients_reachable_on("134.675")
+                    .await
+                    .contains(&client_id)
+            );
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn handle_application_message_set_monitored_frequencies_invalid() {
+        let (_dir, mut setup) = profile_setup().await;
+        setup.register_client(create_client_info(1)).await;
+        setup
+            .app_state
+            .clients
+            .set_monitored_frequencies(&ClientId::from("client1"), vec!["121.500".to_string()])
+            .await
+            .unwrap();
+
+        for frequencies in [vec!["121.5"], vec!["121.600", "134.675"]] {
+            handle_application_message(
+                &setup.app_state,
+                &setup.session,
+                ClientMessage::SetMonitoredFrequencies(client::SetMonitoredFrequencies {
+                    frequencies: frequencies.into_iter().map(str::to_string).collect(),
+                }),
+            )
+            .await;
+
+            let message = setup.rx.recv().await.expect("No message received");
+            assert_matches!(
+                message,
+                ServerMessage::Error(shared::Error {
+                    reason: ErrorReason::InvalidFrequencies(_),
+                    ..
+                })
+            );
+        }
+
+        // Rejected updates keep the previously monitored frequencies
+        let session = setup
+            .app_state
+            .clients
+            .get_client(&ClientId::from("client1"))
+            .await
+            .unwrap();
+        assert_eq!(session.monitored_frequencies(), ["121.500".to_string()]);
+        assert!(
+            setup
+                .app_state
+                .clients
+                .clients_reachable_on("121.600")
+                .await
+                .is_empty()
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use crate::metrics::{ClientMetrics, ErrorMetrics};
 use crate::state::AppState;
+use crate::state::clients::PositionMatch;
 use crate::ws::message::{MessageResult, receive_message, send_message_raw};
 use axum::extract::ws;
 use axum::extract::ws::WebSocket;
@@ -12,7 +13,7 @@ use vacs_protocol::profile::{ActiveProfile, ProfileId};
 use vacs_protocol::vatsim::{ClientId, PositionId};
 use vacs_protocol::ws::client::ClientMessage;
 use vacs_protocol::ws::server::{ClientInfo, LoginFailureReason};
-use vacs_protocol::ws::shared::ErrorReason;
+use vacs_protocol::ws::shared::{Codec, ErrorReason};
 use vacs_protocol::ws::{server, shared};
 use vacs_vatsim::{ControllerInfo, FacilityType};
 
@@ -21,14 +22,15 @@ pub async fn handle_websocket_login(
     state: Arc<AppState>,
     websocket_receiver: &mut SplitStream<WebSocket>,
     websocket_sender: &mut SplitSink<WebSocket, ws::Message>,
-) -> Option<(ClientInfo, ActiveProfile<ProfileId>)> {
+) -> Option<(ClientInfo, ActiveProfile<ProfileId>, Vec<String>)> {
     tracing::trace!("Handling websocket login flow");
 
     let result = tokio::time::timeout(Duration::from_millis(state.config.auth.login_flow_timeout_millis), async {
         loop {
             match receive_message(websocket_receiver).await {
                 MessageResult::ApplicationMessage(ClientMessage::Login (login)) => {
-                    return process_login_request(&state, &login.token, &login.protocol_version, login.custom_profile, login.position_id).await;
+                    return process_login_request(&state, login.token.expose(), &login.protocol_version, login.client_version.as_deref(), login.custom_profile, login.position_id, &login.codecs, &login.tags).await
+                        .map(|(client_info, active_profile)| (client_info, active_profile, login.tags));
                 }
                 MessageResult::ApplicationMessage(message) => {
                     tracing::debug!(msg = ?message, "Received unexpected message during websocket login flow");
@@ -51,7 +53,7 @@ pub async fn handle_websocket_login(
     }).await;
 
     match result {
-        Ok(Ok((client_info, active_profile))) => Some((client_info, active_profile)),
+        Ok(Ok(login)) => Some(login),
         Ok(Err(outcome)) => {
             handle_login_outcome(websocket_sender, outcome).await;
             None
@@ -74,14 +76,23 @@ enum LoginOutcome {
     Disconnected,
 }
 
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip(state, token), level = "debug")]
 async fn process_login_request(
     state: &Arc<AppState>,
     token: &str,
     protocol_version: &str,
+    client_version: Option<&str>,
     custom_profile: bool,
     position_id: Option<PositionId>,
+    codecs: &[Codec],
+    tags: &[String],
 ) -> Result<(ClientInfo, ActiveProfile<ProfileId>), LoginOutcome> {
+    if !state.is_ready() {
+        tracing::debug!("Websocket login flow failed, network coverage data not loaded yet");
+        return Err(LoginOutcome::Failure(LoginFailureReason::NotReady));
+    }
+
     if !is_protocol_compatible(state, protocol_version) {
         tracing::debug!("Websocket login flow failed, due to incompatible protocol version");
         return Err(LoginOutcome::Failure(
@@ -89,6 +100,33 @@ async fn process_login_request(
         ));
     }
 
+    if let Some(minimum) = &state.config.updates.minimum_client_version
+        && !client_version
+            .and_then(|version| Version::parse(version).ok())
+            .is_some_and(|version| version >= *minimum)
+    {
+        tracing::debug!(?client_version, %minimum, "Websocket login flow failed, client too old");
+        return Err(LoginOutcome::Failure(LoginFailureReason::ClientTooOld {
+            minimum: minimum.to_string(),
+        }));
+    }
+
+    let Some(codec) = state.config.websocket.negotiate_codec(codecs) else {
+        tracing::debug!(
+            ?codecs,
+            "Websocket login flow failed, none of the offered codecs are allowed"
+        );
+        return Err(LoginOutcome::Failure(LoginFailureReason::UnsupportedCodec(
+            state.config.websocket.allowed_codecs(),
+        )));
+    };
+    tracing::trace!(%codec, "Negotiated websocket codec");
+
+    if !state.config.websocket.are_client_tags_valid(tags) {
+        tracing::debug!(?tags, "Websocket login flow failed, invalid tags");
+        return Err(LoginOutcome::Failure(LoginFailureReason::InvalidTags));
+    }
+
     let cid = state.verify_ws_auth_token(token).await.map_err(|err| {
         tracing::debug!(?err, "Websocket login flow failed");
         LoginOutcome::Failure(LoginFailureReason::InvalidCredentials)
@@ -106,11 +144,8 @@ async fn process_login_request(
         } else {
             position
                 .as_ref()
-                .and_then(|p| {
-                    p.profile_id
-                        .as_ref()
-                        .map(|p| ActiveProfile::Specific(p.clone()))
-                })
+                .and_then(|p| state.clients.resolve_profile_id(p))
+                .map(ActiveProfile::Specific)
                 .unwrap_or(ActiveProfile::None)
         };
 
@@ -154,56 +189,74 @@ async fn resolve_vatsim_position(
                     LoginFailureReason::NoActiveVatsimConnection,
                 ))
             }
+            Some(controller_info)
+                if !state
+                    .config
+                    .vatsim
+                    .allowed_facility_types
+                    .contains(&controller_info.facility_type) =>
+            {
+                tracing::trace!(
+                    ?cid,
+                    ?controller_info,
+                    "VATSIM facility type not allowed, rejecting login"
+                );
+                Err(LoginOutcome::Failure(
+                    LoginFailureReason::FacilityNotAllowed,
+                ))
+            }
             Some(controller_info) => {
                 tracing::trace!(
                     ?cid,
                     ?controller_info,
                     "VATSIM user info found, resolving matching positions"
                 );
-                let positions = state.clients.find_positions(&controller_info);
-
-                let position = if positions.is_empty() {
-                    tracing::trace!(?cid, ?controller_info, "No matching position found");
-                    None
-                } else if positions.len() == 1 {
-                    tracing::trace!(?cid, ?controller_info, position = ?positions[0], "Found matching position");
-                    Some(&positions[0])
-                } else if let Some(target_pid) = position_id.as_ref() {
-                    if let Some(position) = positions.iter().find(|p| &p.id == target_pid) {
+                let position = match state
+                    .clients
+                    .match_position(&controller_info, position_id.as_ref())
+                {
+                    PositionMatch::None => {
+                        tracing::trace!(?cid, ?controller_info, "No matching position found");
+                        None
+                    }
+                    PositionMatch::Position(position) => {
                         tracing::trace!(
                             ?cid,
                             ?controller_info,
                             ?position,
-                            "Found multiple matching positions, user selection is included, assigning selection"
+                            "Found matching position"
                         );
                         Some(position)
-                    } else {
+                    }
+                    PositionMatch::InvalidSelection(position_ids) => {
                         tracing::trace!(
                             ?cid,
                             ?controller_info,
-                            ?target_pid,
+                            ?position_id,
+                            ?position_ids,
                             "Found multiple matching positions, but user selection is not included, rejecting login as invalid"
                         );
                         return Err(LoginOutcome::Failure(
                             LoginFailureReason::InvalidVatsimPosition,
                         ));
                     }
-                } else {
-                    tracing::trace!(
-                        ?cid,
-                        ?controller_info,
-                        positions = positions.len(),
-                        "Found multiple matching positions, rejecting login as ambiguous"
-                    );
-                    let position_ids = positions.into_iter().map(|p| p.id.clone()).collect();
-                    return Err(LoginOutcome::Failure(
-                        LoginFailureReason::AmbiguousVatsimPosition(position_ids),
-                    ));
+                    PositionMatch::Ambiguous(position_ids) => {
+                        tracing::trace!(
+                            ?cid,
+                            ?controller_info,
+                            ?position_id,
+                            ?position_ids,
+                            "Found multiple matching positions, rejecting login as ambiguous"
+                        );
+                        return Err(LoginOutcome::Failure(
+                            LoginFailureReason::AmbiguousVatsimPosition(position_ids),
+                        ));
+                    }
                 };
 
                 let client_info = ClientInfo {
                     id: cid,
-                    position_id: position.map(|p| p.id.clone()),
+                    position_id: position.as_ref().map(|p| p.id.clone()),
                     display_name: controller_info.callsign.clone(),
                     frequency: controller_info.frequency.clone(),
                 };
@@ -212,11 +265,9 @@ async fn resolve_vatsim_position(
                     ActiveProfile::Custom
                 } else {
                     position
-                        .and_then(|p| {
-                            p.profile_id
-                                .as_ref()
-                                .map(|p| ActiveProfile::Specific(p.clone()))
-                        })
+                        .as_ref()
+                        .and_then(|p| state.clients.resolve_profile_id(p))
+                        .map(ActiveProfile::Specific)
                         .unwrap_or(ActiveProfile::None)
                 };
 
@@ -5,10 +5,12 @@ use crate::ws::auth::handle_websocket_login;
 use crate::ws::message::send_message_raw;
 use axum::extract::ws::{CloseCode, CloseFrame, Message, Utf8Bytes, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum_client_ip::ClientIp;
 use futures_util::{SinkExt, StreamExt};
 use std::sync::Arc;
+use tokio::time::Instant;
 use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode as TungsteniteCloseCode;
 use tracing::Instrument;
 use vacs_protocol::ws::server;
@@ -18,31 +20,43 @@ pub async fn ws_handler(
     ws: WebSocketUpgrade,
     ClientIp(ip): ClientIp,
     State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
+) -> Response {
+    let Some(permit) = state.try_acquire_ws_connection() else {
+        tracing::debug!(client_ip = ?ip, "Websocket connection limit reached, rejecting upgrade");
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+
+    let upgrade_deadline = Instant::now() + state.config.websocket.upgrade_timeout;
+    let ws = ws.max_message_size(state.config.websocket.max_message_size);
     ws.on_upgrade(move |socket| {
         let span = tracing::trace_span!("websocket_connection", client_ip = ?ip, client_id = tracing::field::Empty);
         async move {
-            handle_socket(socket, state).await;
+            // Hold the connection slot for the whole lifetime of the socket.
+            let _permit = permit;
+            handle_socket(socket, state, upgrade_deadline).await;
         }.instrument(span)
     })
 }
 
-async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
+async fn handle_socket(socket: WebSocket, state: Arc<AppState>, upgrade_deadline: Instant) {
     tracing::trace!("Handling new websocket connection");
     let client_connection_guard = ClientConnectionGuard::new();
 
     let (mut websocket_tx, mut websocket_rx) = socket.split();
 
-    let Some((client_info, active_profile)) =
-        handle_websocket_login(state.clone(), &mut websocket_rx, &mut websocket_tx).await
-    else {
+    let login = handle_websocket_login(state.clone(), &mut websocket_rx, &mut websocket_tx);
+    let Ok(login) = tokio::time::timeout_at(upgrade_deadline, login).await else {
+        tracing::debug!("Websocket upgrade timed out, dropping connection");
+        return;
+    };
+    let Some((client_info, active_profile, tags)) = login else {
         return;
     };
 
     tracing::Span::current().record("client_id", tracing::field::display(&client_info.id));
 
     let res = state
-        .register_client(client_info, active_profile, client_connection_guard)
+        .register_client_with_tags(client_info, active_profile, tags, client_connection_guard)
         .await;
     let (mut client, mut rx) = match res {
         Ok(client) => client,
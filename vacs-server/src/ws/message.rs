@@ -3,8 +3,9 @@ use crate::ws::traits::{WebSocketSink, WebSocketStream};
 use axum::extract::ws;
 use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
+use vacs_protocol::profile::ActiveProfile;
 use vacs_protocol::ws::client::ClientMessage;
-use vacs_protocol::ws::server::ServerMessage;
+use vacs_protocol::ws::server::{self, ServerMessage, SessionProfile};
 
 /// Represents the outcome of [`receive_message`], indicating whether the message received should be handled, skipped, or receiving errored.
 #[derive(Debug)]
@@ -34,13 +35,17 @@ impl PartialEq for MessageResult {
     }
 }
 
+/// Serializes and sends `message`, refusing to send messages larger than `max_message_size`.
+///
+/// A [`server::SessionInfo`] exceeding the limit due to its profile is replaced by a
+/// [`server::ProfileTooLarge`], allowing the client to show a meaningful error instead of never
+/// receiving the message.
 pub async fn send_message(
     ws_outbound_tx: &mpsc::Sender<ws::Message>,
     message: impl Into<ServerMessage>,
+    max_message_size: usize,
 ) -> anyhow::Result<()> {
-    let message = message.into();
-    let serialized_message = ServerMessage::serialize(&message)
-        .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize message"))?;
+    let (message, serialized_message) = serialize_limited(message.into(), max_message_size)?;
     MessageMetrics::sent(&message, serialized_message.len());
     ws_outbound_tx
         .send(ws::Message::from(serialized_message))
@@ -64,6 +69,42 @@ pub async fn send_message_raw<T: WebSocketSink>(
     Ok(())
 }
 
+fn serialize_limited(
+    message: ServerMessage,
+    max_message_size: usize,
+) -> anyhow::Result<(ServerMessage, String)> {
+    let serialized_message = ServerMessage::serialize(&message)
+        .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize message"))?;
+    if serialized_message.len() <= max_message_size {
+        return Ok((message, serialized_message));
+    }
+
+    match message {
+        ServerMessage::SessionInfo(server::SessionInfo {
+            profile: SessionProfile::Changed(ActiveProfile::Specific(profile)),
+            ..
+        }) => {
+            tracing::error!(
+                profile_id = ?profile.id,
+                size = serialized_message.len(),
+                max_message_size,
+                "Session info exceeds maximum message size, sending profile too large instead"
+            );
+            let message = ServerMessage::from(server::ProfileTooLarge {
+                profile_id: profile.id,
+            });
+            let serialized_message = ServerMessage::serialize(&message)
+                .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize message"))?;
+            Ok((message, serialized_message))
+        }
+        message => Err(anyhow::anyhow!(
+            "{} message of {} bytes exceeds maximum message size of {max_message_size} bytes",
+            message.variant(),
+            serialized_message.len()
+        )),
+    }
+}
+
 pub async fn receive_message<R: WebSocketStream>(websocket_rx: &mut R) -> MessageResult {
     match websocket_rx.next().await {
         Some(Ok(ws::Message::Text(raw_message))) => {
@@ -111,6 +152,7 @@ mod tests {
     use tokio::sync::{Mutex, mpsc};
     use tokio_tungstenite::tungstenite;
     use uuid::Uuid;
+    use vacs_protocol::profile::{Profile, ProfileId, ProfileType};
     use vacs_protocol::vatsim::{ClientId, PositionId};
     use vacs_protocol::ws::server::{self, ClientInfo, ServerMessage};
     use vacs_protocol::ws::shared::CallId;
@@ -249,6 +291,76 @@ mod tests {
         );
     }
 
+    fn session_info(profile: SessionProfile) -> server::SessionInfo {
+        server::SessionInfo {
+            client: ClientInfo {
+                id: ClientId::from("client1"),
+                position_id: Some(PositionId::from("position1")),
+                display_name: "Client 1".to_string(),
+                frequency: "100.000".to_string(),
+            },
+            profile,
+            ice_servers: None,
+            frequency_mismatch: None,
+            position_without_coverage: false,
+            available_profiles: Vec::new(),
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn send_message_oversized_profile() {
+        let (tx, mut rx) = mpsc::channel(100);
+        let profile = Profile {
+            id: ProfileId::from("LOVV"),
+            profile_type: ProfileType::Tabbed(Vec::new()),
+        };
+        let message = session_info(SessionProfile::Changed(ActiveProfile::Specific(profile)));
+        let max_message_size = 128;
+        assert!(
+            ServerMessage::from(message.clone())
+                .serialize()
+                .unwrap()
+                .len()
+                > max_message_size
+        );
+
+        send_message(&tx, message.clone(), max_message_size)
+            .await
+            .expect("Failed to send message");
+        let sent = rx.recv().await.expect("No message received");
+        let ws::Message::Text(raw_message) = sent else {
+            panic!("Expected a Text message, got: {:?}", sent);
+        };
+        assert_eq!(
+            ServerMessage::deserialize(&raw_message).expect("Failed to deserialize message"),
+            ServerMessage::ProfileTooLarge(server::ProfileTooLarge {
+                profile_id: ProfileId::from("LOVV"),
+            })
+        );
+
+        send_message(&tx, message, usize::MAX)
+            .await
+            .expect("Failed to send message");
+        assert_matches!(
+            rx.recv().await,
+            Some(ws::Message::Text(raw_message))
+                if matches!(ServerMessage::deserialize(&raw_message), Ok(ServerMessage::SessionInfo(_)))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn send_message_oversized_without_profile() {
+        let (tx, mut rx) = mpsc::channel(100);
+        let message = session_info(SessionProfile::Unchanged);
+
+        assert!(
+            send_message(&tx, message, 16)
+                .await
+                .is_err_and(|err| err.to_string().contains("exceeds maximum message size"))
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test(tokio::test)]
     async fn receive_single_message() {
         let mut mock_stream = MockStream::new(vec![Ok(ws::Message::from(
@@ -261,10 +373,13 @@ mod tests {
             result,
             MessageResult::ApplicationMessage(ClientMessage::Login(
                 vacs_protocol::ws::client::Login {
-                    token: "token1".to_string(),
+                    token: "token1".into(),
                     protocol_version: "0.0.0".to_string(),
                     custom_profile: false,
                     position_id: None,
+                    codecs: Vec::new(),
+                    client_version: None,
+                    tags: Vec::new(),
                 }
             ))
         );
@@ -286,10 +401,13 @@ mod tests {
             receive_message(&mut mock_stream).await,
             MessageResult::ApplicationMessage(ClientMessage::Login(
                 vacs_protocol::ws::client::Login {
-                    token: "token1".to_string(),
+                    token: "token1".into(),
                     protocol_version: "0.0.0".to_string(),
                     custom_profile: false,
                     position_id: None,
+                    codecs: Vec::new(),
+                    client_version: None,
+                    tags: Vec::new(),
                 }
             ))
         );
@@ -304,7 +422,8 @@ mod tests {
                     call_id: CallId::from(Uuid::nil()),
                     from_client_id: ClientId::from("client1"),
                     to_client_id: ClientId::from("client2"),
-                    sdp: "sdp1".to_string()
+                    sdp: "sdp1".to_string(),
+                    audio_codecs: Vec::new(),
                 }
             ))
         );
@@ -351,10 +470,13 @@ mod tests {
                 receive_message(&mut mock_stream).await,
                 MessageResult::ApplicationMessage(ClientMessage::Login(
                     vacs_protocol::ws::client::Login {
-                        token: "token1".to_string(),
+                        token: "token1".into(),
                         protocol_version: "0.0.0".to_string(),
                         custom_profile: false,
                         position_id: None,
+                        codecs: Vec::new(),
+                        client_version: None,
+                        tags: Vec::new(),
                     }
                 ))
             );
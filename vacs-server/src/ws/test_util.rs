@@ -4,6 +4,7 @@ use crate::metrics::guards::ClientConnectionGuard;
 use crate::ratelimit::RateLimiters;
 use crate::release::UpdateChecker;
 use crate::state::AppState;
+use crate::state::clients::queue::{ClientReceiver, client_channel};
 use crate::state::clients::session::ClientSession;
 use crate::store::Store;
 use crate::store::memory::MemoryStore;
@@ -80,7 +81,7 @@ pub struct TestSetup {
     pub mock_sink: MockSink,
     pub websocket_tx: Arc<Mutex<mpsc::Sender<ws::Message>>>,
     pub websocket_rx: Arc<Mutex<mpsc::Receiver<ws::Message>>>,
-    pub rx: mpsc::Receiver<ServerMessage>,
+    pub rx: ClientReceiver,
     pub broadcast_rx: broadcast::Receiver<ServerMessage>,
     pub shutdown_tx: watch::Sender<()>,
     pub coverage_dir: tempfile::TempDir,
@@ -94,13 +95,19 @@ impl Default for TestSetup {
 
 impl TestSetup {
     pub fn new() -> Self {
+        Self::with_config(|_| {})
+    }
+
+    /// Creates a test setup, letting `configure` adjust the default test config before the app
+    /// state is created.
+    pub fn with_config(configure: impl FnOnce(&mut AppConfig)) -> Self {
         let coverage_dir = tempfile::tempdir().unwrap();
         let mut vatsim_users = HashMap::new();
         for i in 0..=5 {
             vatsim_users.insert(format!("token{i}"), format!("client{i}"));
         }
         let (shutdown_tx, shutdown_rx) = watch::channel(());
-        let config = AppConfig {
+        let mut config = AppConfig {
             vatsim: VatsimConfig {
                 user_service: Default::default(),
                 require_active_connection: false,
@@ -109,9 +116,25 @@ impl TestSetup {
                 data_feed_url: Default::default(),
                 data_feed_timeout: Default::default(),
                 coverage_dir: coverage_dir.path().to_str().unwrap().to_string(),
+                unknown_facility_tolerance: 0,
+                max_pending_disconnects: usize::MAX,
+                frequency_only_matching: false,
+                ambiguous_position_selection: true,
+                warn_position_without_coverage: false,
+                show_vatsim_only_stations: false,
+                retain_coverage_without_clients: false,
+                station_flap_debounce: Default::default(),
+                station_change_coalescing: Default::default(),
+                last_controller_retention: Default::default(),
+                relief_patterns: Default::default(),
+                allowed_facility_types: VatsimConfig::default_allowed_facility_types(),
+                default_profiles: Default::default(),
+                require_profiles: false,
+                max_firs: None,
             },
             ..Default::default()
         };
+        configure(&mut config);
         let mock_data_feed = Arc::new(MockDataFeed::default());
         let app_state = Arc::new(AppState::new(
             config,
@@ -131,7 +154,7 @@ impl TestSetup {
             display_name: "Client 1".to_string(),
             frequency: "100.000".to_string(),
         };
-        let (tx, rx) = mpsc::channel(10);
+        let (tx, rx) = client_channel(10);
         let session = ClientSession::new(
             client_info,
             ActiveProfile::Specific(ProfileId::from("profile1")),
@@ -165,7 +188,7 @@ impl TestSetup {
     pub async fn register_client(
         &self,
         client_info: ClientInfo,
-    ) -> (ClientSession, mpsc::Receiver<ServerMessage>) {
+    ) -> (ClientSession, ClientReceiver) {
         self.app_state
             .register_client(
                 client_info,
@@ -180,7 +203,7 @@ impl TestSetup {
         &self,
         client_info: ClientInfo,
         active_profile: ActiveProfile<ProfileId>,
-    ) -> (ClientSession, mpsc::Receiver<ServerMessage>) {
+    ) -> (ClientSession, ClientReceiver) {
         self.app_state
             .register_client(
                 client_info,
@@ -194,7 +217,7 @@ impl TestSetup {
     pub async fn register_clients(
         &self,
         client_ids: Vec<ClientInfo>,
-    ) -> HashMap<String, (ClientSession, mpsc::Receiver<ServerMessage>)> {
+    ) -> HashMap<String, (ClientSession, ClientReceiver)> {
         futures_util::future::join_all(client_ids.into_iter().map(|client_id| async move {
             (
                 client_id.id.to_string(),
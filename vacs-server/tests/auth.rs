@@ -7,10 +7,14 @@ use vacs_protocol::VACS_PROTOCOL_VERSION;
 use vacs_protocol::vatsim::ClientId;
 use vacs_protocol::ws::client::ClientMessage;
 use vacs_protocol::ws::server::{self, ServerMessage};
+use vacs_protocol::ws::shared::Codec;
 use vacs_server::test_utils::{
     TestApp, TestClient, assert_message_matches, assert_raw_message_matches, connect_to_websocket,
     setup_test_clients,
 };
+use vacs_vatsim::FacilityType;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test(tokio::test)]
 async fn login() {
@@ -189,10 +193,13 @@ async fn login_timeout() {
     ws_stream
         .send(tungstenite::Message::from(
             ClientMessage::serialize(&ClientMessage::Login(vacs_protocol::ws::client::Login {
-                token: "token".to_string(),
+                token: "token".into(),
                 protocol_version: VACS_PROTOCOL_VERSION.to_string(),
                 custom_profile: false,
                 position_id: None,
+                codecs: Vec::new(),
+                client_version: None,
+                tags: Vec::new(),
             }))
             .unwrap(),
         ))
@@ -361,3 +368,234 @@ async fn logout() {
         _ => panic!("Unexpected message: {message:?}"),
     });
 }
+
+async fn login_with_codecs(addr: &str, token: &str, codecs: Vec<Codec>) -> ServerMessage {
+    login_with(addr, token, codecs, None).await
+}
+
+async fn login_with(
+    addr: &str,
+    token: &str,
+    codecs: Vec<Codec>,
+    client_version: Option<&str>,
+) -> ServerMessage {
+    send_login(
+        addr,
+        vacs_protocol::ws::client::Login {
+            token: token.into(),
+            protocol_version: VACS_PROTOCOL_VERSION.to_string(),
+            custom_profile: false,
+            position_id: None,
+            codecs,
+            client_version: client_version.map(str::to_string),
+            tags: Vec::new(),
+        },
+    )
+    .await
+}
+
+async fn send_login(addr: &str, login: vacs_protocol::ws::client::Login) -> ServerMessage {
+    let mut ws_stream = connect_to_websocket(addr).await;
+
+    ws_stream
+        .send(tungstenite::Message::from(
+            ClientMessage::serialize(&ClientMessage::Login(login)).unwrap(),
+        ))
+        .await
+        .expect("Failed to send login message");
+
+    match ws_stream.next().await {
+        Some(Ok(tungstenite::Message::Text(response))) => ServerMessage::deserialize(&response)
+            .unwrap_or_else(|err| panic!("Failed to deserialize {response:?}: {err}")),
+        other => panic!("Unexpected response: {other:?}"),
+    }
+}
+
+#[test(tokio::test)]
+async fn login_default_codec() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.websocket.allowed_codecs = Vec::new();
+    })
+    .await;
+
+    let response = login_with_codecs(test_app.addr(), "token1", Vec::new()).await;
+    assert!(
+        matches!(response, ServerMessage::SessionInfo(_)),
+        "Unexpected response: {response:?}"
+    );
+
+    let response = login_with_codecs(test_app.addr(), "token2", vec![Codec::from("json")]).await;
+    assert!(
+        matches!(response, ServerMessage::SessionInfo(_)),
+        "Unexpected response: {response:?}"
+    );
+}
+
+#[test(tokio::test)]
+async fn login_accepted_codec() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.websocket.allowed_codecs = vec![Codec::json(), Codec::from("msgpack")];
+    })
+    .await;
+
+    let response = login_with_codecs(
+        test_app.addr(),
+        "token1",
+        vec![Codec::from("zstd"), Codec::from("msgpack")],
+    )
+    .await;
+    assert!(
+        matches!(response, ServerMessage::SessionInfo(_)),
+        "Unexpected response: {response:?}"
+    );
+}
+
+#[test(tokio::test)]
+async fn login_rejected_codec() {
+    let test_app = TestApp::new().await;
+
+    let response = login_with_codecs(test_app.addr(), "token1", vec![Codec::from("msgpack")]).await;
+    match response {
+        ServerMessage::LoginFailure(server::LoginFailure { reason }) => {
+            assert_eq!(
+                reason,
+                server::LoginFailureReason::UnsupportedCodec(vec![Codec::json()])
+            );
+        }
+        _ => panic!("Unexpected response: {response:?}"),
+    }
+}
+
+async fn mock_slurper(cid: &str, callsign: &str, frequency: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/info"))
+        .and(query_param("cid", cid))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "{cid},{callsign},atc,{frequency},50,48.11028,16.56972,0,0,0,0,0,0,0,0,\n"
+        )))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[test(tokio::test)]
+async fn login_allowed_facility() {
+    let slurper = mock_slurper("client1", "LOWW_TWR", "119.400").await;
+    let test_app = TestApp::new_with_config(|config| {
+        config.vatsim.require_active_connection = true;
+        config.vatsim.slurper_base_url = slurper.uri();
+        config.vatsim.allowed_facility_types = [FacilityType::Tower].into();
+    })
+    .await;
+
+    let response = login_with_codecs(test_app.addr(), "token1", Vec::new()).await;
+    match response {
+        ServerMessage::SessionInfo(server::SessionInfo { client, .. }) => {
+            assert_eq!(client.display_name, "LOWW_TWR");
+        }
+        _ => panic!("Unexpected response: {response:?}"),
+    }
+}
+
+#[test(tokio::test)]
+async fn login_rejected_facility() {
+    let slurper = mock_slurper("client1", "LOWW_APP", "134.675").await;
+    let test_app = TestApp::new_with_config(|config| {
+        config.vatsim.require_active_connection = true;
+        config.vatsim.slurper_base_url = slurper.uri();
+        config.vatsim.allowed_facility_types = [FacilityType::Tower].into();
+    })
+    .await;
+
+    let response = login_with_codecs(test_app.addr(), "token1", Vec::new()).await;
+    match response {
+        ServerMessage::LoginFailure(server::LoginFailure { reason }) => {
+            assert_eq!(reason, server::LoginFailureReason::FacilityNotAllowed);
+        }
+        _ => panic!("Unexpected response: {response:?}"),
+    }
+}
+
+#[test(tokio::test)]
+async fn login_client_version_up_to_date() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.updates.minimum_client_version = Some(semver::Version::new(2, 0, 0));
+    })
+    .await;
+
+    for (token, version) in [("token1", "2.0.0"), ("token2", "2.1.3")] {
+        let response = login_with(test_app.addr(), token, Vec::new(), Some(version)).await;
+        assert!(
+            matches!(response, ServerMessage::SessionInfo(_)),
+            "Unexpected response for {version}: {response:?}"
+        );
+    }
+}
+
+#[test(tokio::test)]
+async fn login_client_version_too_old() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.updates.minimum_client_version = Some(semver::Version::new(2, 0, 0));
+    })
+    .await;
+
+    for version in [Some("1.3.1"), Some("2.0.0-rc.1"), Some("invalid"), None] {
+        let response = login_with(test_app.addr(), "token1", Vec::new(), version).await;
+        match response {
+            ServerMessage::LoginFailure(server::LoginFailure { reason }) => {
+                assert_eq!(
+                    reason,
+                    server::LoginFailureReason::ClientTooOld {
+                        minimum: "2.0.0".to_string()
+                    }
+                );
+            }
+            _ => panic!("Unexpected response for {version:?}: {response:?}"),
+        }
+    }
+}
+
+#[test(tokio::test)]
+async fn login_client_version_without_minimum() {
+    let test_app = TestApp::new().await;
+
+    for (token, version) in [("token1", Some("0.1.0")), ("token2", None)] {
+        let response = login_with(test_app.addr(), token, Vec::new(), version).await;
+        assert!(
+            matches!(response, ServerMessage::SessionInfo(_)),
+            "Unexpected response for {version:?}: {response:?}"
+        );
+    }
+}
+
+#[test(tokio::test)]
+async fn login_with_tags() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.websocket.max_client_tags = 2;
+    })
+    .await;
+    let login = |token: &str, tags: &[&str]| vacs_protocol::ws::client::Login {
+        token: token.into(),
+        protocol_version: VACS_PROTOCOL_VERSION.to_string(),
+        custom_profile: false,
+        position_id: None,
+        codecs: Vec::new(),
+        client_version: None,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+    };
+
+    let response = send_login(test_app.addr(), login("token1", &["a", "b", "c"])).await;
+    assert_eq!(
+        response,
+        ServerMessage::LoginFailure(server::LoginFailure {
+            reason: server::LoginFailureReason::InvalidTags,
+        })
+    );
+
+    let response = send_login(test_app.addr(), login("token1", &["platform:MacOs"])).await;
+    assert!(
+        matches!(response, ServerMessage::SessionInfo(_)),
+        "Unexpected response: {response:?}"
+    );
+}
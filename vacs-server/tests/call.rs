@@ -1,10 +1,13 @@
 use std::time::Duration;
 use test_log::test;
-use vacs_protocol::vatsim::ClientId;
+use vacs_protocol::vatsim::{ClientId, StationId};
 use vacs_protocol::ws::client::ClientMessage;
-use vacs_protocol::ws::server::ServerMessage;
-use vacs_protocol::ws::shared::{CallId, CallTarget};
-use vacs_server::test_utils::{TestApp, setup_n_test_clients};
+use vacs_protocol::ws::server::{self, CallCancelReason, ServerMessage};
+use vacs_protocol::ws::shared::{AudioCodec, CallId, CallTarget};
+use vacs_server::test_utils::{TestApp, TestClient, setup_n_test_clients};
+use vacs_vatsim::coverage::test_support::TestFirBuilder;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test(tokio::test)]
 async fn call_offer() -> anyhow::Result<()> {
@@ -46,6 +49,7 @@ async fn call_offer() -> anyhow::Result<()> {
             vacs_protocol::ws::shared::CallAccept {
                 call_id,
                 accepting_client_id: client2.id().clone(),
+                auto_answered: false,
             },
         ))
         .await?;
@@ -68,6 +72,7 @@ async fn call_offer() -> anyhow::Result<()> {
                 from_client_id: client1.id().clone(),
                 to_client_id: client2.id().clone(),
                 sdp: "sdp1".to_string(),
+                audio_codecs: Vec::new(),
             },
         ))
         .await?;
@@ -162,6 +167,7 @@ async fn call_offer_answer() -> anyhow::Result<()> {
             vacs_protocol::ws::shared::CallAccept {
                 call_id,
                 accepting_client_id: client2.id().clone(),
+                auto_answered: false,
             },
         ))
         .await?;
@@ -178,16 +184,24 @@ async fn call_offer_answer() -> anyhow::Result<()> {
                 from_client_id: client1.id().clone(),
                 to_client_id: client2.id().clone(),
                 sdp: "sdp1".to_string(),
+                audio_codecs: vec![AudioCodec::opus()],
             },
         ))
         .await?;
 
-    // Consume offer on client2
-    let _ = client2
+    // Consume offer on client2, codecs are relayed unchanged
+    let call_offer_messages = client2
         .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
             matches!(m, ServerMessage::WebrtcOffer(_))
         })
         .await;
+    let audio_codec = match &call_offer_messages[..] {
+        [ServerMessage::WebrtcOffer(offer)] => {
+            assert_eq!(offer.audio_codecs, vec![AudioCodec::opus()]);
+            AudioCodec::negotiate(&offer.audio_codecs, &[AudioCodec::opus()])
+        }
+        messages => panic!("Unexpected messages: {messages:?}, expected WebrtcOffer from client1"),
+    };
 
     client2
         .send(ClientMessage::WebrtcAnswer(
@@ -196,6 +210,7 @@ async fn call_offer_answer() -> anyhow::Result<()> {
                 from_client_id: client2.id().clone(),
                 to_client_id: client1.id().clone(),
                 sdp: "sdp2".to_string(),
+                audio_codec,
             },
         ))
         .await?;
@@ -220,6 +235,11 @@ async fn call_offer_answer() -> anyhow::Result<()> {
                 "CallAnswer targeted the wrong client"
             );
             assert_eq!(answer.sdp, "sdp2", "CallAnswer contains the wrong SDP");
+            assert_eq!(
+                answer.audio_codec,
+                Some(AudioCodec::opus()),
+                "CallAnswer contains the wrong audio codec"
+            );
         }
         message => panic!(
             "Unexpected message: {:?}, expected CallAnswer from client2",
@@ -270,6 +290,115 @@ async fn call_offer_answer() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test(tokio::test)]
+async fn call_auto_answered() -> anyhow::Result<()> {
+    let test_app = TestApp::new().await;
+    let mut clients = setup_n_test_clients(test_app.addr(), 2).await;
+
+    let mut client1 = clients.remove(0);
+    let mut client2 = clients.remove(0);
+
+    let call_id = CallId::new();
+    client1
+        .send(ClientMessage::CallInvite(
+            vacs_protocol::ws::shared::CallInvite {
+                call_id,
+                source: vacs_protocol::ws::shared::CallSource {
+                    client_id: client1.id().clone(),
+                    position_id: None,
+                    station_id: None,
+                },
+                target: CallTarget::Client(client2.id().clone()),
+                prio: false,
+            },
+        ))
+        .await?;
+
+    // client2 answers as soon as the invite arrives, as an auto-answering client would
+    let invite_messages = client2
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::CallInvite(_))
+        })
+        .await;
+    assert_eq!(
+        invite_messages.len(),
+        1,
+        "client2 should receive CallInvite"
+    );
+    client2
+        .send(ClientMessage::CallAccept(
+            vacs_protocol::ws::shared::CallAccept {
+                call_id,
+                accepting_client_id: client2.id().clone(),
+                auto_answered: true,
+            },
+        ))
+        .await?;
+
+    let accept_messages = client1
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::CallAccept(_))
+        })
+        .await;
+    match &accept_messages[..] {
+        [ServerMessage::CallAccept(accept)] => {
+            assert_eq!(accept.call_id, call_id);
+            assert_eq!(&accept.accepting_client_id, client2.id());
+            assert!(
+                accept.auto_answered,
+                "CallAccept should indicate the call was auto-answered"
+            );
+        }
+        messages => panic!("Unexpected messages: {messages:?}, expected CallAccept from client2"),
+    };
+
+    client1
+        .send(ClientMessage::WebrtcOffer(
+            vacs_protocol::ws::shared::WebrtcOffer {
+                call_id,
+                from_client_id: client1.id().clone(),
+                to_client_id: client2.id().clone(),
+                sdp: "sdp1".to_string(),
+                audio_codecs: vec![AudioCodec::opus()],
+            },
+        ))
+        .await?;
+    let call_offer_messages = client2
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::WebrtcOffer(_))
+        })
+        .await;
+    assert_eq!(
+        call_offer_messages.len(),
+        1,
+        "client2 should receive WebrtcOffer"
+    );
+
+    client2
+        .send(ClientMessage::WebrtcAnswer(
+            vacs_protocol::ws::shared::WebrtcAnswer {
+                call_id,
+                from_client_id: client2.id().clone(),
+                to_client_id: client1.id().clone(),
+                sdp: "sdp2".to_string(),
+                audio_codec: Some(AudioCodec::opus()),
+            },
+        ))
+        .await?;
+    let call_answer_messages = client1
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::WebrtcAnswer(_))
+        })
+        .await;
+    assert_eq!(
+        call_answer_messages.len(),
+        1,
+        "client1 should receive WebrtcAnswer"
+    );
+
+    Ok(())
+}
+
 #[test(tokio::test)]
 async fn target_not_found() -> anyhow::Result<()> {
     let test_app = TestApp::new().await;
@@ -352,3 +481,188 @@ async fn target_not_found() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test(tokio::test)]
+async fn call_answer_timeout() -> anyhow::Result<()> {
+    let test_app =
+        TestApp::new_with_config(|c| c.calls.answer_timeout = Duration::from_millis(200)).await;
+    let mut clients = setup_n_test_clients(test_app.addr(), 2).await;
+
+    let mut client1 = clients.remove(0);
+    let mut client2 = clients.remove(0);
+
+    let call_id = CallId::new();
+    client1
+        .send(ClientMessage::CallInvite(
+            vacs_protocol::ws::shared::CallInvite {
+                call_id,
+                source: vacs_protocol::ws::shared::CallSource {
+                    client_id: client1.id().clone(),
+                    position_id: None,
+                    station_id: None,
+                },
+                target: CallTarget::Client(client2.id().clone()),
+                prio: false,
+            },
+        ))
+        .await?;
+
+    let invite_messages = client2
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::CallInvite(_))
+        })
+        .await;
+    assert_eq!(
+        invite_messages.len(),
+        1,
+        "client2 should have received exactly one CallInvite message"
+    );
+
+    for client in [&mut client1, &mut client2] {
+        let cancelled_messages = client
+            .recv_until_timeout_with_filter(Duration::from_millis(500), |m| {
+                matches!(m, ServerMessage::CallCancelled(_))
+            })
+            .await;
+
+        assert_eq!(
+            cancelled_messages.len(),
+            1,
+            "client {} should have received exactly one CallCancelled message",
+            client.id()
+        );
+        match &cancelled_messages[0] {
+            ServerMessage::CallCancelled(cancelled) => {
+                assert_eq!(cancelled.call_id, call_id);
+                assert_eq!(cancelled.reason, CallCancelReason::Timeout);
+            }
+            message => panic!(
+                "Unexpected message: {:?}, expected CallCancelled from server",
+                message
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn list_active_calls_only_includes_relevant_calls() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let network = TestFirBuilder::new("LOVV")
+        .station("LOVV_CTR", &["LOVV_CTR"])
+        .station("LOWW_APP", &["LOWW_APP"])
+        .station("LOWW_TWR", &["LOWW_TWR"])
+        .station("LOWW_GND", &["LOWW_GND"])
+        .station("LOWW_DEL", &["LOWW_DEL"])
+        .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+        .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+        .position_with_profile("LOWW_TWR", &["LOWW"], "119.400", "TWR", "TWR_PROFILE")
+        .position("LOWW_GND", &["LOWW"], "121.600", "GND")
+        .position("LOWW_DEL", &["LOWW"], "122.125", "DEL")
+        .tabbed_profile("TWR_PROFILE", &[("LOWW APP", "LOWW_APP")])
+        .build(dir.path());
+
+    let slurper = mock_slurper(&[
+        ("client1", "LOWW_APP", "134.675"),
+        ("client2", "LOWW_DEL", "122.125"),
+        ("client3", "LOVV_CTR", "132.600"),
+        ("client4", "LOWW_GND", "121.600"),
+        ("client5", "LOWW_TWR", "119.400"),
+    ])
+    .await;
+    let test_app = TestApp::new_with_network_and_config(network, |config| {
+        config.vatsim.require_active_connection = true;
+        config.vatsim.slurper_base_url = slurper.uri();
+    })
+    .await;
+    let mut clients = setup_n_test_clients(test_app.addr(), 5).await;
+
+    let mut client1 = clients.remove(0);
+    let mut client2 = clients.remove(0);
+    let mut client3 = clients.remove(0);
+    let mut client4 = clients.remove(0);
+    let mut client5 = clients.remove(0);
+
+    // LOWW_APP is part of client5's profile, while neither LOWW_DEL nor LOWW_GND are
+    let relevant_call_id = open_call(&mut client1, &mut client3).await?;
+    let irrelevant_call_id = open_call(&mut client2, &mut client4).await?;
+    assert_eq!(test_app.state().calls.active_calls().len(), 2);
+
+    client5.send(ClientMessage::ListActiveCalls).await?;
+    let messages = client5
+        .recv_until_timeout_with_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::ActiveCallList(_))
+        })
+        .await;
+    assert_eq!(
+        messages,
+        vec![ServerMessage::ActiveCallList(server::ActiveCallList {
+            calls: vec![server::ActiveCallInfo {
+                call_id: relevant_call_id,
+                station_ids: vec![StationId::from("LOWW_APP")],
+            }],
+        })],
+        "client5 should only see the call involving LOWW_APP, not {irrelevant_call_id}"
+    );
+
+    Ok(())
+}
+
+async fn mock_slurper(controllers: &[(&str, &str, &str)]) -> MockServer {
+    let server = MockServer::start().await;
+    for (cid, callsign, frequency) in controllers {
+        Mock::given(method("GET"))
+            .and(path("/users/info"))
+            .and(query_param("cid", *cid))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "{cid},{callsign},atc,{frequency},50,48.11028,16.56972,0,0,0,0,0,0,0,0,\n"
+            )))
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+/// Opens a call from `caller` to `callee`, returning once the callee accepted it.
+async fn open_call(caller: &mut TestClient, callee: &mut TestClient) -> anyhow::Result<CallId> {
+    let call_id = CallId::new();
+    caller
+        .send(ClientMessage::CallInvite(
+            vacs_protocol::ws::shared::CallInvite {
+                call_id,
+                source: vacs_protocol::ws::shared::CallSource {
+                    client_id: caller.id().clone(),
+                    position_id: None,
+                    station_id: None,
+                },
+                target: CallTarget::Client(callee.id().clone()),
+                prio: false,
+            },
+        ))
+        .await?;
+    callee
+        .recv_with_timeout_and_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::CallInvite(_))
+        })
+        .await
+        .ok_or_else(|| anyhow::anyhow!("{} did not receive CallInvite", callee.id()))?;
+
+    callee
+        .send(ClientMessage::CallAccept(
+            vacs_protocol::ws::shared::CallAccept {
+                call_id,
+                accepting_client_id: callee.id().clone(),
+                auto_answered: false,
+            },
+        ))
+        .await?;
+    caller
+        .recv_with_timeout_and_filter(Duration::from_millis(100), |m| {
+            matches!(m, ServerMessage::CallAccept(_))
+        })
+        .await
+        .ok_or_else(|| anyhow::anyhow!("{} did not receive CallAccept", caller.id()))?;
+
+    Ok(call_id)
+}
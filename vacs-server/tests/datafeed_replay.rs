@@ -0,0 +1,77 @@
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use test_log::test;
+use tokio::sync::broadcast;
+use vacs_protocol::profile::ActiveProfile;
+use vacs_protocol::vatsim::{ClientId, PositionId, StationId};
+use vacs_protocol::ws::server::ClientInfo;
+use vacs_server::metrics::guards::ClientConnectionGuard;
+use vacs_server::state::clients::ClientManager;
+use vacs_server::test_utils::replay_data_feed;
+use vacs_vatsim::coverage::test_support::TestFirBuilder;
+use vacs_vatsim::data_feed::replay::load_snapshots;
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/datafeed");
+
+fn lovv_network(dir: &Path) -> vacs_vatsim::coverage::network::Network {
+    TestFirBuilder::new("LOVV")
+        .station("LOWW_APP", &["LOWW_APP", "LOVV_CTR"])
+        .station_with_parent("LOWW_TWR", "LOWW_APP", &["LOWW_TWR"])
+        .station_with_parent("LOWW_GND", "LOWW_TWR", &["LOWW_GND"])
+        .station_with_parent("LOWW_DEL", "LOWW_GND", &["LOWW_DEL"])
+        .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+        .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+        .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+        .position("LOWW_GND", &["LOWW"], "121.600", "GND")
+        .position("LOWW_DEL", &["LOWW"], "122.125", "DEL")
+        .build(dir)
+}
+
+#[test(tokio::test)]
+async fn replay_recorded_datafeed_sequence() {
+    let snapshots = load_snapshots(FIXTURES_DIR).expect("Failed to load recorded snapshots");
+    assert_eq!(snapshots.len(), 4);
+
+    let dir = tempfile::tempdir().unwrap();
+    let (broadcast_tx, _broadcast_rx) = broadcast::channel(64);
+    let manager = ClientManager::new(broadcast_tx, lovv_network(dir.path()));
+
+    let (_session, _rx) = manager
+        .add_client(
+            ClientInfo {
+                id: ClientId::from("1000001"),
+                display_name: "1000001".to_string(),
+                frequency: "132.600".to_string(),
+                position_id: Some(PositionId::from("LOVV_CTR")),
+            },
+            ActiveProfile::Custom,
+            ClientConnectionGuard::default(),
+        )
+        .await
+        .unwrap();
+
+    let report = replay_data_feed(&manager, &snapshots, Some(0.001), true).await;
+    assert_eq!(report.snapshots, 4);
+    assert!(report.disconnected_clients.is_empty());
+
+    let stations = manager.list_stations(&ActiveProfile::Custom, None).await;
+    let clients = manager.list_clients(None).await;
+    assert_eq!(
+        stations.iter().map(|s| s.id.clone()).collect::<Vec<_>>(),
+        vec![StationId::from("LOWW_APP")]
+    );
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].position_id, Some(PositionId::from("LOVV_CTR")));
+
+    // Replaying the same sequence again must converge to the same state without accumulating
+    // any leftovers from the previous pass.
+    for _ in 0..3 {
+        let report = replay_data_feed(&manager, &snapshots, None, true).await;
+        assert!(report.disconnected_clients.is_empty());
+        assert_eq!(
+            manager.list_stations(&ActiveProfile::Custom, None).await,
+            stations
+        );
+        assert_eq!(manager.list_clients(None).await, clients);
+    }
+}
@@ -1,7 +1,10 @@
 use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
 use test_log::test;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tokio_tungstenite::tungstenite;
-use vacs_server::test_utils::{TestApp, connect_to_websocket};
+use vacs_server::test_utils::{TestApp, connect_to_websocket, setup_n_test_clients};
 
 #[test(tokio::test)]
 async fn websocket_ping_pong() {
@@ -20,3 +23,76 @@ async fn websocket_ping_pong() {
         _ => panic!("Did not receive pong message"),
     }
 }
+
+#[test(tokio::test)]
+async fn websocket_connection_limit_rejects_excess() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.websocket.max_connections = 2;
+    })
+    .await;
+    let clients = setup_n_test_clients(test_app.addr(), 2).await;
+
+    match tokio_tungstenite::connect_async(test_app.addr()).await {
+        Err(tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 503);
+        }
+        Ok(_) => panic!("Connection beyond the limit should have been rejected"),
+        Err(err) => panic!("Unexpected error: {err:?}"),
+    }
+
+    // Closing a connection frees up a slot again
+    drop(clients);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    connect_to_websocket(test_app.addr()).await;
+}
+
+#[test(tokio::test)]
+async fn websocket_stalled_login_is_dropped_and_releases_slot() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.auth.login_flow_timeout_millis = 10_000;
+        config.websocket.max_connections = 1;
+        config.websocket.upgrade_timeout = Duration::from_millis(200);
+    })
+    .await;
+
+    // Upgrade the connection, but never log in
+    let mut stalled = connect_to_websocket(test_app.addr()).await;
+    match tokio_tungstenite::connect_async(test_app.addr()).await {
+        Err(tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 503);
+        }
+        Ok(_) => panic!("Connection beyond the limit should have been rejected"),
+        Err(err) => panic!("Unexpected error: {err:?}"),
+    }
+
+    let closed = tokio::time::timeout(Duration::from_secs(2), async {
+        while let Some(Ok(_)) = stalled.next().await {}
+    })
+    .await;
+    assert!(closed.is_ok(), "Stalled connection was not dropped");
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    connect_to_websocket(test_app.addr()).await;
+}
+
+#[test(tokio::test)]
+async fn stalled_request_headers_are_dropped() {
+    let test_app = TestApp::new_with_config(|config| {
+        config.websocket.upgrade_timeout = Duration::from_millis(200);
+    })
+    .await;
+    let addr = test_app
+        .addr()
+        .trim_start_matches("ws://")
+        .trim_end_matches("/ws");
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET /ws HTTP/1.1\r\nHost: localhost\r\n")
+        .await
+        .unwrap();
+
+    let mut buf = Vec::new();
+    let closed = tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut buf)).await;
+    assert!(closed.is_ok(), "Stalled connection was not dropped");
+}
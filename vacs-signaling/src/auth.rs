@@ -3,8 +3,122 @@ pub mod mock;
 
 use crate::error::SignalingError;
 use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use vacs_protocol::secret::Secret;
 
 #[async_trait]
 pub trait TokenProvider: Send + Sync + 'static {
-    async fn get_token(&self) -> Result<String, SignalingError>;
+    async fn get_token(&self) -> Result<Secret, SignalingError>;
+}
+
+/// Tracks the time of the initial interactive auth grant.
+///
+/// Token refreshes do not extend the grant. Once it is older than the configured maximum age,
+/// [`AuthGrant::check`] returns [`SignalingError::AuthExpiredNeedsReauth`] until a new grant is
+/// recorded, forcing a full interactive reauth instead of refreshing indefinitely.
+#[derive(Debug, Clone, Default)]
+pub struct AuthGrant {
+    max_age: Option<Duration>,
+    granted_at: Arc<Mutex<Option<Instant>>>,
+}
+
+impl AuthGrant {
+    /// Creates a new grant tracker. A `max_age` of `None` disables the check.
+    pub fn new(max_age: Option<Duration>) -> Self {
+        Self {
+            max_age,
+            granted_at: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Records a new interactive grant, resetting its age.
+    pub fn record(&self) {
+        self.record_at(Instant::now());
+    }
+
+    /// Records a grant only if none is tracked yet, e.g. for a session restored from a previous
+    /// run whose original grant time is unknown.
+    pub fn record_if_unset(&self) {
+        self.granted_at.lock().get_or_insert_with(Instant::now);
+    }
+
+    pub fn clear(&self) {
+        self.granted_at.lock().take();
+    }
+
+    pub fn granted_at(&self) -> Option<Instant> {
+        *self.granted_at.lock()
+    }
+
+    /// Returns [`SignalingError::AuthExpiredNeedsReauth`] if the tracked grant exceeds the
+    /// maximum age.
+    pub fn check(&self) -> Result<(), SignalingError> {
+        let (Some(max_age), Some(granted_at)) = (self.max_age, self.granted_at()) else {
+            return Ok(());
+        };
+
+        let age = granted_at.elapsed();
+        if age > max_age {
+            tracing::info!(?age, ?max_age, "Auth grant exceeded maximum age");
+            return Err(SignalingError::AuthExpiredNeedsReauth);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record_at(&self, granted_at: Instant) {
+        *self.granted_at.lock() = Some(granted_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_matches};
+
+    #[test]
+    fn check_without_grant() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        assert_matches!(grant.check(), Ok(()));
+    }
+
+    #[test]
+    fn check_within_max_age() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        grant.record_at(Instant::now() - Duration::from_secs(30));
+        assert_matches!(grant.check(), Ok(()));
+    }
+
+    #[test]
+    fn check_exceeded_max_age() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        grant.record_at(Instant::now() - Duration::from_secs(61));
+        assert_matches!(grant.check(), Err(SignalingError::AuthExpiredNeedsReauth));
+
+        grant.record();
+        assert_matches!(grant.check(), Ok(()));
+    }
+
+    #[test]
+    fn check_disabled() {
+        let grant = AuthGrant::new(None);
+        grant.record_at(Instant::now() - Duration::from_secs(3600));
+        assert_matches!(grant.check(), Ok(()));
+    }
+
+    #[test]
+    fn record_if_unset_keeps_existing_grant() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        let granted_at = Instant::now() - Duration::from_secs(61);
+        grant.record_at(granted_at);
+
+        grant.record_if_unset();
+        assert_eq!(grant.granted_at(), Some(granted_at));
+        assert_matches!(grant.check(), Err(SignalingError::AuthExpiredNeedsReauth));
+
+        grant.clear();
+        grant.record_if_unset();
+        assert_matches!(grant.check(), Ok(()));
+    }
 }
@@ -1,29 +1,43 @@
-use crate::auth::TokenProvider;
+use crate::auth::{AuthGrant, TokenProvider};
 use crate::error::SignalingError;
 use async_trait::async_trait;
 use std::time::Duration;
+use vacs_protocol::secret::Secret;
 
 #[derive(Debug, Clone)]
 pub struct MockTokenProvider {
     client_id: usize,
     delay: Option<Duration>,
+    grant: Option<AuthGrant>,
 }
 
 impl MockTokenProvider {
     pub fn new(client_id: usize, delay: Option<Duration>) -> Self {
-        Self { client_id, delay }
+        Self {
+            client_id,
+            delay,
+            grant: None,
+        }
+    }
+
+    pub fn with_grant(mut self, grant: AuthGrant) -> Self {
+        self.grant = Some(grant);
+        self
     }
 }
 
 #[async_trait]
 impl TokenProvider for MockTokenProvider {
-    async fn get_token(&self) -> Result<String, SignalingError> {
+    async fn get_token(&self) -> Result<Secret, SignalingError> {
+        if let Some(grant) = &self.grant {
+            grant.check()?;
+        }
         if let Some(delay) = self.delay {
             tokio::time::sleep(delay).await;
         }
         if self.client_id == usize::MAX {
-            return Ok("".to_string());
+            return Ok(Secret::default());
         }
-        Ok(format!("token{}", self.client_id))
+        Ok(format!("token{}", self.client_id).into())
     }
 }
@@ -1,5 +1,5 @@
 use crate::auth::TokenProvider;
-use crate::error::{SignalingError, SignalingRuntimeError, UntilInstant};
+use crate::error::{ReconnectFailureReason, SignalingError, SignalingRuntimeError, UntilInstant};
 use crate::matcher::ResponseMatcher;
 use crate::transport::{SignalingReceiver, SignalingSender, SignalingTransport};
 use parking_lot::{Mutex, RwLock};
@@ -7,21 +7,27 @@ use rand::RngExt;
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::{broadcast, mpsc, watch};
 use tokio::task::{JoinHandle, JoinSet};
 use tokio_tungstenite::tungstenite;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, instrument};
 use vacs_protocol::VACS_PROTOCOL_VERSION;
-use vacs_protocol::profile::{ActiveProfile, Profile};
+use vacs_protocol::http::webrtc::IceServer;
+use vacs_protocol::profile::{ActiveProfile, Profile, ProfileId};
 use vacs_protocol::vatsim::PositionId;
 use vacs_protocol::ws::client::ClientMessage;
-use vacs_protocol::ws::server::{ClientInfo, ServerMessage, SessionProfile};
+use vacs_protocol::ws::server::{ClientInfo, FrequencyMismatch, ServerMessage, SessionProfile};
 use vacs_protocol::ws::{client, server};
 
 const BROADCAST_CHANNEL_SIZE: usize = 100;
 const SEND_CHANNEL_SIZE: usize = 100;
+const SEND_CHANNEL_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+/// Fraction of [`SEND_CHANNEL_SIZE`] at which the send channel is considered near full.
+const SEND_CHANNEL_NEAR_FULL_RATIO: f64 = 0.8;
+/// Maximum number of messages deferred by [`SignalingClient::send_buffered`] while reconnecting.
+const SEND_BUFFER_SIZE: usize = 32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum State {
@@ -51,6 +57,16 @@ pub enum SignalingEvent {
         client_info: ClientInfo,
         /// The profile associated with the current session.
         profile: ActiveProfile<Profile>,
+        /// ICE servers configured for the client's FIR, if the FIR overrides the global ones.
+        ice_servers: Option<Vec<IceServer>>,
+        /// Set if the client's VATSIM frequency differs from the one defined for its position.
+        frequency_mismatch: Option<FrequencyMismatch>,
+        /// Set if the client's position controls no stations in the server's dataset.
+        position_without_coverage: bool,
+        /// Profiles the client may switch to using [`client::SetProfile`].
+        available_profiles: Vec<ActiveProfile<ProfileId>>,
+        /// Endpoint of the signaling server the client connected to, if known.
+        endpoint: Option<String>,
     },
     /// Emitted for every [`ServerMessage`] received by a connected and authenticated [`SignalingClient`].
     Message(ServerMessage),
@@ -59,6 +75,117 @@ pub enum SignalingEvent {
     Error(SignalingRuntimeError),
 }
 
+/// Parameters of the current session, established by a successful login and kept up to date with
+/// session updates sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// Information about the client as assigned by the server, including its ID and display name.
+    pub client_info: ClientInfo,
+    /// Protocol version the session was established with.
+    pub protocol_version: String,
+    /// Profiles the client may switch to using [`client::SetProfile`].
+    pub available_profiles: Vec<ActiveProfile<ProfileId>>,
+    /// Endpoint of the signaling server the client connected to, if known.
+    pub endpoint: Option<String>,
+    /// Time the login completed.
+    pub connected_at: SystemTime,
+}
+
+impl ConnectionInfo {
+    fn update(&mut self, msg: &ServerMessage) {
+        match msg {
+            ServerMessage::SessionInfo(session_info) => {
+                self.client_info = session_info.client.clone();
+                self.available_profiles = session_info.available_profiles.clone();
+            }
+            ServerMessage::ClientInfo(client_info) if client_info.id == self.client_info.id => {
+                self.client_info = client_info.clone();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Occupancy of the outgoing message channel, sampled periodically while connected.
+///
+/// A send channel that is constantly near full indicates a congested uplink, causing sends to
+/// await until the transport catches up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendChannelStats {
+    /// Maximum number of messages the channel can queue.
+    pub capacity: usize,
+    /// Number of queued messages at the time of the last sample.
+    pub occupancy: usize,
+    /// Highest number of queued messages sampled since connecting.
+    pub peak_occupancy: usize,
+    /// Number of samples since connecting at which the channel was near full.
+    pub near_full_samples: u64,
+}
+
+impl Default for SendChannelStats {
+    fn default() -> Self {
+        Self {
+            capacity: SEND_CHANNEL_SIZE,
+            occupancy: 0,
+            peak_occupancy: 0,
+            near_full_samples: 0,
+        }
+    }
+}
+
+impl SendChannelStats {
+    /// Returns whether the channel was near full at the time of the last sample.
+    pub fn is_near_full(&self) -> bool {
+        self.occupancy as f64 >= self.capacity as f64 * SEND_CHANNEL_NEAR_FULL_RATIO
+    }
+
+    fn record(&mut self, occupancy: usize) {
+        self.occupancy = occupancy;
+        self.peak_occupancy = self.peak_occupancy.max(occupancy);
+        if self.is_near_full() {
+            self.near_full_samples += 1;
+        }
+    }
+}
+
+/// Result of a successful [`SignalingClient::send_buffered`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// The message was handed to the transport immediately.
+    Sent,
+    /// The client is reconnecting, the message was queued and will be sent after the next
+    /// successful login.
+    Buffered,
+}
+
+/// Returns whether a message may be deferred by [`SignalingClient::send_buffered`].
+///
+/// Only requests and state updates that are still meaningful after a reconnect are bufferable:
+/// [`ClientMessage::ListClients`], [`ClientMessage::ListStations`],
+/// [`ClientMessage::ListActiveCalls`], [`ClientMessage::GetDatasetInfo`],
+/// [`ClientMessage::GetStationHierarchy`], [`ClientMessage::GetStationCoverage`] and [`ClientMessage::TransmitState`]. Session lifecycle and call signaling messages are not, as the
+/// server drops all call state of a disconnected client and a deferred offer or answer would be
+/// stale by the time it is delivered.
+pub const fn is_bufferable(msg: &ClientMessage) -> bool {
+    matches!(
+        msg,
+        ClientMessage::ListClients
+            | ClientMessage::ListStations
+            | ClientMessage::ListActiveCalls
+            | ClientMessage::GetDatasetInfo
+            | ClientMessage::GetStationHierarchy
+            | ClientMessage::GetStationCoverage(_)
+            | ClientMessage::TransmitState(_)
+    )
+}
+
+/// Messages deferred while reconnecting, flushed in FIFO order after the next successful login.
+#[derive(Debug, Default)]
+struct SendBuffer {
+    reconnecting: bool,
+    queue: VecDeque<ClientMessage>,
+}
+
 type BoxFutUnit = Pin<Box<dyn Future<Output = ()> + Send>>;
 type OnEventCb = Arc<dyn Fn(SignalingEvent) -> BoxFutUnit + Send + Sync>;
 
@@ -119,6 +246,32 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClient<ST, TP> {
         self.inner.state()
     }
 
+    /// Subscribes to a watch containing the latest [`SendChannelStats`] sample.
+    pub fn subscribe_send_channel_stats(&self) -> watch::Receiver<SendChannelStats> {
+        self.inner.send_stats_tx.subscribe()
+    }
+
+    pub fn send_channel_stats(&self) -> SendChannelStats {
+        *self.inner.send_stats_tx.borrow()
+    }
+
+    /// Sets the application version reported to the server on the next login, allowing the server
+    /// to reject outdated clients.
+    pub fn set_client_version(&self, version: impl Into<String>) {
+        *self.inner.client_version.write() = Some(version.into());
+    }
+
+    /// Sets the tags describing the connection (e.g. the client platform) sent to the server on
+    /// the next login.
+    pub fn set_tags(&self, tags: Vec<String>) {
+        *self.inner.tags.write() = tags;
+    }
+
+    /// Returns the parameters of the current session, or `None` if not logged in.
+    pub fn connection_info(&self) -> Option<ConnectionInfo> {
+        self.inner.connection_info.read().clone()
+    }
+
     pub async fn connect(&self, position_id: Option<PositionId>) -> Result<(), SignalingError> {
         self.inner.set_position_id(position_id);
         self.inner.connect().await
@@ -132,6 +285,19 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClient<ST, TP> {
         self.inner.send(msg).await
     }
 
+    /// Like [`Self::send`], but defers [bufferable](is_bufferable) messages while the client is
+    /// automatically reconnecting instead of failing them.
+    ///
+    /// Deferred messages are sent in the order they were buffered after the next successful
+    /// login, before any message sent after the login. Once [`SEND_BUFFER_SIZE`] messages are
+    /// buffered, further messages are rejected with [`SignalingRuntimeError::SendBufferFull`].
+    /// If reconnecting fails permanently or a disconnect is requested, all buffered messages are
+    /// dropped. Messages that are not bufferable or sent while not reconnecting behave exactly
+    /// like [`Self::send`].
+    pub async fn send_buffered(&self, msg: ClientMessage) -> Result<SendOutcome, SignalingError> {
+        self.inner.send_buffered(msg).await
+    }
+
     pub async fn recv(&self) -> Result<ServerMessage, SignalingError> {
         self.inner.recv().await
     }
@@ -172,9 +338,14 @@ struct SignalingClientInner<ST: SignalingTransport, TP: TokenProvider> {
     broadcast_tx: broadcast::Sender<SignalingEvent>,
 
     send_tx: Arc<Mutex<Option<mpsc::Sender<tungstenite::Message>>>>,
+    send_stats_tx: watch::Sender<SendChannelStats>,
+    send_buffer: Arc<Mutex<SendBuffer>>,
 
     custom_profile: bool,
     position_id: Arc<RwLock<Option<PositionId>>>,
+    client_version: Arc<RwLock<Option<String>>>,
+    tags: Arc<RwLock<Vec<String>>>,
+    connection_info: Arc<RwLock<Option<ConnectionInfo>>>,
 
     login_timeout: Duration,
     reconnect_max_tries: u8,
@@ -211,9 +382,14 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
             broadcast_tx: broadcast::channel(BROADCAST_CHANNEL_SIZE).0,
 
             send_tx: Arc::new(Mutex::new(None)),
+            send_stats_tx: watch::Sender::new(SendChannelStats::default()),
+            send_buffer: Arc::new(Mutex::new(SendBuffer::default())),
 
             custom_profile,
             position_id: Arc::new(RwLock::new(None)),
+            client_version: Arc::new(RwLock::new(None)),
+            tags: Arc::new(RwLock::new(Vec::new())),
+            connection_info: Arc::new(RwLock::new(None)),
 
             login_timeout,
             reconnect_max_tries,
@@ -254,6 +430,7 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
         self.cleanup().await;
         if requested {
             self.reconnect_gate.lock().clear();
+            self.drop_send_buffer("disconnect requested");
         }
     }
 
@@ -275,6 +452,11 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
             _ => {}
         };
 
+        self.send_unchecked(msg).await
+    }
+
+    /// Sends a message regardless of the current [`State`].
+    async fn send_unchecked(&self, msg: ClientMessage) -> Result<(), SignalingError> {
         let send_tx = {
             self.send_tx.lock().as_ref().cloned().ok_or_else(|| {
                 tracing::error!("Client is connected, but send_tx is not initialized");
@@ -293,6 +475,64 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
             .map_err(|_| SignalingError::Runtime(SignalingRuntimeError::Disconnected(None)))
     }
 
+    #[instrument(level = "debug", skip(self, msg), fields(message_type = msg.variant()), err)]
+    async fn send_buffered(&self, msg: ClientMessage) -> Result<SendOutcome, SignalingError> {
+        if is_bufferable(&msg) {
+            let mut buffer = self.send_buffer.lock();
+            if buffer.reconnecting && self.state() != State::LoggedIn {
+                if buffer.queue.len() >= SEND_BUFFER_SIZE {
+                    tracing::warn!("Send buffer full, rejecting message");
+                    return Err(SignalingRuntimeError::SendBufferFull.into());
+                }
+                tracing::debug!("Reconnecting, buffering message");
+                buffer.queue.push_back(msg);
+                return Ok(SendOutcome::Buffered);
+            }
+        }
+
+        self.send(msg).await.map(|_| SendOutcome::Sent)
+    }
+
+    fn begin_reconnect(&self) {
+        self.send_buffer.lock().reconnecting = true;
+    }
+
+    fn drop_send_buffer(&self, reason: &str) {
+        let mut buffer = self.send_buffer.lock();
+        buffer.reconnecting = false;
+        if !buffer.queue.is_empty() {
+            tracing::warn!(
+                dropped = buffer.queue.len(),
+                reason,
+                "Dropping buffered messages"
+            );
+            buffer.queue.clear();
+        }
+    }
+
+    /// Sends all buffered messages and marks the client as logged in once the buffer is drained,
+    /// ensuring buffered messages are sent before any message sent after the login.
+    async fn flush_send_buffer_and_log_in(&self) {
+        loop {
+            let buffered = {
+                let mut buffer = self.send_buffer.lock();
+                if buffer.queue.is_empty() {
+                    buffer.reconnecting = false;
+                    self.set_state(State::LoggedIn);
+                    return;
+                }
+                std::mem::take(&mut buffer.queue)
+            };
+
+            tracing::debug!(count = buffered.len(), "Flushing buffered messages");
+            for msg in buffered {
+                if let Err(err) = self.send_unchecked(msg).await {
+                    tracing::warn!(?err, "Failed to send buffered message");
+                }
+            }
+        }
+    }
+
     #[instrument(level = "debug", skip(self), err)]
     async fn recv(&self) -> Result<ServerMessage, SignalingError> {
         self.recv_with_timeout(Duration::MAX).await
@@ -344,7 +584,7 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
     }
 
     #[instrument(level = "debug", skip(self), err)]
-    async fn login(&self) -> Result<(ClientInfo, ActiveProfile<Profile>), SignalingError> {
+    async fn login(&self) -> Result<SignalingEvent, SignalingError> {
         tracing::trace!("Retrieving auth token from token provider");
         let token = self.token_provider.get_token().await?;
 
@@ -352,10 +592,13 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
         tracing::debug!("Sending Login message to server");
         self.send(
             client::Login {
-                token: token.to_string(),
+                token,
                 protocol_version: VACS_PROTOCOL_VERSION.to_string(),
                 custom_profile: self.custom_profile,
                 position_id,
+                codecs: Vec::new(),
+                client_version: self.client_version.read().clone(),
+                tags: self.tags.read().clone(),
             }
             .into(),
         )
@@ -363,10 +606,38 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
 
         tracing::debug!("Awaiting authentication response from server");
         match self.recv_with_timeout(self.login_timeout).await? {
-            ServerMessage::SessionInfo(server::SessionInfo { client, profile }) => {
+            ServerMessage::SessionInfo(server::SessionInfo {
+                client,
+                profile,
+                ice_servers,
+                frequency_mismatch,
+                position_without_coverage,
+                available_profiles,
+            }) => {
                 if let SessionProfile::Changed(profile) = profile {
                     tracing::info!(?client, %profile, "Login successful, received session info");
-                    Ok((client, profile))
+                    if let Some(mismatch) = &frequency_mismatch {
+                        tracing::warn!(
+                            expected = %mismatch.expected,
+                            actual = %mismatch.actual,
+                            "VATSIM frequency does not match position frequency"
+                        );
+                    }
+                    if position_without_coverage {
+                        tracing::warn!(
+                            position_id = ?client.position_id,
+                            "Position controls no stations in the server's dataset"
+                        );
+                    }
+                    Ok(SignalingEvent::Connected {
+                        client_info: client,
+                        profile,
+                        ice_servers,
+                        frequency_mismatch,
+                        position_without_coverage,
+                        available_profiles,
+                        endpoint: self.transport.endpoint(),
+                    })
                 } else {
                     tracing::error!(
                         ?client,
@@ -422,6 +693,16 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                 &rt_handle,
             );
 
+            self.send_stats_tx.send_replace(SendChannelStats::default());
+            tasks.spawn_on(
+                Self::send_stats_task(
+                    send_tx.downgrade(),
+                    self.send_stats_tx.clone(),
+                    self.disconnect_token.lock().clone(),
+                ),
+                &rt_handle,
+            );
+
             let broadcast_tx = self.broadcast_tx.clone();
             tasks.spawn_on(
                 Self::writer_task(
@@ -440,14 +721,26 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
 
         tracing::trace!("Successfully started worker tasks, logging in");
         match self.login().await {
-            Ok((client_info, profile)) => {
+            Ok(connected) => {
                 tracing::trace!("Successfully logged in to server");
-
-                self.set_state(State::LoggedIn);
-                if let Err(err) = self.broadcast_tx.send(SignalingEvent::Connected {
+                if let SignalingEvent::Connected {
                     client_info,
-                    profile,
-                }) {
+                    available_profiles,
+                    endpoint,
+                    ..
+                } = &connected
+                {
+                    *self.connection_info.write() = Some(ConnectionInfo {
+                        client_info: client_info.clone(),
+                        protocol_version: VACS_PROTOCOL_VERSION.to_string(),
+                        available_profiles: available_profiles.clone(),
+                        endpoint: endpoint.clone(),
+                        connected_at: SystemTime::now(),
+                    });
+                }
+
+                self.flush_send_buffer_and_log_in().await;
+                if let Err(err) = self.broadcast_tx.send(connected) {
                     tracing::warn!(?err, "Failed to broadcast connected event");
                 }
 
@@ -482,6 +775,7 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
         self.matcher.clear().await;
         *self.disconnect_token.lock() = self.shutdown_token.child_token();
         self.send_tx.lock().take();
+        self.connection_info.write().take();
 
         tracing::debug!("Finished cleaning up after disconnect");
     }
@@ -499,6 +793,7 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                 _ = self.shutdown_token.cancelled() => {
                     tracing::debug!("Shutdown signal received, exiting supervisor task");
                     self.set_state(State::Disconnected);
+                    self.drop_send_buffer("shutdown");
                     break;
                 }
 
@@ -508,6 +803,10 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                             if let SignalingEvent::Error(err) = &event && err.is_fatal() {
                                 (self.on_event)(event.clone()).await;
 
+                                if err.can_reconnect() && self.reconnect_max_tries > 0 {
+                                    self.begin_reconnect();
+                                }
+
                                 tracing::debug!(?err, "Received error event, disconnecting");
                                 self.disconnect(false).await;
 
@@ -520,6 +819,8 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                                                 tracing::warn!(?err, "Failed to broadcast reconnect suppressed error event");
                                             }
                                             gate.clear();
+                                            drop(gate);
+                                            self.drop_send_buffer("reconnect suppressed");
                                             continue;
                                         }
 
@@ -527,7 +828,11 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                                     }
 
                                     tracing::info!("Reconnecting after error");
-                                    if let Err(err) = self.reconnect().await {
+                                    let result = self.reconnect().await;
+                                    if self.state() != State::LoggedIn {
+                                        self.drop_send_buffer("reconnect failed");
+                                    }
+                                    if let Err(err) = result {
                                         tracing::warn!(?err, "Received error while reconnecting");
                                         if let Err(err) = self.broadcast_tx.send(SignalingEvent::Error(err)) {
                                             tracing::warn!(?err, "Failed to broadcast reconnect error event");
@@ -535,6 +840,11 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
                                     }
                                 }
                             } else {
+                                if let SignalingEvent::Message(msg) = &event
+                                    && let Some(connection_info) = self.connection_info.write().as_mut()
+                                {
+                                    connection_info.update(msg);
+                                }
                                 (self.on_event)(event).await;
                             }
                         },
@@ -571,6 +881,12 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
             tracing::trace!(?attempt, "Reconnecting");
             match self.connect().await {
                 Ok(()) => return Ok(()),
+                Err(SignalingError::AuthExpiredNeedsReauth) => {
+                    tracing::info!(?attempt, "Auth grant expired, aborting reconnect");
+                    return Err(SignalingRuntimeError::ReconnectFailed(
+                        ReconnectFailureReason::AuthExpiredNeedsReauth,
+                    ));
+                }
                 Err(err) => {
                     tracing::warn!(?err, ?attempt, "Failed to reconnect");
                     reconnect_error = err;
@@ -709,6 +1025,49 @@ impl<ST: SignalingTransport, TP: TokenProvider> SignalingClientInner<ST, TP> {
             }
         }.instrument(tracing::Span::current())
     }
+
+    #[instrument(level = "debug", skip_all)]
+    fn send_stats_task(
+        send_tx: mpsc::WeakSender<tungstenite::Message>,
+        send_stats_tx: watch::Sender<SendChannelStats>,
+        disconnect_token: CancellationToken,
+    ) -> impl Future<Output = ()> + Send {
+        async move {
+            tracing::debug!("Starting send channel stats task");
+            let _guard = TaskDropLogger::new("send_stats");
+
+            let mut interval = tokio::time::interval(SEND_CHANNEL_SAMPLE_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    biased;
+
+                    _ = disconnect_token.cancelled() => {
+                        tracing::debug!("Disconnect signal received, exiting send channel stats task");
+                        break;
+                    }
+
+                    _ = interval.tick() => {
+                        let Some(send_tx) = send_tx.upgrade() else {
+                            tracing::debug!("Send channel closed, exiting send channel stats task");
+                            break;
+                        };
+                        let occupancy = send_tx.max_capacity() - send_tx.capacity();
+                        drop(send_tx);
+
+                        send_stats_tx.send_modify(|stats| {
+                            let was_near_full = stats.is_near_full();
+                            stats.record(occupancy);
+                            if stats.is_near_full() && !was_near_full {
+                                tracing::warn!(?stats, "Send channel is near full, uplink might be congested");
+                            }
+                        });
+                    }
+                }
+            }
+        }.instrument(tracing::Span::current())
+    }
 }
 
 struct TaskDropLogger {
@@ -744,9 +1103,30 @@ impl Default for RetryStrategy {
 }
 
 impl RetryStrategy {
+    /// Replaces the OS-seeded RNG used for jitter, e.g. with a seeded one for deterministic tests.
+    #[cfg(test)]
+    fn with_rng(mut self, rng: rand::rngs::StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
     fn timeout(&mut self, attempt: u32) -> Duration {
+        let max_delay_nanos = self.max_delay(attempt).as_nanos();
+
+        let jitter_nanos = if max_delay_nanos == 0 {
+            0
+        } else {
+            // full jitter
+            self.rng.random_range(0..=max_delay_nanos)
+        };
+
+        Duration::from_nanos(jitter_nanos.min(u128::from(u64::MAX)) as u64)
+    }
+
+    /// Upper bound of the jittered timeout before the given attempt.
+    fn max_delay(&self, attempt: u32) -> Duration {
         if attempt == 0 {
-            return Duration::from_millis(0);
+            return Duration::ZERO;
         }
 
         // exp = base * 2^(attempt - 1), capped
@@ -756,14 +1136,7 @@ impl RetryStrategy {
             .saturating_mul(1u128 << attempt.saturating_sub(1).min(63));
         let max_delay_nanos = exp_nanos.min(self.cap.as_nanos());
 
-        let jitter_nanos = if max_delay_nanos == 0 {
-            0
-        } else {
-            // full jitter
-            self.rng.random_range(0..=max_delay_nanos)
-        };
-
-        Duration::from_nanos(jitter_nanos.min(u128::from(u64::MAX)) as u64)
+        Duration::from_nanos(max_delay_nanos.min(u128::from(u64::MAX)) as u64)
     }
 }
 
@@ -837,30 +1210,26 @@ impl ReconnectGate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::AuthGrant;
     use crate::auth::mock::MockTokenProvider;
+    use crate::error::CloseReason;
     use crate::test_utils::RecvWithTimeoutExt;
-    use crate::transport::mock::MockTransport;
+    use crate::transport::mock::{MockTransport, NetworkConditions};
     use pretty_assertions::{assert_eq, assert_matches};
     use test_log::test;
     use tokio::sync::Notify;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
     use vacs_protocol::vatsim::{ClientId, PositionId};
     use vacs_protocol::ws::server::LoginFailureReason;
     use vacs_protocol::ws::shared::ErrorReason;
 
-    async fn setup_test_client(
-        transport: MockTransport,
-        custom_profile: bool,
-        reconnect_max_tries: u8,
-    ) -> (
-        Arc<SignalingClient<MockTransport, MockTokenProvider>>,
-        CancellationToken,
+    /// Answers the next login on the transport behind `mock_tx` with a successful session info
+    /// once it signals `ready`.
+    fn respond_with_session_info(
+        mock_tx: broadcast::Sender<tungstenite::Message>,
+        ready: Arc<Notify>,
     ) {
-        let shutdown_token = CancellationToken::new();
-        let token_provider = MockTokenProvider::new(1, None);
-
-        let mock_tx = transport.incoming_tx.clone();
-        let ready = transport.ready.clone();
-
         tokio::spawn(async move {
             ready.notified().await;
             let msg = tungstenite::Message::Text(
@@ -875,12 +1244,46 @@ mod tests {
                         id: vacs_protocol::profile::ProfileId::from("1"),
                         profile_type: vacs_protocol::profile::ProfileType::Tabbed(vec![]),
                     })),
+                    ice_servers: None,
+                    frequency_mismatch: None,
+                    position_without_coverage: false,
+                    available_profiles: Vec::new(),
                 }))
                 .unwrap()
                 .into(),
             );
             let _ = mock_tx.send(msg);
         });
+    }
+
+    async fn setup_test_client(
+        transport: MockTransport,
+        custom_profile: bool,
+        reconnect_max_tries: u8,
+    ) -> (
+        Arc<SignalingClient<MockTransport, MockTokenProvider>>,
+        CancellationToken,
+    ) {
+        setup_test_client_with_token_provider(
+            transport,
+            MockTokenProvider::new(1, None),
+            custom_profile,
+            reconnect_max_tries,
+        )
+        .await
+    }
+
+    async fn setup_test_client_with_token_provider(
+        transport: MockTransport,
+        token_provider: MockTokenProvider,
+        custom_profile: bool,
+        reconnect_max_tries: u8,
+    ) -> (
+        Arc<SignalingClient<MockTransport, MockTokenProvider>>,
+        CancellationToken,
+    ) {
+        let shutdown_token = CancellationToken::new();
+        respond_with_session_info(transport.incoming_tx.clone(), transport.ready.clone());
 
         let client = SignalingClient::new(
             transport,
@@ -905,6 +1308,75 @@ mod tests {
         setup_test_client(MockTransport::default(), false, 0).await;
     }
 
+    #[test(tokio::test)]
+    async fn connect_recovers_from_degraded_network() {
+        let transport = MockTransport::default().with_network_conditions(
+            NetworkConditions {
+                drop_rate: 1.0,
+                ..Default::default()
+            },
+            42,
+        );
+        let network = transport.network_conditions_handle();
+        let incoming_tx = transport.incoming_tx.clone();
+        let ready = transport.ready.clone();
+        respond_with_session_info(incoming_tx.clone(), ready.clone());
+
+        let client = SignalingClient::new(
+            transport,
+            MockTokenProvider::new(1, None),
+            |_| async {},
+            CancellationToken::new(),
+            false,
+            Duration::from_millis(100),
+            0,
+            &tokio::runtime::Handle::current(),
+        );
+
+        let res = client.connect(None).await;
+        assert_matches!(res.unwrap_err(), SignalingError::Timeout(_));
+        assert_matches!(client.state(), State::Disconnected);
+
+        network.set(NetworkConditions {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(20),
+            drop_rate: 0.0,
+        });
+        respond_with_session_info(incoming_tx, ready);
+
+        let res = client.connect(None).await;
+        assert!(res.is_ok());
+        assert_matches!(client.state(), State::LoggedIn);
+    }
+
+    #[test(tokio::test)]
+    async fn connection_info() {
+        let transport = MockTransport::default();
+        let incoming_tx = transport.incoming_tx.clone();
+        let (client, _shutdown_token) = setup_test_client(transport, false, 0).await;
+
+        let connection_info = client.connection_info().expect("Missing connection info");
+        assert_eq!(connection_info.client_info.id, ClientId::from("client1"));
+        assert_eq!(connection_info.client_info.display_name, "Client 1");
+        assert_eq!(connection_info.protocol_version, VACS_PROTOCOL_VERSION);
+        assert!(connection_info.connected_at <= SystemTime::now());
+
+        let updated = ClientInfo {
+            display_name: "LOWW_TWR".into(),
+            ..connection_info.client_info.clone()
+        };
+        incoming_tx
+            .send(tungstenite::Message::from(
+                ServerMessage::serialize(&ServerMessage::ClientInfo(updated.clone())).unwrap(),
+            ))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(client.connection_info().unwrap().client_info, updated);
+
+        client.disconnect().await;
+        assert_eq!(client.connection_info(), None);
+    }
+
     #[test(tokio::test)]
     async fn shutdown() {
         let (client, shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
@@ -952,6 +1424,35 @@ mod tests {
         assert!(sent_msg.is_ok());
     }
 
+    #[test(tokio::test)]
+    async fn send_channel_stats_rise_when_flooded() {
+        let transport = MockTransport::default().with_send_delay(Duration::from_secs(5));
+        let (client, _shutdown_token) = setup_test_client(transport, false, 0).await;
+        let mut stats_rx = client.subscribe_send_channel_stats();
+        assert_eq!(client.send_channel_stats().occupancy, 0);
+
+        for _ in 0..SEND_CHANNEL_SIZE {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let _ = client.send(ClientMessage::ListClients).await;
+            });
+        }
+
+        let stats = tokio::time::timeout(
+            SEND_CHANNEL_SAMPLE_INTERVAL * 4,
+            stats_rx.wait_for(|stats| stats.is_near_full()),
+        )
+        .await
+        .expect("send channel stats did not report a near full channel")
+        .map(|stats| *stats)
+        .unwrap();
+
+        assert!(stats.occupancy > 0);
+        assert!(stats.peak_occupancy >= stats.occupancy);
+        assert!(stats.near_full_samples >= 1);
+        assert_eq!(stats.capacity, SEND_CHANNEL_SIZE);
+    }
+
     #[test(tokio::test)]
     async fn send_without_start() {
         let shutdown_token = CancellationToken::new();
@@ -969,10 +1470,13 @@ mod tests {
         );
 
         let msg = ClientMessage::Login(client::Login {
-            token: "test".to_string(),
+            token: "test".into(),
             protocol_version: VACS_PROTOCOL_VERSION.to_string(),
             custom_profile: false,
             position_id: None,
+            codecs: Vec::new(),
+            client_version: None,
+            tags: Vec::new(),
         });
 
         let result = client.send(msg.clone()).await;
@@ -1030,6 +1534,153 @@ mod tests {
         assert_matches!(res.unwrap_err(), SignalingError::Timeout(_));
     }
 
+    #[test(tokio::test)]
+    async fn send_buffered_while_connected() {
+        let transport = MockTransport::default();
+        let mut outgoing_rx = transport.outgoing_tx.subscribe();
+        let (client, _shutdown_token) = setup_test_client(transport, false, 0).await;
+
+        let serialized = tungstenite::Message::from(
+            ClientMessage::serialize(&ClientMessage::ListStations).unwrap(),
+        );
+
+        let result = client.send_buffered(ClientMessage::ListStations).await;
+        assert_matches!(result, Ok(SendOutcome::Sent));
+
+        let sent_msg = outgoing_rx
+            .recv_with_timeout(Duration::from_millis(100), |m| m == &serialized)
+            .await;
+        assert!(sent_msg.is_ok());
+    }
+
+    #[test(tokio::test)]
+    async fn send_buffered_without_reconnect() {
+        let (client, _shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
+        client.disconnect().await;
+
+        let result = client.send_buffered(ClientMessage::ListStations).await;
+        assert_matches!(
+            result,
+            Err(SignalingError::Runtime(
+                SignalingRuntimeError::Disconnected(None)
+            ))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn send_buffered_flushes_after_reconnect() {
+        let transport = MockTransport::default();
+        let mock_tx = transport.incoming_tx.clone();
+        let ready = transport.ready.clone();
+        let mut outgoing_rx = transport.outgoing_tx.subscribe();
+        let (client, _shutdown_token) = setup_test_client(transport, false, 0).await;
+
+        client.inner.begin_reconnect();
+        client.inner.disconnect(false).await;
+
+        assert_matches!(
+            client.send_buffered(ClientMessage::ListStations).await,
+            Ok(SendOutcome::Buffered)
+        );
+        assert_matches!(
+            client.send_buffered(ClientMessage::ListClients).await,
+            Ok(SendOutcome::Buffered)
+        );
+        respond_with_session_info(mock_tx, ready);
+        client.inner.connect().await.unwrap();
+        assert_matches!(client.state(), State::LoggedIn);
+
+        assert_matches!(
+            client.send_buffered(ClientMessage::GetDatasetInfo).await,
+            Ok(SendOutcome::Sent)
+        );
+
+        let mut sent = Vec::new();
+        for _ in 0..3 {
+            let msg = outgoing_rx
+                .recv_with_timeout(Duration::from_millis(100), |m| {
+                    !matches!(
+                        ClientMessage::deserialize(m.to_text().unwrap()),
+                        Ok(ClientMessage::Login(_) | ClientMessage::Logout)
+                    )
+                })
+                .await
+                .unwrap();
+            sent.push(ClientMessage::deserialize(msg.to_text().unwrap()).unwrap());
+        }
+        assert_eq!(
+            sent,
+            vec![
+                ClientMessage::ListStations,
+                ClientMessage::ListClients,
+                ClientMessage::GetDatasetInfo,
+            ]
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn send_buffered_rejects_unbufferable_message() {
+        let (client, _shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
+
+        client.inner.begin_reconnect();
+        client.inner.disconnect(false).await;
+
+        let result = client.send_buffered(ClientMessage::Logout).await;
+        assert_matches!(
+            result,
+            Err(SignalingError::Runtime(
+                SignalingRuntimeError::Disconnected(None)
+            ))
+        );
+        assert!(client.inner.send_buffer.lock().queue.is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn send_buffered_overflow() {
+        let (client, _shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
+
+        client.inner.begin_reconnect();
+        client.inner.disconnect(false).await;
+
+        for _ in 0..SEND_BUFFER_SIZE {
+            assert_matches!(
+                client.send_buffered(ClientMessage::ListStations).await,
+                Ok(SendOutcome::Buffered)
+            );
+        }
+        assert_matches!(
+            client.send_buffered(ClientMessage::ListStations).await,
+            Err(SignalingError::Runtime(
+                SignalingRuntimeError::SendBufferFull
+            ))
+        );
+        assert_eq!(
+            client.inner.send_buffer.lock().queue.len(),
+            SEND_BUFFER_SIZE
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn send_buffered_dropped_on_disconnect() {
+        let (client, _shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
+
+        client.inner.begin_reconnect();
+        client.inner.disconnect(false).await;
+        assert_matches!(
+            client.send_buffered(ClientMessage::ListStations).await,
+            Ok(SendOutcome::Buffered)
+        );
+
+        client.disconnect().await;
+        assert!(client.inner.send_buffer.lock().queue.is_empty());
+        assert_matches!(
+            client.send_buffered(ClientMessage::ListStations).await,
+            Err(SignalingError::Runtime(
+                SignalingRuntimeError::Disconnected(None)
+            ))
+        );
+    }
+
     #[test(tokio::test)]
     async fn send_disconnected() {
         let (client, _shutdown_token) = setup_test_client(MockTransport::default(), false, 0).await;
@@ -1039,10 +1690,13 @@ mod tests {
         assert_matches!(client.state(), State::Disconnected);
 
         let msg = ClientMessage::Login(client::Login {
-            token: "test".to_string(),
+            token: "test".into(),
             protocol_version: VACS_PROTOCOL_VERSION.to_string(),
             custom_profile: false,
             position_id: None,
+            codecs: Vec::new(),
+            client_version: None,
+            tags: Vec::new(),
         });
 
         let result = client.send(msg.clone()).await;
@@ -1065,10 +1719,13 @@ mod tests {
         assert_matches!(client.state(), State::Disconnected);
 
         let msg = ClientMessage::Login(client::Login {
-            token: "test".to_string(),
+            token: "test".into(),
             protocol_version: VACS_PROTOCOL_VERSION.to_string(),
             custom_profile: false,
             position_id: None,
+            codecs: Vec::new(),
+            client_version: None,
+            tags: Vec::new(),
         });
 
         let result = client.send(msg.clone()).await;
@@ -1326,6 +1983,145 @@ mod tests {
         assert_matches!(client.state(), State::Disconnected);
     }
 
+    #[test(tokio::test)]
+    async fn login_auth_grant_within_max_age() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        grant.record_at(Instant::now() - Duration::from_secs(30));
+        let token_provider = MockTokenProvider::new(1, None).with_grant(grant);
+
+        setup_test_client_with_token_provider(MockTransport::default(), token_provider, false, 0)
+            .await;
+    }
+
+    #[test(tokio::test)]
+    async fn login_auth_grant_exceeded_max_age() {
+        let shutdown_token = CancellationToken::new();
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        grant.record_at(Instant::now() - Duration::from_secs(61));
+        let token_provider = MockTokenProvider::new(1, None).with_grant(grant);
+
+        let client = SignalingClient::new(
+            MockTransport::default(),
+            token_provider,
+            |_| async {},
+            shutdown_token.clone(),
+            false,
+            Duration::from_millis(100),
+            0,
+            &tokio::runtime::Handle::current(),
+        );
+
+        let res = client.connect(None).await;
+        assert_matches!(res, Err(SignalingError::AuthExpiredNeedsReauth));
+        assert_matches!(client.state(), State::Disconnected);
+    }
+
+    fn is_login(msg: &tungstenite::Message) -> bool {
+        matches!(
+            msg,
+            tungstenite::Message::Text(text)
+                if matches!(ClientMessage::deserialize(text), Ok(ClientMessage::Login(_)))
+        )
+    }
+
+    /// Sends a close frame with `code` from the server and returns the resulting error event.
+    async fn receive_close_frame(
+        client: &SignalingClient<MockTransport, MockTokenProvider>,
+        mock_tx: &broadcast::Sender<tungstenite::Message>,
+        code: CloseCode,
+    ) -> SignalingRuntimeError {
+        let mut events = client.subscribe();
+        mock_tx
+            .send(tungstenite::Message::Close(Some(CloseFrame {
+                code,
+                reason: "closed by test".into(),
+            })))
+            .unwrap();
+
+        match events
+            .recv_with_timeout(Duration::from_millis(500), |event| {
+                matches!(event, SignalingEvent::Error(_))
+            })
+            .await
+        {
+            Ok(SignalingEvent::Error(err)) => err,
+            other => panic!("expected error event, got {other:?}"),
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn close_frame_policy_violation_does_not_reconnect() {
+        let transport = MockTransport::default();
+        let mock_tx = transport.incoming_tx.clone();
+        let mut outgoing_rx = transport.outgoing_tx.subscribe();
+        let (client, _shutdown_token) = setup_test_client(transport, false, 8).await;
+
+        let err = receive_close_frame(&client, &mock_tx, CloseCode::Policy).await;
+        assert_matches!(
+            &err,
+            SignalingRuntimeError::Closed(CloseReason::PolicyViolation(reason)) if reason == "closed by test"
+        );
+        assert!(!err.can_reconnect());
+
+        let mut state_rx = client.subscribe_state();
+        tokio::time::timeout(
+            Duration::from_millis(500),
+            state_rx.wait_for(|state| *state == State::Disconnected),
+        )
+        .await
+        .expect("client did not disconnect")
+        .unwrap();
+
+        let login = outgoing_rx
+            .recv_with_timeout(Duration::from_millis(200), is_login)
+            .await;
+        assert!(login.is_err(), "client must not reconnect: {login:?}");
+    }
+
+    #[test(tokio::test)]
+    async fn close_frame_going_away_reconnects() {
+        let transport = MockTransport::default();
+        let mock_tx = transport.incoming_tx.clone();
+        let ready = transport.ready.clone();
+        let outgoing_tx = transport.outgoing_tx.clone();
+        let (client, _shutdown_token) = setup_test_client(transport, false, 8).await;
+        let mut outgoing_rx = outgoing_tx.subscribe();
+        respond_with_session_info(mock_tx.clone(), ready);
+
+        let err = receive_close_frame(&client, &mock_tx, CloseCode::Away).await;
+        assert_matches!(&err, SignalingRuntimeError::Closed(CloseReason::GoingAway));
+        assert!(err.can_reconnect());
+
+        outgoing_rx
+            .recv_with_timeout(Duration::from_secs(2), is_login)
+            .await
+            .expect("client did not reconnect");
+    }
+
+    #[test(tokio::test)]
+    async fn reconnect_auth_grant_exceeded_max_age() {
+        let grant = AuthGrant::new(Some(Duration::from_secs(60)));
+        let token_provider = MockTokenProvider::new(1, None).with_grant(grant.clone());
+        let (client, _shutdown_token) = setup_test_client_with_token_provider(
+            MockTransport::default(),
+            token_provider,
+            false,
+            8,
+        )
+        .await;
+
+        client.disconnect().await;
+        grant.record_at(Instant::now() - Duration::from_secs(61));
+
+        let res = client.inner.reconnect().await;
+        assert_matches!(
+            res,
+            Err(SignalingRuntimeError::ReconnectFailed(
+                ReconnectFailureReason::AuthExpiredNeedsReauth
+            ))
+        );
+    }
+
     #[test(tokio::test)]
     async fn login_invalid_credentials() {
         let transport = MockTransport::default();
@@ -1426,6 +2222,7 @@ mod tests {
                         from_client_id: ClientId::from("client1"),
                         to_client_id: ClientId::from("client2"),
                         sdp: "sdp2".to_string(),
+                        audio_codec: None,
                     },
                 ))
                 .unwrap()
@@ -1491,6 +2288,81 @@ mod tests {
         assert_matches!(client.state(), State::Disconnected);
     }
 
+    mod retry_strategy {
+        use super::super::*;
+        use pretty_assertions::assert_eq;
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        use test_log::test;
+
+        fn seeded(seed: u64) -> RetryStrategy {
+            RetryStrategy::default().with_rng(StdRng::seed_from_u64(seed))
+        }
+
+        #[test]
+        fn max_delay_doubles_until_cap() {
+            let strategy = RetryStrategy::default();
+            let max_delays = (0..10)
+                .map(|attempt| strategy.max_delay(attempt))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                max_delays,
+                vec![
+                    Duration::ZERO,
+                    Duration::from_millis(100),
+                    Duration::from_millis(200),
+                    Duration::from_millis(400),
+                    Duration::from_millis(800),
+                    Duration::from_millis(1600),
+                    Duration::from_millis(3200),
+                    Duration::from_secs(5),
+                    Duration::from_secs(5),
+                    Duration::from_secs(5),
+                ]
+            );
+        }
+
+        #[test]
+        fn max_delay_saturates_for_large_attempts() {
+            let strategy = RetryStrategy::default();
+            assert_eq!(strategy.max_delay(64), Duration::from_secs(5));
+            assert_eq!(strategy.max_delay(u32::MAX), Duration::from_secs(5));
+        }
+
+        #[test]
+        fn timeout_uses_injected_rng() {
+            let mut strategy = seeded(42);
+            let timeouts = (0..10)
+                .map(|attempt| strategy.timeout(attempt))
+                .collect::<Vec<_>>();
+
+            // Replays the full jitter draws on an identically seeded RNG
+            let mut rng = StdRng::seed_from_u64(42);
+            let expected = (0..10)
+                .map(|attempt| match strategy.max_delay(attempt).as_nanos() {
+                    0 => Duration::ZERO,
+                    max => Duration::from_nanos(rng.random_range(0..=max) as u64),
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(timeouts, expected);
+            assert_eq!(timeouts[0], Duration::ZERO);
+            for (attempt, timeout) in timeouts.iter().enumerate() {
+                assert!(*timeout <= strategy.max_delay(attempt as u32));
+            }
+        }
+
+        #[test]
+        fn timeout_deterministic_for_same_seed() {
+            let mut a = seeded(7);
+            let mut b = seeded(7);
+            for attempt in 0..20 {
+                assert_eq!(a.timeout(attempt), b.timeout(attempt));
+            }
+        }
+    }
+
     mod reconnect_gate {
         use super::super::*;
         use pretty_assertions::assert_eq;
@@ -3,6 +3,8 @@ use std::ops::Add;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio_tungstenite::tungstenite;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use vacs_protocol::ws::server::{DisconnectReason, LoginFailureReason};
 use vacs_protocol::ws::shared::ErrorReason;
 
@@ -16,6 +18,8 @@ pub enum SignalingError {
     ProtocolError(String),
     #[error("timeout: {0}")]
     Timeout(String),
+    #[error("auth grant expired, interactive reauthentication required")]
+    AuthExpiredNeedsReauth,
     #[error("runtime error: {0:?}")]
     Runtime(SignalingRuntimeError),
     #[error("{0}")]
@@ -38,11 +42,16 @@ pub enum SignalingRuntimeError {
     SerializationError(String),
     #[error("rate limited for {0}")]
     RateLimited(UntilInstant),
+    #[error("send buffer full")]
+    SendBufferFull,
+    #[error("connection closed: {0:?}")]
+    Closed(CloseReason),
 }
 
 impl SignalingRuntimeError {
     pub fn can_reconnect(&self) -> bool {
         matches!(self, SignalingRuntimeError::Disconnected(reason) if reason.is_none())
+            || matches!(self, SignalingRuntimeError::Closed(reason) if reason.can_reconnect())
             || matches!(
                 self,
                 SignalingRuntimeError::ServerError(_)
@@ -55,6 +64,7 @@ impl SignalingRuntimeError {
         matches!(
             self,
             SignalingRuntimeError::Disconnected(_)
+                | SignalingRuntimeError::Closed(_)
                 | SignalingRuntimeError::ReconnectFailed(_)
                 | SignalingRuntimeError::ReconnectSuppressed(_)
                 | SignalingRuntimeError::ServerError(_)
@@ -73,9 +83,63 @@ impl From<SignalingRuntimeError> for SignalingError {
 pub enum ReconnectFailureReason {
     Connection,
     Login(LoginFailureReason),
+    AuthExpiredNeedsReauth,
     Other(String),
 }
 
+/// Reason the server closed the WebSocket connection, derived from the status code of its close
+/// frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloseReason {
+    /// Normal closure, or a close frame without status code.
+    Normal,
+    /// The server is going away, e.g. due to a shutdown or restart.
+    GoingAway,
+    /// The server closed the connection because the client violated its policy.
+    PolicyViolation(String),
+    /// The server could not process the client's messages, e.g. due to a protocol error or
+    /// invalid or oversized data.
+    Protocol { code: u16, reason: String },
+    /// The server encountered a transient failure and asked the client to try again later.
+    ServerError(u16),
+    /// Any other status code.
+    Other(u16),
+}
+
+impl CloseReason {
+    pub fn from_frame(frame: Option<&CloseFrame>) -> Self {
+        let Some(frame) = frame else {
+            return CloseReason::Normal;
+        };
+        let reason = frame.reason.to_string();
+        match frame.code {
+            CloseCode::Normal | CloseCode::Status => CloseReason::Normal,
+            CloseCode::Away => CloseReason::GoingAway,
+            CloseCode::Policy => CloseReason::PolicyViolation(reason),
+            code @ (CloseCode::Protocol
+            | CloseCode::Unsupported
+            | CloseCode::Invalid
+            | CloseCode::Size) => CloseReason::Protocol {
+                code: code.into(),
+                reason,
+            },
+            code @ (CloseCode::Error | CloseCode::Restart | CloseCode::Again) => {
+                CloseReason::ServerError(code.into())
+            }
+            code => CloseReason::Other(code.into()),
+        }
+    }
+
+    /// Whether reconnecting is expected to succeed. Reconnecting after the server rejected the
+    /// client's behaviour or messages would most likely be rejected again.
+    pub fn can_reconnect(&self) -> bool {
+        !matches!(
+            self,
+            CloseReason::PolicyViolation(_) | CloseReason::Protocol { .. }
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TransportFailureReason {
     Send(String),
@@ -90,12 +154,21 @@ impl From<SignalingError> for ReconnectFailureReason {
             SignalingError::Transport(_) => ReconnectFailureReason::Connection,
             SignalingError::ProtocolError(reason) => ReconnectFailureReason::Other(reason),
             SignalingError::Timeout(reason) => ReconnectFailureReason::Other(reason),
+            SignalingError::AuthExpiredNeedsReauth => {
+                ReconnectFailureReason::AuthExpiredNeedsReauth
+            }
             SignalingError::Runtime(error) => match error {
                 SignalingRuntimeError::Disconnected(_)
                 | SignalingRuntimeError::ServerError(_)
                 | SignalingRuntimeError::SerializationError(_) => {
                     ReconnectFailureReason::Connection
                 }
+                SignalingRuntimeError::Closed(reason) if reason.can_reconnect() => {
+                    ReconnectFailureReason::Connection
+                }
+                SignalingRuntimeError::Closed(reason) => {
+                    ReconnectFailureReason::Other(format!("connection closed: {reason:?}"))
+                }
                 _ => {
                     unreachable!("SignalingRuntimeError is not valid as ReconnectFailureReason");
                 }
@@ -129,3 +202,82 @@ impl From<u64> for UntilInstant {
         UntilInstant(Instant::now().add(Duration::from_secs(value)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_matches};
+
+    fn close_frame(code: u16, reason: &str) -> CloseFrame {
+        CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.to_string().into(),
+        }
+    }
+
+    #[test]
+    fn close_reason_from_frame() {
+        assert_eq!(CloseReason::from_frame(None), CloseReason::Normal);
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(1000, ""))),
+            CloseReason::Normal
+        );
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(1001, "shutting down"))),
+            CloseReason::GoingAway
+        );
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(1008, "banned"))),
+            CloseReason::PolicyViolation("banned".to_string())
+        );
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(1009, "too big"))),
+            CloseReason::Protocol {
+                code: 1009,
+                reason: "too big".to_string()
+            }
+        );
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(1013, ""))),
+            CloseReason::ServerError(1013)
+        );
+        assert_eq!(
+            CloseReason::from_frame(Some(&close_frame(4000, ""))),
+            CloseReason::Other(4000)
+        );
+    }
+
+    #[test]
+    fn closed_can_reconnect() {
+        for (code, can_reconnect) in [
+            (1000, true),
+            (1001, true),
+            (1002, false),
+            (1008, false),
+            (1011, true),
+            (4000, true),
+        ] {
+            let err = SignalingRuntimeError::Closed(CloseReason::from_frame(Some(&close_frame(
+                code, "",
+            ))));
+            assert!(err.is_fatal());
+            assert_eq!(err.can_reconnect(), can_reconnect, "close code {code}");
+        }
+    }
+
+    #[test]
+    fn closed_reconnect_failure_reason() {
+        assert_matches!(
+            ReconnectFailureReason::from(SignalingError::Runtime(SignalingRuntimeError::Closed(
+                CloseReason::GoingAway
+            ))),
+            ReconnectFailureReason::Connection
+        );
+        assert_matches!(
+            ReconnectFailureReason::from(SignalingError::Runtime(SignalingRuntimeError::Closed(
+                CloseReason::PolicyViolation("banned".to_string())
+            ))),
+            ReconnectFailureReason::Other(_)
+        );
+    }
+}
@@ -167,18 +167,21 @@ mod tests {
                 from_client_id: ClientId::from("client1"),
                 to_client_id: ClientId::from("client2"),
                 sdp: "sdp1".to_string(),
+                audio_codec: None,
             }),
             ServerMessage::WebrtcAnswer(vacs_protocol::ws::shared::WebrtcAnswer {
                 call_id: vacs_protocol::ws::shared::CallId::new(),
                 from_client_id: ClientId::from("client2"),
                 to_client_id: ClientId::from("client3"),
                 sdp: "sdp2".to_string(),
+                audio_codec: None,
             }),
             ServerMessage::WebrtcAnswer(vacs_protocol::ws::shared::WebrtcAnswer {
                 call_id: vacs_protocol::ws::shared::CallId::new(),
                 from_client_id: ClientId::from("client3"),
                 to_client_id: ClientId::from("client1"),
                 sdp: "sdp3".to_string(),
+                audio_codec: None,
             }),
         ];
 
@@ -389,6 +392,7 @@ mod tests {
                 from_client_id: ClientId::from("client2"),
                 to_client_id: ClientId::from("client1"),
                 sdp: "sdp2".into(),
+                audio_codec: None,
             },
         ));
 
@@ -14,6 +14,12 @@ pub trait SignalingTransport: Send + Sync + 'static {
     type Receiver: SignalingReceiver;
 
     async fn connect(&self) -> Result<(Self::Sender, Self::Receiver), SignalingError>;
+
+    /// Returns the endpoint of the most recent successful connection, if the transport connects
+    /// to an addressable server.
+    fn endpoint(&self) -> Option<String> {
+        None
+    }
 }
 
 #[async_trait]
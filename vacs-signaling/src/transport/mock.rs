@@ -1,7 +1,11 @@
-use crate::error::{SignalingError, SignalingRuntimeError, TransportFailureReason};
+use crate::error::{CloseReason, SignalingError, SignalingRuntimeError, TransportFailureReason};
 use crate::transport::{SignalingReceiver, SignalingSender, SignalingTransport};
 use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, mpsc};
 use tokio_tungstenite::tungstenite;
 use tokio_util::sync::CancellationToken;
@@ -14,6 +18,56 @@ pub struct MockTransport {
     pub incoming_rx: broadcast::Receiver<tungstenite::Message>,
     pub ready: Arc<tokio::sync::Notify>,
     pub disconnect_token: CancellationToken,
+    /// Delay applied before every sent message, simulating a slow uplink.
+    pub send_delay: Option<Duration>,
+    network: Arc<Mutex<SimulatedNetwork>>,
+}
+
+/// Degraded network conditions simulated by the [`MockTransport`], applied to text and ping
+/// messages in both directions. Messages are delayed one after another, so they are never
+/// reordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkConditions {
+    /// Delay applied to every message.
+    pub latency: Duration,
+    /// Maximum random delay added on top of `latency`.
+    pub jitter: Duration,
+    /// Probability (`0.0..=1.0`) of a message being lost.
+    pub drop_rate: f64,
+}
+
+#[derive(Debug)]
+struct SimulatedNetwork {
+    conditions: NetworkConditions,
+    rng: StdRng,
+}
+
+impl Default for SimulatedNetwork {
+    fn default() -> Self {
+        Self {
+            conditions: NetworkConditions::default(),
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+}
+
+impl SimulatedNetwork {
+    /// Returns the delay to apply to the next message, or `None` if it is lost.
+    fn next_delay(&mut self) -> Option<Duration> {
+        let conditions = self.conditions;
+        if conditions.drop_rate > 0.0 && self.rng.random_bool(conditions.drop_rate.min(1.0)) {
+            return None;
+        }
+        let jitter = if conditions.jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos(
+                self.rng
+                    .random_range(0..=conditions.jitter.as_nanos() as u64),
+            )
+        };
+        Some(conditions.latency + jitter)
+    }
 }
 
 impl Default for MockTransport {
@@ -27,6 +81,8 @@ impl Default for MockTransport {
             incoming_rx,
             ready: Arc::new(tokio::sync::Notify::new()),
             disconnect_token: CancellationToken::new(),
+            send_delay: None,
+            network: Default::default(),
         }
     }
 }
@@ -35,6 +91,42 @@ impl MockTransport {
     pub fn disconnect_token(&self) -> CancellationToken {
         self.disconnect_token.clone()
     }
+
+    pub fn with_send_delay(mut self, send_delay: Duration) -> Self {
+        self.send_delay = Some(send_delay);
+        self
+    }
+
+    /// Simulates the given network conditions, drawing delays and losses from an RNG seeded with
+    /// `seed` to keep test runs deterministic.
+    pub fn with_network_conditions(self, conditions: NetworkConditions, seed: u64) -> Self {
+        *self.network.lock() = SimulatedNetwork {
+            conditions,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        self
+    }
+
+    /// Returns a handle to change the simulated network conditions at runtime, e.g. to let a
+    /// degraded connection recover after the transport was moved into a client.
+    pub fn network_conditions_handle(&self) -> NetworkConditionsHandle {
+        NetworkConditionsHandle {
+            network: self.network.clone(),
+        }
+    }
+}
+
+/// Handle to change the network conditions simulated by a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct NetworkConditionsHandle {
+    network: Arc<Mutex<SimulatedNetwork>>,
+}
+
+impl NetworkConditionsHandle {
+    /// Replaces the simulated network conditions, applying to existing connections as well.
+    pub fn set(&self, conditions: NetworkConditions) {
+        self.network.lock().conditions = conditions;
+    }
 }
 
 #[async_trait]
@@ -46,10 +138,13 @@ impl SignalingTransport for MockTransport {
         let sender = MockSender {
             tx: Some(self.outgoing_tx.clone()),
             disconnect_token: self.disconnect_token.child_token(),
+            send_delay: self.send_delay,
+            network: self.network.clone(),
         };
         let receiver = MockReceiver {
             rx: self.incoming_tx.subscribe(),
             disconnect_token: self.disconnect_token.child_token(),
+            network: self.network.clone(),
         };
 
         self.ready.notify_one();
@@ -61,11 +156,33 @@ impl SignalingTransport for MockTransport {
 pub struct MockSender {
     tx: Option<broadcast::Sender<tungstenite::Message>>,
     disconnect_token: CancellationToken,
+    send_delay: Option<Duration>,
+    network: Arc<Mutex<SimulatedNetwork>>,
 }
 
 pub struct MockReceiver {
     rx: broadcast::Receiver<tungstenite::Message>,
     disconnect_token: CancellationToken,
+    network: Arc<Mutex<SimulatedNetwork>>,
+}
+
+impl MockReceiver {
+    /// Applies the simulated network conditions to a received message, returning whether it
+    /// should be delivered.
+    async fn deliver(&self) -> bool {
+        let delay = self.network.lock().next_delay();
+        let Some(delay) = delay else {
+            tracing::debug!("Dropping received message due to simulated network conditions");
+            return false;
+        };
+        if !delay.is_zero() {
+            tokio::select! {
+                _ = self.disconnect_token.cancelled() => {}
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+        true
+    }
 }
 
 #[async_trait]
@@ -73,6 +190,20 @@ impl SignalingSender for MockSender {
     #[tracing::instrument(level = "debug", skip(self))]
     async fn send(&mut self, msg: tungstenite::Message) -> Result<(), SignalingRuntimeError> {
         tracing::debug!("Sending SignalingMessage");
+        if let Some(send_delay) = self.send_delay {
+            tokio::time::sleep(send_delay).await;
+        }
+        if matches!(
+            msg,
+            tungstenite::Message::Text(_) | tungstenite::Message::Ping(_)
+        ) {
+            let delay = self.network.lock().next_delay();
+            let Some(delay) = delay else {
+                tracing::debug!("Dropping sent message due to simulated network conditions");
+                return Ok(());
+            };
+            tokio::time::sleep(delay).await;
+        }
         if self.disconnect_token.is_cancelled() {
             return Err(SignalingRuntimeError::Transport(
                 TransportFailureReason::Send("Sender closed".to_string()),
@@ -117,6 +248,7 @@ impl SignalingReceiver for MockReceiver {
                 msg = self.rx.recv() => {
                     tracing::debug!(?msg, "Received tungstenite::Message");
                     match msg {
+                        Ok(tungstenite::Message::Text(_) | tungstenite::Message::Ping(_)) if !self.deliver().await => {}
                         Ok(tungstenite::Message::Text(text)) => {
                             tracing::debug!("Received message");
                             return ServerMessage::deserialize(&text).map_err(|err| {
@@ -124,9 +256,10 @@ impl SignalingReceiver for MockReceiver {
                                 SignalingRuntimeError::SerializationError(err.to_string())
                             });
                         }
-                        Ok(tungstenite::Message::Close(reason)) => {
-                            tracing::warn!(?reason, "Received Close WebSocket frame");
-                            return Err(SignalingRuntimeError::Disconnected(None));
+                        Ok(tungstenite::Message::Close(frame)) => {
+                            let reason = CloseReason::from_frame(frame.as_ref());
+                            tracing::warn!(?frame, ?reason, "Received Close WebSocket frame");
+                            return Err(SignalingRuntimeError::Closed(reason));
                         }
                         Ok(tungstenite::Message::Ping(data)) => {
                             if let Err(err) = send_tx.send(tungstenite::Message::Pong(data)).await {
@@ -1,10 +1,11 @@
-use crate::error::{SignalingError, SignalingRuntimeError, TransportFailureReason};
+use crate::error::{CloseReason, SignalingError, SignalingRuntimeError, TransportFailureReason};
 use crate::transport::{SignalingReceiver, SignalingSender, SignalingTransport};
 use async_trait::async_trait;
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::RwLock;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tokio::sync::{Notify, mpsc, watch};
@@ -18,17 +19,30 @@ use vacs_protocol::ws::server::ServerMessage;
 const HEARTBEAT_PING_INTERVAL: Duration = Duration::from_secs(15);
 const HEARTBEAT_PONG_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// WebSocket transport connecting to a primary signaling server and optional backup servers.
+///
+/// Every connect attempt starts at the endpoint of the last successful connection and fails over
+/// to the remaining endpoints in order, so repeated reconnects alternate between servers while
+/// the client's reconnect backoff applies between attempts.
 #[derive(Debug, Clone)]
 pub struct TokioTransport {
-    url: String,
+    endpoints: Vec<String>,
+    current: Arc<AtomicUsize>,
 }
 
 impl TokioTransport {
     pub fn new(url: &str) -> Self {
         Self {
-            url: url.to_string(),
+            endpoints: vec![url.to_string()],
+            current: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Adds a backup endpoint used if connecting to the previous endpoints fails.
+    pub fn with_backup(mut self, url: &str) -> Self {
+        self.endpoints.push(url.to_string());
+        self
+    }
 }
 
 #[async_trait]
@@ -38,20 +52,43 @@ impl SignalingTransport for TokioTransport {
 
     #[tracing::instrument(level = "info", err)]
     async fn connect(&self) -> Result<(Self::Sender, Self::Receiver), SignalingError> {
-        let (websocket_stream, response) = tokio_tungstenite::connect_async(&self.url)
-            .await
-            .map_err(|err| {
-                tracing::error!(?err, "Failed to connect to signaling server");
-                SignalingError::Transport(err.into())
-            })?;
-        tracing::debug!(?response, "WebSocket handshake response");
-
-        let (websocket_tx, websocket_rx) = websocket_stream.split();
-
-        Ok((
-            TokioSender::new(websocket_tx),
-            TokioReceiver::new(websocket_rx),
-        ))
+        let start = self.current.load(Ordering::Relaxed);
+        let mut error = None;
+
+        for offset in 0..self.endpoints.len() {
+            let index = (start + offset) % self.endpoints.len();
+            let url = &self.endpoints[index];
+
+            let (websocket_stream, response) = match tokio_tungstenite::connect_async(url).await {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::error!(?err, %url, "Failed to connect to signaling server");
+                    error = Some(SignalingError::Transport(err.into()));
+                    continue;
+                }
+            };
+            tracing::debug!(?response, "WebSocket handshake response");
+
+            if index != start {
+                tracing::info!(%url, "Failed over to signaling server");
+            }
+            self.current.store(index, Ordering::Relaxed);
+
+            let (websocket_tx, websocket_rx) = websocket_stream.split();
+
+            return Ok((
+                TokioSender::new(websocket_tx),
+                TokioReceiver::new(websocket_rx),
+            ));
+        }
+
+        Err(error.unwrap_or_else(|| SignalingError::Other("No endpoints configured".to_string())))
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        self.endpoints
+            .get(self.current.load(Ordering::Relaxed))
+            .cloned()
     }
 }
 
@@ -141,9 +178,10 @@ impl SignalingReceiver for TokioReceiver {
                                 }
                             };
                         }
-                        Ok(tungstenite::Message::Close(reason)) => {
-                            tracing::warn!(?reason, "Received Close WebSocket frame");
-                            return Err(SignalingRuntimeError::Disconnected(None));
+                        Ok(tungstenite::Message::Close(frame)) => {
+                            let reason = CloseReason::from_frame(frame.as_ref());
+                            tracing::warn!(?frame, ?reason, "Received Close WebSocket frame");
+                            return Err(SignalingRuntimeError::Closed(reason));
                         }
                         Ok(tungstenite::Message::Ping(data)) => {
                             self.heartbeat_state.mark_rx();
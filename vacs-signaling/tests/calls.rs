@@ -50,6 +50,7 @@ async fn call_offer_answer() {
             vacs_protocol::ws::shared::CallAccept {
                 call_id,
                 accepting_client_id: ClientId::from("client1"),
+                auto_answered: false,
             },
         ))
         .await
@@ -76,6 +77,7 @@ async fn call_offer_answer() {
                 from_client_id: ClientId::from("client0"),
                 to_client_id: ClientId::from("client1"),
                 sdp: "sdp0".to_string(),
+                audio_codecs: Vec::new(),
             },
         ))
         .await
@@ -102,6 +104,7 @@ async fn call_offer_answer() {
                 from_client_id: ClientId::from("client1"),
                 to_client_id: ClientId::from("client0"),
                 sdp: "sdp1".to_string(),
+                audio_codec: None,
             },
         ))
         .await
@@ -0,0 +1,78 @@
+use pretty_assertions::{assert_eq, assert_matches};
+use std::time::Duration;
+use test_log::test;
+use tokio_util::sync::CancellationToken;
+use vacs_server::test_utils::TestApp;
+use vacs_signaling::auth::mock::MockTokenProvider;
+use vacs_signaling::client::{SignalingClient, SignalingEvent};
+use vacs_signaling::error::SignalingError;
+use vacs_signaling::test_utils::RecvWithTimeoutExt;
+use vacs_signaling::transport::SignalingTransport;
+use vacs_signaling::transport::tokio::TokioTransport;
+
+/// Returns a WebSocket URL no server is listening on.
+fn unreachable_addr() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("ws://{addr}/ws")
+}
+
+fn signaling_client(
+    transport: TokioTransport,
+    shutdown_token: &CancellationToken,
+) -> SignalingClient<TokioTransport, MockTokenProvider> {
+    SignalingClient::new(
+        transport,
+        MockTokenProvider::new(1, None),
+        |_| async {},
+        shutdown_token.clone(),
+        false,
+        Duration::from_millis(100),
+        8,
+        &tokio::runtime::Handle::current(),
+    )
+}
+
+#[test(tokio::test)]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn connect_fails_over_to_backup() {
+    let test_app = TestApp::new().await;
+    let primary = unreachable_addr();
+
+    let transport = TokioTransport::new(&primary).with_backup(test_app.addr());
+    let shutdown_token = CancellationToken::new();
+    let client = signaling_client(transport.clone(), &shutdown_token);
+
+    let mut broadcast_rx = client.subscribe();
+    let res = client.connect(None).await;
+    let connected_event = broadcast_rx
+        .recv_with_timeout(Duration::from_millis(100), |event| {
+            matches!(event, SignalingEvent::Connected { endpoint, .. } if endpoint.as_deref() == Some(test_app.addr()))
+        })
+        .await;
+
+    assert!(res.is_ok());
+    assert!(connected_event.is_ok());
+    assert_eq!(transport.endpoint().as_deref(), Some(test_app.addr()));
+
+    shutdown_token.cancel();
+    client.disconnect().await;
+}
+
+#[test(tokio::test)]
+async fn connect_fails_if_all_endpoints_unreachable() {
+    let primary = unreachable_addr();
+    let backup = unreachable_addr();
+
+    let transport = TokioTransport::new(&primary).with_backup(&backup);
+    let shutdown_token = CancellationToken::new();
+    let client = signaling_client(transport, &shutdown_token);
+
+    let res = client.connect(None).await;
+
+    assert_matches!(res, Err(SignalingError::Transport(_)));
+
+    shutdown_token.cancel();
+    client.disconnect().await;
+}
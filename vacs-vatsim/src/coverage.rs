@@ -2,11 +2,15 @@ pub mod flight_information_region;
 pub mod network;
 pub mod position;
 pub mod profile;
+pub mod relief;
+pub mod simulate;
 pub mod station;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_support;
 
 use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Clone, Error)]
@@ -22,6 +26,9 @@ pub enum CoverageError {
 
     #[error(transparent)]
     Context(#[from] Context),
+
+    #[error(transparent)]
+    Warning(#[from] CoverageWarning),
 }
 
 #[derive(Debug, Clone, Error)]
@@ -57,6 +64,12 @@ pub enum ValidationError {
     #[error("station `{0}` has no coverage")]
     EmptyCoverage(String),
 
+    #[error("station `{station}` is defined multiple times: {}", .definitions.join(", "))]
+    DuplicateStation {
+        station: String,
+        definitions: Vec<String>,
+    },
+
     #[error("cycle detected in station parent chain: {chain:?}")]
     CycleDetected { chain: Vec<String> },
 
@@ -66,6 +79,9 @@ pub enum ValidationError {
     #[error("fields {fields:?} are mutually exclusive")]
     MutuallyExclusive { fields: Vec<String> },
 
+    #[error("positions without a profile: {}", .positions.join(", "))]
+    MissingProfiles { positions: Vec<String> },
+
     #[error("{0}")]
     Custom(String),
 }
@@ -86,6 +102,12 @@ pub enum IoError {
 
     #[error("failed to read directory entry: {0}")]
     ReadEntry(String),
+
+    #[error("failed to write `{path}`: {reason}")]
+    Write {
+        path: std::path::PathBuf,
+        reason: String,
+    },
 }
 
 #[derive(Debug, Clone, Error)]
@@ -99,6 +121,9 @@ pub enum StructureError {
         id: String,
         reason: String,
     },
+
+    #[error("more than {max} {entity} entries found")]
+    TooMany { entity: String, max: usize },
 }
 
 #[derive(Debug, Clone, Error)]
@@ -109,6 +134,44 @@ pub struct Context {
     pub error: Box<CoverageError>,
 }
 
+/// Non-fatal issue detected while loading coverage data. Warnings do not prevent the network from
+/// loading, but likely point at mistakes in the dataset.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CoverageWarning {
+    #[error("station `{station_id}` is referenced {count} times in profile `{profile_id}`")]
+    RepeatedStationReference {
+        profile_id: String,
+        station_id: String,
+        count: usize,
+    },
+
+    #[error(
+        "position `{position_id}` uses frequency {frequency}, which is implausible for facility type {facility_type}"
+    )]
+    ImplausibleFrequency {
+        position_id: String,
+        frequency: String,
+        facility_type: String,
+    },
+
+    #[error("station `{station_id}` of profile `{profile_id}` is not covered by any position")]
+    UncoverableProfileStation {
+        profile_id: String,
+        station_id: String,
+    },
+
+    #[error(
+        "container `{path}` of profile `{profile_id}` has a {dimension} of {declared}rem, but its children require at least {required}rem"
+    )]
+    GeoLayoutOverflow {
+        profile_id: String,
+        path: String,
+        dimension: String,
+        declared: String,
+        required: String,
+    },
+}
+
 impl CoverageError {
     pub fn context(self, location: impl Into<String>) -> Self {
         Self::Context(Context {
@@ -118,6 +181,145 @@ impl CoverageError {
     }
 }
 
+/// Maximum number of attempts made when reading a dataset file before giving up.
+const MAX_READ_ATTEMPTS: u32 = 3;
+/// Base backoff between read attempts, doubled after every failed attempt.
+const READ_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Reads the file at `path`, retrying transient I/O errors (e.g. on flaky network-mounted
+/// dataset directories) a bounded number of times with a short backoff.
+pub(crate) fn read_file(path: &Path) -> Result<Vec<u8>, IoError> {
+    read_file_with(path, std::fs::read)
+}
+
+/// Compression formats recognized by their file extension, only gzip is supported.
+const COMPRESSION_EXTENSIONS: &[&str] = &["gz", "zst", "xz", "bz2", "br"];
+/// Maximum size of a decompressed dataset file, guarding against decompression bombs.
+const MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Reads the dataset file at `path` like [`read_file`], transparently decompressing gzipped
+/// files (e.g. `stations.toml.gz`). Returns the content along with the extension of the
+/// contained format, e.g. `toml` for both `profile.toml` and `profile.toml.gz`.
+pub(crate) fn read_dataset_file(path: &Path) -> Result<(String, Vec<u8>), IoError> {
+    let extension = |path: &Path| {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let ext = extension(path);
+    if !COMPRESSION_EXTENSIONS.contains(&ext.as_str()) {
+        return Ok((ext, read_file(path)?));
+    }
+    if ext != "gz" {
+        tracing::warn!(?ext, "Unsupported compression");
+        return Err(IoError::Read {
+            path: path.into(),
+            reason: format!("unsupported compression: {ext}, only gzip (.gz) is supported"),
+        });
+    }
+
+    let compressed = read_file(path)?;
+    let bytes = decompress_gzip(&compressed, MAX_DECOMPRESSED_SIZE).map_err(|reason| {
+        tracing::warn!(?path, %reason, "Failed to decompress file");
+        IoError::Read {
+            path: path.into(),
+            reason,
+        }
+    })?;
+    tracing::trace!(
+        compressed = compressed.len(),
+        decompressed = bytes.len(),
+        "Decompressed file"
+    );
+
+    Ok((
+        extension(Path::new(path.file_stem().unwrap_or_default())),
+        bytes,
+    ))
+}
+
+/// Decompresses gzipped `compressed` content, failing if it exceeds `limit` bytes once
+/// decompressed.
+fn decompress_gzip(compressed: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let mut bytes = Vec::new();
+    flate2::read::GzDecoder::new(compressed)
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| format!("failed to decompress: {err}"))?;
+    if bytes.len() as u64 > limit {
+        return Err(format!(
+            "decompressed content exceeds the maximum size of {limit} bytes"
+        ));
+    }
+    Ok(bytes)
+}
+
+fn read_file_with<F>(path: &Path, mut read: F) -> Result<Vec<u8>, IoError>
+where
+    F: FnMut(&Path) -> std::io::Result<Vec<u8>>,
+{
+    let mut backoff = READ_RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match read(path) {
+            Ok(bytes) => return Ok(bytes),
+            Err(err) if attempt < MAX_READ_ATTEMPTS && is_retryable(&err) => {
+                tracing::debug!(
+                    ?path,
+                    ?err,
+                    attempt,
+                    ?backoff,
+                    "Transient read error, retrying"
+                );
+                std::thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => {
+                return Err(IoError::Read {
+                    path: path.into(),
+                    reason: err.to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn is_retryable(err: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        err.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::TimedOut
+            | ErrorKind::WouldBlock
+            | ErrorKind::ResourceBusy
+            | ErrorKind::StaleNetworkFileHandle
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+    )
+}
+
+/// Ensures a dataset ID only consists of ASCII letters, digits, `_` and `-`, so stray whitespace,
+/// control characters or separators can't break matching or serialization.
+pub(crate) fn validate_id(id: &str) -> Result<(), CoverageError> {
+    if id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Ok(());
+    }
+    Err(ValidationError::InvalidFormat {
+        field: "id".to_string(),
+        value: id.to_string(),
+        reason: format!("`{id}` may only contain ASCII letters, digits, `_` and `-`"),
+    }
+    .into())
+}
+
 pub trait Validator {
     fn validate(&self) -> Result<(), CoverageError>;
 }
@@ -125,3 +327,82 @@ pub trait Validator {
 pub trait ReferenceValidator<T> {
     fn validate_references(&self, entities: &HashSet<&T>) -> Result<(), CoverageError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_matches};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn read_file_retries_transient_error() {
+        let mut calls = 0;
+        let bytes = read_file_with(Path::new("profile.toml"), |_| {
+            calls += 1;
+            if calls == 1 {
+                Err(std::io::Error::from(ErrorKind::Interrupted))
+            } else {
+                Ok(b"id = \"LOVV\"".to_vec())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(calls, 2);
+        assert_eq!(bytes, b"id = \"LOVV\"".to_vec());
+    }
+
+    #[test]
+    fn read_file_gives_up_after_max_attempts() {
+        let mut calls = 0;
+        let result = read_file_with(Path::new("profile.toml"), |_| {
+            calls += 1;
+            Err(std::io::Error::from(ErrorKind::TimedOut))
+        });
+
+        assert_eq!(calls, MAX_READ_ATTEMPTS);
+        assert_matches!(result, Err(IoError::Read { .. }));
+    }
+
+    fn gzip(content: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn decompress_gzip_within_limit() {
+        let content = vec![b'a'; 1024];
+        assert_eq!(decompress_gzip(&gzip(&content), 1024).unwrap(), content);
+    }
+
+    #[test]
+    fn decompress_gzip_exceeding_limit() {
+        let compressed = gzip(&vec![b'a'; 1025]);
+        assert_matches!(
+            decompress_gzip(&compressed, 1024),
+            Err(reason) if reason.contains("maximum size of 1024 bytes")
+        );
+    }
+
+    #[test]
+    fn decompress_gzip_invalid_content() {
+        assert_matches!(
+            decompress_gzip(b"not gzip", 1024),
+            Err(reason) if reason.starts_with("failed to decompress")
+        );
+    }
+
+    #[test]
+    fn read_file_fails_fast_on_permanent_error() {
+        let mut calls = 0;
+        let result = read_file_with(Path::new("profile.toml"), |_| {
+            calls += 1;
+            Err(std::io::Error::from(ErrorKind::NotFound))
+        });
+
+        assert_eq!(calls, 1);
+        assert_matches!(result, Err(IoError::Read { .. }));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::coverage::position::{PositionConfigFile, PositionRaw};
 use crate::coverage::profile::{FromRaw, Profile, ProfileRaw};
-use crate::coverage::station::{StationConfigFile, StationRaw};
-use crate::coverage::{CoverageError, IoError, ValidationError, Validator};
+use crate::coverage::station::{StationConfigFile, StationGroupRaw, StationRaw};
+use crate::coverage::{
+    CoverageError, IoError, ReferenceValidator, ValidationError, Validator, validate_id,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use vacs_protocol::http::webrtc::IceServer;
 use vacs_protocol::profile::ProfileId;
 use vacs_protocol::vatsim::{PositionId, StationId};
 
@@ -15,16 +18,131 @@ pub struct FlightInformationRegionId(String);
 pub struct FlightInformationRegion {
     pub id: FlightInformationRegionId,
     pub stations: HashSet<StationId>,
+    pub station_groups: HashSet<StationId>,
     pub positions: HashSet<PositionId>,
     pub profiles: HashSet<ProfileId>,
+    pub settings: FirSettings,
 }
 
 #[derive(Clone)]
 pub(super) struct FlightInformationRegionRaw {
     pub id: FlightInformationRegionId,
     pub stations: Vec<StationRaw>,
+    pub station_groups: Vec<StationGroupRaw>,
     pub positions: Vec<PositionRaw>,
     pub profiles: HashMap<ProfileId, Profile>,
+    pub settings: FirSettings,
+}
+
+/// Optional per-FIR override of the ICE servers handed out to clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IceConfigFile {
+    pub ice_servers: Vec<IceServer>,
+}
+
+/// Optional FIR-level settings read from `fir.toml`, applying to all positions of the FIR. A
+/// missing file means defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FirSettings {
+    /// Profile used by positions of the FIR that don't define a profile themselves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<ProfileId>,
+    /// Whether positions of the FIR may originate calls. If `false`, all positions of the FIR
+    /// are receive-only, regardless of their own `transmit` setting.
+    #[serde(default = "default_transmit", skip_serializing_if = "is_transmit")]
+    pub transmit: bool,
+    /// ICE servers handed out to clients on positions of the FIR. May alternatively be defined in
+    /// a separate `ice.toml`, but not in both.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_servers: Option<Vec<IceServer>>,
+    /// Neighboring FIRs whose station changes are sent to clients on positions of the FIR, in
+    /// addition to the FIR's own. If unset, these clients receive station changes of all FIRs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neighbors: Option<Vec<FlightInformationRegionId>>,
+}
+
+impl Default for FirSettings {
+    fn default() -> Self {
+        Self {
+            default_profile: None,
+            transmit: true,
+            ice_servers: None,
+            neighbors: None,
+        }
+    }
+}
+
+impl Validator for FirSettings {
+    fn validate(&self) -> Result<(), CoverageError> {
+        if self.default_profile.as_ref().is_some_and(|p| p.is_empty()) {
+            return Err(ValidationError::Empty {
+                field: "default_profile".to_string(),
+            }
+            .into());
+        }
+        if let Some(ice_servers) = &self.ice_servers {
+            if ice_servers.is_empty() {
+                return Err(ValidationError::Empty {
+                    field: "ice_servers".to_string(),
+                }
+                .into());
+            }
+            if ice_servers
+                .iter()
+                .any(|s| s.urls.is_empty() || s.urls.iter().any(|u| u.is_empty()))
+            {
+                return Err(ValidationError::Empty {
+                    field: "ice_servers.urls".to_string(),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ReferenceValidator<ProfileId> for FirSettings {
+    fn validate_references(&self, profiles: &HashSet<&ProfileId>) -> Result<(), CoverageError> {
+        if let Some(profile_id) = &self.default_profile
+            && !profiles.contains(profile_id)
+        {
+            return Err(ValidationError::MissingReference {
+                field: "default_profile".to_string(),
+                ref_id: profile_id.to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl ReferenceValidator<FlightInformationRegionId> for FirSettings {
+    fn validate_references(
+        &self,
+        firs: &HashSet<&FlightInformationRegionId>,
+    ) -> Result<(), CoverageError> {
+        if let Some(neighbor) = self
+            .neighbors
+            .iter()
+            .flatten()
+            .find(|neighbor| !firs.contains(neighbor))
+        {
+            return Err(ValidationError::MissingReference {
+                field: "neighbors".to_string(),
+                ref_id: neighbor.to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+fn default_transmit() -> bool {
+    true
+}
+
+fn is_transmit(transmit: &bool) -> bool {
+    *transmit
 }
 
 impl std::fmt::Debug for FlightInformationRegion {
@@ -32,8 +150,10 @@ impl std::fmt::Debug for FlightInformationRegion {
         f.debug_struct("FlightInformationRegion")
             .field("id", &self.id)
             .field("stations", &self.stations.len())
+            .field("station_groups", &self.station_groups.len())
             .field("positions", &self.positions.len())
             .field("profiles", &self.profiles.len())
+            .field("settings", &self.settings)
             .finish()
     }
 }
@@ -55,8 +175,10 @@ impl std::fmt::Debug for FlightInformationRegionRaw {
         f.debug_struct("FlightInformationRegionRaw")
             .field("id", &self.id)
             .field("stations", &self.stations.len())
+            .field("station_groups", &self.station_groups.len())
             .field("positions", &self.positions.len())
             .field("profiles", &self.profiles.len())
+            .field("settings", &self.settings)
             .finish()
     }
 }
@@ -81,8 +203,7 @@ impl Validator for FlightInformationRegionRaw {
             }
             .into());
         }
-
-        Ok(())
+        self.settings.validate()
     }
 }
 
@@ -116,13 +237,14 @@ impl FlightInformationRegionRaw {
 
         let mut errors = Vec::new();
 
-        let stations = match Self::read_file::<StationConfigFile>(path, "stations") {
-            Ok(config) => config.stations,
-            Err(err) => {
-                errors.push(err);
-                Vec::new()
-            }
-        };
+        let (stations, station_groups) =
+            match Self::read_file::<StationConfigFile>(path, "stations") {
+                Ok(config) => (config.stations, config.groups),
+                Err(err) => {
+                    errors.push(err);
+                    (Vec::new(), Vec::new())
+                }
+            };
 
         let positions = match Self::read_file::<PositionConfigFile>(path, "positions") {
             Ok(config) => config.positions,
@@ -140,6 +262,47 @@ impl FlightInformationRegionRaw {
             }
         };
 
+        let mut settings = match Self::read_optional_file::<FirSettings>(path, "fir") {
+            Ok(settings) => settings.unwrap_or_default(),
+            Err(err) => {
+                errors.push(err);
+                FirSettings::default()
+            }
+        };
+
+        match Self::read_optional_file::<IceConfigFile>(path, "ice") {
+            Ok(Some(_)) if settings.ice_servers.is_some() => {
+                errors.push(
+                    ValidationError::MutuallyExclusive {
+                        fields: vec!["fir.ice_servers".to_string(), "ice.ice_servers".to_string()],
+                    }
+                    .into(),
+                );
+            }
+            Ok(config) => {
+                if let Some(config) = config {
+                    settings.ice_servers = Some(config.ice_servers);
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+
+        errors.extend(Self::invalid_ids(
+            path,
+            "stations",
+            stations.iter().map(|s| s.id.as_str()),
+        ));
+        errors.extend(Self::invalid_ids(
+            path,
+            "stations",
+            station_groups.iter().map(|g| g.id.as_str()),
+        ));
+        errors.extend(Self::invalid_ids(
+            path,
+            "positions",
+            positions.iter().map(|p| p.id.as_str()),
+        ));
+
         if !errors.is_empty() {
             return Err(errors);
         }
@@ -147,47 +310,143 @@ impl FlightInformationRegionRaw {
         let fir_raw = Self {
             id: FlightInformationRegionId::from(dir_name),
             stations,
+            station_groups,
             positions,
             profiles,
+            settings,
         };
 
         tracing::trace!(?fir_raw, "Successfully loaded FIR");
         Ok(fir_raw)
     }
 
-    const FILE_EXTENSIONS: &'static [&'static str] = &["toml", "json"];
+    /// Writes the FIR to `dir` as TOML files in the layout read by [`Self::load_from_dir`],
+    /// storing every profile in a separate file in the `profiles` subdirectory.
+    #[tracing::instrument(level = "trace", skip(self, dir), fields(dir = tracing::field::Empty))]
+    pub fn write_to_dir(&self, dir: impl AsRef<std::path::Path>) -> Result<(), CoverageError> {
+        let path = dir.as_ref();
+        tracing::Span::current().record("dir", tracing::field::debug(path));
+        tracing::trace!("Writing FIR");
+
+        Self::create_dir(path)?;
+        if !self.profiles.is_empty() {
+            Self::create_dir(&path.join("profiles"))?;
+        }
+        for (name, content) in self.files()? {
+            let file_path = path.join(name);
+            std::fs::write(&file_path, content).map_err(|err| IoError::Write {
+                path: file_path,
+                reason: err.to_string(),
+            })?;
+        }
+
+        tracing::trace!(fir_raw = ?self, "Successfully wrote FIR");
+        Ok(())
+    }
+
+    /// Serializes the FIR into the TOML files written by [`Self::write_to_dir`], returning their
+    /// paths relative to the FIR directory along with their content. Files are sorted by path,
+    /// making the result deterministic for identical FIRs.
+    pub fn files(&self) -> Result<Vec<(String, String)>, CoverageError> {
+        let mut files = vec![
+            self.file(
+                "stations.toml",
+                &StationConfigFile {
+                    stations: self.stations.clone(),
+                    groups: self.station_groups.clone(),
+                },
+            )?,
+            self.file(
+                "positions.toml",
+                &PositionConfigFile {
+                    positions: self.positions.clone(),
+                },
+            )?,
+        ];
+        if self.settings != FirSettings::default() {
+            files.push(self.file("fir.toml", &self.settings)?);
+        }
+        for (profile_id, profile) in &self.profiles {
+            files.push(self.file(
+                &format!("profiles/{profile_id}.toml"),
+                &ProfileRaw::from(profile),
+            )?);
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn create_dir(path: &std::path::Path) -> Result<(), CoverageError> {
+        std::fs::create_dir_all(path).map_err(|err| {
+            IoError::Write {
+                path: path.into(),
+                reason: err.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn file<T: Serialize>(&self, name: &str, value: &T) -> Result<(String, String), CoverageError> {
+        let content = toml::to_string(value).map_err(|err| IoError::Write {
+            path: std::path::Path::new(self.id.as_str()).join(name),
+            reason: err.to_string(),
+        })?;
+        Ok((name.to_string(), content))
+    }
+
+    const FILE_EXTENSIONS: &'static [&'static str] = &["toml", "json", "toml.gz", "json.gz"];
     fn read_file<T: for<'de> Deserialize<'de>>(
         dir: &std::path::Path,
         kind: &str,
     ) -> Result<T, CoverageError> {
-        let path = Self::FILE_EXTENSIONS
-            .iter()
-            .find_map(|ext| {
-                let path = dir.join(std::path::Path::new(kind).with_extension(ext));
-                if path.is_file() { Some(path) } else { None }
-            })
-            .ok_or_else(|| IoError::Read {
+        Self::read_optional_file(dir, kind)?.ok_or_else(|| {
+            IoError::Read {
                 path: dir.into(),
                 reason: format!("No {kind} file found"),
-            })?;
+            }
+            .into()
+        })
+    }
 
-        Self::parse_file(&path)
+    fn read_optional_file<T: for<'de> Deserialize<'de>>(
+        dir: &std::path::Path,
+        kind: &str,
+    ) -> Result<Option<T>, CoverageError> {
+        match Self::find_file(dir, kind) {
+            Some(path) => Self::parse_file(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn find_file(dir: &std::path::Path, kind: &str) -> Option<std::path::PathBuf> {
+        Self::FILE_EXTENSIONS.iter().find_map(|ext| {
+            let path = dir.join(std::path::Path::new(kind).with_extension(ext));
+            if path.is_file() { Some(path) } else { None }
+        })
+    }
+
+    /// Validates the IDs read from the `kind` file in `dir`, naming the file in returned errors.
+    fn invalid_ids<'a>(
+        dir: &std::path::Path,
+        kind: &str,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<CoverageError> {
+        let file = Self::find_file(dir, kind).unwrap_or_else(|| dir.join(kind));
+        ids.into_iter()
+            .filter_map(|id| validate_id(id).err())
+            .map(|err| err.context(file.display().to_string()))
+            .collect()
     }
 
     #[tracing::instrument(level = "trace", err)]
     fn parse_file<T: for<'de> Deserialize<'de>>(
         path: &std::path::Path,
     ) -> Result<T, CoverageError> {
-        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-        tracing::trace!(?ext, "Reading file");
-
-        let bytes = std::fs::read(path).map_err(|err| IoError::Read {
-            path: path.into(),
-            reason: err.to_string(),
-        })?;
+        tracing::trace!("Reading file");
+        let (ext, bytes) = crate::coverage::read_dataset_file(path)?;
 
         tracing::trace!(?ext, length = bytes.len(), "Parsing file");
-        match ext {
+        match ext.as_str() {
             "toml" => toml::from_slice(&bytes).map_err(|err| IoError::Parse {
                 path: path.into(),
                 reason: err.to_string(),
@@ -213,8 +472,12 @@ impl FlightInformationRegionRaw {
     ) -> Result<HashMap<ProfileId, Profile>, CoverageError> {
         let mut profiles = HashMap::new();
 
-        if let Ok(profile_raw) = Self::read_file::<ProfileRaw>(base_dir, "profile") {
+        if let Some(path) = Self::find_file(base_dir, "profile")
+            && let Ok(profile_raw) = Self::parse_file::<ProfileRaw>(&path)
+        {
             tracing::trace!(?profile_raw.id, "Loaded profile from file");
+            validate_id(profile_raw.id.as_str())
+                .map_err(|err| err.context(path.display().to_string()))?;
             profiles.insert(profile_raw.id.clone(), Profile::from_raw(profile_raw)?);
         }
 
@@ -238,6 +501,8 @@ impl FlightInformationRegionRaw {
 
                 let profile_raw = Self::parse_file::<ProfileRaw>(&path)?;
                 tracing::trace!(?profile_raw.id, ?path, "Loaded profile from directory");
+                validate_id(profile_raw.id.as_str())
+                    .map_err(|err| err.context(path.display().to_string()))?;
                 profiles.insert(profile_raw.id.clone(), Profile::from_raw(profile_raw)?);
             }
         }
@@ -255,8 +520,10 @@ impl TryFrom<FlightInformationRegionRaw> for FlightInformationRegion {
         Ok(Self {
             id: value.id,
             stations: value.stations.iter().map(|s| s.id.clone()).collect(),
+            station_groups: value.station_groups.iter().map(|g| g.id.clone()).collect(),
             positions: value.positions.iter().map(|p| p.id.clone()).collect(),
             profiles: value.profiles.keys().cloned().collect(),
+            settings: value.settings,
         })
     }
 }
@@ -329,14 +596,18 @@ mod tests {
                 parent_id: None,
                 controlled_by: vec![],
             }],
+            station_groups: vec![],
             positions: vec![PositionRaw {
                 id: "LOWW_TWR".into(),
                 prefixes: HashSet::from(["LOWW".to_string()]),
                 frequency: "119.400".to_string(),
                 facility_type: crate::FacilityType::Tower,
                 profile_id: Some(ProfileId::from("LOWW")),
+                transmit: true,
+                monitor_frequencies: Vec::new(),
             }],
             profiles: HashMap::new(),
+            settings: FirSettings::default(),
         };
         assert!(raw.validate().is_ok());
     }
@@ -350,14 +621,18 @@ mod tests {
                 parent_id: None,
                 controlled_by: vec![],
             }],
+            station_groups: vec![],
             positions: vec![PositionRaw {
                 id: "LOWW_TWR".into(),
                 prefixes: HashSet::from(["LOWW".to_string()]),
                 frequency: "119.400".to_string(),
                 facility_type: crate::FacilityType::Tower,
                 profile_id: Some(ProfileId::from("LOWW")),
+                transmit: true,
+                monitor_frequencies: Vec::new(),
             }],
             profiles: HashMap::new(),
+            settings: FirSettings::default(),
         };
         assert_matches!(
             raw.validate(),
@@ -370,14 +645,18 @@ mod tests {
         let raw = FlightInformationRegionRaw {
             id: "LOVV".into(),
             stations: vec![],
+            station_groups: vec![],
             positions: vec![PositionRaw {
                 id: "LOWW_TWR".into(),
                 prefixes: HashSet::from(["LOWW".to_string()]),
                 frequency: "119.400".to_string(),
                 facility_type: crate::FacilityType::Tower,
                 profile_id: Some(ProfileId::from("LOWW")),
+                transmit: true,
+                monitor_frequencies: Vec::new(),
             }],
             profiles: HashMap::new(),
+            settings: FirSettings::default(),
         };
         assert_matches!(
             raw.validate(),
@@ -394,8 +673,10 @@ mod tests {
                 parent_id: None,
                 controlled_by: vec![],
             }],
+            station_groups: vec![],
             positions: vec![],
             profiles: HashMap::new(),
+            settings: FirSettings::default(),
         };
         assert_matches!(
             raw.validate(),
@@ -412,14 +693,18 @@ mod tests {
                 parent_id: None,
                 controlled_by: vec![],
             }],
+            station_groups: vec![],
             positions: vec![PositionRaw {
                 id: "LOWW_TWR".into(),
                 prefixes: HashSet::from(["LOWW".to_string()]),
                 frequency: "119.400".to_string(),
                 facility_type: crate::FacilityType::Tower,
                 profile_id: Some(ProfileId::from("LOWW")),
+                transmit: true,
+                monitor_frequencies: Vec::new(),
             }],
             profiles: HashMap::new(),
+            settings: FirSettings::default(),
         };
         let fir = FlightInformationRegion::try_from(raw).unwrap();
         assert_eq!(fir.id.as_str(), "LOVV");
@@ -432,14 +717,18 @@ mod tests {
         let f1 = FlightInformationRegion {
             id: "LOVV".into(),
             stations: HashSet::new(),
+            station_groups: HashSet::new(),
             positions: HashSet::new(),
             profiles: HashSet::new(),
+            settings: FirSettings::default(),
         };
         let f2 = FlightInformationRegion {
             id: "LOVV".into(),
             stations: HashSet::from(["LOWW_TWR".into()]),
+            station_groups: HashSet::new(),
             positions: HashSet::from(["LOWW_TWR".into()]),
             profiles: HashSet::new(),
+            settings: FirSettings::default(),
         };
         assert_eq!(f1, f2); // Should be equal because only IDs check
     }
@@ -741,6 +1030,60 @@ mod tests {
         assert_matches!(res, Err(errors) if matches!(errors[0], CoverageError::Io(IoError::Parse { .. })));
     }
 
+    #[test]
+    fn load_from_dir_rejects_id_with_whitespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let stations_toml = r#"
+            [[stations]]
+            id = "LOWW TWR"
+        "#;
+        std::fs::write(fir_path.join("stations.toml"), stations_toml).unwrap();
+
+        let positions_toml = r#"
+            [[positions]]
+            id = "LOWW_TWR"
+            prefixes = ["LOWW"]
+            frequency = "119.400"
+            facility_type = "Tower"
+        "#;
+        std::fs::write(fir_path.join("positions.toml"), positions_toml).unwrap();
+
+        let errors = FlightInformationRegionRaw::load_from_dir(&fir_path).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        let message = errors[0].to_string();
+        assert!(message.contains("`LOWW TWR`"), "{message}");
+        assert!(message.contains("stations.toml"), "{message}");
+        assert_matches!(&errors[0], CoverageError::Context(ctx) if matches!(*ctx.error, CoverageError::Validation(ValidationError::InvalidFormat { ref field, .. }) if field == "id"));
+    }
+
+    #[test]
+    fn load_from_dir_rejects_non_ascii_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        let stations_toml = r#"
+            [[stations]]
+            id = "LOWW_TWR"
+        "#;
+        std::fs::write(fir_path.join("stations.toml"), stations_toml).unwrap();
+
+        let positions_json = r#"{"positions": [
+            {"id": "LOWW_TWR", "prefixes": ["LOWW"], "frequency": "119.400", "facility_type": "Tower"},
+            {"id": "LÖWW_GND", "prefixes": ["LOWW"], "frequency": "121.600", "facility_type": "Ground"}
+        ]}"#;
+        std::fs::write(fir_path.join("positions.json"), positions_json).unwrap();
+
+        let errors = FlightInformationRegionRaw::load_from_dir(&fir_path).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        let message = errors[0].to_string();
+        assert!(message.contains("`LÖWW_GND`"), "{message}");
+        assert!(message.contains("positions.json"), "{message}");
+    }
+
     #[test]
     fn load_profiles() {
         let dir = tempfile::tempdir().unwrap();
@@ -794,4 +1137,52 @@ mod tests {
         assert!(ids.contains(&"Default"));
         assert!(ids.contains(&"Other"));
     }
+
+    #[test]
+    fn load_ice_servers() {
+        let dir = tempfile::tempdir().unwrap();
+        let fir_path = dir.path().join("LOVV");
+        std::fs::create_dir(&fir_path).unwrap();
+
+        std::fs::write(
+            fir_path.join("stations.toml"),
+            "[[stations]]\nid=\"S\"\ncontrolled_by=[]",
+        )
+        .unwrap();
+        std::fs::write(
+            fir_path.join("positions.toml"),
+            "[[positions]]\nid=\"P\"\nprefixes=[]\nfrequency=\"118.0\"\nfacility_type=\"Tower\"",
+        )
+        .unwrap();
+
+        let raw = FlightInformationRegionRaw::load_from_dir(&fir_path).expect("Should load");
+        assert_eq!(raw.settings.ice_servers, None);
+
+        std::fs::write(
+            fir_path.join("ice.toml"),
+            "[[ice_servers]]\nurls=[\"stun:stun.example.com:3478\"]",
+        )
+        .unwrap();
+
+        let raw = FlightInformationRegionRaw::load_from_dir(&fir_path).expect("Should load");
+        assert_eq!(
+            raw.settings.ice_servers,
+            Some(vec![IceServer::new(vec![
+                "stun:stun.example.com:3478".to_string()
+            ])])
+        );
+
+        std::fs::write(
+            fir_path.join("fir.toml"),
+            "[[ice_servers]]\nurls=[\"turn:turn.example.com:3478\"]",
+        )
+        .unwrap();
+
+        let errors = FlightInformationRegionRaw::load_from_dir(&fir_path).unwrap_err();
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            CoverageError::Validation(ValidationError::MutuallyExclusive { fields })
+                if fields == &["fir.ice_servers", "ice.ice_servers"]
+        )));
+    }
 }
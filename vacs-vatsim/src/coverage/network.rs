@@ -1,23 +1,120 @@
-use crate::FacilityType;
 use crate::coverage::flight_information_region::{
-    FlightInformationRegion, FlightInformationRegionId, FlightInformationRegionRaw,
+    FirSettings, FlightInformationRegion, FlightInformationRegionId, FlightInformationRegionRaw,
 };
-use crate::coverage::position::Position;
+use crate::coverage::position::{Position, PositionRaw};
 use crate::coverage::profile::Profile;
-use crate::coverage::station::Station;
+use crate::coverage::relief::ReliefPatterns;
+use crate::coverage::station::{
+    Station, StationConfigFile, StationGroup, StationGroupRaw, StationRaw,
+};
 use crate::coverage::{
-    CoverageError, IoError, ReferenceValidator, StructureError, ValidationError,
+    CoverageError, CoverageWarning, IoError, ReferenceValidator, StructureError, ValidationError,
+    validate_id,
 };
+use crate::{FacilityType, frequencies_match};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use vacs_protocol::http::webrtc::IceServer;
 use vacs_protocol::profile::{ActiveProfile, ProfileId};
 use vacs_protocol::vatsim::{PositionId, StationChange, StationId};
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Network {
     firs: HashMap<FlightInformationRegionId, FlightInformationRegion>,
     positions: HashMap<PositionId, Position>,
     stations: HashMap<StationId, Station>,
+    station_groups: HashMap<StationId, StationGroup>,
     profiles: HashMap<ProfileId, Profile>,
+    disabled_firs: HashSet<FlightInformationRegionId>,
+    relief_patterns: ReliefPatterns,
+    warnings: Vec<CoverageWarning>,
+    content_hash: u64,
+    digest: String,
+}
+
+/// Options for the optional, non-fatal checks performed while loading a [`Network`].
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Maximum number of times a single profile may reference the same station before a
+    /// [`CoverageWarning::RepeatedStationReference`] is emitted. Disabled if `None`.
+    pub max_station_references_per_profile: Option<usize>,
+    /// Whether to emit a [`CoverageWarning::ImplausibleFrequency`] for positions whose frequency
+    /// lies outside the conventional range of their facility type.
+    pub check_frequency_plausibility: bool,
+    /// Whether warnings fail loading the network instead, e.g. to validate datasets in CI.
+    pub warnings_as_errors: bool,
+    /// Whether loading fails if any position resolves to no profile, neither explicitly nor via
+    /// `profile_defaults`, e.g. for divisions mandating a profile for every position.
+    pub require_profiles: bool,
+    /// Profiles positions without an explicit profile fall back to, only considered by
+    /// `require_profiles`.
+    pub profile_defaults: ProfileDefaults,
+    /// Maximum number of FIR directories loaded from a dataset directory, guarding against
+    /// accidentally loading a huge unrelated directory. Unlimited if `None`.
+    pub max_firs: Option<usize>,
+}
+
+/// Number of entities and warnings of a loaded [`Network`], allowing operators to confirm the
+/// expected dataset was loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkSummary {
+    pub firs: usize,
+    pub positions: usize,
+    pub stations: usize,
+    pub profiles: usize,
+    pub warnings: usize,
+}
+
+impl std::fmt::Display for NetworkSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} FIRs, {} positions, {} stations, {} profiles, {} warnings",
+            self.firs, self.positions, self.stations, self.profiles, self.warnings
+        )
+    }
+}
+
+/// Profiles assigned to positions that don't define an explicit profile, by facility type and
+/// network-wide. Defaults not present in the network are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileDefaults {
+    pub facility_types: HashMap<FacilityType, ProfileId>,
+    pub network: Option<ProfileId>,
+}
+
+impl ProfileDefaults {
+    /// Whether `position` has an explicit profile or one of the defaults applies to it.
+    fn has_profile(&self, profiles: &HashMap<ProfileId, Profile>, position: &Position) -> bool {
+        position.profile_id.is_some()
+            || self
+                .facility_types
+                .get(&position.facility_type)
+                .into_iter()
+                .chain(self.network.as_ref())
+                .any(|profile_id| profiles.contains_key(profile_id))
+    }
+}
+
+/// Options for resolving a controller connection to a position of a [`Network`].
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    /// Whether controllers whose callsign doesn't match any position are matched by their
+    /// frequency alone, as long as exactly one position uses that frequency.
+    pub frequency_only_matching: bool,
+}
+
+/// Outcome of resolving a controller connection to a position of a [`Network`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveOutcome<'a> {
+    /// No position matches the connection.
+    NoMatch,
+    /// Exactly one position matches the connection.
+    Single(&'a Position),
+    /// Multiple positions match the connection, sorted by ID. Which one is controlled can't be
+    /// decided automatically.
+    Ambiguous(Vec<&'a Position>),
 }
 
 impl std::fmt::Debug for Network {
@@ -26,14 +123,56 @@ impl std::fmt::Debug for Network {
             .field("firs", &self.firs.len())
             .field("positions", &self.positions.len())
             .field("stations", &self.stations.len())
+            .field("station_groups", &self.station_groups.len())
             .field("profiles", &self.profiles.len())
+            .field("disabled_firs", &self.disabled_firs)
+            .field("relief_patterns", &self.relief_patterns)
+            .field("warnings", &self.warnings.len())
+            .field("content_hash", &format_args!("{:016x}", self.content_hash))
+            .field("digest", &self.digest)
             .finish()
     }
 }
 
+impl Default for Network {
+    fn default() -> Self {
+        Self {
+            firs: HashMap::new(),
+            positions: HashMap::new(),
+            stations: HashMap::new(),
+            station_groups: HashMap::new(),
+            profiles: HashMap::new(),
+            disabled_firs: HashSet::new(),
+            relief_patterns: ReliefPatterns::default(),
+            warnings: Vec::new(),
+            content_hash: 0,
+            digest: Self::compute_digest(std::iter::empty()),
+        }
+    }
+}
+
 impl Network {
-    #[tracing::instrument(level = "trace", skip(dir), fields(dir = tracing::field::Empty))]
+    /// Creates a network without any FIRs, positions, stations or profiles, e.g. for a server
+    /// that has not been provisioned with a dataset yet. Clients connecting to it never match a
+    /// position and see no stations until a dataset is loaded.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Whether the network doesn't contain any positions, stations or profiles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty() && self.stations.is_empty() && self.profiles.is_empty()
+    }
+
     pub fn load_from_dir(dir: impl AsRef<std::path::Path>) -> Result<Self, Vec<CoverageError>> {
+        Self::load_from_dir_with_options(dir, &LoadOptions::default())
+    }
+
+    #[tracing::instrument(level = "trace", skip(dir), fields(dir = tracing::field::Empty))]
+    pub fn load_from_dir_with_options(
+        dir: impl AsRef<std::path::Path>,
+        options: &LoadOptions,
+    ) -> Result<Self, Vec<CoverageError>> {
         let dir = dir.as_ref();
         tracing::Span::current().record("dir", tracing::field::debug(dir));
         tracing::trace!("Loading network");
@@ -53,6 +192,7 @@ impl Network {
 
         let mut errors = Vec::new();
         let mut raw_firs = Vec::new();
+        let mut fir_dirs = 0usize;
 
         for entry in entries {
             let entry = match entry {
@@ -70,6 +210,20 @@ impl Network {
                 continue;
             }
 
+            fir_dirs += 1;
+            if let Some(max) = options.max_firs
+                && fir_dirs > max
+            {
+                tracing::warn!(max, "Too many FIR directories, refusing to load network");
+                return Err(vec![
+                    StructureError::TooMany {
+                        entity: "FIR".to_string(),
+                        max,
+                    }
+                    .into(),
+                ]);
+            }
+
             let fir = match FlightInformationRegionRaw::load_from_dir(&path) {
                 Ok(fir) => fir,
                 Err(errs) => {
@@ -90,8 +244,19 @@ impl Network {
             raw_firs.push(fir);
         }
 
+        Self::from_raw_firs(raw_firs, errors, options)
+    }
+
+    /// Builds a network from raw FIRs, validating all references between them. Fails if any
+    /// errors occur, including the `errors` encountered while reading the raw FIRs.
+    fn from_raw_firs(
+        raw_firs: Vec<FlightInformationRegionRaw>,
+        mut errors: Vec<CoverageError>,
+        options: &LoadOptions,
+    ) -> Result<Self, Vec<CoverageError>> {
         let mut firs = HashMap::new();
         let mut stations = HashMap::new();
+        let mut station_groups = HashMap::new();
         let mut positions = HashMap::new();
         let mut profiles = HashMap::new();
 
@@ -101,6 +266,12 @@ impl Network {
             .collect::<HashMap<_, _>>();
 
         let all_station_ids = all_stations.keys().collect::<HashSet<_>>();
+        // Profiles may reference station groups in place of stations.
+        let all_station_and_group_ids = raw_firs
+            .iter()
+            .flat_map(|fir| fir.station_groups.iter().map(|g| &g.id))
+            .chain(all_station_ids.iter().copied())
+            .collect::<HashSet<_>>();
         let all_position_ids = raw_firs
             .iter()
             .flat_map(|fir| fir.positions.iter().map(|p| &p.id))
@@ -109,6 +280,9 @@ impl Network {
             .iter()
             .flat_map(|fir| fir.profiles.keys())
             .collect::<HashSet<_>>();
+        let all_fir_ids = raw_firs.iter().map(|fir| &fir.id).collect::<HashSet<_>>();
+
+        errors.extend(duplicate_stations(&raw_firs));
 
         for fir_raw in &raw_firs {
             for station in &fir_raw.stations {
@@ -129,8 +303,22 @@ impl Network {
                     );
                 }
             }
+            for group in &fir_raw.station_groups {
+                if let Err(err) = group.validate_references(&all_station_ids) {
+                    tracing::warn!(?err, ?group.id, ?fir_raw.id, "Invalid station reference in station group");
+                    errors.push(err.context(group.id.as_str()).context(fir_raw.id.as_str()));
+                }
+            }
+            if let Err(err) = fir_raw.settings.validate_references(&all_profile_ids) {
+                tracing::warn!(?err, ?fir_raw.id, "Invalid profile reference in FIR settings");
+                errors.push(err.context("fir").context(fir_raw.id.as_str()));
+            }
+            if let Err(err) = fir_raw.settings.validate_references(&all_fir_ids) {
+                tracing::warn!(?err, ?fir_raw.id, "Invalid neighbor reference in FIR settings");
+                errors.push(err.context("fir").context(fir_raw.id.as_str()));
+            }
             for profile in fir_raw.profiles.values() {
-                if let Err(err) = profile.validate_references(&all_station_ids) {
+                if let Err(err) = profile.validate_references(&all_station_and_group_ids) {
                     tracing::warn!(?err, ?profile.id, ?fir_raw.id, "Invalid station reference in profile");
                     errors.push(
                         err.context(profile.id.as_str())
@@ -178,7 +366,14 @@ impl Network {
                 }
 
                 match Position::from_raw(position_raw.clone(), fir_raw.id.clone()) {
-                    Ok(position) => positions.insert(position.id.clone(), position),
+                    Ok(mut position) => {
+                        // Apply the FIR-level defaults and transmit rule
+                        if position.profile_id.is_none() {
+                            position.profile_id = fir_raw.settings.default_profile.clone();
+                        }
+                        position.transmit &= fir_raw.settings.transmit;
+                        positions.insert(position.id.clone(), position)
+                    }
                     Err(err) => {
                         let err: CoverageError = StructureError::Load {
                             entity: "Position".to_string(),
@@ -195,13 +390,7 @@ impl Network {
 
             for station_raw in &fir_raw.stations {
                 if stations.contains_key(&station_raw.id) {
-                    let err: CoverageError = StructureError::Duplicate {
-                        entity: "Station".to_string(),
-                        id: station_raw.id.to_string(),
-                    }
-                    .into();
-                    tracing::warn!(?station_raw, "Duplicate station ID");
-                    errors.push(err.context(fir_raw.id.as_str()));
+                    // Already reported by the duplicate detection pass above.
                     continue;
                 }
 
@@ -235,6 +424,45 @@ impl Network {
                 stations.insert(station.id.clone(), station);
             }
 
+            for group_raw in &fir_raw.station_groups {
+                if all_stations.contains_key(&group_raw.id) {
+                    let err: CoverageError = ValidationError::InvalidValue {
+                        field: "id".to_string(),
+                        value: group_raw.id.to_string(),
+                        reason: "station group ID collides with a station ID".to_string(),
+                    }
+                    .into();
+                    tracing::warn!(?group_raw, "Station group ID collides with station ID");
+                    errors.push(err.context(fir_raw.id.as_str()));
+                    continue;
+                }
+                if station_groups.contains_key(&group_raw.id) {
+                    let err: CoverageError = StructureError::Duplicate {
+                        entity: "Station group".to_string(),
+                        id: group_raw.id.to_string(),
+                    }
+                    .into();
+                    tracing::warn!(?group_raw, "Duplicate station group ID");
+                    errors.push(err.context(fir_raw.id.as_str()));
+                    continue;
+                }
+
+                match StationGroup::from_raw(group_raw.clone(), fir_raw.id.clone()) {
+                    Ok(group) => station_groups.insert(group.id.clone(), group),
+                    Err(err) => {
+                        let err: CoverageError = StructureError::Load {
+                            entity: "Station group".to_string(),
+                            id: group_raw.id.to_string(),
+                            reason: err.to_string(),
+                        }
+                        .into();
+                        tracing::warn!(?err, ?group_raw, "Failed to parse station group");
+                        errors.push(err.context(fir_raw.id.as_str()));
+                        continue;
+                    }
+                };
+            }
+
             for (profile_id, profile) in &fir_raw.profiles {
                 if profiles.contains_key(profile_id) {
                     let err: CoverageError = StructureError::Duplicate {
@@ -274,17 +502,239 @@ impl Network {
             return Err(errors);
         }
 
-        let network = Self {
+        if options.require_profiles {
+            let mut missing = positions
+                .values()
+                .filter(|position| !options.profile_defaults.has_profile(&profiles, position))
+                .map(|position| position.id.to_string())
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                missing.sort();
+                let err: CoverageError =
+                    ValidationError::MissingProfiles { positions: missing }.into();
+                tracing::warn!(
+                    ?err,
+                    "Failed to load network due to positions without profile"
+                );
+                return Err(vec![err]);
+            }
+        }
+
+        for profile in profiles.values_mut() {
+            profile.expand_station_groups(&station_groups);
+        }
+
+        let mut warnings = Vec::new();
+        if let Some(max_references) = options.max_station_references_per_profile {
+            let mut profile_ids = profiles.keys().collect::<Vec<_>>();
+            profile_ids.sort();
+            for profile_id in profile_ids {
+                for (station_id, count) in
+                    profiles[profile_id].repeated_station_references(max_references)
+                {
+                    let warning = CoverageWarning::RepeatedStationReference {
+                        profile_id: profile_id.to_string(),
+                        station_id: station_id.to_string(),
+                        count,
+                    };
+                    tracing::warn!(%warning, "Station referenced repeatedly in profile");
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        if options.check_frequency_plausibility {
+            let mut position_ids = positions.keys().collect::<Vec<_>>();
+            position_ids.sort();
+            for position_id in position_ids {
+                let position: &Position = &positions[position_id];
+                if !position.has_plausible_frequency() {
+                    let warning = CoverageWarning::ImplausibleFrequency {
+                        position_id: position_id.to_string(),
+                        frequency: position.frequency.clone(),
+                        facility_type: position.facility_type.as_str().to_string(),
+                    };
+                    tracing::warn!(%warning, "Implausible frequency for position");
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        for (profile_id, station_id) in
+            uncoverable_profile_stations(&profiles, &stations, &positions)
+        {
+            let warning = CoverageWarning::UncoverableProfileStation {
+                profile_id: profile_id.to_string(),
+                station_id: station_id.to_string(),
+            };
+            tracing::warn!(%warning, "Profile references uncoverable station");
+            warnings.push(warning);
+        }
+
+        let mut profile_ids = profiles.keys().collect::<Vec<_>>();
+        profile_ids.sort();
+        for profile_id in profile_ids {
+            for overflow in profiles[profile_id].geo_layout_overflows() {
+                let warning = CoverageWarning::GeoLayoutOverflow {
+                    profile_id: profile_id.to_string(),
+                    path: overflow.path,
+                    dimension: overflow.dimension.to_string(),
+                    declared: overflow.declared.to_string(),
+                    required: overflow.required.to_string(),
+                };
+                tracing::warn!(%warning, "GEO page container too small for its children");
+                warnings.push(warning);
+            }
+        }
+
+        if options.warnings_as_errors && !warnings.is_empty() {
+            tracing::warn!(?warnings, "Failed to load network due to warnings");
+            return Err(warnings.into_iter().map(Into::into).collect());
+        }
+
+        let content_hash = content_hash(&firs, &positions, &stations, &station_groups, &profiles);
+        let mut network = Self {
             firs,
             positions,
             stations,
+            station_groups,
             profiles,
+            disabled_firs: HashSet::new(),
+            relief_patterns: ReliefPatterns::default(),
+            warnings,
+            content_hash,
+            digest: String::new(),
         };
+        network.digest = network.dataset_digest().map_err(|err| vec![err])?;
 
         tracing::info!(?network, "Successfully loaded network");
         Ok(network)
     }
 
+    /// Writes the network to `dir` in the on-disk format read by [`Self::load_from_dir`], using
+    /// one subdirectory per FIR. Loading the written directory yields an equivalent network.
+    /// Runtime state like disabled FIRs and relief patterns is not part of the dataset and thus
+    /// not written.
+    pub fn write_to_dir(&self, dir: impl AsRef<std::path::Path>) -> Result<(), CoverageError> {
+        let dir = dir.as_ref();
+        for fir in self.firs.values() {
+            self.fir_raw(fir).write_to_dir(dir.join(fir.id.as_str()))?;
+        }
+        Ok(())
+    }
+
+    /// Reconstructs the raw form of `fir` as read from disk.
+    fn fir_raw(&self, fir: &FlightInformationRegion) -> FlightInformationRegionRaw {
+        fn sorted<'a, T: Ord + 'a>(ids: impl IntoIterator<Item = &'a T>) -> Vec<&'a T> {
+            let mut ids = ids.into_iter().collect::<Vec<_>>();
+            ids.sort();
+            ids
+        }
+
+        FlightInformationRegionRaw {
+            id: fir.id.clone(),
+            stations: sorted(&fir.stations)
+                .into_iter()
+                .filter_map(|id| self.stations.get(id))
+                .map(|station| {
+                    let parent = station
+                        .parent_id
+                        .as_ref()
+                        .and_then(|parent_id| self.stations.get(parent_id));
+                    StationRaw::from_station(station, parent)
+                })
+                .collect(),
+            station_groups: sorted(&fir.station_groups)
+                .into_iter()
+                .filter_map(|id| self.station_groups.get(id))
+                .map(StationGroupRaw::from)
+                .collect(),
+            positions: sorted(&fir.positions)
+                .into_iter()
+                .filter_map(|id| self.positions.get(id))
+                .map(|position| {
+                    // Settings inherited from the FIR are written to `fir.toml` only
+                    let mut position_raw = PositionRaw::from(position);
+                    if position_raw.profile_id == fir.settings.default_profile {
+                        position_raw.profile_id = None;
+                    }
+                    if !fir.settings.transmit {
+                        position_raw.transmit = true;
+                    }
+                    position_raw
+                })
+                .collect(),
+            profiles: fir
+                .profiles
+                .iter()
+                .filter_map(|id| Some((id.clone(), self.profiles.get(id)?.clone())))
+                .collect(),
+            settings: fir.settings.clone(),
+        }
+    }
+
+    /// Hash of the loaded network content, used to detect changes between reloads. Only stable
+    /// within a single build of the server, see [`Self::digest`] for a stable identifier.
+    pub fn content_hash(&self) -> u64 {
+        self.content_hash
+    }
+
+    /// Hex-encoded SHA-256 digest of the dataset, identifying its version across servers and
+    /// builds.
+    ///
+    /// The digest covers the files written by [`Self::write_to_dir`] in their canonical form:
+    /// for every FIR in order of its ID and every file in order of its path, the path relative to
+    /// the dataset root (e.g. `LOVV/stations.toml`), a NUL byte, the TOML content and another NUL
+    /// byte. Runtime state like disabled FIRs and relief patterns is not part of the digest.
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    fn dataset_digest(&self) -> Result<String, CoverageError> {
+        let mut fir_ids = self.firs.keys().collect::<Vec<_>>();
+        fir_ids.sort();
+
+        let mut files = Vec::new();
+        for fir_id in fir_ids {
+            for (name, content) in self.fir_raw(&self.firs[fir_id]).files()? {
+                files.push((format!("{fir_id}/{name}"), content));
+            }
+        }
+        Ok(Self::compute_digest(files))
+    }
+
+    fn compute_digest(files: impl IntoIterator<Item = (String, String)>) -> String {
+        let mut hasher = Sha256::new();
+        for (path, content) in files {
+            hasher.update(path.as_bytes());
+            hasher.update([0]);
+            hasher.update(content.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Non-fatal issues detected while loading the network.
+    pub fn warnings(&self) -> &[CoverageWarning] {
+        &self.warnings
+    }
+
+    pub fn summary(&self) -> NetworkSummary {
+        NetworkSummary {
+            firs: self.firs.len(),
+            positions: self.positions.len(),
+            stations: self.stations.len(),
+            profiles: self.profiles.len(),
+            warnings: self.warnings.len(),
+        }
+    }
+
+    /// Returns all stations referenced by a profile that can never become callable, as they are
+    /// unknown or not controlled by any existing position. Sorted by profile and station.
+    pub fn uncoverable_profile_stations(&self) -> Vec<(&ProfileId, &StationId)> {
+        uncoverable_profile_stations(&self.profiles, &self.stations, &self.positions)
+    }
+
     pub fn get_profile(&self, profile_id: &ProfileId) -> Option<&Profile> {
         self.profiles.get(profile_id)
     }
@@ -293,6 +743,183 @@ impl Network {
         self.positions.get(position_id)
     }
 
+    pub fn has_station(&self, station_id: &StationId) -> bool {
+        self.stations.contains_key(station_id)
+    }
+
+    /// Returns all stations of the network, in no particular order.
+    pub fn stations(&self) -> impl Iterator<Item = &Station> {
+        self.stations.values()
+    }
+
+    /// Returns the member stations if `station_id` refers to a station group.
+    pub fn station_group_members(&self, station_id: &StationId) -> Option<&[StationId]> {
+        self.station_groups
+            .get(station_id)
+            .map(|group| group.members.as_slice())
+    }
+
+    /// Returns the ICE server override of the FIR the given position belongs to, if any.
+    pub fn ice_servers(&self, position_id: &PositionId) -> Option<&[IceServer]> {
+        let position = self.positions.get(position_id)?;
+        self.firs
+            .get(&position.fir_id)?
+            .settings
+            .ice_servers
+            .as_deref()
+    }
+
+    /// Returns the settings of the given FIR, read from its optional `fir.toml`.
+    pub fn fir_settings(&self, fir_id: &FlightInformationRegionId) -> Option<&FirSettings> {
+        self.firs.get(fir_id).map(|fir| &fir.settings)
+    }
+
+    pub fn has_fir(&self, fir_id: &FlightInformationRegionId) -> bool {
+        self.firs.contains_key(fir_id)
+    }
+
+    pub fn is_fir_enabled(&self, fir_id: &FlightInformationRegionId) -> bool {
+        !self.disabled_firs.contains(fir_id)
+    }
+
+    pub fn disabled_firs(&self) -> impl Iterator<Item = &FlightInformationRegionId> {
+        self.disabled_firs.iter()
+    }
+
+    /// Enables or disables the given FIR. Positions of a disabled FIR never control any stations,
+    /// excluding them from all coverage computations until the FIR is enabled again.
+    ///
+    /// Returns whether the state of the FIR changed, unknown FIRs are ignored.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn set_fir_enabled(&mut self, fir_id: &FlightInformationRegionId, enabled: bool) -> bool {
+        if !self.firs.contains_key(fir_id) {
+            tracing::debug!("FIR not found");
+            return false;
+        }
+
+        if enabled {
+            self.disabled_firs.remove(fir_id)
+        } else {
+            self.disabled_firs.insert(fir_id.clone())
+        }
+    }
+
+    pub fn relief_patterns(&self) -> &ReliefPatterns {
+        &self.relief_patterns
+    }
+
+    /// Sets the relief infixes stripped from callsigns before matching them against position ids.
+    pub fn set_relief_patterns(&mut self, relief_patterns: ReliefPatterns) {
+        self.relief_patterns = relief_patterns;
+    }
+
+    /// Returns a copy of the network with the FIR `fir_id` replaced by its counterpart in `other`,
+    /// e.g. a freshly loaded copy of the dataset, leaving all other FIRs untouched. The FIR is
+    /// added if it's new and removed if `other` doesn't contain it.
+    ///
+    /// The result is validated like a freshly loaded network, so this fails if other FIRs
+    /// reference positions, stations or profiles the FIR no longer contains. Runtime state like
+    /// disabled FIRs and relief patterns is kept.
+    #[tracing::instrument(level = "debug", skip(self, other, options))]
+    pub fn replace_fir(
+        &self,
+        fir_id: &FlightInformationRegionId,
+        other: &Network,
+        options: &LoadOptions,
+    ) -> Result<Network, Vec<CoverageError>> {
+        let raw_firs = self
+            .firs
+            .values()
+            .filter(|fir| &fir.id != fir_id)
+            .map(|fir| self.fir_raw(fir))
+            .chain(other.firs.get(fir_id).map(|fir| other.fir_raw(fir)))
+            .collect();
+
+        let mut network = Self::from_raw_firs(raw_firs, Vec::new(), options)?;
+        network.disabled_firs = self
+            .disabled_firs
+            .iter()
+            .filter(|disabled| network.firs.contains_key(*disabled))
+            .cloned()
+            .collect();
+        network.relief_patterns = self.relief_patterns.clone();
+        Ok(network)
+    }
+
+    /// Returns all stations whose coverage depends on the FIR `fir_id`: its own stations and their
+    /// descendants, as well as all stations controlled by any of its positions. Empty if the FIR
+    /// is unknown.
+    pub fn fir_coverage(&self, fir_id: &FlightInformationRegionId) -> HashSet<StationId> {
+        let Some(fir) = self.firs.get(fir_id) else {
+            return HashSet::new();
+        };
+
+        let descends_from_fir = |station: &Station| {
+            let mut parent_id = station.parent_id.as_ref();
+            while let Some(id) = parent_id {
+                if fir.stations.contains(id) {
+                    return true;
+                }
+                parent_id = self.stations.get(id).and_then(|s| s.parent_id.as_ref());
+            }
+            false
+        };
+
+        self.stations
+            .values()
+            .filter(|station| fir.stations.contains(&station.id) || descends_from_fir(station))
+            .map(|station| station.id.clone())
+            .chain(
+                fir.positions
+                    .iter()
+                    .filter_map(|position_id| self.positions.get(position_id))
+                    .flat_map(|position| position.controlled_stations.iter().cloned()),
+            )
+            .collect()
+    }
+
+    /// Replaces the profiles with the ones of `other`, e.g. a freshly loaded copy of the dataset,
+    /// keeping runtime state like disabled FIRs and relief patterns.
+    ///
+    /// Only profiles may differ: if `other` changes any FIR, position, station or station group,
+    /// an error is returned and the network is left untouched, as those changes affect coverage
+    /// and require a full replacement. Since `other` was validated while loading, its profiles are
+    /// guaranteed to only reference existing stations.
+    pub fn replace_profiles(&mut self, other: Network) -> Result<(), CoverageError> {
+        if !self.has_same_coverage(&other) {
+            return Err(ValidationError::Custom(
+                "coverage data changed, replacing profiles requires a full network reload"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        self.firs = other.firs;
+        self.profiles = other.profiles;
+        self.warnings = other.warnings;
+        self.content_hash = other.content_hash;
+        self.digest = other.digest;
+        Ok(())
+    }
+
+    /// Whether `other` contains the same coverage data, i.e. differs at most in its profiles.
+    /// Runtime state like disabled FIRs and relief patterns is not compared.
+    pub fn has_same_coverage(&self, other: &Network) -> bool {
+        self.coverage_hash() == other.coverage_hash()
+    }
+
+    /// Hash of all coverage-relevant content, i.e. the [content hash](Self::content_hash)
+    /// without profiles.
+    fn coverage_hash(&self) -> u64 {
+        content_hash(
+            &self.firs,
+            &self.positions,
+            &self.stations,
+            &self.station_groups,
+            &HashMap::new(),
+        )
+    }
+
     #[tracing::instrument(level = "trace", skip_all, fields(callsign = tracing::field::Empty, frequency = tracing::field::Empty, facility_type = tracing::field::Empty))]
     pub fn find_positions(
         &self,
@@ -309,13 +936,26 @@ impl Network {
             .record("frequency", frequency)
             .record("facility_type", tracing::field::debug(&facility_type));
 
+        // Strip configured relief infixes (e.g. `LOWW_T1_TWR` --> `LOWW_TWR`), while still
+        // preferring positions explicitly defined with the full callsign
+        let relieved = self.relief_patterns.normalize(&callsign);
+        let callsigns = std::iter::once(callsign.as_str())
+            .chain(relieved.as_deref())
+            .collect::<Vec<_>>();
+
         // Check if a position with the exact callsign exists and the frequency and facility type match
-        if let Some(position) = self.positions.get(callsign.as_str())
-            && position.frequency == frequency
-            && position.facility_type == facility_type
-        {
-            tracing::trace!(?position, "Found exact match for callsign");
-            return vec![position];
+        for candidate in &callsigns {
+            if let Some(position) = self.positions.get(*candidate)
+                && frequencies_match(&position.frequency, frequency)
+                && position.facility_type == facility_type
+            {
+                tracing::trace!(
+                    ?position,
+                    callsign = *candidate,
+                    "Found exact match for callsign"
+                );
+                return vec![position];
+            }
         }
 
         // Find all positions with the same frequency and facility type that have a prefix matching the callsign
@@ -323,9 +963,11 @@ impl Network {
             .positions
             .values()
             .filter(|p| {
-                p.frequency == frequency
+                frequencies_match(&p.frequency, frequency)
                     && p.facility_type == facility_type
-                    && p.prefixes.iter().any(|pre| callsign.starts_with(pre))
+                    && p.prefixes
+                        .iter()
+                        .any(|pre| callsigns.iter().any(|c| c.starts_with(pre)))
             })
             .collect::<Vec<_>>();
 
@@ -345,6 +987,83 @@ impl Network {
         positions
     }
 
+    /// Returns all positions using the given frequency, regardless of their callsign prefixes and
+    /// facility type. Only considers primary frequencies, as monitored ones don't identify a
+    /// position.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn find_positions_by_frequency(&self, frequency: &str) -> Vec<&Position> {
+        let mut positions = self
+            .positions
+            .values()
+            .filter(|p| frequencies_match(&p.frequency, frequency))
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.id.cmp(&b.id));
+        positions
+    }
+
+    /// Resolves a controller connection to the position it controls, the same way the server
+    /// does when a client logs in or its VATSIM connection changes.
+    pub fn resolve(
+        &self,
+        callsign: impl AsRef<str>,
+        frequency: impl AsRef<str>,
+        facility_type: impl Into<FacilityType>,
+    ) -> ResolveOutcome<'_> {
+        self.resolve_with_options(
+            callsign,
+            frequency,
+            facility_type,
+            &ResolveOptions::default(),
+        )
+    }
+
+    pub fn resolve_with_options(
+        &self,
+        callsign: impl AsRef<str>,
+        frequency: impl AsRef<str>,
+        facility_type: impl Into<FacilityType>,
+        options: &ResolveOptions,
+    ) -> ResolveOutcome<'_> {
+        let frequency = frequency.as_ref();
+        let mut positions = self.find_positions(callsign, frequency, facility_type);
+
+        if positions.is_empty() && options.frequency_only_matching {
+            positions = self.find_positions_by_frequency(frequency);
+            if positions.len() != 1 {
+                tracing::trace!(
+                    positions = positions.len(),
+                    "No unique position found by frequency only"
+                );
+                return ResolveOutcome::NoMatch;
+            }
+            tracing::debug!(position = ?positions[0], "Matched position by frequency only");
+        }
+
+        match positions.len() {
+            0 => ResolveOutcome::NoMatch,
+            1 => ResolveOutcome::Single(positions[0]),
+            _ => ResolveOutcome::Ambiguous(positions),
+        }
+    }
+
+    /// Returns all positions callers on the given frequency can reach, i.e. positions using it as
+    /// their primary frequency or monitoring it. Positions using it as their primary frequency
+    /// are listed first, each group sorted by ID.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn find_positions_reachable_on(&self, frequency: &str) -> Vec<&Position> {
+        let mut positions = self
+            .positions
+            .values()
+            .filter(|p| p.is_reachable_on(frequency))
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| {
+            let a_monitored = !frequencies_match(&a.frequency, frequency);
+            let b_monitored = !frequencies_match(&b.frequency, frequency);
+            a_monitored.cmp(&b_monitored).then_with(|| a.id.cmp(&b.id))
+        });
+        positions
+    }
+
     #[tracing::instrument(level = "trace", skip(self, online_positions), fields(online_positions = online_positions.len()))]
     pub fn covered_stations(
         &'_ self,
@@ -370,6 +1089,20 @@ impl Network {
         stations
     }
 
+    /// Returns all stations the position would cover if it were the only position online.
+    ///
+    /// Takes the `controlled_by` lists inherited via `parent_id` into account, matching the result
+    /// of [`Network::covered_stations`] with only `position_id` online. Empty if the position is
+    /// unknown or belongs to a disabled FIR.
+    pub fn coverage_closure(&self, position_id: &PositionId) -> HashSet<StationId> {
+        match self.positions.get(position_id) {
+            Some(position) if self.is_fir_enabled(&position.fir_id) => {
+                position.controlled_stations.clone()
+            }
+            _ => HashSet::new(),
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip(self, online_positions), fields(online_positions = online_positions.len()))]
     pub fn controlling_position(
         &self,
@@ -383,6 +1116,10 @@ impl Network {
             .find_map(|pos_id| {
                 if online_positions.contains(pos_id) {
                     let position = self.positions.get(pos_id.as_str())?;
+                    if self.disabled_firs.contains(&position.fir_id) {
+                        tracing::trace!(?position, "Skipping position of disabled FIR");
+                        return None;
+                    }
                     tracing::trace!(?position, "Found position with matching coverage");
                     Some(position)
                 } else {
@@ -472,6 +1209,7 @@ impl Network {
                     tracing::trace!(?station, ?old_pos, "Station is now offline");
                     changes.push(StationChange::Offline {
                         station_id: station.id.clone(),
+                        reason: None,
                     });
                 }
                 (Some(old_pos), Some(new_pos)) => {
@@ -504,6 +1242,267 @@ impl Network {
             ActiveProfile::None => RelevantStations::None,
         }
     }
+
+    /// Returns the FIRs whose station changes are relevant to clients on positions of the given
+    /// FIR, i.e. the FIR itself and its configured neighbors. Returns `None` if the FIR doesn't
+    /// configure neighbors, in which case station changes of all FIRs are relevant.
+    pub fn relevant_firs(
+        &self,
+        fir_id: &FlightInformationRegionId,
+    ) -> Option<HashSet<&FlightInformationRegionId>> {
+        let fir = self.firs.get(fir_id)?;
+        let neighbors = fir.settings.neighbors.as_ref()?;
+        Some(std::iter::once(&fir.id).chain(neighbors).collect())
+    }
+
+    /// Returns the FIR the given station belongs to.
+    pub fn station_fir(&self, station_id: &StationId) -> Option<&FlightInformationRegionId> {
+        self.stations.get(station_id).map(|station| &station.fir_id)
+    }
+
+    /// Validates a candidate `stations.toml` against the currently loaded positions without
+    /// reloading the network, returning all errors found.
+    ///
+    /// Applies the same station checks as [`Self::load_from_dir`]: IDs, duplicate stations,
+    /// parent references and cycles, `controlled_by` references and empty coverage, as well as
+    /// station group members. Candidate stations replace loaded stations with the same ID, so
+    /// parents may reference both the candidate and the rest of the network.
+    pub fn validate_stations(&self, raw: &str) -> Result<(), Vec<CoverageError>> {
+        const FILE: &str = "stations.toml";
+
+        let config = toml::from_str::<StationConfigFile>(raw).map_err(|err| {
+            vec![CoverageError::from(IoError::Parse {
+                path: FILE.into(),
+                reason: err.to_string(),
+            })]
+        })?;
+
+        let mut errors = config
+            .stations
+            .iter()
+            .map(|s| s.id.as_str())
+            .chain(config.groups.iter().map(|g| g.id.as_str()))
+            .filter_map(|id| validate_id(id).err())
+            .map(|err| err.context(FILE))
+            .collect::<Vec<_>>();
+
+        let mut definitions: HashMap<&StationId, Vec<String>> = HashMap::new();
+        for (index, station_raw) in config.stations.iter().enumerate() {
+            definitions
+                .entry(&station_raw.id)
+                .or_default()
+                .push(format!("`{FILE}` (station #{})", index + 1));
+        }
+        errors.extend(duplicate_station_errors(definitions));
+
+        let loaded_stations = self
+            .stations
+            .values()
+            .map(|station| {
+                let parent = station
+                    .parent_id
+                    .as_ref()
+                    .and_then(|parent_id| self.stations.get(parent_id));
+                StationRaw::from_station(station, parent)
+            })
+            .collect::<Vec<_>>();
+        let all_stations = loaded_stations
+            .iter()
+            .chain(&config.stations)
+            .map(|s| (s.id.clone(), s))
+            .collect::<HashMap<_, _>>();
+        let all_station_ids = all_stations.keys().collect::<HashSet<_>>();
+        let all_position_ids = self.positions.keys().collect::<HashSet<_>>();
+
+        let mut seen = HashSet::new();
+        for station_raw in &config.stations {
+            if !seen.insert(&station_raw.id) {
+                // Already reported by the duplicate detection above.
+                continue;
+            }
+            if let Err(err) = station_raw.validate_references(&all_position_ids) {
+                errors.push(err.context(station_raw.id.as_str()).context(FILE));
+            }
+            match Station::from_raw(
+                station_raw.clone(),
+                FlightInformationRegionId::default(),
+                &all_stations,
+            ) {
+                Ok(station) if station.controlled_by.is_empty() => {
+                    errors.push(
+                        CoverageError::from(ValidationError::EmptyCoverage(station.id.to_string()))
+                            .context(FILE),
+                    );
+                }
+                Ok(_) => {}
+                Err(errs) => {
+                    errors.extend(errs.into_iter().map(|err| {
+                        CoverageError::from(StructureError::Load {
+                            entity: "Station".to_string(),
+                            id: station_raw.id.to_string(),
+                            reason: err.to_string(),
+                        })
+                        .context(FILE)
+                    }));
+                }
+            }
+        }
+
+        let mut seen_groups = HashSet::new();
+        for group_raw in &config.groups {
+            if let Err(err) = group_raw.validate_references(&all_station_ids) {
+                errors.push(err.context(group_raw.id.as_str()).context(FILE));
+            }
+            if all_stations.contains_key(&group_raw.id) {
+                errors.push(
+                    CoverageError::from(ValidationError::InvalidValue {
+                        field: "id".to_string(),
+                        value: group_raw.id.to_string(),
+                        reason: "station group ID collides with a station ID".to_string(),
+                    })
+                    .context(FILE),
+                );
+            } else if !seen_groups.insert(&group_raw.id) {
+                errors.push(
+                    CoverageError::from(StructureError::Duplicate {
+                        entity: "Station group".to_string(),
+                        id: group_raw.id.to_string(),
+                    })
+                    .context(FILE),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            tracing::debug!(?errors, "Candidate stations failed validation");
+            Err(errors)
+        }
+    }
+}
+
+/// Detects station IDs defined more than once across all FIRs, returning an error naming every
+/// definition of each duplicated station.
+fn duplicate_stations(raw_firs: &[FlightInformationRegionRaw]) -> Vec<CoverageError> {
+    let mut definitions: HashMap<&StationId, Vec<String>> = HashMap::new();
+    for fir_raw in raw_firs {
+        for (index, station_raw) in fir_raw.stations.iter().enumerate() {
+            definitions
+                .entry(&station_raw.id)
+                .or_default()
+                .push(format!("FIR `{}` (station #{})", fir_raw.id, index + 1));
+        }
+    }
+    duplicate_station_errors(definitions)
+}
+
+/// Returns an error for every station with more than one of the given definitions.
+fn duplicate_station_errors(definitions: HashMap<&StationId, Vec<String>>) -> Vec<CoverageError> {
+    let mut duplicates = definitions
+        .into_iter()
+        .filter(|(_, definitions)| definitions.len() > 1)
+        .collect::<Vec<_>>();
+    duplicates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    duplicates
+        .into_iter()
+        .map(|(station_id, mut definitions)| {
+            definitions.sort();
+            let err: CoverageError = ValidationError::DuplicateStation {
+                station: station_id.to_string(),
+                definitions,
+            }
+            .into();
+            tracing::warn!(?err, "Duplicate station ID");
+            err
+        })
+        .collect()
+}
+
+fn uncoverable_profile_stations<'a>(
+    profiles: &'a HashMap<ProfileId, Profile>,
+    stations: &HashMap<StationId, Station>,
+    positions: &HashMap<PositionId, Position>,
+) -> Vec<(&'a ProfileId, &'a StationId)> {
+    let mut uncoverable = profiles
+        .iter()
+        .flat_map(|(profile_id, profile)| {
+            profile
+                .relevant_station_ids
+                .iter()
+                .map(move |station_id| (profile_id, station_id))
+        })
+        .filter(|(_, station_id)| {
+            !stations.get(*station_id).is_some_and(|station| {
+                station
+                    .controlled_by
+                    .iter()
+                    .any(|position_id| positions.contains_key(position_id))
+            })
+        })
+        .collect::<Vec<_>>();
+    uncoverable.sort();
+    uncoverable
+}
+
+/// Hashes the network content in a deterministic order, independent of the iteration order of the
+/// underlying maps and sets.
+fn content_hash(
+    firs: &HashMap<FlightInformationRegionId, FlightInformationRegion>,
+    positions: &HashMap<PositionId, Position>,
+    stations: &HashMap<StationId, Station>,
+    station_groups: &HashMap<StationId, StationGroup>,
+    profiles: &HashMap<ProfileId, Profile>,
+) -> u64 {
+    fn sorted<T: Ord>(items: impl IntoIterator<Item = T>) -> Vec<T> {
+        let mut items = items.into_iter().collect::<Vec<_>>();
+        items.sort();
+        items
+    }
+
+    let mut hasher = DefaultHasher::new();
+
+    for fir in sorted(firs.keys()).into_iter().map(|id| &firs[id]) {
+        fir.id.hash(&mut hasher);
+        serde_json::to_vec(&fir.settings)
+            .expect("FIR settings always serialize to JSON")
+            .hash(&mut hasher);
+    }
+    for position in sorted(positions.keys())
+        .into_iter()
+        .map(|id| &positions[id])
+    {
+        position.id.hash(&mut hasher);
+        sorted(&position.prefixes).hash(&mut hasher);
+        position.frequency.hash(&mut hasher);
+        position.facility_type.hash(&mut hasher);
+        position.profile_id.hash(&mut hasher);
+        position.transmit.hash(&mut hasher);
+        position.monitor_frequencies.hash(&mut hasher);
+        position.fir_id.hash(&mut hasher);
+        sorted(&position.controlled_stations).hash(&mut hasher);
+    }
+    for station in sorted(stations.keys()).into_iter().map(|id| &stations[id]) {
+        station.id.hash(&mut hasher);
+        station.parent_id.hash(&mut hasher);
+        station.controlled_by.hash(&mut hasher);
+        station.fir_id.hash(&mut hasher);
+    }
+    for group in sorted(station_groups.keys())
+        .into_iter()
+        .map(|id| &station_groups[id])
+    {
+        group.id.hash(&mut hasher);
+        group.members.hash(&mut hasher);
+        group.fir_id.hash(&mut hasher);
+    }
+    for profile in sorted(profiles.keys()).into_iter().map(|id| &profiles[id]) {
+        profile.id.hash(&mut hasher);
+        profile.content_hash.hash(&mut hasher);
+    }
+
+    hasher.finish()
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -637,6 +1636,341 @@ mod tests {
         assert_eq!(network.positions.len(), 2);
     }
 
+    fn create_fir_with_repeated_station(dir: &std::path::Path) {
+        TestFirBuilder::new("LOVV")
+            .station("LOVV_CTR", &["LOVV_CTR"])
+            .station("LOWW_APP", &["LOWW_APP"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "Enroute")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .tabbed_profile(
+                "LOVV",
+                &[
+                    ("CTR", "LOVV_CTR"),
+                    ("CTR", "LOVV_CTR"),
+                    ("CTR", "LOVV_CTR"),
+                    ("APP", "LOWW_APP"),
+                    ("APP", "LOWW_APP"),
+                ],
+            )
+            .create(dir);
+    }
+
+    #[test]
+    fn write_to_dir_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP", "LOVV_CTR"])
+            .station_with_parent("LOWW_TWR", "LOWW_APP", &["LOWW_TWR"])
+            .station_with_parent("LOWW_GND", "LOWW_TWR", &["LOVV_CTR", "LOWW_GND"])
+            .station_group("LOWW_SURFACE", &["LOWW_TWR", "LOWW_GND"])
+            .position("LOVV_CTR", &["LOVV", "LOWW"], "132.600", "CTR")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position_with_profile("LOWW_TWR", &["LOWW"], "119.400", "TWR", "LOWW")
+            .position_with_profile("LOWW_GND", &["LOWW"], "121.600", "GND", "GEO")
+            .tabbed_profile("LOWW", &[("APP", "LOWW_APP"), ("SURFACE", "LOWW_SURFACE")])
+            .ice_server(&["stun:stun.example.org:3478"])
+            .create(dir.path());
+        std::fs::write(
+            dir.path().join("LOVV/profiles/GEO.toml"),
+            r##"
+            id = "GEO"
+            type = "Geo"
+            direction = "row"
+            gap = 1.5
+            [[children]]
+            label = ["TWR"]
+            size = 10.0
+            page.keys = [{ label = ["TWR"], station_id = "LOWW_TWR" }]
+            page.rows = 1
+            [[children]]
+            orientation = "vertical"
+            thickness = 1.0
+            color = "#000000"
+        "##,
+        )
+        .unwrap();
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        network.write_to_dir(out.path()).unwrap();
+        let reloaded = Network::load_from_dir(out.path()).unwrap();
+
+        assert_eq!(reloaded.content_hash(), network.content_hash());
+        assert_eq!(reloaded.digest(), network.digest());
+        assert_eq!(
+            reloaded.stations["LOWW_GND"].controlled_by,
+            network.stations["LOWW_GND"].controlled_by
+        );
+        let stations = std::fs::read_to_string(out.path().join("LOVV/stations.toml")).unwrap();
+        assert_eq!(stations.matches("LOVV_CTR").count(), 2);
+    }
+
+    #[test]
+    fn replace_fir_keeps_other_firs() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let mut network = Network::load_from_dir(dir.path()).unwrap();
+        network.set_fir_enabled(&FlightInformationRegionId::from("EDMM"), false);
+
+        let updated_dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOVV_CTR", &["LOVV_CTR"])
+            .station_with_parent("LOWW_APP", "LOVV_CTR", &["LOWW_APP"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .create(updated_dir.path());
+        let updated = Network::load_from_dir(updated_dir.path()).unwrap();
+
+        let lovv = FlightInformationRegionId::from("LOVV");
+        let edmm = FlightInformationRegionId::from("EDMM");
+        let replaced = network
+            .replace_fir(&lovv, &updated, &LoadOptions::default())
+            .unwrap();
+        assert!(replaced.has_station(&StationId::from("LOWW_APP")));
+        assert!(replaced.has_station(&StationId::from("EDMM_CTR")));
+        assert!(!replaced.is_fir_enabled(&edmm));
+        assert_eq!(
+            replaced.stations["LOWW_APP"].controlled_by,
+            updated.stations["LOWW_APP"].controlled_by
+        );
+        assert_eq!(
+            replaced.fir_coverage(&lovv),
+            HashSet::from([StationId::from("LOVV_CTR"), StationId::from("LOWW_APP")])
+        );
+        assert_eq!(
+            replaced.fir_coverage(&edmm),
+            HashSet::from([StationId::from("EDMM_CTR")])
+        );
+
+        let removed = network
+            .replace_fir(&lovv, &Network::empty(), &LoadOptions::default())
+            .unwrap();
+        assert!(!removed.has_fir(&lovv));
+        assert!(removed.has_fir(&edmm));
+        assert!(removed.fir_coverage(&lovv).is_empty());
+    }
+
+    #[test]
+    fn replace_fir_validates_references_of_other_firs() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        TestFirBuilder::new("EDMM")
+            .station("EDMM_CTR", &["EDMM_CTR", "LOVV_CTR"])
+            .position("EDMM_CTR", &["EDMM"], "127.950", "CTR")
+            .create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let errors = network
+            .replace_fir(
+                &FlightInformationRegionId::from("LOVV"),
+                &Network::empty(),
+                &LoadOptions::default(),
+            )
+            .unwrap_err();
+        assert!(errors.iter().any(|err| causes(err, |err| matches!(
+            err,
+            CoverageError::Validation(ValidationError::MissingReference { .. })
+        ))));
+    }
+
+    #[test]
+    fn load_from_dir_repeated_station_references_disabled_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_repeated_station(dir.path());
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        assert!(network.warnings().is_empty());
+    }
+
+    #[test]
+    fn load_from_dir_repeated_station_references_warns_above_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_repeated_station(dir.path());
+
+        let options = LoadOptions {
+            max_station_references_per_profile: Some(2),
+            ..Default::default()
+        };
+        let network = Network::load_from_dir_with_options(dir.path(), &options).unwrap();
+        assert_eq!(
+            network.warnings(),
+            &[CoverageWarning::RepeatedStationReference {
+                profile_id: "LOVV".to_string(),
+                station_id: "LOVV_CTR".to_string(),
+                count: 3,
+            }]
+        );
+        assert_eq!(network.profiles.len(), 1);
+    }
+
+    #[test]
+    fn load_from_dir_repeated_station_references_at_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_repeated_station(dir.path());
+
+        let options = LoadOptions {
+            max_station_references_per_profile: Some(3),
+            ..Default::default()
+        };
+        let network = Network::load_from_dir_with_options(dir.path(), &options).unwrap();
+        assert!(network.warnings().is_empty());
+    }
+
+    #[test]
+    fn uncoverable_profile_stations_valid_network() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_repeated_station(dir.path());
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        assert!(network.uncoverable_profile_stations().is_empty());
+        assert!(network.warnings().is_empty());
+    }
+
+    #[test]
+    fn uncoverable_profile_stations_orphaned() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_repeated_station(dir.path());
+        let mut network = Network::load_from_dir(dir.path()).unwrap();
+
+        // Loading rejects such networks, so break the coverage graph afterwards
+        network.positions.remove(&PositionId::from("LOWW_APP"));
+        network.stations.remove(&StationId::from("LOVV_CTR"));
+
+        let profile_id = ProfileId::from("LOVV");
+        assert_eq!(
+            network.uncoverable_profile_stations(),
+            vec![
+                (&profile_id, &StationId::from("LOVV_CTR")),
+                (&profile_id, &StationId::from("LOWW_APP")),
+            ]
+        );
+    }
+
+    fn create_fir_with_mismatched_frequency(dir: &std::path::Path) {
+        TestFirBuilder::new("LOVV")
+            .station("LOVV_CTR", &["LOVV_CTR"])
+            .station("LOWW_TWR", &["LOWW_TWR", "LOWW_DEL"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_DEL", &["LOWW"], "119.400", "DEL")
+            .position("LOVV_CTR", &["LOVV"], "199.998", "CTR")
+            .create(dir);
+    }
+
+    #[test]
+    fn load_from_dir_frequency_plausibility_disabled_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_mismatched_frequency(dir.path());
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        assert!(network.warnings().is_empty());
+    }
+
+    #[test]
+    fn load_from_dir_frequency_plausibility_warns() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_mismatched_frequency(dir.path());
+
+        let options = LoadOptions {
+            check_frequency_plausibility: true,
+            ..Default::default()
+        };
+        let network = Network::load_from_dir_with_options(dir.path(), &options).unwrap();
+        assert_eq!(
+            network.warnings(),
+            &[CoverageWarning::ImplausibleFrequency {
+                position_id: "LOWW_DEL".to_string(),
+                frequency: "119.400".to_string(),
+                facility_type: "DEL".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn load_from_dir_warnings_as_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_mismatched_frequency(dir.path());
+
+        let options = LoadOptions {
+            check_frequency_plausibility: true,
+            warnings_as_errors: true,
+            ..Default::default()
+        };
+        let errors = Network::load_from_dir_with_options(dir.path(), &options).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_matches!(
+            &errors[0],
+            CoverageError::Warning(CoverageWarning::ImplausibleFrequency { position_id, .. }) if position_id == "LOWW_DEL"
+        );
+    }
+
+    fn create_fir_with_profileless_positions(dir: &std::path::Path) {
+        TestFirBuilder::new("LOVV")
+            .station("LOVV_CTR", &["LOVV_CTR"])
+            .station("LOWW_TWR", &["LOWW_TWR", "LOVV_CTR"])
+            .station("LOWW_GND", &["LOWW_GND", "LOWW_TWR"])
+            .position_with_profile("LOVV_CTR", &["LOVV"], "134.350", "CTR", "LOVV")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_GND", &["LOWW"], "121.600", "GND")
+            .tabbed_profile("LOVV", &[("CTR", "LOVV_CTR")])
+            .tabbed_profile("LOWW", &[("TWR", "LOWW_TWR")])
+            .create(dir);
+    }
+
+    #[test]
+    fn load_from_dir_require_profiles_lists_profileless_positions() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_profileless_positions(dir.path());
+        assert!(Network::load_from_dir(dir.path()).is_ok());
+
+        let options = LoadOptions {
+            require_profiles: true,
+            profile_defaults: ProfileDefaults {
+                // Defaults missing from the network don't count
+                network: Some(ProfileId::from("MISSING")),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let errors = Network::load_from_dir_with_options(dir.path(), &options).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_matches!(
+            &errors[0],
+            CoverageError::Validation(ValidationError::MissingProfiles { positions }) if positions == &["LOWW_GND", "LOWW_TWR"]
+        );
+    }
+
+    #[test]
+    fn load_from_dir_require_profiles_considers_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        create_fir_with_profileless_positions(dir.path());
+
+        let options = LoadOptions {
+            require_profiles: true,
+            profile_defaults: ProfileDefaults {
+                facility_types: HashMap::from([(FacilityType::Tower, ProfileId::from("LOWW"))]),
+                network: None,
+            },
+            ..Default::default()
+        };
+        let errors = Network::load_from_dir_with_options(dir.path(), &options).unwrap_err();
+        assert_matches!(
+            &errors[0],
+            CoverageError::Validation(ValidationError::MissingProfiles { positions }) if positions == &["LOWW_GND"]
+        );
+
+        let options = LoadOptions {
+            profile_defaults: ProfileDefaults {
+                network: Some(ProfileId::from("LOWW")),
+                ..options.profile_defaults
+            },
+            ..options
+        };
+        let network = Network::load_from_dir_with_options(dir.path(), &options).unwrap();
+        assert_eq!(network.positions.len(), 3);
+    }
+
     #[test]
     #[cfg_attr(target_os = "windows", ignore)]
     fn load_from_dir_duplicate_fir_id() {
@@ -659,8 +1993,13 @@ mod tests {
             .create(dir.path());
 
         let errors = Network::load_from_dir(dir.path()).unwrap_err();
-        assert!(!errors.is_empty());
-        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Duplicate { entity, .. }) if entity == "Station"))));
+        assert_eq!(errors.len(), 1);
+        assert_matches!(
+            &errors[0],
+            CoverageError::Validation(ValidationError::DuplicateStation { station, definitions })
+                if station == "LOWW_TWR"
+                    && definitions == &["FIR `LOVV` (station #1)", "FIR `LOVV` (station #2)"]
+        );
     }
 
     #[test]
@@ -677,8 +2016,19 @@ mod tests {
             .create(dir.path());
 
         let errors = Network::load_from_dir(dir.path()).unwrap_err();
-        assert!(!errors.is_empty());
-        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Duplicate { entity, .. }) if entity == "Station"))));
+        let duplicate = errors
+            .iter()
+            .find(|e| {
+                matches!(
+                    e,
+                    CoverageError::Validation(ValidationError::DuplicateStation { .. })
+                )
+            })
+            .expect("duplicate station error");
+        assert_eq!(
+            duplicate.to_string(),
+            "station `LOWW_TWR` is defined multiple times: FIR `EDMM` (station #1), FIR `LOVV` (station #1)"
+        );
     }
 
     #[test]
@@ -729,6 +2079,83 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn load_from_dir_empty_coverage_inherited_from_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP"])
+            .station_with_parent("LOWW_TWR", "LOWW_APP", &[])
+            .position("LOWW_APP", &["LOWW"], "134.675", "Approach")
+            .create(dir.path());
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        assert_eq!(
+            network.stations[&StationId::from("LOWW_TWR")].controlled_by,
+            vec![PositionId::from("LOWW_APP")]
+        );
+        assert!(network.warnings().is_empty());
+    }
+
+    #[test]
+    fn load_from_dir_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        TestFirBuilder::new("EDMM")
+            .station("EDMM_CTR", &["EDMM_CTR"])
+            .station("EDDM_TWR", &["EDDM_TWR"])
+            .position("EDMM_CTR", &["EDMM"], "199.998", "Enroute")
+            .position("EDDM_TWR", &["EDDM"], "120.505", "Tower")
+            .tabbed_profile("EDMM", &[("CTR", "EDMM_CTR"), ("CTR2", "EDMM_CTR")])
+            .create(dir.path());
+
+        let network = Network::load_from_dir_with_options(
+            dir.path(),
+            &LoadOptions {
+                max_station_references_per_profile: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            network.summary(),
+            NetworkSummary {
+                firs: 2,
+                positions: 3,
+                stations: 3,
+                profiles: 1,
+                warnings: 1,
+            }
+        );
+        assert_eq!(
+            network.summary().to_string(),
+            "2 FIRs, 3 positions, 3 stations, 1 profiles, 1 warnings"
+        );
+    }
+
+    #[test]
+    fn load_from_dir_max_firs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["EDMM", "LOVV", "LZBB"] {
+            create_minimal_valid_fir(dir.path(), name);
+        }
+        // Files don't count towards the limit
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+
+        let options = |max_firs| LoadOptions {
+            max_firs: Some(max_firs),
+            ..Default::default()
+        };
+        let network = Network::load_from_dir_with_options(dir.path(), &options(3)).unwrap();
+        assert_eq!(network.summary().firs, 3);
+
+        let errors = Network::load_from_dir_with_options(dir.path(), &options(2)).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_matches!(
+            &errors[0],
+            CoverageError::Structure(StructureError::TooMany { entity, max: 2 }) if entity == "FIR"
+        );
+    }
+
     #[test]
     fn load_from_dir_error() {
         let dir = tempfile::tempdir().unwrap();
@@ -758,8 +2185,8 @@ mod tests {
         assert_eq!(errors.len(), 8);
         assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Load { entity, id, reason }) if entity == "FIR" && id.contains("FIR1") && reason.contains("stations.toml")))));
         assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Load { entity, id, reason }) if entity == "FIR" && id.contains("FIR1") && reason.contains("positions.toml")))));
-        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Duplicate { entity, id }) if entity == "Station" && id == "A"))));
-        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Duplicate { entity, id }) if entity == "Station" && id == "B"))));
+        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::DuplicateStation { station, .. }) if station == "A"))));
+        assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::DuplicateStation { station, .. }) if station == "B"))));
         assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Structure(StructureError::Duplicate { entity, id }) if entity == "Position" && id == "B"))));
         assert!(errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) if field == "position_id" && ref_id == "A"))));
     }
@@ -788,6 +2215,54 @@ mod tests {
         assert_eq!(positions[0].id.as_str(), "LOVV_CTR");
     }
 
+    #[test]
+    fn find_positions_relief_infix_match() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station("LOWW_E_TWR", &["LOWW_E_TWR"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_E_TWR", &["LOWW"], "119.400", "TWR")
+            .create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        for (callsign, expected) in [
+            ("LOWW_1_TWR", "LOWW_TWR"),
+            ("LOWW_T1_TWR", "LOWW_TWR"),
+            ("loww_t2_twr", "LOWW_TWR"),
+            ("LOWW_E_1_TWR", "LOWW_E_TWR"),
+            ("LOWW_E_T1_TWR", "LOWW_E_TWR"),
+        ] {
+            let positions = network.find_positions(callsign, "119.400", FacilityType::Tower);
+            assert_eq!(positions.len(), 1, "{callsign}");
+            assert_eq!(positions[0].id.as_str(), expected, "{callsign}");
+        }
+
+        // Unrecognized infixes are kept, leaving the selection to the user
+        let positions = network.find_positions("LOWW_X_TWR", "119.400", FacilityType::Tower);
+        assert_eq!(positions.len(), 2);
+    }
+
+    #[test]
+    fn find_positions_custom_relief_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station("LOWW_E_TWR", &["LOWW_E_TWR"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_E_TWR", &["LOWW"], "119.400", "TWR")
+            .create(dir.path());
+        let mut network = Network::load_from_dir(dir.path()).unwrap();
+        network.set_relief_patterns(ReliefPatterns::new(&["R"]).unwrap());
+
+        let positions = network.find_positions("LOWW_R_TWR", "119.400", FacilityType::Tower);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].id.as_str(), "LOWW_TWR");
+
+        let positions = network.find_positions("LOWW_1_TWR", "119.400", FacilityType::Tower);
+        assert_eq!(positions.len(), 2);
+    }
+
     #[test]
     fn find_positions_prefix_match() {
         let dir = tempfile::tempdir().unwrap();
@@ -817,13 +2292,42 @@ mod tests {
     }
 
     #[test]
-    fn find_positions_different_frequency() {
+    fn find_positions_different_frequency() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let positions = network.find_positions("LOVV_CTR", "121.500", FacilityType::Enroute);
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn find_positions_833_channel_representations() {
         let dir = tempfile::tempdir().unwrap();
-        create_minimal_valid_fir(dir.path(), "LOVV");
-        create_minimal_valid_fir(dir.path(), "EDMM");
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station("LOWW_GND", &["LOWW_GND"])
+            .position("LOWW_TWR", &["LOWW"], "118.005", "TWR")
+            .position("LOWW_GND", &["LOWW"], "121.610", "GND")
+            .create(dir.path());
         let network = Network::load_from_dir(dir.path()).unwrap();
 
-        let positions = network.find_positions("LOVV_CTR", "121.500", FacilityType::Enroute);
+        for frequency in ["118.005", "118.000", "118.0000"] {
+            let positions = network.find_positions("LOWW_TWR", frequency, FacilityType::Tower);
+            assert_eq!(positions.len(), 1, "{frequency}");
+            let positions = network.find_positions("LOWW_1_TWR", frequency, FacilityType::Tower);
+            assert_eq!(positions.len(), 1, "{frequency}");
+            assert_eq!(network.find_positions_by_frequency(frequency).len(), 1);
+        }
+        for frequency in ["121.610", "121.6083"] {
+            let positions = network.find_positions("LOWW_GND", frequency, FacilityType::Ground);
+            assert_eq!(positions.len(), 1, "{frequency}");
+        }
+
+        let positions = network.find_positions("LOWW_TWR", "118.010", FacilityType::Tower);
+        assert!(positions.is_empty());
+        let positions = network.find_positions("LOWW_GND", "121.600", FacilityType::Ground);
         assert!(positions.is_empty());
     }
 
@@ -881,6 +2385,60 @@ mod tests {
         assert_eq!(positions[0].id.as_str(), "LOWI_S_APP");
     }
 
+    #[test]
+    fn resolve_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWI_E_APP", &["LOWI_E_APP"])
+            .station("LOWI_S_APP", &["LOWI_S_APP"])
+            .station("LOWI_TWR", &["LOWI_TWR"])
+            .position("LOWI_S_APP", &["LOWI"], "128.975", "APP")
+            .position("LOWI_E_APP", &["LOWI"], "128.975", "APP")
+            .position("LOWI_N_APP", &["LOWI"], "119.275", "APP")
+            .position("LOWI_TWR", &["LOWI"], "120.100", "TWR")
+            .create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        let ids = |outcome: ResolveOutcome| match outcome {
+            ResolveOutcome::NoMatch => vec![],
+            ResolveOutcome::Single(position) => vec![position.id.as_str()],
+            ResolveOutcome::Ambiguous(positions) => {
+                assert!(positions.len() > 1);
+                positions.iter().map(|p| p.id.as_str()).collect()
+            }
+        };
+
+        assert!(matches!(
+            network.resolve("LOWI_TWR", "120.100", FacilityType::Tower),
+            ResolveOutcome::Single(position) if position.id.as_str() == "LOWI_TWR"
+        ));
+        assert_eq!(
+            ids(network.resolve("LOWI_X_APP", "128.975", FacilityType::Approach)),
+            vec!["LOWI_E_APP", "LOWI_S_APP"]
+        );
+        // Positions sharing a prefix are told apart by their frequency
+        assert!(matches!(
+            network.resolve("LOWI_X_APP", "119.275", FacilityType::Approach),
+            ResolveOutcome::Single(position) if position.id.as_str() == "LOWI_N_APP"
+        ));
+        assert_eq!(
+            network.resolve("LOWX_TWR", "120.100", FacilityType::Tower),
+            ResolveOutcome::NoMatch
+        );
+
+        // Frequency-only matching only applies to unique frequencies
+        let options = ResolveOptions {
+            frequency_only_matching: true,
+        };
+        assert_eq!(
+            ids(network.resolve_with_options("LOWX_TWR", "120.100", FacilityType::Tower, &options)),
+            vec!["LOWI_TWR"]
+        );
+        assert_eq!(
+            network.resolve_with_options("LOWX_APP", "128.975", FacilityType::Approach, &options),
+            ResolveOutcome::NoMatch
+        );
+    }
+
     #[test]
     fn relevant_stations_specific_found() {
         let dir = tempfile::tempdir().unwrap();
@@ -1045,6 +2603,44 @@ mod tests {
         assert!(pos.is_none());
     }
 
+    #[test]
+    fn controlling_position_disabled_fir() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let mut network = Network::load_from_dir(dir.path()).unwrap();
+
+        let online = ["LOVV_CTR", "EDMM_CTR"]
+            .into_iter()
+            .map(PositionId::from)
+            .collect::<HashSet<_>>();
+        let online = online.iter().collect();
+        let fir_id = FlightInformationRegionId::from("LOVV");
+
+        assert!(network.set_fir_enabled(&fir_id, false));
+        assert!(!network.set_fir_enabled(&fir_id, false));
+        assert!(!network.is_fir_enabled(&fir_id));
+        assert!(
+            network
+                .controlling_position(&StationId::from("LOVV_CTR"), &online)
+                .is_none()
+        );
+        assert!(
+            network
+                .controlling_position(&StationId::from("EDMM_CTR"), &online)
+                .is_some()
+        );
+
+        assert!(network.set_fir_enabled(&fir_id, true));
+        assert!(
+            network
+                .controlling_position(&StationId::from("LOVV_CTR"), &online)
+                .is_some()
+        );
+
+        assert!(!network.set_fir_enabled(&FlightInformationRegionId::from("LOWW"), false));
+    }
+
     #[test]
     fn covered_stations_basic() {
         let dir = tempfile::tempdir().unwrap();
@@ -1062,6 +2658,69 @@ mod tests {
         assert!(!covered[0].is_self_controlled);
     }
 
+    fn assert_coverage_closure_matches_covered_stations(
+        network: &Network,
+        position_id: &PositionId,
+    ) -> HashSet<StationId> {
+        let closure = network.coverage_closure(position_id);
+        let covered = network
+            .covered_stations(None, &HashSet::from([position_id]))
+            .into_iter()
+            .map(|s| s.station.id.clone())
+            .collect::<HashSet<_>>();
+        assert_eq!(closure, covered);
+        closure
+    }
+
+    #[test]
+    fn coverage_closure_ctr() {
+        let dir = tempfile::tempdir().unwrap();
+        create_extended_valid_fir(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let closure =
+            assert_coverage_closure_matches_covered_stations(&network, &"LOVV_CTR".into());
+        for station_id in [
+            "LOVV_E1",
+            "LOVV_E2",
+            "LOWW_APP",
+            "LOWW_TWR",
+            "LOWW_E_TWR",
+            "LOWW_GND",
+            "LOWW_W_GND",
+            "LOWW_DEL",
+        ] {
+            assert!(
+                closure.contains(station_id),
+                "expected {station_id} in closure {closure:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn coverage_closure_del() {
+        let dir = tempfile::tempdir().unwrap();
+        create_extended_valid_fir(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let closure =
+            assert_coverage_closure_matches_covered_stations(&network, &"LOWW_DEL".into());
+        assert_eq!(closure, HashSet::from([StationId::from("LOWW_DEL")]));
+    }
+
+    #[test]
+    fn coverage_closure_unknown_or_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        create_extended_valid_fir(dir.path());
+        let mut network = Network::load_from_dir(dir.path()).unwrap();
+
+        assert!(network.coverage_closure(&"LOWW_XYZ".into()).is_empty());
+
+        network.set_fir_enabled(&FlightInformationRegionId::from("LOVV"), false);
+        assert_coverage_closure_matches_covered_stations(&network, &"LOVV_CTR".into());
+        assert!(network.coverage_closure(&"LOVV_CTR".into()).is_empty());
+    }
+
     #[test]
     fn covered_stations_complex() {
         let dir = tempfile::tempdir().unwrap();
@@ -1523,4 +3182,440 @@ mod tests {
         let res = Network::load_from_dir(dir.path());
         assert_matches!(res, Err(errors) if errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) if field == "station_id" && ref_id == "NON_EXISTENT"))));
     }
+
+    #[test]
+    fn ice_servers_per_fir() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOVV_CTR", &["LOVV_CTR"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .ice_server(&["turn:lovv.example.com:3478"])
+            .create(dir.path());
+        create_minimal_valid_fir(dir.path(), "EDMM");
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        let expected = vec![IceServer::from("turn:lovv.example.com:3478".to_string())];
+        assert_eq!(
+            network.ice_servers(&PositionId::from("LOVV_CTR")),
+            Some(expected.as_slice())
+        );
+        assert_eq!(network.ice_servers(&PositionId::from("EDMM_CTR")), None);
+        assert_eq!(network.ice_servers(&PositionId::from("UNKNOWN")), None);
+    }
+
+    fn fir_with_settings(settings: &str) -> TestFirBuilder {
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP", "LOVV_CTR"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .position_with_profile("LOWW_APP", &["LOWW"], "134.675", "APP", "APP")
+            .tabbed_profile("APP", &[("APP", "LOWW_APP")])
+            .tabbed_profile("DEFAULT", &[("APP", "LOWW_APP")])
+            .fir_settings(settings)
+    }
+
+    #[test]
+    fn fir_settings_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        fir_with_settings(
+            r#"
+            default_profile = "DEFAULT"
+            transmit = false
+            [[ice_servers]]
+            urls = ["turn:lovv.example.com:3478"]
+            "#,
+        )
+        .create(dir.path());
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let lovv = network
+            .fir_settings(&FlightInformationRegionId::from("LOVV"))
+            .unwrap();
+        assert_eq!(lovv.default_profile, Some(ProfileId::from("DEFAULT")));
+        assert!(!lovv.transmit);
+
+        let ctr = network.get_position(&PositionId::from("LOVV_CTR")).unwrap();
+        assert_eq!(ctr.profile_id, Some(ProfileId::from("DEFAULT")));
+        assert!(!ctr.transmit);
+        let app = network.get_position(&PositionId::from("LOWW_APP")).unwrap();
+        assert_eq!(app.profile_id, Some(ProfileId::from("APP")));
+        assert!(!app.transmit);
+        assert_eq!(
+            network.ice_servers(&PositionId::from("LOVV_CTR")),
+            Some([IceServer::from("turn:lovv.example.com:3478".to_string())].as_slice())
+        );
+
+        // FIRs without `fir.toml` use the defaults
+        assert_eq!(
+            network.fir_settings(&FlightInformationRegionId::from("EDMM")),
+            Some(&FirSettings::default())
+        );
+        let edmm = network.get_position(&PositionId::from("EDMM_CTR")).unwrap();
+        assert_eq!(edmm.profile_id, None);
+        assert!(edmm.transmit);
+        assert_eq!(network.ice_servers(&PositionId::from("EDMM_CTR")), None);
+    }
+
+    #[test]
+    fn fir_settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        fir_with_settings("default_profile = \"DEFAULT\"\ntransmit = false").create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        network.write_to_dir(out.path()).unwrap();
+        let reloaded = Network::load_from_dir(out.path()).unwrap();
+
+        assert_eq!(reloaded.content_hash(), network.content_hash());
+        assert_eq!(reloaded.digest(), network.digest());
+        let positions = std::fs::read_to_string(out.path().join("LOVV/positions.toml")).unwrap();
+        assert!(!positions.contains("DEFAULT"));
+        assert!(!positions.contains("transmit"));
+    }
+
+    #[test]
+    fn fir_settings_missing_default_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        fir_with_settings("default_profile = \"MISSING\"").create(dir.path());
+
+        let res = Network::load_from_dir(dir.path());
+        assert_matches!(res, Err(errors) if errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) if field == "default_profile" && ref_id == "MISSING"))));
+    }
+
+    #[test]
+    fn relevant_firs_include_neighbors() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        create_minimal_valid_fir(dir.path(), "LKAA");
+        TestFirBuilder::new("LOVV")
+            .fir_settings(r#"neighbors = ["EDMM"]"#)
+            .create(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let lovv = FlightInformationRegionId::from("LOVV");
+        let edmm = FlightInformationRegionId::from("EDMM");
+        assert_eq!(
+            network.relevant_firs(&lovv),
+            Some(HashSet::from([&lovv, &edmm]))
+        );
+        assert_eq!(network.relevant_firs(&edmm), None);
+        assert_eq!(
+            network.relevant_firs(&FlightInformationRegionId::from("UNKNOWN")),
+            None
+        );
+        assert_eq!(
+            network.station_fir(&StationId::from("LKAA_CTR")),
+            Some(&FlightInformationRegionId::from("LKAA"))
+        );
+    }
+
+    #[test]
+    fn fir_settings_missing_neighbor() {
+        let dir = tempfile::tempdir().unwrap();
+        create_minimal_valid_fir(dir.path(), "LOVV");
+        TestFirBuilder::new("LOVV")
+            .fir_settings(r#"neighbors = ["EDMM"]"#)
+            .create(dir.path());
+
+        let res = Network::load_from_dir(dir.path());
+        assert_matches!(res, Err(errors) if errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) if field == "neighbors" && ref_id == "EDMM"))));
+    }
+
+    #[test]
+    fn station_group_expands_members() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station("LOWW_E_TWR", &["LOWW_E_TWR", "LOWW_TWR"])
+            .station_group("LOWW_TWR_ALL", &["LOWW_TWR", "LOWW_E_TWR"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .position("LOWW_E_TWR", &["LOWW"], "123.800", "TWR")
+            .tabbed_profile("P", &[("TWR", "LOWW_TWR_ALL")])
+            .create(dir.path());
+
+        let network = Network::load_from_dir(dir.path()).unwrap();
+        assert_eq!(
+            network.station_group_members(&StationId::from("LOWW_TWR_ALL")),
+            Some([StationId::from("LOWW_TWR"), StationId::from("LOWW_E_TWR")].as_slice())
+        );
+        assert_eq!(
+            network.station_group_members(&StationId::from("LOWW_TWR")),
+            None
+        );
+        assert!(!network.has_station(&StationId::from("LOWW_TWR_ALL")));
+
+        let result = network.relevant_stations(&ActiveProfile::Specific(ProfileId::from("P")));
+        assert_matches!(
+            result,
+            RelevantStations::Subset(ids)
+                if *ids == HashSet::from([StationId::from("LOWW_TWR"), StationId::from("LOWW_E_TWR")])
+        );
+    }
+
+    #[test]
+    fn station_group_missing_member() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station_group("LOWW_TWR_ALL", &["LOWW_TWR", "LOWW_E_TWR"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .create(dir.path());
+
+        let res = Network::load_from_dir(dir.path());
+        assert_matches!(res, Err(errors) if errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::MissingReference { field, ref_id }) if field == "members" && ref_id == "LOWW_E_TWR"))));
+    }
+
+    #[test]
+    fn station_group_id_collides_with_station() {
+        let dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR"])
+            .station_group("LOWW_TWR", &["LOWW_TWR"])
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .create(dir.path());
+
+        let res = Network::load_from_dir(dir.path());
+        assert_matches!(res, Err(errors) if errors.iter().any(|e| causes(e, |x| matches!(x, CoverageError::Validation(ValidationError::InvalidValue { field, value, .. }) if field == "id" && value == "LOWW_TWR"))));
+    }
+
+    fn profile_fir(layout: &[(&str, &str)]) -> TestFirBuilder {
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP"])
+            .station("LOWW_TWR", &["LOWW_TWR", "LOWW_APP"])
+            .position_with_profile("LOWW_APP", &["LOWW"], "134.675", "APP", "APP_PROFILE")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .tabbed_profile("APP_PROFILE", layout)
+    }
+
+    #[test]
+    fn replace_profiles_keeps_coverage_and_runtime_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut network = profile_fir(&[("LOWW TWR", "LOWW_TWR")]).build(dir.path());
+        network.set_fir_enabled(&FlightInformationRegionId::from("LOVV"), false);
+
+        let dir = tempfile::tempdir().unwrap();
+        let updated = profile_fir(&[("TWR", "LOWW_TWR"), ("APP", "LOWW_APP")])
+            .tabbed_profile("TWR_PROFILE", &[("LOWW APP", "LOWW_APP")])
+            .build(dir.path());
+        let profile_hash = updated
+            .get_profile(&ProfileId::from("APP_PROFILE"))
+            .unwrap()
+            .content_hash;
+        let content_hash = updated.content_hash();
+        let digest = updated.digest().to_string();
+
+        network.replace_profiles(updated).unwrap();
+        assert_eq!(
+            network
+                .get_profile(&ProfileId::from("APP_PROFILE"))
+                .unwrap()
+                .content_hash,
+            profile_hash
+        );
+        assert!(
+            network
+                .get_profile(&ProfileId::from("TWR_PROFILE"))
+                .is_some()
+        );
+        assert_eq!(network.content_hash(), content_hash);
+        assert_eq!(network.digest(), digest);
+        assert!(!network.is_fir_enabled(&FlightInformationRegionId::from("LOVV")));
+    }
+
+    #[test]
+    fn replace_profiles_rejects_coverage_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut network = profile_fir(&[("LOWW TWR", "LOWW_TWR")]).build(dir.path());
+        let content_hash = network.content_hash();
+
+        let dir = tempfile::tempdir().unwrap();
+        let updated = profile_fir(&[("LOWW TWR", "LOWW_TWR")])
+            .station("LOWW_GND", &["LOWW_TWR"])
+            .build(dir.path());
+
+        assert_matches!(
+            network.replace_profiles(updated),
+            Err(CoverageError::Validation(ValidationError::Custom(_)))
+        );
+        assert_eq!(network.content_hash(), content_hash);
+        assert!(!network.has_station(&StationId::from("LOWW_GND")));
+    }
+
+    #[test]
+    fn digest_of_empty_network() {
+        assert_eq!(
+            Network::empty().digest(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn digest_covers_written_dataset_files() {
+        fn collect_files(root: &std::path::Path, dir: &std::path::Path, files: &mut Vec<String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    collect_files(root, &path, files);
+                } else {
+                    let relative = path.strip_prefix(root).unwrap().components();
+                    files.push(
+                        relative
+                            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                            .collect::<Vec<_>>()
+                            .join("/"),
+                    );
+                }
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        profile_fir(&[("LOWW TWR", "LOWW_TWR")]).create(dir.path());
+        create_minimal_valid_fir(dir.path(), "EDMM");
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        network.write_to_dir(out.path()).unwrap();
+        let mut files = Vec::new();
+        collect_files(out.path(), out.path(), &mut files);
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        for file in &files {
+            hasher.update(file.as_bytes());
+            hasher.update([0]);
+            hasher.update(std::fs::read(out.path().join(file)).unwrap());
+            hasher.update([0]);
+        }
+        assert_eq!(network.digest(), format!("{:x}", hasher.finalize()));
+        assert_eq!(
+            Network::load_from_dir(out.path()).unwrap().digest(),
+            network.digest()
+        );
+    }
+
+    #[test]
+    fn digest_changes_with_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = profile_fir(&[("LOWW TWR", "LOWW_TWR")]).build(dir.path());
+
+        let dir = tempfile::tempdir().unwrap();
+        let updated = profile_fir(&[("TWR", "LOWW_TWR")]).build(dir.path());
+
+        assert_ne!(network.digest(), updated.digest());
+    }
+
+    #[test]
+    fn validate_stations_valid_candidate() {
+        let dir = tempfile::tempdir().unwrap();
+        create_extended_valid_fir(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let candidate = r#"
+            [[stations]]
+            id = "LOWW_TWR"
+            parent_id = "LOWW_APP"
+            controlled_by = ["LOWW_TWR"]
+
+            [[stations]]
+            id = "LOWW_N_TWR"
+            parent_id = "LOWW_TWR"
+            controlled_by = ["LOWW_E_TWR"]
+
+            [[groups]]
+            id = "LOWW_TWRS"
+            members = ["LOWW_TWR", "LOWW_N_TWR"]
+        "#;
+        network.validate_stations(candidate).unwrap();
+    }
+
+    #[test]
+    fn validate_stations_reports_all_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        create_extended_valid_fir(dir.path());
+        let network = Network::load_from_dir(dir.path()).unwrap();
+
+        let candidate = r#"
+            [[stations]]
+            id = "LOWW_TWR"
+            parent_id = "LOWW_APP"
+            controlled_by = ["LOWW_X_TWR"]
+
+            [[stations]]
+            id = "LOWW_TWR"
+            controlled_by = ["LOWW_TWR"]
+
+            [[stations]]
+            id = "LOWW_N_TWR"
+            parent_id = "LOWW_N_APP"
+            controlled_by = ["LOWW_TWR"]
+
+            [[stations]]
+            id = "LOWW_S_TWR"
+        "#;
+        let errors = network.validate_stations(candidate).unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().any(|err| causes(err, |err| matches!(
+            err,
+            CoverageError::Validation(ValidationError::DuplicateStation { station, .. }) if station == "LOWW_TWR"
+        ))));
+        assert!(errors.iter().any(|err| causes(err, |err| matches!(
+            err,
+            CoverageError::Validation(ValidationError::MissingReference { ref_id, .. }) if ref_id == "LOWW_X_TWR"
+        ))));
+        assert!(errors.iter().any(|err| causes(err, |err| matches!(
+            err,
+            CoverageError::Structure(StructureError::Load { id, .. }) if id == "LOWW_N_TWR"
+        ))));
+        assert!(errors.iter().any(|err| causes(err, |err| matches!(
+            err,
+            CoverageError::Validation(ValidationError::EmptyCoverage(station)) if station == "LOWW_S_TWR"
+        ))));
+
+        assert_matches!(
+            network
+                .validate_stations("[[stations]]")
+                .unwrap_err()
+                .as_slice(),
+            [CoverageError::Io(IoError::Parse { .. })]
+        );
+    }
+
+    #[test]
+    fn find_positions_reachable_on_monitored_frequency() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = TestFirBuilder::new("LOVV")
+            .station("LOWW_TWR", &["LOWW_TWR", "LOWW_APP", "LOVV_GRD"])
+            .monitoring_position("LOWW_TWR", &["LOWW"], "119.400", "TWR", &["121.500"])
+            .monitoring_position("LOWW_APP", &["LOWW"], "134.675", "APP", &["121.500"])
+            .position("LOVV_GRD", &["LOVV"], "121.500", "CTR")
+            .build(dir.path());
+
+        let reachable = network
+            .find_positions_reachable_on("121.500")
+            .into_iter()
+            .map(|p| p.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(reachable, vec!["LOVV_GRD", "LOWW_APP", "LOWW_TWR"]);
+
+        // The monitored frequency doesn't identify the position, the primary stays advertised
+        let position = network.get_position(&PositionId::from("LOWW_TWR")).unwrap();
+        assert_eq!(position.frequency, "119.400");
+        assert!(position.is_reachable_on("119.400"));
+        assert!(position.is_reachable_on("121.500"));
+        assert!(!position.is_reachable_on("121.600"));
+        assert_eq!(
+            network
+                .find_positions_by_frequency("121.500")
+                .into_iter()
+                .map(|p| p.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["LOVV_GRD"]
+        );
+        assert!(
+            network
+                .find_positions("LOWW_TWR", "121.500", FacilityType::Tower)
+                .is_empty()
+        );
+    }
 }
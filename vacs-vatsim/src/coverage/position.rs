@@ -1,9 +1,9 @@
-use crate::FacilityType;
 use crate::coverage::flight_information_region::FlightInformationRegionId;
 use crate::coverage::{CoverageError, ReferenceValidator, ValidationError, Validator};
+use crate::{FacilityType, frequencies_match};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::LazyLock;
 use vacs_protocol::profile::ProfileId;
 use vacs_protocol::vatsim::{PositionId, StationId};
@@ -17,6 +17,13 @@ pub struct Position {
     pub frequency: String,
     pub facility_type: FacilityType,
     pub profile_id: Option<ProfileId>,
+    /// Whether clients on this position may originate calls. Receive-only positions (e.g. flow
+    /// management) can still be called.
+    pub transmit: bool,
+    /// Additional frequencies monitored by the position (e.g. guard or emergency frequencies).
+    /// Callers can reach the position on these, but they don't identify it, so the primary
+    /// [`Self::frequency`] is still the one advertised and used for matching controllers.
+    pub monitor_frequencies: Vec<String>,
     pub fir_id: FlightInformationRegionId,
     pub controlled_stations: HashSet<StationId>,
 }
@@ -24,11 +31,16 @@ pub struct Position {
 #[derive(Clone, Serialize, Deserialize)]
 pub struct PositionRaw {
     pub id: PositionId,
+    #[serde(serialize_with = "serialize_sorted")]
     pub prefixes: HashSet<String>,
     pub frequency: String,
     pub facility_type: FacilityType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub profile_id: Option<ProfileId>,
+    #[serde(default = "default_transmit", skip_serializing_if = "is_transmit")]
+    pub transmit: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub monitor_frequencies: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +56,8 @@ impl std::fmt::Debug for Position {
             .field("frequency", &self.frequency)
             .field("facility_type", &self.facility_type)
             .field("profile_id", &self.profile_id)
+            .field("transmit", &self.transmit)
+            .field("monitor_frequencies", &self.monitor_frequencies)
             .field("fir_id", &self.fir_id)
             .field("controlled_stations", &self.controlled_stations.len())
             .finish()
@@ -75,10 +89,106 @@ impl Position {
             frequency: position_raw.frequency,
             facility_type: position_raw.facility_type,
             profile_id: position_raw.profile_id,
+            transmit: position_raw.transmit,
+            monitor_frequencies: position_raw.monitor_frequencies,
             fir_id: fir_id.into(),
             controlled_stations: HashSet::new(),
         })
     }
+
+    /// Whether the frequency lies within the conventional range of the position's facility type,
+    /// see [`FacilityType::conventional_frequency_range`]. The VATSIM placeholder frequency is
+    /// always considered plausible.
+    pub fn has_plausible_frequency(&self) -> bool {
+        if self.frequency == crate::PLACEHOLDER_FREQUENCY {
+            return true;
+        }
+        let Some(range) = self.facility_type.conventional_frequency_range() else {
+            return true;
+        };
+        self.frequency
+            .replace('.', "")
+            .parse::<u32>()
+            .is_ok_and(|khz| range.contains(&khz))
+    }
+
+    /// Whether the position monitors `frequency` in addition to its primary frequency.
+    pub fn monitors(&self, frequency: &str) -> bool {
+        self.monitor_frequencies
+            .iter()
+            .any(|monitored| frequencies_match(monitored, frequency))
+    }
+
+    /// Whether callers on `frequency` can reach the position, i.e. it's either the position's
+    /// primary frequency or one of its monitored frequencies.
+    pub fn is_reachable_on(&self, frequency: &str) -> bool {
+        frequencies_match(&self.frequency, frequency) || self.monitors(frequency)
+    }
+
+    /// Validates frequencies to be monitored on the position at runtime, applying the same rules
+    /// as the [`Self::monitor_frequencies`] of the dataset.
+    pub fn validate_monitor_frequencies(
+        &self,
+        frequencies: &[String],
+    ) -> Result<(), ValidationError> {
+        validate_monitor_frequencies(&self.frequency, frequencies)
+    }
+}
+
+fn validate_monitor_frequencies(
+    primary: &str,
+    frequencies: &[String],
+) -> Result<(), ValidationError> {
+    for frequency in frequencies {
+        if !FREQUENCY_REGEX.is_match(frequency) {
+            return Err(ValidationError::InvalidFormat {
+                field: "monitor_frequencies".to_string(),
+                value: frequency.clone(),
+                reason: "must match pattern XXX.XXX".to_string(),
+            });
+        }
+        if frequencies_match(frequency, primary) {
+            return Err(ValidationError::InvalidValue {
+                field: "monitor_frequencies".to_string(),
+                value: frequency.clone(),
+                reason: "must not repeat the primary frequency".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+impl From<&Position> for PositionRaw {
+    fn from(position: &Position) -> Self {
+        Self {
+            id: position.id.clone(),
+            prefixes: position.prefixes.clone(),
+            frequency: position.frequency.clone(),
+            facility_type: position.facility_type,
+            profile_id: position.profile_id.clone(),
+            transmit: position.transmit,
+            monitor_frequencies: position.monitor_frequencies.clone(),
+        }
+    }
+}
+
+fn default_transmit() -> bool {
+    true
+}
+
+fn is_transmit(transmit: &bool) -> bool {
+    *transmit
+}
+
+/// Serializes the prefixes in a stable order, keeping written datasets diffable.
+fn serialize_sorted<S>(prefixes: &HashSet<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    prefixes
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .serialize(serializer)
 }
 
 impl std::fmt::Debug for PositionRaw {
@@ -89,6 +199,8 @@ impl std::fmt::Debug for PositionRaw {
             .field("frequency", &self.frequency)
             .field("facility_type", &self.facility_type)
             .field("profile_id", &self.profile_id)
+            .field("transmit", &self.transmit)
+            .field("monitor_frequencies", &self.monitor_frequencies)
             .finish()
     }
 }
@@ -120,6 +232,7 @@ impl Validator for PositionRaw {
             }
             .into());
         }
+        validate_monitor_frequencies(&self.frequency, &self.monitor_frequencies)?;
         if self.facility_type == FacilityType::Unknown {
             return Err(ValidationError::InvalidValue {
                 field: "facility_type".to_string(),
@@ -166,6 +279,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert!(raw.validate().is_ok());
     }
@@ -178,6 +293,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -194,6 +311,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -207,6 +326,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -223,6 +344,8 @@ mod tests {
             frequency: "".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -240,6 +363,8 @@ mod tests {
                 frequency: freq.to_string(),
                 facility_type: FacilityType::Tower,
                 profile_id: Some(ProfileId::from("LOWW")),
+                transmit: true,
+                monitor_frequencies: Vec::new(),
             };
             assert_matches!(
                 raw.validate(),
@@ -249,6 +374,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn position_raw_invalid_monitor_frequencies() {
+        let raw = PositionRaw {
+            id: "LOWW_TWR".into(),
+            prefixes: HashSet::from(["LOWW".to_string()]),
+            frequency: "119.400".to_string(),
+            facility_type: FacilityType::Tower,
+            profile_id: None,
+            transmit: true,
+            monitor_frequencies: vec!["121.500".to_string()],
+        };
+        assert!(raw.validate().is_ok());
+
+        let raw = PositionRaw {
+            monitor_frequencies: vec!["121.5".to_string()],
+            ..raw.clone()
+        };
+        assert_matches!(
+            raw.validate(),
+            Err(CoverageError::Validation(ValidationError::InvalidFormat { field, .. })) if field == "monitor_frequencies"
+        );
+
+        let raw = PositionRaw {
+            monitor_frequencies: vec!["119.400".to_string()],
+            ..raw
+        };
+        assert_matches!(
+            raw.validate(),
+            Err(CoverageError::Validation(ValidationError::InvalidValue { field, .. })) if field == "monitor_frequencies"
+        );
+    }
+
     #[test]
     fn position_raw_invalid_facility_type() {
         let raw = PositionRaw {
@@ -257,6 +414,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Unknown,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -273,6 +432,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw.validate(),
@@ -288,6 +449,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         let pos = Position::from_raw(raw, "LOVV").unwrap();
         assert_eq!(pos.id.as_str(), "LOWW_TWR");
@@ -306,6 +469,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
             fir_id: FlightInformationRegionId::from("LOVV"),
             controlled_stations: HashSet::new(),
         };
@@ -315,6 +480,8 @@ mod tests {
             frequency: "119.000".to_string(),          // Different content
             facility_type: FacilityType::Ground,       // Different content
             profile_id: Some(ProfileId::from("LOVV")), // Different content
+            transmit: false,                           // Different content
+            monitor_frequencies: Vec::new(),
             fir_id: FlightInformationRegionId::from("LOVV"),
             controlled_stations: HashSet::new(),
         };
@@ -326,6 +493,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("LOWW")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
             fir_id: FlightInformationRegionId::from("LOVV"),
             controlled_stations: HashSet::new(),
         };
@@ -344,6 +513,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(profile_id.clone()),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert!(raw.validate_references(&valid_profiles).is_ok());
 
@@ -353,6 +524,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: Some(ProfileId::from("UNKNOWN")),
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert_matches!(
             raw_missing.validate_references(&valid_profiles),
@@ -366,6 +539,8 @@ mod tests {
             frequency: "119.400".to_string(),
             facility_type: FacilityType::Tower,
             profile_id: None,
+            transmit: true,
+            monitor_frequencies: Vec::new(),
         };
         assert!(raw_none.validate_references(&valid_profiles).is_ok());
     }
@@ -1,12 +1,15 @@
+use crate::coverage::station::StationGroup;
 use crate::coverage::{CoverageError, IoError, ReferenceValidator, ValidationError, Validator};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::LazyLock;
 use vacs_protocol::profile::client_page::ClientPageConfig;
 use vacs_protocol::profile::geo::{
-    GeoNode, GeoPageButton, GeoPageContainer, GeoPageDivider, JustifyContent,
+    FlexDirection, GeoNode, GeoPageButton, GeoPageContainer, GeoPageDivider,
+    GeoPageDividerOrientation, JustifyContent,
 };
 use vacs_protocol::profile::tabbed::Tab;
 use vacs_protocol::profile::{
@@ -18,11 +21,21 @@ use vacs_protocol::vatsim::StationId;
 static GEO_PAGE_CONTAINER_SIZE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\d+(%|rem)$").unwrap());
 
+/// Root font size assumed when converting divider thicknesses (in px) to rem.
+const GEO_PAGE_REM_IN_PX: f64 = 16.0;
+/// Tolerance for rounding errors when comparing GEO page layout sizes.
+const GEO_PAGE_LAYOUT_TOLERANCE: f64 = 1e-6;
+
 #[derive(Clone)]
 pub struct Profile {
     pub id: ProfileId,
     pub profile_type: ProfileType,
     pub relevant_station_ids: HashSet<StationId>,
+    /// Number of times each station is referenced across all keys and buttons of the profile.
+    pub station_reference_counts: HashMap<StationId, usize>,
+    /// Hash of the resolved profile content, used to cheaply detect content changes between
+    /// dataset reloads. Only stable within a single process.
+    pub content_hash: u64,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -122,14 +135,9 @@ pub(super) struct DirectAccessKeyRaw {
 
 impl Profile {
     pub fn load(path: &PathBuf) -> Result<Self, CoverageError> {
-        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-
-        let bytes = std::fs::read(path).map_err(|err| IoError::Read {
-            path: path.into(),
-            reason: err.to_string(),
-        })?;
+        let (ext, bytes) = crate::coverage::read_dataset_file(path)?;
 
-        let profile: ProfileRaw = match ext {
+        let profile: ProfileRaw = match ext.as_str() {
             "toml" => toml::from_slice(&bytes).map_err(|err| IoError::Parse {
                 path: path.into(),
                 reason: err.to_string(),
@@ -149,6 +157,138 @@ impl Profile {
 
         Profile::from_raw(profile)
     }
+
+    /// Replaces all station group IDs in [`Self::relevant_station_ids`] by the group's members.
+    pub(super) fn expand_station_groups(&mut self, groups: &HashMap<StationId, StationGroup>) {
+        let group_ids = self
+            .relevant_station_ids
+            .iter()
+            .filter(|id| groups.contains_key(*id))
+            .cloned()
+            .collect::<Vec<_>>();
+        for group_id in group_ids {
+            self.relevant_station_ids.remove(&group_id);
+            self.relevant_station_ids
+                .extend(groups[&group_id].members.iter().cloned());
+        }
+    }
+
+    /// Returns all stations referenced more than `max_references` times, sorted by station ID.
+    pub fn repeated_station_references(&self, max_references: usize) -> Vec<(&StationId, usize)> {
+        let mut repeated = self
+            .station_reference_counts
+            .iter()
+            .filter(|(_, count)| **count > max_references)
+            .map(|(station_id, count)| (station_id, *count))
+            .collect::<Vec<_>>();
+        repeated.sort_by(|a, b| a.0.cmp(b.0));
+        repeated
+    }
+
+    /// Returns all fixed-size (rem) containers of a GEO profile whose children obviously can't
+    /// fit, in layout order. The check is best-effort: buttons and fixed-size containers count
+    /// with their declared size, while percentage-sized containers are flexible and only count with
+    /// the size of their children.
+    pub fn geo_layout_overflows(&self) -> Vec<GeoLayoutOverflow> {
+        let mut overflows = Vec::new();
+        if let ProfileType::Geo(container) = &self.profile_type {
+            collect_geo_layout_overflows(container, "root".to_string(), &mut overflows);
+        }
+        overflows
+    }
+}
+
+/// A fixed-size GEO page container too small for its children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLayoutOverflow {
+    /// Location of the container within the profile, e.g. `root.children[1]`.
+    pub path: String,
+    /// Overflowing dimension, either `width` or `height`.
+    pub dimension: &'static str,
+    /// Declared size of the container (in rem).
+    pub declared: f64,
+    /// Minimum size required by the children, including gaps and padding (in rem).
+    pub required: f64,
+}
+
+fn collect_geo_layout_overflows(
+    container: &GeoPageContainer,
+    path: String,
+    overflows: &mut Vec<GeoLayoutOverflow>,
+) {
+    let (width, height) = geo_content_size(container);
+    for (dimension, declared, required) in [
+        ("width", &container.width, width),
+        ("height", &container.height, height),
+    ] {
+        if let Some(declared) = fixed_rem_size(declared.as_deref())
+            && required > declared + GEO_PAGE_LAYOUT_TOLERANCE
+        {
+            overflows.push(GeoLayoutOverflow {
+                path: path.clone(),
+                dimension,
+                declared,
+                required,
+            });
+        }
+    }
+
+    for (i, child) in container.children.iter().enumerate() {
+        if let GeoNode::Container(child) = child {
+            collect_geo_layout_overflows(child, format!("{path}.children[{i}]"), overflows);
+        }
+    }
+}
+
+/// Parses a container size declared in rem, returning `None` for percentages and unset sizes.
+fn fixed_rem_size(size: Option<&str>) -> Option<f64> {
+    size?.strip_suffix("rem")?.parse().ok()
+}
+
+/// Minimum width and height (in rem) the children of `container` occupy, including gaps and
+/// padding.
+fn geo_content_size(container: &GeoPageContainer) -> (f64, f64) {
+    let (main, cross) = container.children.iter().map(geo_node_size).fold(
+        (0.0f64, 0.0f64),
+        |(main, cross), (width, height)| match container.direction {
+            FlexDirection::Row => (main + width, cross.max(height)),
+            FlexDirection::Col => (main + height, cross.max(width)),
+        },
+    );
+    let main =
+        main + container.gap.unwrap_or(0.0) * container.children.len().saturating_sub(1) as f64;
+    let (width, height) = match container.direction {
+        FlexDirection::Row => (main, cross),
+        FlexDirection::Col => (cross, main),
+    };
+
+    let padding = |side: Option<f64>| side.or(container.padding).unwrap_or(0.0);
+    (
+        width + padding(container.padding_left) + padding(container.padding_right),
+        height + padding(container.padding_top) + padding(container.padding_bottom),
+    )
+}
+
+/// Minimum width and height (in rem) a node occupies within its parent container.
+fn geo_node_size(node: &GeoNode) -> (f64, f64) {
+    match node {
+        GeoNode::Button(button) => (button.size, button.size),
+        // Dividers stretch along their orientation, so only their thickness takes up space
+        GeoNode::Divider(divider) => {
+            let thickness = divider.thickness / GEO_PAGE_REM_IN_PX;
+            match divider.orientation {
+                GeoPageDividerOrientation::Horizontal => (0.0, thickness),
+                GeoPageDividerOrientation::Vertical => (thickness, 0.0),
+            }
+        }
+        GeoNode::Container(container) => {
+            let (width, height) = geo_content_size(container);
+            (
+                fixed_rem_size(container.width.as_deref()).unwrap_or(width),
+                fixed_rem_size(container.height.as_deref()).unwrap_or(height),
+            )
+        }
+    }
 }
 
 pub(super) trait FromRaw<T> {
@@ -158,7 +298,7 @@ pub(super) trait FromRaw<T> {
 }
 
 trait StationIdCollector {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>);
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>);
 }
 
 impl std::fmt::Debug for Profile {
@@ -198,17 +338,31 @@ impl FromRaw<ProfileRaw> for Profile {
             ),
         };
 
-        let mut relevant_station_ids = HashSet::new();
-        profile_type.collect_station_ids(&mut relevant_station_ids);
+        let mut station_reference_counts = HashMap::new();
+        profile_type.collect_station_ids(&mut station_reference_counts);
+        let relevant_station_ids = station_reference_counts.keys().cloned().collect();
+
+        let content_hash = content_hash(&profile_type);
 
         Ok(Self {
             id: profile_raw.id,
             profile_type,
             relevant_station_ids,
+            station_reference_counts,
+            content_hash,
         })
     }
 }
 
+/// Hashes the serialized profile content, as the profile types contain floats and thus cannot
+/// implement [`Hash`] directly.
+fn content_hash(profile_type: &ProfileType) -> u64 {
+    let bytes = serde_json::to_vec(profile_type).expect("profile types always serialize to JSON");
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl From<&Profile> for ProtocolProfile {
     fn from(profile: &Profile) -> Self {
         Self {
@@ -239,7 +393,7 @@ impl ReferenceValidator<StationId> for ProfileType {
 }
 
 impl StationIdCollector for ProfileType {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         match self {
             ProfileType::Geo(container) => container.collect_station_ids(ids),
             ProfileType::Tabbed(tabs) => {
@@ -261,7 +415,7 @@ impl ReferenceValidator<StationId> for GeoPageContainer {
 }
 
 impl StationIdCollector for GeoPageContainer {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         for child in &self.children {
             child.collect_station_ids(ids);
         }
@@ -279,7 +433,7 @@ impl ReferenceValidator<StationId> for GeoNode {
 }
 
 impl StationIdCollector for GeoNode {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         match self {
             GeoNode::Container(c) => c.collect_station_ids(ids),
             GeoNode::Button(b) => b.collect_station_ids(ids),
@@ -298,7 +452,7 @@ impl ReferenceValidator<StationId> for GeoPageButton {
 }
 
 impl StationIdCollector for GeoPageButton {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         if let Some(page) = &self.page {
             page.collect_station_ids(ids);
         }
@@ -326,18 +480,18 @@ impl ReferenceValidator<StationId> for DirectAccessPageContent {
 }
 
 impl StationIdCollector for DirectAccessPage {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         self.content.collect_station_ids(ids);
     }
 }
 
 impl StationIdCollector for DirectAccessPageContent {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         match self {
             DirectAccessPageContent::Keys { keys } => {
                 for key in keys {
                     if let Some(station_id) = &key.station_id {
-                        ids.insert(station_id.clone());
+                        *ids.entry(station_id.clone()).or_default() += 1;
                     }
                     if let Some(page) = &key.page {
                         page.collect_station_ids(ids);
@@ -392,7 +546,7 @@ impl ReferenceValidator<StationId> for Tab {
 }
 
 impl StationIdCollector for Tab {
-    fn collect_station_ids(&self, ids: &mut HashSet<StationId>) {
+    fn collect_station_ids(&self, ids: &mut HashMap<StationId, usize>) {
         self.page.collect_station_ids(ids);
     }
 }
@@ -501,6 +655,113 @@ impl TryFrom<DirectAccessKeyRaw> for DirectAccessKey {
     }
 }
 
+impl From<&Profile> for ProfileRaw {
+    fn from(profile: &Profile) -> Self {
+        Self {
+            id: profile.id.clone(),
+            profile_type: ProfileTypeRaw::from(&profile.profile_type),
+        }
+    }
+}
+
+impl From<&ProfileType> for ProfileTypeRaw {
+    fn from(profile_type: &ProfileType) -> Self {
+        match profile_type {
+            ProfileType::Geo(container) => ProfileTypeRaw::Geo(container.into()),
+            ProfileType::Tabbed(tabs) => ProfileTypeRaw::Tabbed {
+                tabs: tabs.iter().map(TabRaw::from).collect(),
+            },
+        }
+    }
+}
+
+impl From<&Tab> for TabRaw {
+    fn from(tab: &Tab) -> Self {
+        Self {
+            label: tab.label.clone(),
+            page: (&tab.page).into(),
+        }
+    }
+}
+
+impl From<&GeoPageContainer> for GeoPageContainerRaw {
+    fn from(container: &GeoPageContainer) -> Self {
+        Self {
+            height: container.height.clone(),
+            width: container.width.clone(),
+            padding: container.padding,
+            padding_left: container.padding_left,
+            padding_right: container.padding_right,
+            padding_top: container.padding_top,
+            padding_bottom: container.padding_bottom,
+            gap: container.gap,
+            justify_content: container.justify_content.clone(),
+            align_items: container.align_items.clone(),
+            direction: container.direction.clone(),
+            children: container.children.iter().map(GeoNodeRaw::from).collect(),
+        }
+    }
+}
+
+impl From<&GeoNode> for GeoNodeRaw {
+    fn from(node: &GeoNode) -> Self {
+        match node {
+            GeoNode::Container(c) => GeoNodeRaw::Container(c.into()),
+            GeoNode::Button(b) => GeoNodeRaw::Button(b.into()),
+            GeoNode::Divider(d) => GeoNodeRaw::Divider(d.into()),
+        }
+    }
+}
+
+impl From<&GeoPageButton> for GeoPageButtonRaw {
+    fn from(button: &GeoPageButton) -> Self {
+        Self {
+            label: button.label.clone(),
+            size: button.size,
+            page: button.page.as_ref().map(DirectAccessPageRaw::from),
+        }
+    }
+}
+
+impl From<&GeoPageDivider> for GeoPageDividerRaw {
+    fn from(divider: &GeoPageDivider) -> Self {
+        Self {
+            orientation: divider.orientation.clone(),
+            thickness: divider.thickness,
+            color: divider.color.clone(),
+            oversize: divider.oversize,
+        }
+    }
+}
+
+impl From<&DirectAccessPage> for DirectAccessPageRaw {
+    fn from(page: &DirectAccessPage) -> Self {
+        Self {
+            rows: page.rows,
+            content: match &page.content {
+                DirectAccessPageContent::Keys { keys } => DirectAccessPageContentRaw::Keys {
+                    keys: keys.iter().map(DirectAccessKeyRaw::from).collect(),
+                },
+                DirectAccessPageContent::ClientPage { client_page } => {
+                    DirectAccessPageContentRaw::ClientPage {
+                        client_page: client_page.clone(),
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl From<&DirectAccessKey> for DirectAccessKeyRaw {
+    fn from(key: &DirectAccessKey) -> Self {
+        Self {
+            label: key.label.clone(),
+            station_id: key.station_id.clone(),
+            page: key.page.as_ref().map(DirectAccessPageRaw::from),
+        }
+    }
+}
+
 impl Validator for ProfileRaw {
     fn validate(&self) -> Result<(), CoverageError> {
         if self.id.is_empty() {
@@ -851,10 +1112,54 @@ impl std::fmt::Debug for DirectAccessKeyRaw {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::coverage::{CoverageError, ValidationError};
-    use pretty_assertions::assert_matches;
+    use crate::coverage::{CoverageError, IoError, ValidationError};
+    use pretty_assertions::{assert_eq, assert_matches};
     use vacs_protocol::profile::{client_page::ClientPageConfig, geo::FlexDirection};
 
+    #[test]
+    fn load_gzipped_profile() {
+        use std::io::Write;
+
+        let content = r#"
+id = "LOWW"
+type = "Tabbed"
+
+[[tabs]]
+label = "Main"
+
+[tabs.page]
+rows = 4
+
+[[tabs.page.keys]]
+label = "TWR"
+station_id = "LOWW_TWR"
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let plain_path = dir.path().join("profile.toml");
+        std::fs::write(&plain_path, content).unwrap();
+
+        let gz_path = dir.path().join("profile.toml.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&gz_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let plain = Profile::load(&plain_path).unwrap();
+        let gzipped = Profile::load(&gz_path).unwrap();
+        assert_eq!(gzipped.id, plain.id);
+        assert_eq!(gzipped.relevant_station_ids, plain.relevant_station_ids);
+        assert_eq!(gzipped.content_hash, plain.content_hash);
+
+        let zst_path = dir.path().join("profile.toml.zst");
+        std::fs::write(&zst_path, content).unwrap();
+        assert_matches!(
+            Profile::load(&zst_path),
+            Err(CoverageError::Io(IoError::Read { reason, .. })) if reason.contains("unsupported compression")
+        );
+    }
+
     #[test]
     fn profile_raw_validation() {
         let valid_geo = ProfileRaw {
@@ -1138,6 +1443,65 @@ mod tests {
         assert_eq!(profile.relevant_station_ids, expected);
     }
 
+    #[test]
+    fn geo_layout_overflows() {
+        let json = r##"{
+            "id": "test",
+            "type": "Geo",
+            "direction": "row",
+            "width": "20rem",
+            "height": "6rem",
+            "padding": 1,
+            "gap": 1,
+            "children": [
+                { "label": "B1", "size": 4 },
+                { "orientation": "vertical", "thickness": 16, "color": "#000000", "oversize": null },
+                {
+                    "direction": "col",
+                    "height": "5rem",
+                    "gap": 1,
+                    "children": [{ "label": "B2", "size": 2 }, { "label": "B3", "size": 2 }]
+                },
+                {
+                    "direction": "col",
+                    "height": "3rem",
+                    "children": [{ "label": "B4", "size": 2 }, { "label": "B5", "size": 2 }]
+                },
+                {
+                    "direction": "row",
+                    "width": "50%",
+                    "children": [{ "label": "B6", "size": 20 }]
+                }
+            ]
+        }"##;
+        let raw: ProfileRaw = serde_json::from_str(json).expect("valid json");
+        let profile = Profile::from_raw(raw).expect("Should be valid");
+
+        assert_eq!(
+            profile.geo_layout_overflows(),
+            vec![
+                GeoLayoutOverflow {
+                    path: "root".to_string(),
+                    dimension: "width",
+                    declared: 20.0,
+                    required: 35.0,
+                },
+                GeoLayoutOverflow {
+                    path: "root".to_string(),
+                    dimension: "height",
+                    declared: 6.0,
+                    required: 22.0,
+                },
+                GeoLayoutOverflow {
+                    path: "root.children[3]".to_string(),
+                    dimension: "height",
+                    declared: 3.0,
+                    required: 4.0,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn validate_references() {
         let station_id = StationId::from("S1");
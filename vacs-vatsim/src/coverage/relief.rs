@@ -0,0 +1,162 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Relief infixes recognized by default: plain numbers (`LOWW_1_TWR`) and numbered training
+/// callsigns (`LOWW_T1_TWR`).
+const DEFAULT_PATTERNS: &[&str] = &[r"\d+", r"T\d+"];
+
+/// Set of callsign infixes denoting relief or training connections of a regular position, e.g.
+/// `LOWW_T1_TWR` relieving `LOWW_TWR`.
+///
+/// Patterns are regular expressions matched against complete `_`-separated callsign segments
+/// between the prefix and the facility suffix. As relief conventions vary between divisions, the
+/// set of patterns is configurable.
+#[derive(Debug, Clone)]
+pub struct ReliefPatterns {
+    sources: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl ReliefPatterns {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, regex::Error> {
+        let sources = patterns
+            .iter()
+            .map(|p| p.as_ref().to_string())
+            .collect::<Vec<_>>();
+        let patterns = sources
+            .iter()
+            .map(|p| Regex::new(&format!("^(?:{p})$")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { sources, patterns })
+    }
+
+    /// Patterns not recognizing any relief infix.
+    pub fn none() -> Self {
+        Self {
+            sources: Vec::new(),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Strips all recognized relief infixes from `callsign`, returning `None` if none were found.
+    /// The first and last segments (prefix and facility suffix) are never removed.
+    pub fn normalize(&self, callsign: &str) -> Option<String> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+
+        let segments = callsign.split('_').collect::<Vec<_>>();
+        if segments.len() < 3 {
+            return None;
+        }
+
+        let last = segments.len() - 1;
+        let normalized = segments
+            .iter()
+            .enumerate()
+            .filter(|(i, segment)| {
+                *i == 0 || *i == last || !self.patterns.iter().any(|p| p.is_match(segment))
+            })
+            .map(|(_, segment)| *segment)
+            .collect::<Vec<_>>();
+
+        (normalized.len() != segments.len()).then(|| normalized.join("_"))
+    }
+}
+
+impl Default for ReliefPatterns {
+    fn default() -> Self {
+        Self::new(DEFAULT_PATTERNS).expect("Default relief patterns must be valid")
+    }
+}
+
+impl PartialEq for ReliefPatterns {
+    fn eq(&self, other: &Self) -> bool {
+        self.sources == other.sources
+    }
+}
+
+impl Eq for ReliefPatterns {}
+
+impl Serialize for ReliefPatterns {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.sources.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReliefPatterns {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let sources = Vec::<String>::deserialize(deserializer)?;
+        Self::new(&sources).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn normalize_default_patterns() {
+        let patterns = ReliefPatterns::default();
+        assert_eq!(
+            patterns.normalize("LOWW_1_TWR"),
+            Some("LOWW_TWR".to_string())
+        );
+        assert_eq!(
+            patterns.normalize("LOWW_T1_TWR"),
+            Some("LOWW_TWR".to_string())
+        );
+        assert_eq!(
+            patterns.normalize("LOWW_E_T12_TWR"),
+            Some("LOWW_E_TWR".to_string())
+        );
+        assert_eq!(patterns.normalize("LOWW_E_TWR"), None);
+        assert_eq!(patterns.normalize("LOWW_TWR"), None);
+        assert_eq!(patterns.normalize("LOWW_T_TWR"), None);
+    }
+
+    #[test]
+    fn normalize_keeps_prefix_and_suffix() {
+        let patterns = ReliefPatterns::new(&[r"[A-Z]+"]).unwrap();
+        assert_eq!(
+            patterns.normalize("EDDM_N_APP"),
+            Some("EDDM_APP".to_string())
+        );
+        assert_eq!(patterns.normalize("EDDM_APP"), None);
+    }
+
+    #[test]
+    fn normalize_custom_patterns() {
+        let patterns = ReliefPatterns::new(&["R", r"I\d?"]).unwrap();
+        assert_eq!(
+            patterns.normalize("LOWW_R_TWR"),
+            Some("LOWW_TWR".to_string())
+        );
+        assert_eq!(
+            patterns.normalize("LOWW_I2_TWR"),
+            Some("LOWW_TWR".to_string())
+        );
+        assert_eq!(patterns.normalize("LOWW_1_TWR"), None);
+    }
+
+    #[test]
+    fn normalize_without_patterns() {
+        assert_eq!(ReliefPatterns::none().normalize("LOWW_1_TWR"), None);
+    }
+
+    #[test]
+    fn deserialize_invalid_pattern() {
+        assert!(serde_json::from_str::<ReliefPatterns>(r#"["("]"#).is_err());
+        assert_eq!(
+            serde_json::from_str::<ReliefPatterns>(r#"["\\d+", "T\\d+"]"#).unwrap(),
+            ReliefPatterns::default()
+        );
+    }
+}
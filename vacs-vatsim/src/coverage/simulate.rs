@@ -0,0 +1,802 @@
+//! Side-effect-free coverage simulation.
+//!
+//! Replays a scripted sequence of [`SimulationEvent`]s against a [`Network`] and reports the
+//! resulting coverage state together with the [`StationChange`]s that would have been emitted to
+//! vacs clients after every event. This mirrors the coverage bookkeeping performed by the server
+//! without requiring any client sessions, broadcasts or async runtime, allowing external tooling
+//! to reason about coverage deterministically.
+use crate::coverage::network::{Network, ResolveOutcome};
+use crate::{ControllerInfo, FacilityType};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use vacs_protocol::vatsim::{ClientId, PositionId, StationChange, StationId, StationOfflineReason};
+
+/// A single scripted event applied during a simulation.
+///
+/// Events are (de)serialized as JSON objects tagged by `type`, allowing scenarios to be stored
+/// alongside tests or exported from a running server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SimulationEvent {
+    /// A vacs client connects, optionally controlling the given position.
+    #[serde(rename_all = "camelCase")]
+    Connect {
+        client_id: ClientId,
+        position_id: Option<PositionId>,
+    },
+    /// A vacs client disconnects.
+    #[serde(rename_all = "camelCase")]
+    Disconnect { client_id: ClientId },
+    /// A new snapshot of the VATSIM data feed is received. Controllers not connected to vacs that
+    /// unambiguously match a single position are tracked as VATSIM-only positions.
+    DataFeed { controllers: Vec<ControllerInfo> },
+    /// The coverage dataset is reloaded, mirroring a network replacement on the server. Positions
+    /// missing from the new network are cleared from their clients and dropped from the VATSIM-only
+    /// positions, while disabled FIRs and relief patterns are carried over.
+    ReloadNetwork { network: NetworkSource },
+}
+
+/// Network loaded by a [`SimulationEvent::ReloadNetwork`] event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkSource {
+    /// A network registered via [`Simulation::with_network`].
+    Named(String),
+    /// A dataset directory loaded via [`Network::load_from_dir`].
+    Path(PathBuf),
+}
+
+/// Coverage state tracked during a simulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageState {
+    pub clients: HashMap<ClientId, Option<PositionId>>,
+    pub online_positions: HashMap<PositionId, HashSet<ClientId>>,
+    pub online_stations: HashMap<StationId, PositionId>,
+    pub vatsim_only: HashSet<PositionId>,
+}
+
+/// Result of [`simulate`], containing the final coverage state and the station changes emitted
+/// after each event, in the order the events were applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimulationResult {
+    pub state: CoverageState,
+    pub changes: Vec<Vec<StationChange>>,
+}
+
+/// Incremental coverage simulation, starting from a borrowed [`Network`].
+#[derive(Debug, Clone)]
+pub struct Simulation<'a> {
+    network: Cow<'a, Network>,
+    networks: HashMap<String, Network>,
+    state: CoverageState,
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new(network: &'a Network) -> Self {
+        Self {
+            network: Cow::Borrowed(network),
+            networks: HashMap::new(),
+            state: CoverageState::default(),
+        }
+    }
+
+    /// Registers a network under the given name, so it can be loaded by a
+    /// [`SimulationEvent::ReloadNetwork`] event referencing [`NetworkSource::Named`].
+    pub fn with_network(mut self, name: impl Into<String>, network: Network) -> Self {
+        self.networks.insert(name.into(), network);
+        self
+    }
+
+    pub fn with_state(mut self, state: CoverageState) -> Self {
+        self.state = state;
+        self
+    }
+
+    pub fn state(&self) -> &CoverageState {
+        &self.state
+    }
+
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    pub fn into_state(self) -> CoverageState {
+        self.state
+    }
+
+    /// Applies a single event and returns the station changes visible to vacs clients.
+    ///
+    /// Connecting an already connected client, disconnecting an unknown client or reloading a
+    /// network that can't be resolved is a no-op.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn apply(&mut self, event: &SimulationEvent) -> Vec<StationChange> {
+        let vatsim_only_before = self.state.vatsim_only.clone();
+
+        match event {
+            SimulationEvent::Connect {
+                client_id,
+                position_id,
+            } => {
+                if self.state.clients.contains_key(client_id) {
+                    tracing::trace!("Client already connected, ignoring event");
+                    return Vec::new();
+                }
+                self.state
+                    .clients
+                    .insert(client_id.clone(), position_id.clone());
+                if let Some(position_id) = position_id {
+                    self.state.vatsim_only.remove(position_id);
+                    self.state
+                        .online_positions
+                        .entry(position_id.clone())
+                        .or_default()
+                        .insert(client_id.clone());
+                }
+            }
+            SimulationEvent::Disconnect { client_id } => {
+                let Some(position_id) = self.state.clients.remove(client_id) else {
+                    tracing::trace!("Client not connected, ignoring event");
+                    return Vec::new();
+                };
+                if let Some(position_id) = position_id
+                    && let Some(client_ids) = self.state.online_positions.get_mut(&position_id)
+                {
+                    client_ids.remove(client_id);
+                    if client_ids.is_empty() {
+                        self.state.online_positions.remove(&position_id);
+                    }
+                }
+            }
+            SimulationEvent::DataFeed { controllers } => {
+                self.state.vatsim_only = controllers
+                    .iter()
+                    .filter(|c| {
+                        c.facility_type != FacilityType::Unknown
+                            && !self.state.clients.contains_key(&c.cid)
+                    })
+                    .filter_map(|c| {
+                        match self
+                            .network
+                            .resolve(&c.callsign, &c.frequency, c.facility_type)
+                        {
+                            ResolveOutcome::Single(position)
+                                if !self.state.online_positions.contains_key(&position.id) =>
+                            {
+                                Some(position.id.clone())
+                            }
+                            _ => None,
+                        }
+                    })
+                    .collect();
+            }
+            SimulationEvent::ReloadNetwork { network } => {
+                let Some(mut network) = self.resolve_network(network) else {
+                    return Vec::new();
+                };
+                for fir_id in self.network.disabled_firs() {
+                    network.set_fir_enabled(fir_id, false);
+                }
+                network.set_relief_patterns(self.network.relief_patterns().clone());
+                self.network = Cow::Owned(network);
+
+                let stale_positions: Vec<PositionId> = self
+                    .state
+                    .online_positions
+                    .keys()
+                    .filter(|pos_id| self.network.get_position(pos_id).is_none())
+                    .cloned()
+                    .collect();
+                for stale_pos_id in &stale_positions {
+                    tracing::trace!(?stale_pos_id, "Position no longer exists, removing");
+                    for client_id in self
+                        .state
+                        .online_positions
+                        .remove(stale_pos_id)
+                        .unwrap_or_default()
+                    {
+                        self.state.clients.insert(client_id, None);
+                    }
+                }
+                self.state
+                    .vatsim_only
+                    .retain(|pos_id| self.network.get_position(pos_id).is_some());
+            }
+        }
+
+        let all_online_pos_ids: HashSet<&PositionId> = self
+            .state
+            .online_positions
+            .keys()
+            .chain(self.state.vatsim_only.iter())
+            .collect();
+        let new_online_stations = online_stations(&self.network, &all_online_pos_ids);
+        let all_changes = station_diff(&self.state.online_stations, &new_online_stations);
+        let mut changes = client_visible_changes(&all_changes, &self.state.online_positions);
+
+        // Positions transitioning from VATSIM-only to vacs don't change the raw coverage, but the
+        // stations they control become visible to vacs clients.
+        for (station_id, position_id) in &new_online_stations {
+            if vatsim_only_before.contains(position_id)
+                && self.state.online_positions.contains_key(position_id)
+                && self.state.online_stations.get(station_id) == Some(position_id)
+            {
+                changes.push(StationChange::Online {
+                    station_id: station_id.clone(),
+                    position_id: position_id.clone(),
+                });
+            }
+        }
+
+        self.state.online_stations = new_online_stations;
+
+        if self.state.clients.is_empty() {
+            tracing::trace!("Last client disconnected, clearing VATSIM-only positions");
+            self.state.vatsim_only.clear();
+            self.state.online_stations.clear();
+        }
+
+        changes.sort();
+        changes
+    }
+
+    fn resolve_network(&self, source: &NetworkSource) -> Option<Network> {
+        match source {
+            NetworkSource::Named(name) => {
+                let network = self.networks.get(name).cloned();
+                if network.is_none() {
+                    tracing::warn!(?name, "Unknown network, ignoring event");
+                }
+                network
+            }
+            NetworkSource::Path(path) => match Network::load_from_dir(path) {
+                Ok(network) => Some(network),
+                Err(errors) => {
+                    tracing::warn!(?path, ?errors, "Failed to load network, ignoring event");
+                    None
+                }
+            },
+        }
+    }
+}
+
+/// Replays all events in order, starting from an empty coverage state.
+pub fn simulate<'a>(
+    network: &Network,
+    events: impl IntoIterator<Item = &'a SimulationEvent>,
+) -> SimulationResult {
+    let mut simulation = Simulation::new(network);
+    let changes = events
+        .into_iter()
+        .map(|event| simulation.apply(event))
+        .collect();
+
+    SimulationResult {
+        state: simulation.into_state(),
+        changes,
+    }
+}
+
+/// Computes the controlling position of every station covered by the given online positions.
+pub fn online_stations(
+    network: &Network,
+    online_positions: &HashSet<&PositionId>,
+) -> HashMap<StationId, PositionId> {
+    network
+        .covered_stations(None, online_positions)
+        .into_iter()
+        .filter_map(|covered_station| {
+            network
+                .controlling_position(&covered_station.station.id, online_positions)
+                .map(|position| (covered_station.station.id.clone(), position.id.clone()))
+        })
+        .collect()
+}
+
+/// Computes the station changes required to transition from the `old` to the `new` online
+/// stations.
+pub fn station_diff(
+    old: &HashMap<StationId, PositionId>,
+    new: &HashMap<StationId, PositionId>,
+) -> Vec<StationChange> {
+    let mut changes = Vec::new();
+
+    // Stations that went offline or changed controller
+    for (station_id, old_pos_id) in old {
+        match new.get(station_id) {
+            None => {
+                changes.push(StationChange::Offline {
+                    station_id: station_id.clone(),
+                    reason: None,
+                });
+            }
+            Some(new_pos_id) if new_pos_id != old_pos_id => {
+                changes.push(StationChange::Handoff {
+                    station_id: station_id.clone(),
+                    from_position_id: old_pos_id.clone(),
+                    to_position_id: new_pos_id.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // Stations that came online
+    for (station_id, new_pos_id) in new {
+        if !old.contains_key(station_id) {
+            changes.push(StationChange::Online {
+                station_id: station_id.clone(),
+                position_id: new_pos_id.clone(),
+            });
+        }
+    }
+
+    changes.sort();
+    changes
+}
+
+/// Transforms station changes to only include changes visible to vacs clients.
+/// Stations covered solely by VATSIM-only positions are not callable, so:
+/// - `Online` for a VATSIM-only position is dropped
+/// - `Offline` events are always forwarded, even if the previous covering position
+///   was VATSIM-only. Clients handle duplicate/unknown `Offline` events gracefully.
+/// - `Handoff` to a VATSIM-only position becomes `Offline` (station leaves vacs coverage)
+/// - `Handoff` from a VATSIM-only position becomes `Online` (station enters vacs coverage)
+pub fn client_visible_changes(
+    changes: &[StationChange],
+    online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+) -> Vec<StationChange> {
+    visible_changes(changes, online_positions, None)
+}
+
+/// Transforms station changes like [`client_visible_changes`], but keeps stations covered solely
+/// by VATSIM-only positions visible to clients as uncallable:
+/// - `Online` for a VATSIM-only position becomes `Offline` with
+///   [`StationOfflineReason::VatsimOnly`]
+/// - `Handoff` to a VATSIM-only position becomes `Offline` with
+///   [`StationOfflineReason::VatsimOnly`]
+pub fn client_visible_changes_with_vatsim_only(
+    changes: &[StationChange],
+    online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+) -> Vec<StationChange> {
+    visible_changes(
+        changes,
+        online_positions,
+        Some(StationOfflineReason::VatsimOnly),
+    )
+}
+
+fn visible_changes(
+    changes: &[StationChange],
+    online_positions: &HashMap<PositionId, HashSet<ClientId>>,
+    vatsim_only_reason: Option<StationOfflineReason>,
+) -> Vec<StationChange> {
+    changes
+        .iter()
+        .filter_map(|change| match change {
+            StationChange::Online {
+                station_id,
+                position_id,
+            } => {
+                if online_positions.contains_key(position_id) {
+                    Some(change.clone())
+                } else {
+                    vatsim_only_reason.map(|reason| StationChange::Offline {
+                        station_id: station_id.clone(),
+                        reason: Some(reason),
+                    })
+                }
+            }
+            StationChange::Handoff {
+                station_id,
+                from_position_id,
+                to_position_id,
+            } => {
+                let from_vacs = online_positions.contains_key(from_position_id);
+                let to_vacs = online_positions.contains_key(to_position_id);
+                match (from_vacs, to_vacs) {
+                    // vacs -> vacs: normal handoff
+                    (true, true) => Some(change.clone()),
+                    // vacs -> VATSIM-only: station leaves vacs coverage
+                    (true, false) => Some(StationChange::Offline {
+                        station_id: station_id.clone(),
+                        reason: vatsim_only_reason,
+                    }),
+                    // VATSIM-only -> vacs: station enters vacs coverage
+                    (false, true) => Some(StationChange::Online {
+                        station_id: station_id.clone(),
+                        position_id: to_position_id.clone(),
+                    }),
+                    // VATSIM-only -> VATSIM-only: invisible to clients
+                    (false, false) => None,
+                }
+            }
+            StationChange::Offline { .. } => Some(change.clone()),
+        })
+        .collect()
+}
+
+/// Merges consecutive batches of station changes into a single sorted batch containing at most
+/// one change per station, describing the net effect of applying all changes in order. Stations
+/// ending up in the state they started in are omitted.
+pub fn coalesce_station_changes(
+    changes: impl IntoIterator<Item = StationChange>,
+) -> Vec<StationChange> {
+    let mut merged: HashMap<StationId, (StationChange, StationChange)> = HashMap::new();
+    for change in changes {
+        let station_id = match &change {
+            StationChange::Online { station_id, .. }
+            | StationChange::Handoff { station_id, .. }
+            | StationChange::Offline { station_id, .. } => station_id.clone(),
+        };
+        merged
+            .entry(station_id)
+            .and_modify(|(_, last)| *last = change.clone())
+            .or_insert_with(|| (change.clone(), change));
+    }
+
+    let mut changes = merged
+        .into_values()
+        .filter_map(|(first, last)| match (first, last) {
+            // Online in between, but offline again in the end
+            (StationChange::Online { .. }, StationChange::Offline { .. }) => None,
+            (_, last @ StationChange::Offline { .. }) => Some(last),
+            (
+                StationChange::Handoff {
+                    from_position_id, ..
+                },
+                StationChange::Online {
+                    station_id,
+                    position_id: to_position_id,
+                }
+                | StationChange::Handoff {
+                    station_id,
+                    to_position_id,
+                    ..
+                },
+            ) => (from_position_id != to_position_id).then_some(StationChange::Handoff {
+                station_id,
+                from_position_id,
+                to_position_id,
+            }),
+            (
+                _,
+                StationChange::Online {
+                    station_id,
+                    position_id,
+                }
+                | StationChange::Handoff {
+                    station_id,
+                    to_position_id: position_id,
+                    ..
+                },
+            ) => Some(StationChange::Online {
+                station_id,
+                position_id,
+            }),
+        })
+        .collect::<Vec<_>>();
+    changes.sort();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::test_support::TestFirBuilder;
+    use pretty_assertions::assert_eq;
+
+    fn lovv_network(dir: &std::path::Path) -> Network {
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP", "LOVV_CTR"])
+            .station_with_parent("LOWW_TWR", "LOWW_APP", &["LOWW_TWR"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .position("LOWW_TWR", &["LOWW"], "119.400", "TWR")
+            .build(dir)
+    }
+
+    fn connect(cid: &str, position_id: &str) -> SimulationEvent {
+        SimulationEvent::Connect {
+            client_id: ClientId::from(cid),
+            position_id: Some(PositionId::from(position_id)),
+        }
+    }
+
+    fn online(station_id: &str, position_id: &str) -> StationChange {
+        StationChange::Online {
+            station_id: StationId::from(station_id),
+            position_id: PositionId::from(position_id),
+        }
+    }
+
+    fn offline(station_id: &str) -> StationChange {
+        StationChange::Offline {
+            station_id: StationId::from(station_id),
+            reason: None,
+        }
+    }
+
+    fn handoff(station_id: &str, from: &str, to: &str) -> StationChange {
+        StationChange::Handoff {
+            station_id: StationId::from(station_id),
+            from_position_id: PositionId::from(from),
+            to_position_id: PositionId::from(to),
+        }
+    }
+
+    #[test]
+    fn connect_and_disconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_network(dir.path());
+
+        let events = [
+            connect("client0", "LOVV_CTR"),
+            connect("client1", "LOWW_TWR"),
+            SimulationEvent::Disconnect {
+                client_id: ClientId::from("client1"),
+            },
+        ];
+        let result = simulate(&network, &events);
+
+        assert_eq!(
+            result.changes,
+            vec![
+                vec![
+                    online("LOWW_APP", "LOVV_CTR"),
+                    online("LOWW_TWR", "LOVV_CTR")
+                ],
+                vec![StationChange::Handoff {
+                    station_id: StationId::from("LOWW_TWR"),
+                    from_position_id: PositionId::from("LOVV_CTR"),
+                    to_position_id: PositionId::from("LOWW_TWR"),
+                }],
+                vec![StationChange::Handoff {
+                    station_id: StationId::from("LOWW_TWR"),
+                    from_position_id: PositionId::from("LOWW_TWR"),
+                    to_position_id: PositionId::from("LOVV_CTR"),
+                }],
+            ]
+        );
+        assert_eq!(result.state.online_stations.len(), 2);
+        assert_eq!(
+            result.state.online_positions,
+            HashMap::from([(
+                PositionId::from("LOVV_CTR"),
+                HashSet::from([ClientId::from("client0")])
+            )])
+        );
+    }
+
+    #[test]
+    fn vatsim_only_positions_are_invisible() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_network(dir.path());
+
+        let twr = ControllerInfo {
+            cid: ClientId::from("client1"),
+            callsign: "LOWW_TWR".to_string(),
+            frequency: "119.400".to_string(),
+            facility_type: FacilityType::Tower,
+        };
+        let events = [
+            connect("client0", "LOVV_CTR"),
+            SimulationEvent::DataFeed {
+                controllers: vec![twr],
+            },
+            connect("client1", "LOWW_TWR"),
+        ];
+        let result = simulate(&network, &events);
+
+        assert_eq!(
+            result.changes,
+            vec![
+                vec![
+                    online("LOWW_APP", "LOVV_CTR"),
+                    online("LOWW_TWR", "LOVV_CTR")
+                ],
+                vec![offline("LOWW_TWR")],
+                vec![online("LOWW_TWR", "LOWW_TWR")],
+            ]
+        );
+        assert!(result.state.vatsim_only.is_empty());
+    }
+
+    #[test]
+    fn last_disconnect_clears_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_network(dir.path());
+
+        let mut simulation = Simulation::new(&network);
+        simulation.apply(&connect("client0", "LOWW_APP"));
+        let changes = simulation.apply(&SimulationEvent::Disconnect {
+            client_id: ClientId::from("client0"),
+        });
+
+        assert_eq!(changes, vec![offline("LOWW_APP"), offline("LOWW_TWR")]);
+        assert_eq!(simulation.into_state(), CoverageState::default());
+    }
+
+    #[test]
+    fn reload_network_drops_stale_positions() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_network(dir.path());
+        let reload_dir = tempfile::tempdir().unwrap();
+        let reloaded = TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOWW_APP", "LOVV_CTR"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .position("LOWW_APP", &["LOWW"], "134.675", "APP")
+            .build(reload_dir.path());
+
+        let mut simulation = Simulation::new(&network).with_network("reloaded", reloaded);
+        simulation.apply(&connect("client0", "LOVV_CTR"));
+        simulation.apply(&connect("client1", "LOWW_TWR"));
+        simulation.apply(&SimulationEvent::DataFeed {
+            controllers: vec![ControllerInfo {
+                cid: ClientId::from("client2"),
+                callsign: "LOWW_APP".to_string(),
+                frequency: "134.675".to_string(),
+                facility_type: FacilityType::Approach,
+            }],
+        });
+        assert_eq!(
+            simulation.state().vatsim_only,
+            HashSet::from([PositionId::from("LOWW_APP")])
+        );
+
+        let changes = simulation.apply(&SimulationEvent::ReloadNetwork {
+            network: NetworkSource::Named("reloaded".to_string()),
+        });
+
+        assert_eq!(changes, vec![offline("LOWW_TWR")]);
+        assert_eq!(
+            simulation.state().clients.get(&ClientId::from("client1")),
+            Some(&None)
+        );
+        assert!(
+            !simulation
+                .state()
+                .online_positions
+                .contains_key(&PositionId::from("LOWW_TWR"))
+        );
+        assert!(
+            simulation
+                .network()
+                .get_position(&PositionId::from("LOWW_TWR"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn reload_network_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = lovv_network(dir.path());
+        let reload_dir = tempfile::tempdir().unwrap();
+        TestFirBuilder::new("LOVV")
+            .station("LOWW_APP", &["LOVV_CTR"])
+            .position("LOVV_CTR", &["LOVV"], "132.600", "CTR")
+            .create(reload_dir.path());
+
+        let events = [
+            connect("client0", "LOWW_APP"),
+            SimulationEvent::ReloadNetwork {
+                network: NetworkSource::Path(reload_dir.path().to_path_buf()),
+            },
+            SimulationEvent::ReloadNetwork {
+                network: NetworkSource::Named("unknown".to_string()),
+            },
+        ];
+        let result = simulate(&network, &events);
+
+        assert_eq!(
+            result.changes,
+            vec![
+                vec![
+                    online("LOWW_APP", "LOWW_APP"),
+                    online("LOWW_TWR", "LOWW_APP")
+                ],
+                vec![offline("LOWW_APP"), offline("LOWW_TWR")],
+                vec![],
+            ]
+        );
+        assert_eq!(
+            result.state.clients,
+            HashMap::from([(ClientId::from("client0"), None)])
+        );
+    }
+
+    #[test]
+    fn events_from_json() {
+        let events: Vec<SimulationEvent> = serde_json::from_str(
+            r#"[
+                {"type": "connect", "clientId": "client0", "positionId": "LOWW_APP"},
+                {"type": "connect", "clientId": "client1", "positionId": null},
+                {"type": "dataFeed", "controllers": [
+                    {"cid": "client2", "callsign": "LOWW_TWR", "frequency": "119.400", "facilityType": "TWR"}
+                ]},
+                {"type": "reloadNetwork", "network": {"named": "LOVV"}},
+                {"type": "disconnect", "clientId": "client0"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                connect("client0", "LOWW_APP"),
+                SimulationEvent::Connect {
+                    client_id: ClientId::from("client1"),
+                    position_id: None,
+                },
+                SimulationEvent::DataFeed {
+                    controllers: vec![ControllerInfo {
+                        cid: ClientId::from("client2"),
+                        callsign: "LOWW_TWR".to_string(),
+                        frequency: "119.400".to_string(),
+                        facility_type: FacilityType::Tower,
+                    }],
+                },
+                SimulationEvent::ReloadNetwork {
+                    network: NetworkSource::Named("LOVV".to_string()),
+                },
+                SimulationEvent::Disconnect {
+                    client_id: ClientId::from("client0"),
+                },
+            ]
+        );
+        assert_eq!(
+            serde_json::from_value::<Vec<SimulationEvent>>(serde_json::to_value(&events).unwrap())
+                .unwrap(),
+            events
+        );
+    }
+
+    #[test]
+    fn coalesce_keeps_single_changes_sorted() {
+        assert_eq!(
+            coalesce_station_changes([
+                online("LOWW_TWR", "LOWW_TWR"),
+                offline("LOWW_DEL"),
+                handoff("LOWW_APP", "LOWW_APP", "LOVV_CTR"),
+            ]),
+            vec![
+                online("LOWW_TWR", "LOWW_TWR"),
+                handoff("LOWW_APP", "LOWW_APP", "LOVV_CTR"),
+                offline("LOWW_DEL"),
+            ]
+        );
+    }
+
+    #[test]
+    fn coalesce_merges_changes_per_station() {
+        assert_eq!(
+            coalesce_station_changes([
+                handoff("LOWW_APP", "LOWW_APP", "LOWW_TWR"),
+                online("LOWW_DEL", "LOWW_TWR"),
+                handoff("LOWW_GND", "LOWW_GND", "LOWW_TWR"),
+                handoff("LOWW_APP", "LOWW_TWR", "LOVV_CTR"),
+                handoff("LOWW_DEL", "LOWW_TWR", "LOVV_CTR"),
+                offline("LOWW_GND"),
+            ]),
+            vec![
+                online("LOWW_DEL", "LOVV_CTR"),
+                handoff("LOWW_APP", "LOWW_APP", "LOVV_CTR"),
+                offline("LOWW_GND"),
+            ]
+        );
+    }
+
+    #[test]
+    fn coalesce_drops_reverted_changes() {
+        assert_eq!(
+            coalesce_station_changes([
+                online("LOWW_TWR", "LOWW_TWR"),
+                handoff("LOWW_APP", "LOWW_APP", "LOVV_CTR"),
+                offline("LOWW_TWR"),
+                handoff("LOWW_APP", "LOVV_CTR", "LOWW_APP"),
+            ]),
+            vec![]
+        );
+    }
+}
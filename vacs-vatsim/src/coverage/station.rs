@@ -21,9 +21,26 @@ pub struct StationRaw {
     pub controlled_by: Vec<PositionId>,
 }
 
+/// Named group of stations that profile keys can target like a single station. A group expands to
+/// all of its members for coverage and calling.
+#[derive(Debug, Clone)]
+pub struct StationGroup {
+    pub id: StationId,
+    pub members: Vec<StationId>,
+    pub fir_id: FlightInformationRegionId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationGroupRaw {
+    pub id: StationId,
+    pub members: Vec<StationId>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StationConfigFile {
     pub stations: Vec<StationRaw>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<StationGroupRaw>,
 }
 
 impl std::fmt::Debug for Station {
@@ -104,7 +121,88 @@ impl ReferenceValidator<PositionId> for StationRaw {
     }
 }
 
+impl StationGroup {
+    pub(super) fn from_raw(
+        group_raw: StationGroupRaw,
+        fir_id: impl Into<FlightInformationRegionId>,
+    ) -> Result<Self, CoverageError> {
+        group_raw.validate()?;
+        Ok(Self {
+            id: group_raw.id,
+            members: group_raw.members,
+            fir_id: fir_id.into(),
+        })
+    }
+}
+
+impl From<&StationGroup> for StationGroupRaw {
+    fn from(group: &StationGroup) -> Self {
+        Self {
+            id: group.id.clone(),
+            members: group.members.clone(),
+        }
+    }
+}
+
+impl Validator for StationGroupRaw {
+    fn validate(&self) -> Result<(), CoverageError> {
+        if self.id.is_empty() {
+            return Err(ValidationError::Empty {
+                field: "id".to_string(),
+            }
+            .into());
+        }
+        if self.members.is_empty() {
+            return Err(ValidationError::Empty {
+                field: "members".to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl ReferenceValidator<StationId> for StationGroupRaw {
+    fn validate_references(&self, stations: &HashSet<&StationId>) -> Result<(), CoverageError> {
+        if let Some(station_id) = self.members.iter().find(|s| !stations.contains(s)) {
+            return Err(ValidationError::MissingReference {
+                field: "members".to_string(),
+                ref_id: station_id.to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
 impl StationRaw {
+    /// Reconstructs the raw form of a resolved `station`, omitting the trailing positions
+    /// inherited from its `parent` again.
+    pub(super) fn from_station(station: &Station, parent: Option<&Station>) -> Self {
+        let resolved = &station.controlled_by;
+        let inherited = parent
+            .map(|p| p.controlled_by.as_slice())
+            .unwrap_or_default();
+
+        // Shortest prefix of the resolved positions that resolves to the same positions again.
+        let own = (0..resolved.len())
+            .find(|&len| {
+                let mut seen = HashSet::new();
+                resolved[..len]
+                    .iter()
+                    .chain(inherited)
+                    .filter(|p| seen.insert(*p))
+                    .eq(resolved.iter())
+            })
+            .unwrap_or(resolved.len());
+
+        Self {
+            id: station.id.clone(),
+            parent_id: station.parent_id.clone(),
+            controlled_by: resolved[..own].to_vec(),
+        }
+    }
+
     pub(super) fn resolve_controlled_by(
         &self,
         all_stations: &HashMap<StationId, &StationRaw>,
@@ -196,6 +294,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn station_group_raw_validate() {
+        let raw = StationGroupRaw {
+            id: "LOWW_TWRS".into(),
+            members: vec!["LOWW_TWR".into(), "LOWW_E_TWR".into()],
+        };
+        assert!(raw.validate().is_ok());
+
+        let raw = StationGroupRaw {
+            id: "LOWW_TWRS".into(),
+            members: vec![],
+        };
+        assert_matches!(
+            raw.validate(),
+            Err(CoverageError::Validation(ValidationError::Empty { field })) if field == "members"
+        );
+    }
+
+    #[test]
+    fn station_group_raw_missing_member() {
+        let raw = StationGroupRaw {
+            id: "LOWW_TWRS".into(),
+            members: vec!["LOWW_TWR".into(), "LOWW_E_TWR".into()],
+        };
+        let twr = StationId::from("LOWW_TWR");
+        assert_matches!(
+            raw.validate_references(&HashSet::from([&twr])),
+            Err(CoverageError::Validation(ValidationError::MissingReference { field, ref_id }))
+                if field == "members" && ref_id == "LOWW_E_TWR"
+        );
+    }
+
     #[test]
     fn station_equality() {
         let s1 = Station {
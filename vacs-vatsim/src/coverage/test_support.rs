@@ -8,6 +8,8 @@ pub struct TestFirBuilder {
     stations: Vec<String>,
     positions: Vec<String>,
     profiles: Vec<(String, String)>,
+    ice_servers: Vec<String>,
+    settings: Option<String>,
 }
 
 impl TestFirBuilder {
@@ -17,6 +19,8 @@ impl TestFirBuilder {
             stations: Vec::new(),
             positions: Vec::new(),
             profiles: Vec::new(),
+            ice_servers: Vec::new(),
+            settings: None,
         }
     }
 
@@ -48,6 +52,18 @@ controlled_by = {controlled_by:?}
         self
     }
 
+    /// Add a station group expanding to the given member stations.
+    pub fn station_group(mut self, id: &str, members: &[&str]) -> Self {
+        self.stations.push(format!(
+            r#"
+[[groups]]
+id = "{id}"
+members = {members:?}
+"#
+        ));
+        self
+    }
+
     pub fn position(
         mut self,
         id: &str,
@@ -67,6 +83,47 @@ facility_type = "{facility_type}"
         self
     }
 
+    pub fn receive_only_position(
+        mut self,
+        id: &str,
+        prefixes: &[&str],
+        frequency: &str,
+        facility_type: &str,
+    ) -> Self {
+        self.positions.push(format!(
+            r#"
+[[positions]]
+id = "{id}"
+prefixes = {prefixes:?}
+frequency = "{frequency}"
+facility_type = "{facility_type}"
+transmit = false
+"#
+        ));
+        self
+    }
+
+    pub fn monitoring_position(
+        mut self,
+        id: &str,
+        prefixes: &[&str],
+        frequency: &str,
+        facility_type: &str,
+        monitor_frequencies: &[&str],
+    ) -> Self {
+        self.positions.push(format!(
+            r#"
+[[positions]]
+id = "{id}"
+prefixes = {prefixes:?}
+frequency = "{frequency}"
+facility_type = "{facility_type}"
+monitor_frequencies = {monitor_frequencies:?}
+"#
+        ));
+        self
+    }
+
     pub fn position_with_profile(
         mut self,
         id: &str,
@@ -133,6 +190,23 @@ rows = 4
         self
     }
 
+    /// Add an ICE server override for this FIR with the given URLs.
+    pub fn ice_server(mut self, urls: &[&str]) -> Self {
+        self.ice_servers.push(format!(
+            r#"
+[[ice_servers]]
+urls = {urls:?}
+"#
+        ));
+        self
+    }
+
+    /// Write the given TOML content to the FIR's `fir.toml`.
+    pub fn fir_settings(mut self, content: &str) -> Self {
+        self.settings = Some(content.to_string());
+        self
+    }
+
     pub fn create(self, dir: &std::path::Path) {
         let fir_path = dir.join(&self.name);
         if !fir_path.exists() {
@@ -147,6 +221,14 @@ rows = 4
             std::fs::write(fir_path.join("positions.toml"), self.positions.join("\n")).unwrap();
         }
 
+        if !self.ice_servers.is_empty() {
+            std::fs::write(fir_path.join("ice.toml"), self.ice_servers.join("\n")).unwrap();
+        }
+
+        if let Some(settings) = &self.settings {
+            std::fs::write(fir_path.join("fir.toml"), settings).unwrap();
+        }
+
         if !self.profiles.is_empty() {
             let profiles_dir = fir_path.join("profiles");
             std::fs::create_dir_all(&profiles_dir).unwrap();
@@ -1,5 +1,7 @@
 #[cfg(feature = "test-utils")]
 pub mod mock;
+#[cfg(feature = "test-utils")]
+pub mod replay;
 mod vatsim;
 
 pub use vatsim::VatsimDataFeed;
@@ -12,9 +14,38 @@ use thiserror::Error;
 pub enum DataFeedError {
     #[error("Request failed: {0}")]
     Request(#[from] reqwest::Error),
+    #[error("Replay failed: {0}")]
+    Replay(String),
+}
+
+/// Tally of the controller entries of parsed data feeds, giving insight into the feed quality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Entries parsed into controllers with a controlling facility type.
+    pub parsed: usize,
+    /// Malformed entries skipped, e.g. with an empty callsign or frequency.
+    pub skipped: usize,
+    /// Valid entries whose callsign doesn't identify a controlling facility (e.g. supervisors,
+    /// observers or ATIS), which never match any position.
+    pub ignored: usize,
+}
+
+impl std::ops::AddAssign for ParseStats {
+    fn add_assign(&mut self, other: Self) {
+        self.parsed += other.parsed;
+        self.skipped += other.skipped;
+        self.ignored += other.ignored;
+    }
 }
 
 #[async_trait]
 pub trait DataFeed: Send + Sync {
     async fn fetch_controller_info(&self) -> crate::Result<Vec<ControllerInfo>>;
+
+    /// Takes the accumulated [`ParseStats`] of all data feeds parsed since the previous call.
+    /// Returns `None` if no data feed was parsed in the meantime (e.g. because cached controllers
+    /// were returned) or the implementation doesn't track them.
+    fn take_parse_stats(&self) -> Option<ParseStats> {
+        None
+    }
 }
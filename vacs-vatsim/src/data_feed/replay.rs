@@ -0,0 +1,174 @@
+use crate::ControllerInfo;
+use crate::data_feed::vatsim::VatsimDataFeedResponse;
+use crate::data_feed::{DataFeed, DataFeedError};
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A single recorded VATSIM data feed snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFeedSnapshot {
+    /// Unix timestamp (in seconds) the snapshot was recorded at.
+    pub timestamp: u64,
+    pub controllers: Vec<ControllerInfo>,
+}
+
+impl DataFeedSnapshot {
+    /// Parses a snapshot in the VATSIM data feed (v3) JSON format.
+    pub fn from_json(timestamp: u64, json: &str) -> crate::Result<Self> {
+        let response: VatsimDataFeedResponse = serde_json::from_str(json)
+            .map_err(|err| DataFeedError::Replay(format!("invalid snapshot: {err}")))?;
+        Ok(Self {
+            timestamp,
+            controllers: response.into_controller_info().0,
+        })
+    }
+
+    /// Time elapsed between `previous` and this snapshot at their original cadence, scaled by
+    /// `time_scale`.
+    pub fn delay_since(&self, previous: &DataFeedSnapshot, time_scale: f64) -> Duration {
+        Duration::from_secs(self.timestamp.saturating_sub(previous.timestamp))
+            .mul_f64(time_scale.max(0.0))
+    }
+}
+
+/// Loads all recorded snapshots from `dir`, ordered by their timestamp.
+///
+/// Every snapshot is expected to be stored as `<unix timestamp>.json`, other files are ignored.
+pub fn load_snapshots(dir: impl AsRef<Path>) -> crate::Result<Vec<DataFeedSnapshot>> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| DataFeedError::Replay(format!("failed to read directory {dir:?}: {err}")))?;
+
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| DataFeedError::Replay(format!("failed to read entry: {err}")))?
+            .path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(timestamp) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        else {
+            tracing::debug!(?path, "Skipping snapshot without timestamp file name");
+            continue;
+        };
+
+        let json = std::fs::read_to_string(&path)
+            .map_err(|err| DataFeedError::Replay(format!("failed to read {path:?}: {err}")))?;
+        snapshots.push(DataFeedSnapshot::from_json(timestamp, &json)?);
+    }
+
+    snapshots.sort_by_key(|s| s.timestamp);
+    Ok(snapshots)
+}
+
+/// Data feed replaying recorded snapshots in order. Every fetch returns the next snapshot, the
+/// last snapshot is returned repeatedly once the sequence is exhausted.
+#[derive(Debug)]
+pub struct ReplayDataFeed {
+    snapshots: Vec<DataFeedSnapshot>,
+    next: Mutex<usize>,
+}
+
+impl ReplayDataFeed {
+    pub fn new(snapshots: Vec<DataFeedSnapshot>) -> Self {
+        Self {
+            snapshots,
+            next: Mutex::new(0),
+        }
+    }
+
+    pub fn from_dir(dir: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Self::new(load_snapshots(dir)?))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        *self.next.lock().unwrap() >= self.snapshots.len()
+    }
+}
+
+#[async_trait]
+impl DataFeed for ReplayDataFeed {
+    async fn fetch_controller_info(&self) -> crate::Result<Vec<ControllerInfo>> {
+        let mut next = self.next.lock().unwrap();
+        let index = (*next).min(self.snapshots.len().saturating_sub(1));
+        *next = next.saturating_add(1);
+
+        Ok(self
+            .snapshots
+            .get(index)
+            .map(|s| s.controllers.clone())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FacilityType;
+    use pretty_assertions::assert_eq;
+
+    const SNAPSHOT: &str = r#"{"controllers":[
+        {"cid":1234567,"callsign":"LOVV_CTR","frequency":"132.600"},
+        {"cid":7654321,"callsign":"LOWW_TWR","frequency":"119.4"},
+        {"cid":1111111,"callsign":"","frequency":"121.600"}
+    ]}"#;
+
+    #[test]
+    fn load_snapshots_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("1700000030.json"), r#"{"controllers":[]}"#).unwrap();
+        std::fs::write(dir.path().join("1700000015.json"), SNAPSHOT).unwrap();
+        std::fs::write(dir.path().join("README.md"), "ignored").unwrap();
+
+        let snapshots = load_snapshots(dir.path()).unwrap();
+        assert_eq!(
+            snapshots.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![1700000015, 1700000030]
+        );
+        assert_eq!(snapshots[0].controllers.len(), 2);
+        assert_eq!(snapshots[0].controllers[1].frequency, "119.400");
+        assert_eq!(
+            snapshots[1].delay_since(&snapshots[0], 0.5),
+            Duration::from_millis(7500)
+        );
+    }
+
+    #[test]
+    fn facility_resolved_from_callsign_suffix() {
+        let snapshot = DataFeedSnapshot::from_json(
+            1,
+            r#"{"controllers":[
+                {"cid":1234567,"callsign":"LOWW_TWR","frequency":"119.400","facility":0},
+                {"cid":7654321,"callsign":"LOWW_DEP","frequency":"118.775","facility":5}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            snapshot
+                .controllers
+                .iter()
+                .map(|c| c.facility_type)
+                .collect::<Vec<_>>(),
+            vec![FacilityType::Tower, FacilityType::Departure]
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_repeats_last_snapshot() {
+        let feed = ReplayDataFeed::new(vec![
+            DataFeedSnapshot::from_json(1, SNAPSHOT).unwrap(),
+            DataFeedSnapshot::from_json(2, r#"{"controllers":[]}"#).unwrap(),
+        ]);
+
+        assert_eq!(feed.fetch_controller_info().await.unwrap().len(), 2);
+        assert!(feed.fetch_controller_info().await.unwrap().is_empty());
+        assert!(feed.is_exhausted());
+        assert!(feed.fetch_controller_info().await.unwrap().is_empty());
+    }
+}
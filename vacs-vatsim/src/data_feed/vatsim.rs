@@ -1,7 +1,7 @@
-use crate::data_feed::{DataFeed, DataFeedError};
-use crate::{ControllerInfo, FacilityType, Result};
+use crate::data_feed::{DataFeed, DataFeedError, ParseStats};
+use crate::{ControllerInfo, FacilityType, RawController, Result};
 use async_trait::async_trait;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::Deserialize;
 use std::fmt::{Debug, Formatter};
 use std::time::{Duration, Instant};
@@ -16,6 +16,7 @@ pub struct VatsimDataFeed {
     client: reqwest::Client,
     cache_ttl: Duration,
     cache: RwLock<Option<Cache>>,
+    parse_stats: Mutex<Option<ParseStats>>,
 }
 
 impl VatsimDataFeed {
@@ -31,6 +32,7 @@ impl VatsimDataFeed {
             client,
             cache_ttl: DATA_FEED_DEFAULT_CACHE_TTL,
             cache: Default::default(),
+            parse_stats: Default::default(),
         })
     }
 
@@ -79,9 +81,9 @@ impl DataFeed for VatsimDataFeed {
             return Ok(cache.data.clone());
         }
 
-        let data_feed = self.fetch_data_feed().await?;
-        let controllers: Vec<ControllerInfo> =
-            data_feed.controllers.into_iter().map(Into::into).collect();
+        let (controllers, stats) = self.fetch_data_feed().await?.into_controller_info();
+        tracing::debug!(?stats, "Parsed VATSIM data feed");
+        *self.parse_stats.lock().get_or_insert_default() += stats;
 
         let cache = Cache {
             data: controllers.clone(),
@@ -92,6 +94,10 @@ impl DataFeed for VatsimDataFeed {
         tracing::debug!(controllers = ?controllers.len(), "Returning controller info");
         Ok(controllers)
     }
+
+    fn take_parse_stats(&self) -> Option<ParseStats> {
+        self.parse_stats.lock().take()
+    }
 }
 
 struct Cache {
@@ -109,24 +115,105 @@ impl Debug for Cache {
 }
 
 #[derive(Debug, Deserialize)]
-struct VatsimDataFeedResponse {
+pub(super) struct VatsimDataFeedResponse {
     pub controllers: Vec<VatsimDataFeedController>,
 }
 
+impl VatsimDataFeedResponse {
+    /// Converts all valid entries into controllers. Entries without a controlling facility type
+    /// are still returned, they are only tallied as ignored.
+    pub(super) fn into_controller_info(self) -> (Vec<ControllerInfo>, ParseStats) {
+        let mut stats = ParseStats::default();
+        let controllers = self
+            .controllers
+            .into_iter()
+            .filter_map(|controller| match RawController::try_from(controller) {
+                Ok(raw) => {
+                    let controller = ControllerInfo::from(raw);
+                    if controller.facility_type == FacilityType::Unknown {
+                        stats.ignored += 1;
+                    } else {
+                        stats.parsed += 1;
+                    }
+                    Some(controller)
+                }
+                Err(err) => {
+                    tracing::trace!(?err, "Skipping invalid controller in VATSIM data feed");
+                    stats.skipped += 1;
+                    None
+                }
+            })
+            .collect();
+        (controllers, stats)
+    }
+}
+
+/// Controller entry of the VATSIM data feed.
+///
+/// The numeric `facility` field is intentionally ignored: it is frequently reported as `0`
+/// (undefined) for active controllers and can't distinguish e.g. departure from approach, so the
+/// facility type is always derived from the callsign suffix instead.
 #[derive(Debug, Deserialize)]
-struct VatsimDataFeedController {
+pub(super) struct VatsimDataFeedController {
     cid: i32,
     callsign: String,
     frequency: String,
 }
 
-impl From<VatsimDataFeedController> for ControllerInfo {
-    fn from(value: VatsimDataFeedController) -> Self {
-        Self {
-            cid: ClientId::from(value.cid),
-            frequency: value.frequency,
-            facility_type: FacilityType::from(value.callsign.as_str()),
-            callsign: value.callsign,
-        }
+impl TryFrom<VatsimDataFeedController> for RawController {
+    type Error = crate::Error;
+
+    fn try_from(value: VatsimDataFeedController) -> Result<Self> {
+        RawController::new(ClientId::from(value.cid), value.callsign, value.frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test(tokio::test)]
+    async fn parse_stats_tally_mixed_feed() -> Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/vatsim-data.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"controllers": [
+                    {"cid": 1, "callsign": "LOVV_CTR", "frequency": "134.350"},
+                    {"cid": 2, "callsign": "LOWW_TWR", "frequency": "119.400"},
+                    {"cid": 3, "callsign": "XX_SUP", "frequency": "199.998"},
+                    {"cid": 4, "callsign": "LOWW_OBS", "frequency": "199.998"},
+                    {"cid": 5, "callsign": "", "frequency": "121.600"},
+                    {"cid": 6, "callsign": "LOWW_GND", "frequency": " "}
+                ]}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let data_feed = VatsimDataFeed::new(
+            &format!("{}/vatsim-data.json", server.uri()),
+            Duration::from_secs(1),
+        )?;
+        assert_eq!(data_feed.take_parse_stats(), None);
+
+        let controllers = data_feed.fetch_controller_info().await?;
+        assert_eq!(controllers.len(), 4);
+        assert_eq!(
+            data_feed.take_parse_stats(),
+            Some(ParseStats {
+                parsed: 2,
+                skipped: 2,
+                ignored: 2,
+            })
+        );
+
+        // Cached controllers are not tallied again
+        data_feed.fetch_controller_info().await?;
+        assert_eq!(data_feed.take_parse_stats(), None);
+        Ok(())
     }
 }
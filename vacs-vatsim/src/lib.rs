@@ -6,6 +6,9 @@ pub mod data_feed;
 pub mod slurper;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 use thiserror::Error;
 use vacs_protocol::vatsim::ClientId;
@@ -18,6 +21,8 @@ static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_P
 pub enum Error {
     #[error("Unknown facility type: {0}")]
     UnknownFacilityType(String),
+    #[error("Invalid controller data: {0}")]
+    InvalidController(String),
     #[error(transparent)]
     #[cfg(feature = "coverage")]
     Coverage(#[from] coverage::CoverageError),
@@ -33,7 +38,8 @@ pub enum Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ControllerInfo {
     pub cid: ClientId,
     pub callsign: String,
@@ -41,6 +47,185 @@ pub struct ControllerInfo {
     pub facility_type: FacilityType,
 }
 
+/// Frequency used by VATSIM for connections without a primary frequency (e.g. observers).
+pub const PLACEHOLDER_FREQUENCY: &str = "199.998";
+
+impl ControllerInfo {
+    /// Whether this entry is a better candidate than `other` for being the primary controlling
+    /// connection of a CID appearing multiple times. Higher facility types are preferred, followed
+    /// by entries with a non-placeholder frequency and finally the lowest frequency.
+    pub fn is_preferred_over(&self, other: &ControllerInfo) -> bool {
+        self.primary_key() > other.primary_key()
+    }
+
+    fn primary_key(&self) -> (u8, bool, Reverse<&str>) {
+        (
+            self.facility_type.primary_rank(),
+            self.frequency != PLACEHOLDER_FREQUENCY,
+            Reverse(self.frequency.as_str()),
+        )
+    }
+}
+
+/// Deduplicates controllers by CID, keeping only the primary controlling entry of CIDs connected
+/// multiple times (e.g. a CTR controller also monitoring a TWR frequency). For entries considered
+/// equal, the first one is kept.
+pub fn dedupe_controllers(
+    controllers: impl IntoIterator<Item = ControllerInfo>,
+) -> HashMap<ClientId, ControllerInfo> {
+    let mut deduped: HashMap<ClientId, ControllerInfo> = HashMap::new();
+    for controller in controllers {
+        match deduped.get(&controller.cid) {
+            Some(existing) if !controller.is_preferred_over(existing) => {}
+            _ => {
+                deduped.insert(controller.cid.clone(), controller);
+            }
+        }
+    }
+    deduped
+}
+
+/// Intermediate controller representation shared by all VATSIM ingestion paths (slurper and data
+/// feed). Format-specific parsing and validation happens in the respective `TryFrom` impls, while
+/// facility inference and frequency normalization are handled by the single
+/// `From<RawController> for ControllerInfo` conversion.
+#[cfg(any(feature = "data-feed", feature = "slurper"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawController {
+    pub cid: ClientId,
+    pub callsign: String,
+    pub frequency: String,
+}
+
+#[cfg(any(feature = "data-feed", feature = "slurper"))]
+impl RawController {
+    /// Creates a new [`RawController`], rejecting empty CIDs, callsigns and frequencies.
+    pub fn new(
+        cid: impl Into<ClientId>,
+        callsign: impl Into<String>,
+        frequency: impl Into<String>,
+    ) -> Result<Self> {
+        let raw = Self {
+            cid: cid.into(),
+            callsign: callsign.into().trim().to_string(),
+            frequency: frequency.into().trim().to_string(),
+        };
+        if raw.cid.is_empty() {
+            return Err(Error::InvalidController("empty CID".to_string()));
+        } else if raw.callsign.is_empty() {
+            return Err(Error::InvalidController("empty callsign".to_string()));
+        } else if raw.frequency.is_empty() {
+            return Err(Error::InvalidController("empty frequency".to_string()));
+        }
+        Ok(raw)
+    }
+}
+
+#[cfg(any(feature = "data-feed", feature = "slurper"))]
+impl From<RawController> for ControllerInfo {
+    fn from(value: RawController) -> Self {
+        Self {
+            cid: value.cid,
+            facility_type: FacilityType::from(value.callsign.as_str()),
+            callsign: value.callsign.to_ascii_uppercase(),
+            frequency: normalize_frequency(&value.frequency),
+        }
+    }
+}
+
+/// Normalizes a frequency to the `123.450` format used by VATSIM, padding missing decimals.
+/// Actual 8.33 kHz frequencies with more than three decimals (e.g. `118.0083`) are converted to
+/// their channel name (`118.010`). Values that cannot be interpreted as a frequency are returned
+/// unchanged.
+pub fn normalize_frequency(frequency: &str) -> String {
+    match frequency.split_once('.') {
+        Some((mhz, khz))
+            if !mhz.is_empty()
+                && khz.len() <= 3
+                && mhz.chars().chain(khz.chars()).all(|c| c.is_ascii_digit()) =>
+        {
+            format!("{mhz}.{khz:0<3}")
+        }
+        Some((_, khz)) if khz.len() > 3 => frequency_channel(frequency)
+            .map(channel_name)
+            .unwrap_or_else(|| frequency.to_string()),
+        None if !frequency.is_empty() && frequency.chars().all(|c| c.is_ascii_digit()) => {
+            format!("{frequency}.000")
+        }
+        _ => frequency.to_string(),
+    }
+}
+
+/// Returns whether both frequencies refer to the same channel.
+///
+/// Understands 8.33 kHz channel spacing, so the 25 kHz channel (`118.000`), the 8.33 kHz channel
+/// name (`118.005`) and the actual frequency (`118.0000`) of the same frequency are considered
+/// equal, as are `118.010` and `118.0083`. Frequencies outside the channel grid are compared by
+/// their [normalized](normalize_frequency) representation.
+pub fn frequencies_match(a: &str, b: &str) -> bool {
+    match (frequency_channel(a), frequency_channel(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => normalize_frequency(a) == normalize_frequency(b),
+    }
+}
+
+/// Width of a 25 kHz channel block in Hz, split into three 8.33 kHz channels.
+const CHANNEL_BLOCK_HZ: u64 = 25_000;
+/// Maximum deviation in Hz of an actual frequency from the 8.33 kHz grid, accounting for the
+/// frequency being truncated or rounded to four or five decimals.
+const CHANNEL_TOLERANCE_HZ: u64 = 500;
+
+/// Index of the frequency on the 8.33 kHz grid (three channels per 25 kHz block), accepting both
+/// channel names with up to three decimals and actual frequencies with more decimals.
+fn frequency_channel(frequency: &str) -> Option<u64> {
+    let (mhz, fraction) = frequency.split_once('.').unwrap_or((frequency, ""));
+    if mhz.is_empty()
+        || fraction.len() > 6
+        || !mhz
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let hz = mhz
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(1_000_000)?
+        .checked_add(format!("{fraction:0<6}").parse().ok()?)?;
+    let block = hz / CHANNEL_BLOCK_HZ;
+    let offset = hz % CHANNEL_BLOCK_HZ;
+
+    if fraction.len() <= 3 {
+        // Channel name: `.x00`/`.x05` share the first, `.x10` and `.x15` are the other channels
+        let channel = match offset {
+            0 | 5_000 => 0,
+            10_000 => 1,
+            15_000 => 2,
+            _ => return None,
+        };
+        return Some(block * 3 + channel);
+    }
+
+    // Actual frequency: round to the closest third of the block
+    let channel = (offset * 3 + CHANNEL_BLOCK_HZ / 2) / CHANNEL_BLOCK_HZ;
+    if (offset * 3).abs_diff(channel * CHANNEL_BLOCK_HZ) > CHANNEL_TOLERANCE_HZ * 3 {
+        return None;
+    }
+    Some(block * 3 + channel)
+}
+
+/// Formats a channel index returned by [`frequency_channel`] as its channel name, using the
+/// 25 kHz name for channels on the 25 kHz grid.
+fn channel_name(channel: u64) -> String {
+    let khz = channel / 3 * 25;
+    let khz = match channel % 3 {
+        0 => khz,
+        n => khz + 5 * (n + 1),
+    };
+    format!("{}.{:03}", khz / 1000, khz % 1000)
+}
+
 /// Enum representing the different VATSIM facility types as parsed from their respective callsign suffixes
 /// (in accordance with the [VATSIM GCAP](https://vatsim.net/docs/policy/global-controller-administration-policy).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
@@ -60,6 +245,24 @@ pub enum FacilityType {
 }
 
 impl FacilityType {
+    /// All facility types in declaration order, e.g. for populating selections or validating
+    /// configured facility types.
+    pub const fn all() -> &'static [FacilityType] {
+        &[
+            FacilityType::Unknown,
+            FacilityType::Ramp,
+            FacilityType::Delivery,
+            FacilityType::Ground,
+            FacilityType::Tower,
+            FacilityType::Approach,
+            FacilityType::Departure,
+            FacilityType::Enroute,
+            FacilityType::FlightServiceStation,
+            FacilityType::Radio,
+            FacilityType::TrafficFlow,
+        ]
+    }
+
     pub const fn as_str(&self) -> &str {
         match self {
             FacilityType::Ramp => "RMP",
@@ -76,6 +279,41 @@ impl FacilityType {
         }
     }
 
+    /// Rank used to determine the primary controlling connection of a controller connected
+    /// multiple times. Non-controlling facility types rank lowest.
+    pub const fn primary_rank(&self) -> u8 {
+        match self {
+            FacilityType::Unknown | FacilityType::Radio | FacilityType::TrafficFlow => 0,
+            FacilityType::Ramp => 1,
+            FacilityType::Delivery => 2,
+            FacilityType::Ground => 3,
+            FacilityType::Tower => 4,
+            FacilityType::Approach | FacilityType::Departure => 5,
+            FacilityType::Enroute => 6,
+            FacilityType::FlightServiceStation => 7,
+        }
+    }
+
+    /// Conventional frequency range (in kHz) of the facility type, used to detect implausible
+    /// frequencies in coverage datasets. Surface positions usually operate in the ground band,
+    /// while all other facilities may use the whole VHF air band. `None` for facility types
+    /// without a conventional range.
+    pub const fn conventional_frequency_range(&self) -> Option<RangeInclusive<u32>> {
+        match self {
+            FacilityType::Ramp | FacilityType::Delivery | FacilityType::Ground => {
+                Some(121_600..=122_975)
+            }
+            FacilityType::Tower
+            | FacilityType::Approach
+            | FacilityType::Departure
+            | FacilityType::Enroute
+            | FacilityType::FlightServiceStation
+            | FacilityType::Radio
+            | FacilityType::TrafficFlow => Some(118_000..=136_975),
+            FacilityType::Unknown => None,
+        }
+    }
+
     pub fn from_vatsim_facility(facility: u8) -> Self {
         FacilityType::try_from(facility).unwrap_or_default()
     }
@@ -155,6 +393,179 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    fn controller(cid: &str, callsign: &str, frequency: &str) -> ControllerInfo {
+        ControllerInfo {
+            cid: ClientId::from(cid),
+            facility_type: FacilityType::from(callsign),
+            callsign: callsign.to_string(),
+            frequency: frequency.to_string(),
+        }
+    }
+
+    #[test]
+    fn all_facility_types_listed() {
+        // Adding a variant fails to compile here, forcing it to be added to `FacilityType::all`.
+        fn index(facility_type: FacilityType) -> usize {
+            match facility_type {
+                FacilityType::Unknown => 0,
+                FacilityType::Ramp => 1,
+                FacilityType::Delivery => 2,
+                FacilityType::Ground => 3,
+                FacilityType::Tower => 4,
+                FacilityType::Approach => 5,
+                FacilityType::Departure => 6,
+                FacilityType::Enroute => 7,
+                FacilityType::FlightServiceStation => 8,
+                FacilityType::Radio => 9,
+                FacilityType::TrafficFlow => 10,
+            }
+        }
+
+        let all = FacilityType::all();
+        assert_eq!(all.len(), 11);
+        for (i, facility_type) in all.iter().enumerate() {
+            assert_eq!(
+                index(*facility_type),
+                i,
+                "{facility_type:?} listed out of order"
+            );
+        }
+    }
+
+    #[test]
+    fn facility_type_str_round_trip() {
+        for facility_type in FacilityType::all() {
+            if *facility_type == FacilityType::Unknown {
+                continue;
+            }
+            assert_eq!(
+                facility_type.as_str().parse::<FacilityType>().unwrap(),
+                *facility_type
+            );
+        }
+    }
+
+    #[test]
+    fn dedupe_controllers_prefers_highest_facility() {
+        let controllers = dedupe_controllers([
+            controller("1234567", "LOWW_TWR", "119.400"),
+            controller("1234567", "LOVV_CTR", "132.600"),
+            controller("7654321", "LOWW_GND", "121.600"),
+        ]);
+
+        assert_eq!(controllers.len(), 2);
+        assert_eq!(controllers[&ClientId::from("1234567")].callsign, "LOVV_CTR");
+        assert_eq!(controllers[&ClientId::from("7654321")].callsign, "LOWW_GND");
+
+        // Order of entries doesn't matter
+        let controllers = dedupe_controllers([
+            controller("1234567", "LOVV_CTR", "132.600"),
+            controller("1234567", "LOWW_TWR", "119.400"),
+        ]);
+        assert_eq!(controllers[&ClientId::from("1234567")].callsign, "LOVV_CTR");
+    }
+
+    #[test]
+    fn normalize_frequency_formats() {
+        for (frequency, expected) in [
+            ("119.400", "119.400"),
+            ("119.4", "119.400"),
+            ("119", "119.000"),
+            ("118.0083", "118.010"),
+            ("118.00833", "118.010"),
+            ("118.0167", "118.015"),
+            ("118.0250", "118.025"),
+            ("118.0333", "118.035"),
+            ("118.9917", "118.990"),
+            ("118.99999", "119.000"),
+            ("118.0120", "118.0120"),
+            ("UNICOM", "UNICOM"),
+        ] {
+            assert_eq!(normalize_frequency(frequency), expected, "{frequency}");
+        }
+    }
+
+    #[test]
+    fn frequencies_match_833_channels() {
+        // Equivalent representations of the same channel
+        for (a, b) in [
+            ("118.000", "118.005"),
+            ("118.000", "118.0000"),
+            ("118.005", "118.0000"),
+            ("118.010", "118.0083"),
+            ("118.010", "118.00833"),
+            ("118.010", "118.008333"),
+            ("118.015", "118.0167"),
+            ("118.015", "118.0166"),
+            ("118.025", "118.030"),
+            ("118.030", "118.0250"),
+            ("118.035", "118.0333"),
+            ("118.040", "118.0417"),
+            ("118.050", "118.055"),
+            ("118.060", "118.0583"),
+            ("118.065", "118.0667"),
+            ("118.075", "118.080"),
+            ("118.085", "118.0833"),
+            ("118.090", "118.0917"),
+            ("132.605", "132.600"),
+            ("134.355", "134.35"),
+            ("121.5", "121.500"),
+            ("121", "121.000"),
+        ] {
+            assert!(frequencies_match(a, b), "{a} should match {b}");
+            assert!(frequencies_match(b, a), "{b} should match {a}");
+        }
+
+        // Different channels
+        for (a, b) in [
+            ("118.005", "118.010"),
+            ("118.010", "118.015"),
+            ("118.015", "118.025"),
+            ("118.000", "118.0083"),
+            ("118.010", "118.0167"),
+            ("118.0083", "118.0167"),
+            ("119.400", "119.410"),
+            ("119.400", "118.400"),
+        ] {
+            assert!(!frequencies_match(a, b), "{a} should not match {b}");
+        }
+    }
+
+    #[test]
+    fn frequencies_match_off_grid() {
+        assert!(frequencies_match(
+            PLACEHOLDER_FREQUENCY,
+            PLACEHOLDER_FREQUENCY
+        ));
+        assert!(frequencies_match("118.020", "118.02"));
+        assert!(!frequencies_match("118.020", "118.025"));
+        assert!(!frequencies_match("118.0120", "118.010"));
+        assert!(frequencies_match("UNICOM", "UNICOM"));
+        assert!(!frequencies_match("", "118.000"));
+    }
+
+    #[test]
+    fn dedupe_controllers_prefers_primary_frequency() {
+        let controllers = dedupe_controllers([
+            controller("1234567", "LOVV_CTR", PLACEHOLDER_FREQUENCY),
+            controller("1234567", "LOVV_E_CTR", "134.350"),
+            controller("1234567", "LOVV_N_CTR", "129.200"),
+        ]);
+        assert_eq!(
+            controllers[&ClientId::from("1234567")].callsign,
+            "LOVV_N_CTR"
+        );
+    }
+
+    #[test]
+    fn dedupe_controllers_ignores_non_controlling_entries() {
+        let controllers = dedupe_controllers([
+            controller("1234567", "LOWW_TWR", "119.400"),
+            controller("1234567", "LOVV_FMP", "118.000"),
+        ]);
+        assert_eq!(controllers[&ClientId::from("1234567")].callsign, "LOWW_TWR");
+    }
+
     #[test]
     fn facility_type_parse_valid() {
         assert_eq!(
@@ -283,6 +694,47 @@ mod tests {
         assert!(FacilityType::try_from(7).is_err());
     }
 
+    #[cfg(any(feature = "data-feed", feature = "slurper"))]
+    #[test]
+    fn raw_controller_validation() {
+        assert!(RawController::new("1234567", "LOVV_CTR", "132.600").is_ok());
+        assert!(matches!(
+            RawController::new("", "LOVV_CTR", "132.600"),
+            Err(Error::InvalidController(_))
+        ));
+        assert!(matches!(
+            RawController::new("1234567", " ", "132.600"),
+            Err(Error::InvalidController(_))
+        ));
+        assert!(matches!(
+            RawController::new("1234567", "LOVV_CTR", ""),
+            Err(Error::InvalidController(_))
+        ));
+    }
+
+    #[cfg(any(feature = "data-feed", feature = "slurper"))]
+    #[test]
+    fn raw_controller_into_controller_info() {
+        let info: ControllerInfo = RawController::new("1234567", "loww_twr", "119.4")
+            .unwrap()
+            .into();
+        assert_eq!(
+            info,
+            ControllerInfo {
+                cid: ClientId::from("1234567"),
+                callsign: "LOWW_TWR".to_string(),
+                frequency: "119.400".to_string(),
+                facility_type: FacilityType::Tower,
+            }
+        );
+
+        let info: ControllerInfo = RawController::new("1234567", "XX_SUP", "199.998")
+            .unwrap()
+            .into();
+        assert_eq!(info.frequency, "199.998");
+        assert_eq!(info.facility_type, FacilityType::Unknown);
+    }
+
     #[test]
     fn facility_type_serialization() {
         assert_eq!(FacilityType::Delivery.as_str(), "DEL");
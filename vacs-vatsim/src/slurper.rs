@@ -33,7 +33,7 @@
 //! }
 //! ```
 
-use crate::{ControllerInfo, FacilityType, Result};
+use crate::{ControllerInfo, FacilityType, RawController, Result};
 use thiserror::Error;
 use tracing::instrument;
 use vacs_protocol::vatsim::ClientId;
@@ -218,6 +218,7 @@ impl SlurperClient {
             .has_headers(false)
             .from_reader(body.as_ref());
 
+        let mut primary: Option<ControllerInfo> = None;
         for result in reader.records() {
             let record = match result {
                 Ok(rec) => rec,
@@ -227,16 +228,23 @@ impl SlurperClient {
                 }
             };
 
-            match self.extract_controller_info(cid, record)? {
-                Some(info) => return Ok(Some(info)),
-                None => continue,
+            // A CID might be connected multiple times (e.g. a CTR controller also monitoring a
+            // TWR frequency), so we keep the entry of its primary controlling connection.
+            if let Some(info) = self.extract_controller_info(cid, record)?
+                && primary
+                    .as_ref()
+                    .is_none_or(|primary| info.is_preferred_over(primary))
+            {
+                primary = Some(info);
             }
         }
 
-        tracing::debug!(
-            "CID is present in slurper, but no valid controller info found, returning None"
-        );
-        Ok(None)
+        if primary.is_none() {
+            tracing::debug!(
+                "CID is present in slurper, but no valid controller info found, returning None"
+            );
+        }
+        Ok(primary)
     }
 
     /// Extracts the [`ControllerInfo`] from the parsed [`csv::StringRecord`], validating the client is
@@ -267,54 +275,45 @@ impl SlurperClient {
             return Ok(None);
         }
 
-        let callsign = match record.get(SLURPER_CALLSIGN_FIELD_INDEX) {
-            Some(callsign) => callsign,
-            None => {
-                tracing::trace!("Callsign is not present in CSV record in slurper, returning None");
+        let raw = match RawController::try_from((cid, &record)) {
+            Ok(raw) => raw,
+            Err(err) => {
+                tracing::trace!(?err, "Invalid controller data in slurper, returning None");
                 return Ok(None);
             }
         };
-        if callsign.is_empty() {
-            tracing::trace!("Empty callsign, returning None");
-            return Ok(None);
-        }
 
-        let frequency = match record.get(SLURPER_FREQUENCY_FIELD_INDEX) {
-            Some(frequency) => frequency,
-            None => {
-                tracing::trace!(
-                    "Frequency is not present in CSV record in slurper, returning None"
-                );
-                return Ok(None);
-            }
-        };
-        if frequency.is_empty() {
-            tracing::trace!("Empty frequency, returning None");
-            return Ok(None);
-        }
-
-        let facility_type: FacilityType = callsign.into();
-        if matches!(facility_type, FacilityType::Unknown) {
+        let controller_info = ControllerInfo::from(raw);
+        if matches!(controller_info.facility_type, FacilityType::Unknown) {
             tracing::warn!(
-                ?callsign,
-                ?frequency,
+                callsign = ?controller_info.callsign,
+                frequency = ?controller_info.frequency,
                 "Callsign is not a valid facility type, returning None"
             );
             return Ok(None);
         }
 
         tracing::debug!(
-            ?callsign,
-            ?frequency,
-            ?facility_type,
+            callsign = ?controller_info.callsign,
+            frequency = ?controller_info.frequency,
+            facility_type = ?controller_info.facility_type,
             "Found controller info for CID"
         );
-        Ok(Some(ControllerInfo {
-            cid: cid.clone(),
-            callsign: callsign.to_string(),
-            frequency: frequency.to_string(),
-            facility_type,
-        }))
+        Ok(Some(controller_info))
+    }
+}
+
+impl TryFrom<(&ClientId, &csv::StringRecord)> for RawController {
+    type Error = crate::Error;
+
+    fn try_from((cid, record): (&ClientId, &csv::StringRecord)) -> Result<Self> {
+        RawController::new(
+            cid.clone(),
+            record.get(SLURPER_CALLSIGN_FIELD_INDEX).unwrap_or_default(),
+            record
+                .get(SLURPER_FREQUENCY_FIELD_INDEX)
+                .unwrap_or_default(),
+        )
     }
 }
 
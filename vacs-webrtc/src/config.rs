@@ -6,6 +6,13 @@ pub(crate) const WEBRTC_TRACK_ID: &str = "audio";
 pub(crate) const WEBRTC_TRACK_STREAM_ID: &str = "main";
 pub(crate) const WEBRTC_CHANNELS: u16 = 1;
 pub(crate) const PEER_EVENTS_CAPACITY: usize = 128;
+/// MIME type of uncompressed 16-bit PCM as defined in RFC 3551.
+pub(crate) const MIME_TYPE_L16: &str = "audio/L16";
+/// Dynamic RTP payload type L16 is registered with, identical for all clients.
+pub(crate) const L16_PAYLOAD_TYPE: u8 = 118;
+/// Number of samples per L16 RTP packet. 10 ms of mono audio are 960 bytes, keeping packets
+/// well below the path MTU and the receive buffer size, unlike a full 20 ms frame.
+pub(crate) const L16_PACKET_SAMPLES: usize = 480;
 
 pub trait IntoRtc<T> {
     fn into_rtc(self) -> T;
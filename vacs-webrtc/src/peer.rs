@@ -1,12 +1,14 @@
 use crate::config::{
-    IntoRtc, PEER_EVENTS_CAPACITY, WEBRTC_CHANNELS, WEBRTC_TRACK_ID, WEBRTC_TRACK_STREAM_ID,
+    IntoRtc, L16_PAYLOAD_TYPE, MIME_TYPE_L16, PEER_EVENTS_CAPACITY, WEBRTC_CHANNELS,
+    WEBRTC_TRACK_ID, WEBRTC_TRACK_STREAM_ID,
 };
 use crate::error::WebrtcError;
+use crate::sender::L16Packetizer;
 use anyhow::Context;
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 use tracing::instrument;
-use vacs_audio::{EncodedAudioFrame, TARGET_SAMPLE_RATE};
+use vacs_audio::{EncodedAudioFrame, FrameCodec, TARGET_SAMPLE_RATE};
 use vacs_protocol::http::webrtc::IceConfig;
 use webrtc::api::APIBuilder;
 use webrtc::api::interceptor_registry::register_default_interceptors;
@@ -16,8 +18,12 @@ use webrtc::interceptor::registry::Registry;
 use webrtc::peer_connection::RTCPeerConnection;
 use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
 use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
-use webrtc::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use webrtc::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTPCodecType,
+};
+use webrtc::rtp_transceiver::rtp_sender::RTCRtpSender;
 use webrtc::track::track_local::TrackLocal;
+use webrtc::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
 use webrtc::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 
 pub type PeerConnectionState = RTCPeerConnectionState;
@@ -31,7 +37,12 @@ pub enum PeerEvent {
 
 pub struct Peer {
     peer_connection: RTCPeerConnection,
-    track: Arc<TrackLocalStaticSample>,
+    rtp_sender: Arc<RTCRtpSender>,
+    opus_track: Arc<TrackLocalStaticSample>,
+    pcm_track: Arc<TrackLocalStaticRTP>,
+    /// Codec of the track currently attached to `rtp_sender`.
+    sending_codec: FrameCodec,
+    packetizer: Arc<L16Packetizer>,
     sender: Option<crate::Sender>,
     receiver: Option<crate::Receiver>,
     events_tx: broadcast::Sender<PeerEvent>,
@@ -46,6 +57,16 @@ impl Peer {
         media_engine
             .register_default_codecs()
             .context("Failed to register default codecs")?;
+        media_engine
+            .register_codec(
+                RTCRtpCodecParameters {
+                    capability: l16_capability(),
+                    payload_type: L16_PAYLOAD_TYPE,
+                    ..Default::default()
+                },
+                RTPCodecType::Audio,
+            )
+            .context("Failed to register L16 codec")?;
 
         let mut registry = Registry::new();
         registry = register_default_interceptors(registry, &mut media_engine)
@@ -61,7 +82,7 @@ impl Peer {
             .await
             .context("Failed to create peer connection")?;
 
-        let track = Arc::new(TrackLocalStaticSample::new(
+        let opus_track = Arc::new(TrackLocalStaticSample::new(
             RTCRtpCodecCapability {
                 mime_type: MIME_TYPE_OPUS.to_owned(),
                 clock_rate: TARGET_SAMPLE_RATE,
@@ -71,9 +92,16 @@ impl Peer {
             WEBRTC_TRACK_ID.to_owned(),
             WEBRTC_TRACK_STREAM_ID.to_owned(),
         ));
+        // Attached in place of the Opus track once a call negotiated PCM. Both tracks share the
+        // same transceiver, so the remote peer only sees the payload type change.
+        let pcm_track = Arc::new(TrackLocalStaticRTP::new(
+            l16_capability(),
+            WEBRTC_TRACK_ID.to_owned(),
+            WEBRTC_TRACK_STREAM_ID.to_owned(),
+        ));
 
-        peer_connection
-            .add_track(Arc::clone(&track) as Arc<dyn TrackLocal + Send + Sync>)
+        let rtp_sender = peer_connection
+            .add_track(Arc::clone(&opus_track) as Arc<dyn TrackLocal + Send + Sync>)
             .await
             .context("Failed to add track to peer connection")?;
 
@@ -123,7 +151,11 @@ impl Peer {
         Ok((
             Self {
                 peer_connection,
-                track,
+                rtp_sender,
+                opus_track,
+                pcm_track,
+                sending_codec: FrameCodec::Opus,
+                packetizer: Arc::new(L16Packetizer::default()),
                 sender: None,
                 receiver: None,
                 events_tx,
@@ -132,11 +164,14 @@ impl Peer {
         ))
     }
 
-    #[instrument(level = "debug", skip_all, err)]
-    pub fn start(
+    /// Starts sending frames from `input_rx` encoded with `codec` and receiving frames to
+    /// `output_tx`. Received frames are tagged with the codec the remote peer sends them with.
+    #[instrument(level = "debug", skip(self, input_rx, output_tx), err)]
+    pub async fn start(
         &mut self,
         input_rx: mpsc::Receiver<EncodedAudioFrame>,
         output_tx: mpsc::Sender<EncodedAudioFrame>,
+        codec: FrameCodec,
     ) -> Result<(), WebrtcError> {
         tracing::debug!("Starting peer");
         if self.sender.is_some() {
@@ -152,7 +187,25 @@ impl Peer {
             self.receiver = Some(crate::Receiver::new(&self.peer_connection, output_tx));
         }
 
-        self.sender = Some(crate::Sender::new(Arc::clone(&self.track), input_rx));
+        if self.sending_codec != codec {
+            tracing::trace!(?codec, "Switching sending track codec");
+            let track: Arc<dyn TrackLocal + Send + Sync> = match codec {
+                FrameCodec::Opus => Arc::clone(&self.opus_track) as _,
+                FrameCodec::Pcm => Arc::clone(&self.pcm_track) as _,
+            };
+            self.rtp_sender
+                .replace_track(Some(track))
+                .await
+                .context("Failed to replace sending track")?;
+            self.sending_codec = codec;
+        }
+
+        self.sender = Some(crate::Sender::new(
+            Arc::clone(&self.opus_track),
+            Arc::clone(&self.pcm_track),
+            Arc::clone(&self.packetizer),
+            input_rx,
+        ));
 
         tracing::trace!("Successfully started peer");
         Ok(())
@@ -297,3 +350,13 @@ impl Peer {
         Ok(())
     }
 }
+
+/// Uncompressed 16-bit mono PCM, registered as a dynamic payload type next to the default codecs.
+fn l16_capability() -> RTCRtpCodecCapability {
+    RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_L16.to_owned(),
+        clock_rate: TARGET_SAMPLE_RATE,
+        channels: WEBRTC_CHANNELS,
+        ..Default::default()
+    }
+}
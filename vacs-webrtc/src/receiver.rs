@@ -1,7 +1,8 @@
+use crate::config::L16_PAYLOAD_TYPE;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tracing::instrument;
-use vacs_audio::EncodedAudioFrame;
+use vacs_audio::{EncodedAudioFrame, FrameCodec};
 use webrtc::peer_connection::RTCPeerConnection;
 
 pub struct Receiver {
@@ -10,6 +11,10 @@ pub struct Receiver {
 }
 
 impl Receiver {
+    /// Creates a new receiver forwarding received frames to `output_tx`.
+    ///
+    /// Received payloads are tagged with the codec of their RTP payload type, so the remote peer
+    /// may switch between Opus and L16 without renegotiating.
     #[instrument(level = "trace", skip_all)]
     pub fn new(
         peer_connection: &RTCPeerConnection,
@@ -38,8 +43,13 @@ impl Receiver {
                         rtp = track.read_rtp() => {
                             match rtp {
                                 Ok((packet, _)) => {
+                                    let codec = if packet.header.payload_type == L16_PAYLOAD_TYPE {
+                                        FrameCodec::Pcm
+                                    } else {
+                                        FrameCodec::Opus
+                                    };
                                     if let Some(output_tx) = output_tx.as_ref() &&
-                                        output_tx.send(packet.payload).await.is_err() {
+                                        output_tx.send(EncodedAudioFrame::new(codec, packet.payload)).await.is_err() {
                                             tracing::warn!("Failed to send received RTP packet to output");
                                             break;
                                     }
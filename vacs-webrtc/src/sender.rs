@@ -1,11 +1,17 @@
+use crate::config::L16_PACKET_SAMPLES;
 use anyhow::{Context, Result};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, AtomicU32, Ordering};
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tracing::{Instrument, instrument};
-use vacs_audio::{EncodedAudioFrame, FRAME_DURATION_MS};
+use vacs_audio::{EncodedAudioFrame, FRAME_DURATION_MS, FrameCodec};
 use webrtc::media::Sample;
+use webrtc::rtp::header::Header;
+use webrtc::rtp::packet::Packet;
+use webrtc::track::track_local::TrackLocalWriter;
+use webrtc::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
 use webrtc::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 
 pub struct Sender {
@@ -14,9 +20,14 @@ pub struct Sender {
 }
 
 impl Sender {
+    /// Creates a new sender writing Opus frames from `input_rx` to `opus_track` and PCM frames,
+    /// split into L16 packets by `packetizer`, to `pcm_track`. Only the track currently attached
+    /// to the peer connection actually transmits.
     #[instrument(level = "trace", skip_all)]
     pub fn new(
-        track: Arc<TrackLocalStaticSample>,
+        opus_track: Arc<TrackLocalStaticSample>,
+        pcm_track: Arc<TrackLocalStaticRTP>,
+        packetizer: Arc<L16Packetizer>,
         mut input_rx: mpsc::Receiver<EncodedAudioFrame>,
     ) -> Self {
         let (shutdown_tx, mut shutdown_rx) = watch::channel(());
@@ -31,14 +42,22 @@ impl Sender {
                     }
                     frame = input_rx.recv() => {
                         match frame {
+                            Some(frame) if frame.codec == FrameCodec::Pcm => {
+                                for packet in packetizer.packetize(&frame) {
+                                    if let Err(err) = pcm_track.write_rtp(&packet).await {
+                                        tracing::warn!(?err, "Failed to write RTP packet to track");
+                                        break;
+                                    }
+                                }
+                            }
                             Some(frame) => {
                                 let sample = Sample {
-                                    data: frame,
+                                    data: frame.data,
                                     duration: std::time::Duration::from_millis(FRAME_DURATION_MS),
                                     ..Default::default()
                                 };
 
-                                if let Err(err) = track.write_sample(&sample).await {
+                                if let Err(err) = opus_track.write_sample(&sample).await {
                                     tracing::warn!(?err, "Failed to write sample to track");
                                 }
                             }
@@ -65,3 +84,39 @@ impl Sender {
         self.task.await.context("Failed to join sender task")
     }
 }
+
+/// Splits PCM frames into L16 RTP packets of [`L16_PACKET_SAMPLES`] samples each.
+///
+/// Sequence numbers and timestamps continue across consecutive senders of the same peer, so the
+/// receiver doesn't discard packets after a call was paused and resumed. SSRC and payload type
+/// are filled in by the track.
+#[derive(Debug, Default)]
+pub struct L16Packetizer {
+    sequence_number: AtomicU16,
+    timestamp: AtomicU32,
+}
+
+impl L16Packetizer {
+    pub fn packetize(&self, frame: &EncodedAudioFrame) -> Vec<Packet> {
+        let samples = frame.data.len() / 2;
+        // Every frame advances the timestamp by its full duration, even if it ends in a partial
+        // packet.
+        let timestamp = self.timestamp.fetch_add(samples as u32, Ordering::Relaxed);
+
+        (0..samples)
+            .step_by(L16_PACKET_SAMPLES)
+            .map(|offset| {
+                let end = (offset + L16_PACKET_SAMPLES).min(samples);
+                Packet {
+                    header: Header {
+                        version: 2,
+                        sequence_number: self.sequence_number.fetch_add(1, Ordering::Relaxed),
+                        timestamp: timestamp.wrapping_add(offset as u32),
+                        ..Default::default()
+                    },
+                    payload: frame.data.slice(offset * 2..end * 2),
+                }
+            })
+            .collect()
+    }
+}